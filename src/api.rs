@@ -1,10 +1,25 @@
 use crate::dataset::Dataset;
 use crate::engine;
+use crate::field_path::FieldPath;
 use crate::model::{
-    CountEntry, CountResult, FileInfo, ScanKind, ScanOptions, ScanResult, SchemaResult, StatsResult,
+    AppendSummary, BenchOperation, BenchOptions, BenchResult, ChecksumOptions, ChecksumResult,
+    ColumnCase, ColumnChunksResult, ConvertOptions, CountEntry, CountResult, DdlDialect, DdlResult,
+    ExistsOptions, ExistsResult, FileDescription, FileInfo, GrepOptions, GroupCountOptions,
+    GroupCountResult, Histogram, HistogramOptions, KeyValueMetadataResult, MatchCount,
+    MetadataEdit, NullsOptions, NullsResult, PageIndexOptions, PageIndexResult,
+    ParquetWriteOptions, PruneReport, ReshapeOptions, RowCountCheck, RowGroupRange,
+    RowGroupStatsResult, RowGroupsResult, SampleOptions, SampleResult, ScanKind, ScanOptions,
+    ScanResult, SchemaResult, SelectOptions, SortKey, SortedMergeOptions, StatsDiff, StatsResult,
+    StatsTolerance, TransformOptions, UnifiedSchema, DEFAULT_BATCH_SIZE,
 };
+use crate::output::{FileOutput, FileOutputFormat};
+use crate::path_text::display_path;
+use crate::predicate::Predicate;
 use crate::Result;
+use arrow::array::{RecordBatch, RecordBatchReader};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
 
 pub fn dataset_from_inputs(inputs: Vec<PathBuf>) -> Result<Dataset> {
     Dataset::from_inputs(inputs)
@@ -21,16 +36,261 @@ pub fn schema(dataset: &Dataset) -> Result<Vec<SchemaResult>> {
         .collect()
 }
 
-pub fn scan(dataset: &Dataset, kind: ScanKind, options: ScanOptions) -> Result<Vec<ScanResult>> {
+/// One schema covering every file, with the columns the files disagree on.
+pub fn unify_schemas(dataset: &Dataset) -> Result<UnifiedSchema> {
+    engine::unify::unify(dataset.paths())
+}
+
+/// Each file's schema as a `CREATE TABLE` statement or Arrow schema JSON.
+pub fn schema_ddl(dataset: &Dataset, dialect: DdlDialect) -> Result<Vec<DdlResult>> {
+    dataset
+        .paths()
+        .map(|path| {
+            Ok(DdlResult {
+                path: path.to_path_buf(),
+                ddl: engine::ddl::ddl(path, dialect)?,
+            })
+        })
+        .collect()
+}
+
+pub fn column_names(path: &Path) -> Result<Vec<String>> {
+    engine::parquet::root_column_names(path)
+}
+
+pub fn scan(dataset: &Dataset, kind: ScanKind, options: &ScanOptions) -> Result<Vec<ScanResult>> {
+    let parsed = options
+        .filter
+        .as_deref()
+        .map(Predicate::parse)
+        .transpose()?;
+    let filter = options.filter.as_deref().zip(parsed.as_ref());
+    if options.global {
+        return scan_global(dataset, kind, options, filter);
+    }
+    dataset
+        .paths()
+        .map(|path| scan_file(path, kind, options.rows, options, filter, dataset))
+        .collect()
+}
+
+/// Head reads files in order and tail works back from the last one, and neither opens
+/// another file once `rows` are found. At least one file is read, for its schema.
+fn scan_global(
+    dataset: &Dataset,
+    kind: ScanKind,
+    options: &ScanOptions,
+    filter: Option<(&str, &Predicate)>,
+) -> Result<Vec<ScanResult>> {
+    let mut paths: Vec<&Path> = dataset.paths().collect();
+    if kind == ScanKind::Tail {
+        paths.reverse();
+    }
+
+    let mut remaining = options.rows;
+    let mut results = Vec::new();
+    for path in paths {
+        if remaining == 0 && !results.is_empty() {
+            break;
+        }
+        let result = scan_file(path, kind, remaining, options, filter, dataset)?;
+        let returned: usize = result.batches.iter().map(RecordBatch::num_rows).sum();
+        remaining = remaining.saturating_sub(returned);
+        results.push(result);
+    }
+
+    if kind == ScanKind::Tail {
+        results.reverse();
+    }
+    Ok(results)
+}
+
+/// `rows` stands in for `options.rows`, which global scans share out across files.
+fn scan_file(
+    path: &Path,
+    kind: ScanKind,
+    rows: usize,
+    options: &ScanOptions,
+    filter: Option<(&str, &Predicate)>,
+    dataset: &Dataset,
+) -> Result<ScanResult> {
+    let path = path.to_path_buf();
+    let (columns, row_groups) = (options.columns.as_slice(), options.row_groups.as_slice());
+    let (case, batch_size) = (dataset.column_case(), dataset.batch_size());
+    if let Some((text, predicate)) = filter {
+        let batches = match kind {
+            ScanKind::Head => engine::filter::head_matching(
+                &path, text, predicate, rows, columns, case, batch_size,
+            )?,
+            ScanKind::Tail => engine::filter::tail_matching(
+                &path, text, predicate, rows, columns, case, batch_size,
+            )?,
+        };
+        return Ok(ScanResult {
+            path,
+            batches,
+            first_row: None,
+        });
+    }
+
+    let (batches, first_row) = match kind {
+        ScanKind::Head => (
+            engine::parquet::read_head(&path, rows, columns, case, batch_size, row_groups)?,
+            0,
+        ),
+        ScanKind::Tail => {
+            let batches =
+                engine::parquet::read_tail(&path, rows, columns, case, batch_size, row_groups)?;
+            let returned: usize = batches.iter().map(RecordBatch::num_rows).sum();
+            let total = usize::try_from(engine::parquet::row_count(&path)?)
+                .map_err(|error| crate::PqError::invalid_metadata(&path, error))?;
+            (batches, total.saturating_sub(returned))
+        }
+    };
+    Ok(ScanResult {
+        path,
+        batches,
+        first_row: row_groups.is_empty().then_some(first_row),
+    })
+}
+
+/// The rows `scan` would return for a tail, one batch per result and in the same
+/// order, read as the iterator advances rather than collected up front. Global tails
+/// share `options.rows` out from the last file back using footer row counts.
+///
+/// The batches are meant to be combined, so every file must have the first file's
+/// schema; that is checked from the footers before any rows are read. A filtered tail,
+/// or one from chosen row groups, does not know where its rows start, so it is read with
+/// `scan` and then split up.
+pub fn scan_tail_batches<'a>(
+    dataset: &'a Dataset,
+    options: &'a ScanOptions,
+) -> Result<Box<dyn Iterator<Item = Result<ScanResult>> + 'a>> {
+    if options.filter.is_some() || !options.row_groups.is_empty() {
+        let results = scan(dataset, ScanKind::Tail, options)?;
+        return Ok(Box::new(results.into_iter().flat_map(|result| {
+            let path = result.path;
+            result.batches.into_iter().map(move |batch| {
+                Ok(ScanResult {
+                    path: path.clone(),
+                    batches: vec![batch],
+                    first_row: None,
+                })
+            })
+        })));
+    }
+
+    let mut plan = Vec::new();
+    let mut remaining = options.rows;
+    let paths: Vec<&Path> = dataset.paths().collect();
+    for path in paths.into_iter().rev() {
+        if options.global && remaining == 0 && !plan.is_empty() {
+            break;
+        }
+        let total = usize::try_from(engine::parquet::row_count(path)?)
+            .map_err(|error| crate::PqError::invalid_metadata(path, error))?;
+        let rows = if options.global {
+            remaining
+        } else {
+            options.rows
+        };
+        remaining = remaining.saturating_sub(total);
+        plan.push((path, rows, total.saturating_sub(rows)));
+    }
+    plan.reverse();
+
+    let (case, batch_size) = (dataset.column_case(), dataset.batch_size());
+    if let Some(((first, ..), rest)) = plan.split_first() {
+        let expected = engine::parquet::scan_schema(first, &options.columns, case)?;
+        for (path, ..) in rest {
+            if engine::parquet::scan_schema(path, &options.columns, case)? != expected {
+                return Err(crate::PqError::SchemaMismatch {
+                    file1: display_path(first),
+                    file2: display_path(path),
+                    details:
+                        "Cannot combine scan results with different schemas for structured output"
+                            .to_string(),
+                });
+            }
+        }
+    }
+    Ok(Box::new(plan.into_iter().flat_map(
+        move |(path, rows, mut first_row)| {
+            let batches: Box<dyn Iterator<Item = Result<RecordBatch>>> =
+                match engine::parquet::tail_batches(
+                    path,
+                    rows,
+                    &options.columns,
+                    case,
+                    batch_size,
+                    &[],
+                ) {
+                    Ok(batches) => batches,
+                    Err(error) => Box::new(std::iter::once(Err(error))),
+                };
+            batches.map(move |batch| {
+                let batch = batch?;
+                let batch_first_row = first_row;
+                first_row += batch.num_rows();
+                Ok(ScanResult {
+                    path: path.to_path_buf(),
+                    batches: vec![batch],
+                    first_row: Some(batch_first_row),
+                })
+            })
+        },
+    )))
+}
+
+/// The values of `column` in every file, in order, a batch at a time. Each file is
+/// opened only once the one before it has been read.
+pub fn extract<'a>(
+    dataset: &'a Dataset,
+    column: &'a str,
+) -> Box<dyn Iterator<Item = Result<RecordBatch>> + 'a> {
+    let (case, batch_size) = (dataset.column_case(), dataset.batch_size());
+    Box::new(dataset.paths().flat_map(move |path| {
+        match engine::parquet::column_batches(path, column, case, batch_size) {
+            Ok(batches) => batches,
+            Err(error) => Box::new(std::iter::once(Err(error))),
+        }
+    }))
+}
+
+/// Evaluate the field paths in `options.expression` against every file, reading only
+/// the leaf columns they reach.
+pub fn select(dataset: &Dataset, options: &SelectOptions) -> Result<Vec<ScanResult>> {
+    let paths = FieldPath::parse_list(&options.expression)?;
+    dataset
+        .paths()
+        .map(|path| {
+            Ok(ScanResult {
+                path: path.to_path_buf(),
+                batches: engine::select::select(path, &paths, options, dataset.batch_size())?,
+                first_row: Some(0),
+            })
+        })
+        .collect()
+}
+
+pub fn grep(dataset: &Dataset, options: &GrepOptions) -> Result<Vec<ScanResult>> {
+    let matcher = engine::grep::Matcher::new(options)?;
     dataset
         .paths()
         .map(|path| {
             let path = path.to_path_buf();
-            let batches = match kind {
-                ScanKind::Head => engine::parquet::read_head(&path, options.rows)?,
-                ScanKind::Tail => engine::parquet::read_tail(&path, options.rows)?,
-            };
-            Ok(ScanResult { path, batches })
+            let batches = engine::grep::grep_file(
+                &path,
+                options,
+                &matcher,
+                dataset.column_case(),
+                dataset.batch_size(),
+            )?;
+            Ok(ScanResult {
+                path,
+                batches,
+                first_row: None,
+            })
         })
         .collect()
 }
@@ -56,39 +316,382 @@ pub fn count(dataset: &Dataset) -> Result<CountResult> {
     })
 }
 
-pub fn stats(dataset: &Dataset, column_name: Option<&str>) -> Result<Vec<StatsResult>> {
+/// Each file's rows matching `predicate`, decoding only the columns it names,
+/// skipping row groups whose statistics rule it out and counting those whose
+/// statistics prove it without decoding them.
+pub fn count_where(dataset: &Dataset, predicate: &str) -> Result<Vec<MatchCount>> {
+    let parsed = Predicate::parse(predicate)?;
+    dataset
+        .paths()
+        .map(|path| engine::filter::count_matching(path, predicate, &parsed, dataset.batch_size()))
+        .collect()
+}
+
+/// Decode each file's rows to check its footer row count.
+pub fn verify_count(dataset: &Dataset) -> Result<Vec<RowCountCheck>> {
+    dataset
+        .paths()
+        .map(|path| engine::parquet::verify_row_count(path, dataset.batch_size()))
+        .collect()
+}
+
+pub fn count_by(
+    dataset: &Dataset,
+    columns: &[String],
+    options: GroupCountOptions,
+) -> Result<GroupCountResult> {
+    let mut counter = engine::group::GroupCounter::default();
+    for path in dataset.paths() {
+        counter.add_file(path, columns, dataset.column_case(), dataset.batch_size())?;
+    }
+    Ok(counter.finish(columns.to_vec(), options))
+}
+
+/// Statistics for each row group separately, for every leaf column or those the
+/// `columns` selectors pick, and for every row group or those `row_groups` covers.
+pub fn stats_by_row_group(
+    dataset: &Dataset,
+    columns: &[String],
+    row_groups: &[RowGroupRange],
+) -> Result<Vec<RowGroupStatsResult>> {
+    let case = dataset.column_case();
     dataset
         .paths()
         .map(|path| {
-            let path = path.to_path_buf();
-            let rows = engine::stats::column_stats(&path, column_name)?;
-            Ok(StatsResult { path, rows })
+            Ok(RowGroupStatsResult {
+                path: path.to_path_buf(),
+                rows: engine::stats::row_group_stats(path, columns, case, row_groups)?,
+            })
         })
         .collect()
 }
 
+/// Statistics for every leaf column, or for those the `columns` selectors pick, merged
+/// across every row group or those `row_groups` covers.
+pub fn stats(
+    dataset: &Dataset,
+    columns: &[String],
+    row_groups: &[RowGroupRange],
+) -> Result<Vec<StatsResult>> {
+    let case = dataset.column_case();
+    dataset
+        .paths()
+        .map(|path| engine::stats::column_stats(path, columns, case, row_groups))
+        .collect()
+}
+
+/// Compare two files' row counts and each column's null count, min, and max, reading
+/// only their footers.
+pub fn stats_diff(old: &Path, new: &Path, tolerance: Option<StatsTolerance>) -> Result<StatsDiff> {
+    engine::stats_diff::diff(old, new, tolerance)
+}
+
+pub fn prune_check(dataset: &Dataset, predicate: &str) -> Result<Vec<PruneReport>> {
+    let parsed = Predicate::parse(predicate)?;
+    dataset
+        .paths()
+        .map(|path| engine::prune::prune_file(path, predicate, &parsed))
+        .collect()
+}
+
+pub fn exists(dataset: &Dataset, options: &ExistsOptions) -> Result<Vec<ExistsResult>> {
+    dataset
+        .paths()
+        .map(|path| engine::exists::exists_in_file(path, options, dataset.batch_size()))
+        .collect()
+}
+
+/// Histogram of one numeric or temporal column across every file in the dataset.
+pub fn hist(dataset: &Dataset, options: &HistogramOptions) -> Result<Histogram> {
+    let paths: Vec<_> = dataset.paths().collect();
+    engine::hist::histogram(&paths, options, dataset.column_case(), dataset.batch_size())
+}
+
+/// A random sample of rows from all files together, uniform or stratified.
+pub fn sample(dataset: &Dataset, options: &SampleOptions) -> Result<SampleResult> {
+    let paths: Vec<_> = dataset.paths().collect();
+    engine::sample::sample(&paths, options, dataset.column_case(), dataset.batch_size())
+}
+
+/// Hashes of each file's logical row data, optionally per column.
+pub fn checksum(dataset: &Dataset, options: &ChecksumOptions) -> Result<Vec<ChecksumResult>> {
+    dataset
+        .paths()
+        .map(|path| engine::checksum::checksum(path, options, dataset.batch_size()))
+        .collect()
+}
+
+/// Null counts per column, sorted by null percentage, optionally with null runs.
+pub fn nulls(dataset: &Dataset, options: &NullsOptions) -> Result<Vec<NullsResult>> {
+    dataset
+        .paths()
+        .map(|path| {
+            engine::nulls::nulls(path, options, dataset.column_case(), dataset.batch_size())
+        })
+        .collect()
+}
+
+/// Run one operation over the dataset `warmup` times untimed, then `iterations` times
+/// timed, in this process so no startup cost is measured.
+pub fn bench(dataset: &Dataset, options: &BenchOptions) -> Result<BenchResult> {
+    let run = || -> Result<u64> {
+        match options.operation {
+            BenchOperation::Head { rows } => {
+                let options = ScanOptions {
+                    rows,
+                    ..ScanOptions::default()
+                };
+                let results = scan(dataset, ScanKind::Head, &options)?;
+                Ok(results
+                    .iter()
+                    .flat_map(|result| &result.batches)
+                    .map(|batch| batch.num_rows() as u64)
+                    .sum())
+            }
+            BenchOperation::Count => Ok(count(dataset)?.total_rows.unsigned_abs()),
+            BenchOperation::Stats => {
+                stats(dataset, &[], &[])?;
+                Ok(0)
+            }
+        }
+    };
+
+    for _ in 0..options.warmup {
+        run()?;
+    }
+    let mut rows = 0;
+    let mut timings = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations.max(1) {
+        let start = Instant::now();
+        rows = run()?;
+        timings.push(start.elapsed());
+    }
+    if options.operation == BenchOperation::Stats {
+        rows = count(dataset)?.total_rows.unsigned_abs();
+    }
+
+    Ok(BenchResult {
+        operation: options.operation,
+        files: dataset.paths().len(),
+        rows,
+        warmup: options.warmup,
+        timings,
+    })
+}
+
 pub fn info(dataset: &Dataset) -> Result<Vec<FileInfo>> {
     dataset.paths().map(engine::parquet::file_info).collect()
 }
 
-pub(crate) fn convert(input: &Path, output: &Path) -> Result<()> {
-    let builder = engine::parquet::reader_builder(input)?;
+/// File metadata, schema, and column statistics in one footer-only report per file.
+pub fn describe(dataset: &Dataset) -> Result<Vec<FileDescription>> {
+    dataset.paths().map(engine::describe::describe).collect()
+}
+
+pub fn page_indexes(dataset: &Dataset, options: &PageIndexOptions) -> Result<Vec<PageIndexResult>> {
+    dataset
+        .paths()
+        .map(|path| engine::pages::page_indexes(path, options))
+        .collect()
+}
+
+pub fn column_chunks(dataset: &Dataset) -> Result<Vec<ColumnChunksResult>> {
+    dataset
+        .paths()
+        .map(|path| {
+            let path = path.to_path_buf();
+            let columns = engine::parquet::column_chunks(&path)?;
+            Ok(ColumnChunksResult { path, columns })
+        })
+        .collect()
+}
+
+pub fn row_groups(dataset: &Dataset) -> Result<Vec<RowGroupsResult>> {
+    dataset
+        .paths()
+        .map(|path| {
+            let path = path.to_path_buf();
+            let row_groups = engine::parquet::row_groups(&path)?;
+            Ok(RowGroupsResult { path, row_groups })
+        })
+        .collect()
+}
+
+pub fn key_value_metadata(dataset: &Dataset) -> Result<Vec<KeyValueMetadataResult>> {
+    dataset
+        .paths()
+        .map(|path| {
+            let path = path.to_path_buf();
+            let entries = engine::parquet::key_value_metadata(&path)?;
+            Ok(KeyValueMetadataResult { path, entries })
+        })
+        .collect()
+}
+
+/// Flatten struct columns and explode a list column in scan results, for tabular output.
+pub fn reshape(results: &mut [ScanResult], options: &ReshapeOptions) -> Result<()> {
+    if options.is_noop() {
+        return Ok(());
+    }
+    for result in results {
+        result.batches = result
+            .batches
+            .iter()
+            .map(|batch| engine::reshape::reshape(&result.path, batch, options))
+            .collect::<Result<_>>()?;
+    }
+    Ok(())
+}
+
+/// Sort each result's rows by `keys`, one file at a time.
+pub fn sort_rows(results: &mut [ScanResult], keys: &[SortKey], case: ColumnCase) -> Result<()> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+    for result in results {
+        result.batches = engine::sorting::sort_batches(&result.path, &result.batches, keys, case)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn convert(
+    input: &Path,
+    output: &Path,
+    mut encoding: FileOutput,
+    options: &ConvertOptions,
+) -> Result<()> {
+    let mut builder = engine::parquet::reader_builder(input)?;
+    let mut selection = None;
+    if !options.columns.is_empty() {
+        let selected = engine::parquet::selected_projection(
+            input,
+            &builder,
+            &options.columns,
+            options.column_case,
+        )?;
+        builder = builder.with_projection(selected.mask.clone());
+        selection = Some(selected);
+    } else if !options.exclude.is_empty() {
+        let projection = engine::parquet::excluding_projection(
+            input,
+            &builder,
+            &options.exclude,
+            options.column_case,
+        )?;
+        builder = builder.with_projection(projection);
+    }
+    if let Some(limit) = options.limit {
+        builder = builder.with_limit(limit);
+    }
     let reader = builder
+        .with_batch_size(options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE))
         .build()
-        .map_err(|error| crate::PqError::from_read(input, error))?;
+        .map_err(|error| engine::parquet::build_error(input, error))?;
+    let mut schema = match &selection {
+        Some(selection) => selection.arrange_schema(input, &reader.schema())?,
+        None => reader.schema().as_ref().clone(),
+    };
+    if !options.reshape.is_noop() {
+        schema = engine::reshape::reshape_schema(input, Arc::new(schema), &options.reshape)?;
+    }
+    if encoding.format == FileOutputFormat::Parquet {
+        encoding.parquet = Some(
+            engine::parquet::writer_properties(&schema, &options.write, output, output)?.build(),
+        );
+    }
+    let reader = reader.map(|batch_result| {
+        let batch = batch_result.map_err(|error| engine::parquet::decode_error(input, &error))?;
+        let batch = match &selection {
+            Some(selection) => selection.arrange(input, &batch)?,
+            None => batch,
+        };
+        if options.reshape.is_noop() {
+            Ok(batch)
+        } else {
+            engine::reshape::reshape(input, &batch, &options.reshape)
+        }
+    });
+
+    if crate::output::is_stdout_path(output) {
+        let mut writer = crate::output::BatchFileWriter::stdout(encoding, &schema)?;
+        for batch in reader {
+            writer.write(&batch?)?;
+        }
+        return writer.finish();
+    }
+
     let pending_output = crate::atomic_output::PendingOutput::new(output)?;
-    let mut writer = crate::output::BatchFileWriter::create_at(pending_output.path(), output)?;
+    let mut writer = crate::output::BatchFileWriter::create_at(
+        pending_output.path(),
+        output,
+        encoding,
+        &schema,
+    )?;
 
-    for batch_result in reader {
-        let batch = batch_result.map_err(|error| crate::PqError::corrupted(input, &error))?;
-        writer.write(&batch)?;
+    for batch in reader {
+        writer.write(&batch?)?;
     }
 
     writer.finish()?;
     pending_output.commit()
 }
 
-pub fn merge(dataset: &Dataset, output: &Path) -> Result<()> {
+/// Write `input` to `output` with columns renamed, dropped, and cast.
+pub fn transform(
+    input: &Path,
+    output: &Path,
+    options: &TransformOptions,
+    write: &ParquetWriteOptions,
+) -> Result<()> {
+    engine::transform::transform(input, output, options, write)
+}
+
+/// Write `input` to `output` with its footer key-value metadata edited; `output` may be
+/// `input` itself, which is replaced once the new file is complete.
+pub fn edit_metadata(input: &Path, output: &Path, edit: &MetadataEdit) -> Result<()> {
+    engine::parquet::rewrite_key_value_metadata(input, output, edit)
+}
+
+pub fn merge(dataset: &Dataset, output: &Path, write: &ParquetWriteOptions) -> Result<()> {
+    let paths: Vec<_> = dataset.paths().collect();
+    engine::parquet::merge_files(&paths, output, write, dataset.batch_size())
+}
+
+/// Merge inputs that are each sorted by `sort.column` into one file sorted the same
+/// way, declaring that order in its `sorting_columns` metadata.
+pub fn merge_sorted(
+    dataset: &Dataset,
+    output: &Path,
+    write: &ParquetWriteOptions,
+    sort: &SortedMergeOptions,
+) -> Result<()> {
+    let paths: Vec<_> = dataset.paths().collect();
+    engine::merge::merge_sorted_files(
+        &paths,
+        output,
+        write,
+        sort,
+        dataset.column_case(),
+        dataset.batch_size(),
+    )
+}
+
+/// Add the dataset's rows to the end of `output`, or create it like `merge` when it
+/// does not exist yet.
+pub fn merge_append(
+    dataset: &Dataset,
+    output: &Path,
+    write: &ParquetWriteOptions,
+) -> Result<AppendSummary> {
     let paths: Vec<_> = dataset.paths().collect();
-    engine::parquet::merge_files(&paths, output)
+    if output.exists() {
+        return engine::parquet::append_files(&paths, output, write, dataset.batch_size());
+    }
+
+    engine::parquet::merge_files(&paths, output, write, dataset.batch_size())?;
+    let appended_rows = u64::try_from(engine::parquet::row_count(output)?).unwrap_or_default();
+    Ok(AppendSummary {
+        existing_rows: 0,
+        appended_rows,
+    })
 }
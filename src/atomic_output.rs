@@ -5,6 +5,29 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 static TEMP_OUTPUT_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// Refuse an output path that names one of the inputs, or an existing file unless
+/// `overwrite` allows replacing it.
+pub(crate) fn check_destination(
+    target_path: &Path,
+    inputs: &[&Path],
+    overwrite: bool,
+) -> Result<()> {
+    let Ok(target) = fs::canonicalize(target_path) else {
+        return Ok(());
+    };
+    if inputs
+        .iter()
+        .filter_map(|input| fs::canonicalize(input).ok())
+        .any(|input| input == target)
+    {
+        return Err(PqError::output_is_input(target_path));
+    }
+    if !overwrite {
+        return Err(PqError::output_exists(target_path));
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub(crate) struct PendingOutput {
     target_path: PathBuf,
@@ -125,4 +148,36 @@ mod tests {
         assert!(!temp_file_name.ends_with(".jsonl"));
         Ok(())
     }
+
+    #[test]
+    fn check_destination_guards_existing_outputs_and_inputs() -> Result<()> {
+        let input_path = temp_path("parquet")?;
+        let output_path = temp_path("parquet")?;
+        fs::write(&input_path, b"input")?;
+
+        check_destination(&output_path, &[input_path.as_path()], false)?;
+        fs::write(&output_path, b"existing")?;
+        assert!(matches!(
+            check_destination(&output_path, &[input_path.as_path()], false),
+            Err(PqError::OutputExists { .. })
+        ));
+        check_destination(&output_path, &[input_path.as_path()], true)?;
+
+        let aliased = input_path
+            .parent()
+            .map(|parent| {
+                parent
+                    .join(".")
+                    .join(input_path.file_name().unwrap_or_default())
+            })
+            .ok_or_else(|| PqError::output_error("temp path should have a parent"))?;
+        assert!(matches!(
+            check_destination(&aliased, &[input_path.as_path()], true),
+            Err(PqError::OutputIsInput { .. })
+        ));
+
+        fs::remove_file(input_path)?;
+        fs::remove_file(output_path)?;
+        Ok(())
+    }
 }
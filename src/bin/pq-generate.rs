@@ -3,15 +3,24 @@
 //! Generates Parquet files with configurable size, schema, and data characteristics.
 
 use anyhow::{Context, Result};
-use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, NullArray, StringBuilder};
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::array::{
+    ArrayRef, BinaryBuilder, BooleanArray, Date32Array, Decimal128Array, FixedSizeBinaryBuilder,
+    Float64Array, Int64Array, Int64Builder, ListBuilder, MapBuilder, NullArray, StringArray,
+    StringBuilder, StructArray, Time64MicrosecondArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray, UInt32Array,
+};
+use arrow::buffer::NullBuffer;
+use arrow::compute::{cast, take_record_batch};
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
 use clap::{Parser, ValueEnum};
 use parquet::arrow::ArrowWriter;
 use parquet::basic::Compression;
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use parquet::schema::types::ColumnPath;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -55,6 +64,44 @@ struct Cli {
     /// Compression codec
     #[arg(long, default_value = "snappy")]
     compression: CompressionCodec,
+
+    /// Maximum rows per row group
+    #[arg(long, value_name = "N")]
+    row_group_size: Option<usize>,
+
+    /// Write no column statistics
+    #[arg(long, conflicts_with = "write_page_index")]
+    no_statistics: bool,
+
+    /// Disable dictionary encoding
+    #[arg(long)]
+    no_dictionary: bool,
+
+    /// Write page-level statistics with column and offset indexes
+    #[arg(long)]
+    write_page_index: bool,
+
+    /// Write bloom filters for these columns
+    #[arg(long, value_delimiter = ',', value_name = "COLS")]
+    bloom_filter: Vec<String>,
+
+    /// Generate non-null, monotonically increasing values for this column
+    #[arg(long, value_name = "COL")]
+    sorted_by: Option<String>,
+
+    /// Exact columns to generate instead of a profile, e.g. `id:int64,score:float64?:0.3`
+    ///
+    /// Each entry is `name:type`, with `?` marking the column nullable and an optional
+    /// `:ratio` overriding --null-ratio for it. Types: int64, float64, utf8, bool, binary,
+    /// date32, timestamp_s, timestamp_ms, timestamp_us, timestamp_ns, time64_us, decimal(P,S),
+    /// list (of int64), and the 64-bit offset and view layouts large_utf8, utf8_view,
+    /// large_binary, binary_view, and large_list.
+    #[arg(long, value_name = "SPEC", conflicts_with_all = ["cols", "profile"])]
+    schema: Option<String>,
+
+    /// Fraction of rows that repeat an earlier row (duplicates profile)
+    #[arg(long, default_value = "0.25")]
+    dup_ratio: f64,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -77,6 +124,18 @@ enum DataProfile {
     AllNulls,
     /// Empty file (0 rows, just schema)
     Empty,
+    /// Binary data: raw bytes, 8-byte fixed-size, and UUID-tagged 16-byte columns
+    Binary,
+    /// Dates, timestamps (ms/us, with and without time zone), and times of day
+    Temporal,
+    /// Decimal128 columns with assorted precisions and scales
+    Decimal,
+    /// Lists, structs, and maps
+    Nested,
+    /// Mixed columns plus a `key` column where one value holds ~90% of rows
+    Skewed,
+    /// Mixed columns where `--dup-ratio` of rows are exact copies of earlier rows
+    Duplicates,
 }
 
 #[derive(Clone, Copy, ValueEnum)]
@@ -102,21 +161,35 @@ impl From<CompressionCodec> for Compression {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    anyhow::ensure!(
+        (0.0..=1.0).contains(&cli.dup_ratio),
+        "--dup-ratio must be between 0.0 and 1.0"
+    );
+
+    let columns = match &cli.schema {
+        Some(spec) => parse_schema_spec(spec, cli.null_ratio)?,
+        None => profile_columns(&cli),
+    };
+    let null_ratios: Vec<f64> = columns.iter().map(|column| column.null_ratio).collect();
+    let schema = Arc::new(Schema::new(
+        columns
+            .into_iter()
+            .map(|column| column.field)
+            .collect::<Vec<_>>(),
+    ));
+
     eprintln!(
         "Generating {} rows x {} cols -> {}",
         cli.rows,
-        cli.cols,
+        schema.fields().len(),
         cli.output.display()
     );
 
-    let schema = build_schema(&cli);
+    let sorted_column = sorted_column(&cli, &schema)?;
+    let props = writer_properties(&cli, &schema)?;
     let file = File::create(&cli.output)
         .with_context(|| format!("Failed to create output file: {}", cli.output.display()))?;
 
-    let props = WriterProperties::builder()
-        .set_compression(cli.compression.into())
-        .build();
-
     let mut writer = ArrowWriter::try_new(file, Arc::clone(&schema), Some(props))
         .context("Failed to create Arrow writer")?;
 
@@ -132,7 +205,17 @@ fn main() -> Result<()> {
 
     while rows_written < cli.rows {
         let batch_rows = std::cmp::min(cli.batch_size, cli.rows - rows_written);
-        let batch = generate_batch(&cli, &schema, batch_rows, &mut rng)?;
+        let mut batch = generate_batch(&cli, &schema, &null_ratios, batch_rows, &mut rng)?;
+        if matches!(cli.profile, DataProfile::Duplicates) {
+            batch = duplicate_rows(&batch, cli.dup_ratio, &mut rng)?;
+        }
+        if let Some(index) = sorted_column {
+            let mut columns = batch.columns().to_vec();
+            columns[index] =
+                generate_sorted(schema.field(index).data_type(), rows_written, batch_rows)?;
+            batch = RecordBatch::try_new(Arc::clone(&schema), columns)
+                .context("Failed to create record batch")?;
+        }
         writer
             .write(&batch)
             .context("Failed to write record batch")?;
@@ -153,8 +236,54 @@ fn main() -> Result<()> {
     Ok(())
 }
 
-fn build_schema(cli: &Cli) -> Arc<Schema> {
-    let fields: Vec<Field> = (0..cli.cols)
+fn writer_properties(cli: &Cli, schema: &Schema) -> Result<WriterProperties> {
+    let mut builder = WriterProperties::builder()
+        .set_compression(cli.compression.into())
+        .set_dictionary_enabled(!cli.no_dictionary);
+    if let Some(rows) = cli.row_group_size {
+        anyhow::ensure!(rows > 0, "--row-group-size must be at least 1");
+        builder = builder.set_max_row_group_size(rows);
+    }
+    if cli.no_statistics {
+        builder = builder.set_statistics_enabled(EnabledStatistics::None);
+    } else if cli.write_page_index {
+        builder = builder.set_statistics_enabled(EnabledStatistics::Page);
+    }
+    for column in &cli.bloom_filter {
+        schema
+            .field_with_name(column)
+            .with_context(|| format!("Unknown --bloom-filter column: {column}"))?;
+        builder = builder.set_column_bloom_filter_enabled(ColumnPath::from(column.as_str()), true);
+    }
+    Ok(builder.build())
+}
+
+/// Index of the `--sorted-by` column, checked up front so a bad name fails before writing.
+fn sorted_column(cli: &Cli, schema: &Schema) -> Result<Option<usize>> {
+    let Some(name) = &cli.sorted_by else {
+        return Ok(None);
+    };
+    let index = schema
+        .index_of(name)
+        .with_context(|| format!("Unknown --sorted-by column: {name}"))?;
+    generate_sorted(schema.field(index).data_type(), 0, 0)?;
+    Ok(Some(index))
+}
+
+/// A generated column and the share of its values that are null.
+struct ColumnSpec {
+    field: Field,
+    null_ratio: f64,
+}
+
+fn profile_columns(cli: &Cli) -> Vec<ColumnSpec> {
+    let null_ratio = match cli.profile {
+        DataProfile::Sparse => 0.9,
+        DataProfile::AllNulls => 1.0,
+        _ => cli.null_ratio,
+    };
+
+    (0..cli.cols)
         .map(|i| {
             let (name, dtype) = match cli.profile {
                 DataProfile::Integers => (format!("int_{i}"), DataType::Int64),
@@ -164,49 +293,288 @@ fn build_schema(cli: &Cli) -> Arc<Schema> {
                 DataProfile::Sparse => (format!("sparse_{i}"), DataType::Int64),
                 DataProfile::AllNulls => (format!("null_{i}"), DataType::Null),
                 DataProfile::Empty => (format!("col_{i}"), DataType::Int64),
-                DataProfile::Mixed | DataProfile::EdgeCases => match i % 4 {
+                DataProfile::Skewed if i == 0 => ("key".to_string(), DataType::Utf8),
+                DataProfile::Mixed
+                | DataProfile::EdgeCases
+                | DataProfile::Skewed
+                | DataProfile::Duplicates => match i % 4 {
                     0 => (format!("int_{i}"), DataType::Int64),
                     1 => (format!("float_{i}"), DataType::Float64),
                     2 => (format!("str_{i}"), DataType::Utf8),
                     _ => (format!("bool_{i}"), DataType::Boolean),
                 },
+                DataProfile::Binary => match i % 3 {
+                    0 => (format!("bin_{i}"), DataType::Binary),
+                    1 => (format!("fixed_{i}"), DataType::FixedSizeBinary(8)),
+                    _ => {
+                        // The canonical Arrow UUID extension, kept in the embedded Arrow schema
+                        let metadata = HashMap::from([(
+                            "ARROW:extension:name".to_string(),
+                            "arrow.uuid".to_string(),
+                        )]);
+                        return Field::new(
+                            format!("uuid_{i}"),
+                            DataType::FixedSizeBinary(16),
+                            true,
+                        )
+                        .with_metadata(metadata);
+                    }
+                },
+                DataProfile::Temporal => match i % 6 {
+                    0 => (format!("date_{i}"), DataType::Date32),
+                    1 => (
+                        format!("ts_ms_{i}"),
+                        DataType::Timestamp(TimeUnit::Millisecond, None),
+                    ),
+                    2 => (
+                        format!("ts_ms_utc_{i}"),
+                        DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                    ),
+                    3 => (
+                        format!("ts_us_{i}"),
+                        DataType::Timestamp(TimeUnit::Microsecond, None),
+                    ),
+                    4 => (
+                        format!("ts_us_tz_{i}"),
+                        DataType::Timestamp(TimeUnit::Microsecond, Some("America/New_York".into())),
+                    ),
+                    _ => (format!("time_{i}"), DataType::Time64(TimeUnit::Microsecond)),
+                },
+                DataProfile::Decimal => match i % 4 {
+                    0 => (format!("dec_{i}"), DataType::Decimal128(9, 2)),
+                    1 => (format!("dec_{i}"), DataType::Decimal128(18, 4)),
+                    2 => (format!("dec_{i}"), DataType::Decimal128(38, 10)),
+                    _ => (format!("dec_{i}"), DataType::Decimal128(5, 0)),
+                },
+                DataProfile::Nested => match i % 3 {
+                    0 => (
+                        format!("list_{i}"),
+                        DataType::new_list(DataType::Int64, true),
+                    ),
+                    1 => (format!("struct_{i}"), DataType::Struct(struct_fields())),
+                    _ => (format!("map_{i}"), map_type()),
+                },
             };
             Field::new(name, dtype, true)
         })
-        .collect();
+        .map(|field| ColumnSpec { field, null_ratio })
+        .collect()
+}
+
+/// Parse a `--schema` spec: comma-separated `name:type[?][:null_ratio]` entries.
+fn parse_schema_spec(spec: &str, default_null_ratio: f64) -> Result<Vec<ColumnSpec>> {
+    let mut columns: Vec<ColumnSpec> = Vec::new();
+    for (position, entry) in split_spec_entries(spec).into_iter().enumerate() {
+        let column = parse_column_spec(entry, default_null_ratio)
+            .with_context(|| format!("Invalid --schema column {} '{entry}'", position + 1))?;
+        if columns
+            .iter()
+            .any(|existing| existing.field.name() == column.field.name())
+        {
+            anyhow::bail!("Duplicate --schema column '{}'", column.field.name());
+        }
+        columns.push(column);
+    }
+    anyhow::ensure!(!columns.is_empty(), "--schema needs at least one column");
+    Ok(columns)
+}
+
+/// Split on commas outside parentheses, so `decimal(9,2)` stays one entry.
+fn split_spec_entries(spec: &str) -> Vec<&str> {
+    let mut entries = Vec::new();
+    let mut depth = 0_usize;
+    let mut start = 0;
+    for (index, ch) in spec.char_indices() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                entries.push(spec[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push(spec[start..].trim());
+    entries
+}
+
+fn parse_column_spec(entry: &str, default_null_ratio: f64) -> Result<ColumnSpec> {
+    let mut parts = entry.splitn(3, ':');
+    let name = parts.next().unwrap_or_default().trim();
+    anyhow::ensure!(!name.is_empty(), "missing column name");
+    let type_text = parts
+        .next()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .context("missing type after ':'")?;
+    let (type_text, nullable) = match type_text.strip_suffix('?') {
+        Some(type_text) => (type_text, true),
+        None => (type_text, false),
+    };
+    let dtype = parse_column_type(type_text)?;
+
+    let null_ratio = match parts.next().map(str::trim) {
+        None => {
+            if nullable {
+                default_null_ratio
+            } else {
+                0.0
+            }
+        }
+        Some(text) => {
+            anyhow::ensure!(
+                nullable,
+                "null ratio '{text}' needs a nullable type such as '{type_text}?'"
+            );
+            let ratio: f64 = text
+                .parse()
+                .ok()
+                .filter(|ratio| (0.0..=1.0).contains(ratio))
+                .with_context(|| format!("null ratio '{text}' must be between 0.0 and 1.0"))?;
+            ratio
+        }
+    };
+
+    Ok(ColumnSpec {
+        field: Field::new(name, dtype, nullable),
+        null_ratio,
+    })
+}
+
+fn parse_column_type(text: &str) -> Result<DataType> {
+    let lower = text.to_ascii_lowercase();
+    if let Some(args) = lower
+        .strip_prefix("decimal(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        let (precision, scale) = args
+            .split_once(',')
+            .context("decimal needs a precision and scale, e.g. decimal(9,2)")?;
+        let precision: u8 = precision
+            .trim()
+            .parse()
+            .ok()
+            .filter(|precision| (1..=38).contains(precision))
+            .with_context(|| format!("decimal precision '{}' must be 1 to 38", precision.trim()))?;
+        let scale: i8 = scale
+            .trim()
+            .parse()
+            .ok()
+            .filter(|scale| (0..=i16::from(precision)).contains(&i16::from(*scale)))
+            .with_context(|| {
+                format!(
+                    "decimal scale '{}' must be 0 to the precision",
+                    scale.trim()
+                )
+            })?;
+        return Ok(DataType::Decimal128(precision, scale));
+    }
 
-    Arc::new(Schema::new(fields))
+    Ok(match lower.as_str() {
+        "int64" => DataType::Int64,
+        "float64" => DataType::Float64,
+        "utf8" | "string" => DataType::Utf8,
+        "bool" | "boolean" => DataType::Boolean,
+        "binary" => DataType::Binary,
+        "date32" | "date" => DataType::Date32,
+        "timestamp_s" => DataType::Timestamp(TimeUnit::Second, None),
+        "timestamp_ms" => DataType::Timestamp(TimeUnit::Millisecond, None),
+        "timestamp_us" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "timestamp_ns" => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        "time64_us" | "time" => DataType::Time64(TimeUnit::Microsecond),
+        "large_utf8" | "large_string" => DataType::LargeUtf8,
+        "utf8_view" | "string_view" => DataType::Utf8View,
+        "large_binary" => DataType::LargeBinary,
+        "binary_view" => DataType::BinaryView,
+        "list" => DataType::List(Arc::new(Field::new_list_field(DataType::Int64, true))),
+        "large_list" => DataType::LargeList(Arc::new(Field::new_list_field(DataType::Int64, true))),
+        _ => anyhow::bail!("unknown type '{text}'"),
+    })
+}
+
+fn struct_fields() -> Fields {
+    Fields::from(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+    ])
+}
+
+/// Matches the layout `MapBuilder` produces with its default field names.
+fn map_type() -> DataType {
+    let entries = Field::new(
+        "entries",
+        DataType::Struct(Fields::from(vec![
+            Field::new("keys", DataType::Utf8, false),
+            Field::new("values", DataType::Int64, true),
+        ])),
+        false,
+    );
+    DataType::Map(Arc::new(entries), false)
 }
 
 fn generate_batch(
     cli: &Cli,
     schema: &Arc<Schema>,
+    null_ratios: &[f64],
     num_rows: usize,
     rng: &mut StdRng,
 ) -> Result<RecordBatch> {
-    let columns: Vec<ArrayRef> = schema
+    let columns = schema
         .fields()
         .iter()
-        .map(|field| generate_column(cli, field, num_rows, rng))
-        .collect();
+        .zip(null_ratios)
+        .map(|(field, null_ratio)| generate_column(cli, field, *null_ratio, num_rows, rng))
+        .collect::<Result<Vec<_>>>()?;
 
     RecordBatch::try_new(Arc::clone(schema), columns).context("Failed to create record batch")
 }
 
-fn generate_column(cli: &Cli, field: &Field, num_rows: usize, rng: &mut StdRng) -> ArrayRef {
-    let null_ratio = match cli.profile {
-        DataProfile::Sparse => 0.9,
-        DataProfile::AllNulls => 1.0,
-        _ => cli.null_ratio,
-    };
+fn generate_column(
+    cli: &Cli,
+    field: &Field,
+    null_ratio: f64,
+    num_rows: usize,
+    rng: &mut StdRng,
+) -> Result<ArrayRef> {
+    if matches!(cli.profile, DataProfile::Skewed) && field.name() == "key" {
+        return Ok(generate_skewed_keys(num_rows, rng));
+    }
 
-    match field.data_type() {
+    Ok(match field.data_type() {
         DataType::Int64 => generate_int64(cli, num_rows, null_ratio, rng),
         DataType::Float64 => generate_float64(cli, num_rows, null_ratio, rng),
         DataType::Utf8 => generate_string(cli, num_rows, null_ratio, rng),
         DataType::Boolean => generate_boolean(num_rows, null_ratio, rng),
+        DataType::Binary => generate_binary(num_rows, null_ratio, rng),
+        DataType::FixedSizeBinary(width) => {
+            generate_fixed_binary(*width, num_rows, null_ratio, rng)?
+        }
+        DataType::Date32 => generate_date32(num_rows, null_ratio, rng),
+        DataType::Timestamp(unit, zone) => {
+            generate_timestamp(*unit, zone.clone(), num_rows, null_ratio, rng)
+        }
+        DataType::Time64(_) => generate_time64(num_rows, null_ratio, rng),
+        DataType::Decimal128(precision, scale) => {
+            generate_decimal(*precision, *scale, num_rows, null_ratio, rng)?
+        }
+        DataType::List(_) => generate_list(num_rows, null_ratio, rng),
+        DataType::Struct(fields) => generate_struct(cli, fields, num_rows, null_ratio, rng)?,
+        DataType::Map(_, _) => generate_map(num_rows, null_ratio, rng)?,
+        // Same values as the 32-bit layouts, so output can be compared across them
+        DataType::LargeUtf8 | DataType::Utf8View => cast(
+            &generate_string(cli, num_rows, null_ratio, rng),
+            field.data_type(),
+        )?,
+        DataType::LargeBinary | DataType::BinaryView => cast(
+            &generate_binary(num_rows, null_ratio, rng),
+            field.data_type(),
+        )?,
+        DataType::LargeList(_) => {
+            cast(&generate_list(num_rows, null_ratio, rng), field.data_type())?
+        }
         _ => Arc::new(NullArray::new(num_rows)),
-    }
+    })
 }
 
 fn generate_int64(cli: &Cli, num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> ArrayRef {
@@ -285,6 +653,251 @@ fn generate_boolean(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> Array
     Arc::new(BooleanArray::from(values))
 }
 
+/// One step per row from `start`, so every row group gets tight, non-overlapping bounds.
+fn generate_sorted(dtype: &DataType, start: usize, num_rows: usize) -> Result<ArrayRef> {
+    let start = i64::try_from(start).context("Row offset out of range")?;
+    let len = i64::try_from(num_rows).context("Row count out of range")?;
+    let steps = start..start + len;
+
+    Ok(match dtype {
+        DataType::Int64 => Arc::new(Int64Array::from_iter_values(steps)),
+        #[allow(clippy::cast_precision_loss)]
+        DataType::Float64 => Arc::new(Float64Array::from_iter_values(
+            steps.map(|step| step as f64),
+        )),
+        DataType::Utf8 => Arc::new(StringArray::from_iter_values(
+            steps.map(|step| format!("{step:012}")),
+        )),
+        DataType::Date32 => {
+            let first_day = EPOCH_2000_SECS / 86_400;
+            let days = steps
+                .map(|step| i32::try_from(first_day + step))
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .context("Too many rows for a sorted date column")?;
+            Arc::new(Date32Array::from(days))
+        }
+        DataType::Timestamp(unit, zone) => {
+            let values: Vec<Option<i64>> = steps
+                .map(|step| Some((EPOCH_2000_SECS + step) * per_second(*unit)))
+                .collect();
+            timestamp_array(*unit, zone.clone(), values)
+        }
+        other => anyhow::bail!("--sorted-by does not support {other} columns"),
+    })
+}
+
+/// Distinct keys in the skewed profile's long tail.
+const SKEWED_TAIL_KEYS: f64 = 10_000.0;
+
+/// One hot key for ~90% of rows; the rest draw from a long tail where key rank `r` has
+/// probability roughly proportional to `1/r`.
+fn generate_skewed_keys(num_rows: usize, rng: &mut StdRng) -> ArrayRef {
+    let keys = (0..num_rows).map(|_| {
+        if rng.gen::<f64>() < 0.9 {
+            "hot".to_string()
+        } else {
+            // SKEWED_TAIL_KEYS^u is log-uniform, so its integer part follows a 1/r density
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let rank = SKEWED_TAIL_KEYS.powf(rng.gen::<f64>()) as u64;
+            format!("key_{rank}")
+        }
+    });
+    Arc::new(StringArray::from_iter_values(keys))
+}
+
+/// Replace about `ratio` of the batch's rows with copies of rows before them.
+fn duplicate_rows(batch: &RecordBatch, ratio: f64, rng: &mut StdRng) -> Result<RecordBatch> {
+    let mut sources: Vec<u32> = Vec::with_capacity(batch.num_rows());
+    for row in 0..batch.num_rows() {
+        let source = if row > 0 && rng.gen::<f64>() < ratio {
+            // Copy whatever the earlier row ended up as, so the result is an exact repeat
+            sources[rng.gen_range(0..row)]
+        } else {
+            u32::try_from(row).context("Batch too large to duplicate rows")?
+        };
+        sources.push(source);
+    }
+    take_record_batch(batch, &UInt32Array::from(sources)).context("Failed to duplicate rows")
+}
+
+fn generate_binary(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> ArrayRef {
+    let mut builder = BinaryBuilder::new();
+
+    for _ in 0..num_rows {
+        if rng.gen::<f64>() < null_ratio {
+            builder.append_null();
+        } else {
+            // Arbitrary bytes, so most values are not valid UTF-8
+            let len = rng.gen_range(0..=32);
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            builder.append_value(&bytes);
+        }
+    }
+
+    Arc::new(builder.finish())
+}
+
+fn generate_fixed_binary(
+    width: i32,
+    num_rows: usize,
+    null_ratio: f64,
+    rng: &mut StdRng,
+) -> Result<ArrayRef> {
+    let mut builder = FixedSizeBinaryBuilder::new(width);
+    let len = usize::try_from(width).context("Invalid fixed binary width")?;
+
+    for _ in 0..num_rows {
+        if rng.gen::<f64>() < null_ratio {
+            builder.append_null();
+        } else {
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            builder
+                .append_value(&bytes)
+                .context("Failed to append fixed binary value")?;
+        }
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Seconds from 2000-01-01 to 2030-01-01, the range temporal values are drawn from.
+const EPOCH_2000_SECS: i64 = 946_684_800;
+const EPOCH_2030_SECS: i64 = 1_893_456_000;
+
+fn maybe<T>(null_ratio: f64, rng: &mut StdRng, value: impl FnOnce(&mut StdRng) -> T) -> Option<T> {
+    if rng.gen::<f64>() < null_ratio {
+        None
+    } else {
+        Some(value(rng))
+    }
+}
+
+fn generate_date32(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> ArrayRef {
+    let days = (EPOCH_2000_SECS / 86_400) as i32..(EPOCH_2030_SECS / 86_400) as i32;
+    let values: Vec<Option<i32>> = (0..num_rows)
+        .map(|_| maybe(null_ratio, rng, |rng| rng.gen_range(days.clone())))
+        .collect();
+    Arc::new(Date32Array::from(values))
+}
+
+fn generate_timestamp(
+    unit: TimeUnit,
+    zone: Option<Arc<str>>,
+    num_rows: usize,
+    null_ratio: f64,
+    rng: &mut StdRng,
+) -> ArrayRef {
+    let range = EPOCH_2000_SECS * per_second(unit)..EPOCH_2030_SECS * per_second(unit);
+    let values: Vec<Option<i64>> = (0..num_rows)
+        .map(|_| maybe(null_ratio, rng, |rng| rng.gen_range(range.clone())))
+        .collect();
+    timestamp_array(unit, zone, values)
+}
+
+fn per_second(unit: TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::Second => 1,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    }
+}
+
+fn timestamp_array(unit: TimeUnit, zone: Option<Arc<str>>, values: Vec<Option<i64>>) -> ArrayRef {
+    match unit {
+        TimeUnit::Second => Arc::new(TimestampSecondArray::from(values).with_timezone_opt(zone)),
+        TimeUnit::Millisecond => {
+            Arc::new(TimestampMillisecondArray::from(values).with_timezone_opt(zone))
+        }
+        TimeUnit::Microsecond => {
+            Arc::new(TimestampMicrosecondArray::from(values).with_timezone_opt(zone))
+        }
+        TimeUnit::Nanosecond => {
+            Arc::new(TimestampNanosecondArray::from(values).with_timezone_opt(zone))
+        }
+    }
+}
+
+fn generate_time64(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> ArrayRef {
+    let values: Vec<Option<i64>> = (0..num_rows)
+        .map(|_| maybe(null_ratio, rng, |rng| rng.gen_range(0..86_400_000_000)))
+        .collect();
+    Arc::new(Time64MicrosecondArray::from(values))
+}
+
+fn generate_decimal(
+    precision: u8,
+    scale: i8,
+    num_rows: usize,
+    null_ratio: f64,
+    rng: &mut StdRng,
+) -> Result<ArrayRef> {
+    let bound = 10_i128.pow(u32::from(precision)) - 1;
+    let values: Vec<Option<i128>> = (0..num_rows)
+        .map(|_| maybe(null_ratio, rng, |rng| rng.gen_range(-bound..=bound)))
+        .collect();
+    let array = Decimal128Array::from(values)
+        .with_precision_and_scale(precision, scale)
+        .context("Invalid decimal precision or scale")?;
+    Ok(Arc::new(array))
+}
+
+fn generate_list(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> ArrayRef {
+    let mut builder = ListBuilder::new(Int64Builder::new());
+
+    for _ in 0..num_rows {
+        if rng.gen::<f64>() < null_ratio {
+            builder.append_null();
+        } else {
+            for _ in 0..rng.gen_range(0..5) {
+                builder.values().append_value(rng.gen_range(-1_000..1_000));
+            }
+            builder.append(true);
+        }
+    }
+
+    Arc::new(builder.finish())
+}
+
+fn generate_struct(
+    cli: &Cli,
+    fields: &Fields,
+    num_rows: usize,
+    null_ratio: f64,
+    rng: &mut StdRng,
+) -> Result<ArrayRef> {
+    let children = fields
+        .iter()
+        .map(|field| generate_column(cli, field, null_ratio, num_rows, rng))
+        .collect::<Result<Vec<_>>>()?;
+    let validity: Vec<bool> = (0..num_rows)
+        .map(|_| rng.gen::<f64>() >= null_ratio)
+        .collect();
+    let array = StructArray::try_new(fields.clone(), children, Some(NullBuffer::from(validity)))
+        .context("Failed to build struct column")?;
+    Ok(Arc::new(array))
+}
+
+fn generate_map(num_rows: usize, null_ratio: f64, rng: &mut StdRng) -> Result<ArrayRef> {
+    let mut builder = MapBuilder::new(None, StringBuilder::new(), Int64Builder::new());
+
+    for _ in 0..num_rows {
+        if rng.gen::<f64>() < null_ratio {
+            builder
+                .append(false)
+                .context("Failed to append map entry")?;
+        } else {
+            for key in 0..rng.gen_range(0..4) {
+                builder.keys().append_value(format!("k{key}"));
+                builder.values().append_value(rng.gen_range(-1_000..1_000));
+            }
+            builder.append(true).context("Failed to append map entry")?;
+        }
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
 fn generate_random_string(rng: &mut StdRng, avg_len: usize) -> String {
     let len = rng.gen_range(1..=avg_len * 2);
     (0..len)
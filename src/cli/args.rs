@@ -1,6 +1,23 @@
-use crate::output::OutputFormat;
+use crate::dataset::{InputOptions, InputOrder};
+use crate::model::{
+    BenchOperation, ChecksumAlgorithm, ColumnCase, DdlDialect, ParquetWriteOptions, ReshapeOptions,
+    RowGroupRange, SortKey, SortedMergeOptions, StatsSort, StatsTolerance, TransformOptions,
+};
+use crate::output::{
+    parse_strftime, BinaryEncoding, CsvDialect, CsvQuoteStyle, DisplayOptions, DisplayTimeZone,
+    FileOutputFormat, FloatFormat, FloatStyle, Int64Format, OutputFormat, TableOverflow,
+    TemporalFormat, TextCompression,
+};
+use crate::warning::{MessageFormat, WarningPolicy};
+use arrow::datatypes::{DataType, TimeUnit};
 use clap::{Args, Parser, Subcommand};
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterVersion;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// How long `--wait-for-complete` waits when `--timeout` does not say.
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Parser)]
 #[command(name = "pq")]
@@ -11,6 +28,14 @@ use std::path::PathBuf;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Command,
+    #[command(flatten)]
+    pub display: DisplayArgs,
+    #[command(flatten)]
+    pub input: InputArgs,
+    #[command(flatten)]
+    pub messages: MessageArgs,
+    #[command(flatten)]
+    pub output: OutputFileArgs,
 }
 
 #[derive(Debug, Subcommand)]
@@ -18,19 +43,143 @@ pub enum Command {
     /// Show schema (column names, types, nullability)
     Schema(SchemaArgs),
     /// Show first N rows
-    Head(HeadArgs),
+    Head(ScanArgs),
     /// Show last N rows
-    Tail(TailArgs),
+    Tail(ScanArgs),
+    /// Pick values by jq-style field path, e.g. '.user.id, .items[0].sku'
+    Select(SelectArgs),
+    /// Print one column's values, one per line
+    Extract(ExtractArgs),
+    /// Show N random rows, optionally stratified by a column
+    Sample(SampleArgs),
+    /// Search rows for matching values
+    Grep(GrepArgs),
     /// Count total rows
     Count(CountArgs),
     /// Column statistics (min, max, nulls)
     Stats(StatsArgs),
-    /// Convert to CSV, JSON, or JSONL
+    /// Compare two files' row counts and column statistics, from the footers alone
+    StatsDiff(StatsDiffArgs),
+    /// Per-column null counts and percentages, with optional null-run detection
+    Nulls(NullsArgs),
+    /// Histogram of a numeric or temporal column
+    Hist(HistArgs),
+    /// Hash the logical row data, to compare files whatever their layout
+    Checksum(ChecksumArgs),
+    /// Report which row groups and pages a predicate would skip
+    PruneCheck(PruneCheckArgs),
+    /// Check whether a value may exist using bloom filters and statistics
+    Exists(ExistsArgs),
+    /// Convert to CSV, TSV, JSON, JSONL, or Arrow IPC
     Convert(ConvertArgs),
     /// Merge multiple parquet files
     Merge(MergeArgs),
+    /// Rename, drop, and cast columns, writing a new parquet file
+    Transform(TransformArgs),
+    /// Set or remove footer key-value metadata, copying the data unchanged
+    Meta(MetaArgs),
     /// File metadata (row groups, compression, size)
     Info(InfoArgs),
+    /// One-shot summary: file metadata, schema, and column statistics, from the footer alone
+    Describe(DescribeArgs),
+    /// Time head, count, or stats over repeated in-process runs
+    Bench(BenchArgs),
+    /// Print a shell completion script (bash, zsh, or fish)
+    Completions(CompletionsArgs),
+    /// Re-run a command whenever its input files change
+    Watch(WatchArgs),
+    /// Inspect option defaults from the config file and PQ_* environment variables
+    Config(ConfigArgs),
+}
+
+impl Command {
+    /// Input paths and glob patterns as given on the command line.
+    pub fn inputs(&self) -> Vec<PathBuf> {
+        match self {
+            Self::Schema(args) => args.inputs.clone(),
+            Self::Head(args) => args.inputs.clone(),
+            Self::Tail(args) => args.inputs.clone(),
+            Self::Select(args) => args.inputs.clone(),
+            Self::Extract(args) => args.inputs.clone(),
+            Self::Sample(args) => args.inputs.clone(),
+            Self::Grep(args) => args.inputs.clone(),
+            Self::Count(args) => args.inputs.clone(),
+            Self::Stats(args) => args.inputs.clone(),
+            Self::StatsDiff(args) => vec![args.old.clone(), args.new.clone()],
+            Self::Nulls(args) => args.inputs.clone(),
+            Self::Hist(args) => args.inputs.clone(),
+            Self::Checksum(args) => args.inputs.clone(),
+            Self::PruneCheck(args) => args.inputs.clone(),
+            Self::Exists(args) => args.inputs.clone(),
+            Self::Convert(args) => vec![args.input.clone()],
+            Self::Merge(args) => args.inputs.clone(),
+            Self::Transform(args) => vec![args.input.clone()],
+            Self::Meta(args) => vec![args.command.input().clone()],
+            Self::Info(args) => args.inputs.clone(),
+            Self::Describe(args) => args.inputs.clone(),
+            Self::Bench(args) => args.inputs.clone(),
+            Self::Completions(_) | Self::Watch(_) | Self::Config(_) => Vec::new(),
+        }
+    }
+
+    /// Whether `-q, --quiet` was given, which also silences warnings.
+    pub fn quiet(&self) -> bool {
+        match self {
+            Self::Schema(args) => args.quiet,
+            Self::Head(args) | Self::Tail(args) => args.quiet,
+            Self::Select(args) => args.quiet,
+            Self::Sample(args) => args.quiet,
+            Self::Grep(args) => args.quiet,
+            Self::Count(args) => args.quiet,
+            Self::Stats(args) => args.quiet,
+            Self::Nulls(args) => args.quiet,
+            Self::Hist(args) => args.quiet,
+            Self::Checksum(args) => args.quiet,
+            Self::PruneCheck(args) => args.quiet,
+            Self::Exists(args) => args.quiet,
+            Self::Merge(args) => args.quiet,
+            Self::Info(args) => args.quiet,
+            Self::Describe(args) => args.quiet,
+            Self::StatsDiff(_)
+            | Self::Bench(_)
+            | Self::Extract(_)
+            | Self::Convert(_)
+            | Self::Transform(_)
+            | Self::Meta(_)
+            | Self::Completions(_)
+            | Self::Watch(_)
+            | Self::Config(_) => false,
+        }
+    }
+
+    /// Whether `--force` was given, which lets `--output-file` replace an existing file.
+    pub fn force(&self) -> bool {
+        match self {
+            Self::Schema(args) => args.force,
+            Self::Head(args) | Self::Tail(args) => args.force,
+            Self::Select(args) => args.force,
+            Self::Extract(args) => args.force,
+            Self::Sample(args) => args.force,
+            Self::Grep(args) => args.force,
+            Self::Count(args) => args.force,
+            Self::Stats(args) => args.force,
+            Self::StatsDiff(args) => args.force,
+            Self::Nulls(args) => args.force,
+            Self::Hist(args) => args.force,
+            Self::Checksum(args) => args.force,
+            Self::PruneCheck(args) => args.force,
+            Self::Exists(args) => args.force,
+            Self::Convert(args) => args.force,
+            Self::Merge(args) => args.force,
+            Self::Transform(args) => args.force,
+            Self::Meta(args) => args.command.destination().force,
+            Self::Info(args) => args.force,
+            Self::Describe(args) => args.force,
+            Self::Bench(args) => args.force,
+            Self::Completions(args) => args.force,
+            Self::Watch(_) | Self::Config(_) => false,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
@@ -40,6 +189,8 @@ pub enum OutputFormatArg {
     Json,
     Jsonl,
     Csv,
+    /// CSV with a tab delimiter
+    Tsv,
 }
 
 impl From<OutputFormatArg> for OutputFormat {
@@ -49,53 +200,858 @@ impl From<OutputFormatArg> for OutputFormat {
             OutputFormatArg::Json => Self::Json,
             OutputFormatArg::Jsonl => Self::Jsonl,
             OutputFormatArg::Csv => Self::Csv,
+            OutputFormatArg::Tsv => Self::Tsv,
+        }
+    }
+}
+
+/// Output formats for commands that return rows, which can also be written as Arrow.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum RowOutputFormatArg {
+    #[default]
+    Table,
+    Json,
+    Jsonl,
+    Csv,
+    /// CSV with a tab delimiter
+    Tsv,
+    /// Arrow IPC stream (binary)
+    Arrow,
+    /// Parquet file (binary)
+    Parquet,
+}
+
+impl From<RowOutputFormatArg> for OutputFormat {
+    fn from(value: RowOutputFormatArg) -> Self {
+        match value {
+            RowOutputFormatArg::Table => Self::Table,
+            RowOutputFormatArg::Json => Self::Json,
+            RowOutputFormatArg::Jsonl => Self::Jsonl,
+            RowOutputFormatArg::Csv => Self::Csv,
+            RowOutputFormatArg::Tsv => Self::Tsv,
+            RowOutputFormatArg::Arrow => Self::Arrow,
+            RowOutputFormatArg::Parquet => Self::Parquet,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum ConvertFormatArg {
+    Csv,
+    /// CSV with a tab delimiter
+    Tsv,
+    Json,
+    Jsonl,
+    /// Arrow IPC: the file format, or the stream format on stdout
+    Arrow,
+    /// A Parquet rewrite, with the Parquet write options
+    Parquet,
+}
+
+impl From<ConvertFormatArg> for FileOutputFormat {
+    fn from(value: ConvertFormatArg) -> Self {
+        match value {
+            ConvertFormatArg::Csv => Self::Csv,
+            ConvertFormatArg::Tsv => Self::Tsv,
+            ConvertFormatArg::Json => Self::Json,
+            ConvertFormatArg::Jsonl => Self::Jsonl,
+            ConvertFormatArg::Arrow => Self::Arrow,
+            ConvertFormatArg::Parquet => Self::Parquet,
+        }
+    }
+}
+
+/// How files matched by a glob or found in a directory are ordered; explicit paths keep
+/// their command-line place.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum InputOrderArg {
+    /// Byte-wise by path (`part-10` before `part-2`)
+    #[default]
+    Name,
+    /// By path, with numbers compared by value (`part-2` before `part-10`)
+    NameNumeric,
+    /// Oldest modification time first
+    Mtime,
+    /// As the glob walk yields them, without re-sorting
+    None,
+}
+
+impl From<InputOrderArg> for InputOrder {
+    fn from(value: InputOrderArg) -> Self {
+        match value {
+            InputOrderArg::Name => Self::Name,
+            InputOrderArg::NameNumeric => Self::NameNumeric,
+            InputOrderArg::Mtime => Self::Mtime,
+            InputOrderArg::None => Self::None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum QuoteStyleArg {
+    /// Quote every field
+    Always,
+    /// Quote fields containing delimiters, quotes, or line breaks
+    #[default]
+    Necessary,
+    /// Never quote fields
+    Never,
+}
+
+impl From<QuoteStyleArg> for CsvQuoteStyle {
+    fn from(value: QuoteStyleArg) -> Self {
+        match value {
+            QuoteStyleArg::Always => Self::Always,
+            QuoteStyleArg::Necessary => Self::Necessary,
+            QuoteStyleArg::Never => Self::Never,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "CSV Options")]
+pub struct CsvArgs {
+    /// Field delimiter: a single ASCII character, or \t / tab for tabs
+    #[arg(long, default_value = ",", value_parser = parse_delimiter)]
+    pub delimiter: u8,
+    /// When to quote fields
+    #[arg(long, value_enum, default_value_t)]
+    pub quote_style: QuoteStyleArg,
+    /// Text written for null values (default: empty)
+    #[arg(long)]
+    pub null_value: Option<String>,
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Parquet Write Options")]
+pub struct ParquetWriteArgs {
+    /// Codec for every column: uncompressed, snappy, gzip[:0-9], zstd[:1-22],
+    /// brotli[:0-11], lz4, or lz4_raw
+    #[arg(long, default_value = "snappy", value_name = "CODEC", value_parser = parse_parquet_compression)]
+    pub compression: Compression,
+    /// Codec for one column or nested field, overriding --compression; repeatable
+    #[arg(long, value_name = "COLUMN=CODEC", value_parser = parse_column_compression)]
+    pub column_compression: Vec<(String, Compression)>,
+    /// Parquet format version; 1 also writes V1 data pages, which older readers need
+    #[arg(long, value_name = "VERSION")]
+    pub writer_version: Option<WriterVersionArg>,
+    /// Largest dictionary page before a column falls back to plain encoding
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1..))]
+    pub dictionary_page_size_limit: Option<u64>,
+    /// Target size of each data page
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u64).range(1..))]
+    pub data_page_size_limit: Option<u64>,
+    /// Write these columns without dictionary encoding (comma-separated)
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub disable_dictionary: Vec<String>,
+}
+
+impl ParquetWriteArgs {
+    pub fn options(&self) -> ParquetWriteOptions {
+        let bytes = |limit: u64| usize::try_from(limit).unwrap_or(usize::MAX);
+        ParquetWriteOptions {
+            compression: self.compression,
+            column_compression: self.column_compression.clone(),
+            writer_version: self.writer_version.map(Into::into),
+            dictionary_page_size_limit: self.dictionary_page_size_limit.map(bytes),
+            data_page_size_limit: self.data_page_size_limit.map(bytes),
+            disable_dictionary: self.disable_dictionary.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum WriterVersionArg {
+    /// Parquet 1.0 with V1 data pages
+    #[value(name = "1")]
+    V1,
+    /// Parquet 2.0 with V2 data pages
+    #[value(name = "2")]
+    V2,
+}
+
+impl From<WriterVersionArg> for WriterVersion {
+    fn from(value: WriterVersionArg) -> Self {
+        match value {
+            WriterVersionArg::V1 => Self::PARQUET_1_0,
+            WriterVersionArg::V2 => Self::PARQUET_2_0,
+        }
+    }
+}
+
+/// A codec name with an optional `:level`, spelled like `--compress` does for text.
+fn parse_parquet_compression(text: &str) -> Result<Compression, String> {
+    let (codec, level) = match text.split_once(':') {
+        Some((codec, level)) => (codec, Some(level)),
+        None => (text, None),
+    };
+    let level_of = |range: std::ops::RangeInclusive<u32>| match level {
+        Some(level) => level
+            .parse::<u32>()
+            .ok()
+            .filter(|level| range.contains(level))
+            .ok_or_else(|| {
+                format!(
+                    "invalid {codec} level '{level}' (expected {}-{})",
+                    range.start(),
+                    range.end()
+                )
+            })
+            .map(Some),
+        None => Ok(None),
+    };
+    let no_level = || match level {
+        Some(_) => Err(format!("{codec} does not take a level")),
+        None => Ok(()),
+    };
+
+    match codec.to_ascii_lowercase().as_str() {
+        "uncompressed" | "none" => no_level().map(|()| Compression::UNCOMPRESSED),
+        "snappy" => no_level().map(|()| Compression::SNAPPY),
+        "lz4" => no_level().map(|()| Compression::LZ4),
+        "lz4_raw" | "lz4-raw" => no_level().map(|()| Compression::LZ4_RAW),
+        "gzip" | "gz" => {
+            let level = level_of(0..=9)?
+                .map(GzipLevel::try_new)
+                .transpose()
+                .map_err(|error| error.to_string())?;
+            Ok(Compression::GZIP(level.unwrap_or_default()))
+        }
+        "zstd" | "zst" => {
+            let level = level_of(1..=22)?
+                .map(|level| ZstdLevel::try_new(level as i32))
+                .transpose()
+                .map_err(|error| error.to_string())?;
+            Ok(Compression::ZSTD(level.unwrap_or_default()))
+        }
+        "brotli" => {
+            let level = level_of(0..=11)?
+                .map(BrotliLevel::try_new)
+                .transpose()
+                .map_err(|error| error.to_string())?;
+            Ok(Compression::BROTLI(level.unwrap_or_default()))
+        }
+        _ => Err(format!(
+            "unknown compression '{text}' (expected uncompressed, snappy, gzip, zstd, brotli, lz4, or lz4_raw)"
+        )),
+    }
+}
+
+fn parse_column_compression(text: &str) -> Result<(String, Compression), String> {
+    let (column, codec) = split_assignment(text)?;
+    Ok((column.to_string(), parse_parquet_compression(codec)?))
+}
+
+impl CsvArgs {
+    /// `-o tsv` replaces the delimiter with a tab.
+    pub fn dialect(&self, format: OutputFormat, no_header: bool) -> CsvDialect {
+        let dialect = CsvDialect {
+            delimiter: self.delimiter,
+            quote_style: self.quote_style.into(),
+            null_value: self.null_value.clone().unwrap_or_default(),
+            header: !no_header,
+        };
+        if format == OutputFormat::Tsv {
+            dialect.tab_separated()
+        } else {
+            dialect
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Nested Column Options")]
+pub struct ReshapeArgs {
+    /// Replace struct columns with one `parent.child` column per field
+    #[arg(long)]
+    pub flatten: bool,
+    /// Give each element of this list column its own row, repeating the other columns
+    #[arg(long, value_name = "COLUMN")]
+    pub explode: Option<String>,
+    /// With --explode, keep rows whose list is null or empty, with a null element
+    #[arg(long, requires = "explode")]
+    pub keep_empty: bool,
+}
+
+impl ReshapeArgs {
+    pub fn options(&self) -> ReshapeOptions {
+        ReshapeOptions {
+            flatten: self.flatten,
+            explode: self.explode.clone(),
+            keep_empty: self.keep_empty,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum ShellArg {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl From<ShellArg> for clap_complete::Shell {
+    fn from(value: ShellArg) -> Self {
+        match value {
+            ShellArg::Bash => Self::Bash,
+            ShellArg::Zsh => Self::Zsh,
+            ShellArg::Fish => Self::Fish,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum BinaryArg {
+    Hex,
+    Base64,
+    /// Decode as UTF-8, replacing invalid bytes
+    #[value(name = "utf8-lossy")]
+    Utf8Lossy,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum FloatFormatArg {
+    /// Fixed digits after the point, e.g. 123.456000
+    Fixed,
+    /// Scientific notation, e.g. 1.234560e2
+    Scientific,
+    /// Like printf's %g: scientific only for very large or small values, no trailing zeros
+    General,
+}
+
+impl From<FloatFormatArg> for FloatStyle {
+    fn from(value: FloatFormatArg) -> Self {
+        match value {
+            FloatFormatArg::Fixed => Self::Fixed,
+            FloatFormatArg::Scientific => Self::Scientific,
+            FloatFormatArg::General => Self::General,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum Int64Arg {
+    /// Bare JSON numbers
+    Number,
+    /// Quoted strings, exact for readers that parse numbers as doubles
+    String,
+}
+
+impl From<Int64Arg> for Int64Format {
+    fn from(value: Int64Arg) -> Self {
+        match value {
+            Int64Arg::Number => Self::Number,
+            Int64Arg::String => Self::String,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum OverflowArg {
+    /// Wrap long cells onto several lines
+    Wrap,
+    /// Cut long cells short with an ellipsis, one line per row
+    Truncate,
+    /// Truncate, or show one record per block when there are too many columns to fit
+    AutoVertical,
+}
+
+impl From<OverflowArg> for TableOverflow {
+    fn from(value: OverflowArg) -> Self {
+        match value {
+            OverflowArg::Wrap => Self::Wrap,
+            OverflowArg::Truncate => Self::Truncate,
+            OverflowArg::AutoVertical => Self::AutoVertical,
+        }
+    }
+}
+
+impl From<BinaryArg> for BinaryEncoding {
+    fn from(value: BinaryArg) -> Self {
+        match value {
+            BinaryArg::Hex => Self::Hex,
+            BinaryArg::Base64 => Self::Base64,
+            BinaryArg::Utf8Lossy => Self::Utf8Lossy,
         }
     }
 }
 
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Display Options")]
+pub struct DisplayArgs {
+    /// strftime format for timestamp values, e.g. "%Y-%m-%d %H:%M:%S"
+    #[arg(long, global = true, value_parser = parse_strftime)]
+    pub timestamp_format: Option<String>,
+    /// strftime format for date values, e.g. "%d/%m/%Y"
+    #[arg(long, global = true, value_parser = parse_strftime)]
+    pub date_format: Option<String>,
+    /// Show timestamps in this zone: UTC, local, an IANA name, or an offset like +05:30
+    #[arg(long, global = true, value_name = "TZ")]
+    pub timezone: Option<DisplayTimeZone>,
+    /// Binary value encoding (default: hex for tables and CSV, base64 for JSON)
+    #[arg(long, global = true, value_enum)]
+    pub binary: Option<BinaryArg>,
+    /// Digits after the point for float values (significant digits with
+    /// `--float-format general`)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u8).range(0..=60))]
+    pub float_precision: Option<u8>,
+    /// Float notation (default: fixed when --float-precision is given); JSON keeps
+    /// numbers, rounded to match
+    #[arg(long, global = true, value_enum, value_name = "STYLE")]
+    pub float_format: Option<FloatFormatArg>,
+    /// How JSON writes Int64, UInt64, and decimal values (default: number); strings keep
+    /// values beyond 2^53 exact for readers such as JavaScript
+    #[arg(long, global = true, value_enum, value_name = "MODE")]
+    pub int64: Option<Int64Arg>,
+    /// What tables wider than the terminal do (default: truncate); piped output is
+    /// never fitted
+    #[arg(long, global = true, value_enum, value_name = "MODE")]
+    pub overflow: Option<OverflowArg>,
+    /// Print control characters and ANSI escapes in table cells as they are, instead of
+    /// as visible `\x1b`-style escapes; JSON and CSV always keep them
+    #[arg(long, global = true)]
+    pub raw_control_chars: bool,
+    /// Group integer digits in tables as 1,234,567, and info's row counts too; CSV and
+    /// JSON always keep plain numbers
+    #[arg(long, global = true)]
+    pub human_readable: bool,
+}
+
+impl DisplayArgs {
+    pub fn options(&self) -> DisplayOptions {
+        DisplayOptions {
+            temporal: TemporalFormat {
+                timestamp_format: self.timestamp_format.clone(),
+                date_format: self.date_format.clone(),
+                timezone: self.timezone,
+            },
+            binary: self.binary.map(Into::into),
+            float: FloatFormat {
+                precision: self.float_precision.map(usize::from),
+                style: self.float_format.map(Into::into),
+            },
+            int64: self.int64.map(Into::into),
+            overflow: self.overflow.map(Into::into),
+            raw_control_chars: self.raw_control_chars,
+            human_readable: self.human_readable,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+#[command(next_help_heading = "Input Options")]
+pub struct InputArgs {
+    /// Extensions of the files read from directory inputs (comma-separated, default: parquet)
+    #[arg(long, global = true, value_delimiter = ',', value_name = "EXT")]
+    pub ext: Vec<String>,
+    /// Levels of a directory input to read; 1 reads only its own files
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_depth: Option<u64>,
+    /// Read hidden files and directories inside directory inputs
+    #[arg(long, global = true)]
+    pub hidden: bool,
+    /// Descend into symlinked directories inside directory inputs; each real directory
+    /// is read once, so link cycles end
+    #[arg(long, global = true)]
+    pub follow_symlinks: bool,
+    /// Treat directory inputs as errors instead of reading the files inside them
+    #[arg(long, global = true)]
+    pub no_recursive: bool,
+    /// Match column names given to -c, --by, hist, and transform regardless of case;
+    /// an exact match still wins, and several case-insensitive matches are an error
+    #[arg(long, global = true)]
+    pub ignore_column_case: bool,
+    /// Rows decoded per batch while scanning files (default: 1024); smaller batches bound
+    /// memory for very wide rows, larger ones speed up narrow rows
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub batch_size: Option<u64>,
+    /// Wait for input files another process is still writing to get their footer,
+    /// instead of failing on them
+    #[arg(long, global = true)]
+    pub wait_for_complete: bool,
+    /// Longest --wait-for-complete waits, e.g. 30s, 500ms, or 2m (default: 30s)
+    #[arg(long, global = true, value_name = "DURATION", value_parser = parse_duration, requires = "wait_for_complete")]
+    pub timeout: Option<Duration>,
+}
+
+impl InputArgs {
+    pub fn options(&self) -> InputOptions {
+        let defaults = InputOptions::default();
+        InputOptions {
+            recursive: !self.no_recursive,
+            extensions: if self.ext.is_empty() {
+                defaults.extensions
+            } else {
+                self.ext.clone()
+            },
+            max_depth: self
+                .max_depth
+                .map(|depth| usize::try_from(depth).unwrap_or(usize::MAX)),
+            hidden: self.hidden,
+            follow_symlinks: self.follow_symlinks,
+            column_case: if self.ignore_column_case {
+                ColumnCase::Insensitive
+            } else {
+                ColumnCase::Sensitive
+            },
+            batch_size: self.batch_size.map_or(defaults.batch_size, |size| {
+                usize::try_from(size).unwrap_or(usize::MAX)
+            }),
+            wait_for_complete: self
+                .wait_for_complete
+                .then(|| self.timeout.unwrap_or(DEFAULT_WAIT_TIMEOUT)),
+            ..defaults
+        }
+    }
+
+    /// Fill flags this set leaves unspecified from `fallback`.
+    pub fn or(self, fallback: &Self) -> Self {
+        Self {
+            ext: if self.ext.is_empty() {
+                fallback.ext.clone()
+            } else {
+                self.ext
+            },
+            max_depth: self.max_depth.or(fallback.max_depth),
+            hidden: self.hidden || fallback.hidden,
+            follow_symlinks: self.follow_symlinks || fallback.follow_symlinks,
+            no_recursive: self.no_recursive || fallback.no_recursive,
+            ignore_column_case: self.ignore_column_case || fallback.ignore_column_case,
+            batch_size: self.batch_size.or(fallback.batch_size),
+            wait_for_complete: self.wait_for_complete || fallback.wait_for_complete,
+            timeout: self.timeout.or(fallback.timeout),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+#[command(next_help_heading = "Message Options")]
+pub struct MessageArgs {
+    /// Fail on the first warning instead of printing it
+    #[arg(long, global = true)]
+    pub strict: bool,
+    /// Write warnings and errors on stderr as text or as one JSON object per line
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        value_name = "FORMAT",
+        default_value = "text"
+    )]
+    pub error_format: ErrorFormatArg,
+}
+
+impl MessageArgs {
+    pub fn policy(&self, quiet: bool) -> WarningPolicy {
+        WarningPolicy {
+            quiet,
+            strict: self.strict,
+            format: self.error_format.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Args)]
+#[command(next_help_heading = "Output Options")]
+pub struct OutputFileArgs {
+    /// Write the command's data output to this file instead of stdout, replacing it only
+    /// once the command succeeds; warnings and progress stay on stderr. The command's
+    /// --force overwrites an existing file
+    #[arg(short = 'O', long, global = true, value_name = "PATH")]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum ErrorFormatArg {
+    /// `warning: ...` and `error: ...` lines
+    #[default]
+    Text,
+    /// `{"level":"warning","message":"..."}`, one object per line
+    Json,
+}
+
+impl From<ErrorFormatArg> for MessageFormat {
+    fn from(value: ErrorFormatArg) -> Self {
+        match value {
+            ErrorFormatArg::Text => Self::Text,
+            ErrorFormatArg::Json => Self::Json,
+        }
+    }
+}
+
+/// A length of time as a number and a unit of `ms`, `s`, or `m`; a bare number is
+/// seconds.
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| format!("expected a duration such as 30s or 500ms, got '{text}'"))?;
+    let seconds = match unit.trim() {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        other => return Err(format!("unknown duration unit '{other}'; use ms, s, or m")),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
+
+fn parse_delimiter(text: &str) -> Result<u8, String> {
+    match text {
+        "\\t" | "tab" => Ok(b'\t'),
+        _ => match text.as_bytes() {
+            [byte] if byte.is_ascii() && !matches!(byte, b'"' | b'\n' | b'\r') => Ok(*byte),
+            _ => Err(format!(
+                "expected a single ASCII character other than a quote or line break, got '{text}'"
+            )),
+        },
+    }
+}
+
 #[derive(Debug, Args)]
 pub struct SchemaArgs {
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
+    /// Print a CREATE TABLE statement for a SQL dialect, or the Arrow schema as JSON
+    #[arg(long, value_name = "DIALECT")]
+    pub ddl: Option<DdlDialectArg>,
+    /// Report one schema covering every file, listing the files that lack a column or
+    /// declare it differently; exits non-zero when any do
+    #[arg(long, conflicts_with = "ddl")]
+    pub unify: bool,
+    /// Also show each leaf column's ordinal, maximum definition and repetition levels,
+    /// and Parquet field ID
+    #[arg(long, conflicts_with_all = ["ddl", "unify"])]
+    pub extended: bool,
     /// Output format
     #[arg(short, long, default_value = "table")]
     pub output: OutputFormatArg,
-    /// Suppress headers and formatting
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum DdlDialectArg {
+    Duckdb,
+    Postgres,
+    Spark,
+    /// Arrow schema JSON
+    Arrow,
 }
 
+impl From<DdlDialectArg> for DdlDialect {
+    fn from(value: DdlDialectArg) -> Self {
+        match value {
+            DdlDialectArg::Duckdb => Self::Duckdb,
+            DdlDialectArg::Postgres => Self::Postgres,
+            DdlDialectArg::Spark => Self::Spark,
+            DdlDialectArg::Arrow => Self::Arrow,
+        }
+    }
+}
+
+/// Arguments shared by `head` and `tail`.
 #[derive(Debug, Args)]
-pub struct HeadArgs {
+pub struct ScanArgs {
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
     /// Number of rows to show
     #[arg(short = 'n', long = "rows", default_value = "10")]
     pub rows: usize,
+    /// Count --rows across all files in order, as if they were one file, instead of
+    /// per file
+    #[arg(long)]
+    pub global: bool,
+    /// Only show rows matching this predicate, e.g. "id = 12345"; row groups whose
+    /// statistics rule it out are skipped
+    #[arg(short = 'w', long = "where", value_name = "PREDICATE")]
+    pub predicate: Option<String>,
+    /// Print how many row groups --where skipped in each file to stderr
+    #[arg(long, requires = "predicate")]
+    pub explain_pruning: bool,
+    /// Only read these 0-based row groups: an index or an inclusive range like `3:7`
+    /// (repeatable or comma-separated; see `pq info --row-groups`)
+    #[arg(long, value_name = "N[:M]", value_delimiter = ',', value_parser = parse_row_group_range, conflicts_with = "predicate")]
+    pub row_group: Vec<RowGroupRange>,
+    /// Only show these columns, in this order: names, `re:` regexes, globs, or
+    /// 1-based positions and ranges like `2` or `1:3` (comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Prepend each row's 0-based index within its file
+    #[arg(long, conflicts_with_all = ["predicate", "row_group"])]
+    pub row_numbers: bool,
+    /// Sort each file's rows by this column, one of those shown, before showing them; add
+    /// `,desc` or `,nulls-last` (repeatable: later keys break ties)
+    #[arg(long, value_name = "COLUMN[,desc][,nulls-last]", value_parser = parse_sort_by)]
+    pub sort_by: Vec<SortKey>,
+    /// Prepend the path of the file each row came from, as `file` (`_file` in JSON)
+    #[arg(short = 'H', long)]
+    pub with_filename: bool,
+    /// Order of the files each glob or directory matches
+    #[arg(long, default_value = "name")]
+    pub order: InputOrderArg,
     /// Output format
     #[arg(short, long, default_value = "table")]
-    pub output: OutputFormatArg,
-    /// Suppress headers and formatting
+    pub output: RowOutputFormatArg,
+    /// Write binary output (`-o arrow`, `-o parquet`) even when stdout is a terminal,
+    /// and overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub reshape: ReshapeArgs,
+    #[command(flatten)]
+    pub csv: CsvArgs,
 }
 
 #[derive(Debug, Args)]
-pub struct TailArgs {
+pub struct SelectArgs {
+    /// Comma-separated field paths: `.column`, `.struct.field`, `.list[0]`, `."quoted name"`
+    pub expression: String,
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
-    /// Number of rows to show
+    /// Only read the first N rows of each file
+    #[arg(short = 'n', long = "rows")]
+    pub rows: Option<usize>,
+    /// Name columns by full path (`user.id`) instead of rebuilding nested structs
+    #[arg(long)]
+    pub flatten: bool,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: RowOutputFormatArg,
+    /// Write binary output (`-o arrow`, `-o parquet`) even when stdout is a terminal,
+    /// and overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct SampleArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Number of rows to pick, across all files
     #[arg(short = 'n', long = "rows", default_value = "10")]
     pub rows: usize,
+    /// Only show these columns, in this order: names, `re:` regexes, globs, or
+    /// 1-based positions and ranges like `2` or `1:3` (comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Seed the random choice, so the same inputs give the same sample
+    #[arg(long, value_name = "N")]
+    pub seed: Option<u64>,
+    /// Give each distinct value of this column an equal share of the rows
+    #[arg(long, value_name = "COLUMN")]
+    pub stratify: Option<String>,
+    /// With --stratify, share rows in proportion to each value's rows instead, but at
+    /// least N per value
+    #[arg(long, value_name = "N", requires = "stratify")]
+    pub min_per_group: Option<usize>,
     /// Output format
     #[arg(short, long, default_value = "table")]
-    pub output: OutputFormatArg,
-    /// Suppress headers and formatting
+    pub output: RowOutputFormatArg,
+    /// Write binary output (`-o arrow`, `-o parquet`) even when stdout is a terminal,
+    /// and overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    /// Skip the per-value counts --stratify prints on stderr; column headers stay (see
+    /// --no-header)
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ExtractArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Column to print
+    #[arg(short, long)]
+    pub column: String,
+    /// Print each distinct value once, where it first appears
+    #[arg(long)]
+    pub unique: bool,
+    /// Skip nulls instead of printing --null-value for them
+    #[arg(long, conflicts_with = "null_value")]
+    pub no_nulls: bool,
+    /// Line printed for null values
+    #[arg(long, value_name = "TEXT", default_value = "")]
+    pub null_value: String,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct GrepArgs {
+    /// Text to search for
+    pub pattern: String,
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Columns to search (comma-separated selectors as in `head --columns`, default: all
+    /// string columns).
+    /// Non-string columns match on their display form, and literal patterns must match the whole value
+    #[arg(short, long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Treat the pattern as a regular expression
+    #[arg(long)]
+    pub regex: bool,
+    /// Match case-insensitively
+    #[arg(short, long)]
+    pub ignore_case: bool,
+    /// Stop after N matching rows per file
+    #[arg(short = 'n', long = "max-count")]
+    pub max_count: Option<usize>,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: RowOutputFormatArg,
+    /// Write binary output (`-o arrow`, `-o parquet`) even when stdout is a terminal,
+    /// and overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    /// Suppress `==>` file separators and the match count; column headers stay (see
+    /// --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
 }
 
 #[derive(Debug, Args)]
@@ -103,9 +1059,40 @@ pub struct CountArgs {
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
-    /// Suppress headers and formatting
+    /// Count rows per distinct value of these columns (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub by: Vec<String>,
+    /// Show at most N groups, largest first (with --by)
+    #[arg(long, requires = "by")]
+    pub limit: Option<usize>,
+    /// Also decode each file's rows and fail if they differ from the footer's count
+    #[arg(long, conflicts_with = "by")]
+    pub verify: bool,
+    /// Count only rows matching this predicate, e.g. "id = 12345"; row groups whose
+    /// statistics rule it out are skipped
+    #[arg(short = 'w', long = "where", value_name = "PREDICATE", conflicts_with_all = ["by", "verify"])]
+    pub predicate: Option<String>,
+    /// Print how many row groups --where skipped in each file to stderr
+    #[arg(long, requires = "predicate")]
+    pub explain_pruning: bool,
+    /// Print how many rows --where counted from statistics and how many it decoded
+    /// in each file to stderr
+    #[arg(short, long, requires = "predicate")]
+    pub verbose: bool,
+    /// Output format (with --by)
+    #[arg(short, long, default_value = "table", requires = "by")]
+    pub output: OutputFormatArg,
+    /// Print bare counts, without file names or the total
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
 }
 
 #[derive(Debug, Args)]
@@ -113,25 +1100,418 @@ pub struct StatsArgs {
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
-    /// Specific column to show stats for
+    /// Columns to show stats for, in this order: names, `re:` regexes, globs, or
+    /// 1-based positions and ranges like `2` or `1:3` (comma-separated)
+    #[arg(short, long, alias = "column", value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Show each row group's statistics separately instead of merging them
+    #[arg(long)]
+    pub by_row_group: bool,
+    /// Only use these 0-based row groups' statistics: an index or an inclusive range
+    /// like `3:7` (repeatable or comma-separated)
+    #[arg(long, value_name = "N[:M]", value_delimiter = ',', value_parser = parse_row_group_range)]
+    pub row_group: Vec<RowGroupRange>,
+    /// Order columns by compressed size or null count (largest first), or by name,
+    /// instead of file order
+    #[arg(long, value_name = "KEY", conflicts_with = "by_row_group")]
+    pub sort_by: Option<StatsSortArg>,
+    /// Cut min and max values in tables to this many characters
+    #[arg(long, value_name = "N", default_value = "64", value_parser = clap::value_parser!(u64).range(1..))]
+    pub value_width: u64,
+    /// Show min and max values in tables in full
+    #[arg(long, conflicts_with = "value_width")]
+    pub full_values: bool,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
     #[arg(short, long)]
-    pub column: Option<String>,
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum StatsSortArg {
+    /// Most compressed bytes first
+    Size,
+    /// Most nulls first
+    Nulls,
+    /// By column name
+    Name,
+}
+
+impl From<StatsSortArg> for StatsSort {
+    fn from(value: StatsSortArg) -> Self {
+        match value {
+            StatsSortArg::Size => Self::Size,
+            StatsSortArg::Nulls => Self::Nulls,
+            StatsSortArg::Name => Self::Name,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct StatsDiffArgs {
+    /// The file before the change
+    pub old: PathBuf,
+    /// The file after it
+    pub new: PathBuf,
+    /// Largest change allowed, absolute (`10`) or a percent of the old value (`5%`);
+    /// exits non-zero when a statistic moves further or a column appears or disappears
+    #[arg(long, value_name = "N[%]", value_parser = parse_tolerance)]
+    pub tolerance: Option<StatsTolerance>,
     /// Output format
     #[arg(short, long, default_value = "table")]
     pub output: OutputFormatArg,
-    /// Suppress headers and formatting
+    /// Omit column headers from tables and CSV
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+/// `N` or `N:M` with `N <= M`, both 0-based row group indexes.
+fn parse_row_group_range(text: &str) -> Result<RowGroupRange, String> {
+    let index = |part: &str| {
+        part.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("expected a row group index or range like 3:7, got '{text}'"))
+    };
+    let (first, last) = match text.split_once(':') {
+        Some((first, last)) => (index(first)?, index(last)?),
+        None => (index(text)?, index(text)?),
+    };
+    if first > last {
+        return Err(format!("range '{text}' ends before it starts"));
+    }
+    Ok(RowGroupRange { first, last })
+}
+
+/// `N` or `N%`, a non-negative number.
+fn parse_tolerance(text: &str) -> Result<StatsTolerance, String> {
+    let (number, percent) = match text.strip_suffix('%') {
+        Some(number) => (number, true),
+        None => (text, false),
+    };
+    let limit: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("expected a number or a percentage like 5%, got '{text}'"))?;
+    if !limit.is_finite() || limit < 0.0 {
+        return Err(format!("tolerance must be zero or more, got '{text}'"));
+    }
+    Ok(if percent {
+        StatsTolerance::Percent(limit)
+    } else {
+        StatsTolerance::Absolute(limit)
+    })
+}
+
+#[derive(Debug, Args)]
+pub struct NullsArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Columns to analyze: names, `re:` regexes, globs, or 1-based positions and ranges
+    /// like `2` or `1:3` (comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub columns: Vec<String>,
+    /// Also report the longest run of consecutive nulls and the first and last non-null rows
+    #[arg(long)]
+    pub runs: bool,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct HistArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Integer, float, decimal, date, time, or timestamp column to bucket
+    #[arg(short, long, required = true)]
+    pub column: String,
+    /// Number of buckets
+    #[arg(short, long, default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    pub buckets: u64,
+    /// Space bucket edges geometrically; needs a positive minimum
+    #[arg(long)]
+    pub log_scale: bool,
+    /// Lower bound of the first bucket, in the column's type (default: the smallest value)
+    #[arg(long, allow_hyphen_values = true)]
+    pub min: Option<String>,
+    /// Upper bound of the last bucket, in the column's type (default: the largest value)
+    #[arg(long, allow_hyphen_values = true)]
+    pub max: Option<String>,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress the summary of excluded values; column headers stay (see --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum ChecksumAlgorithmArg {
+    /// 64-bit XXH3 (fast)
+    #[default]
+    Xxh3,
+    /// SHA-256
+    Sha256,
+}
+
+impl From<ChecksumAlgorithmArg> for ChecksumAlgorithm {
+    fn from(value: ChecksumAlgorithmArg) -> Self {
+        match value {
+            ChecksumAlgorithmArg::Xxh3 => Self::Xxh3,
+            ChecksumAlgorithmArg::Sha256 => Self::Sha256,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ChecksumArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Also hash each column on its own
+    #[arg(long)]
+    pub by_column: bool,
+    /// Hash function
+    #[arg(long, default_value = "xxh3")]
+    pub algorithm: ChecksumAlgorithmArg,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PruneCheckArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Predicate to evaluate, e.g. "ts >= '2024-01-01' AND ts < '2024-01-02'"
+    #[arg(short = 'w', long = "where", value_name = "PREDICATE", required = true)]
+    pub predicate: String,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct ExistsArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Column to look up
+    #[arg(short, long, required = true)]
+    pub column: String,
+    /// Value to look for
+    #[arg(short, long, required = true)]
+    pub value: String,
+    /// Scan candidate row groups to confirm the value is present
+    #[arg(long)]
+    pub scan: bool,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Accepted for compatibility; there is nothing besides data to suppress, and
+    /// --no-header drops column headers
+    #[arg(short, long, hide = true)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
 }
 
 #[derive(Debug, Args)]
 pub struct ConvertArgs {
-    /// Input parquet file
+    /// Input parquet file, or with --out-dir a glob or directory of them
     #[arg(required = true)]
     pub input: PathBuf,
-    /// Output file path
+    /// Output file path, or - for stdout
+    #[arg(required_unless_present = "out_dir", conflicts_with = "out_dir")]
+    pub output_path: Option<PathBuf>,
+    /// Convert every input file into this directory as <stem>.<format>, creating it
+    #[arg(long, value_name = "DIR", requires = "format")]
+    pub out_dir: Option<PathBuf>,
+    /// Files to convert at once with --out-dir
+    #[arg(short, long, default_value = "1", value_name = "N", requires = "out_dir", value_parser = clap::value_parser!(u64).range(1..))]
+    pub jobs: u64,
+    /// With --out-dir, report files that fail and convert the rest instead of stopping
+    #[arg(long, requires = "out_dir")]
+    pub skip_bad_files: bool,
+    /// Output format (default: inferred from the output extension)
+    #[arg(short, long)]
+    pub format: Option<ConvertFormatArg>,
+    /// Compress the output: gzip or zstd, optionally with a level (zstd:19).
+    /// Inferred from a .gz or .zst output extension
+    #[arg(long, value_name = "CODEC[:LEVEL]")]
+    pub compress: Option<TextCompression>,
+    /// Only convert these columns, in this order: names, `re:` regexes, globs, or
+    /// 1-based positions and ranges like `2` or `1:3` (comma-separated)
+    #[arg(short, long, value_delimiter = ',', conflicts_with = "exclude")]
+    pub columns: Vec<String>,
+    /// Convert every column except those these selectors pick (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub exclude: Vec<String>,
+    /// Convert only the first N rows
+    #[arg(short = 'n', long)]
+    pub limit: Option<usize>,
+    /// Overwrite an existing output file, and write binary output (Arrow, Parquet) even
+    /// when stdout is a terminal
+    #[arg(long)]
+    pub force: bool,
+    /// Omit the header row of CSV and TSV output
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub reshape: ReshapeArgs,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+    #[command(flatten)]
+    pub write: ParquetWriteArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct TransformArgs {
+    /// Input parquet file
     #[arg(required = true)]
-    pub output_path: PathBuf,
+    pub input: PathBuf,
+    /// Output parquet file
+    #[arg(short, long, required = true)]
+    pub output: PathBuf,
+    /// Rename columns (comma-separated OLD=NEW pairs)
+    #[arg(long, value_delimiter = ',', value_name = "OLD=NEW", value_parser = parse_rename)]
+    pub rename: Vec<(String, String)>,
+    /// Drop these columns (comma-separated)
+    #[arg(long, value_delimiter = ',')]
+    pub drop: Vec<String>,
+    /// Cast a column, repeatable: COLUMN=TYPE with TYPE one of bool, int8-64, uint8-64,
+    /// float32, float64, string, large_string, binary, date32, date64,
+    /// timestamp_s/ms/us/ns, or an Arrow type name such as `Decimal128(10, 2)`
+    #[arg(long, value_name = "COLUMN=TYPE", value_parser = parse_cast)]
+    pub cast: Vec<(String, DataType)>,
+    /// Write values a cast cannot represent as null, or truncated, instead of failing
+    #[arg(long)]
+    pub allow_lossy: bool,
+    /// Overwrite the output file if it already exists
+    #[arg(short, long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub write: ParquetWriteArgs,
+}
+
+impl TransformArgs {
+    pub fn options(&self, input: &InputOptions) -> TransformOptions {
+        TransformOptions {
+            renames: self.rename.clone(),
+            drops: self.drop.clone(),
+            casts: self.cast.clone(),
+            allow_lossy: self.allow_lossy,
+            column_case: input.column_case,
+            batch_size: Some(input.batch_size),
+        }
+    }
+}
+
+fn split_assignment(text: &str) -> Result<(&str, &str), String> {
+    match text.split_once('=') {
+        Some((name, value)) if !name.is_empty() && !value.is_empty() => Ok((name, value)),
+        _ => Err(format!("expected NAME=VALUE, got '{text}'")),
+    }
+}
+
+fn parse_rename(text: &str) -> Result<(String, String), String> {
+    let (from, to) = split_assignment(text)?;
+    Ok((from.to_string(), to.to_string()))
+}
+
+fn parse_cast(text: &str) -> Result<(String, DataType), String> {
+    let (column, type_name) = split_assignment(text)?;
+    let data_type = match type_name.to_ascii_lowercase().as_str() {
+        "bool" | "boolean" => DataType::Boolean,
+        "int8" => DataType::Int8,
+        "int16" => DataType::Int16,
+        "int32" => DataType::Int32,
+        "int64" => DataType::Int64,
+        "uint8" => DataType::UInt8,
+        "uint16" => DataType::UInt16,
+        "uint32" => DataType::UInt32,
+        "uint64" => DataType::UInt64,
+        "float32" | "float" => DataType::Float32,
+        "float64" | "double" => DataType::Float64,
+        "string" | "utf8" => DataType::Utf8,
+        "large_string" => DataType::LargeUtf8,
+        "binary" => DataType::Binary,
+        "date32" | "date" => DataType::Date32,
+        "date64" => DataType::Date64,
+        "timestamp_s" => DataType::Timestamp(TimeUnit::Second, None),
+        "timestamp_ms" => DataType::Timestamp(TimeUnit::Millisecond, None),
+        "timestamp_us" | "timestamp" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "timestamp_ns" => DataType::Timestamp(TimeUnit::Nanosecond, None),
+        _ => type_name
+            .parse()
+            .map_err(|_| format!("unknown type '{type_name}' for column {column}"))?,
+    };
+    Ok((column.to_string(), data_type))
 }
 
 #[derive(Debug, Args)]
@@ -142,6 +1522,153 @@ pub struct MergeArgs {
     /// Output file path
     #[arg(short, long, required = true)]
     pub output: PathBuf,
+    /// Overwrite the output file if it already exists
+    #[arg(short, long)]
+    pub force: bool,
+    /// Add the inputs' rows to the end of the output, which must have the same schema
+    #[arg(short, long, conflicts_with = "force")]
+    pub append: bool,
+    /// Order of the files each glob or directory matches
+    #[arg(long, default_value = "name")]
+    pub order: InputOrderArg,
+    /// Interleave inputs already sorted by this column into one sorted file instead of
+    /// concatenating them; add `,desc` for descending order
+    #[arg(long, value_name = "COLUMN[,desc]", value_parser = parse_sort_key, conflicts_with = "append")]
+    pub sorted_by: Option<(String, bool)>,
+    /// Skip checking that every input really is sorted by --sorted-by
+    #[arg(long, requires = "sorted_by")]
+    pub assume_sorted: bool,
+    /// Skip the summary of each input's rows on stderr
+    #[arg(short, long)]
+    pub quiet: bool,
+    #[command(flatten)]
+    pub write: ParquetWriteArgs,
+}
+
+impl MergeArgs {
+    pub fn sorted_merge(&self) -> Option<SortedMergeOptions> {
+        self.sorted_by
+            .clone()
+            .map(|(column, descending)| SortedMergeOptions {
+                column,
+                descending,
+                assume_sorted: self.assume_sorted,
+            })
+    }
+}
+
+/// `COLUMN`, `COLUMN,asc`, or `COLUMN,desc`, as the column and whether it descends.
+fn parse_sort_key(text: &str) -> Result<(String, bool), String> {
+    let (column, descending) = match text.rsplit_once(',') {
+        Some((column, direction)) => match direction.to_ascii_lowercase().as_str() {
+            "asc" => (column, false),
+            "desc" => (column, true),
+            _ => {
+                return Err(format!(
+                    "unknown direction '{direction}' (expected asc or desc)"
+                ))
+            }
+        },
+        None => (text, false),
+    };
+    if column.is_empty() {
+        return Err("expected a column name".to_string());
+    }
+    Ok((column.to_string(), descending))
+}
+
+/// `COLUMN` followed by any of `,asc`, `,desc`, `,nulls-first`, and `,nulls-last`.
+fn parse_sort_by(text: &str) -> Result<SortKey, String> {
+    let mut parts = text.split(',');
+    let column = parts.next().unwrap_or_default();
+    if column.is_empty() {
+        return Err("expected a column name".to_string());
+    }
+    let mut key = SortKey::new(column);
+    for part in parts {
+        match part.to_ascii_lowercase().as_str() {
+            "asc" => key.descending = false,
+            "desc" => key.descending = true,
+            "nulls-first" => key.nulls_first = true,
+            "nulls-last" => key.nulls_first = false,
+            _ => {
+                return Err(format!(
+                    "unknown sort option '{part}' (expected asc, desc, nulls-first, or nulls-last)"
+                ))
+            }
+        }
+    }
+    Ok(key)
+}
+
+#[derive(Debug, Args)]
+pub struct DescribeArgs {
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
+    #[arg(short, long)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum BenchOperationArg {
+    /// Read the first --rows rows of each file
+    Head,
+    /// Count rows from the footers
+    Count,
+    /// Merge every column's footer statistics
+    Stats,
+}
+
+#[derive(Debug, Args)]
+pub struct BenchArgs {
+    /// Operation to time
+    pub operation: BenchOperationArg,
+    /// Parquet file(s) to read
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    /// Rows head reads from each file
+    #[arg(short = 'n', long = "rows", default_value = "10")]
+    pub rows: usize,
+    /// Timed runs
+    #[arg(long, default_value = "10", value_parser = clap::value_parser!(u64).range(1..))]
+    pub iterations: u64,
+    /// Untimed runs before the timed ones
+    #[arg(long, default_value = "2")]
+    pub warmup: u64,
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Omit column headers from tables and CSV
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+impl BenchArgs {
+    pub fn operation(&self) -> BenchOperation {
+        match self.operation {
+            BenchOperationArg::Head => BenchOperation::Head { rows: self.rows },
+            BenchOperationArg::Count => BenchOperation::Count,
+            BenchOperationArg::Stats => BenchOperation::Stats,
+        }
+    }
 }
 
 #[derive(Debug, Args)]
@@ -149,10 +1676,164 @@ pub struct InfoArgs {
     /// Parquet file(s) to read
     #[arg(required = true)]
     pub inputs: Vec<PathBuf>,
+    /// Show per-column storage details (compression, sizes, bloom filters)
+    #[arg(long)]
+    pub columns: bool,
+    /// Show page index details (rows, min/max, nulls per page) for each column chunk
+    #[arg(long, conflicts_with = "columns")]
+    pub pages: bool,
+    /// Show per-row-group layout (rows, sizes, statistics, dictionaries, indexes)
+    #[arg(long, conflicts_with_all = ["columns", "pages"])]
+    pub row_groups: bool,
+    /// Show the footer's key-value metadata
+    #[arg(long, conflicts_with_all = ["columns", "pages", "row_groups"])]
+    pub metadata: bool,
+    /// Only show pages for this column
+    #[arg(short, long, requires = "pages")]
+    pub column: Option<String>,
+    /// Only show pages for this row group
+    #[arg(long, value_name = "N", requires = "pages")]
+    pub row_group: Option<usize>,
+    /// Print exact byte counts in tables instead of sizes like 1.50 MB
+    #[arg(long)]
+    pub raw_bytes: bool,
     /// Output format
     #[arg(short, long, default_value = "table")]
     pub output: OutputFormatArg,
-    /// Suppress headers and formatting
+    /// Suppress `==>` file separators; column headers stay (see --no-header)
     #[arg(short, long)]
     pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate completions for
+    pub shell: ShellArg,
+    /// Overwrite the --output-file if it already exists
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigCommand {
+    /// Show each configurable default, its value, and where it came from
+    Show(ConfigShowArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct ConfigShowArgs {
+    /// Output format
+    #[arg(short, long, default_value = "table")]
+    pub output: OutputFormatArg,
+    /// Accepted for compatibility; there is nothing besides data to suppress, and
+    /// --no-header drops column headers
+    #[arg(short, long, hide = true)]
+    pub quiet: bool,
+    /// Omit column headers from tables and CSV; --quiet keeps them
+    #[arg(long)]
+    pub no_header: bool,
+    #[command(flatten)]
+    pub csv: CsvArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct MetaArgs {
+    #[command(subcommand)]
+    pub command: MetaCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum MetaCommand {
+    /// Add key-value entries, replacing the values of keys already present
+    Set(MetaSetArgs),
+    /// Remove key-value entries
+    Remove(MetaRemoveArgs),
+}
+
+impl MetaCommand {
+    pub fn input(&self) -> &PathBuf {
+        match self {
+            Self::Set(args) => &args.input,
+            Self::Remove(args) => &args.input,
+        }
+    }
+
+    pub fn destination(&self) -> &MetaDestinationArgs {
+        match self {
+            Self::Set(args) => &args.destination,
+            Self::Remove(args) => &args.destination,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct MetaSetArgs {
+    /// Input parquet file
+    pub input: PathBuf,
+    /// Entries to set
+    #[arg(required = true, value_name = "KEY=VALUE", value_parser = parse_metadata_entry)]
+    pub entries: Vec<(String, String)>,
+    #[command(flatten)]
+    pub destination: MetaDestinationArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct MetaRemoveArgs {
+    /// Input parquet file
+    pub input: PathBuf,
+    /// Keys to remove
+    #[arg(required = true, value_name = "KEY")]
+    pub keys: Vec<String>,
+    #[command(flatten)]
+    pub destination: MetaDestinationArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct MetaDestinationArgs {
+    /// Output parquet file
+    #[arg(short, long, required_unless_present = "in_place")]
+    pub output: Option<PathBuf>,
+    /// Replace the input file, through a temp file renamed over it once complete
+    #[arg(long, conflicts_with = "output")]
+    pub in_place: bool,
+    /// Overwrite the output file if it already exists
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+/// `KEY=VALUE`, where only the key has to be non-empty.
+fn parse_metadata_entry(text: &str) -> Result<(String, String), String> {
+    match text.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got '{text}'")),
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct WatchArgs {
+    /// Wait until input files have been quiet this long before re-running
+    #[arg(long, value_name = "MS", default_value = "200")]
+    pub debounce: u64,
+    /// Command to run, e.g. head data.parquet -n 5
+    #[arg(
+        required = true,
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        value_name = "COMMAND"
+    )]
+    pub command: Vec<String>,
 }
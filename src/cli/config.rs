@@ -0,0 +1,349 @@
+//! Option defaults from a config file and `PQ_*` environment variables
+//!
+//! Defaults are installed as clap default values before parsing, so a flag given on the
+//! command line always wins, then the environment, then the config file.
+
+use crate::cli::args::Cli;
+use crate::error::PqError;
+use crate::model::{ConfigSetting, ConfigSource};
+use crate::path_text::display_path;
+use crate::Result;
+use clap::CommandFactory;
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+/// A configurable default: its config file key, environment variable, and the clap
+/// argument it supplies.
+#[derive(Debug)]
+struct Setting {
+    key: &'static str,
+    env: &'static str,
+    arg: &'static str,
+    /// Global display options live on the top-level command; the others belong to
+    /// each subcommand that accepts the value.
+    global: bool,
+}
+
+const SETTINGS: [Setting; 9] = [
+    Setting {
+        key: "output",
+        env: "PQ_OUTPUT",
+        arg: "output",
+        global: false,
+    },
+    Setting {
+        key: "float_precision",
+        env: "PQ_FLOAT_PRECISION",
+        arg: "float_precision",
+        global: true,
+    },
+    Setting {
+        key: "float_format",
+        env: "PQ_FLOAT_FORMAT",
+        arg: "float_format",
+        global: true,
+    },
+    Setting {
+        key: "timestamp_format",
+        env: "PQ_TIMESTAMP_FORMAT",
+        arg: "timestamp_format",
+        global: true,
+    },
+    Setting {
+        key: "date_format",
+        env: "PQ_DATE_FORMAT",
+        arg: "date_format",
+        global: true,
+    },
+    Setting {
+        key: "timezone",
+        env: "PQ_TIMEZONE",
+        arg: "timezone",
+        global: true,
+    },
+    Setting {
+        key: "binary",
+        env: "PQ_BINARY",
+        arg: "binary",
+        global: true,
+    },
+    Setting {
+        key: "int64",
+        env: "PQ_INT64",
+        arg: "int64",
+        global: true,
+    },
+    Setting {
+        key: "overflow",
+        env: "PQ_OVERFLOW",
+        arg: "overflow",
+        global: true,
+    },
+];
+
+/// Overrides the config file location; set it empty to read no file.
+const CONFIG_PATH_ENV: &str = "PQ_CONFIG";
+
+/// The merged defaults, one per configured setting.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    values: Vec<(&'static Setting, String, ConfigSource)>,
+}
+
+impl Config {
+    /// Read the config file, if there is one, then the environment. Every value is
+    /// checked against the flag it stands for, so a bad default fails every command
+    /// up front rather than only the ones that use it.
+    pub fn load() -> Result<Self> {
+        let mut config = Self::default();
+        if let Some(path) = config_path() {
+            match fs::read_to_string(&path) {
+                Ok(text) => config.read_file(&path, &text)?,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                Err(error) => return Err(PqError::invalid_config(display_path(&path), error)),
+            }
+        }
+        for setting in &SETTINGS {
+            if let Some(value) = env::var_os(setting.env) {
+                let value = value.into_string().map_err(|_| {
+                    PqError::invalid_config(format!("${}", setting.env), "value is not UTF-8")
+                })?;
+                config.set(setting, value, ConfigSource::Env(setting.env.to_string()));
+            }
+        }
+
+        let command = Cli::command();
+        for (setting, value, source) in &config.values {
+            check(&command, setting, value).map_err(|details| {
+                PqError::invalid_config(source, format!("{}: {details}", setting.key))
+            })?;
+        }
+        Ok(config)
+    }
+
+    fn read_file(&mut self, path: &std::path::Path, text: &str) -> Result<()> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|error| PqError::invalid_config(display_path(path), error))?;
+        for (key, value) in table {
+            let Some(setting) = SETTINGS.iter().find(|setting| setting.key == key) else {
+                let keys: Vec<&str> = SETTINGS.iter().map(|setting| setting.key).collect();
+                return Err(PqError::invalid_config(
+                    display_path(path),
+                    format!(
+                        "unknown setting '{key}'; expected one of {}",
+                        keys.join(", ")
+                    ),
+                ));
+            };
+            let value = match value {
+                toml::Value::String(text) => text,
+                toml::Value::Integer(number) => number.to_string(),
+                other => {
+                    return Err(PqError::invalid_config(
+                        display_path(path),
+                        format!(
+                            "{key}: expected a string or integer, got {}",
+                            other.type_str()
+                        ),
+                    ))
+                }
+            };
+            self.set(setting, value, ConfigSource::File(path.to_path_buf()));
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, setting: &'static Setting, value: String, source: ConfigSource) {
+        self.values
+            .retain(|(existing, _, _)| existing.key != setting.key);
+        self.values.push((setting, value, source));
+    }
+
+    /// The command line parser with the configured defaults installed.
+    pub fn command(&self) -> clap::Command {
+        self.install(Cli::command(), true)
+    }
+
+    /// Like [`Config::command`], but leaving the global display options alone: `watch`
+    /// parses its wrapped command this way, as the display options it was given already
+    /// carry the configured defaults.
+    pub fn subcommand_defaults(&self) -> clap::Command {
+        self.install(Cli::command(), false)
+    }
+
+    fn install(&self, mut command: clap::Command, globals: bool) -> clap::Command {
+        for (setting, value, _) in &self.values {
+            if setting.global {
+                if globals {
+                    command = command.mut_arg(setting.arg, |arg| arg.default_value(value.clone()));
+                }
+            } else {
+                command = install_in_subcommands(command, setting.arg, value);
+            }
+        }
+        command
+    }
+
+    /// Every setting, configured or not, in a fixed order.
+    pub fn settings(&self) -> Vec<ConfigSetting> {
+        SETTINGS
+            .iter()
+            .map(|setting| {
+                let configured = self
+                    .values
+                    .iter()
+                    .find(|(configured, _, _)| configured.key == setting.key);
+                ConfigSetting {
+                    key: setting.key.to_string(),
+                    value: configured.map(|(_, value, _)| value.clone()),
+                    source: configured
+                        .map_or(ConfigSource::Default, |(_, _, source)| source.clone()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// `$PQ_CONFIG`, else `$XDG_CONFIG_HOME/pq/config.toml`, else
+/// `~/.config/pq/config.toml`.
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_PATH_ENV) {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_home.join("pq").join("config.toml"))
+}
+
+/// Run `value` through the parser of the flag it is a default for. An output format
+/// only has to suit one subcommand; the others keep their own default.
+fn check(
+    command: &clap::Command,
+    setting: &Setting,
+    value: &str,
+) -> std::result::Result<(), String> {
+    if setting.global {
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == setting.arg)
+            .ok_or_else(|| format!("no --{} option", setting.arg.replace('_', "-")))?;
+        let long = arg.get_long().unwrap_or(setting.arg);
+        return clap::Command::new("pq")
+            .no_binary_name(true)
+            .arg(arg.clone())
+            .try_get_matches_from([format!("--{long}={value}")])
+            .map(drop)
+            .map_err(|error| {
+                let message = error.to_string();
+                let first_line = message.lines().next().unwrap_or_default();
+                first_line.trim_start_matches("error: ").to_string()
+            });
+    }
+
+    let mut known = Vec::new();
+    if accepted_anywhere(command, setting.arg, value, &mut known) {
+        return Ok(());
+    }
+    known.sort();
+    known.dedup();
+    Err(format!(
+        "'{value}' is not a known format; expected one of {}",
+        known.join(", ")
+    ))
+}
+
+fn accepted_anywhere(
+    command: &clap::Command,
+    id: &str,
+    value: &str,
+    known: &mut Vec<String>,
+) -> bool {
+    command.get_subcommands().any(|subcommand| {
+        let values = possible_values(subcommand, id);
+        let accepted = values.iter().any(|possible| possible.matches(value, false));
+        known.extend(
+            values
+                .iter()
+                .map(|possible| possible.get_name().to_string()),
+        );
+        accepted || accepted_anywhere(subcommand, id, value, known)
+    })
+}
+
+fn possible_values(command: &clap::Command, id: &str) -> Vec<clap::builder::PossibleValue> {
+    command
+        .get_arguments()
+        .find(|arg| arg.get_id() == id)
+        .map(clap::Arg::get_possible_values)
+        .unwrap_or_default()
+}
+
+/// Make `value` the default of `id` in every subcommand, at any depth, whose `id`
+/// accepts it.
+fn install_in_subcommands(mut command: clap::Command, id: &str, value: &str) -> clap::Command {
+    let names: Vec<String> = command
+        .get_subcommands()
+        .map(|subcommand| subcommand.get_name().to_string())
+        .collect();
+    for name in names {
+        command = command.mut_subcommand(name, |mut subcommand| {
+            if possible_values(&subcommand, id)
+                .iter()
+                .any(|possible| possible.matches(value, false))
+            {
+                subcommand = subcommand.mut_arg(id, |arg| arg.default_value(value.to_string()));
+            }
+            install_in_subcommands(subcommand, id, value)
+        });
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(text: &str) -> Result<Config> {
+        let mut config = Config::default();
+        config.read_file(std::path::Path::new("config.toml"), text)?;
+        Ok(config)
+    }
+
+    #[test]
+    fn reads_strings_and_integers() -> Result<()> {
+        let config = config("output = \"jsonl\"\nfloat_precision = 2\n")?;
+        let settings = config.settings();
+        assert_eq!(settings[0].value.as_deref(), Some("jsonl"));
+        assert_eq!(settings[1].value.as_deref(), Some("2"));
+        assert_eq!(settings[2].source, ConfigSource::Default);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let error = config("colour = \"never\"\n")
+            .err()
+            .map(|error| error.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("unknown setting 'colour'"));
+    }
+
+    #[test]
+    fn checks_values_against_their_flags() {
+        let command = Cli::command();
+        let check = |key: &str, value: &str| {
+            SETTINGS
+                .iter()
+                .find(|setting| setting.key == key)
+                .map(|setting| check(&command, setting, value))
+        };
+        assert_eq!(check("output", "parquet"), Some(Ok(())));
+        assert!(matches!(check("output", "xml"), Some(Err(_))));
+        assert_eq!(check("float_precision", "3"), Some(Ok(())));
+        assert!(matches!(check("float_precision", "99"), Some(Err(_))));
+        assert!(matches!(check("timestamp_format", "%Q"), Some(Err(_))));
+    }
+}
@@ -1,31 +1,100 @@
-use crate::cli::args::Command;
+use crate::cli::args::{Command, InputArgs};
+use crate::cli::config::Config;
 use crate::dataset::Dataset;
-use crate::Result;
+use crate::model::ColumnStats;
+use crate::output::{self, DisplayOptions, OutputFormat};
+use crate::path_text::display_path;
+use crate::{warning, Result};
+use std::io::Write;
 use std::path::Path;
 
+mod bench;
+mod checksum;
+mod completions;
+mod config;
 mod convert;
 mod count;
+mod describe;
+mod exists;
+mod extract;
+mod grep;
+mod hist;
 mod info;
 mod merge;
+mod meta;
+mod nulls;
+mod prune_check;
+mod sample;
 mod scan;
 mod schema;
+mod select;
 mod stats;
+mod stats_diff;
+mod transform;
+mod watch;
 
-pub(crate) fn run(command: Command) -> Result<()> {
+pub(crate) use completions::{columns_helper_input, complete_columns};
+
+pub(crate) fn run(
+    command: Command,
+    display: &DisplayOptions,
+    input: &InputArgs,
+    config: &Config,
+) -> Result<()> {
+    let inputs = input.options();
     match command {
-        Command::Schema(args) => schema::run(args),
-        Command::Head(args) => scan::run_head(args),
-        Command::Tail(args) => scan::run_tail(args),
-        Command::Count(args) => count::run(args),
-        Command::Stats(args) => stats::run(args),
-        Command::Convert(args) => convert::run(args),
-        Command::Merge(args) => merge::run(args),
-        Command::Info(args) => info::run(args),
+        Command::Schema(args) => schema::run(args, &inputs),
+        Command::Head(args) => scan::run_head(args, display, &inputs),
+        Command::Tail(args) => scan::run_tail(args, display, &inputs),
+        Command::Select(args) => select::run(args, display, &inputs),
+        Command::Extract(args) => extract::run(args, display, &inputs),
+        Command::Sample(args) => sample::run(args, display, &inputs),
+        Command::Grep(args) => grep::run(args, display, &inputs),
+        Command::Count(args) => count::run(args, &inputs),
+        Command::Stats(args) => stats::run(args, display, &inputs),
+        Command::StatsDiff(args) => stats_diff::run(args),
+        Command::Nulls(args) => nulls::run(args, &inputs),
+        Command::Hist(args) => hist::run(args, &inputs),
+        Command::Checksum(args) => checksum::run(args, &inputs),
+        Command::PruneCheck(args) => prune_check::run(args, &inputs),
+        Command::Exists(args) => exists::run(args, &inputs),
+        Command::Convert(args) => convert::run(args, display, &inputs),
+        Command::Merge(args) => merge::run(args, &inputs),
+        Command::Transform(args) => transform::run(args, &inputs),
+        Command::Meta(args) => meta::run(args, &inputs),
+        Command::Info(args) => info::run(args, display, &inputs),
+        Command::Describe(args) => describe::run(args, display, &inputs),
+        Command::Bench(args) => bench::run(args, &inputs),
+        Command::Completions(args) => completions::run(args),
+        Command::Watch(args) => watch::run(args, display, input, config),
+        Command::Config(args) => config::run(args, config),
     }
 }
 
-fn print_source_header(dataset: &Dataset, path: &Path, quiet: bool) {
+fn print_source_header(dataset: &Dataset, path: &Path, quiet: bool) -> Result<()> {
     if dataset.is_multi_source() && !quiet {
-        println!("==> {} <==", path.display());
+        let path = display_path(path);
+        writeln!(output::stdout(), "==> {path} <==")?;
+    }
+    Ok(())
+}
+
+/// Statistics that cannot be right are still shown as written, so say which.
+fn warn_suspicious_statistics(path: &Path, stats: &ColumnStats) -> Result<()> {
+    for problem in &stats.problems {
+        warning::warn(format!(
+            "{}: column '{}' has suspicious statistics: {problem}",
+            display_path(path),
+            stats.column
+        ))?;
+    }
+    Ok(())
+}
+
+/// Checked before any reading, so a refused run does no work.
+fn check_binary_output(format: OutputFormat, force: bool) -> Result<()> {
+    if let Some(name) = format.binary_name() {
+        output::ensure_binary_stdout(name, force)?;
     }
+    Ok(())
 }
@@ -0,0 +1,38 @@
+//! Bench command: time an operation over repeated in-process runs
+
+use crate::api;
+use crate::cli::args::BenchArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::BenchOptions;
+use crate::{output, Result};
+
+pub fn run(args: BenchArgs, input_options: &InputOptions) -> Result<()> {
+    let options = BenchOptions {
+        operation: args.operation(),
+        iterations: usize::try_from(args.iterations).unwrap_or(usize::MAX),
+        warmup: usize::try_from(args.warmup).unwrap_or(usize::MAX),
+    };
+    let BenchArgs {
+        operation: _,
+        inputs,
+        rows: _,
+        iterations: _,
+        warmup: _,
+        output,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let result = api::bench(&dataset, &options)?;
+
+    let output_format: output::OutputFormat = output.into();
+    match output_format.structured() {
+        Some(structured_output) => output::write_bench_result(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &result,
+        ),
+        None => output::write_bench_table(no_header, &result),
+    }
+}
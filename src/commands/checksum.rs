@@ -0,0 +1,42 @@
+//! Logical data checksum command
+
+use crate::api;
+use crate::cli::args::ChecksumArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::ChecksumOptions;
+use crate::{commands, output, Result};
+
+pub fn run(args: ChecksumArgs, input_options: &InputOptions) -> Result<()> {
+    let ChecksumArgs {
+        inputs,
+        by_column,
+        algorithm,
+        output,
+        quiet,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let options = ChecksumOptions {
+        algorithm: algorithm.into(),
+        by_column,
+    };
+    let results = api::checksum(&dataset, &options)?;
+
+    let output_format: output::OutputFormat = output.into();
+    match output_format.structured() {
+        Some(structured_output) => output::write_checksum_results(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &results,
+        ),
+        None => {
+            for result in &results {
+                commands::print_source_header(&dataset, &result.path, quiet)?;
+                output::write_checksum_table(no_header, result)?;
+            }
+            Ok(())
+        }
+    }
+}
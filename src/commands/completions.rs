@@ -0,0 +1,101 @@
+//! Shell completion scripts
+
+use crate::cli::args::{Cli, CompletionsArgs, ShellArg};
+use crate::Result;
+use crate::{api, output};
+use clap::CommandFactory;
+use std::ffi::OsString;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Hidden helper the completion scripts call to list a file's columns. It stays out of
+/// the clap command so static completions never offer it.
+const COLUMNS_HELPER: &str = "__complete-columns";
+
+/// Value names of options that take column names, as clap renders them in zsh specs.
+const COLUMN_VALUE_NAMES: [&str; 4] = ["COLUMN", "COLUMNS", "EXCLUDE", "BY"];
+
+pub fn run(args: CompletionsArgs) -> Result<()> {
+    let mut script = Vec::new();
+    clap_complete::generate(
+        clap_complete::Shell::from(args.shell),
+        &mut Cli::command(),
+        "pq",
+        &mut script,
+    );
+    let script = String::from_utf8_lossy(&script);
+
+    let script = match args.shell {
+        ShellArg::Bash => script.into_owned(),
+        ShellArg::Zsh => zsh_with_column_hooks(&script),
+        ShellArg::Fish => format!("{script}{FISH_COLUMN_HOOKS}"),
+    };
+    let mut stdout = output::stdout();
+    stdout.write_all(script.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// `pq __complete-columns <file>`, recognized before clap parsing.
+pub(crate) fn columns_helper_input(args: impl IntoIterator<Item = OsString>) -> Option<PathBuf> {
+    let mut args = args.into_iter().skip(1);
+    if args.next()? != COLUMNS_HELPER {
+        return None;
+    }
+    let input = args.next()?;
+    args.next().is_none().then(|| PathBuf::from(input))
+}
+
+pub(crate) fn complete_columns(input: &Path) -> Result<()> {
+    let mut stdout = output::stdout();
+    for name in api::column_names(input)? {
+        writeln!(stdout, "{name}")?;
+    }
+    Ok(())
+}
+
+/// Point zsh's column-valued options at `_pq_columns` instead of file completion.
+fn zsh_with_column_hooks(script: &str) -> String {
+    let mut script = COLUMN_VALUE_NAMES
+        .iter()
+        .fold(script.to_string(), |script, name| {
+            script.replace(
+                &format!("]:{name}:_default'"),
+                &format!("]:{name}:_pq_columns'"),
+            )
+        });
+    script.push_str(ZSH_COLUMN_HOOKS);
+    script
+}
+
+const ZSH_COLUMN_HOOKS: &str = r#"
+(( $+functions[_pq_columns] )) ||
+_pq_columns() {
+    local word
+    local -a columns
+    for word in ${words[@]}; do
+        if [[ $word == *.parquet && -f $word ]]; then
+            columns=(${(f)"$(pq __complete-columns $word 2>/dev/null)"})
+            compset -P '*,'
+            compadd -S '' -a columns
+            return
+        fi
+    done
+    _message 'column name'
+}
+"#;
+
+const FISH_COLUMN_HOOKS: &str = r#"
+function __fish_pq_columns
+    for token in (commandline -opc)
+        if string match -q -- '*.parquet' $token; and test -f $token
+            pq __complete-columns $token 2>/dev/null
+            return
+        end
+    end
+end
+complete -c pq -n "__fish_pq_using_subcommand grep convert" -s c -l columns -f -a "(__fish_pq_columns)"
+complete -c pq -n "__fish_pq_using_subcommand convert" -l exclude -f -a "(__fish_pq_columns)"
+complete -c pq -n "__fish_pq_using_subcommand count" -l by -f -a "(__fish_pq_columns)"
+complete -c pq -n "__fish_pq_using_subcommand stats exists info" -s c -l column -f -a "(__fish_pq_columns)"
+"#;
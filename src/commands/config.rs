@@ -0,0 +1,31 @@
+//! Config command: report option defaults and where they came from
+
+use crate::cli::args::{ConfigArgs, ConfigCommand, ConfigShowArgs};
+use crate::cli::config::Config;
+use crate::{output, Result};
+
+pub fn run(args: ConfigArgs, config: &Config) -> Result<()> {
+    match args.command {
+        ConfigCommand::Show(args) => show(args, config),
+    }
+}
+
+fn show(args: ConfigShowArgs, config: &Config) -> Result<()> {
+    let ConfigShowArgs {
+        output,
+        quiet: _,
+        no_header,
+        csv,
+    } = args;
+    let output_format: output::OutputFormat = output.into();
+    let settings = config.settings();
+
+    match output_format.structured() {
+        Some(structured_output) => output::write_config_results(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &settings,
+        ),
+        None => output::write_config_table(no_header, &settings),
+    }
+}
@@ -1,11 +1,149 @@
 //! Format conversion command
 
 use crate::api;
+use crate::atomic_output::check_destination;
 use crate::cli::args::ConvertArgs;
-use crate::dataset::InputFile;
+use crate::dataset::{Dataset, InputFile, InputOptions};
+use crate::error::PqError;
+use crate::model::ConvertOptions;
+use crate::output::{self, DisplayOptions, FileOutput, FileOutputFormat, OutputFormat};
 use crate::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
 
-pub fn run(args: ConvertArgs) -> Result<()> {
-    let input = InputFile::from_input(args.input)?;
-    api::convert(input.path(), args.output_path.as_path())
+pub fn run(
+    args: ConvertArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    let options = ConvertOptions {
+        columns: args.columns,
+        exclude: args.exclude,
+        limit: args.limit,
+        reshape: args.reshape.options(),
+        column_case: input_options.column_case,
+        batch_size: Some(input_options.batch_size),
+        write: args.write.options(),
+    };
+    let csv = args.csv.dialect(OutputFormat::Csv, args.no_header);
+    let format = args.format.map(Into::into);
+    let Some(output_path) = args.output_path else {
+        let out_dir = args.out_dir.unwrap_or_default();
+        let dataset = Dataset::from_inputs_with(vec![args.input], input_options)?;
+        let plan = plan_outputs(&dataset, &out_dir, format, args.compress, args.force)?;
+        let Some((_, first_output)) = plan.first() else {
+            return Err(PqError::NoInputFiles);
+        };
+        let encoding =
+            output::resolve_file_output(first_output, format, args.compress, csv, display.clone())?;
+        fs::create_dir_all(&out_dir).map_err(|error| PqError::write_error(&out_dir, error))?;
+        let jobs = usize::try_from(args.jobs).unwrap_or(usize::MAX);
+        let results = convert_all(&plan, &encoding, &options, jobs, args.skip_bad_files);
+        return finish_all(&plan, results, args.skip_bad_files);
+    };
+
+    let input = InputFile::from_input(args.input, input_options)?;
+    let encoding =
+        output::resolve_file_output(&output_path, format, args.compress, csv, display.clone())?;
+    if !output::is_stdout_path(&output_path) {
+        check_destination(&output_path, &[input.path()], args.force)?;
+    } else if matches!(
+        encoding.format,
+        FileOutputFormat::Arrow | FileOutputFormat::Parquet
+    ) {
+        output::ensure_binary_stdout(encoding.format.as_str(), args.force)?;
+    }
+    api::convert(input.path(), output_path.as_path(), encoding, &options)
+}
+
+/// Each input's `<stem>.<format>` path under `out_dir`, checked before anything is
+/// written: two inputs may not share an output, and none may overwrite an input.
+fn plan_outputs(
+    dataset: &Dataset,
+    out_dir: &Path,
+    format: Option<FileOutputFormat>,
+    compress: Option<output::TextCompression>,
+    force: bool,
+) -> Result<Vec<(PathBuf, PathBuf)>> {
+    let extension = format.map_or("", FileOutputFormat::as_str);
+    let suffix = compress.map_or(String::new(), |codec| format!(".{}", codec.extension()));
+    let inputs: Vec<&Path> = dataset.paths().collect();
+    let mut plan = Vec::with_capacity(inputs.len());
+    let mut claimed: HashMap<PathBuf, &Path> = HashMap::new();
+    for input in &inputs {
+        let stem = input.file_stem().unwrap_or(input.as_os_str());
+        let mut name = stem.to_os_string();
+        name.push(format!(".{extension}{suffix}"));
+        let output = out_dir.join(name);
+        if let Some(first) = claimed.insert(output.clone(), input) {
+            return Err(PqError::output_collision(&output, first, input));
+        }
+        check_destination(&output, &inputs, force)?;
+        plan.push((input.to_path_buf(), output));
+    }
+    Ok(plan)
+}
+
+/// Results in input order; `None` for files never started because an earlier one failed.
+type Outcomes = Vec<Option<Result<()>>>;
+
+/// Convert on `jobs` threads, each taking the next file in turn. Unless `keep_going`,
+/// the first failure stops new files from starting.
+fn convert_all(
+    plan: &[(PathBuf, PathBuf)],
+    encoding: &FileOutput,
+    options: &ConvertOptions,
+    jobs: usize,
+    keep_going: bool,
+) -> Outcomes {
+    let next = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let results = Mutex::new((0..plan.len()).map(|_| None).collect::<Outcomes>());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(plan.len()) {
+            scope.spawn(|| {
+                while !stopped.load(Ordering::Relaxed) {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some((input, output)) = plan.get(index) else {
+                        break;
+                    };
+                    let result = api::convert(input, output, encoding.clone(), options);
+                    if result.is_err() && !keep_going {
+                        stopped.store(true, Ordering::Relaxed);
+                    }
+                    if let Ok(mut results) = results.lock() {
+                        results[index] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+    results.into_inner().unwrap_or_default()
+}
+
+fn finish_all(plan: &[(PathBuf, PathBuf)], results: Outcomes, skip_bad_files: bool) -> Result<()> {
+    let outcomes: Vec<_> = plan
+        .iter()
+        .zip(&results)
+        .filter_map(|((input, output), result)| {
+            let result = result.as_ref()?;
+            Some((input.as_path(), output.as_path(), result.as_ref().err()))
+        })
+        .collect();
+    output::write_convert_summary(&outcomes, plan.len())?;
+    let failed = outcomes
+        .iter()
+        .filter(|(_, _, error)| error.is_some())
+        .count();
+    if failed > 0 && !skip_bad_files {
+        return Err(PqError::ConvertFailed {
+            failed,
+            total: plan.len(),
+        });
+    }
+    Ok(())
 }
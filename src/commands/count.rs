@@ -2,11 +2,41 @@
 
 use crate::api;
 use crate::cli::args::CountArgs;
-use crate::dataset::Dataset;
-use crate::{output, Result};
+use crate::dataset::{Dataset, InputOptions};
+use crate::{output, GroupCountOptions, MatchCount, PqError, Result};
 
-pub fn run(args: CountArgs) -> Result<()> {
-    let dataset = Dataset::from_inputs(args.inputs)?;
-    let counts = api::count(&dataset)?;
-    output::write_counts(args.quiet, dataset.is_multi_source(), &counts)
+pub fn run(args: CountArgs, input_options: &InputOptions) -> Result<()> {
+    let dataset = Dataset::from_inputs_with(args.inputs, input_options)?;
+    if let Some(predicate) = &args.predicate {
+        if args.explain_pruning {
+            output::write_pruning_summary(&api::prune_check(&dataset, predicate)?)?;
+        }
+        let matches = api::count_where(&dataset, predicate)?;
+        if args.verbose {
+            output::write_match_sources(&matches)?;
+        }
+        let counts = matches.iter().map(MatchCount::entry).collect();
+        return output::write_counts(args.quiet, dataset.is_multi_source(), &counts);
+    }
+    if args.by.is_empty() {
+        let counts = api::count(&dataset)?;
+        output::write_counts(args.quiet, dataset.is_multi_source(), &counts)?;
+        if args.verify {
+            let checks = api::verify_count(&dataset)?;
+            if let Some(check) = checks.iter().find(|check| !check.matches()) {
+                return Err(PqError::row_count_mismatch(
+                    &check.path,
+                    check.metadata_rows,
+                    check.decoded_rows,
+                    check.column.as_deref(),
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    let options = GroupCountOptions { limit: args.limit };
+    let groups = api::count_by(&dataset, &args.by, options)?;
+    let dialect = args.csv.dialect(args.output.into(), args.no_header);
+    output::write_group_counts(args.output.into(), args.no_header, &dialect, &groups)
 }
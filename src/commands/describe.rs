@@ -0,0 +1,40 @@
+//! One-shot file summary command
+
+use crate::api;
+use crate::cli::args::DescribeArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::DisplayOptions;
+use crate::{commands, output, Result};
+
+pub fn run(
+    args: DescribeArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    let DescribeArgs {
+        inputs,
+        output,
+        quiet,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let output_format: output::OutputFormat = output.into();
+    let descriptions = api::describe(&dataset)?;
+
+    if let Some(structured_output) = output_format.structured() {
+        output::write_descriptions(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            display.binary,
+            &descriptions,
+        )
+    } else {
+        for description in &descriptions {
+            commands::print_source_header(&dataset, description.info.path(), quiet)?;
+            output::write_description_table(no_header, display.binary, description)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! Value existence check command
+
+use crate::api;
+use crate::cli::args::ExistsArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::ExistsOptions;
+use crate::{output, Result};
+
+pub fn run(args: ExistsArgs, input_options: &InputOptions) -> Result<()> {
+    let ExistsArgs {
+        inputs,
+        column,
+        value,
+        scan,
+        output,
+        quiet: _,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let options = ExistsOptions {
+        column,
+        value,
+        scan,
+    };
+    let results = api::exists(&dataset, &options)?;
+    output::write_exists_results(
+        output.into(),
+        no_header,
+        &csv.dialect(output.into(), no_header),
+        &results,
+    )
+}
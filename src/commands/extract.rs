@@ -0,0 +1,20 @@
+//! Single-column extraction command
+
+use crate::api;
+use crate::cli::args::ExtractArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::{self, DisplayOptions, ValueLines};
+use crate::Result;
+
+pub fn run(
+    args: ExtractArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    let dataset = Dataset::from_inputs_with(args.inputs, input_options)?;
+    let lines = ValueLines {
+        unique: args.unique,
+        null_value: (!args.no_nulls).then_some(args.null_value),
+    };
+    output::write_value_lines(api::extract(&dataset, &args.column), &lines, display)
+}
@@ -0,0 +1,40 @@
+//! Row search command
+
+use crate::api;
+use crate::cli::args::GrepArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::DisplayOptions;
+use crate::{commands, GrepOptions, Result};
+
+pub fn run(args: GrepArgs, display: &DisplayOptions, input_options: &InputOptions) -> Result<()> {
+    let dataset = Dataset::from_inputs_with(args.inputs, input_options)?;
+    let output_format = args.output.into();
+    commands::check_binary_output(output_format, args.force)?;
+    let options = GrepOptions {
+        pattern: args.pattern,
+        columns: args.columns,
+        regex: args.regex,
+        ignore_case: args.ignore_case,
+        max_matches: args.max_count,
+    };
+    let results = api::grep(&dataset, &options)?;
+    let matches: usize = results
+        .iter()
+        .flat_map(|result| &result.batches)
+        .map(|batch| batch.num_rows())
+        .sum();
+
+    commands::scan::write_results(
+        &dataset,
+        results,
+        output_format,
+        args.quiet,
+        args.no_header,
+        &args.csv.dialect(output_format, args.no_header),
+        display,
+    )?;
+    if !args.quiet {
+        eprintln!("{matches} matching rows");
+    }
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Histogram command
+
+use crate::api;
+use crate::cli::args::HistArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::HistogramOptions;
+use crate::{output, Result};
+
+pub fn run(args: HistArgs, input_options: &InputOptions) -> Result<()> {
+    let HistArgs {
+        inputs,
+        column,
+        buckets,
+        log_scale,
+        min,
+        max,
+        output,
+        quiet,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let options = HistogramOptions {
+        column,
+        buckets: usize::try_from(buckets).unwrap_or(usize::MAX),
+        log_scale,
+        min,
+        max,
+    };
+    let histogram = api::hist(&dataset, &options)?;
+    output::write_histogram(
+        output.into(),
+        no_header,
+        quiet,
+        &csv.dialect(output.into(), no_header),
+        &histogram,
+    )
+}
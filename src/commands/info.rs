@@ -2,24 +2,141 @@
 
 use crate::api;
 use crate::cli::args::InfoArgs;
-use crate::dataset::Dataset;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::PageIndexOptions;
+use crate::output::{CsvDialect, DisplayOptions, InfoNumbers};
 use crate::{commands, output, Result};
 
-pub fn run(args: InfoArgs) -> Result<()> {
+pub fn run(args: InfoArgs, display: &DisplayOptions, input_options: &InputOptions) -> Result<()> {
     let InfoArgs {
         inputs,
+        columns,
+        pages,
+        row_groups,
+        metadata,
+        column,
+        row_group,
+        raw_bytes,
         output,
         quiet,
+        no_header,
+        force: _,
+        csv,
     } = args;
-    let dataset = Dataset::from_inputs(inputs)?;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
     let output_format: output::OutputFormat = output.into();
+    let dialect = csv.dialect(output_format, no_header);
+    let numbers = InfoNumbers {
+        human_readable: display.human_readable,
+        raw_bytes,
+    };
+    if columns {
+        return run_columns(&dataset, output_format, quiet, no_header, numbers, &dialect);
+    }
+    if row_groups {
+        return run_row_groups(&dataset, output_format, quiet, no_header, numbers, &dialect);
+    }
+    if metadata {
+        return run_metadata(&dataset, output_format, quiet, no_header, &dialect);
+    }
+    if pages {
+        let options = PageIndexOptions { column, row_group };
+        return run_pages(
+            &dataset,
+            &options,
+            output_format,
+            quiet,
+            no_header,
+            &dialect,
+        );
+    }
+
     let infos = api::info(&dataset)?;
     if let Some(structured_output) = output_format.structured() {
-        output::write_file_infos(structured_output, quiet, &infos)
+        output::write_file_infos(structured_output, &dialect, &infos)
     } else {
         for info in &infos {
-            commands::print_source_header(&dataset, info.path(), quiet);
-            output::write_file_info(quiet, info)?;
+            commands::print_source_header(&dataset, info.path(), quiet)?;
+            output::write_file_info(no_header, numbers, info)?;
+        }
+        Ok(())
+    }
+}
+
+fn run_columns(
+    dataset: &Dataset,
+    output_format: output::OutputFormat,
+    quiet: bool,
+    no_header: bool,
+    numbers: InfoNumbers,
+    dialect: &CsvDialect,
+) -> Result<()> {
+    let results = api::column_chunks(dataset)?;
+    if let Some(structured_output) = output_format.structured() {
+        output::write_column_chunks(structured_output, dialect, &results)
+    } else {
+        for result in &results {
+            commands::print_source_header(dataset, &result.path, quiet)?;
+            output::write_column_chunks_table(no_header, numbers, &result.columns)?;
+        }
+        Ok(())
+    }
+}
+
+fn run_row_groups(
+    dataset: &Dataset,
+    output_format: output::OutputFormat,
+    quiet: bool,
+    no_header: bool,
+    numbers: InfoNumbers,
+    dialect: &CsvDialect,
+) -> Result<()> {
+    let results = api::row_groups(dataset)?;
+    if let Some(structured_output) = output_format.structured() {
+        output::write_row_groups(structured_output, dialect, &results)
+    } else {
+        for result in &results {
+            commands::print_source_header(dataset, &result.path, quiet)?;
+            output::write_row_groups_table(no_header, numbers, &result.row_groups)?;
+        }
+        Ok(())
+    }
+}
+
+fn run_metadata(
+    dataset: &Dataset,
+    output_format: output::OutputFormat,
+    quiet: bool,
+    no_header: bool,
+    dialect: &CsvDialect,
+) -> Result<()> {
+    let results = api::key_value_metadata(dataset)?;
+    if let Some(structured_output) = output_format.structured() {
+        output::write_key_value_metadata(structured_output, dialect, &results)
+    } else {
+        for result in &results {
+            commands::print_source_header(dataset, &result.path, quiet)?;
+            output::write_key_value_metadata_table(no_header, &result.entries)?;
+        }
+        Ok(())
+    }
+}
+
+fn run_pages(
+    dataset: &Dataset,
+    options: &PageIndexOptions,
+    output_format: output::OutputFormat,
+    quiet: bool,
+    no_header: bool,
+    dialect: &CsvDialect,
+) -> Result<()> {
+    let results = api::page_indexes(dataset, options)?;
+    if let Some(structured_output) = output_format.structured() {
+        output::write_page_indexes(structured_output, dialect, &results)
+    } else {
+        for result in &results {
+            commands::print_source_header(dataset, &result.path, quiet)?;
+            output::write_page_index_table(no_header, result)?;
         }
         Ok(())
     }
@@ -1,11 +1,42 @@
 //! File merging command
 
 use crate::api;
+use crate::atomic_output::check_destination;
 use crate::cli::args::MergeArgs;
-use crate::dataset::Dataset;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output;
+use crate::path_text::display_path;
 use crate::Result;
+use std::io::Write;
 
-pub fn run(args: MergeArgs) -> Result<()> {
-    let dataset = Dataset::from_inputs(args.inputs)?;
-    api::merge(&dataset, &args.output)
+pub fn run(args: MergeArgs, input_options: &InputOptions) -> Result<()> {
+    let sorted_merge = args.sorted_merge();
+    let dataset =
+        Dataset::from_inputs_with(args.inputs, &input_options.with_order(args.order.into()))?;
+    let inputs: Vec<_> = dataset.paths().collect();
+    // Footer row counts, for the summary once the output is written
+    let counts = api::count(&dataset)?;
+    if let Some(sort) = sorted_merge {
+        check_destination(&args.output, &inputs, args.force)?;
+        api::merge_sorted(&dataset, &args.output, &args.write.options(), &sort)?;
+        return output::write_merge_summary(&args.output, &counts, counts.total_rows, args.quiet);
+    }
+    if !args.append {
+        check_destination(&args.output, &inputs, args.force)?;
+        api::merge(&dataset, &args.output, &args.write.options())?;
+        return output::write_merge_summary(&args.output, &counts, counts.total_rows, args.quiet);
+    }
+
+    check_destination(&args.output, &inputs, true)?;
+    let summary = api::merge_append(&dataset, &args.output, &args.write.options())?;
+    writeln!(
+        output::stdout(),
+        "Appended {} rows to {}: {} existing, {} total",
+        summary.appended_rows,
+        display_path(&args.output),
+        summary.existing_rows,
+        summary.total_rows()
+    )?;
+    let total_rows = i64::try_from(summary.total_rows()).unwrap_or(i64::MAX);
+    output::write_merge_summary(&args.output, &counts, total_rows, args.quiet)
 }
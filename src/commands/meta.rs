@@ -0,0 +1,32 @@
+//! Footer key-value metadata editing command
+
+use crate::api;
+use crate::atomic_output::check_destination;
+use crate::cli::args::{MetaArgs, MetaCommand};
+use crate::dataset::{InputFile, InputOptions};
+use crate::model::MetadataEdit;
+use crate::Result;
+
+pub fn run(args: MetaArgs, input_options: &InputOptions) -> Result<()> {
+    let (input, edit, destination) = match args.command {
+        MetaCommand::Set(args) => (
+            args.input,
+            MetadataEdit::Set(args.entries),
+            args.destination,
+        ),
+        MetaCommand::Remove(args) => (
+            args.input,
+            MetadataEdit::Remove(args.keys),
+            args.destination,
+        ),
+    };
+    let input = InputFile::from_input(input, input_options)?;
+    let output = match destination.output {
+        Some(output) => {
+            check_destination(&output, &[input.path()], destination.force)?;
+            output
+        }
+        None => input.path().to_path_buf(),
+    };
+    api::edit_metadata(input.path(), &output, &edit)
+}
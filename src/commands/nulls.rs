@@ -0,0 +1,38 @@
+//! Null analysis command
+
+use crate::api;
+use crate::cli::args::NullsArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::NullsOptions;
+use crate::{commands, output, Result};
+
+pub fn run(args: NullsArgs, input_options: &InputOptions) -> Result<()> {
+    let NullsArgs {
+        inputs,
+        columns,
+        runs,
+        output,
+        quiet,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let results = api::nulls(&dataset, &NullsOptions { columns, runs })?;
+
+    let output_format: output::OutputFormat = output.into();
+    match output_format.structured() {
+        Some(structured_output) => output::write_nulls_results(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &results,
+        ),
+        None => {
+            for result in &results {
+                commands::print_source_header(&dataset, &result.path, quiet)?;
+                output::write_nulls_table(no_header, result)?;
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! Row-group pruning report command
+
+use crate::api;
+use crate::cli::args::PruneCheckArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::{commands, output, Result};
+
+pub fn run(args: PruneCheckArgs, input_options: &InputOptions) -> Result<()> {
+    let PruneCheckArgs {
+        inputs,
+        predicate,
+        output,
+        quiet,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    let output_format: output::OutputFormat = output.into();
+    let reports = api::prune_check(&dataset, &predicate)?;
+    for report in &reports {
+        for stats in report.row_groups.iter().flat_map(|group| &group.columns) {
+            commands::warn_suspicious_statistics(&report.path, stats)?;
+        }
+    }
+
+    if let Some(structured_output) = output_format.structured() {
+        output::write_prune_reports(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &reports,
+        )?;
+    } else {
+        for report in &reports {
+            commands::print_source_header(&dataset, &report.path, quiet)?;
+            output::write_prune_table(no_header, report)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Random sample command
+
+use crate::api;
+use crate::cli::args::SampleArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::{self, DisplayOptions};
+use crate::{commands, warning, Result, SampleOptions, MAX_STRATA};
+
+pub fn run(args: SampleArgs, display: &DisplayOptions, input_options: &InputOptions) -> Result<()> {
+    let dataset = Dataset::from_inputs_with(args.inputs, input_options)?;
+    let output_format = args.output.into();
+    commands::check_binary_output(output_format, args.force)?;
+    let options = SampleOptions {
+        rows: args.rows,
+        columns: args.columns,
+        seed: args.seed,
+        stratify: args.stratify,
+        min_per_group: args.min_per_group,
+    };
+    let result = api::sample(&dataset, &options)?;
+    let column = options.stratify.as_deref().unwrap_or_default();
+    if result.too_many_strata {
+        warning::warn(format!(
+            "--stratify {column}: more than {MAX_STRATA} distinct values, so the sample is uniform"
+        ))?;
+    }
+
+    commands::scan::write_rows(
+        &result.batches,
+        output_format,
+        args.no_header,
+        &args.csv.dialect(output_format, args.no_header),
+        display,
+    )?;
+    if !args.quiet {
+        output::write_strata_summary(column, &result.strata)?;
+    }
+    Ok(())
+}
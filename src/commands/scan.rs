@@ -1,79 +1,242 @@
 //! Head and tail commands
 
 use crate::api;
-use crate::cli::args::{HeadArgs, TailArgs};
-use crate::dataset::Dataset;
+use crate::cli::args::ScanArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::{CsvDialect, DisplayOptions, OutputFormat, StructuredOutputFormat};
+use crate::path_text::display_path;
 use crate::{commands, output, PqError, Result, ScanKind, ScanOptions, ScanResult};
+use arrow::array::RecordBatch;
 use arrow::datatypes::SchemaRef;
+use std::io::BufWriter;
 use std::path::PathBuf;
 
-pub fn run_head(args: HeadArgs) -> Result<()> {
-    run_scan(
-        args.inputs,
-        ScanKind::Head,
-        args.rows,
-        args.output.into(),
+pub fn run_head(
+    args: ScanArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    run_scan(ScanKind::Head, args, display, input_options)
+}
+
+pub fn run_tail(
+    args: ScanArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    run_scan(ScanKind::Tail, args, display, input_options)
+}
+
+fn run_scan(
+    kind: ScanKind,
+    mut args: ScanArgs,
+    display: &DisplayOptions,
+    input_options: &InputOptions,
+) -> Result<()> {
+    let inputs = std::mem::take(&mut args.inputs);
+    let dataset = Dataset::from_inputs_with(inputs, &input_options.with_order(args.order.into()))?;
+    let output_format = args.output.into();
+    let options = ScanOptions {
+        rows: args.rows,
+        columns: std::mem::take(&mut args.columns),
+        global: args.global,
+        filter: args.predicate.clone(),
+        row_groups: std::mem::take(&mut args.row_group),
+    };
+    commands::check_binary_output(output_format, args.force)?;
+    if let (true, Some(predicate)) = (args.explain_pruning, &args.predicate) {
+        output::write_pruning_summary(&api::prune_check(&dataset, predicate)?)?;
+    }
+    let streamed = output_format
+        .structured()
+        .filter(|_| args.sort_by.is_empty());
+    if let (ScanKind::Tail, Some(structured)) = (kind, streamed) {
+        return stream_tail(
+            &dataset,
+            &options,
+            &args,
+            output_format,
+            structured,
+            display,
+        );
+    }
+    let mut results = api::scan(&dataset, kind, &options)?;
+    if args.with_filename {
+        name_files(&mut results, output_format, &mut None)?;
+    }
+    if args.row_numbers {
+        number_rows(&dataset, &mut results, output_format, args.with_filename)?;
+    }
+    api::sort_rows(&mut results, &args.sort_by, dataset.column_case())?;
+    api::reshape(&mut results, &args.reshape.options())?;
+    write_results(
+        &dataset,
+        results,
+        output_format,
         args.quiet,
+        args.no_header,
+        &args.csv.dialect(output_format, args.no_header),
+        display,
     )
 }
 
-pub fn run_tail(args: TailArgs) -> Result<()> {
-    run_scan(
-        args.inputs,
-        ScanKind::Tail,
-        args.rows,
-        args.output.into(),
-        args.quiet,
+/// A tail can be most of a file, so structured output takes it a batch at a time,
+/// naming files, numbering rows, and reshaping as each batch arrives. `--sort-by`
+/// needs every row first, so it takes the collecting path instead.
+fn stream_tail(
+    dataset: &Dataset,
+    options: &ScanOptions,
+    args: &ScanArgs,
+    output_format: OutputFormat,
+    structured: StructuredOutputFormat,
+    display: &DisplayOptions,
+) -> Result<()> {
+    let reshape = args.reshape.options();
+    let mut file_column = None;
+    let mut first_schema = None;
+    let batches = api::scan_tail_batches(dataset, options)?
+        .map(|result| {
+            let mut results = [result?];
+            if args.with_filename {
+                name_files(&mut results, output_format, &mut file_column)?;
+            }
+            if args.row_numbers {
+                number_rows(dataset, &mut results, output_format, args.with_filename)?;
+            }
+            api::reshape(&mut results, &reshape)?;
+            let [result] = results;
+            check_schema(&mut first_schema, &result)?;
+            Ok(result.batches)
+        })
+        .flat_map(|batches: Result<Vec<RecordBatch>>| match batches {
+            Ok(batches) => batches.into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(error)],
+        });
+    output::write_structured_stream(
+        structured,
+        &args.csv.dialect(output_format, args.no_header),
+        display,
+        batches,
     )
 }
 
-fn run_scan(
-    inputs: Vec<std::path::PathBuf>,
-    kind: ScanKind,
-    rows: usize,
-    output_format: crate::output::OutputFormat,
+pub(super) fn write_results(
+    dataset: &Dataset,
+    results: Vec<ScanResult>,
+    output_format: OutputFormat,
     quiet: bool,
+    no_header: bool,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
 ) -> Result<()> {
-    let dataset = Dataset::from_inputs(inputs)?;
-    let results = api::scan(&dataset, kind, ScanOptions { rows })?;
-
-    if let Some(structured_output) = output_format.structured() {
+    if output_format != OutputFormat::Table {
         validate_compatible_schemas(&results)?;
         let batches = results
             .into_iter()
             .flat_map(|result| result.batches)
             .collect::<Vec<_>>();
-        output::write_structured_batches(structured_output, quiet, &batches)?;
+        write_rows(&batches, output_format, no_header, dialect, display)?;
     } else {
         for result in results {
-            commands::print_source_header(&dataset, &result.path, quiet);
-            output::write_table_batches(quiet, &result.batches)?;
+            commands::print_source_header(dataset, &result.path, quiet)?;
+            output::write_table_batches(no_header, display, &result.batches)?;
         }
     }
 
     Ok(())
 }
 
-fn validate_compatible_schemas(results: &[ScanResult]) -> Result<()> {
-    let mut first_schema: Option<(PathBuf, SchemaRef)> = None;
+/// Rows with one schema, as one table or stream whatever files they came from.
+pub(super) fn write_rows(
+    batches: &[RecordBatch],
+    output_format: OutputFormat,
+    no_header: bool,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
+) -> Result<()> {
+    match output_format.structured() {
+        Some(structured_output) => {
+            output::write_structured_batches(structured_output, dialect, display, batches)
+        }
+        None if output_format == OutputFormat::Table => {
+            output::write_table_batches(no_header, display, batches)
+        }
+        None => output::write_batches(BufWriter::new(output::stdout()), output_format, batches),
+    }
+}
+
+/// The column is named from the first schema, so every file's rows share it; `column`
+/// keeps the name across calls.
+fn name_files(
+    results: &mut [ScanResult],
+    output_format: OutputFormat,
+    column: &mut Option<String>,
+) -> Result<()> {
+    let column = match column {
+        Some(column) => column,
+        None => {
+            let Some(schema) = results
+                .iter()
+                .find_map(|result| result.batches.first())
+                .map(RecordBatch::schema)
+            else {
+                return Ok(());
+            };
+            column.insert(output::source_file_column(output_format, &schema)?)
+        }
+    };
+    for result in results {
+        result.batches = output::prepend_source_file(&result.batches, column, &result.path)?;
+    }
+    Ok(())
+}
 
+/// Tables name each file in a header, so only combined output in other formats needs a
+/// file column, and none when `--with-filename` already added one.
+fn number_rows(
+    dataset: &Dataset,
+    results: &mut [ScanResult],
+    output_format: OutputFormat,
+    with_filename: bool,
+) -> Result<()> {
+    let name_files =
+        !with_filename && dataset.is_multi_source() && output_format != OutputFormat::Table;
     for result in results {
-        for batch in &result.batches {
-            let schema = batch.schema();
-            if let Some((first_path, expected_schema)) = &first_schema {
-                if schema.as_ref() != expected_schema.as_ref() {
-                    return Err(PqError::SchemaMismatch {
-                        file1: first_path.display().to_string(),
-                        file2: result.path.display().to_string(),
-                        details: "Cannot combine scan results with different schemas for structured output"
+        let first_row = result.first_row.unwrap_or_default();
+        let file = name_files.then_some(result.path.as_path());
+        result.batches = output::number_rows(&result.batches, first_row, output_format, file)?;
+    }
+    Ok(())
+}
+
+fn validate_compatible_schemas(results: &[ScanResult]) -> Result<()> {
+    let mut first_schema = None;
+    for result in results {
+        check_schema(&mut first_schema, result)?;
+    }
+    Ok(())
+}
+
+/// `first_schema` holds the first batch's file and schema, which later batches must match.
+fn check_schema(
+    first_schema: &mut Option<(PathBuf, SchemaRef)>,
+    result: &ScanResult,
+) -> Result<()> {
+    for batch in &result.batches {
+        let schema = batch.schema();
+        if let Some((first_path, expected_schema)) = first_schema {
+            if schema.as_ref() != expected_schema.as_ref() {
+                return Err(PqError::SchemaMismatch {
+                    file1: display_path(first_path),
+                    file2: display_path(&result.path),
+                    details:
+                        "Cannot combine scan results with different schemas for structured output"
                             .to_string(),
-                    });
-                }
-            } else {
-                first_schema = Some((result.path.clone(), schema));
+                });
             }
+        } else {
+            *first_schema = Some((result.path.clone(), schema));
         }
     }
-
     Ok(())
 }
@@ -2,25 +2,63 @@
 
 use crate::api;
 use crate::cli::args::SchemaArgs;
-use crate::dataset::Dataset;
-use crate::{commands, output, Result};
+use crate::dataset::{Dataset, InputOptions};
+use crate::{commands, output, PqError, Result};
 
-pub fn run(args: SchemaArgs) -> Result<()> {
+pub fn run(args: SchemaArgs, input_options: &InputOptions) -> Result<()> {
     let SchemaArgs {
         inputs,
+        ddl,
+        unify,
+        extended,
         output,
         quiet,
+        no_header,
+        force: _,
+        csv,
     } = args;
-    let dataset = Dataset::from_inputs(inputs)?;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
+    if let Some(dialect) = ddl {
+        for result in api::schema_ddl(&dataset, dialect.into())? {
+            commands::print_source_header(&dataset, &result.path, quiet)?;
+            output::write_ddl(&result.ddl)?;
+        }
+        return Ok(());
+    }
+
     let output_format: output::OutputFormat = output.into();
+    if unify {
+        let unified = api::unify_schemas(&dataset)?;
+        match output_format.structured() {
+            Some(structured_output) => output::write_unified_schema(
+                structured_output,
+                &csv.dialect(output_format, no_header),
+                &unified,
+            )?,
+            None => output::write_unified_schema_table(no_header, &unified)?,
+        }
+        if !unified.is_compatible() {
+            return Err(PqError::incompatible_schemas(
+                unified.files.len(),
+                unified.incompatible_columns(),
+            ));
+        }
+        return Ok(());
+    }
+
     let results = api::schema(&dataset)?;
 
     if let Some(structured_output) = output_format.structured() {
-        output::write_schema_results(structured_output, quiet, &results)?;
+        output::write_schema_results(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &results,
+            extended,
+        )?;
     } else {
         for result in results {
-            commands::print_source_header(&dataset, &result.path, quiet);
-            output::write_schema_table(quiet, &result.columns)?;
+            commands::print_source_header(&dataset, &result.path, quiet)?;
+            output::write_schema_table(no_header, &result.columns, extended)?;
         }
     }
 
@@ -0,0 +1,29 @@
+//! Field path selection command
+
+use crate::api;
+use crate::cli::args::SelectArgs;
+use crate::dataset::{Dataset, InputOptions};
+use crate::output::DisplayOptions;
+use crate::{commands, Result, SelectOptions};
+
+pub fn run(args: SelectArgs, display: &DisplayOptions, input_options: &InputOptions) -> Result<()> {
+    let dataset = Dataset::from_inputs_with(args.inputs, input_options)?;
+    let output_format = args.output.into();
+    commands::check_binary_output(output_format, args.force)?;
+    let options = SelectOptions {
+        expression: args.expression,
+        rows: args.rows,
+        flatten: args.flatten,
+    };
+    let results = api::select(&dataset, &options)?;
+
+    commands::scan::write_results(
+        &dataset,
+        results,
+        output_format,
+        args.quiet,
+        args.no_header,
+        &args.csv.dialect(output_format, args.no_header),
+        display,
+    )
+}
@@ -2,28 +2,103 @@
 
 use crate::api;
 use crate::cli::args::StatsArgs;
-use crate::dataset::Dataset;
-use crate::{commands, output, Result};
+use crate::dataset::{Dataset, InputOptions};
+use crate::model::StatsResult;
+use crate::output::DisplayOptions;
+use crate::path_text::display_path;
+use crate::{commands, output, warning, Result};
 
-pub fn run(args: StatsArgs) -> Result<()> {
+pub fn run(args: StatsArgs, display: &DisplayOptions, input_options: &InputOptions) -> Result<()> {
     let StatsArgs {
         inputs,
-        column,
+        columns,
+        by_row_group,
+        row_group,
+        sort_by,
+        value_width,
+        full_values,
         output,
         quiet,
+        no_header,
+        force: _,
+        csv,
     } = args;
-    let dataset = Dataset::from_inputs(inputs)?;
+    let dataset = Dataset::from_inputs_with(inputs, input_options)?;
     let output_format: output::OutputFormat = output.into();
-    let results = api::stats(&dataset, column.as_deref())?;
+    let value_width = (!full_values).then(|| usize::try_from(value_width).unwrap_or(usize::MAX));
+    if by_row_group {
+        let results = api::stats_by_row_group(&dataset, &columns, &row_group)?;
+        for result in &results {
+            for row in &result.rows {
+                commands::warn_suspicious_statistics(&result.path, &row.stats)?;
+            }
+        }
+        if let Some(structured_output) = output_format.structured() {
+            output::write_row_group_stats_results(
+                structured_output,
+                &csv.dialect(output_format, no_header),
+                display.binary,
+                &results,
+            )?;
+        } else {
+            for result in results {
+                commands::print_source_header(&dataset, &result.path, quiet)?;
+                output::write_row_group_stats_table(
+                    no_header,
+                    display.binary,
+                    value_width,
+                    &result.rows,
+                )?;
+            }
+        }
+        return Ok(());
+    }
+
+    let mut results = api::stats(&dataset, &columns, &row_group)?;
+    warn_missing_statistics(&results)?;
+    for result in &results {
+        for row in &result.rows {
+            commands::warn_suspicious_statistics(&result.path, row)?;
+        }
+    }
+    if let Some(order) = sort_by {
+        for result in &mut results {
+            result.sort_rows(order.into());
+        }
+    }
 
     if let Some(structured_output) = output_format.structured() {
-        output::write_stats_results(structured_output, quiet, &results)?;
+        output::write_stats_results(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            display.binary,
+            &results,
+        )?;
     } else {
         for result in results {
-            commands::print_source_header(&dataset, &result.path, quiet);
-            output::write_stats_table(quiet, &result.rows)?;
+            commands::print_source_header(&dataset, &result.path, quiet)?;
+            output::write_stats_table(no_header, display.binary, value_width, &result)?;
         }
     }
 
     Ok(())
 }
+
+/// Merged statistics quietly leave out row groups written without them, so say so.
+fn warn_missing_statistics(results: &[StatsResult]) -> Result<()> {
+    for result in results {
+        for row in &result.rows {
+            let missing = row.row_groups_without_statistics;
+            if missing > 0 {
+                warning::warn(format!(
+                    "{}: column '{}' has no statistics in {missing} row group{}; its null count, min, and max leave {}",
+                    display_path(&result.path),
+                    row.column,
+                    if missing == 1 { "" } else { "s" },
+                    if missing == 1 { "it out" } else { "them out" },
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+//! Column statistics comparison command
+
+use crate::api;
+use crate::cli::args::StatsDiffArgs;
+use crate::{output, PqError, Result};
+
+pub fn run(args: StatsDiffArgs) -> Result<()> {
+    let StatsDiffArgs {
+        old,
+        new,
+        tolerance,
+        output,
+        no_header,
+        force: _,
+        csv,
+    } = args;
+    let diff = api::stats_diff(&old, &new, tolerance)?;
+
+    let output_format: output::OutputFormat = output.into();
+    match output_format.structured() {
+        Some(structured_output) => output::write_stats_diff(
+            structured_output,
+            &csv.dialect(output_format, no_header),
+            &diff,
+        )?,
+        None => output::write_stats_diff_table(no_header, &diff)?,
+    }
+
+    match (diff.exceeding(), tolerance) {
+        (changes, Some(tolerance)) if changes > 0 => {
+            Err(PqError::stats_out_of_tolerance(changes, tolerance))
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,18 @@
+//! Column transform command
+
+use crate::api;
+use crate::atomic_output::check_destination;
+use crate::cli::args::TransformArgs;
+use crate::dataset::{InputFile, InputOptions};
+use crate::Result;
+
+pub fn run(args: TransformArgs, input_options: &InputOptions) -> Result<()> {
+    let input = InputFile::from_input(args.input.clone(), input_options)?;
+    check_destination(&args.output, &[input.path()], args.force)?;
+    api::transform(
+        input.path(),
+        &args.output,
+        &args.options(input_options),
+        &args.write.options(),
+    )
+}
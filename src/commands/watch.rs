@@ -0,0 +1,219 @@
+//! Watch command: re-run another command when its inputs change
+
+use crate::cli::args::{Cli, Command, InputArgs, WatchArgs};
+use crate::cli::config::Config;
+use crate::dataset::{expand_braces, is_glob_pattern};
+use crate::error::PqError;
+use crate::output::DisplayOptions;
+use crate::path_text::display_path;
+use crate::Result;
+use clap::FromArgMatches;
+use glob::Pattern;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// How often, and how many times, to retry a run whose input is briefly missing, as
+/// happens while a writer swaps in a new file.
+const MISSING_INPUT_RETRY: Duration = Duration::from_millis(50);
+const MISSING_INPUT_ATTEMPTS: usize = 20;
+
+enum Signal {
+    Changed,
+    Interrupted,
+}
+
+/// A directory to watch and the paths inside it that trigger a re-run.
+struct WatchTarget {
+    dir: PathBuf,
+    pattern: Pattern,
+    mode: RecursiveMode,
+}
+
+pub fn run(
+    args: WatchArgs,
+    display: &DisplayOptions,
+    input: &InputArgs,
+    config: &Config,
+) -> Result<()> {
+    let watched = match parse(&args.command, config) {
+        Ok(watched) => watched,
+        // `--help` and `--version` for the wrapped command are answers, not failures
+        Err(error) if !error.use_stderr() => {
+            error.print()?;
+            return Ok(());
+        }
+        Err(error) => return Err(PqError::invalid_watch_command(&args.command, &error)),
+    };
+    if matches!(
+        watched.command,
+        Command::Watch(_) | Command::Completions(_) | Command::Config(_)
+    ) {
+        return Err(PqError::watch_error(format!(
+            "'{}' has no input files to watch",
+            args.command[0]
+        )));
+    }
+    // Display options given before `watch` apply unless the wrapped command sets its own
+    let display = watched.display.options().or(display);
+    let input = watched.input.or(input);
+    let targets = watch_targets(&watched.command.inputs(), !input.no_recursive)?;
+
+    let (sender, receiver) = mpsc::channel();
+    let interrupt = sender.clone();
+    ctrlc::set_handler(move || {
+        let _ = interrupt.send(Signal::Interrupted);
+    })
+    .map_err(PqError::watch_error)?;
+
+    let patterns: Vec<Pattern> = targets
+        .iter()
+        .map(|target| target.pattern.clone())
+        .collect();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        let relevant = !matches!(event.kind, EventKind::Access(_))
+            && event
+                .paths
+                .iter()
+                .any(|path| patterns.iter().any(|pattern| pattern.matches_path(path)));
+        if relevant {
+            let _ = sender.send(Signal::Changed);
+        }
+    })
+    .map_err(PqError::watch_error)?;
+    for target in &targets {
+        watcher.watch(&target.dir, target.mode).map_err(|error| {
+            PqError::watch_error(format!("{}: {error}", display_path(&target.dir)))
+        })?;
+    }
+
+    let title = format!("pq {}", args.command.join(" "));
+    let debounce = Duration::from_millis(args.debounce);
+    loop {
+        run_once(&title, &args.command, &display, &input, config)?;
+
+        match receiver.recv() {
+            Ok(Signal::Changed) => {}
+            Ok(Signal::Interrupted) | Err(_) => return Ok(()),
+        }
+        // Let a burst of writes settle before running again
+        loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(Signal::Changed) => {}
+                Ok(Signal::Interrupted) | Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                Err(RecvTimeoutError::Timeout) => break,
+            }
+        }
+    }
+}
+
+fn run_once(
+    title: &str,
+    command: &[String],
+    display: &DisplayOptions,
+    input: &InputArgs,
+    config: &Config,
+) -> Result<()> {
+    let mut stdout = io::stdout().lock();
+    if stdout.is_terminal() {
+        write!(stdout, "\x1b[2J\x1b[H")?;
+    }
+    writeln!(
+        stdout,
+        "Every change: {title}    {}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+    )?;
+    stdout.flush()?;
+    drop(stdout);
+
+    for attempt in 1..=MISSING_INPUT_ATTEMPTS {
+        // The command parsed before watching began, so this cannot fail
+        let Ok(cli) = parse(command, config) else {
+            return Ok(());
+        };
+        match super::run(cli.command, display, input, config) {
+            Err(
+                PqError::FileNotFound { .. }
+                | PqError::NoFilesMatched { .. }
+                | PqError::NoFilesInDirectory { .. },
+            ) if attempt < MISSING_INPUT_ATTEMPTS => {
+                thread::sleep(MISSING_INPUT_RETRY);
+            }
+            // Nobody is left to read the next refresh
+            Err(error) if error.is_broken_pipe() => return Err(error),
+            // A failed run is reported, and the next change tries again
+            Err(error) => {
+                eprintln!("error: {error}");
+                break;
+            }
+            Ok(()) => break,
+        }
+    }
+    io::stdout().flush()?;
+    Ok(())
+}
+
+fn parse(command: &[String], config: &Config) -> std::result::Result<Cli, clap::Error> {
+    let matches = config
+        .subcommand_defaults()
+        .try_get_matches_from(std::iter::once("pq").chain(command.iter().map(String::as_str)))?;
+    Cli::from_arg_matches(&matches)
+}
+
+fn watch_targets(inputs: &[PathBuf], recursive: bool) -> Result<Vec<WatchTarget>> {
+    if inputs.is_empty() {
+        return Err(PqError::NoInputFiles);
+    }
+    let mut targets = Vec::new();
+    for input in inputs {
+        let input = std::path::absolute(input).map_err(PqError::watch_error)?;
+        if is_glob_pattern(&input) {
+            // One target per brace-set piece, each watched from its own literal directory
+            for piece in expand_braces(&input.to_string_lossy()) {
+                let dir = literal_prefix(Path::new(&piece));
+                // A wildcard short of the last component can match files in subdirectories
+                let nested = Path::new(&piece).components().count() > dir.components().count() + 1;
+                let mode = if nested {
+                    RecursiveMode::Recursive
+                } else {
+                    RecursiveMode::NonRecursive
+                };
+                targets.push(watch_target(dir, piece, mode)?);
+            }
+        } else if recursive && input.is_dir() {
+            let pattern = format!("{}/**", Pattern::escape(&input.to_string_lossy()));
+            targets.push(watch_target(input, pattern, RecursiveMode::Recursive)?);
+        } else {
+            let dir = input.parent().map(Path::to_path_buf).unwrap_or_default();
+            let pattern = Pattern::escape(&input.to_string_lossy());
+            targets.push(watch_target(dir, pattern, RecursiveMode::NonRecursive)?);
+        }
+    }
+    Ok(targets)
+}
+
+fn watch_target(dir: PathBuf, pattern: String, mode: RecursiveMode) -> Result<WatchTarget> {
+    let pattern =
+        Pattern::new(&pattern).map_err(|error| PqError::invalid_glob_pattern(&pattern, error))?;
+    Ok(WatchTarget { dir, pattern, mode })
+}
+
+/// The directory holding a glob's first wildcard component.
+fn literal_prefix(pattern: &Path) -> PathBuf {
+    let mut dir = PathBuf::new();
+    for component in pattern.components() {
+        if let Component::Normal(part) = component {
+            if is_glob_pattern(Path::new(part)) {
+                break;
+            }
+        }
+        dir.push(component);
+    }
+    dir
+}
@@ -1,4 +1,8 @@
+mod braces;
+mod order;
 mod source;
 
-pub use source::Dataset;
-pub(crate) use source::InputFile;
+pub(crate) use braces::expand_braces;
+pub use order::InputOrder;
+pub(crate) use source::{is_glob_pattern, InputFile};
+pub use source::{Dataset, InputOptions};
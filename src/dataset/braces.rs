@@ -0,0 +1,113 @@
+//! Brace sets in input patterns, expanded before globbing
+//!
+//! The glob crate has no `{a,b}` syntax, and whether a shell expands one depends on the
+//! shell and on quoting, so pq expands brace sets itself: `data/{2023,2024}/*.parquet`
+//! becomes `data/2023/*.parquet` then `data/2024/*.parquet`. Sets nest and expand left
+//! to right. As in bash, a set needs a comma at its top level, so `{}`, `{x}`, and
+//! unbalanced braces stay literal.
+
+/// Every pattern `pattern`'s brace sets spell out, in order and without repeats; the
+/// pattern itself when it has none.
+pub(crate) fn expand_braces(pattern: &str) -> Vec<String> {
+    let mut expanded = Vec::new();
+    expand_into(pattern, &mut expanded);
+    expanded
+}
+
+fn expand_into(pattern: &str, expanded: &mut Vec<String>) {
+    let Some(set) = first_set(pattern) else {
+        if !expanded.iter().any(|seen| seen == pattern) {
+            expanded.push(pattern.to_string());
+        }
+        return;
+    };
+    let prefix = &pattern[..set.open];
+    let suffix = &pattern[set.close + 1..];
+    for alternative in set.alternatives(pattern) {
+        expand_into(&format!("{prefix}{alternative}{suffix}"), expanded);
+    }
+}
+
+/// A `{...}` span and the positions of the commas at its top level.
+struct BraceSet {
+    open: usize,
+    close: usize,
+    commas: Vec<usize>,
+}
+
+impl BraceSet {
+    fn alternatives<'a>(&self, pattern: &'a str) -> Vec<&'a str> {
+        let starts = std::iter::once(self.open).chain(self.commas.iter().copied());
+        let ends = self
+            .commas
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.close));
+        starts
+            .zip(ends)
+            .map(|(start, end)| &pattern[start + 1..end])
+            .collect()
+    }
+}
+
+/// The leftmost brace set that expands: balanced, with at least one top-level comma.
+fn first_set(pattern: &str) -> Option<BraceSet> {
+    let bytes = pattern.as_bytes();
+    (0..bytes.len())
+        .filter(|&open| bytes[open] == b'{')
+        .find_map(|open| {
+            let mut depth = 0usize;
+            let mut commas = Vec::new();
+            for (index, &byte) in bytes.iter().enumerate().skip(open + 1) {
+                match byte {
+                    b'{' => depth += 1,
+                    b'}' if depth == 0 => {
+                        return (!commas.is_empty()).then_some(BraceSet {
+                            open,
+                            close: index,
+                            commas,
+                        });
+                    }
+                    b'}' => depth -= 1,
+                    b',' if depth == 0 => commas.push(index),
+                    _ => {}
+                }
+            }
+            None
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_sets_left_to_right() {
+        assert_eq!(
+            expand_braces("data/{2023,2024}/**/*.parquet"),
+            ["data/2023/**/*.parquet", "data/2024/**/*.parquet"]
+        );
+        assert_eq!(
+            expand_braces("{a,b}-{1,2}.parquet"),
+            ["a-1.parquet", "a-2.parquet", "b-1.parquet", "b-2.parquet"]
+        );
+        assert_eq!(expand_braces("part-{,old-}1"), ["part-1", "part-old-1"]);
+    }
+
+    #[test]
+    fn expands_nested_sets_and_drops_repeats() {
+        assert_eq!(
+            expand_braces("{x,y{1,2}}.parquet"),
+            ["x.parquet", "y1.parquet", "y2.parquet"]
+        );
+        assert_eq!(expand_braces("{a,a,b}"), ["a", "b"]);
+    }
+
+    #[test]
+    fn leaves_sets_without_commas_and_unbalanced_braces_literal() {
+        for pattern in ["plain.parquet", "{}", "{x}.parquet", "{a,b", "a,b}", "}{"] {
+            assert_eq!(expand_braces(pattern), [pattern]);
+        }
+        assert_eq!(expand_braces("{x}-{a,b}"), ["{x}-a", "{x}-b"]);
+    }
+}
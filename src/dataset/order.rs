@@ -0,0 +1,142 @@
+use crate::error::PqError;
+use crate::Result;
+use std::cmp::Ordering;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// How the files a glob matches are ordered. Explicit paths always keep their place on
+/// the command line.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InputOrder {
+    /// Byte-wise by path, so `part-10` sorts before `part-2`
+    #[default]
+    Name,
+    /// By path, comparing runs of digits as numbers, so `part-2` sorts before `part-10`
+    NameNumeric,
+    /// Oldest modification time first, ties broken by path
+    Mtime,
+    /// As the glob walk yields them, without re-sorting
+    None,
+}
+
+impl InputOrder {
+    pub(crate) fn sort(self, paths: &mut [PathBuf]) -> Result<()> {
+        match self {
+            Self::Name => paths.sort(),
+            Self::NameNumeric => paths.sort_by(|left, right| {
+                natural_cmp(&left.to_string_lossy(), &right.to_string_lossy())
+            }),
+            Self::Mtime => {
+                let mut keyed = paths
+                    .iter()
+                    .map(|path| Ok((modified(path)?, path.clone())))
+                    .collect::<Result<Vec<(SystemTime, PathBuf)>>>()?;
+                keyed.sort();
+                for (slot, (_, path)) in paths.iter_mut().zip(keyed) {
+                    *slot = path;
+                }
+            }
+            Self::None => {}
+        }
+        Ok(())
+    }
+}
+
+fn modified(path: &Path) -> Result<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|error| PqError::from_read(path, error))
+}
+
+/// Compare strings with runs of ASCII digits ordered by numeric value, so `a2 < a10` and
+/// `a02` equals `a2` until the final tie-break, which is a plain byte-wise comparison.
+pub(crate) fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let (mut left_rest, mut right_rest) = (left, right);
+    loop {
+        let (left_run, left_tail) = next_run(left_rest);
+        let (right_run, right_tail) = next_run(right_rest);
+        let ordering = match (left_run.is_empty(), right_run.is_empty()) {
+            (true, true) => return left.cmp(right),
+            (true, false) => return Ordering::Less,
+            (false, true) => return Ordering::Greater,
+            (false, false) if is_digits(left_run) && is_digits(right_run) => {
+                let left_number = left_run.trim_start_matches('0');
+                let right_number = right_run.trim_start_matches('0');
+                left_number
+                    .len()
+                    .cmp(&right_number.len())
+                    .then_with(|| left_number.cmp(right_number))
+            }
+            (false, false) => left_run.cmp(right_run),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        (left_rest, right_rest) = (left_tail, right_tail);
+    }
+}
+
+/// Split off the leading run of digits, or of non-digits.
+fn next_run(text: &str) -> (&str, &str) {
+    let starts_with_digit = text.starts_with(|c: char| c.is_ascii_digit());
+    let end = text
+        .find(|c: char| c.is_ascii_digit() != starts_with_digit)
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn is_digits(run: &str) -> bool {
+    run.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(ToString::to_string).collect();
+        names.sort_by(|left, right| natural_cmp(left, right));
+        names
+    }
+
+    #[test]
+    fn orders_digit_runs_numerically() {
+        assert_eq!(
+            sorted(&[
+                "part-10.parquet",
+                "part-2.parquet",
+                "part-1.parquet",
+                "part-100.parquet"
+            ]),
+            vec![
+                "part-1.parquet",
+                "part-2.parquet",
+                "part-10.parquet",
+                "part-100.parquet"
+            ]
+        );
+        assert_eq!(
+            sorted(&["v1.10/a", "v1.9/b", "v1.9/a", "v0.99/z"]),
+            vec!["v0.99/z", "v1.9/a", "v1.9/b", "v1.10/a"]
+        );
+    }
+
+    #[test]
+    fn handles_mixed_padding_deterministically() {
+        assert_eq!(
+            sorted(&["day-10", "day-002", "day-2", "day-02", "day-1", "day-0010"]),
+            vec!["day-1", "day-002", "day-02", "day-2", "day-0010", "day-10"]
+        );
+        assert_eq!(natural_cmp("x007", "x7"), Ordering::Less);
+        assert_eq!(natural_cmp("x7", "x7"), Ordering::Equal);
+    }
+
+    #[test]
+    fn compares_text_and_digits_at_the_same_position() {
+        assert_eq!(
+            sorted(&["b", "a10", "a", "10", "a2b", "a2"]),
+            vec!["10", "a", "a2", "a2b", "a10", "b"]
+        );
+    }
+}
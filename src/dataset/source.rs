@@ -1,13 +1,84 @@
+use super::braces::expand_braces;
+use super::order::InputOrder;
+use crate::engine::footer;
 use crate::error::PqError;
+use crate::model::{ColumnCase, DEFAULT_BATCH_SIZE};
+use crate::path_text::display_path;
 use crate::Result;
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
 use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
 
 const MAX_GLOB_FILES: usize = 10_000;
 
+// `**` matches any number of whole directories, itself included, so `data/**/*.parquet`
+// finds `data/a.parquet` and `data/x/y/b.parquet`; it must be a component of its own.
+// `*` and `?` never cross a `/`, and a leading `.` needs no literal match.
+/// How often `--wait-for-complete` checks whether a writer has finished.
+const WAIT_POLL: Duration = Duration::from_millis(100);
+
+const GLOB_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
 #[derive(Clone, Debug)]
 pub struct Dataset {
     paths: Vec<PathBuf>,
+    column_case: ColumnCase,
+    batch_size: usize,
+}
+
+/// How input arguments turn into files.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputOptions {
+    /// Order of the files a glob matches or a directory holds
+    pub order: InputOrder,
+    /// Walk directory arguments; when false a directory is an error
+    pub recursive: bool,
+    /// File extensions picked up from directories, without the dot
+    pub extensions: Vec<String>,
+    /// Levels of a directory to read, where 1 is only its own files
+    pub max_depth: Option<usize>,
+    /// Also read hidden files and directories, whose names start with `.`
+    pub hidden: bool,
+    /// Descend into symlinked directories while walking a directory argument
+    pub follow_symlinks: bool,
+    /// How operations on the dataset match the column names they are given
+    pub column_case: ColumnCase,
+    /// Rows per batch when decoding the dataset's files; at least 1
+    pub batch_size: usize,
+    /// Wait up to this long for files another process is still writing to get their
+    /// footer; without it such files are an error straight away
+    pub wait_for_complete: Option<Duration>,
+}
+
+impl Default for InputOptions {
+    fn default() -> Self {
+        Self {
+            order: InputOrder::default(),
+            recursive: true,
+            extensions: vec!["parquet".to_string()],
+            max_depth: None,
+            hidden: false,
+            follow_symlinks: false,
+            column_case: ColumnCase::Sensitive,
+            batch_size: DEFAULT_BATCH_SIZE,
+            wait_for_complete: None,
+        }
+    }
+}
+
+impl InputOptions {
+    pub fn with_order(&self, order: InputOrder) -> Self {
+        Self {
+            order,
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -16,8 +87,9 @@ pub(crate) struct InputFile {
 }
 
 impl InputFile {
-    pub(crate) fn from_input(input: PathBuf) -> Result<Self> {
-        let paths = paths_from_input(&input)?;
+    pub(crate) fn from_input(input: PathBuf, options: &InputOptions) -> Result<Self> {
+        let paths = paths_from_input(&input, options)?;
+        wait_for_writers(&paths, options.wait_for_complete)?;
         match paths.as_slice() {
             [path] => Ok(Self {
                 path: path.to_path_buf(),
@@ -34,6 +106,11 @@ impl InputFile {
 
 impl Dataset {
     pub fn from_inputs(inputs: Vec<PathBuf>) -> Result<Self> {
+        Self::from_inputs_with(inputs, &InputOptions::default())
+    }
+
+    /// Resolve globs and directories as `options` describe; explicit files keep their place.
+    pub fn from_inputs_with(inputs: Vec<PathBuf>, options: &InputOptions) -> Result<Self> {
         if inputs.is_empty() {
             return Err(PqError::NoInputFiles);
         }
@@ -44,7 +121,10 @@ impl Dataset {
 
         for input in inputs {
             if is_glob_pattern(&input) {
-                let matches = glob_matches(&input)?;
+                let matches = glob_matches(&input, options.order)?;
+                push_glob_matches(&matches, &mut paths, &mut seen_paths, &mut seen_from_globs);
+            } else if options.recursive && input.is_dir() {
+                let matches = directory_files(&input, options)?;
                 push_glob_matches(&matches, &mut paths, &mut seen_paths, &mut seen_from_globs);
             } else {
                 validate_file_path(&input)?;
@@ -58,44 +138,66 @@ impl Dataset {
         if paths.is_empty() {
             return Err(PqError::NoInputFiles);
         }
+        wait_for_writers(&paths, options.wait_for_complete)?;
 
-        Ok(Self { paths })
+        Ok(Self {
+            paths,
+            column_case: options.column_case,
+            batch_size: options.batch_size.max(1),
+        })
     }
 
     pub fn paths(&self) -> impl ExactSizeIterator<Item = &Path> {
         self.paths.iter().map(PathBuf::as_path)
     }
 
+    pub fn column_case(&self) -> ColumnCase {
+        self.column_case
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
     pub fn is_multi_source(&self) -> bool {
         self.paths.len() > 1
     }
 }
 
-fn paths_from_input(input: &Path) -> Result<Vec<PathBuf>> {
+fn paths_from_input(input: &Path, options: &InputOptions) -> Result<Vec<PathBuf>> {
     if is_glob_pattern(input) {
-        glob_matches(input)
+        glob_matches(input, options.order)
+    } else if options.recursive && input.is_dir() {
+        directory_files(input, options)
     } else {
         validate_file_path(input)?;
         Ok(vec![input.to_path_buf()])
     }
 }
 
-fn glob_matches(input: &Path) -> Result<Vec<PathBuf>> {
+/// Files `input` matches once its brace sets are expanded, each file once. Errors name
+/// the pattern as given rather than the piece that failed.
+fn glob_matches(input: &Path, order: InputOrder) -> Result<Vec<PathBuf>> {
     let pattern = input.to_string_lossy().into_owned();
     let mut matches = Vec::new();
+    let mut seen = BTreeSet::new();
+
+    for piece in expand_braces(&pattern) {
+        let entries = glob::glob_with(&piece, GLOB_OPTIONS)
+            .map_err(|error| PqError::invalid_glob_pattern(&pattern, error))?;
+        for entry in entries {
+            let path = entry.map_err(|error| PqError::from_read(error.path(), error.error()))?;
+            validate_file_path(&path)?;
+            if seen.insert(path.clone()) {
+                matches.push(path);
+            }
 
-    for entry in
-        glob::glob(&pattern).map_err(|error| PqError::invalid_glob_pattern(&pattern, error))?
-    {
-        let path = entry.map_err(|error| PqError::from_read(error.path(), error.error()))?;
-        validate_file_path(&path)?;
-        matches.push(path);
-
-        if matches.len() > MAX_GLOB_FILES {
-            return Err(PqError::TooManyFilesMatched {
-                pattern,
-                max_matches: MAX_GLOB_FILES,
-            });
+            if matches.len() > MAX_GLOB_FILES {
+                return Err(PqError::TooManyFilesMatched {
+                    pattern,
+                    max_matches: MAX_GLOB_FILES,
+                });
+            }
         }
     }
 
@@ -103,10 +205,75 @@ fn glob_matches(input: &Path) -> Result<Vec<PathBuf>> {
         return Err(PqError::NoFilesMatched { pattern });
     }
 
-    matches.sort();
+    order.sort(&mut matches)?;
     Ok(matches)
 }
 
+/// Files under `dir` with one of the wanted extensions, walking at most `max_depth`
+/// levels. Symlinked directories are followed only with `follow_symlinks`, and then
+/// each real directory is walked once, so a link cycle cannot loop.
+fn directory_files(dir: &Path, options: &InputOptions) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let mut pending = vec![(dir.to_path_buf(), 1usize)];
+    let mut visited = HashSet::new();
+    if options.follow_symlinks {
+        visited.insert(real_path(dir)?);
+    }
+
+    while let Some((current, depth)) = pending.pop() {
+        let entries =
+            fs::read_dir(&current).map_err(|error| PqError::from_read(&current, error))?;
+        for entry in entries {
+            let entry = entry.map_err(|error| PqError::from_read(&current, error))?;
+            let path = entry.path();
+            if !options.hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry
+                .file_type()
+                .map_err(|error| PqError::from_read(&path, error))?;
+            let linked_dir = options.follow_symlinks && file_type.is_symlink() && path.is_dir();
+            if file_type.is_dir() || linked_dir {
+                let first_visit = !options.follow_symlinks || visited.insert(real_path(&path)?);
+                if first_visit && options.max_depth.is_none_or(|max_depth| depth < max_depth) {
+                    pending.push((path, depth + 1));
+                }
+            } else if has_extension(&path, &options.extensions) && path.is_file() {
+                files.push(path);
+                if files.len() > MAX_GLOB_FILES {
+                    return Err(PqError::TooManyFilesInDirectory {
+                        path: display_path(dir),
+                        max_matches: MAX_GLOB_FILES,
+                    });
+                }
+            }
+        }
+    }
+
+    if files.is_empty() {
+        return Err(PqError::no_files_in_directory(dir, &options.extensions));
+    }
+
+    // Walk order depends on the filesystem, so start from a sorted list
+    files.sort();
+    options.order.sort(&mut files)?;
+    Ok(files)
+}
+
+fn real_path(path: &Path) -> Result<PathBuf> {
+    fs::canonicalize(path).map_err(|error| PqError::from_read(path, error))
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension().is_some_and(|extension| {
+        extensions.iter().any(|wanted| {
+            extension
+                .to_string_lossy()
+                .eq_ignore_ascii_case(wanted.trim_start_matches('.'))
+        })
+    })
+}
+
 fn push_glob_matches(
     matches: &[PathBuf],
     paths: &mut Vec<PathBuf>,
@@ -121,9 +288,28 @@ fn push_glob_matches(
     }
 }
 
-fn is_glob_pattern(path: &Path) -> bool {
+pub(crate) fn is_glob_pattern(path: &Path) -> bool {
     let path = path.to_string_lossy();
-    path.contains('*') || path.contains('?') || path.contains('[')
+    path.contains('*') || path.contains('?') || path.contains('[') || expand_braces(&path).len() > 1
+}
+
+/// Block while any of `paths` looks like it is mid-write, up to `timeout` in all. A
+/// writer that stalls for several seconds stops looking mid-write, and its file is then
+/// read, and reported, as truncated.
+fn wait_for_writers(paths: &[PathBuf], timeout: Option<Duration>) -> Result<()> {
+    let Some(timeout) = timeout else {
+        return Ok(());
+    };
+    let deadline = Instant::now() + timeout;
+    for path in paths {
+        while footer::is_being_written(path) {
+            if Instant::now() >= deadline {
+                return Err(PqError::being_written(path, Some(timeout)));
+            }
+            thread::sleep(WAIT_POLL);
+        }
+    }
+    Ok(())
 }
 
 fn validate_file_path(path: &Path) -> Result<()> {
@@ -208,6 +394,172 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn walks_directories_by_extension_depth_and_visibility() -> Result<()> {
+        let dir = temp_dir()?;
+        for relative in [
+            "b.parquet",
+            "a.PARQUET",
+            "notes.txt",
+            "nested/c.parquet",
+            "nested/deeper/d.parquet",
+            ".hidden/e.parquet",
+            ".f.parquet",
+        ] {
+            let path = dir.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, b"PAR1")?;
+        }
+        let names = |options: &InputOptions| -> Result<Vec<String>> {
+            let dataset = Dataset::from_inputs_with(vec![dir.clone()], options)?;
+            Ok(dataset
+                .paths()
+                .filter_map(|path| path.strip_prefix(&dir).ok())
+                .map(|path| path.display().to_string())
+                .collect())
+        };
+
+        let options = InputOptions::default();
+        assert_eq!(
+            names(&options)?,
+            vec![
+                "a.PARQUET",
+                "b.parquet",
+                "nested/c.parquet",
+                "nested/deeper/d.parquet"
+            ]
+        );
+        let shallow = InputOptions {
+            max_depth: Some(2),
+            ..InputOptions::default()
+        };
+        assert_eq!(
+            names(&shallow)?,
+            vec!["a.PARQUET", "b.parquet", "nested/c.parquet"]
+        );
+        let hidden = InputOptions {
+            hidden: true,
+            max_depth: Some(1),
+            ..InputOptions::default()
+        };
+        assert_eq!(
+            names(&hidden)?,
+            vec![".f.parquet", "a.PARQUET", "b.parquet"]
+        );
+        let text = InputOptions {
+            extensions: vec![".txt".to_string()],
+            ..InputOptions::default()
+        };
+        assert_eq!(names(&text)?, vec!["notes.txt"]);
+
+        let csv = InputOptions {
+            extensions: vec!["csv".to_string()],
+            ..InputOptions::default()
+        };
+        assert!(matches!(
+            Dataset::from_inputs_with(vec![dir.clone()], &csv),
+            Err(PqError::NoFilesInDirectory { .. })
+        ));
+        let flat = InputOptions {
+            recursive: false,
+            ..InputOptions::default()
+        };
+        assert!(matches!(
+            Dataset::from_inputs_with(vec![dir.clone()], &flat),
+            Err(PqError::IsDirectory { .. })
+        ));
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn expands_brace_sets_and_recursive_globs() -> Result<()> {
+        let dir = temp_dir()?;
+        for relative in [
+            "a.parquet",
+            "2023/b.parquet",
+            "2024/x/c.parquet",
+            "2025/d.parquet",
+        ] {
+            let path = dir.join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, b"PAR1")?;
+        }
+        let names = |pattern: &str| -> Result<Vec<String>> {
+            let dataset = Dataset::from_inputs(vec![dir.join(pattern)])?;
+            Ok(dataset
+                .paths()
+                .filter_map(|path| path.strip_prefix(&dir).ok())
+                .map(|path| path.display().to_string())
+                .collect())
+        };
+
+        assert_eq!(
+            names("**/*.parquet")?,
+            vec![
+                "2023/b.parquet",
+                "2024/x/c.parquet",
+                "2025/d.parquet",
+                "a.parquet"
+            ]
+        );
+        assert_eq!(
+            names("{2023,2024}/**/*.parquet")?,
+            vec!["2023/b.parquet", "2024/x/c.parquet"]
+        );
+        assert_eq!(
+            names("{a,2025/d,2023/*}.parquet")?,
+            vec!["2023/b.parquet", "2025/d.parquet", "a.parquet"]
+        );
+
+        let missing = dir.join("{2026,2027}/*.parquet");
+        let Err(PqError::NoFilesMatched { pattern }) = Dataset::from_inputs(vec![missing.clone()])
+        else {
+            return Err(PqError::output_error(
+                "brace set matching nothing should fail",
+            ));
+        };
+        assert_eq!(pattern, missing.to_string_lossy());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn follows_symlinked_directories_only_when_asked() -> Result<()> {
+        let dir = temp_dir()?;
+        let data = temp_dir()?;
+        fs::write(dir.join("a.parquet"), b"PAR1")?;
+        fs::write(data.join("b.parquet"), b"PAR1")?;
+        std::os::unix::fs::symlink(&data, dir.join("linked"))?;
+        std::os::unix::fs::symlink(&dir, data.join("back"))?;
+        let names = |options: &InputOptions| -> Result<Vec<String>> {
+            let dataset = Dataset::from_inputs_with(vec![dir.clone()], options)?;
+            Ok(dataset
+                .paths()
+                .filter_map(|path| path.strip_prefix(&dir).ok())
+                .map(|path| path.display().to_string())
+                .collect())
+        };
+
+        assert_eq!(names(&InputOptions::default())?, vec!["a.parquet"]);
+        let follow = InputOptions {
+            follow_symlinks: true,
+            ..InputOptions::default()
+        };
+        assert_eq!(names(&follow)?, vec!["a.parquet", "linked/b.parquet"]);
+
+        fs::remove_dir_all(dir)?;
+        fs::remove_dir_all(data)?;
+        Ok(())
+    }
+
     #[test]
     fn input_file_rejects_multi_match_glob() -> Result<()> {
         let dir = temp_dir()?;
@@ -217,7 +569,7 @@ mod tests {
         fs::write(&second, b"PAR1")?;
         let glob = dir.join("*.parquet");
 
-        let Err(error) = InputFile::from_input(glob) else {
+        let Err(error) = InputFile::from_input(glob, &InputOptions::default()) else {
             return Err(PqError::output_error("multi-match glob should fail"));
         };
 
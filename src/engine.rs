@@ -1,2 +1,23 @@
+pub(crate) mod checksum;
+pub(crate) mod ddl;
+pub(crate) mod describe;
+pub(crate) mod exists;
+pub(crate) mod filter;
+pub(crate) mod footer;
+pub(crate) mod grep;
+pub(crate) mod group;
+pub(crate) mod hist;
+pub(crate) mod merge;
+pub(crate) mod nulls;
+pub(crate) mod pages;
 pub(crate) mod parquet;
+pub(crate) mod prune;
+pub(crate) mod reshape;
+pub(crate) mod sample;
+pub(crate) mod select;
+pub(crate) mod sniff;
+pub(crate) mod sorting;
 pub(crate) mod stats;
+pub(crate) mod stats_diff;
+pub(crate) mod transform;
+pub(crate) mod unify;
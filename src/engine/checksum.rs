@@ -0,0 +1,166 @@
+//! Order-sensitive hashes of a file's logical row data
+//!
+//! Values are canonicalized through Arrow's row format before hashing, so the same rows
+//! give the same hash whatever the compression, encoding, or row-group layout, and
+//! whether strings, binaries, and lists use 32-bit offsets, 64-bit offsets, views, or
+//! dictionaries. The hash covers decoded data only; it says nothing about the file's bytes.
+
+use super::parquet::{build_error, decode_error, reader_builder};
+use crate::error::PqError;
+use crate::model::{ChecksumAlgorithm, ChecksumOptions, ChecksumResult, ColumnChecksum};
+use crate::Result;
+use arrow::array::{ArrayRef, RecordBatchReader};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, FieldRef, Fields};
+use arrow::row::{RowConverter, Rows, SortField};
+use sha2::{Digest, Sha256};
+use std::hash::Hasher as _;
+use std::path::Path;
+use twox_hash::XxHash3_64;
+
+pub fn checksum(
+    path: &Path,
+    options: &ChecksumOptions,
+    batch_size: usize,
+) -> Result<ChecksumResult> {
+    let reader = reader_builder(path)?
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    let schema = reader.schema();
+    let fields: Fields = schema.fields().iter().map(canonical_field).collect();
+    let column_converters = fields
+        .iter()
+        .map(|field| {
+            RowConverter::new(vec![SortField::new(field.data_type().clone())]).map_err(|error| {
+                PqError::unsupported_column_type(path, field.name(), field.data_type(), error)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let rows_converter = RowConverter::new(
+        fields
+            .iter()
+            .map(|field| SortField::new(field.data_type().clone()))
+            .collect(),
+    )?;
+
+    let mut file_hash = Hash::new(options.algorithm);
+    let mut column_hashes = Vec::new();
+    for field in &fields {
+        file_hash.update_field(field);
+        if options.by_column {
+            let mut hash = Hash::new(options.algorithm);
+            hash.update_field(field);
+            column_hashes.push(hash);
+        }
+    }
+
+    let mut num_rows = 0u64;
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| decode_error(path, error))?;
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(&fields)
+            .map(|(column, field)| canonical_column(column, field))
+            .collect::<Result<Vec<_>>>()?;
+        file_hash.update_rows(&rows_converter.convert_columns(&columns)?);
+        for ((converter, hash), column) in column_converters
+            .iter()
+            .zip(&mut column_hashes)
+            .zip(&columns)
+        {
+            hash.update_rows(&converter.convert_columns(std::slice::from_ref(column))?);
+        }
+        num_rows += batch.num_rows() as u64;
+    }
+
+    Ok(ChecksumResult {
+        path: path.to_path_buf(),
+        algorithm: options.algorithm,
+        num_rows,
+        checksum: file_hash.finish(),
+        columns: fields
+            .iter()
+            .zip(column_hashes)
+            .map(|(field, hash)| ColumnChecksum {
+                column: field.name().clone(),
+                checksum: hash.finish(),
+            })
+            .collect(),
+    })
+}
+
+/// `field` with 64-bit offset and view layouts replaced by their 32-bit offset
+/// counterparts and dictionaries by their values, at any depth.
+fn canonical_field(field: &FieldRef) -> FieldRef {
+    let data_type = canonical_type(field.data_type());
+    if &data_type == field.data_type() {
+        return FieldRef::clone(field);
+    }
+    std::sync::Arc::new(field.as_ref().clone().with_data_type(data_type))
+}
+
+fn canonical_type(data_type: &DataType) -> DataType {
+    match data_type {
+        DataType::Dictionary(_, value) => canonical_type(value),
+        DataType::LargeUtf8 | DataType::Utf8View => DataType::Utf8,
+        DataType::LargeBinary | DataType::BinaryView => DataType::Binary,
+        DataType::List(item) | DataType::LargeList(item) => DataType::List(canonical_field(item)),
+        DataType::Struct(fields) => DataType::Struct(fields.iter().map(canonical_field).collect()),
+        other => other.clone(),
+    }
+}
+
+fn canonical_column(column: &ArrayRef, field: &Field) -> Result<ArrayRef> {
+    if column.data_type() == field.data_type() {
+        return Ok(ArrayRef::clone(column));
+    }
+    Ok(cast(column, field.data_type())?)
+}
+
+enum Hash {
+    Xxh3(Box<XxHash3_64>),
+    Sha256(Sha256),
+}
+
+impl Hash {
+    fn new(algorithm: ChecksumAlgorithm) -> Self {
+        match algorithm {
+            ChecksumAlgorithm::Xxh3 => Self::Xxh3(Box::new(XxHash3_64::new())),
+            ChecksumAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+        }
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Xxh3(hasher) => hasher.write(bytes),
+            Self::Sha256(hasher) => hasher.update(bytes),
+        }
+    }
+
+    /// Length-prefixed, so adjacent values cannot run together.
+    fn update_framed(&mut self, bytes: &[u8]) {
+        self.update(&(bytes.len() as u64).to_le_bytes());
+        self.update(bytes);
+    }
+
+    /// A column's name and type, so renamed or retyped columns hash differently.
+    fn update_field(&mut self, field: &Field) {
+        self.update_framed(field.name().as_bytes());
+        self.update_framed(field.data_type().to_string().as_bytes());
+    }
+
+    fn update_rows(&mut self, rows: &Rows) {
+        for row in rows {
+            self.update_framed(row.as_ref());
+        }
+    }
+
+    fn finish(self) -> String {
+        match self {
+            Self::Xxh3(hasher) => format!("{:016x}", hasher.finish()),
+            Self::Sha256(hasher) => crate::model::display_hex(&hasher.finalize()),
+        }
+    }
+}
@@ -0,0 +1,490 @@
+//! Schemas rendered as `CREATE TABLE` statements or Arrow schema JSON
+
+use super::parquet::reader_builder;
+use crate::error::PqError;
+use crate::model::{DdlDialect, ARROW_EXTENSION_NAME_KEY, UUID_EXTENSION_NAME};
+use crate::Result;
+use arrow::datatypes::{DataType, Field, IntervalUnit, Schema, TimeUnit};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// The file's Arrow schema in `dialect`. SQL statements are named after the file stem.
+pub fn ddl(path: &Path, dialect: DdlDialect) -> Result<String> {
+    let builder = reader_builder(path)?;
+    let schema = builder.schema();
+    if dialect == DdlDialect::Arrow {
+        let schema = arrow_schema_json(path, schema)?;
+        return Ok(serde_json::to_string_pretty(&schema)?);
+    }
+
+    let table = path.file_stem().map_or_else(
+        || "data".to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    );
+    create_table(path, &table, schema, dialect)
+}
+
+fn create_table(path: &Path, table: &str, schema: &Schema, dialect: DdlDialect) -> Result<String> {
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let sql_type = sql_type(dialect, field).map_err(|details| {
+                PqError::unsupported_column_type(path, field.name(), field.data_type(), details)
+            })?;
+            let not_null = if field.is_nullable() { "" } else { " NOT NULL" };
+            Ok(format!(
+                "  {} {sql_type}{not_null}",
+                quote(dialect, field.name())
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(format!(
+        "CREATE TABLE {} (\n{}\n);",
+        quote(dialect, table),
+        columns.join(",\n")
+    ))
+}
+
+fn quote(dialect: DdlDialect, name: &str) -> String {
+    match dialect {
+        DdlDialect::Spark => format!("`{}`", name.replace('`', "``")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+fn is_uuid(field: &Field) -> bool {
+    field.data_type() == &DataType::FixedSizeBinary(16)
+        && field
+            .metadata()
+            .get(ARROW_EXTENSION_NAME_KEY)
+            .is_some_and(|name| name == UUID_EXTENSION_NAME)
+}
+
+/// A column type in `dialect`, or why the dialect cannot express it.
+fn sql_type(dialect: DdlDialect, field: &Field) -> std::result::Result<String, String> {
+    if is_uuid(field) {
+        return Ok(match dialect {
+            DdlDialect::Spark => "STRING",
+            _ => "UUID",
+        }
+        .to_string());
+    }
+    match dialect {
+        DdlDialect::Duckdb => duckdb_type(field.data_type()),
+        DdlDialect::Postgres => postgres_type(field.data_type()),
+        DdlDialect::Spark => spark_type(field.data_type()),
+        DdlDialect::Arrow => Ok(field.data_type().to_string()),
+    }
+}
+
+fn unsupported(dialect: &str, data_type: &DataType) -> String {
+    format!("{dialect} has no equivalent for {data_type}")
+}
+
+fn decimal(dialect: &str, precision: u8, scale: i8) -> std::result::Result<String, String> {
+    if precision > 38 {
+        return Err(format!(
+            "{dialect} decimals hold at most 38 digits, not {precision}"
+        ));
+    }
+    Ok(format!("DECIMAL({precision},{scale})"))
+}
+
+fn duckdb_type(data_type: &DataType) -> std::result::Result<String, String> {
+    Ok(match data_type {
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int8 => "TINYINT".to_string(),
+        DataType::Int16 => "SMALLINT".to_string(),
+        DataType::Int32 => "INTEGER".to_string(),
+        DataType::Int64 => "BIGINT".to_string(),
+        DataType::UInt8 => "UTINYINT".to_string(),
+        DataType::UInt16 => "USMALLINT".to_string(),
+        DataType::UInt32 => "UINTEGER".to_string(),
+        DataType::UInt64 => "UBIGINT".to_string(),
+        DataType::Float16 | DataType::Float32 => "FLOAT".to_string(),
+        DataType::Float64 => "DOUBLE".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "VARCHAR".to_string(),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => "BLOB".to_string(),
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+        DataType::Time32(_) | DataType::Time64(_) => "TIME".to_string(),
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMPTZ".to_string(),
+        DataType::Timestamp(unit, None) => match unit {
+            TimeUnit::Second => "TIMESTAMP_S",
+            TimeUnit::Millisecond => "TIMESTAMP_MS",
+            TimeUnit::Microsecond => "TIMESTAMP",
+            TimeUnit::Nanosecond => "TIMESTAMP_NS",
+        }
+        .to_string(),
+        DataType::Duration(_) | DataType::Interval(_) => "INTERVAL".to_string(),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            decimal("duckdb", *precision, *scale)?
+        }
+        DataType::List(item) | DataType::LargeList(item) => {
+            format!("{}[]", duckdb_type(item.data_type())?)
+        }
+        DataType::FixedSizeList(item, size) => {
+            format!("{}[{size}]", duckdb_type(item.data_type())?)
+        }
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    Ok(format!(
+                        "{} {}",
+                        quote(DdlDialect::Duckdb, field.name()),
+                        duckdb_type(field.data_type())?
+                    ))
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            format!("STRUCT({})", fields.join(", "))
+        }
+        DataType::Map(entries, _) => {
+            let (key, value) = map_entries("duckdb", entries)?;
+            format!("MAP({}, {})", duckdb_type(key)?, duckdb_type(value)?)
+        }
+        DataType::Dictionary(_, value) => duckdb_type(value)?,
+        other => return Err(unsupported("duckdb", other)),
+    })
+}
+
+fn postgres_type(data_type: &DataType) -> std::result::Result<String, String> {
+    Ok(match data_type {
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 => "SMALLINT".to_string(),
+        DataType::Int32 | DataType::UInt16 => "INTEGER".to_string(),
+        DataType::Int64 | DataType::UInt32 => "BIGINT".to_string(),
+        DataType::UInt64 => "NUMERIC(20,0)".to_string(),
+        DataType::Float16 | DataType::Float32 => "REAL".to_string(),
+        DataType::Float64 => "DOUBLE PRECISION".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "TEXT".to_string(),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => "BYTEA".to_string(),
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+        DataType::Time32(_) | DataType::Time64(_) => "TIME".to_string(),
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMPTZ".to_string(),
+        DataType::Timestamp(_, None) => "TIMESTAMP".to_string(),
+        DataType::Duration(_) | DataType::Interval(_) => "INTERVAL".to_string(),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            format!("NUMERIC({precision},{scale})")
+        }
+        DataType::List(item) | DataType::LargeList(item) | DataType::FixedSizeList(item, _) => {
+            format!("{}[]", postgres_type(item.data_type())?)
+        }
+        DataType::Struct(_) => {
+            return Err(
+                "postgres has no inline struct type; create a composite type for it, or use --ddl duckdb or spark"
+                    .to_string(),
+            )
+        }
+        DataType::Map(..) => {
+            return Err(
+                "postgres has no map type; store it as JSONB, or use --ddl duckdb or spark"
+                    .to_string(),
+            )
+        }
+        DataType::Dictionary(_, value) => postgres_type(value)?,
+        other => return Err(unsupported("postgres", other)),
+    })
+}
+
+fn spark_type(data_type: &DataType) -> std::result::Result<String, String> {
+    Ok(match data_type {
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Int8 => "TINYINT".to_string(),
+        DataType::Int16 | DataType::UInt8 => "SMALLINT".to_string(),
+        DataType::Int32 | DataType::UInt16 => "INT".to_string(),
+        DataType::Int64 | DataType::UInt32 => "BIGINT".to_string(),
+        DataType::UInt64 => "DECIMAL(20,0)".to_string(),
+        DataType::Float16 | DataType::Float32 => "FLOAT".to_string(),
+        DataType::Float64 => "DOUBLE".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => "STRING".to_string(),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::BinaryView
+        | DataType::FixedSizeBinary(_) => "BINARY".to_string(),
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMP".to_string(),
+        DataType::Timestamp(_, None) => "TIMESTAMP_NTZ".to_string(),
+        DataType::Duration(_) => "INTERVAL DAY TO SECOND".to_string(),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            decimal("spark", *precision, *scale)?
+        }
+        DataType::List(item) | DataType::LargeList(item) | DataType::FixedSizeList(item, _) => {
+            format!("ARRAY<{}>", spark_type(item.data_type())?)
+        }
+        DataType::Struct(fields) => {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    let not_null = if field.is_nullable() { "" } else { " NOT NULL" };
+                    Ok(format!(
+                        "{}: {}{not_null}",
+                        quote(DdlDialect::Spark, field.name()),
+                        spark_type(field.data_type())?
+                    ))
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            format!("STRUCT<{}>", fields.join(", "))
+        }
+        DataType::Map(entries, _) => {
+            let (key, value) = map_entries("spark", entries)?;
+            format!("MAP<{}, {}>", spark_type(key)?, spark_type(value)?)
+        }
+        DataType::Dictionary(_, value) => spark_type(value)?,
+        DataType::Time32(_) | DataType::Time64(_) => {
+            return Err(
+                "spark has no time-of-day type; cast it to a string or timestamp".to_string(),
+            )
+        }
+        other => return Err(unsupported("spark", other)),
+    })
+}
+
+/// A map's key and value types, from its entries struct.
+fn map_entries<'a>(
+    dialect: &str,
+    entries: &'a Field,
+) -> std::result::Result<(&'a DataType, &'a DataType), String> {
+    match entries.data_type() {
+        DataType::Struct(fields) => match (fields.first(), fields.get(1)) {
+            (Some(key), Some(value)) => Ok((key.data_type(), value.data_type())),
+            _ => Err(unsupported(dialect, entries.data_type())),
+        },
+        other => Err(unsupported(dialect, other)),
+    }
+}
+
+/// The schema in the JSON form Arrow implementations exchange in integration tests.
+fn arrow_schema_json(path: &Path, schema: &Schema) -> Result<Value> {
+    let fields = schema
+        .fields()
+        .iter()
+        .map(|field| {
+            arrow_field_json(field).map_err(|details| {
+                PqError::unsupported_column_type(path, field.name(), field.data_type(), details)
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut object = Map::new();
+    object.insert("fields".to_string(), Value::Array(fields));
+    if !schema.metadata().is_empty() {
+        object.insert("metadata".to_string(), metadata_json(schema.metadata()));
+    }
+    Ok(Value::Object(object))
+}
+
+fn arrow_field_json(field: &Field) -> std::result::Result<Value, String> {
+    let (data_type, dictionary) = match field.data_type() {
+        DataType::Dictionary(index, value) => (
+            value.as_ref(),
+            Some(json!({
+                "id": 0,
+                "indexType": arrow_type_json(index)?,
+                "isOrdered": false,
+            })),
+        ),
+        data_type => (data_type, None),
+    };
+    let children: Vec<Value> = match data_type {
+        DataType::List(item)
+        | DataType::LargeList(item)
+        | DataType::FixedSizeList(item, _)
+        | DataType::Map(item, _) => vec![arrow_field_json(item)?],
+        DataType::Struct(fields) => fields
+            .iter()
+            .map(|field| arrow_field_json(field))
+            .collect::<std::result::Result<_, _>>()?,
+        _ => Vec::new(),
+    };
+
+    let mut object = Map::new();
+    object.insert("name".to_string(), Value::from(field.name().as_str()));
+    object.insert("nullable".to_string(), Value::from(field.is_nullable()));
+    object.insert("type".to_string(), arrow_type_json(data_type)?);
+    object.insert("children".to_string(), Value::Array(children));
+    if let Some(dictionary) = dictionary {
+        object.insert("dictionary".to_string(), dictionary);
+    }
+    if !field.metadata().is_empty() {
+        object.insert("metadata".to_string(), metadata_json(field.metadata()));
+    }
+    Ok(Value::Object(object))
+}
+
+fn arrow_type_json(data_type: &DataType) -> std::result::Result<Value, String> {
+    let unit = |unit: &TimeUnit| match unit {
+        TimeUnit::Second => "SECOND",
+        TimeUnit::Millisecond => "MILLISECOND",
+        TimeUnit::Microsecond => "MICROSECOND",
+        TimeUnit::Nanosecond => "NANOSECOND",
+    };
+    let int = |bit_width: u8, is_signed: bool| json!({"name": "int", "bitWidth": bit_width, "isSigned": is_signed});
+    Ok(match data_type {
+        DataType::Null => json!({"name": "null"}),
+        DataType::Boolean => json!({"name": "bool"}),
+        DataType::Int8 => int(8, true),
+        DataType::Int16 => int(16, true),
+        DataType::Int32 => int(32, true),
+        DataType::Int64 => int(64, true),
+        DataType::UInt8 => int(8, false),
+        DataType::UInt16 => int(16, false),
+        DataType::UInt32 => int(32, false),
+        DataType::UInt64 => int(64, false),
+        DataType::Float16 => json!({"name": "floatingpoint", "precision": "HALF"}),
+        DataType::Float32 => json!({"name": "floatingpoint", "precision": "SINGLE"}),
+        DataType::Float64 => json!({"name": "floatingpoint", "precision": "DOUBLE"}),
+        DataType::Utf8 => json!({"name": "utf8"}),
+        DataType::LargeUtf8 => json!({"name": "largeutf8"}),
+        DataType::Utf8View => json!({"name": "utf8view"}),
+        DataType::Binary => json!({"name": "binary"}),
+        DataType::LargeBinary => json!({"name": "largebinary"}),
+        DataType::BinaryView => json!({"name": "binaryview"}),
+        DataType::FixedSizeBinary(width) => json!({"name": "fixedsizebinary", "byteWidth": width}),
+        DataType::Date32 => json!({"name": "date", "unit": "DAY"}),
+        DataType::Date64 => json!({"name": "date", "unit": "MILLISECOND"}),
+        DataType::Time32(time_unit) => {
+            json!({"name": "time", "unit": unit(time_unit), "bitWidth": 32})
+        }
+        DataType::Time64(time_unit) => {
+            json!({"name": "time", "unit": unit(time_unit), "bitWidth": 64})
+        }
+        DataType::Timestamp(time_unit, None) => {
+            json!({"name": "timestamp", "unit": unit(time_unit)})
+        }
+        DataType::Timestamp(time_unit, Some(zone)) => {
+            json!({"name": "timestamp", "unit": unit(time_unit), "timezone": zone.as_ref()})
+        }
+        DataType::Duration(time_unit) => json!({"name": "duration", "unit": unit(time_unit)}),
+        DataType::Interval(interval_unit) => json!({
+            "name": "interval",
+            "unit": match interval_unit {
+                IntervalUnit::YearMonth => "YEAR_MONTH",
+                IntervalUnit::DayTime => "DAY_TIME",
+                IntervalUnit::MonthDayNano => "MONTH_DAY_NANO",
+            },
+        }),
+        DataType::Decimal128(precision, scale) => {
+            json!({"name": "decimal", "precision": precision, "scale": scale, "bitWidth": 128})
+        }
+        DataType::Decimal256(precision, scale) => {
+            json!({"name": "decimal", "precision": precision, "scale": scale, "bitWidth": 256})
+        }
+        DataType::List(_) => json!({"name": "list"}),
+        DataType::LargeList(_) => json!({"name": "largelist"}),
+        DataType::FixedSizeList(_, size) => json!({"name": "fixedsizelist", "listSize": size}),
+        DataType::Struct(_) => json!({"name": "struct"}),
+        DataType::Map(_, keys_sorted) => json!({"name": "map", "keysSorted": keys_sorted}),
+        other => return Err(format!("Arrow schema JSON has no form for {other}")),
+    })
+}
+
+fn metadata_json(metadata: &HashMap<String, String>) -> Value {
+    let mut entries: Vec<_> = metadata.iter().collect();
+    entries.sort();
+    Value::Array(
+        entries
+            .into_iter()
+            .map(|(key, value)| json!({"key": key, "value": value}))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::Fields;
+
+    fn nested_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new("price", DataType::Decimal128(10, 2), true),
+            Field::new_list("tags", Field::new("item", DataType::Utf8, true), true),
+            Field::new_struct(
+                "user",
+                Fields::from(vec![
+                    Field::new("name", DataType::Utf8, false),
+                    Field::new("age", DataType::UInt8, true),
+                ]),
+                true,
+            ),
+        ])
+    }
+
+    fn statement(dialect: DdlDialect, schema: &Schema) -> Result<String> {
+        create_table(Path::new("events.parquet"), "events", schema, dialect)
+    }
+
+    #[test]
+    fn renders_duckdb_tables() -> Result<()> {
+        assert_eq!(
+            statement(DdlDialect::Duckdb, &nested_schema())?,
+            "CREATE TABLE \"events\" (\n  \"id\" BIGINT NOT NULL,\n  \"ts\" TIMESTAMPTZ,\n  \"price\" DECIMAL(10,2),\n  \"tags\" VARCHAR[],\n  \"user\" STRUCT(\"name\" VARCHAR, \"age\" UTINYINT)\n);"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_spark_tables() -> Result<()> {
+        assert_eq!(
+            statement(DdlDialect::Spark, &nested_schema())?,
+            "CREATE TABLE `events` (\n  `id` BIGINT NOT NULL,\n  `ts` TIMESTAMP,\n  `price` DECIMAL(10,2),\n  `tags` ARRAY<STRING>,\n  `user` STRUCT<`name`: STRING NOT NULL, `age`: SMALLINT>\n);"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_postgres_tables_and_rejects_structs() -> Result<()> {
+        let flat = Schema::new(nested_schema().fields()[..4].to_vec());
+        assert_eq!(
+            statement(DdlDialect::Postgres, &flat)?,
+            "CREATE TABLE \"events\" (\n  \"id\" BIGINT NOT NULL,\n  \"ts\" TIMESTAMPTZ,\n  \"price\" NUMERIC(10,2),\n  \"tags\" TEXT[]\n);"
+        );
+        let error = statement(DdlDialect::Postgres, &nested_schema())
+            .err()
+            .map(|error| error.to_string())
+            .unwrap_or_default();
+        assert!(error.contains("column user"));
+        assert!(error.contains("composite type"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_arrow_schema_json() -> Result<()> {
+        let schema = Schema::new(vec![Field::new(
+            "tags",
+            DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)),
+            true,
+        )])
+        .with_metadata(HashMap::from([("origin".to_string(), "test".to_string())]));
+        assert_eq!(
+            arrow_schema_json(Path::new("events.parquet"), &schema)?,
+            json!({
+                "fields": [{
+                    "name": "tags",
+                    "nullable": true,
+                    "type": {"name": "utf8"},
+                    "children": [],
+                    "dictionary": {
+                        "id": 0,
+                        "indexType": {"name": "int", "bitWidth": 32, "isSigned": true},
+                        "isOrdered": false,
+                    },
+                }],
+                "metadata": [{"key": "origin", "value": "test"}],
+            })
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+use crate::model::{ColumnDescription, FileDescription};
+use crate::Result;
+use parquet::file::reader::FileReader;
+use std::path::Path;
+
+/// Everything comes from one read of the footer; no data pages are touched.
+pub fn describe(path: &Path) -> Result<FileDescription> {
+    let reader = super::parquet::serialized_reader(path)?;
+    let metadata = reader.metadata();
+    let info = super::parquet::summarize_file(path, metadata)?;
+    let columns = super::parquet::leaf_columns(metadata)
+        .into_iter()
+        .zip(super::stats::metadata_stats(metadata))
+        .map(|(column, stats)| ColumnDescription {
+            name: column.name,
+            column_type: column.column_type,
+            nullable: column.nullable,
+            null_count: stats.null_count,
+            min: stats.min,
+            max: stats.max,
+        })
+        .collect();
+    Ok(FileDescription { info, columns })
+}
@@ -0,0 +1,170 @@
+use crate::error::{PqError, ResultExt};
+use crate::model::{
+    ColumnCase, ExistsAnswer, ExistsEvidence, ExistsOptions, ExistsResult, StatValue,
+};
+use crate::predicate::Literal;
+use crate::Result;
+use arrow::array::{Array, Scalar, StringArray};
+use arrow::compute::cast;
+use arrow::compute::kernels::cmp::eq;
+use parquet::arrow::parquet_to_arrow_schema;
+use parquet::bloom_filter::Sbbf;
+use parquet::file::properties::ReaderProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::serialized_reader::ReadOptionsBuilder;
+use std::fs::File;
+use std::path::Path;
+
+/// Decide whether a value may exist in a column, from the cheapest evidence upward:
+/// row-group statistics, then bloom filters, then (with `scan`) the data itself.
+pub fn exists_in_file(
+    path: &Path,
+    options: &ExistsOptions,
+    batch_size: usize,
+) -> Result<ExistsResult> {
+    let file = File::open(path).with_path_context(path)?;
+    let read_options = ReadOptionsBuilder::new()
+        .with_reader_properties(
+            ReaderProperties::builder()
+                .set_read_bloom_filter(true)
+                .build(),
+        )
+        .build();
+    let reader = SerializedFileReader::new_with_options(file, read_options)
+        .map_err(|error| super::parquet::footer_error(path, error))?;
+    let metadata = reader.metadata();
+    let file_metadata = metadata.file_metadata();
+    let schema = parquet_to_arrow_schema(
+        file_metadata.schema_descr(),
+        file_metadata.key_value_metadata(),
+    )
+    .map_err(|error| PqError::invalid_metadata(path, error))?;
+
+    let text = format!("{} = '{}'", options.column, options.value);
+    let column = super::prune::resolve_column(path, &text, metadata, &schema, &options.column)?;
+    let target = super::prune::literal_target(&column, &Literal::String(options.value.clone()))
+        .map_err(|details| PqError::invalid_predicate(&text, details))?;
+
+    let row_groups = metadata.num_row_groups();
+    let mut candidates = Vec::new();
+    let mut eliminated_by_bloom = false;
+    let mut all_candidates_bloom_checked = true;
+
+    for index in 0..row_groups {
+        if let Some(target) = &target {
+            if !column.may_contain(metadata.row_group(index), target) {
+                continue;
+            }
+        }
+
+        let row_group = reader
+            .get_row_group(index)
+            .map_err(|error| PqError::from_read(path, error))?;
+        match (row_group.get_column_bloom_filter(column.leaf), &target) {
+            (Some(filter), Some(target)) => {
+                if !bloom_may_contain(filter, target) {
+                    eliminated_by_bloom = true;
+                    continue;
+                }
+            }
+            _ => all_candidates_bloom_checked = false,
+        }
+        candidates.push(index);
+    }
+
+    let (answer, evidence) = if candidates.is_empty() {
+        let evidence = if eliminated_by_bloom {
+            ExistsEvidence::BloomFilter
+        } else {
+            ExistsEvidence::Statistics
+        };
+        (ExistsAnswer::DefinitelyAbsent, evidence)
+    } else if options.scan {
+        let answer = if scan_for_value(path, &candidates, options, batch_size)? {
+            ExistsAnswer::ConfirmedPresent
+        } else {
+            ExistsAnswer::DefinitelyAbsent
+        };
+        (answer, ExistsEvidence::Scan)
+    } else if all_candidates_bloom_checked {
+        (ExistsAnswer::PossiblyPresent, ExistsEvidence::BloomFilter)
+    } else {
+        (ExistsAnswer::PossiblyPresent, ExistsEvidence::Statistics)
+    };
+
+    Ok(ExistsResult {
+        path: path.to_path_buf(),
+        column: options.column.clone(),
+        value: options.value.clone(),
+        answer,
+        evidence,
+        row_groups,
+        candidate_row_groups: candidates.len(),
+    })
+}
+
+/// Bloom filters hash the plain-encoded physical value.
+fn bloom_may_contain(filter: &Sbbf, target: &StatValue) -> bool {
+    match target {
+        StatValue::Int32(value) => filter.check(value),
+        StatValue::Int64(value) => filter.check(value),
+        StatValue::Float(value) => filter.check(value),
+        StatValue::Double(value) => filter.check(value),
+        StatValue::Boolean(value) => filter.check(value),
+        StatValue::Binary(value) | StatValue::FixedLenBinary(value) => filter.check(value),
+        StatValue::Int96(_) => true,
+    }
+}
+
+fn scan_for_value(
+    path: &Path,
+    row_groups: &[usize],
+    options: &ExistsOptions,
+    batch_size: usize,
+) -> Result<bool> {
+    let builder = super::parquet::reader_builder(path)?;
+    let projection = super::parquet::root_projection(
+        path,
+        &builder,
+        std::slice::from_ref(&options.column),
+        ColumnCase::Sensitive,
+    )?;
+    let data_type = builder
+        .schema()
+        .field_with_name(&options.column)
+        .map_err(|_| PqError::column_not_found(path, &options.column))?
+        .data_type()
+        .clone();
+    let invalid = |details: String| {
+        PqError::invalid_predicate(
+            &format!("{} = '{}'", options.column, options.value),
+            details,
+        )
+    };
+    let needle = cast(&StringArray::from(vec![options.value.as_str()]), &data_type)
+        .map_err(|error| invalid(error.to_string()))?;
+    if needle.is_null(0) {
+        return Err(invalid(format!(
+            "cannot compare {data_type} with '{}'",
+            options.value
+        )));
+    }
+    let needle = Scalar::new(needle);
+
+    let reader = builder
+        .with_row_groups(row_groups.to_vec())
+        .with_projection(projection)
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| super::parquet::build_error(path, error))?;
+
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| super::parquet::decode_error(path, &error))?;
+        let matches = eq(batch.column(0), &needle).map_err(|error| invalid(error.to_string()))?;
+        if matches.true_count() > 0 {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
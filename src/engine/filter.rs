@@ -0,0 +1,438 @@
+//! Rows matching a `--where` predicate
+//!
+//! Row groups whose statistics rule the predicate out are never read, and counting
+//! skips decoding those whose statistics prove it for every row. Each `AND`ed part
+//! of the predicate that names a single column becomes an [`ArrowPredicate`], so the
+//! reader decodes that column first and the others only for rows that pass. Parts that
+//! name several columns are checked on the decoded batches afterwards.
+
+use super::parquet::{build_error, decode_error, reader_builder, selected_indices};
+use crate::error::PqError;
+use crate::model::{ColumnCase, MatchCount, PruneDecision};
+use crate::predicate::{CompareOp, Predicate};
+use crate::Result;
+use arrow::array::{
+    ArrayRef, BooleanArray, Float64Array, RecordBatch, RecordBatchReader, Scalar, StringArray,
+};
+use arrow::compute::kernels::cmp;
+use arrow::compute::{
+    and_kleene, cast, cast_with_options, filter_record_batch, is_not_null, is_null,
+};
+use arrow::compute::{or_kleene, CastOptions};
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::ArrowError;
+use parquet::arrow::arrow_reader::{ArrowPredicate, ArrowPredicateFn, RowFilter};
+use parquet::arrow::{parquet_column, ProjectionMask};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Arc;
+
+type Batches = Box<dyn Iterator<Item = Result<RecordBatch>>>;
+
+/// The rows of `path` matching `predicate`, with `columns` selected as `read_head`
+/// selects them (every column when empty), or no columns at all when `None`.
+pub fn matching_batches(
+    path: &Path,
+    text: &str,
+    predicate: &Predicate,
+    columns: Option<&[String]>,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<(SchemaRef, Batches)> {
+    let row_groups = super::prune::prune_file(path, text, predicate)?
+        .row_groups
+        .into_iter()
+        .filter(|group| group.decision == PruneDecision::Read)
+        .map(|group| group.index)
+        .collect();
+    batches_in_row_groups(path, text, predicate, row_groups, columns, case, batch_size)
+}
+
+/// The rows of `row_groups` in `path` matching `predicate`, as [`matching_batches`].
+fn batches_in_row_groups(
+    path: &Path,
+    text: &str,
+    predicate: &Predicate,
+    row_groups: Vec<usize>,
+    columns: Option<&[String]>,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<(SchemaRef, Batches)> {
+    let builder = reader_builder(path)?;
+    let schema = Arc::clone(builder.schema());
+    let parquet_schema = builder.parquet_schema();
+    let mut pushed: Vec<Box<dyn ArrowPredicate>> = Vec::new();
+    let mut remaining = Vec::new();
+    for part in conjuncts(predicate) {
+        let filter = Filter::compile(text, part, &schema)?;
+        let names = part.columns();
+        let leaf = match names.as_slice() {
+            [name] => parquet_column(parquet_schema, &schema, name).map(|(leaf, _)| leaf),
+            _ => None,
+        };
+        match leaf {
+            Some(leaf) => pushed.push(Box::new(ArrowPredicateFn::new(
+                ProjectionMask::leaves(parquet_schema, [leaf]),
+                move |batch| filter.evaluate(&batch),
+            ))),
+            None => remaining.push(filter),
+        }
+    }
+
+    // The selected columns plus any the remaining parts need, in file order
+    let selected = match columns {
+        Some([]) => (0..schema.fields().len()).collect(),
+        Some(selectors) => selected_indices(path, &builder, selectors, case)?,
+        None => Vec::new(),
+    };
+    let mut read: Vec<usize> = selected.clone();
+    for name in remaining.iter().flat_map(Filter::columns) {
+        read.extend(schema.index_of(name).ok());
+    }
+    read.sort_unstable();
+    read.dedup();
+    let positions: Vec<usize> = selected
+        .iter()
+        .filter_map(|index| read.binary_search(index).ok())
+        .collect();
+
+    let projection = ProjectionMask::roots(parquet_schema, read.iter().copied());
+    let reader = builder
+        .with_projection(projection)
+        .with_row_groups(row_groups)
+        .with_row_filter(RowFilter::new(pushed))
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    let output_schema = Arc::new(
+        reader
+            .schema()
+            .project(&positions)
+            .map_err(|error| PqError::corrupted(path, &error))?,
+    );
+
+    let path = path.to_path_buf();
+    let batches = reader.map(move |batch| {
+        let mut batch = batch.map_err(|error| decode_error(&path, &error))?;
+        for filter in &remaining {
+            let matches = filter
+                .evaluate(&batch)
+                .map_err(|error| PqError::corrupted(&path, &error))?;
+            batch = filter_record_batch(&batch, &matches)
+                .map_err(|error| PqError::corrupted(&path, &error))?;
+        }
+        batch
+            .project(&positions)
+            .map_err(|error| PqError::corrupted(&path, &error))
+    });
+    Ok((output_schema, Box::new(batches)))
+}
+
+/// The first `rows` matching rows, stopping once they are found.
+pub fn head_matching(
+    path: &Path,
+    text: &str,
+    predicate: &Predicate,
+    rows: usize,
+    columns: &[String],
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<Vec<RecordBatch>> {
+    let (schema, batches) = matching_batches(
+        path,
+        text,
+        predicate,
+        Some(columns),
+        case,
+        rows.clamp(1, batch_size),
+    )?;
+    let mut kept = Vec::new();
+    let mut remaining = rows;
+    for batch in batches {
+        if remaining == 0 {
+            break;
+        }
+        let batch = batch?;
+        let batch = batch.slice(0, batch.num_rows().min(remaining));
+        remaining -= batch.num_rows();
+        kept.push(batch);
+    }
+    Ok(non_empty(schema, kept))
+}
+
+/// The last `rows` matching rows. Every row group the statistics allow is read, but
+/// only enough batches to hold `rows` are kept.
+pub fn tail_matching(
+    path: &Path,
+    text: &str,
+    predicate: &Predicate,
+    rows: usize,
+    columns: &[String],
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<Vec<RecordBatch>> {
+    let (schema, batches) =
+        matching_batches(path, text, predicate, Some(columns), case, batch_size)?;
+    let mut kept = VecDeque::new();
+    let mut kept_rows = 0usize;
+    for batch in batches {
+        let batch = batch?;
+        kept_rows += batch.num_rows();
+        kept.push_back(batch);
+        while kept
+            .front()
+            .is_some_and(|front| kept_rows - front.num_rows() >= rows)
+        {
+            kept_rows -= kept.pop_front().map_or(0, |front| front.num_rows());
+        }
+    }
+    if let Some(front) = kept.front_mut() {
+        let extra = kept_rows.saturating_sub(rows);
+        *front = front.slice(extra, front.num_rows() - extra);
+    }
+    Ok(non_empty(schema, kept.into()))
+}
+
+/// An empty batch stands in for no matches, so output still knows its columns.
+fn non_empty(schema: SchemaRef, batches: Vec<RecordBatch>) -> Vec<RecordBatch> {
+    if batches.is_empty() {
+        return vec![RecordBatch::new_empty(schema)];
+    }
+    batches
+}
+
+/// How many rows of `path` match `predicate`. Row groups whose statistics prove every
+/// row matches are counted from the footer; the rest the statistics allow are decoded,
+/// only the columns the predicate names.
+pub fn count_matching(
+    path: &Path,
+    text: &str,
+    predicate: &Predicate,
+    batch_size: usize,
+) -> Result<MatchCount> {
+    let report = super::prune::prune_file(path, text, predicate)?;
+    let mut metadata_rows = 0u64;
+    let mut scanned_rows = 0u64;
+    let mut scanned = Vec::new();
+    for group in &report.row_groups {
+        let rows = u64::try_from(group.num_rows).unwrap_or_default();
+        match group.decision {
+            PruneDecision::Skip => {}
+            PruneDecision::Read if group.matches_all => metadata_rows += rows,
+            PruneDecision::Read => {
+                scanned_rows += rows;
+                scanned.push(group.index);
+            }
+        }
+    }
+
+    let row_groups_scanned = scanned.len();
+    let mut rows = metadata_rows;
+    if !scanned.is_empty() {
+        let (_, batches) = batches_in_row_groups(
+            path,
+            text,
+            predicate,
+            scanned,
+            None,
+            ColumnCase::default(),
+            batch_size,
+        )?;
+        for batch in batches {
+            rows += batch?.num_rows() as u64;
+        }
+    }
+    Ok(MatchCount {
+        path: path.to_path_buf(),
+        rows,
+        metadata_rows,
+        scanned_rows,
+        row_groups_scanned,
+        row_groups: report.row_groups.len(),
+    })
+}
+
+fn conjuncts(predicate: &Predicate) -> Vec<&Predicate> {
+    match predicate {
+        Predicate::And(children) => children.iter().flat_map(conjuncts).collect(),
+        _ => vec![predicate],
+    }
+}
+
+/// A predicate with its literals cast to their columns' types once, up front.
+enum Filter {
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: ArrayRef,
+    },
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+}
+
+impl Filter {
+    fn compile(text: &str, predicate: &Predicate, schema: &Schema) -> Result<Self> {
+        let compile_all = |children: &[Predicate]| {
+            children
+                .iter()
+                .map(|child| Self::compile(text, child, schema))
+                .collect::<Result<Vec<_>>>()
+        };
+        Ok(match predicate {
+            Predicate::And(children) => Self::And(compile_all(children)?),
+            Predicate::Or(children) => Self::Or(compile_all(children)?),
+            Predicate::IsNull { column, negated } => Self::IsNull {
+                column: column.clone(),
+                negated: *negated,
+            },
+            Predicate::Compare { column, op, value } => {
+                let data_type = schema
+                    .field_with_name(column)
+                    .map_err(|error| PqError::invalid_predicate(text, error))?
+                    .data_type();
+                let options = CastOptions {
+                    safe: false,
+                    ..Default::default()
+                };
+                let literal = StringArray::from(vec![value.text()]);
+                let value = match cast_with_options(&literal, data_type, &options) {
+                    Ok(typed) => typed,
+                    // An integer column meets a fractional or out-of-range number as
+                    // Float64, so `n > 1.5` keeps 2 and up instead of failing
+                    Err(_) if data_type.is_integer() && value.as_float().is_some() => {
+                        Arc::new(Float64Array::from(vec![value.as_float()])) as ArrayRef
+                    }
+                    Err(_) => {
+                        return Err(PqError::invalid_predicate(
+                            text,
+                            format!(
+                                "column '{column}': cannot compare {data_type} with '{}'",
+                                value.text()
+                            ),
+                        ))
+                    }
+                };
+                Self::Compare {
+                    column: column.clone(),
+                    op: *op,
+                    value,
+                }
+            }
+        })
+    }
+
+    fn columns(&self) -> Vec<&str> {
+        match self {
+            Self::And(children) | Self::Or(children) => {
+                children.iter().flat_map(Self::columns).collect()
+            }
+            Self::Compare { column, .. } | Self::IsNull { column, .. } => vec![column.as_str()],
+        }
+    }
+
+    /// Nulls compare as unknown, and the reader keeps only rows that are `true`.
+    fn evaluate(&self, batch: &RecordBatch) -> std::result::Result<BooleanArray, ArrowError> {
+        let column = |name: &str| {
+            batch.column_by_name(name).ok_or_else(|| {
+                ArrowError::SchemaError(format!("column '{name}' is not in the batch"))
+            })
+        };
+        match self {
+            Self::And(children) => fold(children, batch, and_kleene),
+            Self::Or(children) => fold(children, batch, or_kleene),
+            Self::IsNull {
+                column: name,
+                negated,
+            } => {
+                let array = column(name)?;
+                if *negated {
+                    is_not_null(array)
+                } else {
+                    is_null(array)
+                }
+            }
+            Self::Compare {
+                column: name,
+                op,
+                value,
+            } => {
+                let mut array = Arc::clone(column(name)?);
+                if array.data_type() != value.data_type() {
+                    array = cast(&array, value.data_type())?;
+                }
+                let value = Scalar::new(value);
+                match op {
+                    CompareOp::Eq => cmp::eq(&array, &value),
+                    CompareOp::NotEq => cmp::neq(&array, &value),
+                    CompareOp::Lt => cmp::lt(&array, &value),
+                    CompareOp::LtEq => cmp::lt_eq(&array, &value),
+                    CompareOp::Gt => cmp::gt(&array, &value),
+                    CompareOp::GtEq => cmp::gt_eq(&array, &value),
+                }
+            }
+        }
+    }
+}
+
+fn fold(
+    children: &[Filter],
+    batch: &RecordBatch,
+    combine: fn(&BooleanArray, &BooleanArray) -> std::result::Result<BooleanArray, ArrowError>,
+) -> std::result::Result<BooleanArray, ArrowError> {
+    let mut children = children.iter().map(|child| child.evaluate(batch));
+    let Some(first) = children.next() else {
+        return Ok(BooleanArray::from(vec![true; batch.num_rows()]));
+    };
+    children.try_fold(first?, |result, child| combine(&result, &child?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field};
+
+    #[test]
+    fn evaluates_comparisons_with_sql_null_semantics() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(vec![Some(1), Some(5), None])) as ArrayRef,
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])) as ArrayRef,
+            ],
+        )?;
+        let evaluate = |text: &str| -> Result<Vec<Option<bool>>> {
+            let filter = Filter::compile(text, &Predicate::parse(text)?, &schema)?;
+            Ok(filter.evaluate(&batch)?.iter().collect())
+        };
+
+        assert_eq!(evaluate("id >= 2")?, vec![Some(false), Some(true), None]);
+        assert_eq!(
+            evaluate("id = 1 OR name = 'c'")?,
+            vec![Some(true), None, Some(true)]
+        );
+        assert_eq!(
+            evaluate("name IS NOT NULL AND id > 0")?,
+            vec![Some(true), Some(false), None]
+        );
+        assert!(matches!(
+            Filter::compile("id = 'x'", &Predicate::parse("id = 'x'")?, &schema),
+            Err(PqError::InvalidPredicate { .. })
+        ));
+
+        // Fractional and out-of-range numbers compare with integers as floats
+        assert_eq!(evaluate("id > 1.5")?, vec![Some(false), Some(true), None]);
+        assert_eq!(evaluate("id = 1.5")?, vec![Some(false), Some(false), None]);
+        assert_eq!(evaluate("id < 1e30")?, vec![Some(true), Some(true), None]);
+        assert_eq!(evaluate("id >= -1e30")?, vec![Some(true), Some(true), None]);
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+//! Single fields of the Parquet footer, read without decoding the rest of it
+
+use parquet::format::FileMetaData;
+use parquet::thrift::TSerializable;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom, Take};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use thrift::protocol::{TCompactInputProtocol, TInputProtocol, TType};
+
+const MAGIC: &[u8; 4] = b"PAR1";
+/// Trailing magic of files written with an encrypted footer.
+const ENCRYPTED_MAGIC: &[u8; 4] = b"PARE";
+/// How recently a file without its footer must have changed to count as mid-write.
+const RECENT_WRITE: Duration = Duration::from_secs(5);
+/// Field id of `num_rows` in the thrift `FileMetaData` struct.
+const NUM_ROWS_FIELD: i16 = 3;
+
+type FooterProtocol = TCompactInputProtocol<Take<BufReader<File>>>;
+
+enum Footer {
+    Plaintext(FooterProtocol),
+    Encrypted,
+}
+
+/// The row count from `FileMetaData`, skipping the schema before it and never reading
+/// the row group metadata after it, which dominates the footer of wide files. `None`
+/// for encrypted footers or anything unexpected, so callers can use the full reader.
+pub(super) fn row_count(path: &Path) -> Option<i64> {
+    let Footer::Plaintext(mut protocol) = open(path)? else {
+        return None;
+    };
+    protocol.read_struct_begin().ok()?;
+    loop {
+        let field = protocol.read_field_begin().ok()?;
+        match (field.field_type, field.id) {
+            (TType::Stop, _) => return None,
+            (TType::I64, Some(NUM_ROWS_FIELD)) => return protocol.read_i64().ok(),
+            (field_type, _) => protocol.skip(field_type).ok()?,
+        }
+    }
+}
+
+/// Which statistics bounds a writer truncated, as `is_min_value_exact = false` or
+/// `is_max_value_exact = false` say.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Truncated {
+    pub min: bool,
+    pub max: bool,
+}
+
+/// Truncated bounds per row group and leaf column. The parquet crate reads a missing
+/// exactness flag as inexact, while older writers never set it, so the flags are read
+/// here where missing still means exact. Empty when the footer cannot be read this way.
+pub(crate) fn truncated_bounds(path: &Path) -> Vec<Vec<Truncated>> {
+    let Some(Footer::Plaintext(mut protocol)) = open(path) else {
+        return Vec::new();
+    };
+    let Ok(metadata) = FileMetaData::read_from_in_protocol(&mut protocol) else {
+        return Vec::new();
+    };
+    metadata
+        .row_groups
+        .iter()
+        .map(|row_group| {
+            row_group
+                .columns
+                .iter()
+                .map(|chunk| {
+                    let statistics = chunk
+                        .meta_data
+                        .as_ref()
+                        .and_then(|meta| meta.statistics.as_ref());
+                    Truncated {
+                        min: statistics.and_then(|stats| stats.is_min_value_exact) == Some(false),
+                        max: statistics.and_then(|stats| stats.is_max_value_exact) == Some(false),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether the file uses Parquet modular encryption, with either an encrypted footer
+/// or a plaintext one naming an encryption algorithm. The second case decodes the whole
+/// footer, since the algorithm comes after the row groups and thrift cannot skip their
+/// binary statistics without reading them as text; this is meant for explaining a
+/// failure rather than for every open.
+pub(crate) fn is_encrypted(path: &Path) -> bool {
+    match open(path) {
+        Some(Footer::Encrypted) => true,
+        Some(Footer::Plaintext(mut protocol)) => FileMetaData::read_from_in_protocol(&mut protocol)
+            .is_ok_and(|metadata| metadata.encryption_algorithm.is_some()),
+        None => false,
+    }
+}
+
+/// Whether `path` looks like a Parquet file another process is still writing: it starts
+/// with the magic number, has no footer after it yet, and changed within the last few
+/// seconds. An older file in that state is simply truncated.
+pub(crate) fn is_being_written(path: &Path) -> bool {
+    let Ok(mut file) = File::open(path) else {
+        return false;
+    };
+    let Ok(metadata) = file.metadata() else {
+        return false;
+    };
+    let recent = metadata
+        .modified()
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age <= RECENT_WRITE);
+    let mut head = [0u8; 4];
+    recent && file.read_exact(&mut head).is_ok() && &head == MAGIC && !has_footer(&mut file)
+}
+
+/// Whether the file ends in a footer's trailing magic, with room for the leading one.
+fn has_footer(file: &mut File) -> bool {
+    let mut magic = [0u8; 4];
+    let long_enough = file.metadata().is_ok_and(|metadata| metadata.len() >= 12);
+    long_enough
+        && file.seek(SeekFrom::End(-4)).is_ok()
+        && file.read_exact(&mut magic).is_ok()
+        && (&magic == MAGIC || &magic == ENCRYPTED_MAGIC)
+}
+
+/// A thrift reader positioned at the start of the footer, or `Encrypted` when the
+/// footer itself is encrypted and cannot be read.
+fn open(path: &Path) -> Option<Footer> {
+    let mut file = File::open(path).ok()?;
+    let file_len = file.metadata().ok()?.len();
+
+    let mut tail = [0u8; 8];
+    file.seek(SeekFrom::End(-8)).ok()?;
+    file.read_exact(&mut tail).ok()?;
+    let (length, magic) = tail.split_at(4);
+    if magic == ENCRYPTED_MAGIC {
+        return Some(Footer::Encrypted);
+    }
+    if magic != MAGIC {
+        return None;
+    }
+    let footer_len = u64::from(u32::from_le_bytes(length.try_into().ok()?));
+    let footer_start = file_len.checked_sub(footer_len.checked_add(8)?)?;
+    if footer_start < MAGIC.len() as u64 {
+        return None;
+    }
+
+    file.seek(SeekFrom::Start(footer_start)).ok()?;
+    Some(Footer::Plaintext(TCompactInputProtocol::new(
+        BufReader::new(file).take(footer_len),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use std::path::PathBuf;
+
+    fn fixture() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/test.parquet")
+    }
+
+    #[test]
+    fn reads_num_rows_without_the_full_footer() {
+        assert_eq!(row_count(&fixture()), Some(5));
+    }
+
+    #[test]
+    fn gives_up_on_files_that_are_not_parquet() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("pq_footer_{}.parquet", std::process::id()));
+        std::fs::write(&path, b"PAR1 not really parquet PAR2")?;
+        assert_eq!(row_count(&path), None);
+        std::fs::write(&path, b"PAR1\xff\xff\xff\x00PAR1")?;
+        assert_eq!(row_count(&path), None);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn detects_encrypted_footers() -> Result<()> {
+        assert!(!is_encrypted(&fixture()));
+        let path =
+            std::env::temp_dir().join(format!("pq_encrypted_{}.parquet", std::process::id()));
+        let mut bytes = std::fs::read(fixture())?;
+        let magic_start = bytes.len() - ENCRYPTED_MAGIC.len();
+        bytes.truncate(magic_start);
+        bytes.extend_from_slice(ENCRYPTED_MAGIC);
+        std::fs::write(&path, bytes)?;
+        assert!(is_encrypted(&path));
+        assert_eq!(row_count(&path), None);
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn detects_files_whose_footer_is_not_written_yet() -> Result<()> {
+        assert!(!is_being_written(&fixture()));
+        let path =
+            std::env::temp_dir().join(format!("pq_being_written_{}.parquet", std::process::id()));
+        let bytes = std::fs::read(fixture())?;
+        std::fs::write(&path, &bytes[..bytes.len() / 2])?;
+        assert!(is_being_written(&path));
+        std::fs::write(&path, b"PAR1")?;
+        assert!(is_being_written(&path));
+        std::fs::write(&path, &bytes)?;
+        assert!(!is_being_written(&path));
+        std::fs::write(&path, b"not parquet at all")?;
+        assert!(!is_being_written(&path));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
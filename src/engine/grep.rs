@@ -0,0 +1,283 @@
+use crate::error::PqError;
+use crate::model::{ColumnCase, GrepOptions};
+use crate::Result;
+use arrow::array::{Array, AsArray, BooleanArray, RecordBatch};
+use arrow::compute::{cast, filter_record_batch};
+use arrow::datatypes::{DataType, Schema};
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use parquet::arrow::parquet_column;
+use parquet::file::metadata::ParquetMetaData;
+use parquet::file::statistics::Statistics;
+use regex::{Regex, RegexBuilder};
+use std::path::Path;
+
+#[derive(Debug)]
+pub struct Matcher {
+    kind: MatcherKind,
+}
+
+#[derive(Debug)]
+enum MatcherKind {
+    Literal { needle: String, ignore_case: bool },
+    Regex(Regex),
+}
+
+impl Matcher {
+    pub fn new(options: &GrepOptions) -> Result<Self> {
+        let kind = if options.regex {
+            let regex = RegexBuilder::new(&options.pattern)
+                .case_insensitive(options.ignore_case)
+                .build()
+                .map_err(|error| PqError::invalid_pattern(&options.pattern, error))?;
+            MatcherKind::Regex(regex)
+        } else {
+            let needle = if options.ignore_case {
+                options.pattern.to_lowercase()
+            } else {
+                options.pattern.clone()
+            };
+            MatcherKind::Literal {
+                needle,
+                ignore_case: options.ignore_case,
+            }
+        };
+        Ok(Self { kind })
+    }
+
+    /// Strings are searched for the pattern anywhere in the value.
+    fn matches_text(&self, value: &str) -> bool {
+        match &self.kind {
+            MatcherKind::Literal {
+                needle,
+                ignore_case: true,
+            } => value.to_lowercase().contains(needle.as_str()),
+            MatcherKind::Literal { needle, .. } => value.contains(needle.as_str()),
+            MatcherKind::Regex(regex) => regex.is_match(value),
+        }
+    }
+
+    /// Non-string values are compared by display form; literals must match the whole value.
+    fn matches_display(&self, value: &str) -> bool {
+        match &self.kind {
+            MatcherKind::Literal {
+                needle,
+                ignore_case: true,
+            } => value.to_lowercase() == *needle,
+            MatcherKind::Literal { needle, .. } => value == needle,
+            MatcherKind::Regex(regex) => regex.is_match(value),
+        }
+    }
+}
+
+pub fn grep_file(
+    path: &Path,
+    options: &GrepOptions,
+    matcher: &Matcher,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<Vec<RecordBatch>> {
+    if options.max_matches == Some(0) {
+        return Ok(Vec::new());
+    }
+
+    let builder = super::parquet::reader_builder(path)?;
+    let columns = searched_columns(path, builder.schema(), &options.columns, case)?;
+    if columns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let row_groups = prunable_row_groups(builder.schema(), builder.metadata(), &columns, matcher);
+    let builder = match row_groups {
+        Some(row_groups) if row_groups.is_empty() => return Ok(Vec::new()),
+        Some(row_groups) => builder.with_row_groups(row_groups),
+        None => builder,
+    };
+    let reader = builder
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| super::parquet::build_error(path, error))?;
+
+    let mut batches = Vec::new();
+    let mut total_matches = 0usize;
+
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| super::parquet::decode_error(path, &error))?;
+        let mask = match_mask(&batch, &columns, matcher)?;
+        let matched = filter_record_batch(&batch, &mask)?;
+        if matched.num_rows() == 0 {
+            continue;
+        }
+
+        let remaining = options
+            .max_matches
+            .map_or(usize::MAX, |limit| limit.saturating_sub(total_matches));
+        let matched = if matched.num_rows() > remaining {
+            matched.slice(0, remaining)
+        } else {
+            matched
+        };
+
+        total_matches += matched.num_rows();
+        batches.push(matched);
+        if options
+            .max_matches
+            .is_some_and(|limit| total_matches >= limit)
+        {
+            break;
+        }
+    }
+
+    Ok(batches)
+}
+
+fn searched_columns(
+    path: &Path,
+    schema: &Schema,
+    requested: &[String],
+    case: ColumnCase,
+) -> Result<Vec<String>> {
+    if requested.is_empty() {
+        return Ok(schema
+            .fields()
+            .iter()
+            .filter(|field| is_string_type(field.data_type()))
+            .map(|field| field.name().clone())
+            .collect());
+    }
+
+    let names: Vec<&str> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    Ok(
+        crate::selector::select_columns(path, &names, requested, case)?
+            .into_iter()
+            .filter_map(|index| names.get(index).map(ToString::to_string))
+            .collect(),
+    )
+}
+
+fn is_string_type(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Dictionary(_, value) => is_string_type(value),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View => true,
+        _ => false,
+    }
+}
+
+fn match_mask(batch: &RecordBatch, columns: &[String], matcher: &Matcher) -> Result<BooleanArray> {
+    let mut mask = vec![false; batch.num_rows()];
+    let options = FormatOptions::default();
+
+    for column in columns {
+        let Some(array) = batch.column_by_name(column) else {
+            continue;
+        };
+        // Match the values dictionary keys point at, not the keys
+        let array = match array.data_type() {
+            DataType::Dictionary(_, value) => &cast(array, value)?,
+            _ => array,
+        };
+
+        if let Some(strings) = array.as_string_opt::<i32>() {
+            mark_matches(&mut mask, array.as_ref(), |row| {
+                matcher.matches_text(strings.value(row))
+            });
+        } else if let Some(strings) = array.as_string_opt::<i64>() {
+            mark_matches(&mut mask, array.as_ref(), |row| {
+                matcher.matches_text(strings.value(row))
+            });
+        } else if let Some(strings) = array.as_string_view_opt() {
+            mark_matches(&mut mask, array.as_ref(), |row| {
+                matcher.matches_text(strings.value(row))
+            });
+        } else {
+            let formatter = ArrayFormatter::try_new(array.as_ref(), &options)?;
+            mark_matches(&mut mask, array.as_ref(), |row| {
+                matcher.matches_display(&formatter.value(row).to_string())
+            });
+        }
+    }
+
+    Ok(BooleanArray::from(mask))
+}
+
+fn mark_matches(mask: &mut [bool], array: &dyn Array, matches: impl Fn(usize) -> bool) {
+    for (row, matched) in mask.iter_mut().enumerate() {
+        if !*matched && array.is_valid(row) && matches(row) {
+            *matched = true;
+        }
+    }
+}
+
+/// Row groups that may contain a literal match when searching a single numeric column.
+///
+/// Returns `None` when statistics cannot be used and every row group must be read.
+fn prunable_row_groups(
+    schema: &Schema,
+    metadata: &ParquetMetaData,
+    columns: &[String],
+    matcher: &Matcher,
+) -> Option<Vec<usize>> {
+    let ([column], MatcherKind::Literal { needle, .. }) = (columns, &matcher.kind) else {
+        return None;
+    };
+
+    let parquet_schema = metadata.file_metadata().schema_descr();
+    let (leaf_index, field) = parquet_column(parquet_schema, schema, column)?;
+    let target = match field.data_type() {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
+            Target::Integer(needle.parse().ok()?)
+        }
+        DataType::Float32 | DataType::Float64 => {
+            Target::Float(needle.parse().ok().filter(|value: &f64| !value.is_nan())?)
+        }
+        _ => return None,
+    };
+
+    Some(
+        (0..metadata.num_row_groups())
+            .filter(|index| {
+                metadata
+                    .row_group(*index)
+                    .column(leaf_index)
+                    .statistics()
+                    .is_none_or(|statistics| target.may_be_within(statistics))
+            })
+            .collect(),
+    )
+}
+
+enum Target {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Target {
+    fn may_be_within(&self, statistics: &Statistics) -> bool {
+        match (self, statistics) {
+            (Self::Integer(value), Statistics::Int32(stats)) => within(
+                *value,
+                stats.min_opt().copied().map(i64::from),
+                stats.max_opt().copied().map(i64::from),
+            ),
+            (Self::Integer(value), Statistics::Int64(stats)) => {
+                within(*value, stats.min_opt().copied(), stats.max_opt().copied())
+            }
+            (Self::Float(value), Statistics::Float(stats)) => within(
+                *value,
+                stats.min_opt().copied().map(f64::from),
+                stats.max_opt().copied().map(f64::from),
+            ),
+            (Self::Float(value), Statistics::Double(stats)) => {
+                within(*value, stats.min_opt().copied(), stats.max_opt().copied())
+            }
+            _ => true,
+        }
+    }
+}
+
+fn within<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+}
@@ -0,0 +1,95 @@
+use crate::error::PqError;
+use crate::model::{ColumnCase, GroupCount, GroupCountOptions, GroupCountResult};
+use crate::Result;
+use arrow::array::RecordBatch;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Default)]
+pub struct GroupCounter {
+    counts: HashMap<Vec<Option<String>>, u64>,
+    total_rows: u64,
+}
+
+impl GroupCounter {
+    pub fn add_file(
+        &mut self,
+        path: &Path,
+        columns: &[String],
+        case: ColumnCase,
+        batch_size: usize,
+    ) -> Result<()> {
+        let builder = super::parquet::reader_builder(path)?;
+        let columns = &super::parquet::root_names(path, &builder, columns, case)?;
+        let projection =
+            super::parquet::root_projection(path, &builder, columns, ColumnCase::Sensitive)?;
+        let reader = builder
+            .with_projection(projection)
+            .with_batch_size(batch_size)
+            .build()
+            .map_err(|error| super::parquet::build_error(path, error))?;
+
+        for batch_result in reader {
+            let batch = batch_result.map_err(|error| super::parquet::decode_error(path, &error))?;
+            self.add_batch(path, columns, &batch)?;
+        }
+
+        Ok(())
+    }
+
+    fn add_batch(&mut self, path: &Path, columns: &[String], batch: &RecordBatch) -> Result<()> {
+        let options = FormatOptions::default();
+        let arrays = columns
+            .iter()
+            .map(|column| {
+                batch
+                    .column_by_name(column)
+                    .ok_or_else(|| PqError::column_not_found(path, column))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let formatters = arrays
+            .iter()
+            .map(|array| ArrayFormatter::try_new(array.as_ref(), &options))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        for row in 0..batch.num_rows() {
+            let key = arrays
+                .iter()
+                .zip(&formatters)
+                .map(|(array, formatter)| {
+                    (!array.is_null(row)).then(|| formatter.value(row).to_string())
+                })
+                .collect();
+            *self.counts.entry(key).or_insert(0) += 1;
+        }
+        self.total_rows += batch.num_rows() as u64;
+
+        Ok(())
+    }
+
+    pub fn finish(self, columns: Vec<String>, options: GroupCountOptions) -> GroupCountResult {
+        let total_groups = self.counts.len();
+        let mut groups = self
+            .counts
+            .into_iter()
+            .map(|(values, rows)| GroupCount { values, rows })
+            .collect::<Vec<_>>();
+        groups.sort_by(|left, right| {
+            right
+                .rows
+                .cmp(&left.rows)
+                .then_with(|| left.values.cmp(&right.values))
+        });
+        if let Some(limit) = options.limit {
+            groups.truncate(limit);
+        }
+
+        GroupCountResult {
+            columns,
+            groups,
+            total_groups,
+            total_rows: self.total_rows,
+        }
+    }
+}
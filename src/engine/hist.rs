@@ -0,0 +1,356 @@
+//! Histograms of one numeric or temporal column, streamed from a projection
+
+use super::parquet::{build_error, decode_error, reader_builder, root_names, root_projection};
+use crate::error::PqError;
+use crate::model::{ColumnCase, Histogram, HistogramBucket, HistogramOptions};
+use crate::Result;
+use arrow::array::{Array, ArrayRef, AsArray, Int64Array, StringArray};
+use arrow::compute::{cast, cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Float64Type};
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Bucket `options.column` across `paths`. Bounds not given in the options come from a
+/// first pass over the finite values; both passes decode only that column. Infinities
+/// are counted as below or above the bounds.
+pub fn histogram(
+    paths: &[&Path],
+    options: &HistogramOptions,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<Histogram> {
+    let Some(first) = paths.first() else {
+        return Err(PqError::NoInputFiles);
+    };
+    let data_type = column_type(first, &options.column, case)?;
+    let scale = Scale::new(first, &options.column, &data_type)?;
+
+    let mut min = options
+        .min
+        .as_deref()
+        .map(|text| scale.parse_bound(first, &options.column, text))
+        .transpose()?;
+    let mut max = options
+        .max
+        .as_deref()
+        .map(|text| scale.parse_bound(first, &options.column, text))
+        .transpose()?;
+    if min.is_none() || max.is_none() {
+        let (data_min, data_max) = data_bounds(paths, options, case, batch_size, &scale)?;
+        min = min.or(data_min);
+        max = max.or(data_max);
+    }
+
+    let mut histogram = Histogram {
+        column: options.column.clone(),
+        buckets: Vec::new(),
+        null_count: 0,
+        nan_count: 0,
+        below_min: 0,
+        above_max: 0,
+    };
+    let (Some(min), Some(max)) = (min, max) else {
+        // Nothing but nulls and NaNs: count them, with no buckets.
+        for_each_batch(
+            paths,
+            options,
+            case,
+            batch_size,
+            &scale,
+            |_, nulls, nans| {
+                histogram.null_count += nulls;
+                histogram.nan_count += nans;
+            },
+        )?;
+        return Ok(histogram);
+    };
+    if min > max {
+        return Err(PqError::invalid_histogram(format!(
+            "--min {} is greater than --max {}",
+            scale.label(min),
+            scale.label(max)
+        )));
+    }
+    if options.log_scale && min <= 0.0 {
+        return Err(PqError::invalid_histogram(format!(
+            "log-scale buckets need a positive minimum, but the minimum is {}; pass --min",
+            scale.label(min)
+        )));
+    }
+
+    let buckets = if min == max {
+        1
+    } else {
+        options.buckets.max(1)
+    };
+    let edges = edges(min, max, buckets, options.log_scale);
+    let mut counts = vec![0u64; buckets];
+    for_each_batch(
+        paths,
+        options,
+        case,
+        batch_size,
+        &scale,
+        |values, nulls, nans| {
+            histogram.null_count += nulls;
+            histogram.nan_count += nans;
+            for value in values {
+                if value < min {
+                    histogram.below_min += 1;
+                } else if value > max {
+                    histogram.above_max += 1;
+                } else {
+                    let index = bucket_index(value, min, max, buckets, options.log_scale);
+                    if let Some(count) = counts.get_mut(index) {
+                        *count += 1;
+                    }
+                }
+            }
+        },
+    )?;
+
+    histogram.buckets = edges
+        .windows(2)
+        .zip(counts)
+        .map(|(pair, count)| {
+            let (lower, upper) = match pair {
+                [lower, upper] => (*lower, *upper),
+                _ => (min, max),
+            };
+            HistogramBucket {
+                lower,
+                upper,
+                lower_label: scale.label(lower),
+                upper_label: scale.label(upper),
+                count,
+            }
+        })
+        .collect();
+    Ok(histogram)
+}
+
+fn column_type(path: &Path, column: &str, case: ColumnCase) -> Result<DataType> {
+    let builder = reader_builder(path)?;
+    let fields = builder.schema().fields();
+    let names: Vec<&str> = fields.iter().map(|field| field.name().as_str()).collect();
+    let index = crate::selector::find_column(path, &names, column, case)?;
+    fields
+        .get(index)
+        .map(|field| field.data_type().clone())
+        .ok_or_else(|| PqError::column_not_found(path, column))
+}
+
+fn data_bounds(
+    paths: &[&Path],
+    options: &HistogramOptions,
+    case: ColumnCase,
+    batch_size: usize,
+    scale: &Scale,
+) -> Result<(Option<f64>, Option<f64>)> {
+    let mut bounds: Option<(f64, f64)> = None;
+    for_each_batch(paths, options, case, batch_size, scale, |values, _, _| {
+        for value in values.filter(|value| value.is_finite()) {
+            bounds = Some(match bounds {
+                Some((min, max)) => (min.min(value), max.max(value)),
+                None => (value, value),
+            });
+        }
+    })?;
+    Ok((bounds.map(|(min, _)| min), bounds.map(|(_, max)| max)))
+}
+
+/// Stream the column's non-null, non-NaN values to `visit` batch by batch, with the
+/// batch's null and NaN counts.
+fn for_each_batch(
+    paths: &[&Path],
+    options: &HistogramOptions,
+    case: ColumnCase,
+    batch_size: usize,
+    scale: &Scale,
+    mut visit: impl FnMut(&mut dyn Iterator<Item = f64>, u64, u64),
+) -> Result<()> {
+    let requested = [options.column.clone()];
+    for path in paths {
+        let builder = reader_builder(path)?;
+        let columns = root_names(path, &builder, &requested, case)?;
+        let projection = root_projection(path, &builder, &columns, ColumnCase::Sensitive)?;
+        let name = columns.first().unwrap_or(&options.column);
+        let reader = builder
+            .with_projection(projection)
+            .with_batch_size(batch_size)
+            .build()
+            .map_err(|error| build_error(path, error))?;
+        for batch_result in reader {
+            let batch = batch_result.map_err(|error| decode_error(path, &error))?;
+            let column = batch
+                .column_by_name(name)
+                .ok_or_else(|| PqError::column_not_found(path, name))?;
+            let values = scale.values(path, &options.column, column)?;
+            let values = values.as_primitive::<Float64Type>();
+            let null_count = values.null_count() as u64;
+            let nan_count = values
+                .iter()
+                .flatten()
+                .filter(|value| value.is_nan())
+                .count() as u64;
+            let mut present = values.iter().flatten().filter(|value| !value.is_nan());
+            visit(&mut present, null_count, nan_count);
+        }
+    }
+    Ok(())
+}
+
+fn edges(min: f64, max: f64, buckets: usize, log_scale: bool) -> Vec<f64> {
+    (0..=buckets)
+        .map(|index| {
+            let fraction = index as f64 / buckets as f64;
+            if index == 0 {
+                min
+            } else if index == buckets {
+                max
+            } else if log_scale {
+                (min.ln() + (max.ln() - min.ln()) * fraction).exp()
+            } else {
+                // Interpolated without `max - min`, which overflows for the widest ranges.
+                min * (1.0 - fraction) + max * fraction
+            }
+        })
+        .collect()
+}
+
+/// Buckets are half-open except the last, which also takes `max`.
+fn bucket_index(value: f64, min: f64, max: f64, buckets: usize, log_scale: bool) -> usize {
+    if max <= min {
+        return 0;
+    }
+    let fraction = if log_scale {
+        (value.ln() - min.ln()) / (max.ln() - min.ln())
+    } else {
+        (value / 2.0 - min / 2.0) / (max / 2.0 - min / 2.0)
+    };
+    ((fraction * buckets as f64) as usize).min(buckets - 1)
+}
+
+/// How a column's values map to the numbers being bucketed, and back to labels.
+struct Scale {
+    data_type: DataType,
+    /// The integer type temporal values are stored as; `None` for numeric columns.
+    integer: Option<DataType>,
+}
+
+impl Scale {
+    fn new(path: &Path, column: &str, data_type: &DataType) -> Result<Self> {
+        let integer = match data_type {
+            DataType::Date32 | DataType::Time32(_) => Some(DataType::Int32),
+            DataType::Date64
+            | DataType::Timestamp(..)
+            | DataType::Time64(_)
+            | DataType::Duration(_) => Some(DataType::Int64),
+            data_type if data_type.is_numeric() => None,
+            _ => {
+                return Err(PqError::unsupported_column_type(
+                    path,
+                    column,
+                    data_type,
+                    "histograms need an integer, float, decimal, or temporal column",
+                ))
+            }
+        };
+        Ok(Self {
+            data_type: data_type.clone(),
+            integer,
+        })
+    }
+
+    fn values(&self, path: &Path, column: &str, array: &ArrayRef) -> Result<ArrayRef> {
+        let numbers = match &self.integer {
+            Some(integer) => {
+                cast(array, integer).and_then(|array| cast(&array, &DataType::Float64))
+            }
+            None => cast(array, &DataType::Float64),
+        };
+        numbers.map_err(|error| {
+            PqError::unsupported_column_type(path, column, array.data_type(), error)
+        })
+    }
+
+    /// A `--min` or `--max` given as a number, or as a date or timestamp for temporal
+    /// columns.
+    fn parse_bound(&self, path: &Path, column: &str, text: &str) -> Result<f64> {
+        let strict = CastOptions {
+            safe: false,
+            ..CastOptions::default()
+        };
+        let text_array: ArrayRef = Arc::new(StringArray::from(vec![text]));
+        cast_with_options(&text_array, &self.data_type, &strict)
+            .ok()
+            .and_then(|array| self.values(path, column, &array).ok())
+            .or_else(|| cast_with_options(&text_array, &DataType::Float64, &strict).ok())
+            .and_then(|array| {
+                let values = array.as_primitive_opt::<Float64Type>()?;
+                values.is_valid(0).then(|| values.value(0))
+            })
+            .ok_or_else(|| {
+                PqError::invalid_histogram(format!(
+                    "bound '{text}' is not a value of {column}'s type {}",
+                    self.data_type
+                ))
+            })
+    }
+
+    /// A bucket edge as text: dates and times for temporal columns, trimmed decimals
+    /// otherwise.
+    fn label(&self, value: f64) -> String {
+        let Some(integer) = &self.integer else {
+            return number_label(value);
+        };
+        let edge: ArrayRef = Arc::new(Int64Array::from(vec![value.round() as i64]));
+        cast(&edge, integer)
+            .and_then(|edge| cast(&edge, &self.data_type))
+            .ok()
+            .and_then(|edge| {
+                ArrayFormatter::try_new(edge.as_ref(), &FormatOptions::default())
+                    .ok()
+                    .map(|formatter| formatter.value(0).to_string())
+            })
+            .unwrap_or_else(|| number_label(value))
+    }
+}
+
+fn number_label(value: f64) -> String {
+    if value.abs() >= 1e15 {
+        return format!("{value:e}");
+    }
+    if value.fract() == 0.0 {
+        return format!("{value:.0}");
+    }
+    let text = format!("{value:.6}");
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_values_in_half_open_buckets() {
+        assert_eq!(edges(0.0, 10.0, 4, false), vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+        let indexes: Vec<usize> = [0.0, 2.4, 2.5, 9.9, 10.0]
+            .into_iter()
+            .map(|value| bucket_index(value, 0.0, 10.0, 4, false))
+            .collect();
+        assert_eq!(indexes, vec![0, 0, 1, 3, 3]);
+    }
+
+    #[test]
+    fn spaces_log_buckets_geometrically() {
+        let edges: Vec<String> = edges(1.0, 1000.0, 3, true)
+            .into_iter()
+            .map(number_label)
+            .collect();
+        assert_eq!(edges, vec!["1", "10", "100", "1000"]);
+        assert_eq!(bucket_index(99.0, 1.0, 1000.0, 3, true), 1);
+        assert_eq!(bucket_index(100.5, 1.0, 1000.0, 3, true), 2);
+    }
+}
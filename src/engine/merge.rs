@@ -0,0 +1,233 @@
+//! K-way merge of inputs that are each sorted by one column
+//!
+//! Each input streams one batch at a time; the next output row is always the smallest
+//! key among the inputs' current rows, with ties going to the earlier input, so equal
+//! keys keep their input order.
+
+use super::parquet::{build_error, decode_error, merge_schema, reader_builder, writer_properties};
+use crate::error::PqError;
+use crate::model::{ColumnCase, ParquetWriteOptions, SortedMergeOptions};
+use crate::Result;
+use arrow::array::{Array, ArrayRef, RecordBatch};
+use arrow::compute::{interleave, SortOptions};
+use arrow::datatypes::SchemaRef;
+use arrow::row::{OwnedRow, RowConverter, Rows, SortField};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
+use parquet::arrow::{parquet_column, ArrowWriter};
+use parquet::format::SortingColumn;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn merge_sorted_files(
+    paths: &[&Path],
+    output: &Path,
+    write: &ParquetWriteOptions,
+    sort: &SortedMergeOptions,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<()> {
+    let schema = merge_schema(paths)?;
+    let first = paths.first().ok_or(PqError::NoInputFiles)?;
+    let names: Vec<&str> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    let key = crate::selector::find_column(first, &names, &sort.column, case)?;
+    let key_name = names.get(key).copied().unwrap_or(&sort.column);
+
+    // Only a top-level primitive column maps to the one leaf `sorting_columns` names
+    let metadata = reader_builder(first)?.metadata().clone();
+    let leaf = parquet_column(metadata.file_metadata().schema_descr(), &schema, key_name)
+        .map(|(leaf, _)| leaf)
+        .ok_or_else(|| {
+            PqError::unsupported_column_type(
+                first,
+                key_name,
+                schema.field(key).data_type(),
+                "--sorted-by needs a top-level column that is not a list, struct, or map",
+            )
+        })?;
+    let options = SortOptions {
+        descending: sort.descending,
+        nulls_first: true,
+    };
+    let converter = RowConverter::new(vec![SortField::new_with_options(
+        schema.field(key).data_type().clone(),
+        options,
+    )])
+    .map_err(|error| {
+        PqError::unsupported_column_type(first, key_name, schema.field(key).data_type(), error)
+    })?;
+
+    let props = writer_properties(&schema, write, first, output)?
+        .set_sorting_columns(Some(vec![SortingColumn {
+            column_idx: i32::try_from(leaf).unwrap_or(i32::MAX),
+            descending: options.descending,
+            nulls_first: options.nulls_first,
+        }]))
+        .build();
+    let pending_output = crate::atomic_output::PendingOutput::new(output)?;
+    let output_file =
+        File::create(pending_output.path()).map_err(|error| PqError::write_error(output, error))?;
+    let mut writer = ArrowWriter::try_new(output_file, Arc::clone(&schema), Some(props))
+        .map_err(|error| PqError::write_error(output, error))?;
+
+    let mut merge = Merge {
+        schema: Arc::clone(&schema),
+        key,
+        key_name,
+        converter,
+        check: !sort.assume_sorted,
+        inputs: Vec::with_capacity(paths.len()),
+        pending: Vec::new(),
+    };
+    let mut heap = BinaryHeap::with_capacity(paths.len());
+    for path in paths {
+        let reader = reader_builder(path)?
+            .with_batch_size(batch_size)
+            .build()
+            .map_err(|error| build_error(path, error))?;
+        merge.inputs.push(Input {
+            path,
+            reader,
+            batch: RecordBatch::new_empty(Arc::clone(&schema)),
+            rows: merge.converter.empty_rows(0, 0),
+            next: 0,
+            first_row: 0,
+            previous: None,
+        });
+        let index = merge.inputs.len() - 1;
+        if merge.advance(index)? {
+            heap.push(Reverse((merge.inputs[index].current(), index)));
+        }
+    }
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        merge.pending.push((index, merge.inputs[index].next));
+        merge.inputs[index].next += 1;
+        if merge.inputs[index].next < merge.inputs[index].batch.num_rows() {
+            heap.push(Reverse((merge.inputs[index].current(), index)));
+        } else {
+            // The batch is about to be replaced, so its pending rows go out first
+            merge.flush(&mut writer, output)?;
+            if merge.advance(index)? {
+                heap.push(Reverse((merge.inputs[index].current(), index)));
+            }
+        }
+        if merge.pending.len() >= batch_size {
+            merge.flush(&mut writer, output)?;
+        }
+    }
+    merge.flush(&mut writer, output)?;
+
+    writer
+        .close()
+        .map_err(|error| PqError::write_error(output, error))?;
+    pending_output.commit()
+}
+
+struct Merge<'a> {
+    schema: SchemaRef,
+    key: usize,
+    key_name: &'a str,
+    converter: RowConverter,
+    check: bool,
+    inputs: Vec<Input<'a>>,
+    /// `(input, row)` pairs in output order, all within the inputs' current batches.
+    pending: Vec<(usize, usize)>,
+}
+
+struct Input<'a> {
+    path: &'a Path,
+    reader: ParquetRecordBatchReader,
+    batch: RecordBatch,
+    /// The current batch's keys in the row format, which compares like the sort order.
+    rows: Rows,
+    next: usize,
+    /// Index within the file of the current batch's first row.
+    first_row: u64,
+    /// The previous batch's last key, to check order across batches.
+    previous: Option<OwnedRow>,
+}
+
+impl Input<'_> {
+    fn current(&self) -> OwnedRow {
+        self.rows.row(self.next).owned()
+    }
+}
+
+impl Merge<'_> {
+    /// Load the input's next non-empty batch; false once the input is exhausted.
+    fn advance(&mut self, index: usize) -> Result<bool> {
+        let input = &mut self.inputs[index];
+        input.first_row += input.batch.num_rows() as u64;
+        if input.batch.num_rows() > 0 {
+            input.previous = Some(input.rows.row(input.batch.num_rows() - 1).owned());
+        }
+
+        for batch_result in input.reader.by_ref() {
+            let batch = batch_result.map_err(|error| decode_error(input.path, error))?;
+            if batch.num_rows() == 0 {
+                continue;
+            }
+            let key: ArrayRef = Arc::clone(batch.column(self.key));
+            let rows = self
+                .converter
+                .convert_columns(&[key])
+                .map_err(|error| PqError::corrupted(input.path, error))?;
+            if self.check {
+                let previous = input.previous.as_ref().map(OwnedRow::row);
+                let first = rows.row(0);
+                if previous.is_some_and(|previous| first < previous) {
+                    return Err(PqError::not_sorted(
+                        input.path,
+                        self.key_name,
+                        input.first_row,
+                    ));
+                }
+                if let Some(offset) =
+                    (1..rows.num_rows()).find(|&row| rows.row(row) < rows.row(row - 1))
+                {
+                    return Err(PqError::not_sorted(
+                        input.path,
+                        self.key_name,
+                        input.first_row + offset as u64,
+                    ));
+                }
+            }
+            input.batch = batch;
+            input.rows = rows;
+            input.next = 0;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn flush(&mut self, writer: &mut ArrowWriter<File>, output: &Path) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let columns = (0..self.schema.fields().len())
+            .map(|column| {
+                let arrays: Vec<&dyn Array> = self
+                    .inputs
+                    .iter()
+                    .map(|input| input.batch.column(column).as_ref())
+                    .collect();
+                interleave(&arrays, &self.pending)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|error| PqError::write_error(output, error))?;
+        let batch = RecordBatch::try_new(Arc::clone(&self.schema), columns)
+            .map_err(|error| PqError::write_error(output, error))?;
+        writer
+            .write(&batch)
+            .map_err(|error| PqError::write_error(output, error))?;
+        self.pending.clear();
+        Ok(())
+    }
+}
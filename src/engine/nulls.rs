@@ -0,0 +1,148 @@
+//! Null counts and null runs from column validity bitmaps
+
+use super::parquet::{build_error, decode_error, reader_builder, selected_projection};
+use crate::model::{ColumnCase, ColumnNulls, NullRuns, NullsOptions, NullsResult};
+use crate::Result;
+use arrow::array::{Array, RecordBatchReader};
+use arrow::buffer::NullBuffer;
+use std::cmp::Reverse;
+use std::path::Path;
+
+pub fn nulls(
+    path: &Path,
+    options: &NullsOptions,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<NullsResult> {
+    let mut builder = reader_builder(path)?;
+    if !options.columns.is_empty() {
+        let selection = selected_projection(path, &builder, &options.columns, case)?;
+        builder = builder.with_projection(selection.mask);
+    }
+    let reader = builder
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    let names: Vec<String> = reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect();
+
+    let mut tallies = vec![NullTally::default(); names.len()];
+    let mut num_rows = 0u64;
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| decode_error(path, error))?;
+        for (tally, column) in tallies.iter_mut().zip(batch.columns()) {
+            tally.add(
+                num_rows,
+                column.len(),
+                column.logical_nulls().as_ref(),
+                options.runs,
+            );
+        }
+        num_rows += batch.num_rows() as u64;
+    }
+
+    let mut columns: Vec<ColumnNulls> = names
+        .into_iter()
+        .zip(tallies)
+        .map(|(column, tally)| ColumnNulls {
+            column,
+            null_count: tally.null_count,
+            runs: options.runs.then_some(tally.runs),
+        })
+        .collect();
+    columns.sort_by_key(|column| Reverse(column.null_count));
+    Ok(NullsResult {
+        path: path.to_path_buf(),
+        num_rows,
+        columns,
+    })
+}
+
+#[derive(Clone, Default)]
+struct NullTally {
+    null_count: u64,
+    runs: NullRuns,
+    current_run: u64,
+}
+
+impl NullTally {
+    /// Count the nulls of `len` rows starting at row `offset`. Runs walk the set bits of
+    /// the validity bitmap a slice at a time rather than row by row.
+    fn add(&mut self, offset: u64, len: usize, nulls: Option<&NullBuffer>, runs: bool) {
+        self.null_count += nulls.map_or(0, |nulls| nulls.null_count()) as u64;
+        if !runs || len == 0 {
+            return;
+        }
+
+        let valid_slices: Vec<(usize, usize)> = match nulls {
+            Some(nulls) => nulls.inner().set_slices().collect(),
+            None => vec![(0, len)],
+        };
+        let mut position = 0;
+        for (start, end) in valid_slices {
+            self.end_run((start - position) as u64);
+            self.runs
+                .first_non_null
+                .get_or_insert(offset + start as u64);
+            self.runs.last_non_null = Some(offset + end as u64 - 1);
+            position = end;
+        }
+        self.current_run += (len - position) as u64;
+        self.runs.longest = self.runs.longest.max(self.current_run);
+    }
+
+    /// Add `nulls` to the current run, which a non-null value then ends.
+    fn end_run(&mut self, nulls: u64) {
+        self.current_run += nulls;
+        self.runs.longest = self.runs.longest.max(self.current_run);
+        self.current_run = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tally(batches: &[&[bool]]) -> NullTally {
+        let mut tally = NullTally::default();
+        let mut offset = 0;
+        for validity in batches {
+            let nulls = NullBuffer::from(validity.to_vec());
+            tally.add(offset, validity.len(), Some(&nulls), true);
+            offset += validity.len() as u64;
+        }
+        tally
+    }
+
+    #[test]
+    fn tracks_runs_across_batches() {
+        let tally = tally(&[&[false, true, false, false], &[false, false, true, false]]);
+        assert_eq!(tally.null_count, 6);
+        assert_eq!(
+            tally.runs,
+            NullRuns {
+                longest: 4,
+                first_non_null: Some(1),
+                last_non_null: Some(6),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_no_non_null_rows_for_all_null_columns() {
+        let tally = tally(&[&[false, false], &[false]]);
+        assert_eq!(tally.null_count, 3);
+        assert_eq!(
+            tally.runs,
+            NullRuns {
+                longest: 3,
+                first_non_null: None,
+                last_non_null: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use crate::error::{PqError, ResultExt};
+use crate::model::{
+    ColumnPages, ColumnType, PageIndexOptions, PageIndexResult, PageInfo, RowGroupPages, StatValue,
+};
+use crate::Result;
+use parquet::basic::{Encoding, PageType};
+use parquet::file::metadata::ColumnChunkMetaData;
+use parquet::file::page_index::index::{Index, PageIndex};
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::serialized_reader::ReadOptionsBuilder;
+use parquet::format::{PageHeader, PageLocation};
+use parquet::thrift::TSerializable;
+use std::fs::File;
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::Path;
+use thrift::protocol::TCompactInputProtocol;
+
+/// Statistics for one data page, taken from a column index.
+pub struct PageStats {
+    pub min: Option<StatValue>,
+    pub max: Option<StatValue>,
+    pub null_count: Option<i64>,
+}
+
+/// Per-page statistics, or `None` when the column chunk has no column index.
+///
+/// Pages whose values are all null have neither a min nor a max.
+pub fn page_stats(index: &Index) -> Option<Vec<PageStats>> {
+    fn convert<T: Clone>(
+        pages: &[PageIndex<T>],
+        convert: impl Fn(T) -> StatValue,
+    ) -> Vec<PageStats> {
+        pages
+            .iter()
+            .map(|page| PageStats {
+                min: page.min().cloned().map(&convert),
+                max: page.max().cloned().map(&convert),
+                null_count: page.null_count(),
+            })
+            .collect()
+    }
+
+    Some(match index {
+        Index::BOOLEAN(native) => convert(&native.indexes, StatValue::Boolean),
+        Index::INT32(native) => convert(&native.indexes, StatValue::Int32),
+        Index::INT64(native) => convert(&native.indexes, StatValue::Int64),
+        Index::INT96(native) => convert(&native.indexes, |value| {
+            StatValue::Int96(super::stats::display_int96(value))
+        }),
+        Index::FLOAT(native) => convert(&native.indexes, StatValue::Float),
+        Index::DOUBLE(native) => convert(&native.indexes, StatValue::Double),
+        Index::BYTE_ARRAY(native) => convert(&native.indexes, |value| {
+            StatValue::Binary(value.data().to_vec())
+        }),
+        Index::FIXED_LEN_BYTE_ARRAY(native) => convert(&native.indexes, |value| {
+            StatValue::FixedLenBinary(value.data().to_vec())
+        }),
+        Index::NONE => return None,
+    })
+}
+
+pub fn page_indexes(path: &Path, options: &PageIndexOptions) -> Result<PageIndexResult> {
+    let file = File::open(path).with_path_context(path)?;
+    let read_options = ReadOptionsBuilder::new().with_page_index().build();
+    let reader = SerializedFileReader::new_with_options(file, read_options)
+        .map_err(|error| super::parquet::footer_error(path, error))?;
+    let metadata = reader.metadata();
+    let schema = metadata.file_metadata().schema_descr();
+
+    let leaves = (0..schema.num_columns())
+        .filter(|index| {
+            options.column.as_deref().is_none_or(|name| {
+                let column = schema.column(*index);
+                column.path().string() == name
+                    || column.path().parts().first().map(String::as_str) == Some(name)
+            })
+        })
+        .collect::<Vec<_>>();
+    if let (Some(name), true) = (options.column.as_deref(), leaves.is_empty()) {
+        return Err(PqError::column_not_found(path, name));
+    }
+
+    let num_row_groups = metadata.num_row_groups();
+    let row_groups = match options.row_group {
+        Some(index) if index >= num_row_groups => {
+            return Err(PqError::row_group_out_of_range(path, index, num_row_groups));
+        }
+        Some(index) => index..index + 1,
+        None => 0..num_row_groups,
+    };
+
+    let mut has_page_index = false;
+    let row_groups = row_groups
+        .map(|row_group_index| {
+            let row_group = metadata.row_group(row_group_index);
+            let columns = leaves
+                .iter()
+                .map(|&leaf| {
+                    let column_index = metadata
+                        .column_index()
+                        .and_then(|indexes| indexes.get(row_group_index)?.get(leaf))
+                        .and_then(page_stats);
+                    let locations = metadata
+                        .offset_index()
+                        .and_then(|indexes| indexes.get(row_group_index)?.get(leaf))
+                        .map(|index| index.page_locations().as_slice())
+                        .filter(|locations| !locations.is_empty());
+                    has_page_index |= column_index.is_some() || locations.is_some();
+
+                    ColumnPages {
+                        column: schema.column(leaf).path().string(),
+                        column_type: ColumnType::from_parquet(&schema.column(leaf)),
+                        column_index: column_index.is_some(),
+                        offset_index: locations.is_some(),
+                        pages: combine_pages(row_group.num_rows(), column_index, locations),
+                    }
+                })
+                .collect();
+
+            RowGroupPages {
+                index: row_group_index,
+                num_rows: row_group.num_rows(),
+                columns,
+            }
+        })
+        .collect();
+
+    Ok(PageIndexResult {
+        path: path.to_path_buf(),
+        has_page_index,
+        row_groups,
+    })
+}
+
+fn combine_pages(
+    num_rows: i64,
+    stats: Option<Vec<PageStats>>,
+    locations: Option<&[PageLocation]>,
+) -> Vec<PageInfo> {
+    let page_count = stats
+        .as_ref()
+        .map_or(0, Vec::len)
+        .max(locations.map_or(0, <[PageLocation]>::len));
+    let mut stats = stats.map(Vec::into_iter);
+
+    (0..page_count)
+        .map(|page| {
+            let location = locations.and_then(|locations| locations.get(page));
+            let next_first_row = locations
+                .and_then(|locations| locations.get(page + 1))
+                .map_or(num_rows, |next| next.first_row_index);
+            let page_stats = stats.as_mut().and_then(Iterator::next);
+            let (min, max, null_count) = page_stats.map_or((None, None, None), |page| {
+                (page.min, page.max, page.null_count)
+            });
+
+            PageInfo {
+                first_row_index: location.map(|location| location.first_row_index),
+                rows: location.map(|location| next_first_row - location.first_row_index),
+                offset: location.map(|location| location.offset),
+                compressed_size: location.map(|location| location.compressed_page_size),
+                null_count,
+                min,
+                max,
+            }
+        })
+        .collect()
+}
+
+/// Dictionary and data page encodings for one column chunk, read from page headers.
+#[derive(Default)]
+pub struct ChunkEncodings {
+    pub dictionary_entries: Option<u64>,
+    pub dictionary_bytes: Option<u64>,
+    pub data_pages: usize,
+    pub dictionary_data_pages: usize,
+}
+
+impl ChunkEncodings {
+    pub fn has_dictionary(&self) -> bool {
+        self.dictionary_entries.is_some()
+    }
+
+    pub fn fully_dictionary_encoded(&self) -> bool {
+        self.has_dictionary() && self.dictionary_data_pages == self.data_pages
+    }
+}
+
+/// Walk the page headers of a column chunk without decompressing page data.
+///
+/// Writer-provided page encoding stats are used instead of data page headers when present.
+pub fn chunk_encodings(
+    path: &Path,
+    file: &File,
+    chunk: &ColumnChunkMetaData,
+) -> Result<ChunkEncodings> {
+    let mut encodings = ChunkEncodings::default();
+    let encoding_stats = chunk.page_encoding_stats();
+    if let Some(stats) = encoding_stats {
+        for stat in stats.iter().filter(|stat| is_data_page(stat.page_type)) {
+            let count = usize::try_from(stat.count).unwrap_or(0);
+            encodings.data_pages += count;
+            if is_dictionary_encoding(stat.encoding) {
+                encodings.dictionary_data_pages += count;
+            }
+        }
+    }
+
+    let (start, length) = chunk.byte_range();
+    let end = start.saturating_add(length);
+    let mut reader = BufReader::new(file);
+    reader
+        .seek(SeekFrom::Start(start))
+        .with_path_context(path)?;
+    let mut position = start;
+
+    while position < end {
+        let header = {
+            let mut protocol = TCompactInputProtocol::new(&mut reader);
+            PageHeader::read_from_in_protocol(&mut protocol)
+                .map_err(|error| super::parquet::decode_error(path, error))?
+        };
+        let page_size = u64::try_from(header.compressed_page_size)
+            .map_err(|_| PqError::corrupted(path, "negative page size"))?;
+
+        if let Some(dictionary) = &header.dictionary_page_header {
+            encodings.dictionary_entries = u64::try_from(dictionary.num_values).ok();
+            encodings.dictionary_bytes = u64::try_from(header.uncompressed_page_size).ok();
+        } else if encoding_stats.is_some() {
+            break;
+        } else if let Some(encoding) = data_page_encoding(&header) {
+            encodings.data_pages += 1;
+            if is_dictionary_encoding(encoding) {
+                encodings.dictionary_data_pages += 1;
+            }
+        }
+
+        reader
+            .seek_relative(i64::try_from(page_size).unwrap_or(i64::MAX))
+            .with_path_context(path)?;
+        position = reader.stream_position().with_path_context(path)?;
+    }
+
+    Ok(encodings)
+}
+
+fn data_page_encoding(header: &PageHeader) -> Option<Encoding> {
+    let encoding = match (&header.data_page_header, &header.data_page_header_v2) {
+        (Some(page), _) => page.encoding,
+        (None, Some(page)) => page.encoding,
+        (None, None) => return None,
+    };
+    Encoding::try_from(encoding).ok()
+}
+
+fn is_data_page(page_type: PageType) -> bool {
+    matches!(page_type, PageType::DATA_PAGE | PageType::DATA_PAGE_V2)
+}
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+    )
+}
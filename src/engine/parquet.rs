@@ -1,32 +1,64 @@
+use super::pages::ChunkEncodings;
+use super::sorting;
 use crate::error::{PqError, ResultExt};
-use crate::model::{ColumnInfo, ColumnType, CompressionCodec, CompressionSummary, FileInfo};
+use crate::model::{
+    AppendSummary, CodecColumns, ColumnCase, ColumnChunkSummary, ColumnInfo, ColumnType,
+    CompressionCodec, CompressionSummary, FileInfo, KeyValueEntry, MetadataEdit,
+    ParquetWriteOptions, RowCountCheck, RowGroupRange, RowGroupSummary, ARROW_EXTENSION_NAME_KEY,
+    UUID_EXTENSION_NAME,
+};
+use crate::path_text::display_path;
 use crate::Result;
-use arrow::array::RecordBatch;
-use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
-use parquet::arrow::ArrowWriter;
-use parquet::basic::Compression;
-use parquet::file::properties::WriterProperties;
-use parquet::file::reader::{FileReader, SerializedFileReader};
+use arrow::array::{RecordBatch, RecordBatchReader};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use parquet::arrow::arrow_reader::{
+    ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
+};
+use parquet::arrow::arrow_writer::{compute_leaves, get_column_writers, ArrowColumnWriter};
+use parquet::arrow::{arrow_to_parquet_schema, ArrowWriter, ProjectionMask};
+use parquet::basic::{Encoding, LogicalType};
+use parquet::column::writer::ColumnCloseResult;
+use parquet::file::metadata::{ColumnChunkMetaData, KeyValue, ParquetMetaData};
+use parquet::file::properties::{
+    ReaderProperties, WriterProperties, WriterPropertiesBuilder, WriterVersion,
+};
+use parquet::file::reader::{ChunkReader, FileReader, SerializedFileReader};
+use parquet::file::serialized_reader::ReadOptionsBuilder;
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::ColumnPath;
+use parquet::thrift::TSerializable;
 use std::fs::{self, File};
 use std::path::Path;
 use std::sync::Arc;
+use thrift::protocol::TCompactInputProtocol;
 
-pub fn read_head(path: &Path, rows: usize) -> Result<Vec<RecordBatch>> {
-    if rows == 0 {
-        return Ok(Vec::new());
+pub fn read_head(
+    path: &Path,
+    rows: usize,
+    columns: &[String],
+    case: ColumnCase,
+    batch_size: usize,
+    row_groups: &[RowGroupRange],
+) -> Result<Vec<RecordBatch>> {
+    let (builder, selection) = projected_builder(path, columns, case)?;
+    let row_groups = selected_row_groups(path, builder.metadata(), row_groups)?;
+    if rows == 0 || row_groups.is_empty() {
+        let schema = projected_schema(path, builder)?;
+        return empty_result(path, schema, selection.as_ref());
     }
 
-    let builder = reader_builder(path)?;
     let reader = builder
-        .with_batch_size(rows.min(1024))
+        .with_row_groups(row_groups)
+        .with_batch_size(rows.min(batch_size))
         .build()
-        .map_err(|error| PqError::from_read(path, error))?;
+        .map_err(|error| build_error(path, error))?;
+    let schema = reader.schema();
 
     let mut batches = Vec::new();
     let mut total_rows = 0usize;
 
     for batch_result in reader {
-        let batch = batch_result.map_err(|error| PqError::corrupted(path, &error))?;
+        let batch = batch_result.map_err(|error| decode_error(path, &error))?;
         let rows_needed = rows.saturating_sub(total_rows);
         if rows_needed == 0 {
             break;
@@ -39,70 +71,259 @@ pub fn read_head(path: &Path, rows: usize) -> Result<Vec<RecordBatch>> {
         };
 
         total_rows += batch.num_rows();
-        batches.push(batch);
+        batches.push(arranged(path, selection.as_ref(), batch)?);
     }
 
+    if batches.is_empty() {
+        return empty_result(path, schema, selection.as_ref());
+    }
     Ok(batches)
 }
 
-pub fn read_tail(path: &Path, rows: usize) -> Result<Vec<RecordBatch>> {
-    if rows == 0 {
-        return Ok(Vec::new());
-    }
+pub fn read_tail(
+    path: &Path,
+    rows: usize,
+    columns: &[String],
+    case: ColumnCase,
+    batch_size: usize,
+    row_groups: &[RowGroupRange],
+) -> Result<Vec<RecordBatch>> {
+    tail_batches(path, rows, columns, case, batch_size, row_groups)?.collect()
+}
 
-    let builder = reader_builder(path)?;
+/// The last `rows` of the file one batch at a time, reading only the trailing row
+/// groups that hold them, from among `row_groups` when any are given.
+pub fn tail_batches(
+    path: &Path,
+    rows: usize,
+    columns: &[String],
+    case: ColumnCase,
+    batch_size: usize,
+    row_groups: &[RowGroupRange],
+) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>>>> {
+    let (builder, selection) = projected_builder(path, columns, case)?;
     let metadata = Arc::clone(builder.metadata());
-    if metadata.num_row_groups() == 0 {
-        return Ok(Vec::new());
+    let candidates = selected_row_groups(path, &metadata, row_groups)?;
+    if rows == 0 || candidates.is_empty() {
+        let schema = projected_schema(path, builder)?;
+        let empty = empty_result(path, schema, selection.as_ref())?;
+        return Ok(Box::new(empty.into_iter().map(Ok)));
     }
 
-    let (row_groups, rows_to_skip) = tail_row_groups(path, &metadata, rows)?;
+    let (row_groups, rows_to_skip) = tail_row_groups(path, &metadata, &candidates, rows)?;
     let reader = builder
         .with_row_groups(row_groups)
+        .with_batch_size(batch_size)
         .build()
-        .map_err(|error| PqError::from_read(path, error))?;
+        .map_err(|error| build_error(path, error))?;
 
-    let mut result_batches = Vec::new();
+    let path = path.to_path_buf();
     let mut skipped = 0usize;
-
-    for batch_result in reader {
-        let batch = batch_result.map_err(|error| PqError::corrupted(path, &error))?;
+    Ok(Box::new(reader.filter_map(move |batch_result| {
+        let batch = match batch_result {
+            Ok(batch) => batch,
+            Err(error) => return Some(Err(decode_error(&path, &error))),
+        };
 
         if skipped + batch.num_rows() <= rows_to_skip {
             skipped += batch.num_rows();
-            continue;
+            return None;
         }
 
         let offset = rows_to_skip.saturating_sub(skipped);
         let sliced = batch.slice(offset, batch.num_rows() - offset);
-        result_batches.push(sliced);
         skipped = rows_to_skip;
+        Some(arranged(&path, selection.as_ref(), sliced))
+    })))
+}
+
+/// Every value of the root column `column` names, reading no other column.
+pub fn column_batches(
+    path: &Path,
+    column: &str,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<Box<dyn Iterator<Item = Result<RecordBatch>>>> {
+    let builder = reader_builder(path)?;
+    let projection = root_projection(path, &builder, &[column.to_string()], case)?;
+    let reader = builder
+        .with_projection(projection)
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    let path = path.to_path_buf();
+    Ok(Box::new(reader.map(move |batch| {
+        batch.map_err(|error| decode_error(&path, &error))
+    })))
+}
+
+/// The row groups `ranges` pick, in file order without repeats, or every row group
+/// when there are no ranges. A range reaching past the last row group is an error.
+pub fn selected_row_groups(
+    path: &Path,
+    metadata: &ParquetMetaData,
+    ranges: &[RowGroupRange],
+) -> Result<Vec<usize>> {
+    let count = metadata.num_row_groups();
+    if ranges.is_empty() {
+        return Ok((0..count).collect());
+    }
+    let mut selected = Vec::new();
+    for range in ranges {
+        if range.last >= count {
+            return Err(PqError::row_group_out_of_range(path, range.last, count));
+        }
+        selected.extend(range.first..=range.last);
+    }
+    selected.sort_unstable();
+    selected.dedup();
+    Ok(selected)
+}
+
+/// The schema `read_head` and `read_tail` return for `columns`, from the footer alone.
+pub fn scan_schema(path: &Path, columns: &[String], case: ColumnCase) -> Result<SchemaRef> {
+    let (builder, selection) = projected_builder(path, columns, case)?;
+    let schema = projected_schema(path, builder)?;
+    match selection {
+        Some(selection) => Ok(Arc::new(selection.arrange_schema(path, &schema)?)),
+        None => Ok(schema),
     }
+}
 
-    Ok(result_batches)
+/// A single empty batch, so that output with no rows still knows its columns: CSV
+/// keeps its header and Parquet output its schema.
+fn empty_result(
+    path: &Path,
+    schema: SchemaRef,
+    selection: Option<&ColumnSelection>,
+) -> Result<Vec<RecordBatch>> {
+    Ok(vec![arranged(
+        path,
+        selection,
+        RecordBatch::new_empty(schema),
+    )?])
+}
+
+/// The projected schema, from a reader that decodes nothing.
+fn projected_schema(
+    path: &Path,
+    builder: ParquetRecordBatchReaderBuilder<File>,
+) -> Result<SchemaRef> {
+    let reader = builder
+        .with_row_groups(Vec::new())
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    Ok(reader.schema())
+}
+
+fn arranged(
+    path: &Path,
+    selection: Option<&ColumnSelection>,
+    batch: RecordBatch,
+) -> Result<RecordBatch> {
+    match selection {
+        Some(selection) => selection.arrange(path, &batch),
+        None => Ok(batch),
+    }
 }
 
 pub fn row_count(path: &Path) -> Result<i64> {
-    let reader = serialized_reader(path)?;
-    let rows = reader.metadata().file_metadata().num_rows();
+    let rows = match super::footer::row_count(path) {
+        Some(rows) => rows,
+        None => serialized_reader(path)?
+            .metadata()
+            .file_metadata()
+            .num_rows(),
+    };
     if rows < 0 {
         return Err(PqError::invalid_metadata(path, "negative row count"));
     }
     Ok(rows)
 }
 
+/// Count rows by decoding the top-level column with the least compressed data instead
+/// of trusting the footer. An empty projection would be cheaper but proves nothing: the
+/// reader then takes its row counts from the same row group metadata.
+pub fn verify_row_count(path: &Path, batch_size: usize) -> Result<RowCountCheck> {
+    let metadata_rows = row_count(path)?;
+    let builder = reader_builder(path)?;
+    let parquet_schema = builder.parquet_schema();
+    let roots = parquet_schema.root_schema().get_fields();
+
+    let mut root_bytes = vec![0i64; roots.len()];
+    for row_group in builder.metadata().row_groups() {
+        for (leaf, chunk) in row_group.columns().iter().enumerate() {
+            if let Some(bytes) = root_bytes.get_mut(parquet_schema.get_column_root_idx(leaf)) {
+                *bytes = bytes.saturating_add(chunk.compressed_size());
+            }
+        }
+    }
+    let root = root_bytes
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, bytes)| **bytes)
+        .map(|(index, _)| index);
+    let column = root
+        .and_then(|index| roots.get(index))
+        .map(|field| field.name().to_string());
+
+    let mask = ProjectionMask::roots(parquet_schema, root);
+    let reader = builder
+        .with_projection(mask)
+        .with_batch_size(batch_size)
+        .build()
+        .map_err(|error| build_error(path, error))?;
+    let mut decoded_rows = 0u64;
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| decode_error(path, error))?;
+        decoded_rows += batch.num_rows() as u64;
+    }
+
+    Ok(RowCountCheck {
+        path: path.to_path_buf(),
+        metadata_rows,
+        decoded_rows,
+        column,
+    })
+}
+
 pub fn schema_columns(path: &Path) -> Result<Vec<ColumnInfo>> {
     let reader = serialized_reader(path)?;
-    let schema = reader.metadata().file_metadata().schema_descr();
+    Ok(leaf_columns(reader.metadata()))
+}
 
-    Ok(schema
+pub(super) fn leaf_columns(metadata: &ParquetMetaData) -> Vec<ColumnInfo> {
+    metadata
+        .file_metadata()
+        .schema_descr()
         .columns()
         .iter()
-        .map(|column| ColumnInfo {
-            name: column.name().to_string(),
-            column_type: ColumnType::from_parquet(column),
-            nullable: column.self_type().is_optional(),
+        .enumerate()
+        .map(|(ordinal, column)| {
+            let info = column.self_type().get_basic_info();
+            ColumnInfo {
+                name: column.name().to_string(),
+                column_type: ColumnType::from_parquet(column),
+                nullable: column.self_type().is_optional(),
+                ordinal,
+                max_def_level: column.max_def_level(),
+                max_rep_level: column.max_rep_level(),
+                field_id: info.has_id().then(|| info.id()),
+            }
         })
+        .collect()
+}
+
+/// Top-level column names, read from the footer alone.
+pub fn root_column_names(path: &Path) -> Result<Vec<String>> {
+    let reader = serialized_reader(path)?;
+    let schema = reader.metadata().file_metadata().schema_descr();
+
+    Ok(schema
+        .root_schema()
+        .get_fields()
+        .iter()
+        .map(|field| field.name().to_string())
         .collect())
 }
 
@@ -112,7 +333,10 @@ pub fn file_size(path: &Path) -> Result<u64> {
 
 pub fn file_info(path: &Path) -> Result<FileInfo> {
     let reader = serialized_reader(path)?;
-    let metadata = reader.metadata();
+    summarize_file(path, reader.metadata())
+}
+
+pub(super) fn summarize_file(path: &Path, metadata: &ParquetMetaData) -> Result<FileInfo> {
     let file_metadata = metadata.file_metadata();
     let num_rows = file_metadata.num_rows();
     if num_rows < 0 {
@@ -121,6 +345,11 @@ pub fn file_info(path: &Path) -> Result<FileInfo> {
     let num_row_groups = metadata.num_row_groups();
 
     let compression = compression_summary(metadata);
+    let chunks: Vec<&ColumnChunkMetaData> = metadata
+        .row_groups()
+        .iter()
+        .flat_map(|row_group| row_group.columns().iter())
+        .collect();
 
     Ok(FileInfo {
         path: path.to_path_buf(),
@@ -129,57 +358,326 @@ pub fn file_info(path: &Path) -> Result<FileInfo> {
         num_columns: file_metadata.schema_descr().num_columns(),
         num_row_groups,
         compression,
+        codec_columns: codec_columns(metadata),
+        compressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.compressed_size())?,
+        uncompressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.uncompressed_size())?,
         created_by: file_metadata.created_by().map(ToOwned::to_owned),
         version: file_metadata.version(),
+        sort_order: sorting::file_sort_order(path, metadata)?,
     })
 }
 
 pub fn reader_builder(path: &Path) -> Result<ParquetRecordBatchReaderBuilder<File>> {
     let file = File::open(path).with_path_context(path)?;
-    ParquetRecordBatchReaderBuilder::try_new(file).map_err(|error| PqError::from_read(path, error))
+    let metadata = ArrowReaderMetadata::load(&file, ArrowReaderOptions::new())
+        .map_err(|error| footer_error(path, error))?;
+    let metadata = match uuid_tagged_schema(&metadata) {
+        Some(schema) => ArrowReaderMetadata::try_new(
+            Arc::clone(metadata.metadata()),
+            ArrowReaderOptions::new().with_schema(schema),
+        )
+        .map_err(|error| PqError::from_read(path, error))?,
+        None => metadata,
+    };
+    Ok(ParquetRecordBatchReaderBuilder::new_with_metadata(
+        file, metadata,
+    ))
+}
+
+/// Arrow has no UUID type of its own, so top-level UUID columns are marked with the
+/// canonical `arrow.uuid` extension name for output to render them in canonical form.
+fn uuid_tagged_schema(metadata: &ArrowReaderMetadata) -> Option<SchemaRef> {
+    let roots = metadata.parquet_schema().root_schema().get_fields();
+    let schema = metadata.schema();
+    if !roots
+        .iter()
+        .any(|root| root.get_basic_info().logical_type() == Some(LogicalType::Uuid))
+    {
+        return None;
+    }
+
+    let fields = schema
+        .fields()
+        .iter()
+        .zip(roots)
+        .map(|(field, root)| {
+            let is_uuid = root.is_primitive()
+                && root.get_basic_info().logical_type() == Some(LogicalType::Uuid)
+                && field.data_type() == &DataType::FixedSizeBinary(16);
+            if !is_uuid {
+                return Arc::clone(field);
+            }
+            let mut metadata = field.metadata().clone();
+            metadata.insert(
+                ARROW_EXTENSION_NAME_KEY.to_string(),
+                UUID_EXTENSION_NAME.to_string(),
+            );
+            Arc::new(field.as_ref().clone().with_metadata(metadata))
+        })
+        .collect::<Vec<_>>();
+    Some(Arc::new(Schema::new_with_metadata(
+        fields,
+        schema.metadata().clone(),
+    )))
+}
+
+/// A reader over the `columns` selectors only, or every column when none are given.
+fn projected_builder(
+    path: &Path,
+    columns: &[String],
+    case: ColumnCase,
+) -> Result<(
+    ParquetRecordBatchReaderBuilder<File>,
+    Option<ColumnSelection>,
+)> {
+    let builder = reader_builder(path)?;
+    if columns.is_empty() {
+        return Ok((builder, None));
+    }
+    let selection = selected_projection(path, &builder, columns, case)?;
+    let builder = builder.with_projection(selection.mask.clone());
+    Ok((builder, Some(selection)))
+}
+
+pub fn root_projection(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    columns: &[String],
+    case: ColumnCase,
+) -> Result<ProjectionMask> {
+    let indices = root_indices(path, builder, columns, case)?;
+    Ok(ProjectionMask::roots(builder.parquet_schema(), indices))
+}
+
+/// The file's own spelling of each of `columns`, matched as `case` allows.
+pub fn root_names(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    columns: &[String],
+    case: ColumnCase,
+) -> Result<Vec<String>> {
+    let fields = builder.schema().fields();
+    Ok(root_indices(path, builder, columns, case)?
+        .into_iter()
+        .filter_map(|index| fields.get(index).map(|field| field.name().clone()))
+        .collect())
+}
+
+/// Root columns picked by `--columns` selectors. The reader yields them in file order;
+/// [`ColumnSelection::arrange`] puts each batch back into selector order.
+pub struct ColumnSelection {
+    pub mask: ProjectionMask,
+    order: Vec<usize>,
+}
+
+impl ColumnSelection {
+    pub fn arrange(&self, path: &Path, batch: &RecordBatch) -> Result<RecordBatch> {
+        batch
+            .project(&self.order)
+            .map_err(|error| PqError::corrupted(path, &error))
+    }
+
+    /// The reader's (file-order) schema, in selector order.
+    pub fn arrange_schema(&self, path: &Path, schema: &Schema) -> Result<Schema> {
+        schema
+            .project(&self.order)
+            .map_err(|error| PqError::corrupted(path, &error))
+    }
+}
+
+pub fn selected_projection(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    selectors: &[String],
+    case: ColumnCase,
+) -> Result<ColumnSelection> {
+    let indices = selected_indices(path, builder, selectors, case)?;
+    let mut file_order = indices.clone();
+    file_order.sort_unstable();
+    let order = indices
+        .iter()
+        .filter_map(|index| file_order.binary_search(index).ok())
+        .collect();
+    Ok(ColumnSelection {
+        mask: ProjectionMask::roots(builder.parquet_schema(), indices),
+        order,
+    })
+}
+
+/// Project every root column except those the `excluded` selectors pick.
+pub fn excluding_projection(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    excluded: &[String],
+    case: ColumnCase,
+) -> Result<ProjectionMask> {
+    let excluded = selected_indices(path, builder, excluded, case)?;
+    let indices = (0..builder.schema().fields().len()).filter(|index| !excluded.contains(index));
+    Ok(ProjectionMask::roots(builder.parquet_schema(), indices))
+}
+
+pub(super) fn selected_indices(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    selectors: &[String],
+    case: ColumnCase,
+) -> Result<Vec<usize>> {
+    let names: Vec<&str> = builder
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    crate::selector::select_columns(path, &names, selectors, case)
+}
+
+fn root_indices(
+    path: &Path,
+    builder: &ParquetRecordBatchReaderBuilder<File>,
+    columns: &[String],
+    case: ColumnCase,
+) -> Result<Vec<usize>> {
+    let names: Vec<&str> = builder
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    columns
+        .iter()
+        .map(|column| crate::selector::find_column(path, &names, column, case))
+        .collect()
+}
+
+/// Writer properties for `options`. Every column named is checked against `schema`
+/// first, so a misspelled name fails before any output is created. A missing column is
+/// reported against `source`, the file whose columns `schema` lists; other errors name
+/// `output`, the file being written.
+pub(crate) fn writer_properties(
+    schema: &Schema,
+    options: &ParquetWriteOptions,
+    source: &Path,
+    output: &Path,
+) -> Result<WriterPropertiesBuilder> {
+    let mut builder = WriterProperties::builder().set_compression(options.compression);
+    if let Some(version) = options.writer_version {
+        builder = builder.set_writer_version(version);
+    }
+    if let Some(limit) = options.dictionary_page_size_limit {
+        builder = builder.set_dictionary_page_size_limit(limit);
+    }
+    if let Some(limit) = options.data_page_size_limit {
+        builder = builder.set_data_page_size_limit(limit);
+    }
+    if options.column_compression.is_empty() && options.disable_dictionary.is_empty() {
+        return Ok(builder);
+    }
+
+    let descriptor =
+        arrow_to_parquet_schema(schema).map_err(|error| PqError::write_error(output, error))?;
+    let leaves: Vec<ColumnPath> = descriptor
+        .columns()
+        .iter()
+        .map(|leaf| leaf.path().clone())
+        .collect();
+    let leaves_under = |column: &str| -> Result<Vec<ColumnPath>> {
+        let prefix = format!("{column}.");
+        let matching: Vec<ColumnPath> = leaves
+            .iter()
+            .filter(|leaf| {
+                let leaf = leaf.string();
+                leaf == column || leaf.starts_with(&prefix)
+            })
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return Err(PqError::column_not_found_among(
+                source,
+                column,
+                leaves.iter().map(ColumnPath::string),
+            ));
+        }
+        Ok(matching)
+    };
+
+    for (column, codec) in &options.column_compression {
+        for leaf in leaves_under(column)? {
+            builder = builder.set_column_compression(leaf, *codec);
+        }
+    }
+    for column in &options.disable_dictionary {
+        for leaf in leaves_under(column)? {
+            builder = builder.set_column_dictionary_enabled(leaf, false);
+        }
+    }
+    Ok(builder)
 }
 
 pub fn serialized_reader(path: &Path) -> Result<SerializedFileReader<File>> {
     let file = File::open(path).with_path_context(path)?;
-    SerializedFileReader::new(file).map_err(|error| PqError::from_read(path, error))
+    SerializedFileReader::new(file).map_err(|error| footer_error(path, error))
 }
 
-pub fn merge_files(paths: &[&Path], output: &Path) -> Result<()> {
-    if paths.is_empty() {
-        return Err(PqError::NoInputFiles);
+/// Classify a footer that failed to load. The reader's message is the same for a file
+/// that is encrypted, compressed as a whole, or still being written as for one that is
+/// damaged, so look at the file itself before falling back to the message.
+pub(crate) fn footer_error(path: &Path, error: impl std::fmt::Display) -> PqError {
+    if super::footer::is_encrypted(path) {
+        PqError::encrypted(path)
+    } else if let Some(wrapper) = super::sniff::compression_wrapper(path) {
+        PqError::compressed_file(path, wrapper.name, wrapper.command)
+    } else if super::footer::is_being_written(path) {
+        PqError::being_written(path, None)
+    } else {
+        PqError::from_read(path, error)
     }
+}
 
-    let first_builder = reader_builder(paths[0])?;
-    let schema = Arc::clone(first_builder.schema());
+/// Classify an error from decoding a file whose footer loaded. A plaintext footer can
+/// name an encryption algorithm while the pages themselves stay unreadable, so check
+/// for that before calling the file corrupt.
+pub(crate) fn decode_error(path: &Path, error: impl std::fmt::Display) -> PqError {
+    if super::footer::is_encrypted(path) {
+        PqError::encrypted(path)
+    } else {
+        PqError::corrupted(path, error)
+    }
+}
 
-    for path in paths.iter().skip(1) {
-        let builder = reader_builder(path)?;
-        if builder.schema().as_ref() != schema.as_ref() {
-            return Err(PqError::SchemaMismatch {
-                file1: paths[0].display().to_string(),
-                file2: path.display().to_string(),
-                details: "Column names or types differ".to_string(),
-            });
-        }
+/// Like [`decode_error`], for building a reader, which decodes pages when a row filter
+/// is evaluated up front.
+pub(crate) fn build_error(path: &Path, error: impl std::fmt::Display) -> PqError {
+    if super::footer::is_encrypted(path) {
+        PqError::encrypted(path)
+    } else {
+        PqError::from_read(path, error)
     }
+}
 
+pub fn merge_files(
+    paths: &[&Path],
+    output: &Path,
+    options: &ParquetWriteOptions,
+    batch_size: usize,
+) -> Result<()> {
+    let schema = merge_schema(paths)?;
+    let first = paths.first().ok_or(PqError::NoInputFiles)?;
+    let props = writer_properties(&schema, options, first, output)?.build();
     let pending_output = crate::atomic_output::PendingOutput::new(output)?;
     let output_file =
         File::create(pending_output.path()).map_err(|error| PqError::write_error(output, error))?;
-    let props = WriterProperties::builder()
-        .set_compression(Compression::SNAPPY)
-        .build();
     let mut writer = ArrowWriter::try_new(output_file, Arc::clone(&schema), Some(props))
         .map_err(|error| PqError::write_error(output, error))?;
 
     for path in paths {
         let builder = reader_builder(path)?;
         let reader = builder
+            .with_batch_size(batch_size)
             .build()
-            .map_err(|error| PqError::from_read(path, error))?;
+            .map_err(|error| build_error(path, error))?;
 
         for batch_result in reader {
-            let batch = batch_result.map_err(|error| PqError::corrupted(path, error))?;
+            let batch = batch_result.map_err(|error| decode_error(path, error))?;
             writer
                 .write(&batch)
                 .map_err(|error| PqError::write_error(output, error))?;
@@ -192,15 +690,361 @@ pub fn merge_files(paths: &[&Path], output: &Path) -> Result<()> {
     pending_output.commit()
 }
 
+/// The schema every input to a merge shares: the first input with rows sets it, and
+/// the others with rows must match it exactly. Zero-row inputs add nothing to the
+/// output, so they only need the same columns and types.
+pub(super) fn merge_schema(paths: &[&Path]) -> Result<SchemaRef> {
+    let mut builders = Vec::with_capacity(paths.len());
+    for path in paths {
+        builders.push((*path, reader_builder(path)?));
+    }
+    let has_rows = |builder: &ParquetRecordBatchReaderBuilder<File>| {
+        builder.metadata().file_metadata().num_rows() > 0
+    };
+    let Some((first, reference)) = builders
+        .iter()
+        .find(|(_, builder)| has_rows(builder))
+        .or_else(|| builders.first())
+    else {
+        return Err(PqError::NoInputFiles);
+    };
+    let schema = Arc::clone(reference.schema());
+
+    for (path, builder) in &builders {
+        check_merge_schema(first, &schema, path, builder.schema(), has_rows(builder))?;
+    }
+    Ok(schema)
+}
+
+fn check_merge_schema(
+    reference: &Path,
+    schema: &Schema,
+    path: &Path,
+    other: &Schema,
+    has_rows: bool,
+) -> Result<()> {
+    let compatible = if has_rows {
+        other == schema
+    } else {
+        same_columns(schema, other)
+    };
+    if compatible {
+        return Ok(());
+    }
+    Err(PqError::SchemaMismatch {
+        file1: display_path(reference),
+        file2: display_path(path),
+        details: "Column names or types differ".to_string(),
+    })
+}
+
+/// Same column names and types, whatever the nullability and metadata.
+fn same_columns(schema: &Schema, other: &Schema) -> bool {
+    schema.fields().len() == other.fields().len()
+        && schema
+            .fields()
+            .iter()
+            .zip(other.fields())
+            .all(|(a, b)| a.name() == b.name() && a.data_type().equals_datatype(b.data_type()))
+}
+
+/// Append `paths` to the existing Parquet file at `output`. The old row groups are
+/// copied byte for byte and the new rows encoded after them, in a temp file swapped in
+/// once complete; every schema is checked before anything is written.
+pub fn append_files(
+    paths: &[&Path],
+    output: &Path,
+    options: &ParquetWriteOptions,
+    batch_size: usize,
+) -> Result<AppendSummary> {
+    if paths.is_empty() {
+        return Err(PqError::NoInputFiles);
+    }
+
+    let existing = serialized_reader(output)?;
+    let existing_metadata = existing.metadata();
+    let schema = Arc::clone(reader_builder(output)?.schema());
+    for path in paths {
+        let builder = reader_builder(path)?;
+        let compatible = if builder.metadata().file_metadata().num_rows() > 0 {
+            builder.schema().as_ref() == schema.as_ref()
+        } else {
+            same_columns(&schema, builder.schema())
+        };
+        if !compatible {
+            return Err(PqError::SchemaMismatch {
+                file1: display_path(output),
+                file2: display_path(path),
+                details: "Column names or types differ from the existing output".to_string(),
+            });
+        }
+    }
+
+    let file_metadata = existing_metadata.file_metadata();
+    let props = Arc::new(
+        writer_properties(&schema, options, output, output)?
+            .set_key_value_metadata(file_metadata.key_value_metadata().cloned())
+            .build(),
+    );
+
+    let pending_output = crate::atomic_output::PendingOutput::new(output)?;
+    let output_file =
+        File::create(pending_output.path()).map_err(|error| PqError::write_error(output, error))?;
+    let mut writer = SerializedFileWriter::new(
+        output_file,
+        file_metadata.schema_descr().root_schema_ptr(),
+        Arc::clone(&props),
+    )
+    .map_err(|error| PqError::write_error(output, error))?;
+
+    copy_row_groups(&mut writer, output, output)?;
+
+    let parquet_schema = file_metadata.schema_descr();
+    let max_rows = props.max_row_group_size();
+    let mut columns = get_column_writers(parquet_schema, &props, &schema)
+        .map_err(|error| PqError::write_error(output, error))?;
+    let mut buffered_rows = 0usize;
+    let mut appended_rows = 0u64;
+
+    for path in paths {
+        let reader = reader_builder(path)?
+            .with_batch_size(batch_size)
+            .build()
+            .map_err(|error| build_error(path, error))?;
+        for batch_result in reader {
+            let batch = batch_result.map_err(|error| decode_error(path, error))?;
+            let mut column_writers = columns.iter_mut();
+            for (field, array) in schema.fields().iter().zip(batch.columns()) {
+                for leaf in compute_leaves(field, array)
+                    .map_err(|error| PqError::write_error(output, error))?
+                {
+                    let column_writer = column_writers.next().ok_or_else(|| {
+                        PqError::write_error(output, "schema has more leaves than the file")
+                    })?;
+                    column_writer
+                        .write(&leaf)
+                        .map_err(|error| PqError::write_error(output, error))?;
+                }
+            }
+            buffered_rows += batch.num_rows();
+            appended_rows += batch.num_rows() as u64;
+
+            if buffered_rows >= max_rows {
+                let full = std::mem::replace(
+                    &mut columns,
+                    get_column_writers(parquet_schema, &props, &schema)
+                        .map_err(|error| PqError::write_error(output, error))?,
+                );
+                flush_row_group(&mut writer, full, output)?;
+                buffered_rows = 0;
+            }
+        }
+    }
+    if buffered_rows > 0 {
+        flush_row_group(&mut writer, columns, output)?;
+    }
+
+    writer
+        .close()
+        .map_err(|error| PqError::write_error(output, error))?;
+    pending_output.commit()?;
+
+    Ok(AppendSummary {
+        existing_rows: u64::try_from(file_metadata.num_rows()).unwrap_or_default(),
+        appended_rows,
+    })
+}
+
+/// Write `input` to `output` with its footer key-value metadata edited. The row groups
+/// are copied byte for byte, so no data is decoded or re-encoded.
+pub fn rewrite_key_value_metadata(input: &Path, output: &Path, edit: &MetadataEdit) -> Result<()> {
+    let reader = serialized_reader(input)?;
+    let file_metadata = reader.metadata().file_metadata();
+    let entries = edit_key_value_metadata(input, file_metadata.key_value_metadata(), edit)?;
+
+    let mut props = WriterProperties::builder()
+        .set_key_value_metadata((!entries.is_empty()).then_some(entries))
+        .set_sorting_columns(sorting::common_sorting_columns(reader.metadata()))
+        .set_writer_version(if file_metadata.version() >= 2 {
+            WriterVersion::PARQUET_2_0
+        } else {
+            WriterVersion::PARQUET_1_0
+        });
+    if let Some(created_by) = file_metadata.created_by() {
+        props = props.set_created_by(created_by.to_string());
+    }
+
+    let pending_output = crate::atomic_output::PendingOutput::new(output)?;
+    let output_file =
+        File::create(pending_output.path()).map_err(|error| PqError::write_error(output, error))?;
+    let mut writer = SerializedFileWriter::new(
+        output_file,
+        file_metadata.schema_descr().root_schema_ptr(),
+        Arc::new(props.build()),
+    )
+    .map_err(|error| PqError::write_error(output, error))?;
+    copy_row_groups(&mut writer, input, output)?;
+    writer
+        .close()
+        .map_err(|error| PqError::write_error(output, error))?;
+    pending_output.commit()
+}
+
+fn edit_key_value_metadata(
+    path: &Path,
+    existing: Option<&Vec<KeyValue>>,
+    edit: &MetadataEdit,
+) -> Result<Vec<KeyValue>> {
+    let mut entries = existing.cloned().unwrap_or_default();
+    match edit {
+        MetadataEdit::Set(pairs) => {
+            for (key, value) in pairs {
+                match entries.iter_mut().find(|entry| entry.key == *key) {
+                    Some(entry) => entry.value = Some(value.clone()),
+                    None => entries.push(KeyValue::new(key.clone(), value.clone())),
+                }
+            }
+        }
+        MetadataEdit::Remove(keys) => {
+            for key in keys {
+                if !entries.iter().any(|entry| entry.key == *key) {
+                    let available = entries.iter().map(|entry| entry.key.clone()).collect();
+                    return Err(PqError::metadata_key_not_found(path, key, available));
+                }
+                entries.retain(|entry| entry.key != *key);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+pub fn key_value_metadata(path: &Path) -> Result<Vec<KeyValueEntry>> {
+    let reader = serialized_reader(path)?;
+    Ok(reader
+        .metadata()
+        .file_metadata()
+        .key_value_metadata()
+        .into_iter()
+        .flatten()
+        .map(|entry| KeyValueEntry {
+            key: entry.key.clone(),
+            value: entry.value.clone(),
+        })
+        .collect())
+}
+
+/// Copy every row group of `source` to the end of `writer` byte for byte, along with
+/// the statistics, page indexes, and bloom filters that describe them.
+fn copy_row_groups(
+    writer: &mut SerializedFileWriter<File>,
+    source: &Path,
+    output: &Path,
+) -> Result<()> {
+    let file = File::open(source).with_path_context(source)?;
+    let read_options = ReadOptionsBuilder::new()
+        .with_reader_properties(
+            ReaderProperties::builder()
+                .set_read_bloom_filter(true)
+                .build(),
+        )
+        .build();
+    let reader = SerializedFileReader::new_with_options(
+        file.try_clone().with_path_context(source)?,
+        read_options,
+    )
+    .map_err(|error| footer_error(source, error))?;
+
+    for (index, row_group) in reader.metadata().row_groups().iter().enumerate() {
+        let row_group_reader = reader
+            .get_row_group(index)
+            .map_err(|error| PqError::from_read(source, error))?;
+        let rows = u64::try_from(row_group.num_rows()).unwrap_or_default();
+        let mut row_group_writer = writer
+            .next_row_group()
+            .map_err(|error| PqError::write_error(output, error))?;
+        for (column_index, column) in row_group.columns().iter().enumerate() {
+            let chunk = ColumnCloseResult {
+                bytes_written: u64::try_from(column.compressed_size()).unwrap_or_default(),
+                rows_written: rows,
+                metadata: column.clone(),
+                bloom_filter: row_group_reader
+                    .get_column_bloom_filter(column_index)
+                    .cloned(),
+                column_index: read_page_index(
+                    &file,
+                    source,
+                    column.column_index_offset(),
+                    column.column_index_length(),
+                )?,
+                offset_index: read_page_index(
+                    &file,
+                    source,
+                    column.offset_index_offset(),
+                    column.offset_index_length(),
+                )?,
+            };
+            row_group_writer
+                .append_column(&file, chunk)
+                .map_err(|error| PqError::write_error(output, error))?;
+        }
+        row_group_writer
+            .close()
+            .map_err(|error| PqError::write_error(output, error))?;
+    }
+    Ok(())
+}
+
+/// A column or offset index exactly as stored, for copying it to another file.
+fn read_page_index<T: TSerializable>(
+    file: &File,
+    path: &Path,
+    offset: Option<i64>,
+    length: Option<i32>,
+) -> Result<Option<T>> {
+    let (Some(offset), Some(length)) = (offset, length) else {
+        return Ok(None);
+    };
+    let (Ok(offset), Ok(length)) = (u64::try_from(offset), usize::try_from(length)) else {
+        return Err(PqError::corrupted(path, "negative page index location"));
+    };
+    let bytes = file
+        .get_bytes(offset, length)
+        .map_err(|error| PqError::corrupted(path, error))?;
+    T::read_from_in_protocol(&mut TCompactInputProtocol::new(bytes.as_ref()))
+        .map(Some)
+        .map_err(|error| PqError::corrupted(path, error))
+}
+
+fn flush_row_group(
+    writer: &mut SerializedFileWriter<File>,
+    columns: Vec<ArrowColumnWriter>,
+    output: &Path,
+) -> Result<()> {
+    let mut row_group_writer = writer
+        .next_row_group()
+        .map_err(|error| PqError::write_error(output, error))?;
+    for column in columns {
+        column
+            .close()
+            .and_then(|chunk| chunk.append_to_row_group(&mut row_group_writer))
+            .map_err(|error| PqError::write_error(output, error))?;
+    }
+    row_group_writer
+        .close()
+        .map_err(|error| PqError::write_error(output, error))?;
+    Ok(())
+}
+
 fn tail_row_groups(
     path: &Path,
     metadata: &parquet::file::metadata::ParquetMetaData,
+    candidates: &[usize],
     rows: usize,
 ) -> Result<(Vec<usize>, usize)> {
     let mut selected_groups = Vec::new();
     let mut selected_rows = 0usize;
 
-    for row_group_index in (0..metadata.num_row_groups()).rev() {
+    for &row_group_index in candidates.iter().rev() {
         let row_group = metadata.row_group(row_group_index);
         let row_group_rows = usize::try_from(row_group.num_rows()).map_err(|_| {
             PqError::invalid_metadata(
@@ -225,19 +1069,247 @@ fn tail_row_groups(
 }
 
 fn compression_summary(metadata: &parquet::file::metadata::ParquetMetaData) -> CompressionSummary {
-    let mut compression = None;
+    summarize_codecs(
+        metadata
+            .row_groups()
+            .iter()
+            .flat_map(|row_group| row_group.columns().iter())
+            .map(|column| CompressionCodec::from(column.compression())),
+    )
+}
 
-    for row_group_index in 0..metadata.num_row_groups() {
-        let row_group = metadata.row_group(row_group_index);
-        for column_index in 0..row_group.num_columns() {
-            let codec = CompressionCodec::from(row_group.column(column_index).compression());
-            match compression {
-                None => compression = Some(codec),
-                Some(existing) if existing == codec => {}
-                Some(_) => return CompressionSummary::Mixed,
+fn codec_columns(metadata: &ParquetMetaData) -> Vec<CodecColumns> {
+    let num_columns = metadata.file_metadata().schema_descr().num_columns();
+    let mut usage: Vec<CodecColumns> = Vec::new();
+    for index in 0..num_columns {
+        let mut codecs: Vec<CompressionCodec> = metadata
+            .row_groups()
+            .iter()
+            .filter_map(|row_group| row_group.columns().get(index))
+            .map(|chunk| CompressionCodec::from(chunk.compression()))
+            .collect();
+        codecs.sort_by_key(ToString::to_string);
+        codecs.dedup();
+        for codec in codecs {
+            match usage.iter_mut().find(|usage| usage.codec == codec) {
+                Some(usage) => usage.columns += 1,
+                None => usage.push(CodecColumns { codec, columns: 1 }),
             }
         }
     }
+    usage.sort_by(|a, b| {
+        b.columns
+            .cmp(&a.columns)
+            .then_with(|| a.codec.to_string().cmp(&b.codec.to_string()))
+    });
+    usage
+}
+
+fn summarize_codecs(codecs: impl IntoIterator<Item = CompressionCodec>) -> CompressionSummary {
+    let mut compression = None;
+
+    for codec in codecs {
+        match compression {
+            None => compression = Some(codec),
+            Some(existing) if existing == codec => {}
+            Some(_) => return CompressionSummary::Mixed,
+        }
+    }
 
     compression.map_or(CompressionSummary::Unknown, CompressionSummary::Single)
 }
+
+pub fn column_chunks(path: &Path) -> Result<Vec<ColumnChunkSummary>> {
+    let reader = serialized_reader(path)?;
+    let file = File::open(path).with_path_context(path)?;
+    let metadata = reader.metadata();
+    let schema = metadata.file_metadata().schema_descr();
+
+    (0..schema.num_columns())
+        .map(|index| {
+            let descriptor = schema.column(index);
+            let chunks = metadata
+                .row_groups()
+                .iter()
+                .filter_map(|row_group| row_group.columns().get(index))
+                .collect::<Vec<_>>();
+            let bloom_filters = chunks
+                .iter()
+                .filter(|chunk| chunk.bloom_filter_offset().is_some())
+                .collect::<Vec<_>>();
+            let bloom_filter_bytes = if bloom_filters.is_empty() {
+                None
+            } else {
+                bloom_filters
+                    .iter()
+                    .map(|chunk| {
+                        chunk
+                            .bloom_filter_length()
+                            .and_then(|length| u64::try_from(length).ok())
+                    })
+                    .sum::<Option<u64>>()
+            };
+            let encodings = chunks
+                .iter()
+                .map(|chunk| chunk_encodings(path, &file, chunk))
+                .collect::<Result<Vec<_>>>()?;
+            let dictionaries = encodings
+                .iter()
+                .filter(|encodings| encodings.has_dictionary())
+                .collect::<Vec<_>>();
+
+            Ok(ColumnChunkSummary {
+                column: descriptor.path().string(),
+                column_type: ColumnType::from_parquet(&descriptor),
+                compression: summarize_codecs(
+                    chunks
+                        .iter()
+                        .map(|chunk| CompressionCodec::from(chunk.compression())),
+                ),
+                compressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.compressed_size())?,
+                uncompressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.uncompressed_size())?,
+                row_groups: chunks.len(),
+                bloom_filter_row_groups: bloom_filters.len(),
+                bloom_filter_bytes,
+                encodings: distinct_encodings(&chunks),
+                dictionary_row_groups: dictionaries.len(),
+                dictionary_encoded_row_groups: encodings
+                    .iter()
+                    .filter(|encodings| encodings.fully_dictionary_encoded())
+                    .count(),
+                dictionary_entries: dictionary_total(&dictionaries, |encodings| {
+                    encodings.dictionary_entries
+                }),
+                dictionary_bytes: dictionary_total(&dictionaries, |encodings| {
+                    encodings.dictionary_bytes
+                }),
+            })
+        })
+        .collect()
+}
+
+pub fn row_groups(path: &Path) -> Result<Vec<RowGroupSummary>> {
+    let reader = serialized_reader(path)?;
+    let metadata = reader.metadata();
+    metadata
+        .row_groups()
+        .iter()
+        .enumerate()
+        .map(|(index, row_group)| {
+            let chunks = row_group.columns().iter().collect::<Vec<_>>();
+            let count = |has: fn(&ColumnChunkMetaData) -> bool| {
+                chunks.iter().filter(|chunk| has(chunk)).count()
+            };
+
+            Ok(RowGroupSummary {
+                index,
+                num_rows: row_group.num_rows(),
+                compressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.compressed_size())?,
+                uncompressed_bytes: chunk_bytes(path, &chunks, |chunk| chunk.uncompressed_size())?,
+                columns: chunks.len(),
+                statistics_columns: count(|chunk| chunk.statistics().is_some()),
+                dictionary_columns: count(|chunk| chunk.dictionary_page_offset().is_some()),
+                page_index_columns: count(|chunk| {
+                    chunk.column_index_offset().is_some() && chunk.offset_index_offset().is_some()
+                }),
+                bloom_filter_columns: count(|chunk| chunk.bloom_filter_offset().is_some()),
+                sorting_columns: sorting::row_group_sorting(path, metadata, row_group)?,
+            })
+        })
+        .collect()
+}
+
+fn chunk_encodings(
+    path: &Path,
+    file: &File,
+    chunk: &ColumnChunkMetaData,
+) -> Result<ChunkEncodings> {
+    let may_have_dictionary = chunk.dictionary_page_offset().is_some()
+        || chunk.encodings().iter().any(|encoding| {
+            matches!(
+                encoding,
+                Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+            )
+        });
+    if may_have_dictionary {
+        super::pages::chunk_encodings(path, file, chunk)
+    } else {
+        Ok(ChunkEncodings::default())
+    }
+}
+
+fn dictionary_total(
+    dictionaries: &[&ChunkEncodings],
+    value: impl Fn(&ChunkEncodings) -> Option<u64>,
+) -> Option<u64> {
+    if dictionaries.is_empty() {
+        None
+    } else {
+        dictionaries.iter().map(|encodings| value(encodings)).sum()
+    }
+}
+
+fn distinct_encodings(chunks: &[&ColumnChunkMetaData]) -> Vec<String> {
+    let mut encodings = Vec::new();
+    for encoding in chunks.iter().flat_map(|chunk| chunk.encodings()) {
+        let name = encoding.to_string();
+        if !encodings.contains(&name) {
+            encodings.push(name);
+        }
+    }
+    encodings
+}
+
+fn chunk_bytes(
+    path: &Path,
+    chunks: &[&ColumnChunkMetaData],
+    size: impl Fn(&ColumnChunkMetaData) -> i64,
+) -> Result<u64> {
+    chunks.iter().try_fold(0u64, |total, chunk| {
+        u64::try_from(size(chunk))
+            .ok()
+            .and_then(|bytes| total.checked_add(bytes))
+            .ok_or_else(|| PqError::invalid_metadata(path, "invalid column chunk size"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{write_batches, OutputFormat};
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+
+    #[test]
+    fn selected_columns_print_in_selector_order() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("pq_selection_{}.parquet", std::process::id()));
+        let batch = RecordBatch::try_from_iter([
+            ("a", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+            ("b", Arc::new(StringArray::from(vec!["x", "y"])) as ArrayRef),
+            ("c", Arc::new(Int64Array::from(vec![3, 4])) as ArrayRef),
+        ])?;
+        let mut writer = ArrowWriter::try_new(File::create(&path)?, batch.schema(), None)
+            .map_err(|error| PqError::write_error(&path, error))?;
+        writer
+            .write(&batch)
+            .map_err(|error| PqError::write_error(&path, error))?;
+        writer
+            .close()
+            .map_err(|error| PqError::write_error(&path, error))?;
+
+        let builder = reader_builder(&path)?;
+        let selectors = ["b".to_string(), "a".to_string()];
+        let selection = selected_projection(&path, &builder, &selectors, ColumnCase::Sensitive)?;
+        let batches = builder
+            .with_projection(selection.mask.clone())
+            .build()
+            .map_err(|error| build_error(&path, error))?
+            .map(|batch| selection.arrange(&path, &batch?))
+            .collect::<Result<Vec<_>>>()?;
+        let mut csv = Vec::new();
+        write_batches(&mut csv, OutputFormat::Csv, &batches)?;
+        assert_eq!(String::from_utf8_lossy(&csv), "b,a\nx,1\ny,2\n");
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,507 @@
+use super::pages::PageStats;
+use crate::error::{PqError, ResultExt};
+use crate::model::{
+    ColumnStats, ColumnType, LogicalTypeKind, PagePruning, PruneDecision, PruneReport,
+    RowGroupPruning, StatValue, TimeUnit,
+};
+use crate::predicate::{CompareOp, Literal, Predicate};
+use crate::Result;
+use arrow::array::{Array, AsArray, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    DataType, FieldRef, Float32Type, Float64Type, Int32Type, Int64Type, Schema,
+    TimeUnit as ArrowTimeUnit,
+};
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
+use parquet::arrow::parquet_column;
+use parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use parquet::file::page_index::index::Index;
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Evaluate a predicate against row-group statistics and page indexes without reading data.
+pub fn prune_file(path: &Path, text: &str, predicate: &Predicate) -> Result<PruneReport> {
+    let file = File::open(path).with_path_context(path)?;
+    let options = ArrowReaderOptions::new().with_page_index(true);
+    let reader_metadata = ArrowReaderMetadata::load(&file, options)
+        .map_err(|error| super::parquet::footer_error(path, error))?;
+    let metadata = reader_metadata.metadata();
+
+    let columns = predicate.columns();
+    let resolved = columns
+        .iter()
+        .map(|column| resolve_column(path, text, metadata, reader_metadata.schema(), column))
+        .collect::<Result<Vec<_>>>()?;
+    let targets = Targets::new(text, predicate, &resolved)?;
+
+    let row_groups = metadata
+        .row_groups()
+        .iter()
+        .enumerate()
+        .map(|(index, row_group)| {
+            let bounds: HashMap<&str, Bounds> = resolved
+                .iter()
+                .map(|column| {
+                    (
+                        column.name.as_str(),
+                        Bounds::row_group(row_group, column.leaf),
+                    )
+                })
+                .collect();
+            let decision = if targets.may_match(predicate, &bounds) {
+                PruneDecision::Read
+            } else {
+                PruneDecision::Skip
+            };
+            let matches_all =
+                decision == PruneDecision::Read && targets.must_match(predicate, &bounds);
+            let pages = metadata
+                .column_index()
+                .and_then(|index_by_group| index_by_group.get(index))
+                .and_then(|indexes| {
+                    page_pruning(predicate, &targets, &resolved, indexes, &bounds, decision)
+                });
+
+            RowGroupPruning {
+                index,
+                num_rows: row_group.num_rows(),
+                decision,
+                matches_all,
+                columns: resolved
+                    .iter()
+                    .map(|column| column.stats(index, row_group))
+                    .collect(),
+                pages,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(PruneReport {
+        path: path.to_path_buf(),
+        predicate: text.to_string(),
+        columns,
+        page_index: row_groups.iter().any(|group| group.pages.is_some()),
+        row_groups,
+    })
+}
+
+/// A predicate column mapped to its Parquet leaf.
+pub struct ResolvedColumn {
+    name: String,
+    pub leaf: usize,
+    pub field: FieldRef,
+    column_type: ColumnType,
+}
+
+impl ResolvedColumn {
+    /// Whether row-group statistics allow this column to contain `target`.
+    pub fn may_contain(&self, row_group: &RowGroupMetaData, target: &StatValue) -> bool {
+        Bounds::row_group(row_group, self.leaf).may_match_compare(CompareOp::Eq, target)
+    }
+
+    fn stats(&self, index: usize, row_group: &RowGroupMetaData) -> ColumnStats {
+        let chunk = row_group.column(self.leaf);
+        let statistics = chunk.statistics();
+        let (min, max) = statistics.map_or((None, None), super::stats::stat_bounds);
+        ColumnStats {
+            column: self.name.clone(),
+            column_type: self.column_type.clone(),
+            null_count: statistics
+                .and_then(|statistics| statistics.null_count_opt())
+                .unwrap_or(0),
+            min,
+            max,
+            compressed_bytes: u64::try_from(chunk.compressed_size()).unwrap_or_default(),
+            uncompressed_bytes: u64::try_from(chunk.uncompressed_size()).unwrap_or_default(),
+            row_groups_without_statistics: usize::from(statistics.is_none()),
+            min_truncated: false,
+            max_truncated: false,
+            problems: statistics.map_or_else(Vec::new, |statistics| {
+                super::stats::chunk_problems(&self.column_type, index, row_group, statistics)
+            }),
+        }
+    }
+}
+
+pub fn resolve_column(
+    path: &Path,
+    text: &str,
+    metadata: &ParquetMetaData,
+    schema: &Schema,
+    name: &str,
+) -> Result<ResolvedColumn> {
+    let parquet_schema = metadata.file_metadata().schema_descr();
+    let Some((leaf, field)) = parquet_column(parquet_schema, schema, name) else {
+        return match schema.field_with_name(name) {
+            Ok(_) => Err(PqError::invalid_predicate(
+                text,
+                format!("column '{name}' is nested; only primitive columns can be compared"),
+            )),
+            Err(_) => Err(PqError::column_not_found(path, name)),
+        };
+    };
+
+    Ok(ResolvedColumn {
+        name: name.to_string(),
+        leaf,
+        field: Arc::clone(field),
+        column_type: ColumnType::from_parquet(&parquet_schema.column(leaf)),
+    })
+}
+
+/// Predicate literals converted to the physical representation used by statistics.
+///
+/// A literal maps to `None` when the column's statistics cannot be compared with it
+/// (unsigned integers, decimals, INT96 and other fixed-width types, or a fractional or
+/// out-of-range number on an integer column); such comparisons never rule a row group
+/// out.
+struct Targets {
+    values: HashMap<(String, String), Option<StatValue>>,
+}
+
+impl Targets {
+    fn new(text: &str, predicate: &Predicate, columns: &[ResolvedColumn]) -> Result<Self> {
+        let mut values = HashMap::new();
+        collect_targets(text, predicate, columns, &mut values)?;
+        Ok(Self { values })
+    }
+
+    fn get(&self, column: &str, literal: &Literal) -> Option<&StatValue> {
+        self.values
+            .get(&(column.to_string(), literal.text().to_string()))
+            .and_then(Option::as_ref)
+    }
+
+    fn may_match(&self, predicate: &Predicate, bounds: &HashMap<&str, Bounds>) -> bool {
+        match predicate {
+            Predicate::And(children) => children.iter().all(|child| self.may_match(child, bounds)),
+            Predicate::Or(children) => children.iter().any(|child| self.may_match(child, bounds)),
+            Predicate::IsNull { column, negated } => bounds
+                .get(column.as_str())
+                .is_none_or(|bounds| bounds.may_match_null(*negated)),
+            Predicate::Compare { column, op, value } => {
+                match (bounds.get(column.as_str()), self.get(column, value)) {
+                    (Some(bounds), Some(target)) => bounds.may_match_compare(*op, target),
+                    (Some(bounds), None) => !bounds.all_null,
+                    (None, _) => true,
+                }
+            }
+        }
+    }
+
+    /// Whether statistics prove every row matches, so the rows can be counted unread.
+    /// Missing statistics, float targets and string equality never prove anything.
+    fn must_match(&self, predicate: &Predicate, bounds: &HashMap<&str, Bounds>) -> bool {
+        match predicate {
+            Predicate::And(children) => children.iter().all(|child| self.must_match(child, bounds)),
+            Predicate::Or(children) => children.iter().any(|child| self.must_match(child, bounds)),
+            Predicate::IsNull { column, negated } => bounds
+                .get(column.as_str())
+                .is_some_and(|bounds| bounds.must_match_null(*negated)),
+            Predicate::Compare { column, op, value } => {
+                match (bounds.get(column.as_str()), self.get(column, value)) {
+                    (Some(bounds), Some(target)) => bounds.must_match_compare(*op, target),
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+fn collect_targets(
+    text: &str,
+    predicate: &Predicate,
+    columns: &[ResolvedColumn],
+    values: &mut HashMap<(String, String), Option<StatValue>>,
+) -> Result<()> {
+    match predicate {
+        Predicate::And(children) | Predicate::Or(children) => {
+            for child in children {
+                collect_targets(text, child, columns, values)?;
+            }
+        }
+        Predicate::IsNull { .. } => {}
+        Predicate::Compare { column, value, .. } => {
+            let Some(resolved) = columns.iter().find(|resolved| &resolved.name == column) else {
+                return Ok(());
+            };
+            let target = literal_target(resolved, value).map_err(|details| {
+                PqError::invalid_predicate(text, format!("column '{column}': {details}"))
+            })?;
+            values.insert((column.clone(), value.text().to_string()), target);
+        }
+    }
+    Ok(())
+}
+
+pub fn literal_target(
+    column: &ResolvedColumn,
+    literal: &Literal,
+) -> std::result::Result<Option<StatValue>, String> {
+    let Some(data_type) = statistics_type(column) else {
+        return Ok(None);
+    };
+    let physical = match data_type {
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Date32
+        | DataType::Time32(_) => DataType::Int32,
+        DataType::Int64 | DataType::Time64(_) | DataType::Timestamp(_, _) => DataType::Int64,
+        DataType::Float32 => DataType::Float32,
+        DataType::Float64 => DataType::Float64,
+        DataType::Boolean => DataType::Boolean,
+        _ => return Ok(Some(StatValue::Binary(literal.text().as_bytes().to_vec()))),
+    };
+
+    let input = StringArray::from(vec![literal.text()]);
+    let invalid = || format!("cannot compare {data_type} with '{}'", literal.text());
+    let typed = cast(&input, &data_type).map_err(|_| invalid())?;
+    let typed = cast(&typed, &physical).map_err(|_| invalid())?;
+    if typed.is_null(0) {
+        // A fractional or out-of-range number is still a valid bound for an integer
+        // column; the filter compares it as a float, and pruning does without it
+        if data_type.is_integer() && literal.as_float().is_some() {
+            return Ok(None);
+        }
+        return Err(invalid());
+    }
+
+    Ok(match physical {
+        DataType::Int32 => Some(StatValue::Int32(typed.as_primitive::<Int32Type>().value(0))),
+        DataType::Int64 => Some(StatValue::Int64(typed.as_primitive::<Int64Type>().value(0))),
+        DataType::Float32 => Some(StatValue::Float(
+            typed.as_primitive::<Float32Type>().value(0),
+        ))
+        .filter(|_| !literal_is_nan(literal)),
+        DataType::Float64 => Some(StatValue::Double(
+            typed.as_primitive::<Float64Type>().value(0),
+        ))
+        .filter(|_| !literal_is_nan(literal)),
+        DataType::Boolean => Some(StatValue::Boolean(typed.as_boolean().value(0))),
+        _ => None,
+    })
+}
+
+/// The Arrow type whose physical value matches what the column's statistics store.
+///
+/// Temporal types follow the Parquet logical type, since the Arrow schema may use a
+/// different unit than the one written to the file.
+fn statistics_type(column: &ResolvedColumn) -> Option<DataType> {
+    let data_type = column.field.data_type();
+    match &column.column_type.logical {
+        Some(LogicalTypeKind::Timestamp { unit, .. }) => {
+            let timezone = match data_type {
+                DataType::Timestamp(_, timezone) => timezone.clone(),
+                _ => None,
+            };
+            Some(DataType::Timestamp(arrow_time_unit(*unit), timezone))
+        }
+        Some(LogicalTypeKind::Date) => Some(DataType::Date32),
+        Some(LogicalTypeKind::Time { unit, .. }) => Some(match unit {
+            TimeUnit::Millis => DataType::Time32(ArrowTimeUnit::Millisecond),
+            _ => DataType::Time64(arrow_time_unit(*unit)),
+        }),
+        Some(LogicalTypeKind::Integer {
+            is_signed: false, ..
+        })
+        | Some(LogicalTypeKind::Decimal { .. })
+        | Some(LogicalTypeKind::Float16) => None,
+        _ => match data_type {
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Boolean
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Utf8View => Some(data_type.clone()),
+            _ => None,
+        },
+    }
+}
+
+fn arrow_time_unit(unit: TimeUnit) -> ArrowTimeUnit {
+    match unit {
+        TimeUnit::Millis => ArrowTimeUnit::Millisecond,
+        TimeUnit::Micros => ArrowTimeUnit::Microsecond,
+        TimeUnit::Nanos => ArrowTimeUnit::Nanosecond,
+    }
+}
+
+fn literal_is_nan(literal: &Literal) -> bool {
+    literal.text().parse::<f64>().is_ok_and(f64::is_nan)
+}
+
+/// What statistics say about one column within a row group or page.
+#[derive(Clone)]
+struct Bounds {
+    min: Option<StatValue>,
+    max: Option<StatValue>,
+    null_count: Option<u64>,
+    all_null: bool,
+}
+
+impl Bounds {
+    fn row_group(row_group: &RowGroupMetaData, leaf: usize) -> Self {
+        let Some(statistics) = row_group.column(leaf).statistics() else {
+            return Self::unknown();
+        };
+        let (min, max) = super::stats::stat_bounds(statistics);
+        let null_count = statistics.null_count_opt();
+        let all_null = row_group.num_rows() > 0
+            && null_count.is_some_and(|nulls| i64::try_from(nulls) == Ok(row_group.num_rows()));
+        Self {
+            min,
+            max,
+            null_count,
+            all_null,
+        }
+    }
+
+    fn page(page: PageStats) -> Self {
+        let all_null = page.min.is_none() && page.max.is_none();
+        Self {
+            min: page.min,
+            max: page.max,
+            null_count: page.null_count.and_then(|nulls| u64::try_from(nulls).ok()),
+            all_null,
+        }
+    }
+
+    fn unknown() -> Self {
+        Self {
+            min: None,
+            max: None,
+            null_count: None,
+            all_null: false,
+        }
+    }
+
+    fn may_match_null(&self, negated: bool) -> bool {
+        if negated {
+            !self.all_null
+        } else {
+            self.all_null || self.null_count != Some(0)
+        }
+    }
+
+    fn may_match_compare(&self, op: CompareOp, target: &StatValue) -> bool {
+        if self.all_null {
+            return false;
+        }
+        let order = |bound: &Option<StatValue>| {
+            bound
+                .as_ref()
+                .and_then(|bound| super::stats::partial_cmp_value(bound, target))
+        };
+        let (min, max) = (order(&self.min), order(&self.max));
+
+        match op {
+            CompareOp::Eq => min.is_none_or(|min| min.is_le()) && max.is_none_or(|max| max.is_ge()),
+            CompareOp::NotEq => {
+                !(min.is_some_and(|min| min.is_eq()) && max.is_some_and(|max| max.is_eq()))
+            }
+            CompareOp::Lt => min.is_none_or(|min| min.is_lt()),
+            CompareOp::LtEq => min.is_none_or(|min| min.is_le()),
+            CompareOp::Gt => max.is_none_or(|max| max.is_gt()),
+            CompareOp::GtEq => max.is_none_or(|max| max.is_ge()),
+        }
+    }
+
+    fn must_match_null(&self, negated: bool) -> bool {
+        if negated {
+            self.null_count == Some(0)
+        } else {
+            self.all_null
+        }
+    }
+
+    // Nulls never match a comparison, and NaN escapes float bounds. Writers may cut
+    // long string bounds short, which keeps them ordered but not exact.
+    fn must_match_compare(&self, op: CompareOp, target: &StatValue) -> bool {
+        if self.null_count != Some(0) {
+            return false;
+        }
+        if !matches!(
+            target,
+            StatValue::Int32(_)
+                | StatValue::Int64(_)
+                | StatValue::Boolean(_)
+                | StatValue::Binary(_)
+        ) {
+            return false;
+        }
+        let order = |bound: &Option<StatValue>| {
+            bound
+                .as_ref()
+                .and_then(|bound| super::stats::partial_cmp_value(bound, target))
+        };
+        let (Some(min), Some(max)) = (order(&self.min), order(&self.max)) else {
+            return false;
+        };
+
+        match op {
+            CompareOp::Eq => !matches!(target, StatValue::Binary(_)) && min.is_eq() && max.is_eq(),
+            CompareOp::NotEq => max.is_lt() || min.is_gt(),
+            CompareOp::Lt => max.is_lt(),
+            CompareOp::LtEq => max.is_le(),
+            CompareOp::Gt => min.is_gt(),
+            CompareOp::GtEq => min.is_ge(),
+        }
+    }
+}
+
+/// Count pages of the referenced columns that the predicate rules out.
+///
+/// Returns `None` when none of the referenced columns has a column index.
+///
+/// Each page is checked with its own bounds for its column and row-group bounds for the
+/// other columns, since pages of different columns do not share row boundaries.
+fn page_pruning(
+    predicate: &Predicate,
+    targets: &Targets,
+    columns: &[ResolvedColumn],
+    indexes: &[Index],
+    row_group_bounds: &HashMap<&str, Bounds>,
+    decision: PruneDecision,
+) -> Option<PagePruning> {
+    let mut pages = None::<PagePruning>;
+
+    for column in columns {
+        let Some(page_bounds) = indexes.get(column.leaf).and_then(page_bounds) else {
+            continue;
+        };
+        let pages = pages.get_or_insert_with(PagePruning::default);
+        pages.total += page_bounds.len();
+        if decision == PruneDecision::Skip {
+            pages.skipped += page_bounds.len();
+            continue;
+        }
+
+        for bounds in page_bounds {
+            let mut combined: HashMap<&str, Bounds> = columns
+                .iter()
+                .filter(|other| other.name != column.name)
+                .filter_map(|other| {
+                    let bounds = row_group_bounds.get(other.name.as_str())?;
+                    Some((other.name.as_str(), bounds.clone()))
+                })
+                .collect();
+            combined.insert(column.name.as_str(), bounds);
+            if !targets.may_match(predicate, &combined) {
+                pages.skipped += 1;
+            }
+        }
+    }
+
+    pages
+}
+
+fn page_bounds(index: &Index) -> Option<Vec<Bounds>> {
+    super::pages::page_stats(index).map(|pages| pages.into_iter().map(Bounds::page).collect())
+}
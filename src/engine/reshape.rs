@@ -0,0 +1,204 @@
+//! Flattening struct columns and exploding list columns into tabular batches
+
+use crate::error::PqError;
+use crate::model::ReshapeOptions;
+use crate::Result;
+use arrow::array::{
+    make_array, Array, ArrayRef, AsArray, GenericListArray, OffsetSizeTrait, RecordBatch,
+    RecordBatchOptions, UInt64Array,
+};
+use arrow::buffer::NullBuffer;
+use arrow::compute::take;
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use std::path::Path;
+use std::sync::Arc;
+
+/// The schema `reshape` produces for batches of `schema`.
+pub fn reshape_schema(path: &Path, schema: SchemaRef, options: &ReshapeOptions) -> Result<Schema> {
+    let empty = RecordBatch::new_empty(schema);
+    Ok(reshape(path, &empty, options)?.schema().as_ref().clone())
+}
+
+/// Flatten, then explode, then flatten again so exploded structs also become columns.
+/// The explode column is named as it reads after the first flatten, e.g. `user.tags`.
+pub fn reshape(path: &Path, batch: &RecordBatch, options: &ReshapeOptions) -> Result<RecordBatch> {
+    let mut batch = batch.clone();
+    if options.flatten {
+        batch = flatten(&batch)?;
+    }
+    if let Some(column) = &options.explode {
+        batch = explode(path, &batch, column, options.keep_empty)?;
+        if options.flatten {
+            batch = flatten(&batch)?;
+        }
+    }
+    Ok(batch)
+}
+
+fn flatten(batch: &RecordBatch) -> Result<RecordBatch> {
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+        flatten_column(
+            field.name(),
+            field.is_nullable(),
+            array,
+            &mut fields,
+            &mut arrays,
+        )?;
+    }
+    Ok(RecordBatch::try_new_with_options(
+        Arc::new(Schema::new_with_metadata(
+            fields,
+            batch.schema().metadata().clone(),
+        )),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(batch.num_rows())),
+    )?)
+}
+
+fn flatten_column(
+    name: &str,
+    nullable: bool,
+    array: &ArrayRef,
+    fields: &mut Vec<Field>,
+    arrays: &mut Vec<ArrayRef>,
+) -> Result<()> {
+    match array.as_struct_opt() {
+        Some(parent) if !parent.fields().is_empty() => {
+            for (child_field, child) in parent.fields().iter().zip(parent.columns()) {
+                flatten_column(
+                    &format!("{name}.{}", child_field.name()),
+                    nullable || child_field.is_nullable(),
+                    &with_parent_nulls(child, parent.nulls())?,
+                    fields,
+                    arrays,
+                )?;
+            }
+        }
+        _ => {
+            fields.push(Field::new(name, array.data_type().clone(), nullable));
+            arrays.push(Arc::clone(array));
+        }
+    }
+    Ok(())
+}
+
+/// A child of a struct array, null wherever its parent is null.
+pub(super) fn with_parent_nulls(
+    child: &ArrayRef,
+    parent_nulls: Option<&NullBuffer>,
+) -> Result<ArrayRef> {
+    if parent_nulls.is_none() {
+        return Ok(Arc::clone(child));
+    }
+    let nulls = NullBuffer::union(parent_nulls, child.nulls());
+    let data = child.to_data().into_builder().nulls(nulls).build()?;
+    Ok(make_array(data))
+}
+
+/// One row per list element, repeating the other columns. Null and empty lists give no
+/// rows, or a single row with a null element when `keep_empty` is set.
+fn explode(
+    path: &Path,
+    batch: &RecordBatch,
+    column: &str,
+    keep_empty: bool,
+) -> Result<RecordBatch> {
+    let schema = batch.schema();
+    let Some((position, _)) = schema.column_with_name(column) else {
+        return Err(PqError::column_not_found_among(
+            path,
+            column,
+            schema.fields().iter().map(|field| field.name().clone()),
+        ));
+    };
+    let list = batch.column(position);
+    let Some((values, spans)) = list_spans(list) else {
+        let kind = match list.data_type() {
+            DataType::Struct(_) => "a struct".to_string(),
+            DataType::Map(..) => "a map".to_string(),
+            other => other.to_string(),
+        };
+        return Err(PqError::cannot_explode(path, column, kind));
+    };
+
+    let mut rows = Vec::new();
+    let mut elements = Vec::new();
+    for (row, span) in spans.into_iter().enumerate() {
+        match span {
+            Some((start, length)) if length > 0 => {
+                for element in start..start + length {
+                    rows.push(row as u64);
+                    elements.push(Some(element as u64));
+                }
+            }
+            _ if keep_empty => {
+                rows.push(row as u64);
+                elements.push(None);
+            }
+            _ => {}
+        }
+    }
+    let rows = UInt64Array::from(rows);
+    let elements = UInt64Array::from(elements);
+
+    let mut fields = Vec::with_capacity(schema.fields().len());
+    let mut arrays = Vec::with_capacity(schema.fields().len());
+    for (index, (field, array)) in schema.fields().iter().zip(batch.columns()).enumerate() {
+        if index == position {
+            let exploded = take(values.as_ref(), &elements, None)?;
+            fields.push(Field::new(field.name(), exploded.data_type().clone(), true));
+            arrays.push(exploded);
+        } else {
+            fields.push(field.as_ref().clone());
+            arrays.push(take(array.as_ref(), &rows, None)?);
+        }
+    }
+    Ok(RecordBatch::try_new_with_options(
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+        arrays,
+        &RecordBatchOptions::new().with_row_count(Some(rows.len())),
+    )?)
+}
+
+/// A list row's `(start, length)` within the list's child values, `None` for a null row.
+pub(super) type Span = Option<(usize, usize)>;
+
+/// A list array's child values and each row's span within them, or `None` when `array`
+/// is not a list.
+pub(super) fn list_spans(array: &ArrayRef) -> Option<(&ArrayRef, Vec<Span>)> {
+    fn spans<O: OffsetSizeTrait>(list: &GenericListArray<O>) -> Vec<Span> {
+        let offsets = list.value_offsets();
+        (0..list.len())
+            .map(|row| {
+                let start = offsets.get(row).map_or(0, |offset| offset.as_usize());
+                let end = offsets
+                    .get(row + 1)
+                    .map_or(start, |offset| offset.as_usize());
+                list.is_valid(row)
+                    .then(|| (start, end.saturating_sub(start)))
+            })
+            .collect()
+    }
+
+    match array.data_type() {
+        DataType::List(_) => {
+            let list = array.as_list::<i32>();
+            Some((list.values(), spans(list)))
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_list::<i64>();
+            Some((list.values(), spans(list)))
+        }
+        DataType::FixedSizeList(_, size) => {
+            let list = array.as_fixed_size_list();
+            let size = usize::try_from(*size).unwrap_or_default();
+            let spans = (0..list.len())
+                .map(|row| list.is_valid(row).then(|| (row * size, size)))
+                .collect();
+            Some((list.values(), spans))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,441 @@
+//! Random rows for `pq sample`, uniform or stratified by a column
+//!
+//! Every row is read once and offered to a reservoir of candidates (Algorithm R): one
+//! for the whole input and, with `--stratify`, one per distinct value of the column.
+//! How many rows each stratum gives is settled once every row has been seen. Sampled
+//! rows keep their input order.
+
+use super::parquet::{build_error, decode_error, reader_builder, selected_indices};
+use crate::error::PqError;
+use crate::model::{ColumnCase, SampleOptions, SampleResult, Stratum, MAX_STRATA};
+use crate::path_text::display_path;
+use crate::Result;
+use arrow::array::{ArrayRef, RecordBatch, RecordBatchReader, UInt32Array};
+use arrow::compute::{concat_batches, take_record_batch};
+use arrow::datatypes::SchemaRef;
+use arrow::row::{RowConverter, SortField};
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReader;
+use parquet::arrow::ProjectionMask;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn sample(
+    paths: &[&Path],
+    options: &SampleOptions,
+    case: ColumnCase,
+    batch_size: usize,
+) -> Result<SampleResult> {
+    let Some(first) = paths.first() else {
+        return Err(PqError::NoInputFiles);
+    };
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let capacity = options.rows.max(options.min_per_group.unwrap_or(0));
+    let mut uniform = Reservoir::new(options.rows);
+    let mut strata: Option<Strata> = None;
+    let mut too_many_strata = false;
+    let mut expected: Option<(SchemaRef, Vec<usize>)> = None;
+    let mut position = 0u64;
+
+    for path in paths {
+        let scan = FileScan::open(path, options, case, batch_size)?;
+        match &expected {
+            None => expected = Some((scan.reader.schema(), scan.positions.clone())),
+            Some((schema, _)) if *schema != scan.reader.schema() => {
+                return Err(PqError::SchemaMismatch {
+                    file1: display_path(first),
+                    file2: display_path(path),
+                    details: "Cannot sample files with different schemas together".to_string(),
+                });
+            }
+            Some(_) => {}
+        }
+        if let (None, false, Some(key)) = (&strata, too_many_strata, scan.key) {
+            let data_type = scan.reader.schema().field(key).data_type().clone();
+            let column = options.stratify.as_deref().unwrap_or_default();
+            strata = Some(Strata::new(path, column, key, data_type, capacity)?);
+        }
+
+        for batch in scan.reader {
+            let batch = batch.map_err(|error| decode_error(path, &error))?;
+            for row in 0..batch.num_rows() {
+                uniform.offer(&mut rng, row);
+            }
+            if let Some(current) = &mut strata {
+                if !current.offer(path, &mut rng, &batch)? {
+                    strata = None;
+                    too_many_strata = true;
+                }
+            }
+            let compact = Compact::new(
+                path,
+                &batch,
+                uniform
+                    .pending_rows()
+                    .chain(strata.iter().flat_map(|strata| strata.pending_rows())),
+            )?;
+            uniform.settle(&compact, position);
+            if let Some(strata) = &mut strata {
+                strata.settle(&compact, position);
+            }
+            position += batch.num_rows() as u64;
+        }
+    }
+
+    let Some((schema, positions)) = expected else {
+        return Err(PqError::NoInputFiles);
+    };
+    let (picks, strata) = match strata {
+        Some(strata) => strata.pick(&mut rng, options),
+        None => (uniform.picks, Vec::new()),
+    };
+    let batches = ordered_rows(first, &schema, picks)?
+        .into_iter()
+        .map(|batch| {
+            batch
+                .project(&positions)
+                .map_err(|error| PqError::corrupted(first, &error))
+        })
+        .collect::<Result<_>>()?;
+    Ok(SampleResult {
+        batches,
+        strata,
+        too_many_strata,
+    })
+}
+
+/// A file's reader over the selected columns plus the stratify column, which `positions`
+/// and `key` find in its batches.
+struct FileScan {
+    reader: ParquetRecordBatchReader,
+    /// The selected columns in selector order
+    positions: Vec<usize>,
+    key: Option<usize>,
+}
+
+impl FileScan {
+    fn open(
+        path: &Path,
+        options: &SampleOptions,
+        case: ColumnCase,
+        batch_size: usize,
+    ) -> Result<Self> {
+        let builder = reader_builder(path)?;
+        let selected: Vec<usize> = if options.columns.is_empty() {
+            (0..builder.schema().fields().len()).collect()
+        } else {
+            selected_indices(path, &builder, &options.columns, case)?
+        };
+        let names: Vec<&str> = builder
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect();
+        let key = options
+            .stratify
+            .as_deref()
+            .map(|column| crate::selector::find_column(path, &names, column, case))
+            .transpose()?;
+
+        let mut read = selected.clone();
+        read.extend(key);
+        read.sort_unstable();
+        read.dedup();
+        let positions = selected
+            .iter()
+            .filter_map(|index| read.binary_search(index).ok())
+            .collect();
+        let key = key.and_then(|key| read.binary_search(&key).ok());
+        let projection = ProjectionMask::roots(builder.parquet_schema(), read.iter().copied());
+        let reader = builder
+            .with_projection(projection)
+            .with_batch_size(batch_size)
+            .build()
+            .map_err(|error| build_error(path, error))?;
+        Ok(Self {
+            reader,
+            positions,
+            key,
+        })
+    }
+}
+
+/// A row kept for the sample, by its position in the whole input.
+struct Pick {
+    position: u64,
+    row: RecordBatch,
+}
+
+/// Up to `capacity` rows, each row seen so far equally likely to be among them.
+struct Reservoir {
+    capacity: usize,
+    seen: u64,
+    filled: usize,
+    picks: Vec<Pick>,
+    /// Slots the current batch's rows take, in the order they were offered
+    pending: Vec<(usize, usize)>,
+}
+
+impl Reservoir {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            seen: 0,
+            filled: 0,
+            picks: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    fn offer(&mut self, rng: &mut StdRng, row: usize) {
+        self.seen += 1;
+        let slot = if self.filled < self.capacity {
+            self.filled += 1;
+            Some(self.filled - 1)
+        } else {
+            usize::try_from(rng.gen_range(0..self.seen))
+                .ok()
+                .filter(|&slot| slot < self.capacity)
+        };
+        if let Some(slot) = slot {
+            self.pending.push((slot, row));
+        }
+    }
+
+    fn pending_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.pending.iter().map(|&(_, row)| row)
+    }
+
+    /// Fill the pending slots from `compact`, the batch whose first row is at
+    /// `first_position`. A slot taken twice keeps the later row.
+    fn settle(&mut self, compact: &Compact, first_position: u64) {
+        self.pending.sort_by_key(|&(slot, _)| slot);
+        for (slot, row) in self.pending.drain(..) {
+            let pick = Pick {
+                position: first_position + row as u64,
+                row: compact.row(row),
+            };
+            match self.picks.get_mut(slot) {
+                Some(existing) => *existing = pick,
+                None => self.picks.push(pick),
+            }
+        }
+    }
+}
+
+/// The rows of a batch some reservoir took, copied out so the batch can be dropped.
+struct Compact {
+    rows: Vec<usize>,
+    batch: RecordBatch,
+}
+
+impl Compact {
+    fn new(path: &Path, batch: &RecordBatch, rows: impl Iterator<Item = usize>) -> Result<Self> {
+        let mut rows: Vec<usize> = rows.collect();
+        rows.sort_unstable();
+        rows.dedup();
+        let indices =
+            UInt32Array::from_iter_values(rows.iter().filter_map(|&row| u32::try_from(row).ok()));
+        let batch =
+            take_record_batch(batch, &indices).map_err(|error| PqError::corrupted(path, &error))?;
+        Ok(Self { rows, batch })
+    }
+
+    fn row(&self, row: usize) -> RecordBatch {
+        let index = self.rows.binary_search(&row).unwrap_or_default();
+        self.batch.slice(index, 1)
+    }
+}
+
+/// A reservoir per distinct value of the stratify column.
+struct Strata {
+    /// The column's position in the batches read
+    key: usize,
+    converter: RowConverter,
+    index: HashMap<Box<[u8]>, usize>,
+    groups: Vec<(String, Reservoir)>,
+    capacity: usize,
+}
+
+impl Strata {
+    fn new(
+        path: &Path,
+        column: &str,
+        key: usize,
+        data_type: arrow::datatypes::DataType,
+        capacity: usize,
+    ) -> Result<Self> {
+        let converter = RowConverter::new(vec![SortField::new(data_type.clone())])
+            .map_err(|error| PqError::unsupported_column_type(path, column, data_type, error))?;
+        Ok(Self {
+            key,
+            converter,
+            index: HashMap::new(),
+            groups: Vec::new(),
+            capacity,
+        })
+    }
+
+    /// Offer each row to its value's reservoir; `false` once there are more than
+    /// [`MAX_STRATA`] values.
+    fn offer(&mut self, path: &Path, rng: &mut StdRng, batch: &RecordBatch) -> Result<bool> {
+        let column: ArrayRef = Arc::clone(batch.column(self.key));
+        let keys = self
+            .converter
+            .convert_columns(&[Arc::clone(&column)])
+            .map_err(|error| PqError::corrupted(path, &error))?;
+        let options = FormatOptions::default().with_null("null");
+        let labels = ArrayFormatter::try_new(column.as_ref(), &options)
+            .map_err(|error| PqError::corrupted(path, &error))?;
+        for row in 0..batch.num_rows() {
+            let key = keys.row(row);
+            let group = match self.index.get(key.as_ref()) {
+                Some(&group) => group,
+                None => {
+                    if self.groups.len() == MAX_STRATA {
+                        return Ok(false);
+                    }
+                    self.index.insert(key.as_ref().into(), self.groups.len());
+                    let label = labels.value(row).to_string();
+                    self.groups.push((label, Reservoir::new(self.capacity)));
+                    self.groups.len() - 1
+                }
+            };
+            if let Some((_, reservoir)) = self.groups.get_mut(group) {
+                reservoir.offer(rng, row);
+            }
+        }
+        Ok(true)
+    }
+
+    fn pending_rows(&self) -> impl Iterator<Item = usize> + '_ {
+        self.groups
+            .iter()
+            .flat_map(|(_, reservoir)| reservoir.pending_rows())
+    }
+
+    fn settle(&mut self, compact: &Compact, first_position: u64) {
+        for (_, reservoir) in &mut self.groups {
+            reservoir.settle(compact, first_position);
+        }
+    }
+
+    /// Each stratum's share of the sample, drawn at random from its reservoir.
+    fn pick(self, rng: &mut StdRng, options: &SampleOptions) -> (Vec<Pick>, Vec<Stratum>) {
+        let available: Vec<usize> = self
+            .groups
+            .iter()
+            .map(|(_, reservoir)| reservoir.picks.len())
+            .collect();
+        let shares = match options.min_per_group {
+            Some(minimum) => {
+                let rows: Vec<u64> = self
+                    .groups
+                    .iter()
+                    .map(|(_, reservoir)| reservoir.seen)
+                    .collect();
+                proportional_shares(options.rows, minimum, &rows, &available)
+            }
+            None => equal_shares(options.rows, &available),
+        };
+
+        let mut picks = Vec::new();
+        let mut strata = Vec::with_capacity(self.groups.len());
+        for ((value, reservoir), share) in self.groups.into_iter().zip(shares) {
+            strata.push(Stratum {
+                value,
+                rows: reservoir.seen,
+                sampled: share,
+            });
+            let mut chosen = rand::seq::index::sample(rng, reservoir.picks.len(), share).into_vec();
+            chosen.sort_unstable();
+            let mut candidates = reservoir.picks.into_iter().enumerate();
+            picks.extend(chosen.into_iter().filter_map(|index| {
+                candidates
+                    .by_ref()
+                    .find(|(candidate, _)| *candidate == index)
+                    .map(|(_, pick)| pick)
+            }));
+        }
+        (picks, strata)
+    }
+}
+
+/// `rows` spread evenly over strata holding `available` rows each; a stratum with too
+/// few rows for its share leaves the rest to the others.
+fn equal_shares(rows: usize, available: &[usize]) -> Vec<usize> {
+    let mut shares = vec![0; available.len()];
+    let mut remaining = rows;
+    while remaining > 0 {
+        let mut gave = false;
+        for (share, &available) in shares.iter_mut().zip(available) {
+            if remaining > 0 && *share < available {
+                *share += 1;
+                remaining -= 1;
+                gave = true;
+            }
+        }
+        if !gave {
+            break;
+        }
+    }
+    shares
+}
+
+/// Each stratum's share of `rows` in proportion to its `rows_seen`, but at least
+/// `minimum`, and never more than it has `available`.
+fn proportional_shares(
+    rows: usize,
+    minimum: usize,
+    rows_seen: &[u64],
+    available: &[usize],
+) -> Vec<usize> {
+    let total: u128 = rows_seen.iter().map(|&rows| u128::from(rows)).sum();
+    rows_seen
+        .iter()
+        .zip(available)
+        .map(|(&seen, &available)| {
+            let share = (rows as u128 * u128::from(seen) + total / 2)
+                .checked_div(total)
+                .and_then(|share| usize::try_from(share).ok())
+                .unwrap_or(0);
+            share.max(minimum).min(available)
+        })
+        .collect()
+}
+
+/// The picked rows as batches in input order, or one empty batch when there are none.
+fn ordered_rows(path: &Path, schema: &SchemaRef, mut picks: Vec<Pick>) -> Result<Vec<RecordBatch>> {
+    picks.sort_by_key(|pick| pick.position);
+    let rows: Vec<RecordBatch> = picks.into_iter().map(|pick| pick.row).collect();
+    let batch = concat_batches(schema, &rows).map_err(|error| PqError::corrupted(path, &error))?;
+    Ok(vec![batch])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_shares_pass_unused_rows_to_larger_strata() {
+        assert_eq!(equal_shares(9, &[10, 10, 10]), vec![3, 3, 3]);
+        assert_eq!(equal_shares(10, &[1, 10, 10]), vec![1, 5, 4]);
+        assert_eq!(equal_shares(10, &[1, 2]), vec![1, 2]);
+    }
+
+    #[test]
+    fn proportional_shares_keep_the_minimum() {
+        assert_eq!(
+            proportional_shares(10, 2, &[900, 90, 10], &[10, 10, 10]),
+            vec![9, 2, 2]
+        );
+        assert_eq!(proportional_shares(10, 5, &[99, 1], &[10, 1]), vec![10, 1]);
+    }
+}
@@ -0,0 +1,293 @@
+//! Evaluating `pq select` field paths against Parquet files
+
+use super::parquet::{build_error, decode_error, reader_builder};
+use super::reshape::{list_spans, with_parent_nulls};
+use crate::error::PqError;
+use crate::field_path::{FieldPath, Segment};
+use crate::model::SelectOptions;
+use crate::suggest::nearest;
+use crate::Result;
+use arrow::array::{Array, ArrayRef, AsArray, RecordBatch, StructArray, UInt64Array};
+use arrow::compute::take;
+use arrow::datatypes::{DataType, Field, Fields, Schema};
+use parquet::arrow::ProjectionMask;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+pub fn select(
+    path: &Path,
+    paths: &[FieldPath],
+    options: &SelectOptions,
+    batch_size: usize,
+) -> Result<Vec<RecordBatch>> {
+    if options.rows == Some(0) {
+        return Ok(Vec::new());
+    }
+
+    let builder = reader_builder(path)?;
+    let mut leaves = Vec::new();
+    for field_path in paths {
+        leaves.extend(leaf_range(path, builder.schema(), field_path)?);
+    }
+    let mask = ProjectionMask::leaves(builder.parquet_schema(), leaves);
+    let reader = builder
+        .with_projection(mask)
+        .with_batch_size(options.rows.map_or(batch_size, |rows| rows.min(batch_size)))
+        .build()
+        .map_err(|error| build_error(path, error))?;
+
+    let mut batches = Vec::new();
+    let mut remaining = options.rows.unwrap_or(usize::MAX);
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| decode_error(path, &error))?;
+        let batch = batch.slice(0, batch.num_rows().min(remaining));
+        remaining -= batch.num_rows();
+
+        let values = paths
+            .iter()
+            .map(|field_path| evaluate(&batch, field_path))
+            .collect::<Result<Vec<_>>>()?;
+        batches.push(if options.flatten {
+            flat_batch(paths, values)?
+        } else {
+            nested_batch(paths, values)?
+        });
+        if remaining == 0 {
+            break;
+        }
+    }
+    Ok(batches)
+}
+
+/// The Parquet leaf columns under `field_path`. Arrow fields map to Parquet leaves
+/// depth first, so a subtree's leaves are a contiguous range.
+fn leaf_range(path: &Path, schema: &Schema, field_path: &FieldPath) -> Result<Range<usize>> {
+    let mut fields: &Fields = schema.fields();
+    let mut current: Option<&DataType> = None;
+    let mut start = 0;
+    let mut walked = String::new();
+
+    for segment in &field_path.segments {
+        match segment {
+            Segment::Field(name) => {
+                if let Some(data_type) = current {
+                    fields = match data_type {
+                        DataType::Struct(children) => children,
+                        DataType::List(_)
+                        | DataType::LargeList(_)
+                        | DataType::FixedSizeList(..) => {
+                            return Err(PqError::invalid_field_path(
+                                &field_path.to_string(),
+                                format!(
+                                    "{walked} is a list; pick an element first, e.g. {walked}[0]"
+                                ),
+                            ))
+                        }
+                        other => {
+                            return Err(PqError::invalid_field_path(
+                                &field_path.to_string(),
+                                format!("{walked} is {other}, not a struct"),
+                            ))
+                        }
+                    };
+                }
+                let prefix = walked.clone();
+                walked.push_str(&child_path(name));
+                let Some(position) = fields.iter().position(|field| field.name() == name) else {
+                    let candidates = fields.iter().map(|field| field.name().as_str());
+                    let suggestion = nearest(name, candidates)
+                        .map(|name| format!("{prefix}{}", child_path(name)));
+                    return Err(PqError::column_not_found_suggesting(
+                        path,
+                        &walked,
+                        suggestion,
+                        fields
+                            .iter()
+                            .map(|field| format!("{prefix}{}", child_path(field.name()))),
+                    ));
+                };
+                start += fields
+                    .iter()
+                    .take(position)
+                    .map(|field| leaf_count(field.data_type()))
+                    .sum::<usize>();
+                current = fields.get(position).map(|field| field.data_type());
+            }
+            Segment::Index(index) => {
+                current = match current {
+                    Some(
+                        DataType::List(item)
+                        | DataType::LargeList(item)
+                        | DataType::FixedSizeList(item, _),
+                    ) => Some(item.data_type()),
+                    other => {
+                        let kind = other.map_or_else(String::new, ToString::to_string);
+                        return Err(PqError::invalid_field_path(
+                            &field_path.to_string(),
+                            format!("{walked} is {kind}, not a list, so [{index}] does not apply"),
+                        ));
+                    }
+                };
+                walked.push_str(&format!("[{index}]"));
+            }
+        }
+    }
+
+    let count = current.map_or(0, leaf_count);
+    Ok(start..start + count)
+}
+
+fn child_path(name: &str) -> String {
+    FieldPath {
+        segments: vec![Segment::Field(name.to_string())],
+    }
+    .to_string()
+}
+
+fn leaf_count(data_type: &DataType) -> usize {
+    match data_type {
+        DataType::Struct(fields) => fields
+            .iter()
+            .map(|field| leaf_count(field.data_type()))
+            .sum(),
+        DataType::List(item)
+        | DataType::LargeList(item)
+        | DataType::FixedSizeList(item, _)
+        | DataType::Map(item, _) => leaf_count(item.data_type()),
+        _ => 1,
+    }
+}
+
+/// The values at `field_path` for each row; a null struct or a list too short for an
+/// index gives null.
+fn evaluate(batch: &RecordBatch, field_path: &FieldPath) -> Result<ArrayRef> {
+    let mut value: Option<ArrayRef> = None;
+    for segment in &field_path.segments {
+        value = Some(
+            match (segment, value) {
+                (Segment::Field(name), None) => batch.column_by_name(name).cloned(),
+                (Segment::Field(name), Some(parent)) => match parent.as_struct_opt() {
+                    Some(parent) => parent
+                        .column_by_name(name)
+                        .map(|child| with_parent_nulls(child, parent.nulls()))
+                        .transpose()?,
+                    None => None,
+                },
+                (Segment::Index(index), Some(parent)) => Some(list_element(&parent, *index)?),
+                (Segment::Index(_), None) => None,
+            }
+            .ok_or_else(|| {
+                PqError::invalid_field_path(&field_path.to_string(), "path does not match the data")
+            })?,
+        );
+    }
+    value.ok_or_else(|| PqError::invalid_field_path(&field_path.to_string(), "empty path"))
+}
+
+fn list_element(array: &ArrayRef, index: usize) -> Result<ArrayRef> {
+    let Some((values, spans)) = list_spans(array) else {
+        return Err(PqError::output_error(format!(
+            "cannot index into a value of type {}",
+            array.data_type()
+        )));
+    };
+    let indices: UInt64Array = spans
+        .into_iter()
+        .map(|span| {
+            span.filter(|(_, length)| index < *length)
+                .map(|(start, _)| (start + index) as u64)
+        })
+        .collect();
+    Ok(take(values.as_ref(), &indices, None)?)
+}
+
+fn flat_batch(paths: &[FieldPath], values: Vec<ArrayRef>) -> Result<RecordBatch> {
+    let fields: Vec<Field> = paths
+        .iter()
+        .zip(&values)
+        .map(|(field_path, value)| {
+            Field::new(field_path.column_name(), value.data_type().clone(), true)
+        })
+        .collect();
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), values)?)
+}
+
+/// Rebuild the selected values as nested structs keyed by field name, with list indexes
+/// kept on the key they follow, e.g. `.items[0].sku` becomes `{"items[0]": {"sku": ...}}`.
+fn nested_batch(paths: &[FieldPath], values: Vec<ArrayRef>) -> Result<RecordBatch> {
+    let mut root = Node::default();
+    for (field_path, value) in paths.iter().zip(values) {
+        root.insert(field_path, &nested_keys(field_path), value)?;
+    }
+    let (fields, arrays) = root.into_columns()?;
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+fn nested_keys(field_path: &FieldPath) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for segment in &field_path.segments {
+        match segment {
+            Segment::Field(name) => keys.push(name.clone()),
+            Segment::Index(index) => {
+                if let Some(key) = keys.last_mut() {
+                    key.push_str(&format!("[{index}]"));
+                }
+            }
+        }
+    }
+    keys
+}
+
+#[derive(Default)]
+struct Node {
+    children: Vec<(String, Child)>,
+}
+
+enum Child {
+    Value(ArrayRef),
+    Nested(Node),
+}
+
+impl Node {
+    fn insert(&mut self, field_path: &FieldPath, keys: &[String], value: ArrayRef) -> Result<()> {
+        let overlap = || {
+            PqError::invalid_field_path(
+                &field_path.to_string(),
+                "overlaps another selected path; use --flatten to select both",
+            )
+        };
+        let Some((key, rest)) = keys.split_first() else {
+            return Err(overlap());
+        };
+        let existing = self.children.iter_mut().find(|(name, _)| name == key);
+        match (existing, rest.is_empty()) {
+            (None, true) => self.children.push((key.clone(), Child::Value(value))),
+            (None, false) => {
+                let mut node = Node::default();
+                node.insert(field_path, rest, value)?;
+                self.children.push((key.clone(), Child::Nested(node)));
+            }
+            (Some((_, Child::Nested(node))), false) => node.insert(field_path, rest, value)?,
+            (Some(_), _) => return Err(overlap()),
+        }
+        Ok(())
+    }
+
+    fn into_columns(self) -> Result<(Vec<Field>, Vec<ArrayRef>)> {
+        let mut fields = Vec::new();
+        let mut arrays = Vec::new();
+        for (name, child) in self.children {
+            let array = match child {
+                Child::Value(array) => array,
+                Child::Nested(node) => {
+                    let (fields, arrays) = node.into_columns()?;
+                    Arc::new(StructArray::try_new(Fields::from(fields), arrays, None)?)
+                }
+            };
+            fields.push(Field::new(name, array.data_type().clone(), true));
+            arrays.push(array);
+        }
+        Ok((fields, arrays))
+    }
+}
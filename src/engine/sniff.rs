@@ -0,0 +1,122 @@
+//! What a file that fails to open as Parquet starts with instead
+//!
+//! Only consulted on the error path, to explain the failure.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// A whole-file compression format a pipeline may have wrapped a Parquet file in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Wrapper {
+    pub name: &'static str,
+    /// A command that undoes it, when there is a usual one.
+    pub command: Option<&'static str>,
+}
+
+const WRAPPERS: [(&[u8], Wrapper); 6] = [
+    (
+        b"\x1f\x8b",
+        Wrapper {
+            name: "gzip",
+            command: Some("gunzip"),
+        },
+    ),
+    (
+        b"\x28\xb5\x2f\xfd",
+        Wrapper {
+            name: "zstd",
+            command: Some("zstd -d"),
+        },
+    ),
+    (
+        b"BZh",
+        Wrapper {
+            name: "bzip2",
+            command: Some("bunzip2"),
+        },
+    ),
+    (
+        b"\xfd7zXZ\x00",
+        Wrapper {
+            name: "xz",
+            command: Some("unxz"),
+        },
+    ),
+    (
+        b"\x04\x22\x4d\x18",
+        Wrapper {
+            name: "lz4",
+            command: Some("lz4 -d"),
+        },
+    ),
+    (
+        b"\xff\x06\x00\x00sNaPpY",
+        Wrapper {
+            name: "snappy",
+            command: None,
+        },
+    ),
+];
+
+/// The compression format `path` starts with, if it starts with a known one.
+pub(crate) fn compression_wrapper(path: &Path) -> Option<Wrapper> {
+    let mut start = Vec::with_capacity(16);
+    File::open(path)
+        .ok()?
+        .take(16)
+        .read_to_end(&mut start)
+        .ok()?;
+    wrapper_of(&start)
+}
+
+fn wrapper_of(start: &[u8]) -> Option<Wrapper> {
+    WRAPPERS
+        .iter()
+        .find(|(magic, _)| start.starts_with(magic))
+        .map(|(_, wrapper)| *wrapper)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    fn name(start: &[u8]) -> Option<&'static str> {
+        wrapper_of(start).map(|wrapper| wrapper.name)
+    }
+
+    #[test]
+    fn recognizes_each_magic_number() {
+        assert_eq!(name(b"\x1f\x8b\x08\x00rest"), Some("gzip"));
+        assert_eq!(name(b"\x28\xb5\x2f\xfd\x04"), Some("zstd"));
+        assert_eq!(name(b"BZh91AY&SY"), Some("bzip2"));
+        assert_eq!(name(b"\xfd7zXZ\x00\x00\x04"), Some("xz"));
+        assert_eq!(name(b"\x04\x22\x4d\x18\x64"), Some("lz4"));
+        assert_eq!(name(b"\xff\x06\x00\x00sNaPpY\x00"), Some("snappy"));
+    }
+
+    #[test]
+    fn ignores_parquet_and_short_files() {
+        assert_eq!(name(b"PAR1\x15\x04"), None);
+        assert_eq!(name(b"\x1f"), None);
+        assert_eq!(name(b""), None);
+        assert_eq!(name(b"\xfd7zX"), None);
+    }
+
+    #[test]
+    fn sniffs_a_gzipped_file() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("pq_sniff_{}.parquet.gz", std::process::id()));
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"PAR1 data PAR1")?;
+        std::fs::write(&path, encoder.finish()?)?;
+        assert_eq!(
+            compression_wrapper(&path).map(|wrapper| wrapper.name),
+            Some("gzip")
+        );
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
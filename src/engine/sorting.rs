@@ -0,0 +1,188 @@
+//! The sort order row groups declare in their `sorting_columns` metadata, and
+//! `--sort-by` sorting of the rows a command shows
+
+use crate::error::PqError;
+use crate::model::{ColumnCase, SortKey, SortOrder, SortingColumn};
+use crate::Result;
+use arrow::array::{make_comparator, ArrayRef, RecordBatch, UInt64Array};
+use arrow::compute::{concat_batches, lexsort_to_indices, take_record_batch};
+use arrow::compute::{SortColumn, SortOptions};
+use parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use std::path::Path;
+use std::sync::Arc;
+
+/// The order every row group agrees on, or why there is none.
+pub(super) fn file_sort_order(path: &Path, metadata: &ParquetMetaData) -> Result<SortOrder> {
+    let declared = metadata
+        .row_groups()
+        .iter()
+        .map(|row_group| row_group_sorting(path, metadata, row_group))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(agree(declared))
+}
+
+/// The `sorting_columns` every row group declares alike, to carry over when a file's
+/// row groups are copied into a new one; `None` when they differ or declare none.
+pub(super) fn common_sorting_columns(
+    metadata: &ParquetMetaData,
+) -> Option<Vec<parquet::format::SortingColumn>> {
+    let mut row_groups = metadata.row_groups().iter();
+    let first = row_groups.next()?.sorting_columns()?;
+    row_groups
+        .all(|row_group| row_group.sorting_columns() == Some(first))
+        .then(|| first.clone())
+}
+
+/// The row group's sort columns, named by their leaf paths; empty when it declares none.
+pub(super) fn row_group_sorting(
+    path: &Path,
+    metadata: &ParquetMetaData,
+    row_group: &RowGroupMetaData,
+) -> Result<Vec<SortingColumn>> {
+    let schema = metadata.file_metadata().schema_descr();
+    row_group
+        .sorting_columns()
+        .into_iter()
+        .flatten()
+        .map(|sorting| {
+            let column = usize::try_from(sorting.column_idx)
+                .ok()
+                .filter(|&index| index < schema.num_columns())
+                .ok_or_else(|| {
+                    PqError::invalid_metadata(
+                        path,
+                        format!(
+                            "sorting column index {} is out of range",
+                            sorting.column_idx
+                        ),
+                    )
+                })?;
+            Ok(SortingColumn {
+                column: schema.column(column).path().string(),
+                descending: sorting.descending,
+                nulls_first: sorting.nulls_first,
+            })
+        })
+        .collect()
+}
+
+/// The rows of `batches`, read from `path`, as one batch ordered by `keys`. Rows with
+/// equal keys keep the order they were read in.
+pub(crate) fn sort_batches(
+    path: &Path,
+    batches: &[RecordBatch],
+    keys: &[SortKey],
+    case: ColumnCase,
+) -> Result<Vec<RecordBatch>> {
+    let Some(first) = batches.first() else {
+        return Ok(Vec::new());
+    };
+    if keys.is_empty() {
+        return Ok(batches.to_vec());
+    }
+    let schema = first.schema();
+    let batch =
+        concat_batches(&schema, batches).map_err(|error| PqError::corrupted(path, &error))?;
+    let names: Vec<&str> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    let mut columns = keys
+        .iter()
+        .map(|key| {
+            let index = crate::selector::find_column(path, &names, &key.column, case)?;
+            let values = Arc::clone(batch.column(index));
+            let options = SortOptions {
+                descending: key.descending,
+                nulls_first: key.nulls_first,
+            };
+            // Built only to check the type can be sorted, naming the key when not
+            let _comparator = make_comparator(&values, &values, options).map_err(|error| {
+                PqError::unsupported_column_type(path, &key.column, values.data_type(), error)
+            })?;
+            Ok(SortColumn {
+                values,
+                options: Some(options),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    // The kernel's sort is unstable, so the read position breaks ties
+    let positions: ArrayRef = Arc::new(UInt64Array::from_iter_values(0..batch.num_rows() as u64));
+    columns.push(SortColumn {
+        values: positions,
+        options: None,
+    });
+
+    let indices =
+        lexsort_to_indices(&columns, None).map_err(|error| PqError::corrupted(path, &error))?;
+    let sorted =
+        take_record_batch(&batch, &indices).map_err(|error| PqError::corrupted(path, &error))?;
+    Ok(vec![sorted])
+}
+
+fn agree(declared: Vec<Vec<SortingColumn>>) -> SortOrder {
+    let mut groups = declared.into_iter();
+    let Some(first) = groups.next() else {
+        return SortOrder::Undeclared;
+    };
+    let mut undeclared = first.is_empty();
+    for group in groups {
+        if group != first {
+            return SortOrder::Mixed;
+        }
+        undeclared &= group.is_empty();
+    }
+    if undeclared {
+        SortOrder::Undeclared
+    } else {
+        SortOrder::Sorted(first)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(column: &str, descending: bool) -> SortingColumn {
+        SortingColumn {
+            column: column.to_string(),
+            descending,
+            nulls_first: true,
+        }
+    }
+
+    #[test]
+    fn row_groups_must_agree_on_the_whole_order() {
+        assert_eq!(agree(Vec::new()), SortOrder::Undeclared);
+        assert_eq!(agree(vec![Vec::new(), Vec::new()]), SortOrder::Undeclared);
+
+        let ts = vec![key("ts", false)];
+        assert_eq!(
+            agree(vec![ts.clone(), ts.clone()]),
+            SortOrder::Sorted(ts.clone())
+        );
+        assert_eq!(
+            agree(vec![ts.clone(), vec![key("ts", true)]]),
+            SortOrder::Mixed
+        );
+        assert_eq!(agree(vec![ts, Vec::new()]), SortOrder::Mixed);
+    }
+
+    #[test]
+    fn labels_each_key_with_direction_and_null_placement() {
+        let order = SortOrder::Sorted(vec![
+            key("ts", false),
+            SortingColumn {
+                column: "user.id".to_string(),
+                descending: true,
+                nulls_first: false,
+            },
+        ]);
+        assert_eq!(
+            order.to_string(),
+            "ts (asc, nulls first), user.id (desc, nulls last)"
+        );
+        assert_eq!(SortOrder::Mixed.to_string(), "differs across row groups");
+    }
+}
@@ -1,54 +1,242 @@
-use crate::model::{ColumnStats, ColumnType, StatValue};
-use crate::PqError;
+use super::footer::Truncated;
+use crate::model::{
+    ColumnCase, ColumnStats, ColumnType, LogicalTypeKind, RowGroupRange, RowGroupStats, StatValue,
+    StatsProblem, StatsResult, ARROW_EXTENSION_NAME_KEY, UUID_EXTENSION_NAME,
+};
 use crate::Result;
+use arrow::datatypes::DataType;
+use parquet::arrow::{parquet_column, parquet_to_arrow_schema};
 use parquet::data_type::Int96;
+use parquet::file::metadata::{ColumnChunkMetaData, ParquetMetaData, RowGroupMetaData};
 use parquet::file::reader::FileReader;
 use parquet::file::statistics::Statistics;
+use parquet::schema::types::ColumnDescriptor;
+use std::cmp::Ordering;
 use std::path::Path;
 
-pub fn column_stats(path: &Path, column_name: Option<&str>) -> Result<Vec<ColumnStats>> {
+/// Footer statistics merged across the row groups `row_groups` picks (all when empty).
+pub fn column_stats(
+    path: &Path,
+    columns: &[String],
+    case: ColumnCase,
+    row_groups: &[RowGroupRange],
+) -> Result<StatsResult> {
     let reader = super::parquet::serialized_reader(path)?;
     let metadata = reader.metadata();
+    let row_groups = super::parquet::selected_row_groups(path, metadata, row_groups)?;
+    let truncated = super::footer::truncated_bounds(path);
+    let column_stats = merged_stats(metadata, &row_groups, &truncated);
+    let compressed_bytes = column_stats.iter().fold(0u64, |total, stats| {
+        total.saturating_add(stats.compressed_bytes)
+    });
+    let rows = if columns.is_empty() {
+        column_stats
+    } else {
+        let names: Vec<&str> = column_stats
+            .iter()
+            .map(|stats| stats.column.as_str())
+            .collect();
+        let selected = crate::selector::select_columns(path, &names, columns, case)?;
+        let mut column_stats: Vec<_> = column_stats.into_iter().map(Some).collect();
+        selected
+            .into_iter()
+            .filter_map(|index| column_stats.get_mut(index).and_then(Option::take))
+            .collect()
+    };
+    Ok(StatsResult {
+        path: path.to_path_buf(),
+        rows,
+        compressed_bytes,
+    })
+}
+
+/// Each row group's null counts and bounds for every leaf column, or for those the
+/// `columns` selectors pick, straight from the footer.
+pub fn row_group_stats(
+    path: &Path,
+    columns: &[String],
+    case: ColumnCase,
+    row_groups: &[RowGroupRange],
+) -> Result<Vec<RowGroupStats>> {
+    let reader = super::parquet::serialized_reader(path)?;
+    let metadata = reader.metadata();
+    let row_groups = super::parquet::selected_row_groups(path, metadata, row_groups)?;
+    let schema = metadata.file_metadata().schema_descr();
+    let descriptors: Vec<_> = (0..schema.num_columns())
+        .map(|index| schema.column(index))
+        .collect();
+    let selected: Vec<usize> = if columns.is_empty() {
+        (0..descriptors.len()).collect()
+    } else {
+        let names: Vec<&str> = descriptors
+            .iter()
+            .map(|descriptor| descriptor.name())
+            .collect();
+        crate::selector::select_columns(path, &names, columns, case)?
+    };
+
+    let truncated = super::footer::truncated_bounds(path);
+    let uuid_leaves = arrow_uuid_leaves(metadata);
+    let mut rows = Vec::new();
+    for row_group_index in row_groups {
+        let row_group = metadata.row_group(row_group_index);
+        for &column_index in &selected {
+            let Some(descriptor) = descriptors.get(column_index) else {
+                continue;
+            };
+            let chunk = row_group.columns().get(column_index);
+            let statistics = chunk.and_then(|chunk| chunk.statistics());
+            let chunk_bytes = |size: fn(&ColumnChunkMetaData) -> i64| {
+                chunk.map_or(0, |chunk| u64::try_from(size(chunk)).unwrap_or_default())
+            };
+            let (min, max) = statistics.map_or((None, None), stat_bounds);
+            let truncated = chunk_truncation(&truncated, row_group_index, column_index);
+            let column_type = leaf_type(descriptor, &uuid_leaves, column_index);
+            let problems = statistics.map_or_else(Vec::new, |statistics| {
+                chunk_problems(&column_type, row_group_index, row_group, statistics)
+            });
+            rows.push(RowGroupStats {
+                row_group: row_group_index,
+                num_rows: row_group.num_rows(),
+                stats: ColumnStats {
+                    column: descriptor.name().to_string(),
+                    column_type,
+                    null_count: statistics.and_then(Statistics::null_count_opt).unwrap_or(0),
+                    min,
+                    max,
+                    compressed_bytes: chunk_bytes(ColumnChunkMetaData::compressed_size),
+                    uncompressed_bytes: chunk_bytes(ColumnChunkMetaData::uncompressed_size),
+                    row_groups_without_statistics: usize::from(statistics.is_none()),
+                    min_truncated: truncated.min,
+                    max_truncated: truncated.max,
+                    problems,
+                },
+                has_statistics: statistics.is_some(),
+            });
+        }
+    }
+    Ok(rows)
+}
+
+/// Null counts and bounds for every leaf column, merged across row groups.
+pub(super) fn metadata_stats(metadata: &ParquetMetaData) -> Vec<ColumnStats> {
+    let row_groups: Vec<usize> = (0..metadata.num_row_groups()).collect();
+    merged_stats(metadata, &row_groups, &[])
+}
+
+/// Leaf columns the embedded Arrow schema tags with the `arrow.uuid` extension, which
+/// is how Arrow writers mark UUIDs instead of with Parquet's UUID logical type.
+fn arrow_uuid_leaves(metadata: &ParquetMetaData) -> Vec<usize> {
+    let file_metadata = metadata.file_metadata();
+    let parquet_schema = file_metadata.schema_descr();
+    let Ok(schema) = parquet_to_arrow_schema(parquet_schema, file_metadata.key_value_metadata())
+    else {
+        return Vec::new();
+    };
+    schema
+        .fields()
+        .iter()
+        .filter(|field| {
+            field.data_type() == &DataType::FixedSizeBinary(16)
+                && field
+                    .metadata()
+                    .get(ARROW_EXTENSION_NAME_KEY)
+                    .is_some_and(|name| name == UUID_EXTENSION_NAME)
+        })
+        .filter_map(|field| parquet_column(parquet_schema, &schema, field.name()))
+        .map(|(leaf, _)| leaf)
+        .collect()
+}
+
+/// The leaf's type, read as UUID when only the Arrow schema says so, so that its bounds
+/// render like its values.
+fn leaf_type(descriptor: &ColumnDescriptor, uuid_leaves: &[usize], index: usize) -> ColumnType {
+    let mut column_type = ColumnType::from_parquet(descriptor);
+    if column_type.logical.is_none() && uuid_leaves.contains(&index) {
+        column_type.logical = Some(LogicalTypeKind::Uuid);
+    }
+    column_type
+}
+
+fn chunk_truncation(truncated: &[Vec<Truncated>], row_group: usize, column: usize) -> Truncated {
+    truncated
+        .get(row_group)
+        .and_then(|columns| columns.get(column))
+        .copied()
+        .unwrap_or_default()
+}
+
+fn merged_stats(
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+    truncated: &[Vec<Truncated>],
+) -> Vec<ColumnStats> {
     let schema = metadata.file_metadata().schema_descr();
+    let uuid_leaves = arrow_uuid_leaves(metadata);
 
     let mut column_stats: Vec<AccumulatedColumnStats> = (0..schema.num_columns())
         .map(|index| {
             let column = schema.column(index);
             AccumulatedColumnStats {
                 column: column.name().to_string(),
-                column_type: ColumnType::from_parquet(&column),
+                column_type: leaf_type(&column, &uuid_leaves, index),
                 null_count: 0,
                 min: None,
                 max: None,
+                compressed_bytes: 0,
+                uncompressed_bytes: 0,
+                row_groups_without_statistics: 0,
+                min_truncated: false,
+                max_truncated: false,
+                problems: Vec::new(),
+                previous: None,
             }
         })
         .collect();
 
-    for row_group_index in 0..metadata.num_row_groups() {
+    for &row_group_index in row_groups {
         let row_group = metadata.row_group(row_group_index);
         for (column_index, stats) in column_stats
             .iter_mut()
             .enumerate()
             .take(row_group.num_columns())
         {
-            if let Some(column_statistics) = row_group.column(column_index).statistics() {
+            let chunk = row_group.column(column_index);
+            let bytes = |size: i64| u64::try_from(size).unwrap_or_default();
+            stats.compressed_bytes = stats
+                .compressed_bytes
+                .saturating_add(bytes(chunk.compressed_size()));
+            stats.uncompressed_bytes = stats
+                .uncompressed_bytes
+                .saturating_add(bytes(chunk.uncompressed_size()));
+            if let Some(column_statistics) = chunk.statistics() {
                 stats.null_count += column_statistics.null_count_opt().unwrap_or(0);
-                update_min_max(stats, column_statistics);
+                let truncated = chunk_truncation(truncated, row_group_index, column_index);
+                update_min_max(stats, column_statistics, truncated);
+                stats.problems.extend(chunk_problems(
+                    &stats.column_type,
+                    row_group_index,
+                    row_group,
+                    column_statistics,
+                ));
+                check_sorted(
+                    stats,
+                    row_group_index,
+                    row_group,
+                    column_index,
+                    column_statistics,
+                );
+            } else {
+                stats.row_groups_without_statistics += 1;
+                stats.previous = None;
             }
         }
     }
 
-    if let Some(name) = column_name {
-        if !column_stats.iter().any(|stats| stats.column == name) {
-            return Err(PqError::column_not_found(path, name));
-        }
-    }
-
-    Ok(column_stats
+    column_stats
         .into_iter()
-        .filter(|stats| column_name.is_none_or(|name| stats.column == name))
         .map(AccumulatedColumnStats::into_row)
-        .collect())
+        .collect()
 }
 
 struct AccumulatedColumnStats {
@@ -57,6 +245,21 @@ struct AccumulatedColumnStats {
     null_count: u64,
     min: Option<StatValue>,
     max: Option<StatValue>,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+    row_groups_without_statistics: usize,
+    min_truncated: bool,
+    max_truncated: bool,
+    problems: Vec<StatsProblem>,
+    /// The last row group's bounds, while it declared this column its first sort key
+    previous: Option<SortedBounds>,
+}
+
+struct SortedBounds {
+    row_group: usize,
+    descending: bool,
+    min: StatValue,
+    max: StatValue,
 }
 
 impl AccumulatedColumnStats {
@@ -67,139 +270,230 @@ impl AccumulatedColumnStats {
             null_count: self.null_count,
             min: self.min,
             max: self.max,
+            compressed_bytes: self.compressed_bytes,
+            uncompressed_bytes: self.uncompressed_bytes,
+            row_groups_without_statistics: self.row_groups_without_statistics,
+            min_truncated: self.min_truncated,
+            max_truncated: self.max_truncated,
+            problems: self.problems,
         }
     }
 }
 
-fn update_min_max(stats: &mut AccumulatedColumnStats, parquet_stats: &Statistics) {
-    match parquet_stats {
-        Statistics::Int32(source) => {
-            merge_min(
-                &mut stats.min,
-                source.min_opt().copied().map(StatValue::Int32),
-            );
-            merge_max(
-                &mut stats.max,
-                source.max_opt().copied().map(StatValue::Int32),
-            );
-        }
-        Statistics::Int64(source) => {
-            merge_min(
-                &mut stats.min,
-                source.min_opt().copied().map(StatValue::Int64),
-            );
-            merge_max(
-                &mut stats.max,
-                source.max_opt().copied().map(StatValue::Int64),
-            );
-        }
-        Statistics::Float(source) => {
-            merge_min(
-                &mut stats.min,
-                source.min_opt().copied().map(StatValue::Float),
-            );
-            merge_max(
-                &mut stats.max,
-                source.max_opt().copied().map(StatValue::Float),
-            );
-        }
-        Statistics::Double(source) => {
-            merge_min(
-                &mut stats.min,
-                source.min_opt().copied().map(StatValue::Double),
-            );
-            merge_max(
-                &mut stats.max,
-                source.max_opt().copied().map(StatValue::Double),
-            );
+/// Statistics of one column chunk that cannot be right: a min after the max, or more
+/// nulls than the row group has rows.
+pub(super) fn chunk_problems(
+    column_type: &ColumnType,
+    row_group_index: usize,
+    row_group: &RowGroupMetaData,
+    statistics: &Statistics,
+) -> Vec<StatsProblem> {
+    let mut problems = Vec::new();
+    if let (Some(min), Some(max)) = stat_bounds(statistics) {
+        if cmp_bounds(column_type, &min, &max).is_some_and(Ordering::is_gt) {
+            problems.push(StatsProblem::MinAboveMax {
+                row_group: row_group_index,
+            });
         }
-        Statistics::ByteArray(source) => {
-            merge_min(
-                &mut stats.min,
-                source
-                    .min_opt()
-                    .map(|value| StatValue::Binary(value.data().to_vec())),
-            );
-            merge_max(
-                &mut stats.max,
-                source
-                    .max_opt()
-                    .map(|value| StatValue::Binary(value.data().to_vec())),
-            );
-        }
-        Statistics::Boolean(source) => {
-            merge_min(
-                &mut stats.min,
-                source.min_opt().copied().map(StatValue::Boolean),
-            );
-            merge_max(
-                &mut stats.max,
-                source.max_opt().copied().map(StatValue::Boolean),
-            );
-        }
-        Statistics::FixedLenByteArray(source) => {
-            merge_min(
-                &mut stats.min,
-                source
-                    .min_opt()
-                    .map(|value| StatValue::FixedLenBinary(value.data().to_vec())),
-            );
-            merge_max(
-                &mut stats.max,
-                source
-                    .max_opt()
-                    .map(|value| StatValue::FixedLenBinary(value.data().to_vec())),
-            );
-        }
-        Statistics::Int96(source) => {
-            merge_min(
-                &mut stats.min,
-                source
-                    .min_opt()
-                    .copied()
-                    .map(display_int96)
-                    .map(StatValue::Int96),
-            );
-            merge_max(
-                &mut stats.max,
-                source
-                    .max_opt()
-                    .copied()
-                    .map(display_int96)
-                    .map(StatValue::Int96),
-            );
+    }
+    if let Some(null_count) = statistics.null_count_opt() {
+        if i64::try_from(null_count).map_or(true, |nulls| nulls > row_group.num_rows()) {
+            problems.push(StatsProblem::NullsAboveRows {
+                row_group: row_group_index,
+                null_count,
+                num_rows: row_group.num_rows(),
+            });
         }
     }
+    problems
 }
 
-fn merge_min(current: &mut Option<StatValue>, candidate: Option<StatValue>) {
-    merge_bound(current, candidate, |ordering| ordering.is_lt());
+/// Flag a row group whose bounds lie wholly before the previous one's in the order
+/// both declare for the column. Overlapping bounds pass: each row group declares only
+/// its own rows sorted, so only a range running backwards is clearly wrong.
+fn check_sorted(
+    stats: &mut AccumulatedColumnStats,
+    row_group_index: usize,
+    row_group: &RowGroupMetaData,
+    column_index: usize,
+    statistics: &Statistics,
+) {
+    let descending = row_group
+        .sorting_columns()
+        .and_then(|sorting| sorting.first())
+        .filter(|first| usize::try_from(first.column_idx) == Ok(column_index))
+        .map(|first| first.descending);
+    let (Some(descending), (Some(min), Some(max))) = (descending, stat_bounds(statistics)) else {
+        stats.previous = None;
+        return;
+    };
+    if let Some(previous) = stats
+        .previous
+        .as_ref()
+        .filter(|previous| previous.descending == descending)
+    {
+        let backwards = if descending {
+            cmp_bounds(&stats.column_type, &min, &previous.max)
+        } else {
+            cmp_bounds(&stats.column_type, &previous.min, &max)
+        };
+        if backwards.is_some_and(Ordering::is_gt) {
+            stats.problems.push(StatsProblem::OutOfOrder {
+                row_group: row_group_index,
+                previous: previous.row_group,
+                descending,
+            });
+        }
+    }
+    stats.previous = Some(SortedBounds {
+        row_group: row_group_index,
+        descending,
+        min,
+        max,
+    });
 }
 
-fn merge_max(current: &mut Option<StatValue>, candidate: Option<StatValue>) {
-    merge_bound(current, candidate, |ordering| ordering.is_gt());
+/// Merge one chunk's bounds in; a merged bound is truncated when the chunk it came from
+/// truncated it.
+fn update_min_max(
+    stats: &mut AccumulatedColumnStats,
+    parquet_stats: &Statistics,
+    truncated: Truncated,
+) {
+    let (min, max) = stat_bounds(parquet_stats);
+    if merge_bound(&stats.column_type, &mut stats.min, min, Ordering::is_lt) {
+        stats.min_truncated = truncated.min;
+    }
+    if merge_bound(&stats.column_type, &mut stats.max, max, Ordering::is_gt) {
+        stats.max_truncated = truncated.max;
+    }
 }
 
+/// Keep whichever of `current` and `candidate` wins under `should_replace`, saying
+/// whether the candidate did. A NaN bound never wins over a real one, so one row group
+/// of NaNs cannot hide the others' extremes.
 fn merge_bound(
+    column_type: &ColumnType,
     current: &mut Option<StatValue>,
     candidate: Option<StatValue>,
-    should_replace: impl Fn(std::cmp::Ordering) -> bool,
-) {
+    should_replace: impl Fn(Ordering) -> bool,
+) -> bool {
     let Some(candidate) = candidate else {
-        return;
+        return false;
     };
 
     let replace = match current.as_ref() {
         None => true,
-        Some(existing) => partial_cmp_value(&candidate, existing).is_some_and(should_replace),
+        Some(existing) if is_nan(existing) => true,
+        Some(_) if is_nan(&candidate) => false,
+        Some(existing) => {
+            cmp_bounds(column_type, &candidate, existing).is_some_and(&should_replace)
+        }
     };
 
     if replace {
         *current = Some(candidate);
     }
+    replace
+}
+
+pub fn stat_bounds(parquet_stats: &Statistics) -> (Option<StatValue>, Option<StatValue>) {
+    match parquet_stats {
+        Statistics::Int32(source) => (
+            source.min_opt().copied().map(StatValue::Int32),
+            source.max_opt().copied().map(StatValue::Int32),
+        ),
+        Statistics::Int64(source) => (
+            source.min_opt().copied().map(StatValue::Int64),
+            source.max_opt().copied().map(StatValue::Int64),
+        ),
+        Statistics::Float(source) => (
+            source.min_opt().copied().map(StatValue::Float),
+            source.max_opt().copied().map(StatValue::Float),
+        ),
+        Statistics::Double(source) => (
+            source.min_opt().copied().map(StatValue::Double),
+            source.max_opt().copied().map(StatValue::Double),
+        ),
+        Statistics::ByteArray(source) => (
+            source
+                .min_opt()
+                .map(|value| StatValue::Binary(value.data().to_vec())),
+            source
+                .max_opt()
+                .map(|value| StatValue::Binary(value.data().to_vec())),
+        ),
+        Statistics::Boolean(source) => (
+            source.min_opt().copied().map(StatValue::Boolean),
+            source.max_opt().copied().map(StatValue::Boolean),
+        ),
+        Statistics::FixedLenByteArray(source) => (
+            source
+                .min_opt()
+                .map(|value| StatValue::FixedLenBinary(value.data().to_vec())),
+            source
+                .max_opt()
+                .map(|value| StatValue::FixedLenBinary(value.data().to_vec())),
+        ),
+        Statistics::Int96(source) => (
+            source
+                .min_opt()
+                .copied()
+                .map(display_int96)
+                .map(StatValue::Int96),
+            source
+                .max_opt()
+                .copied()
+                .map(display_int96)
+                .map(StatValue::Int96),
+        ),
+    }
 }
 
-fn partial_cmp_value(left: &StatValue, right: &StatValue) -> Option<std::cmp::Ordering> {
+fn is_nan(value: &StatValue) -> bool {
+    match value {
+        StatValue::Float(value) => value.is_nan(),
+        StatValue::Double(value) => value.is_nan(),
+        _ => false,
+    }
+}
+
+/// Order two bounds of one column the way its logical type sorts: unsigned integers as
+/// unsigned and byte-backed decimals as signed big-endian numbers. Everything else
+/// orders by its physical value.
+fn cmp_bounds(column_type: &ColumnType, left: &StatValue, right: &StatValue) -> Option<Ordering> {
+    match (column_type.logical.as_ref(), left, right) {
+        (_, StatValue::Int32(lhs), StatValue::Int32(rhs)) if column_type.is_unsigned() => {
+            Some((*lhs as u32).cmp(&(*rhs as u32)))
+        }
+        (_, StatValue::Int64(lhs), StatValue::Int64(rhs)) if column_type.is_unsigned() => {
+            Some((*lhs as u64).cmp(&(*rhs as u64)))
+        }
+        (
+            Some(LogicalTypeKind::Decimal { .. }),
+            StatValue::Binary(lhs) | StatValue::FixedLenBinary(lhs),
+            StatValue::Binary(rhs) | StatValue::FixedLenBinary(rhs),
+        ) => Some(signed_big_endian(lhs)?.cmp(&signed_big_endian(rhs)?)),
+        _ => partial_cmp_value(left, right),
+    }
+}
+
+/// A two's complement big-endian integer of up to 16 bytes, as decimals are stored.
+fn signed_big_endian(bytes: &[u8]) -> Option<i128> {
+    if bytes.is_empty() || bytes.len() > 16 {
+        return None;
+    }
+    let fill = if bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+        0xff
+    } else {
+        0
+    };
+    let mut padded = [fill; 16];
+    padded.get_mut(16 - bytes.len()..)?.copy_from_slice(bytes);
+    Some(i128::from_be_bytes(padded))
+}
+
+pub fn partial_cmp_value(left: &StatValue, right: &StatValue) -> Option<Ordering> {
     match (left, right) {
         (StatValue::Int32(lhs), StatValue::Int32(rhs)) => lhs.partial_cmp(rhs),
         (StatValue::Int64(lhs), StatValue::Int64(rhs)) => lhs.partial_cmp(rhs),
@@ -213,6 +507,73 @@ fn partial_cmp_value(left: &StatValue, right: &StatValue) -> Option<std::cmp::Or
     }
 }
 
-fn display_int96(value: Int96) -> String {
+pub fn display_int96(value: Int96) -> String {
     format!("{value:?}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::PhysicalType;
+
+    fn merged(
+        column_type: &ColumnType,
+        bounds: Vec<StatValue>,
+    ) -> (Option<StatValue>, Option<StatValue>) {
+        let (mut min, mut max) = (None, None);
+        for bound in bounds {
+            merge_bound(column_type, &mut min, Some(bound.clone()), Ordering::is_lt);
+            merge_bound(column_type, &mut max, Some(bound), Ordering::is_gt);
+        }
+        (min, max)
+    }
+
+    #[test]
+    fn nan_bounds_never_win() {
+        let column_type = ColumnType {
+            physical: PhysicalType::Double,
+            logical: None,
+        };
+        let (min, max) = merged(
+            &column_type,
+            vec![
+                StatValue::Double(f64::NAN),
+                StatValue::Double(2.0),
+                StatValue::Double(f64::NAN),
+                StatValue::Double(1.0),
+            ],
+        );
+        assert_eq!(
+            (min, max),
+            (Some(StatValue::Double(1.0)), Some(StatValue::Double(2.0)))
+        );
+    }
+
+    #[test]
+    fn orders_unsigned_integers_and_byte_decimals_by_value() {
+        let unsigned = ColumnType {
+            physical: PhysicalType::Int32,
+            logical: Some(LogicalTypeKind::Integer {
+                bit_width: 32,
+                is_signed: false,
+            }),
+        };
+        let (min, max) = merged(&unsigned, vec![StatValue::Int32(-1), StatValue::Int32(7)]);
+        assert_eq!(
+            (min, max),
+            (Some(StatValue::Int32(7)), Some(StatValue::Int32(-1)))
+        );
+
+        let decimal = ColumnType {
+            physical: PhysicalType::FixedLenByteArray,
+            logical: Some(LogicalTypeKind::Decimal {
+                scale: 2,
+                precision: 20,
+            }),
+        };
+        let negative = StatValue::FixedLenBinary(vec![0xff, 0x0c]);
+        let positive = StatValue::FixedLenBinary(vec![0x00, 0x2c]);
+        let (min, max) = merged(&decimal, vec![positive.clone(), negative.clone()]);
+        assert_eq!((min, max), (Some(negative), Some(positive)));
+    }
+}
@@ -0,0 +1,165 @@
+//! Two files' footer statistics compared column by column
+//!
+//! Columns pair up by name; a name that repeats, like the `element` leaves of several
+//! lists, pairs its occurrences in order.
+
+use crate::model::{
+    ColumnStats, ColumnStatsDiff, StatComparison, StatValue, StatsDiff, StatsTolerance,
+};
+use crate::Result;
+use parquet::file::reader::FileReader;
+use std::collections::HashMap;
+use std::path::Path;
+
+pub fn diff(old: &Path, new: &Path, tolerance: Option<StatsTolerance>) -> Result<StatsDiff> {
+    let (old_rows, old_stats) = footer_stats(old)?;
+    let (new_rows, new_stats) = footer_stats(new)?;
+
+    // Each name's unmatched columns in the new file, last first so `pop` takes the next
+    let mut unmatched: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, stats) in new_stats.iter().enumerate().rev() {
+        unmatched
+            .entry(stats.column.as_str())
+            .or_default()
+            .push(index);
+    }
+    let mut matched = vec![false; new_stats.len()];
+
+    let mut columns = Vec::new();
+    let mut only_in_old = Vec::new();
+    for before in &old_stats {
+        let Some(index) = unmatched.get_mut(before.column.as_str()).and_then(Vec::pop) else {
+            only_in_old.push(before.column.clone());
+            continue;
+        };
+        matched[index] = true;
+        let after = &new_stats[index];
+        columns.push(ColumnStatsDiff {
+            column: before.column.clone(),
+            column_type: before.column_type.clone(),
+            null_count: compare_counts(before.null_count, after.null_count, tolerance),
+            min: compare_values(before, after, |stats| stats.min.as_ref(), tolerance),
+            max: compare_values(before, after, |stats| stats.max.as_ref(), tolerance),
+        });
+    }
+    let only_in_new = new_stats
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(stats, _)| stats.column.clone())
+        .collect();
+
+    Ok(StatsDiff {
+        old: old.to_path_buf(),
+        new: new.to_path_buf(),
+        tolerance,
+        row_count: compare_counts(old_rows, new_rows, tolerance),
+        columns,
+        only_in_old,
+        only_in_new,
+    })
+}
+
+fn footer_stats(path: &Path) -> Result<(u64, Vec<ColumnStats>)> {
+    let reader = super::parquet::serialized_reader(path)?;
+    let metadata = reader.metadata();
+    let rows = u64::try_from(metadata.file_metadata().num_rows()).unwrap_or_default();
+    Ok((rows, super::stats::metadata_stats(metadata)))
+}
+
+fn compare_counts(before: u64, after: u64, tolerance: Option<StatsTolerance>) -> StatComparison {
+    compare(
+        Some(before.to_string()),
+        Some(after.to_string()),
+        Some((before as f64, after as f64)),
+        tolerance,
+    )
+}
+
+fn compare_values(
+    before: &ColumnStats,
+    after: &ColumnStats,
+    value: impl Fn(&ColumnStats) -> Option<&StatValue>,
+    tolerance: Option<StatsTolerance>,
+) -> StatComparison {
+    let (old_value, new_value) = (value(before), value(after));
+    let numbers = old_value
+        .and_then(|old| before.column_type.stat_number(old))
+        .zip(new_value.and_then(|new| after.column_type.stat_number(new)));
+    compare(
+        old_value.map(|old| before.display_stat_value(old)),
+        new_value.map(|new| after.display_stat_value(new)),
+        numbers,
+        tolerance,
+    )
+}
+
+/// A change without a number to measure, such as a new string minimum or a statistic
+/// one file lacks, is beyond any tolerance.
+fn compare(
+    before: Option<String>,
+    after: Option<String>,
+    numbers: Option<(f64, f64)>,
+    tolerance: Option<StatsTolerance>,
+) -> StatComparison {
+    let delta = numbers.map(|(before, after)| after - before);
+    let delta_percent = numbers
+        .filter(|(before, _)| *before != 0.0)
+        .map(|(before, after)| (after - before) / before.abs() * 100.0);
+    let exceeds_tolerance = before != after
+        && match tolerance {
+            None => false,
+            Some(StatsTolerance::Absolute(limit)) => delta.is_none_or(|delta| delta.abs() > limit),
+            Some(StatsTolerance::Percent(limit)) => {
+                delta_percent.is_none_or(|percent| percent.abs() > limit)
+            }
+        };
+    StatComparison {
+        before,
+        after,
+        delta,
+        delta_percent,
+        exceeds_tolerance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(before: u64, after: u64, tolerance: StatsTolerance) -> StatComparison {
+        compare_counts(before, after, Some(tolerance))
+    }
+
+    #[test]
+    fn tolerances_measure_the_change_absolutely_or_relatively() {
+        let grown = counts(200, 210, StatsTolerance::Percent(10.0));
+        assert_eq!(grown.delta, Some(10.0));
+        assert_eq!(grown.delta_percent, Some(5.0));
+        assert!(!grown.exceeds_tolerance);
+
+        assert!(counts(200, 210, StatsTolerance::Absolute(5.0)).exceeds_tolerance);
+        assert!(!counts(200, 190, StatsTolerance::Absolute(10.0)).exceeds_tolerance);
+        assert!(counts(200, 150, StatsTolerance::Percent(10.0)).exceeds_tolerance);
+    }
+
+    #[test]
+    fn changes_from_zero_or_without_numbers_exceed_any_tolerance() {
+        let from_zero = counts(0, 1, StatsTolerance::Percent(50.0));
+        assert_eq!(from_zero.delta_percent, None);
+        assert!(from_zero.exceeds_tolerance);
+        assert!(!counts(0, 0, StatsTolerance::Percent(0.0)).exceeds_tolerance);
+
+        let text = |before: &str, after: &str| {
+            compare(
+                Some(before.to_string()),
+                Some(after.to_string()),
+                None,
+                Some(StatsTolerance::Absolute(1000.0)),
+            )
+        };
+        assert!(text("apple", "avocado").exceeds_tolerance);
+        assert!(!text("apple", "apple").exceeds_tolerance);
+        assert!(!compare_counts(1, 9, None).exceeds_tolerance);
+    }
+}
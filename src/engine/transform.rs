@@ -0,0 +1,224 @@
+//! Column renames, drops, and casts written to a new Parquet file
+
+use super::parquet::{build_error, decode_error, reader_builder, writer_properties};
+use crate::error::PqError;
+use crate::model::{ParquetWriteOptions, TransformOptions, DEFAULT_BATCH_SIZE};
+use crate::Result;
+use arrow::array::{Array, ArrayRef, RecordBatch};
+use arrow::compute::kernels::cmp::distinct;
+use arrow::compute::{can_cast_types, cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use parquet::arrow::{ArrowWriter, ProjectionMask};
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// What happens to one kept input column.
+struct Plan {
+    source: String,
+    field: Field,
+    cast: Option<DataType>,
+}
+
+/// Stream `input` to `output` with the columns renamed, dropped, and cast. Every column
+/// name, cast, and resulting name is checked before the output is created; a lossy cast
+/// found while streaming leaves no output behind.
+pub fn transform(
+    input: &Path,
+    output: &Path,
+    options: &TransformOptions,
+    write: &ParquetWriteOptions,
+) -> Result<()> {
+    let builder = reader_builder(input)?;
+    let schema = Arc::clone(builder.schema());
+    let (kept, plans) = plan(input, &schema, options)?;
+
+    let mask = ProjectionMask::roots(builder.parquet_schema(), kept);
+    let reader = builder
+        .with_projection(mask)
+        .with_batch_size(options.batch_size.unwrap_or(DEFAULT_BATCH_SIZE))
+        .build()
+        .map_err(|error| build_error(input, error))?;
+    let output_schema = Arc::new(Schema::new_with_metadata(
+        plans
+            .iter()
+            .map(|plan| plan.field.clone())
+            .collect::<Vec<_>>(),
+        schema.metadata().clone(),
+    ));
+
+    let props = writer_properties(&output_schema, write, output, output)?.build();
+    let pending_output = crate::atomic_output::PendingOutput::new(output)?;
+    let output_file =
+        File::create(pending_output.path()).map_err(|error| PqError::write_error(output, error))?;
+    let mut writer = ArrowWriter::try_new(output_file, Arc::clone(&output_schema), Some(props))
+        .map_err(|error| PqError::write_error(output, error))?;
+
+    for batch_result in reader {
+        let batch = batch_result.map_err(|error| decode_error(input, error))?;
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(&plans)
+            .map(|(column, plan)| match &plan.cast {
+                Some(to_type) => cast_column(input, plan, column, to_type, options.allow_lossy),
+                None => Ok(Arc::clone(column)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let batch = RecordBatch::try_new(Arc::clone(&output_schema), columns)?;
+        writer
+            .write(&batch)
+            .map_err(|error| PqError::write_error(output, error))?;
+    }
+
+    writer
+        .close()
+        .map_err(|error| PqError::write_error(output, error))?;
+    pending_output.commit()
+}
+
+/// The input columns to read, in order, and what becomes of each.
+fn plan(
+    path: &Path,
+    schema: &SchemaRef,
+    options: &TransformOptions,
+) -> Result<(Vec<usize>, Vec<Plan>)> {
+    let field_names: Vec<&str> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().as_str())
+        .collect();
+    let index_of = |column: &str| {
+        crate::selector::find_column(path, &field_names, column, options.column_case)
+    };
+
+    let mut dropped = HashSet::new();
+    for column in &options.drops {
+        dropped.insert(index_of(column)?);
+    }
+    let mut names: Vec<String> = schema
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect();
+    for (from, to) in &options.renames {
+        let index = index_of(from)?;
+        if dropped.contains(&index) {
+            return Err(PqError::invalid_transform(
+                &format!("{from}={to}"),
+                format!("{from} is also dropped"),
+            ));
+        }
+        if let Some(name) = names.get_mut(index) {
+            *name = to.clone();
+        }
+    }
+    let mut casts = vec![None; names.len()];
+    for (column, to_type) in &options.casts {
+        let index = index_of(column)?;
+        let from_type = schema.field(index).data_type();
+        if dropped.contains(&index) {
+            return Err(PqError::invalid_transform(
+                &format!("{column}={to_type}"),
+                format!("{column} is also dropped"),
+            ));
+        }
+        if !can_cast_types(from_type, to_type) {
+            return Err(PqError::invalid_cast(
+                path,
+                column,
+                from_type,
+                to_type,
+                "Arrow has no cast between these types",
+            ));
+        }
+        if let Some(cast) = casts.get_mut(index) {
+            *cast = Some(to_type.clone());
+        }
+    }
+
+    let mut kept = Vec::new();
+    let mut plans = Vec::new();
+    let mut seen = HashSet::new();
+    for (index, field) in schema.fields().iter().enumerate() {
+        if dropped.contains(&index) {
+            continue;
+        }
+        let name = names.get(index).cloned().unwrap_or_default();
+        if !seen.insert(name.clone()) {
+            let spec = options
+                .renames
+                .iter()
+                .find(|(_, to)| *to == name)
+                .map_or_else(|| name.clone(), |(from, to)| format!("{from}={to}"));
+            return Err(PqError::invalid_transform(
+                &spec,
+                format!("more than one output column would be named {name}"),
+            ));
+        }
+        let cast = casts.get(index).cloned().flatten();
+        let mut output_field = field.as_ref().clone().with_name(name);
+        if let Some(to_type) = &cast {
+            output_field = output_field
+                .with_data_type(to_type.clone())
+                .with_nullable(field.is_nullable() || options.allow_lossy);
+        }
+        kept.push(index);
+        plans.push(Plan {
+            source: field.name().clone(),
+            field: output_field,
+            cast,
+        });
+    }
+    if plans.is_empty() {
+        return Err(PqError::invalid_transform(
+            &options.drops.join(","),
+            "every column would be dropped",
+        ));
+    }
+    Ok((kept, plans))
+}
+
+/// Cast one column. Unless lossy casts are allowed, a value that becomes null or, for
+/// primitive sources, does not survive a cast back to its original type fails the run.
+fn cast_column(
+    path: &Path,
+    plan: &Plan,
+    column: &ArrayRef,
+    to_type: &DataType,
+    allow_lossy: bool,
+) -> Result<ArrayRef> {
+    let options = CastOptions {
+        safe: true,
+        ..CastOptions::default()
+    };
+    let cast = cast_with_options(column, to_type, &options)?;
+    if allow_lossy {
+        return Ok(cast);
+    }
+
+    let mut lossy = (0..column.len()).find(|&row| column.is_valid(row) && cast.is_null(row));
+    if lossy.is_none() && column.data_type().is_primitive() {
+        let round_trip = cast_with_options(&cast, column.data_type(), &options)?;
+        let changed = distinct(column, &round_trip)?;
+        lossy = (0..column.len()).find(|&row| column.is_valid(row) && changed.value(row));
+    }
+    let Some(row) = lossy else {
+        return Ok(cast);
+    };
+
+    let value = ArrayFormatter::try_new(column.as_ref(), &FormatOptions::default())
+        .map(|formatter| formatter.value(row).to_string())
+        .unwrap_or_default();
+    Err(PqError::invalid_cast(
+        path,
+        &plan.source,
+        column.data_type(),
+        to_type,
+        format!(
+            "Value {value} does not fit the new type; pass --allow-lossy to write it as null or truncated"
+        ),
+    ))
+}
@@ -0,0 +1,168 @@
+//! Union of several files' schemas, with the columns they disagree on
+
+use crate::model::{ColumnType, TypeConflict, UnifiedColumn, UnifiedSchema};
+use crate::Result;
+use parquet::file::reader::FileReader;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A leaf column as one file declares it.
+struct FileColumn {
+    name: String,
+    column_type: ColumnType,
+    nullable: bool,
+}
+
+/// Reads only footers. Columns keep the order in which they first appear.
+pub fn unify<'a>(paths: impl IntoIterator<Item = &'a Path>) -> Result<UnifiedSchema> {
+    let files = paths
+        .into_iter()
+        .map(|path| Ok((path.to_path_buf(), file_columns(path)?)))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(unify_columns(files))
+}
+
+fn file_columns(path: &Path) -> Result<Vec<FileColumn>> {
+    let reader = super::parquet::serialized_reader(path)?;
+    Ok(reader
+        .metadata()
+        .file_metadata()
+        .schema_descr()
+        .columns()
+        .iter()
+        .map(|column| FileColumn {
+            name: column.path().string(),
+            column_type: ColumnType::from_parquet(column),
+            nullable: column.self_type().is_optional(),
+        })
+        .collect())
+}
+
+fn unify_columns(files: Vec<(PathBuf, Vec<FileColumn>)>) -> UnifiedSchema {
+    let mut names: Vec<String> = Vec::new();
+    // Per column, each distinct (type, nullability) with the files that declare it
+    let mut variants: HashMap<String, Vec<TypeConflict>> = HashMap::new();
+
+    for (path, columns) in &files {
+        for column in columns {
+            let column_variants = variants.entry(column.name.clone()).or_insert_with(|| {
+                names.push(column.name.clone());
+                Vec::new()
+            });
+            match column_variants.iter_mut().find(|variant| {
+                variant.column_type == column.column_type && variant.nullable == column.nullable
+            }) {
+                Some(variant) => variant.files.push(path.clone()),
+                None => column_variants.push(TypeConflict {
+                    column_type: column.column_type.clone(),
+                    nullable: column.nullable,
+                    files: vec![path.clone()],
+                }),
+            }
+        }
+    }
+
+    let columns = names
+        .into_iter()
+        .filter_map(|name| {
+            let mut type_conflicts = variants.remove(&name)?;
+            // The variant most files share wins, and the first seen breaks ties
+            let unified = type_conflicts
+                .iter()
+                .enumerate()
+                .max_by_key(|(index, variant)| (variant.files.len(), std::cmp::Reverse(*index)))
+                .map(|(index, _)| index)?;
+            let unified = type_conflicts.remove(unified);
+            let files_missing = files
+                .iter()
+                .map(|(path, _)| path)
+                .filter(|path| {
+                    !unified.files.contains(path)
+                        && !type_conflicts
+                            .iter()
+                            .any(|variant| variant.files.contains(path))
+                })
+                .cloned()
+                .collect();
+            Some(UnifiedColumn {
+                name,
+                column_type: unified.column_type,
+                nullable: unified.nullable,
+                files_missing,
+                type_conflicts,
+            })
+        })
+        .collect();
+
+    UnifiedSchema {
+        files: files.into_iter().map(|(path, _)| path).collect(),
+        columns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::PhysicalType;
+
+    fn column(name: &str, physical: PhysicalType, nullable: bool) -> FileColumn {
+        FileColumn {
+            name: name.to_string(),
+            column_type: ColumnType {
+                physical,
+                logical: None,
+            },
+            nullable,
+        }
+    }
+
+    #[test]
+    fn majority_type_wins_and_deviations_name_their_files() {
+        let schema = unify_columns(vec![
+            (
+                PathBuf::from("a.parquet"),
+                vec![
+                    column("id", PhysicalType::Int64, false),
+                    column("score", PhysicalType::Double, true),
+                ],
+            ),
+            (
+                PathBuf::from("b.parquet"),
+                vec![
+                    column("id", PhysicalType::Int32, false),
+                    column("score", PhysicalType::Double, false),
+                ],
+            ),
+            (
+                PathBuf::from("c.parquet"),
+                vec![
+                    column("id", PhysicalType::Int64, false),
+                    column("extra", PhysicalType::Boolean, true),
+                ],
+            ),
+        ]);
+
+        let names: Vec<_> = schema.columns.iter().map(|column| &column.name).collect();
+        assert_eq!(names, ["id", "score", "extra"]);
+        assert!(!schema.is_compatible());
+        assert_eq!(schema.incompatible_columns(), 3);
+
+        let id = &schema.columns[0];
+        assert_eq!(id.column_type.physical, PhysicalType::Int64);
+        assert!(id.files_missing.is_empty());
+        assert_eq!(id.type_conflicts.len(), 1);
+        assert_eq!(id.type_conflicts[0].files, [PathBuf::from("b.parquet")]);
+
+        // A tie goes to the first file's declaration
+        let score = &schema.columns[1];
+        assert!(score.nullable);
+        assert_eq!(score.files_missing, [PathBuf::from("c.parquet")]);
+        assert!(!score.type_conflicts[0].nullable);
+
+        let extra = &schema.columns[2];
+        assert_eq!(
+            extra.files_missing,
+            [PathBuf::from("a.parquet"), PathBuf::from("b.parquet")]
+        );
+    }
+}
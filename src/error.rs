@@ -1,5 +1,6 @@
 //! Custom error types with user-friendly messages
 
+use crate::path_text::display_path;
 use arrow::error::ArrowError;
 use std::io;
 use std::path::Path;
@@ -23,18 +24,71 @@ pub enum PqError {
     #[error("File appears corrupted: {path}\n  {details}")]
     CorruptedFile { path: String, details: String },
 
+    #[error("File appears to be in the middle of being written: {path}\n  {}", being_written_hint(.waited))]
+    BeingWritten {
+        path: String,
+        waited: Option<String>,
+    },
+
+    #[error(
+        "File is encrypted: {path}\n  It uses Parquet modular encryption, which pq cannot decrypt"
+    )]
+    Encrypted { path: String },
+
+    #[error("File is {format}-compressed: {path}\n  Parquet compresses its own pages, so the file itself must not be compressed; decompress it first{}", command_hint(.command))]
+    CompressedFile {
+        path: String,
+        format: String,
+        command: Option<String>,
+    },
+
     #[error("Cannot read file: {path}\n  {details}")]
     ReadError { path: String, details: String },
 
     #[error("Cannot write file: {path}\n  {details}")]
     WriteError { path: String, details: String },
 
+    #[error("Output file already exists: {path}\n  Pass --force to overwrite it")]
+    OutputExists { path: String },
+
+    #[error("Output file is also an input: {path}\n  Write to a different path")]
+    OutputIsInput { path: String },
+
+    #[error("Two inputs would write the same output: {path}\n  {first}\n  {second}\n  Give the inputs distinct file names")]
+    OutputCollision {
+        path: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("{failed} of {total} files failed to convert\n  Pass --skip-bad-files to convert the others and succeed anyway")]
+    ConvertFailed { failed: usize, total: usize },
+
     #[error("Cannot write output\n  {details}")]
     OutputError { details: String },
 
+    /// The reader closed stdout early, as `head` does once it has enough lines
+    #[error("Output closed by the reader")]
+    BrokenPipe,
+
+    #[error("Refusing to write binary {format} output to a terminal\n  Redirect stdout to a file or pipe, or pass --force")]
+    BinaryToTerminal { format: String },
+
     #[error("Invalid glob pattern: {pattern}\n  {details}")]
     InvalidGlobPattern { pattern: String, details: String },
 
+    #[error("Invalid search pattern: {pattern}\n  {details}")]
+    InvalidPattern { pattern: String, details: String },
+
+    #[error("Invalid column selector: {selector}\n  {details}")]
+    InvalidColumnSelector { selector: String, details: String },
+
+    #[error("Invalid field path: {expression}\n  {details}")]
+    InvalidFieldPath { expression: String, details: String },
+
+    #[error("Invalid predicate: {predicate}\n  {details}")]
+    InvalidPredicate { predicate: String, details: String },
+
     #[error("No files matched pattern: {pattern}")]
     NoFilesMatched { pattern: String },
 
@@ -43,6 +97,14 @@ pub enum PqError {
     )]
     TooManyFilesMatched { pattern: String, max_matches: usize },
 
+    #[error("No files with extension {extensions} found in directory: {path}")]
+    NoFilesInDirectory { path: String, extensions: String },
+
+    #[error(
+        "Directory '{path}' holds more than {max_matches} matching files. Narrow it with --max-depth or --ext."
+    )]
+    TooManyFilesInDirectory { path: String, max_matches: usize },
+
     #[error("Schema mismatch between files:\n  {file1}\n  {file2}\n  {details}")]
     SchemaMismatch {
         file1: String,
@@ -50,24 +112,129 @@ pub enum PqError {
         details: String,
     },
 
+    #[error("Schemas differ across {files} files\n  {columns} column(s) are missing from some files or declared differently")]
+    IncompatibleSchemas { files: usize, columns: usize },
+
+    #[error(
+        "Statistics changed beyond the tolerance of {tolerance}\n  {changes} change(s) exceed it"
+    )]
+    StatsOutOfTolerance { changes: usize, tolerance: String },
+
     #[error("Unsupported format: {format}\n  Supported formats: {supported}")]
     UnsupportedFormat { format: String, supported: String },
 
     #[error("Path is a directory, not a file: {path}")]
     IsDirectory { path: String },
 
-    #[error("Column not found in {path}: {column}")]
-    ColumnNotFound { path: String, column: String },
+    #[error(
+        "Column not found in {path}: {column}{}{}",
+        suggestion_hint(.suggestion),
+        available_columns_hint(.available)
+    )]
+    ColumnNotFound {
+        path: String,
+        column: String,
+        suggestion: Option<String>,
+        available: Vec<String>,
+    },
+
+    #[error(
+        "Column name is ambiguous in {path}: {column}\n  Matches ignoring case: {}",
+        candidates.join(", ")
+    )]
+    AmbiguousColumn {
+        path: String,
+        column: String,
+        candidates: Vec<String>,
+    },
+
+    #[error("Row group {index} out of range in {path} (file has {count} row groups)")]
+    RowGroupOutOfRange {
+        path: String,
+        index: usize,
+        count: usize,
+    },
+
+    #[error("Column position {position} out of range in {path} (file has {count} columns)")]
+    ColumnOutOfRange {
+        path: String,
+        position: usize,
+        count: usize,
+    },
+
+    #[error("Invalid histogram: {details}")]
+    InvalidHistogram { details: String },
+
+    #[error("Unsupported type {data_type} for column {column} in {path}\n  {details}")]
+    UnsupportedColumnType {
+        path: String,
+        column: String,
+        data_type: String,
+        details: String,
+    },
+
+    #[error("Invalid transform: {spec}\n  {details}")]
+    InvalidTransform { spec: String, details: String },
+
+    #[error("Cannot cast column {column} in {path} from {from} to {to}\n  {details}")]
+    InvalidCast {
+        path: String,
+        column: String,
+        from: String,
+        to: String,
+        details: String,
+    },
+
+    #[error("Cannot explode column {column} in {path}\n  It is {data_type}, not a list")]
+    CannotExplode {
+        path: String,
+        column: String,
+        data_type: String,
+    },
+
+    #[error("Row count mismatch in {path}\n  The footer says {expected} rows, but {actual} rows decoded{}", column_hint(.column))]
+    RowCountMismatch {
+        path: String,
+        expected: i64,
+        actual: u64,
+        column: Option<String>,
+    },
+
+    #[error("Input is not sorted by {column}: {path}\n  Row {row} sorts before the row above it; pass --assume-sorted to skip this check")]
+    NotSorted {
+        path: String,
+        column: String,
+        row: u64,
+    },
 
     #[error("Invalid Parquet metadata in {path}\n  {details}")]
     InvalidMetadata { path: String, details: String },
+
+    #[error("No metadata key {key} in {path}\n  {}", metadata_keys_hint(.available))]
+    MetadataKeyNotFound {
+        path: String,
+        key: String,
+        available: Vec<String>,
+    },
+
+    #[error("Cannot watch files\n  {details}")]
+    WatchError { details: String },
+
+    #[error("Invalid command to watch: {command}\n  {details}")]
+    InvalidWatchCommand { command: String, details: String },
+
+    #[error("Invalid configuration in {origin}\n  {details}")]
+    InvalidConfig { origin: String, details: String },
+
+    #[error("{message}\n  --strict treats warnings as errors")]
+    StrictWarning { message: String },
 }
 
 impl PqError {
     /// Create a file-not-found error with path context
     pub fn file_not_found(path: &Path) -> Self {
         Self::FileNotFound {
-            path: path.display().to_string(),
+            path: display_path(path),
         }
     }
 
@@ -76,25 +243,49 @@ impl PqError {
         let details = err.to_string();
         let details = simplify_parquet_error(&details);
         Self::InvalidParquet {
-            path: path.display().to_string(),
+            path: display_path(path),
             details,
         }
     }
 
-    /// Create a corrupted file error
+    /// Create a corrupted file error from a library error
     pub fn corrupted(path: &Path, err: impl std::fmt::Display) -> Self {
         let details = err.to_string();
         let details = simplify_parquet_error(&details);
         Self::CorruptedFile {
-            path: path.display().to_string(),
+            path: display_path(path),
             details,
         }
     }
 
+    /// A file compressed as a whole by `format`, which `command` would undo
+    pub fn compressed_file(path: &Path, format: &str, command: Option<&str>) -> Self {
+        Self::CompressedFile {
+            path: display_path(path),
+            format: format.to_string(),
+            command: command.map(str::to_string),
+        }
+    }
+
+    /// A file with no footer yet that changed moments ago, after `waited` for it if
+    /// `--wait-for-complete` gave up
+    pub fn being_written(path: &Path, waited: Option<std::time::Duration>) -> Self {
+        Self::BeingWritten {
+            path: display_path(path),
+            waited: waited.map(|waited| format!("{waited:?}")),
+        }
+    }
+
+    pub fn encrypted(path: &Path) -> Self {
+        Self::Encrypted {
+            path: display_path(path),
+        }
+    }
+
     /// Create a read error with path context
     pub fn read_error(path: &Path, err: impl std::fmt::Display) -> Self {
         Self::ReadError {
-            path: path.display().to_string(),
+            path: display_path(path),
             details: err.to_string(),
         }
     }
@@ -129,9 +320,30 @@ impl PqError {
     }
 
     /// Create a write error with path context
+    pub fn output_exists(path: &Path) -> Self {
+        Self::OutputExists {
+            path: display_path(path),
+        }
+    }
+
+    pub fn output_is_input(path: &Path) -> Self {
+        Self::OutputIsInput {
+            path: display_path(path),
+        }
+    }
+
+    /// Create an error for two inputs whose output files would share a path
+    pub fn output_collision(path: &Path, first: &Path, second: &Path) -> Self {
+        Self::OutputCollision {
+            path: display_path(path),
+            first: display_path(first),
+            second: display_path(second),
+        }
+    }
+
     pub fn write_error(path: &Path, err: impl std::fmt::Display) -> Self {
         Self::WriteError {
-            path: path.display().to_string(),
+            path: display_path(path),
             details: err.to_string(),
         }
     }
@@ -143,6 +355,17 @@ impl PqError {
         }
     }
 
+    /// Whether the error only means stdout was closed, which callers treat as success
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, Self::BrokenPipe)
+    }
+
+    pub fn binary_to_terminal(format: &str) -> Self {
+        Self::BinaryToTerminal {
+            format: format.to_string(),
+        }
+    }
+
     pub fn invalid_glob_pattern(pattern: &str, err: impl std::fmt::Display) -> Self {
         Self::InvalidGlobPattern {
             pattern: pattern.to_string(),
@@ -150,46 +373,346 @@ impl PqError {
         }
     }
 
+    pub fn invalid_pattern(pattern: &str, err: impl std::fmt::Display) -> Self {
+        Self::InvalidPattern {
+            pattern: pattern.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn invalid_column_selector(selector: &str, err: impl std::fmt::Display) -> Self {
+        Self::InvalidColumnSelector {
+            selector: selector.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn invalid_field_path(expression: &str, err: impl std::fmt::Display) -> Self {
+        Self::InvalidFieldPath {
+            expression: expression.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn invalid_predicate(predicate: &str, err: impl std::fmt::Display) -> Self {
+        Self::InvalidPredicate {
+            predicate: predicate.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn row_group_out_of_range(path: &Path, index: usize, count: usize) -> Self {
+        Self::RowGroupOutOfRange {
+            path: display_path(path),
+            index,
+            count,
+        }
+    }
+
+    pub fn column_out_of_range(path: &Path, position: usize, count: usize) -> Self {
+        Self::ColumnOutOfRange {
+            path: display_path(path),
+            position,
+            count,
+        }
+    }
+
     pub fn column_not_found(path: &Path, column: &str) -> Self {
         Self::ColumnNotFound {
-            path: path.display().to_string(),
+            path: display_path(path),
             column: column.to_string(),
+            suggestion: None,
+            available: Vec::new(),
+        }
+    }
+
+    /// Create a "column not found" error that lists the columns the file does have
+    pub fn column_not_found_among<I, S>(path: &Path, column: &str, available: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::ColumnNotFound {
+            path: display_path(path),
+            column: column.to_string(),
+            suggestion: None,
+            available: available.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Like `column_not_found_among`, naming the likely intended column first
+    pub fn column_not_found_suggesting<I, S>(
+        path: &Path,
+        column: &str,
+        suggestion: Option<String>,
+        available: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::ColumnNotFound {
+            path: display_path(path),
+            column: column.to_string(),
+            suggestion,
+            available: available.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn ambiguous_column<I, S>(path: &Path, column: &str, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::AmbiguousColumn {
+            path: display_path(path),
+            column: column.to_string(),
+            candidates: candidates.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn invalid_histogram(err: impl std::fmt::Display) -> Self {
+        Self::InvalidHistogram {
+            details: err.to_string(),
+        }
+    }
+
+    /// Create an error for the first row of `path` that breaks the expected order
+    pub fn not_sorted(path: &Path, column: &str, row: u64) -> Self {
+        Self::NotSorted {
+            path: display_path(path),
+            column: column.to_string(),
+            row,
+        }
+    }
+
+    pub fn unsupported_column_type(
+        path: &Path,
+        column: &str,
+        data_type: impl std::fmt::Display,
+        err: impl std::fmt::Display,
+    ) -> Self {
+        Self::UnsupportedColumnType {
+            path: display_path(path),
+            column: column.to_string(),
+            data_type: data_type.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn invalid_transform(spec: &str, err: impl std::fmt::Display) -> Self {
+        Self::InvalidTransform {
+            spec: spec.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn invalid_cast(
+        path: &Path,
+        column: &str,
+        from: impl std::fmt::Display,
+        to: impl std::fmt::Display,
+        err: impl std::fmt::Display,
+    ) -> Self {
+        Self::InvalidCast {
+            path: display_path(path),
+            column: column.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn cannot_explode(path: &Path, column: &str, data_type: impl std::fmt::Display) -> Self {
+        Self::CannotExplode {
+            path: display_path(path),
+            column: column.to_string(),
+            data_type: data_type.to_string(),
         }
     }
 
     pub fn invalid_metadata(path: &Path, err: impl std::fmt::Display) -> Self {
         Self::InvalidMetadata {
-            path: path.display().to_string(),
+            path: display_path(path),
+            details: err.to_string(),
+        }
+    }
+
+    pub fn watch_error(err: impl std::fmt::Display) -> Self {
+        Self::WatchError {
+            details: err.to_string(),
+        }
+    }
+
+    /// Create an error for a command `watch` wraps that does not parse, from clap's
+    /// message without its `error:` prefix
+    pub fn invalid_watch_command(command: &[String], error: &clap::Error) -> Self {
+        let message = error.to_string();
+        let message = message.trim_end();
+        let message = message.strip_prefix("error: ").unwrap_or(message);
+        let details = message
+            .lines()
+            .map(|line| line.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n  ")
+            .replace("\n  \n", "\n\n");
+        Self::InvalidWatchCommand {
+            command: command.join(" "),
+            details,
+        }
+    }
+
+    /// Create an error for files whose schemas `schema --unify` could not reconcile
+    pub fn incompatible_schemas(files: usize, columns: usize) -> Self {
+        Self::IncompatibleSchemas { files, columns }
+    }
+
+    pub fn stats_out_of_tolerance(changes: usize, tolerance: impl std::fmt::Display) -> Self {
+        Self::StatsOutOfTolerance {
+            changes,
+            tolerance: tolerance.to_string(),
+        }
+    }
+
+    /// Create an error for a footer row count the data does not bear out
+    pub fn row_count_mismatch(
+        path: &Path,
+        expected: i64,
+        actual: u64,
+        column: Option<&str>,
+    ) -> Self {
+        Self::RowCountMismatch {
+            path: display_path(path),
+            expected,
+            actual,
+            column: column.map(ToOwned::to_owned),
+        }
+    }
+
+    /// Create an error for removing a footer key-value entry the file does not have
+    pub fn metadata_key_not_found(path: &Path, key: &str, available: Vec<String>) -> Self {
+        Self::MetadataKeyNotFound {
+            path: display_path(path),
+            key: key.to_string(),
+            available,
+        }
+    }
+
+    /// Create an invalid configuration error for a config file or environment variable
+    pub fn invalid_config(origin: impl std::fmt::Display, err: impl std::fmt::Display) -> Self {
+        Self::InvalidConfig {
+            origin: origin.to_string(),
             details: err.to_string(),
         }
     }
 
     /// Create an "is directory" error
+    pub fn no_files_in_directory(path: &Path, extensions: &[String]) -> Self {
+        Self::NoFilesInDirectory {
+            path: display_path(path),
+            extensions: extensions
+                .iter()
+                .map(|extension| format!(".{}", extension.trim_start_matches('.')))
+                .collect::<Vec<_>>()
+                .join(", "),
+        }
+    }
+
     pub fn is_directory(path: &Path) -> Self {
         Self::IsDirectory {
-            path: path.display().to_string(),
+            path: display_path(path),
+        }
+    }
+
+    /// Create an error for a warning raised under `--strict`
+    pub fn strict_warning(message: impl Into<String>) -> Self {
+        Self::StrictWarning {
+            message: message.into(),
         }
     }
 }
 
 impl From<io::Error> for PqError {
     fn from(error: io::Error) -> Self {
+        if error.kind() == io::ErrorKind::BrokenPipe {
+            return Self::BrokenPipe;
+        }
         Self::output_error(error)
     }
 }
 
 impl From<serde_json::Error> for PqError {
     fn from(error: serde_json::Error) -> Self {
+        if error.io_error_kind() == Some(io::ErrorKind::BrokenPipe) {
+            return Self::BrokenPipe;
+        }
         Self::output_error(error)
     }
 }
 
 impl From<ArrowError> for PqError {
     fn from(error: ArrowError) -> Self {
+        let broken_pipe = match &error {
+            ArrowError::IoError(_, source) => source.kind() == io::ErrorKind::BrokenPipe,
+            ArrowError::ExternalError(source) => source
+                .downcast_ref::<io::Error>()
+                .is_some_and(|source| source.kind() == io::ErrorKind::BrokenPipe),
+            _ => false,
+        };
+        if broken_pipe {
+            return Self::BrokenPipe;
+        }
         Self::output_error(error)
     }
 }
 
+fn column_hint(column: &Option<String>) -> String {
+    column
+        .as_ref()
+        .map(|column| format!(" from column {column}"))
+        .unwrap_or_default()
+}
+
+fn being_written_hint(waited: &Option<String>) -> String {
+    match waited {
+        Some(waited) => format!(
+            "Its footer had not appeared after {waited}; raise --timeout or try again when the writer finishes"
+        ),
+        None => "Try again when the writer finishes, or pass --wait-for-complete to wait for it"
+            .to_string(),
+    }
+}
+
+fn metadata_keys_hint(available: &[String]) -> String {
+    if available.is_empty() {
+        "The file has no key-value metadata".to_string()
+    } else {
+        format!("Keys in the file: {}", available.join(", "))
+    }
+}
+
+fn suggestion_hint(suggestion: &Option<String>) -> String {
+    suggestion
+        .as_ref()
+        .map(|suggestion| format!("\n  Did you mean: {suggestion}"))
+        .unwrap_or_default()
+}
+
+fn command_hint(command: &Option<String>) -> String {
+    command
+        .as_ref()
+        .map(|command| format!(", e.g. with `{command}`"))
+        .unwrap_or_default()
+}
+
+fn available_columns_hint(available: &[String]) -> String {
+    if available.is_empty() {
+        String::new()
+    } else {
+        format!("\n  Available columns: {}", available.join(", "))
+    }
+}
+
 /// Simplify parquet library error messages to be more user-friendly
 fn simplify_parquet_error(msg: &str) -> String {
     if msg.contains("not a valid Parquet file") || msg.contains("Invalid Parquet file") {
@@ -234,6 +757,15 @@ mod tests {
         assert!(err.to_string().contains("not found"));
     }
 
+    #[test]
+    fn test_column_not_found_lists_available_columns() {
+        let err = PqError::column_not_found_among(Path::new("data.parquet"), "nme", ["id", "name"]);
+        assert_eq!(
+            err.to_string(),
+            "Column not found in data.parquet: nme\n  Available columns: id, name"
+        );
+    }
+
     #[test]
     fn test_simplify_parquet_error() {
         assert_eq!(
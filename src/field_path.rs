@@ -0,0 +1,235 @@
+//! jq-style field paths for `pq select`
+//!
+//! A path starts at a column and descends with `.field` into structs and `[N]` into
+//! lists, e.g. `.user.id` or `.items[0].sku`. Names with characters other than letters,
+//! digits, and `_` are quoted: `."order date"`. Several paths are separated by commas.
+
+use crate::error::PqError;
+use crate::Result;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct FieldPath {
+    pub segments: Vec<Segment>,
+}
+
+impl FieldPath {
+    /// Parse a comma-separated list of paths.
+    pub fn parse_list(expression: &str) -> Result<Vec<Self>> {
+        let mut parser = Parser {
+            text: expression,
+            chars: expression.char_indices().peekable(),
+        };
+        parser
+            .parse_list()
+            .map_err(|details| PqError::invalid_field_path(expression, details))
+    }
+
+    /// The path without its leading dot, used as a flattened column name.
+    pub fn column_name(&self) -> String {
+        let text = self.to_string();
+        text.strip_prefix('.').map(str::to_string).unwrap_or(text)
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                Segment::Field(name) if is_plain_name(name) => write!(formatter, ".{name}")?,
+                Segment::Field(name) => write!(formatter, ".{name:?}")?,
+                Segment::Index(index) => write!(formatter, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_plain_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn parse_list(&mut self) -> std::result::Result<Vec<FieldPath>, String> {
+        let mut paths = Vec::new();
+        loop {
+            self.skip_whitespace();
+            paths.push(self.parse_path()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                None => return Ok(paths),
+                Some((_, ',')) => {}
+                Some((position, c)) => {
+                    return Err(format!("unexpected '{c}' at position {}", position + 1))
+                }
+            }
+        }
+    }
+
+    fn parse_path(&mut self) -> std::result::Result<FieldPath, String> {
+        match self.chars.next() {
+            Some((_, '.')) => {}
+            Some((position, c)) => {
+                return Err(format!(
+                    "expected '.' to start a path at position {}, found '{c}'",
+                    position + 1
+                ))
+            }
+            None => return Err("expected a path after ','".to_string()),
+        }
+
+        let mut segments = vec![Segment::Field(self.parse_name()?)];
+        loop {
+            match self.chars.peek() {
+                Some((_, '.')) => {
+                    self.chars.next();
+                    segments.push(Segment::Field(self.parse_name()?));
+                }
+                Some((_, '[')) => {
+                    self.chars.next();
+                    segments.push(Segment::Index(self.parse_index()?));
+                }
+                _ => return Ok(FieldPath { segments }),
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> std::result::Result<String, String> {
+        if let Some((_, '"')) = self.chars.peek() {
+            self.chars.next();
+            return self.parse_quoted();
+        }
+
+        let mut name = String::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                name.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return Err(match self.chars.peek() {
+                Some((position, c)) => {
+                    format!(
+                        "expected a field name at position {}, found '{c}'",
+                        position + 1
+                    )
+                }
+                None => "expected a field name after '.'".to_string(),
+            });
+        }
+        Ok(name)
+    }
+
+    fn parse_quoted(&mut self) -> std::result::Result<String, String> {
+        let mut name = String::new();
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '"' => return Ok(name),
+                '\\' => match self.chars.next() {
+                    Some((_, escaped)) => name.push(escaped),
+                    None => break,
+                },
+                _ => name.push(c),
+            }
+        }
+        Err(format!("unterminated quoted name in '{}'", self.text))
+    }
+
+    fn parse_index(&mut self) -> std::result::Result<usize, String> {
+        let mut digits = String::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match self.chars.next() {
+            Some((_, ']')) if !digits.is_empty() => digits
+                .parse()
+                .map_err(|error| format!("invalid index [{digits}]: {error}")),
+            _ => Err("expected a non-negative index and ']' after '['".to_string()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fields_indexes_and_quoted_names() -> Result<()> {
+        let paths = FieldPath::parse_list(r#".user.id, .items[0].sku,."order date""#)?;
+        assert_eq!(
+            paths,
+            vec![
+                FieldPath {
+                    segments: vec![
+                        Segment::Field("user".to_string()),
+                        Segment::Field("id".to_string())
+                    ]
+                },
+                FieldPath {
+                    segments: vec![
+                        Segment::Field("items".to_string()),
+                        Segment::Index(0),
+                        Segment::Field("sku".to_string())
+                    ]
+                },
+                FieldPath {
+                    segments: vec![Segment::Field("order date".to_string())]
+                },
+            ]
+        );
+        let names: Vec<String> = paths.iter().map(FieldPath::column_name).collect();
+        assert_eq!(names, vec!["user.id", "items[0].sku", r#""order date""#]);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_paths() {
+        for expression in [
+            "user.id",
+            ".",
+            ".a,",
+            ".a[",
+            ".a[x]",
+            ".a..b",
+            ".a b",
+            r#"."open"#,
+            ".[0]",
+        ] {
+            assert!(
+                matches!(
+                    FieldPath::parse_list(expression),
+                    Err(PqError::InvalidFieldPath { .. })
+                ),
+                "{expression} should not parse"
+            );
+        }
+    }
+}
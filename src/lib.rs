@@ -1,3 +1,30 @@
+//! Parquet inspection as a library: the operations behind the `pq` binary.
+//!
+//! Every operation takes a [`Dataset`] of one or more files (globs are expanded) and
+//! returns typed results or a [`PqError`] carrying the same messages the CLI prints.
+//! Row-returning operations yield Arrow [`RecordBatch`](arrow::array::RecordBatch)es,
+//! which [`write_batches`] renders in any of the CLI's output formats.
+//!
+//! ```no_run
+//! # fn main() -> pq::Result<()> {
+//! let dataset = pq::dataset_from_inputs(vec!["data.parquet".into()])?;
+//! for result in pq::schema(&dataset)? {
+//!     println!("{}: {} columns", result.path.display(), result.columns.len());
+//! }
+//!
+//! let options = pq::ScanOptions {
+//!     rows: 5,
+//!     columns: vec!["id".to_string()],
+//!     global: false,
+//!     filter: None,
+//!     row_groups: Vec::new(),
+//! };
+//! let results = pq::scan(&dataset, pq::ScanKind::Head, &options)?;
+//! pq::write_batches(std::io::stdout(), pq::OutputFormat::Jsonl, &results[0].batches)?;
+//! # Ok(())
+//! # }
+//! ```
+
 mod api;
 mod atomic_output;
 mod cli;
@@ -5,27 +32,81 @@ mod commands;
 mod dataset;
 mod engine;
 mod error;
+mod field_path;
 mod model;
 mod output;
+mod path_text;
+mod predicate;
+mod selector;
+mod suggest;
+mod warning;
 
-pub use api::{count, dataset_from_inputs, info, merge, scan, schema, stats};
-use clap::Parser;
-pub use dataset::Dataset;
+pub use api::{
+    bench, checksum, column_chunks, count, count_by, dataset_from_inputs, describe, edit_metadata,
+    exists, extract, grep, hist, info, key_value_metadata, merge, merge_append, merge_sorted,
+    nulls, page_indexes, prune_check, reshape, row_groups, sample, scan, schema, schema_ddl,
+    select, sort_rows, stats, stats_by_row_group, stats_diff, transform, unify_schemas,
+    verify_count,
+};
+use clap::FromArgMatches;
+pub use dataset::{Dataset, InputOptions, InputOrder};
 pub use error::PqError;
 pub use model::{
-    ColumnInfo, ColumnStats, ColumnType, CompressionCodec, CompressionSummary, CountEntry,
-    CountResult, FileInfo, LogicalTypeKind, PhysicalType, ScanKind, ScanOptions, ScanResult,
-    SchemaResult, StatValue, StatsResult, TimeUnit,
+    AppendSummary, BenchOperation, BenchOptions, BenchResult, ChecksumAlgorithm, ChecksumOptions,
+    ChecksumResult, CodecColumns, ColumnCase, ColumnChecksum, ColumnChunkSummary,
+    ColumnChunksResult, ColumnDescription, ColumnInfo, ColumnNulls, ColumnPages, ColumnStats,
+    ColumnStatsDiff, ColumnType, CompressionCodec, CompressionSummary, CountEntry, CountResult,
+    DdlDialect, DdlResult, ExistsAnswer, ExistsEvidence, ExistsOptions, ExistsResult,
+    FileDescription, FileInfo, GrepOptions, GroupCount, GroupCountOptions, GroupCountResult,
+    Histogram, HistogramBucket, HistogramOptions, KeyValueEntry, KeyValueMetadataResult,
+    LogicalTypeKind, MatchCount, MetadataEdit, NullRuns, NullsOptions, NullsResult,
+    PageIndexOptions, PageIndexResult, PageInfo, PagePruning, ParquetWriteOptions, PhysicalType,
+    PruneDecision, PruneReport, ReshapeOptions, RowCountCheck, RowGroupPages, RowGroupPruning,
+    RowGroupRange, RowGroupStats, RowGroupStatsResult, RowGroupSummary, RowGroupsResult,
+    SampleOptions, SampleResult, ScanKind, ScanOptions, ScanResult, SchemaResult, SelectOptions,
+    SortKey, SortOrder, SortedMergeOptions, SortingColumn, StatComparison, StatValue, StatsDiff,
+    StatsProblem, StatsResult, StatsSort, StatsTolerance, Stratum, TimeUnit, TypeConflict,
+    UnifiedColumn, UnifiedSchema, DEFAULT_BATCH_SIZE, MAX_STRATA,
 };
+pub use output::{write_batches, OutputFormat};
+use std::path::{Path, PathBuf};
+pub use warning::print_error;
 
 pub type Result<T> = std::result::Result<T, PqError>;
 
 #[doc(hidden)]
 pub fn run_cli() -> Result<()> {
-    let cli = cli::args::Cli::parse();
-    run(cli.command)
+    if let Some(input) = commands::columns_helper_input(std::env::args_os()) {
+        return commands::complete_columns(&input);
+    }
+    let config = cli::config::Config::load()?;
+    let matches = config.command().get_matches();
+    let cli = cli::args::Cli::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    warning::configure(cli.messages.policy(cli.command.quiet()));
+    let output_file = match &cli.output.output_file {
+        Some(path) => Some(open_output_file(path, &cli.command)?),
+        None => None,
+    };
+    run(cli.command, &cli.display.options(), &cli.input, &config)?;
+    output_file.map_or(Ok(()), output::OutputFile::commit)
+}
+
+fn open_output_file(path: &Path, command: &cli::args::Command) -> Result<output::OutputFile> {
+    if matches!(command, cli::args::Command::Watch(_)) {
+        return Err(PqError::watch_error(
+            "--output-file does not apply to watch, which redraws the terminal",
+        ));
+    }
+    let inputs = command.inputs();
+    let inputs: Vec<&Path> = inputs.iter().map(PathBuf::as_path).collect();
+    output::OutputFile::create(path, &inputs, command.force())
 }
 
-fn run(command: cli::args::Command) -> Result<()> {
-    commands::run(command)
+fn run(
+    command: cli::args::Command,
+    display: &output::DisplayOptions,
+    input: &cli::args::InputArgs,
+    config: &cli::config::Config,
+) -> Result<()> {
+    commands::run(command, display, input, config)
 }
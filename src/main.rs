@@ -2,7 +2,11 @@ use pq::Result;
 
 fn main() {
     if let Err(err) = run() {
-        eprintln!("error: {err}");
+        // A reader such as `head` closing the pipe early is not a failure
+        if err.is_broken_pipe() {
+            return;
+        }
+        pq::print_error(&err);
         std::process::exit(1);
     }
 }
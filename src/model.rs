@@ -1,11 +1,17 @@
+use crate::path_text::display_path;
 use arrow::array::RecordBatch;
 use parquet::basic::{
     Compression as ParquetCompression, ConvertedType as ParquetConvertedType,
     LogicalType as ParquetLogicalType, TimeUnit as ParquetTimeUnit, Type as ParquetPhysicalType,
 };
+use parquet::file::properties::WriterVersion;
 use parquet::schema::types::ColumnDescriptor;
 use std::fmt;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Rows per decoded batch unless `--batch-size` says otherwise, as in Arrow's reader.
+pub const DEFAULT_BATCH_SIZE: usize = 1024;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ScanKind {
@@ -13,9 +19,130 @@ pub enum ScanKind {
     Tail,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct ScanOptions {
     pub rows: usize,
+    /// Root column selectors (names, `re:` regexes, globs, 1-based positions or ranges);
+    /// batches follow selector order. Empty reads every column.
+    pub columns: Vec<String>,
+    /// Spend `rows` across the files in order, as one stream, instead of per file.
+    pub global: bool,
+    /// Only return rows matching this `--where` predicate; their positions in the file
+    /// are then unknown, so results have no `first_row`.
+    pub filter: Option<String>,
+    /// Only read these row groups, in file order; empty reads them all. Results then
+    /// have no `first_row` either.
+    pub row_groups: Vec<RowGroupRange>,
+}
+
+/// Row groups `first` through `last`, inclusive and 0-based, as `--row-group 3:7` gives them.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RowGroupRange {
+    pub first: usize,
+    pub last: usize,
+}
+
+/// How column names given by the user match a file's columns.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColumnCase {
+    #[default]
+    Sensitive,
+    /// A name matches the column spelled exactly like it, or else the one column that
+    /// differs only in case. Regex and glob selectors ignore case too.
+    Insensitive,
+}
+
+/// Column projection and row limit for `convert`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ConvertOptions {
+    pub columns: Vec<String>,
+    pub exclude: Vec<String>,
+    pub limit: Option<usize>,
+    pub reshape: ReshapeOptions,
+    pub column_case: ColumnCase,
+    /// Rows per decoded batch; `DEFAULT_BATCH_SIZE` when unset
+    pub batch_size: Option<usize>,
+    /// Writer settings for Parquet output; other formats ignore them
+    pub write: ParquetWriteOptions,
+}
+
+/// Struct flattening and list explosion for tabular output from `head`, `tail`, and
+/// `convert`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ReshapeOptions {
+    /// Replace each struct column with one `parent.child` column per leaf field
+    pub flatten: bool,
+    /// List column to expand into one row per element, repeating the other columns
+    pub explode: Option<String>,
+    /// Keep rows whose exploded list is null or empty, with a null element
+    pub keep_empty: bool,
+}
+
+impl ReshapeOptions {
+    pub fn is_noop(&self) -> bool {
+        !self.flatten && self.explode.is_none()
+    }
+}
+
+/// Column renames, drops, and casts for `transform`, each naming an input column.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TransformOptions {
+    pub renames: Vec<(String, String)>,
+    pub drops: Vec<String>,
+    pub casts: Vec<(String, arrow::datatypes::DataType)>,
+    /// Cast values the new type cannot hold to null, or truncate them, instead of failing
+    pub allow_lossy: bool,
+    pub column_case: ColumnCase,
+    /// Rows per decoded batch; `DEFAULT_BATCH_SIZE` when unset
+    pub batch_size: Option<usize>,
+}
+
+/// How `merge` and `transform` encode and compress the Parquet files they write.
+/// Columns are named by a column or a dotted path into one, and settings for them
+/// apply to every leaf under it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParquetWriteOptions {
+    pub compression: ParquetCompression,
+    pub column_compression: Vec<(String, ParquetCompression)>,
+    /// Format version, which also picks V1 or V2 data pages; the writer's default when unset
+    pub writer_version: Option<WriterVersion>,
+    /// Largest dictionary page, in bytes, before a column falls back to plain encoding
+    pub dictionary_page_size_limit: Option<usize>,
+    /// Target data page size in bytes
+    pub data_page_size_limit: Option<usize>,
+    /// Columns written without dictionary encoding
+    pub disable_dictionary: Vec<String>,
+}
+
+impl Default for ParquetWriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: ParquetCompression::SNAPPY,
+            column_compression: Vec::new(),
+            writer_version: None,
+            dictionary_page_size_limit: None,
+            data_page_size_limit: None,
+            disable_dictionary: Vec::new(),
+        }
+    }
+}
+
+/// Field paths such as `.user.id, .items[0].sku` and a per-file row limit for `select`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SelectOptions {
+    pub expression: String,
+    pub rows: Option<usize>,
+    /// Name columns by their full path instead of rebuilding nested structs
+    pub flatten: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct GrepOptions {
+    pub pattern: String,
+    pub columns: Vec<String>,
+    pub regex: bool,
+    pub ignore_case: bool,
+    pub max_matches: Option<usize>,
 }
 
 #[derive(Clone, Debug)]
@@ -24,10 +151,93 @@ pub struct SchemaResult {
     pub columns: Vec<ColumnInfo>,
 }
 
+/// The union of several files' schemas, for `schema --unify`.
+#[derive(Clone, Debug)]
+pub struct UnifiedSchema {
+    pub files: Vec<PathBuf>,
+    pub columns: Vec<UnifiedColumn>,
+}
+
+impl UnifiedSchema {
+    pub fn is_compatible(&self) -> bool {
+        self.columns.iter().all(UnifiedColumn::is_compatible)
+    }
+
+    pub fn incompatible_columns(&self) -> usize {
+        self.columns
+            .iter()
+            .filter(|column| !column.is_compatible())
+            .count()
+    }
+}
+
+/// One leaf column, named by its dotted path, with the type and nullability most files
+/// agree on and the files that deviate from it.
+#[derive(Clone, Debug)]
+pub struct UnifiedColumn {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub nullable: bool,
+    pub files_missing: Vec<PathBuf>,
+    pub type_conflicts: Vec<TypeConflict>,
+}
+
+impl UnifiedColumn {
+    pub fn display_type(&self) -> String {
+        self.column_type.display_name()
+    }
+
+    pub fn is_compatible(&self) -> bool {
+        self.files_missing.is_empty() && self.type_conflicts.is_empty()
+    }
+}
+
+/// A type or nullability some files give a column instead of the unified one.
+#[derive(Clone, Debug)]
+pub struct TypeConflict {
+    pub column_type: ColumnType,
+    pub nullable: bool,
+    pub files: Vec<PathBuf>,
+}
+
+impl TypeConflict {
+    pub fn display_type(&self) -> String {
+        self.column_type.display_name()
+    }
+}
+
+/// Target for `schema --ddl`: a SQL dialect's `CREATE TABLE`, or Arrow schema JSON.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DdlDialect {
+    Duckdb,
+    Postgres,
+    Spark,
+    Arrow,
+}
+
+impl fmt::Display for DdlDialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Duckdb => "duckdb",
+            Self::Postgres => "postgres",
+            Self::Spark => "spark",
+            Self::Arrow => "arrow",
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DdlResult {
+    pub path: PathBuf,
+    pub ddl: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct ScanResult {
     pub path: PathBuf,
     pub batches: Vec<RecordBatch>,
+    /// Index within the file of the first returned row, when the rows are contiguous.
+    pub first_row: Option<usize>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -42,10 +252,466 @@ pub struct CountResult {
     pub total_rows: i64,
 }
 
+impl FromIterator<CountEntry> for CountResult {
+    fn from_iter<I: IntoIterator<Item = CountEntry>>(entries: I) -> Self {
+        let entries: Vec<CountEntry> = entries.into_iter().collect();
+        let total_rows = entries
+            .iter()
+            .fold(0i64, |total, entry| total.saturating_add(entry.rows));
+        Self {
+            entries,
+            total_rows,
+        }
+    }
+}
+
+/// A file's rows matching a `--where` predicate, and how they were counted.
+///
+/// Row groups whose statistics prove every row matches are counted from metadata;
+/// only those the statistics cannot settle either way are decoded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchCount {
+    pub path: PathBuf,
+    pub rows: u64,
+    /// Matching rows taken from row-group row counts without decoding
+    pub metadata_rows: u64,
+    /// Rows in the row groups that were decoded, matching or not
+    pub scanned_rows: u64,
+    pub row_groups_scanned: usize,
+    pub row_groups: usize,
+}
+
+impl MatchCount {
+    pub fn entry(&self) -> CountEntry {
+        CountEntry {
+            path: self.path.clone(),
+            rows: i64::try_from(self.rows).unwrap_or(i64::MAX),
+        }
+    }
+}
+
+/// A file's footer row count next to the rows that actually decode.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RowCountCheck {
+    pub path: PathBuf,
+    pub metadata_rows: i64,
+    pub decoded_rows: u64,
+    /// The top-level column decoded to count rows; `None` for files without columns.
+    pub column: Option<String>,
+}
+
+impl RowCountCheck {
+    pub fn matches(&self) -> bool {
+        u64::try_from(self.metadata_rows).is_ok_and(|rows| rows == self.decoded_rows)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AppendSummary {
+    pub existing_rows: u64,
+    pub appended_rows: u64,
+}
+
+impl AppendSummary {
+    pub fn total_rows(&self) -> u64 {
+        self.existing_rows + self.appended_rows
+    }
+}
+
+/// The key `merge --sorted-by` interleaves already-sorted inputs by.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SortedMergeOptions {
+    /// A top-level column; nulls sort first in either direction.
+    pub column: String,
+    pub descending: bool,
+    /// Trust the inputs' order instead of checking every row.
+    pub assume_sorted: bool,
+}
+
+/// One `--sort-by` key for the rows `head` and `tail` show.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SortKey {
+    /// A top-level column of the rows shown.
+    pub column: String,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl SortKey {
+    /// Ascending, with nulls first.
+    pub fn new(column: impl Into<String>) -> Self {
+        Self {
+            column: column.into(),
+            descending: false,
+            nulls_first: true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GroupCountOptions {
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupCount {
+    pub values: Vec<Option<String>>,
+    pub rows: u64,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupCountResult {
+    pub columns: Vec<String>,
+    pub groups: Vec<GroupCount>,
+    pub total_groups: usize,
+    pub total_rows: u64,
+}
+
+impl GroupCountResult {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self, group: &GroupCount) -> f64 {
+        if self.total_rows == 0 {
+            0.0
+        } else {
+            group.rows as f64 * 100.0 / self.total_rows as f64
+        }
+    }
+}
+
+/// Column, bucket count, and optional bounds for `hist`. Bounds are parsed as values of
+/// the column's type, so temporal columns take dates and timestamps.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HistogramOptions {
+    pub column: String,
+    pub buckets: usize,
+    pub log_scale: bool,
+    pub min: Option<String>,
+    pub max: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    pub column: String,
+    pub buckets: Vec<HistogramBucket>,
+    /// Values left out of the buckets.
+    pub null_count: u64,
+    pub nan_count: u64,
+    pub below_min: u64,
+    pub above_max: u64,
+}
+
+/// Values in `[lower, upper)`, or `[lower, upper]` for the last bucket. Edges are the
+/// column's numeric values (days, or units since the epoch, for temporal columns), with
+/// labels formatted in the column's type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistogramBucket {
+    pub lower: f64,
+    pub upper: f64,
+    pub lower_label: String,
+    pub upper_label: String,
+    pub count: u64,
+}
+
+/// Distinct values `sample --stratify` keeps reservoirs for before sampling uniformly
+/// instead.
+pub const MAX_STRATA: usize = 10_000;
+
+/// How many rows `sample` picks, from which columns, and how.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SampleOptions {
+    pub rows: usize,
+    /// Root column selectors as in `head --columns`; empty keeps every column.
+    pub columns: Vec<String>,
+    /// Seed for a reproducible sample; a random one otherwise.
+    pub seed: Option<u64>,
+    /// Sample each distinct value of this column separately.
+    pub stratify: Option<String>,
+    /// With `stratify`, size strata in proportion to their rows, but with at least this
+    /// many each; without it, strata get equal shares.
+    pub min_per_group: Option<usize>,
+}
+
+/// Sampled rows, in input order, and what each stratum gave.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SampleResult {
+    pub batches: Vec<RecordBatch>,
+    /// Strata in the order their first row was read; empty for a uniform sample.
+    pub strata: Vec<Stratum>,
+    /// Whether `stratify` was asked for but the column had too many distinct values,
+    /// so the sample is uniform instead.
+    pub too_many_strata: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Stratum {
+    /// The column's value, as tables show it.
+    pub value: String,
+    pub rows: u64,
+    pub sampled: usize,
+}
+
+/// Column selection for `nulls`, and whether to track runs of consecutive nulls.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NullsOptions {
+    pub columns: Vec<String>,
+    pub runs: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NullsResult {
+    pub path: PathBuf,
+    pub num_rows: u64,
+    /// Sorted by null percentage, highest first.
+    pub columns: Vec<ColumnNulls>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnNulls {
+    pub column: String,
+    pub null_count: u64,
+    /// Present with `NullsOptions::runs`.
+    pub runs: Option<NullRuns>,
+}
+
+impl ColumnNulls {
+    pub fn null_percent(&self, num_rows: u64) -> f64 {
+        if num_rows == 0 {
+            return 0.0;
+        }
+        self.null_count as f64 * 100.0 / num_rows as f64
+    }
+
+    pub fn is_all_null(&self, num_rows: u64) -> bool {
+        num_rows > 0 && self.null_count == num_rows
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NullRuns {
+    /// Most consecutive null rows.
+    pub longest: u64,
+    /// 0-based row indexes of the first and last non-null values, if any.
+    pub first_non_null: Option<u64>,
+    pub last_non_null: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 pub struct StatsResult {
     pub path: PathBuf,
     pub rows: Vec<ColumnStats>,
+    /// Every column's compressed bytes in the row groups read, selected or not.
+    pub compressed_bytes: u64,
+}
+
+impl StatsResult {
+    /// The part of the file's compressed column data that `column` takes up.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn compressed_percent(&self, column: &ColumnStats) -> f64 {
+        if self.compressed_bytes == 0 {
+            return 0.0;
+        }
+        column.compressed_bytes as f64 * 100.0 / self.compressed_bytes as f64
+    }
+
+    /// Reorder the rows; ties keep file order.
+    pub fn sort_rows(&mut self, order: StatsSort) {
+        match order {
+            StatsSort::Size => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.compressed_bytes)),
+            StatsSort::Nulls => self
+                .rows
+                .sort_by_key(|row| std::cmp::Reverse(row.null_count)),
+            StatsSort::Name => self.rows.sort_by(|a, b| a.column.cmp(&b.column)),
+        }
+    }
+}
+
+/// How `stats` orders its columns instead of file order.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StatsSort {
+    /// Most compressed bytes first.
+    Size,
+    /// Most nulls first.
+    Nulls,
+    /// By column name.
+    Name,
+}
+
+/// How far a statistic may move before `stats-diff` counts the change against it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatsTolerance {
+    Absolute(f64),
+    /// Percent of the old value.
+    Percent(f64),
+}
+
+impl fmt::Display for StatsTolerance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absolute(limit) => write!(f, "{limit}"),
+            Self::Percent(limit) => write!(f, "{limit}%"),
+        }
+    }
+}
+
+/// Two files' footer statistics side by side, old first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatsDiff {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    pub tolerance: Option<StatsTolerance>,
+    pub row_count: StatComparison,
+    /// Columns in both files, in the old file's order.
+    pub columns: Vec<ColumnStatsDiff>,
+    pub only_in_old: Vec<String>,
+    pub only_in_new: Vec<String>,
+}
+
+impl StatsDiff {
+    /// Changes beyond the tolerance. With one, a column in only one file counts too.
+    pub fn exceeding(&self) -> usize {
+        let columns_missing = match self.tolerance {
+            Some(_) => self.only_in_old.len() + self.only_in_new.len(),
+            None => 0,
+        };
+        std::iter::once(&self.row_count)
+            .chain(
+                self.columns
+                    .iter()
+                    .flat_map(|column| column.comparisons().map(|(_, comparison)| comparison)),
+            )
+            .filter(|comparison| comparison.exceeds_tolerance)
+            .count()
+            + columns_missing
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnStatsDiff {
+    pub column: String,
+    pub column_type: ColumnType,
+    pub null_count: StatComparison,
+    pub min: StatComparison,
+    pub max: StatComparison,
+}
+
+impl ColumnStatsDiff {
+    pub fn display_type(&self) -> String {
+        self.column_type.display_name()
+    }
+
+    /// The null count, min, and max comparisons, each with its statistic's name.
+    pub fn comparisons(&self) -> [(&'static str, &StatComparison); 3] {
+        [
+            ("null_count", &self.null_count),
+            ("min", &self.min),
+            ("max", &self.max),
+        ]
+    }
+}
+
+/// One statistic in both files, as displayed; either side is unset when that file has
+/// no such statistic.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatComparison {
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// `after - before`, when both are numbers.
+    pub delta: Option<f64>,
+    /// The delta as a percentage of `before`; unset when `before` is zero.
+    pub delta_percent: Option<f64>,
+    /// Whether the change is beyond the tolerance; never without one.
+    pub exceeds_tolerance: bool,
+}
+
+impl StatComparison {
+    pub fn changed(&self) -> bool {
+        self.before != self.after
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PruneReport {
+    pub path: PathBuf,
+    pub predicate: String,
+    pub columns: Vec<String>,
+    pub page_index: bool,
+    pub row_groups: Vec<RowGroupPruning>,
+}
+
+impl PruneReport {
+    pub fn row_groups_read(&self) -> usize {
+        self.row_groups
+            .iter()
+            .filter(|group| group.decision == PruneDecision::Read)
+            .count()
+    }
+
+    pub fn row_groups_skipped(&self) -> usize {
+        self.row_groups.len() - self.row_groups_read()
+    }
+
+    pub fn rows_read(&self) -> i64 {
+        self.row_groups
+            .iter()
+            .filter(|group| group.decision == PruneDecision::Read)
+            .map(|group| group.num_rows)
+            .sum()
+    }
+
+    pub fn rows_skipped(&self) -> i64 {
+        self.row_groups
+            .iter()
+            .filter(|group| group.decision == PruneDecision::Skip)
+            .map(|group| group.num_rows)
+            .sum()
+    }
+
+    /// Total and skipped pages across the referenced columns, when a page index is present.
+    pub fn pages(&self) -> Option<PagePruning> {
+        self.page_index.then(|| {
+            self.row_groups.iter().filter_map(|group| group.pages).fold(
+                PagePruning::default(),
+                |total, pages| PagePruning {
+                    total: total.total + pages.total,
+                    skipped: total.skipped + pages.skipped,
+                },
+            )
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowGroupPruning {
+    pub index: usize,
+    pub num_rows: i64,
+    pub decision: PruneDecision,
+    /// Statistics prove every row matches, so counting needs no decoding
+    pub matches_all: bool,
+    pub columns: Vec<ColumnStats>,
+    pub pages: Option<PagePruning>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PruneDecision {
+    Read,
+    Skip,
+}
+
+impl PruneDecision {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PagePruning {
+    pub total: usize,
+    pub skipped: usize,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -53,6 +719,12 @@ pub struct ColumnInfo {
     pub name: String,
     pub column_type: ColumnType,
     pub nullable: bool,
+    /// 0-based position among the file's leaf columns, as column chunks number them.
+    pub ordinal: usize,
+    pub max_def_level: i16,
+    pub max_rep_level: i16,
+    /// The Parquet `field_id`, which Iceberg writers use to track columns across renames.
+    pub field_id: Option<i32>,
 }
 
 impl ColumnInfo {
@@ -68,15 +740,309 @@ pub struct FileInfo {
     pub num_rows: i64,
     pub num_columns: usize,
     pub num_row_groups: usize,
+    /// Over every column chunk in the file.
     pub compression: CompressionSummary,
+    /// How many leaf columns use each codec, most used first. A column whose row groups
+    /// use different codecs counts once under each.
+    pub codec_columns: Vec<CodecColumns>,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
     pub created_by: Option<String>,
     pub version: i32,
+    pub sort_order: SortOrder,
 }
 
 impl FileInfo {
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Uncompressed over compressed column chunk bytes; `None` for files without rows.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        (self.num_rows > 0 && self.compressed_bytes > 0)
+            .then(|| self.uncompressed_bytes as f64 / self.compressed_bytes as f64)
+    }
+
+    /// The codec, or for mixed files the codecs with their column counts, e.g.
+    /// `MIXED (SNAPPY: 18 cols, ZSTD: 2 cols)`.
+    pub fn compression_label(&self) -> String {
+        match self.compression {
+            CompressionSummary::Mixed => {
+                let codecs: Vec<String> = self
+                    .codec_columns
+                    .iter()
+                    .map(|usage| format!("{}: {} cols", usage.codec, usage.columns))
+                    .collect();
+                format!("{} ({})", self.compression, codecs.join(", "))
+            }
+            summary => summary.to_string(),
+        }
+    }
+}
+
+/// The sort order a file's row groups declare in their `sorting_columns` metadata.
+/// Nothing checks that the rows actually follow it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SortOrder {
+    /// No row group declares one, or the file has no row groups.
+    Undeclared,
+    /// Every row group declares the same columns.
+    Sorted(Vec<SortingColumn>),
+    /// Row groups declare different orders, or only some declare one.
+    Mixed,
+}
+
+impl SortOrder {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Undeclared => "undeclared",
+            Self::Sorted(_) => "sorted",
+            Self::Mixed => "mixed",
+        }
+    }
+
+    /// The agreed sort columns; empty unless `Sorted`.
+    pub fn columns(&self) -> &[SortingColumn] {
+        match self {
+            Self::Sorted(columns) => columns,
+            Self::Undeclared | Self::Mixed => &[],
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undeclared => f.write_str("not declared"),
+            Self::Sorted(columns) => f.write_str(&sorting_label(columns)),
+            Self::Mixed => f.write_str("differs across row groups"),
+        }
+    }
+}
+
+/// One key of a declared sort order, e.g. `ts (asc, nulls first)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SortingColumn {
+    /// Dotted path of the leaf column.
+    pub column: String,
+    pub descending: bool,
+    pub nulls_first: bool,
+}
+
+impl fmt::Display for SortingColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = if self.descending { "desc" } else { "asc" };
+        let nulls = if self.nulls_first { "first" } else { "last" };
+        write!(f, "{} ({direction}, nulls {nulls})", self.column)
+    }
+}
+
+/// Sort columns joined for display; `-` when there are none.
+pub(crate) fn sorting_label(columns: &[SortingColumn]) -> String {
+    if columns.is_empty() {
+        return "-".to_string();
+    }
+    columns
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CodecColumns {
+    pub codec: CompressionCodec,
+    pub columns: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnChunksResult {
+    pub path: PathBuf,
+    pub columns: Vec<ColumnChunkSummary>,
+}
+
+/// Storage details for one leaf column, aggregated over all row groups.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnChunkSummary {
+    pub column: String,
+    pub column_type: ColumnType,
+    pub compression: CompressionSummary,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    pub row_groups: usize,
+    pub bloom_filter_row_groups: usize,
+    /// Total bloom filter size, when the writer recorded filter lengths.
+    pub bloom_filter_bytes: Option<u64>,
+    pub encodings: Vec<String>,
+    /// Row groups whose column chunk has a dictionary page.
+    pub dictionary_row_groups: usize,
+    /// Row groups where every data page is dictionary-encoded.
+    pub dictionary_encoded_row_groups: usize,
+    pub dictionary_entries: Option<u64>,
+    pub dictionary_bytes: Option<u64>,
+}
+
+impl ColumnChunkSummary {
+    pub fn display_type(&self) -> String {
+        self.column_type.display_name()
+    }
+
+    /// Dictionary-encoded in some row groups but not others, usually after the
+    /// writer's dictionary grew past its page size limit and fell back to PLAIN.
+    pub fn mixed_dictionary_encoding(&self) -> bool {
+        self.dictionary_encoded_row_groups > 0
+            && self.dictionary_encoded_row_groups < self.row_groups
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn dictionary_fraction(&self) -> f64 {
+        if self.row_groups == 0 {
+            0.0
+        } else {
+            self.dictionary_encoded_row_groups as f64 / self.row_groups as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RowGroupsResult {
+    pub path: PathBuf,
+    pub row_groups: Vec<RowGroupSummary>,
+}
+
+/// Layout of one row group; the counts are of leaf column chunks.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RowGroupSummary {
+    pub index: usize,
+    pub num_rows: i64,
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    pub columns: usize,
+    pub statistics_columns: usize,
+    pub dictionary_columns: usize,
+    /// Chunks with both a column index and an offset index.
+    pub page_index_columns: usize,
+    pub bloom_filter_columns: usize,
+    /// The declared sort order; empty when the row group declares none.
+    pub sorting_columns: Vec<SortingColumn>,
+}
+
+/// The footer's key-value metadata of one file, in file order.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyValueMetadataResult {
+    pub path: PathBuf,
+    pub entries: Vec<KeyValueEntry>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyValueEntry {
+    pub key: String,
+    pub value: Option<String>,
+}
+
+/// A change to a file's footer key-value metadata.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MetadataEdit {
+    /// Add entries, replacing the value of keys the file already has.
+    Set(Vec<(String, String)>),
+    /// Drop entries; each key must be present.
+    Remove(Vec<String>),
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PageIndexOptions {
+    pub column: Option<String>,
+    pub row_group: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageIndexResult {
+    pub path: PathBuf,
+    /// Whether any column chunk in the file has a column index or offset index.
+    pub has_page_index: bool,
+    pub row_groups: Vec<RowGroupPages>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowGroupPages {
+    pub index: usize,
+    pub num_rows: i64,
+    pub columns: Vec<ColumnPages>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnPages {
+    pub column: String,
+    pub column_type: ColumnType,
+    pub column_index: bool,
+    pub offset_index: bool,
+    pub pages: Vec<PageInfo>,
+}
+
+/// One data page, combining its offset index location with its column index statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageInfo {
+    pub first_row_index: Option<i64>,
+    pub rows: Option<i64>,
+    pub offset: Option<i64>,
+    pub compressed_size: Option<i32>,
+    pub null_count: Option<i64>,
+    pub min: Option<StatValue>,
+    pub max: Option<StatValue>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExistsOptions {
+    pub column: String,
+    pub value: String,
+    pub scan: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExistsResult {
+    pub path: PathBuf,
+    pub column: String,
+    pub value: String,
+    pub answer: ExistsAnswer,
+    pub evidence: ExistsEvidence,
+    pub row_groups: usize,
+    /// Row groups that could still contain the value after the checks that ran.
+    pub candidate_row_groups: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExistsAnswer {
+    DefinitelyAbsent,
+    PossiblyPresent,
+    ConfirmedPresent,
+}
+
+impl ExistsAnswer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::DefinitelyAbsent => "definitely absent",
+            Self::PossiblyPresent => "possibly present",
+            Self::ConfirmedPresent => "confirmed present",
+        }
+    }
+}
+
+/// The most precise check that decided an exists answer.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExistsEvidence {
+    Statistics,
+    BloomFilter,
+    Scan,
+}
+
+impl ExistsEvidence {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Statistics => "statistics",
+            Self::BloomFilter => "bloom",
+            Self::Scan => "scanned",
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -86,6 +1052,16 @@ pub struct ColumnStats {
     pub null_count: u64,
     pub min: Option<StatValue>,
     pub max: Option<StatValue>,
+    /// Column chunk sizes summed over the row groups the statistics cover.
+    pub compressed_bytes: u64,
+    pub uncompressed_bytes: u64,
+    /// Row groups covered whose chunk of this column was written without statistics.
+    pub row_groups_without_statistics: usize,
+    /// Whether the writer cut the bound short, so it only approximates the true value.
+    pub min_truncated: bool,
+    pub max_truncated: bool,
+    /// Statistics that cannot be right, in the row groups covered.
+    pub problems: Vec<StatsProblem>,
 }
 
 impl ColumnStats {
@@ -93,17 +1069,275 @@ impl ColumnStats {
         self.column_type.display_name()
     }
 
+    /// Whether a row group claims more nulls than rows.
+    pub fn suspicious_nulls(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| matches!(problem, StatsProblem::NullsAboveRows { .. }))
+    }
+
+    /// Whether a row group's bounds contradict themselves or the declared sort order.
+    pub fn suspicious_bounds(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| !matches!(problem, StatsProblem::NullsAboveRows { .. }))
+    }
+
     pub fn display_stat_value(&self, value: &StatValue) -> String {
-        match value {
-            StatValue::Binary(bytes) | StatValue::FixedLenBinary(bytes)
-                if self.column_type.logical == Some(LogicalTypeKind::String) =>
-            {
-                display_utf8_or_hex(bytes)
+        self.column_type.display_stat_value(value)
+    }
+}
+
+/// Statistics a writer got wrong. pq shows them as written, marked with `!`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StatsProblem {
+    /// The row group's min sorts after its max.
+    MinAboveMax { row_group: usize },
+    /// The row group claims more nulls than it has rows.
+    NullsAboveRows {
+        row_group: usize,
+        null_count: u64,
+        num_rows: i64,
+    },
+    /// The column is declared sorted, yet the row group's bounds lie wholly on the
+    /// wrong side of the previous row group's.
+    OutOfOrder {
+        row_group: usize,
+        previous: usize,
+        descending: bool,
+    },
+}
+
+impl fmt::Display for StatsProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MinAboveMax { row_group } => {
+                write!(f, "row group {row_group}: min is greater than max")
             }
-            StatValue::Binary(bytes) | StatValue::FixedLenBinary(bytes) => display_hex(bytes),
-            _ => value.to_string(),
+            Self::NullsAboveRows {
+                row_group,
+                null_count,
+                num_rows,
+            } => write!(
+                f,
+                "row group {row_group}: {null_count} nulls in {num_rows} rows"
+            ),
+            Self::OutOfOrder {
+                row_group,
+                previous,
+                descending,
+            } => write!(
+                f,
+                "row group {row_group}: every value is {} row group {previous}'s, but the column is declared sorted {}",
+                if *descending { "above" } else { "below" },
+                if *descending { "descending" } else { "ascending" },
+            ),
+        }
+    }
+}
+
+/// Hash functions `checksum` can use.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ChecksumAlgorithm {
+    /// 64-bit XXH3: fast, not cryptographic.
+    #[default]
+    Xxh3,
+    Sha256,
+}
+
+impl fmt::Display for ChecksumAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Xxh3 => "xxh3",
+            Self::Sha256 => "sha256",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChecksumOptions {
+    pub algorithm: ChecksumAlgorithm,
+    /// Also hash each top-level column on its own.
+    pub by_column: bool,
+}
+
+/// Hashes of a file's logical row data, as lowercase hex.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChecksumResult {
+    pub path: PathBuf,
+    pub algorithm: ChecksumAlgorithm,
+    pub num_rows: u64,
+    /// Over the schema and every row, in order.
+    pub checksum: String,
+    /// One per top-level column, in schema order; empty unless `by_column` was set.
+    pub columns: Vec<ColumnChecksum>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ColumnChecksum {
+    pub column: String,
+    pub checksum: String,
+}
+
+/// An operation `bench` times, run against the whole dataset.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BenchOperation {
+    /// Read the first `rows` rows of each file.
+    Head { rows: usize },
+    /// Count rows from the footers.
+    Count,
+    /// Merge every column's footer statistics.
+    Stats,
+}
+
+impl fmt::Display for BenchOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Head { .. } => "head",
+            Self::Count => "count",
+            Self::Stats => "stats",
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BenchOptions {
+    pub operation: BenchOperation,
+    /// Timed runs; at least 1.
+    pub iterations: usize,
+    /// Untimed runs first, to warm the page cache and allocator.
+    pub warmup: usize,
+}
+
+/// Wall times of repeated in-process runs of one operation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BenchResult {
+    pub operation: BenchOperation,
+    pub files: usize,
+    /// Rows one run covers: those `head` returns, or every row the footers count.
+    pub rows: u64,
+    /// Untimed runs before the timed ones.
+    pub warmup: usize,
+    /// One per timed run, in the order they ran.
+    pub timings: Vec<Duration>,
+}
+
+impl BenchResult {
+    pub fn min(&self) -> Duration {
+        self.sorted().first().copied().unwrap_or_default()
+    }
+
+    /// The middle run, or the mean of the middle two.
+    pub fn median(&self) -> Duration {
+        let sorted = self.sorted();
+        match sorted.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 1 => sorted[len / 2],
+            len => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
         }
     }
+
+    /// The nearest-rank 95th percentile: no more than 5% of runs took longer.
+    pub fn p95(&self) -> Duration {
+        let sorted = self.sorted();
+        let rank = (sorted.len() * 95).div_ceil(100);
+        sorted
+            .get(rank.saturating_sub(1))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Rows covered per second at the median time; `None` when that rounds to zero.
+    pub fn rows_per_second(&self) -> Option<f64> {
+        let seconds = self.median().as_secs_f64();
+        (seconds > 0.0).then(|| self.rows as f64 / seconds)
+    }
+
+    fn sorted(&self) -> Vec<Duration> {
+        let mut sorted = self.timings.clone();
+        sorted.sort_unstable();
+        sorted
+    }
+}
+
+/// Where a default option value came from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConfigSource {
+    /// Not configured; the built-in default applies.
+    Default,
+    File(PathBuf),
+    /// The named environment variable.
+    Env(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => f.write_str("default"),
+            Self::File(path) => write!(f, "{}", display_path(path)),
+            Self::Env(name) => write!(f, "${name}"),
+        }
+    }
+}
+
+/// One configurable default, as `pq config show` reports it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigSetting {
+    pub key: String,
+    pub value: Option<String>,
+    pub source: ConfigSource,
+}
+
+/// One row group's statistics for one leaf column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowGroupStats {
+    pub row_group: usize,
+    pub num_rows: i64,
+    /// Empty null count and bounds when `has_statistics` is false.
+    pub stats: ColumnStats,
+    pub has_statistics: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct RowGroupStatsResult {
+    pub path: PathBuf,
+    /// By row group, then column.
+    pub rows: Vec<RowGroupStats>,
+}
+
+/// Footer-only overview of one file: what `info`, `schema`, and `stats` report, together.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileDescription {
+    pub info: FileInfo,
+    pub columns: Vec<ColumnDescription>,
+}
+
+impl FileDescription {
+    #[allow(clippy::cast_precision_loss)]
+    pub fn null_percent(&self, column: &ColumnDescription) -> f64 {
+        if self.info.num_rows <= 0 {
+            0.0
+        } else {
+            column.null_count as f64 * 100.0 / self.info.num_rows as f64
+        }
+    }
+}
+
+/// A leaf column's type with its null count and bounds from row-group statistics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnDescription {
+    pub name: String,
+    pub column_type: ColumnType,
+    pub nullable: bool,
+    pub null_count: u64,
+    pub min: Option<StatValue>,
+    pub max: Option<StatValue>,
+}
+
+impl ColumnDescription {
+    pub fn display_type(&self) -> String {
+        self.column_type.display_name()
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -136,6 +1370,51 @@ impl ColumnType {
             .as_ref()
             .map_or_else(|| self.physical.to_string(), LogicalTypeKind::display_name)
     }
+
+    /// The statistic as a number, for integer and floating-point physical types.
+    pub fn stat_number(&self, value: &StatValue) -> Option<f64> {
+        match value {
+            StatValue::Int32(number) if self.is_unsigned() => Some(f64::from(*number as u32)),
+            StatValue::Int64(number) if self.is_unsigned() => Some(*number as u64 as f64),
+            StatValue::Int32(number) => Some(f64::from(*number)),
+            StatValue::Int64(number) => Some(*number as f64),
+            StatValue::Float(number) => Some(f64::from(*number)),
+            StatValue::Double(number) => Some(*number),
+            StatValue::Binary(_)
+            | StatValue::Boolean(_)
+            | StatValue::FixedLenBinary(_)
+            | StatValue::Int96(_) => None,
+        }
+    }
+
+    pub fn display_stat_value(&self, value: &StatValue) -> String {
+        match value {
+            StatValue::Binary(bytes) | StatValue::FixedLenBinary(bytes)
+                if self.logical == Some(LogicalTypeKind::String) =>
+            {
+                display_utf8_or_hex(bytes)
+            }
+            StatValue::FixedLenBinary(bytes) if self.logical == Some(LogicalTypeKind::Uuid) => {
+                display_uuid(bytes).unwrap_or_else(|| display_hex(bytes))
+            }
+            StatValue::Binary(bytes) | StatValue::FixedLenBinary(bytes) => display_hex(bytes),
+            StatValue::Int32(number) if self.is_unsigned() => (*number as u32).to_string(),
+            StatValue::Int64(number) if self.is_unsigned() => (*number as u64).to_string(),
+            _ => value.to_string(),
+        }
+    }
+
+    /// Unsigned integers are stored in signed physical types and must be read back as
+    /// unsigned to display and order correctly.
+    pub fn is_unsigned(&self) -> bool {
+        matches!(
+            self.logical,
+            Some(LogicalTypeKind::Integer {
+                is_signed: false,
+                ..
+            })
+        )
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -181,6 +1460,11 @@ impl fmt::Display for PhysicalType {
     }
 }
 
+/// Arrow field metadata key naming an extension type.
+pub(crate) const ARROW_EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
+/// Canonical Arrow extension name pq attaches to Parquet UUID columns.
+pub(crate) const UUID_EXTENSION_NAME: &str = "arrow.uuid";
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum LogicalTypeKind {
     String,
@@ -462,6 +1746,22 @@ fn display_utf8_or_hex(value: &[u8]) -> String {
     }
 }
 
-fn display_hex(value: &[u8]) -> String {
+pub(crate) fn display_hex(value: &[u8]) -> String {
     value.iter().map(|byte| format!("{byte:02x}")).collect()
 }
+
+/// Canonical 8-4-4-4-12 form, or `None` unless given exactly sixteen bytes.
+pub(crate) fn display_uuid(value: &[u8]) -> Option<String> {
+    if value.len() != 16 {
+        return None;
+    }
+    let hex = display_hex(value);
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    ))
+}
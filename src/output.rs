@@ -1,40 +1,93 @@
 use crate::error::PqError;
 use crate::model::{
-    ColumnInfo, ColumnStats, CountResult, FileInfo, LogicalTypeKind, SchemaResult, StatValue,
-    StatsResult,
+    display_hex, display_uuid, BenchResult, ChecksumResult, ColumnChunkSummary, ColumnChunksResult,
+    ColumnInfo, ColumnPages, ColumnStats, ConfigSetting, CountResult, ExistsResult,
+    FileDescription, FileInfo, GroupCountResult, Histogram, KeyValueEntry, KeyValueMetadataResult,
+    LogicalTypeKind, MatchCount, NullsResult, PageIndexResult, PageInfo, PruneReport,
+    RowGroupPruning, RowGroupStats, RowGroupStatsResult, RowGroupSummary, RowGroupsResult,
+    SchemaResult, SortingColumn, StatValue, StatsDiff, StatsResult, Stratum, TypeConflict,
+    UnifiedColumn, UnifiedSchema,
 };
-use crate::Result;
+use crate::path_text::display_path;
+use crate::{warning, Result};
+use ::parquet::file::properties::WriterProperties;
 use arrow::array::RecordBatch;
+use arrow::datatypes::Schema;
 use serde::Serialize;
-use serde_json::Value;
+use serde_json::{Map, Value};
+use std::fs::File;
 use std::io;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
+mod bench;
+mod binary;
+mod checksum;
+mod config;
 mod csv;
 mod csv_support;
+mod describe;
+mod display;
+mod exists;
+mod float;
+mod groups;
+mod hist;
 mod info;
+mod integer;
+mod ipc;
 mod json;
+mod lines;
+mod nulls;
+mod pages;
+mod parquet;
+mod prune;
+mod row_numbers;
 mod schema;
+mod sink;
 mod stats;
+mod stats_diff;
+mod stdout;
 mod table;
+mod temporal;
+mod values;
 
+/// Formats record batches and reports can be written in.
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub(crate) enum OutputFormat {
+pub enum OutputFormat {
+    /// Bordered table with a header row.
     #[default]
     Table,
+    /// One JSON array of row objects.
     Json,
+    /// One JSON object per line.
     Jsonl,
+    /// Comma-separated values with a header row.
     Csv,
+    /// Tab-separated values: CSV with a tab delimiter.
+    Tsv,
+    /// Arrow IPC stream, keeping column types intact for Arrow-based tools. Record
+    /// batches only; reports have no Arrow form.
+    Arrow,
+    /// A Parquet file, for record batches only.
+    Parquet,
 }
 
 impl OutputFormat {
-    pub fn structured(self) -> Option<StructuredOutputFormat> {
+    pub(crate) fn structured(self) -> Option<StructuredOutputFormat> {
         match self {
-            Self::Table => None,
+            Self::Table | Self::Arrow | Self::Parquet => None,
             Self::Json => Some(StructuredOutputFormat::Json),
             Self::Jsonl => Some(StructuredOutputFormat::Jsonl),
-            Self::Csv => Some(StructuredOutputFormat::Csv),
+            Self::Csv | Self::Tsv => Some(StructuredOutputFormat::Csv),
+        }
+    }
+
+    /// The name of a binary format, which would garble a terminal.
+    pub(crate) fn binary_name(self) -> Option<&'static str> {
+        match self {
+            Self::Arrow => Some("arrow"),
+            Self::Parquet => Some("parquet"),
+            Self::Table | Self::Json | Self::Jsonl | Self::Csv | Self::Tsv => None,
         }
     }
 }
@@ -46,11 +99,42 @@ pub(crate) enum StructuredOutputFormat {
     Csv,
 }
 
-#[derive(Debug)]
-enum FileOutputFormat {
+pub(crate) use binary::BinaryEncoding;
+pub(crate) use csv_support::{CsvDialect, CsvQuoteStyle};
+pub(crate) use display::DisplayOptions;
+use display::DisplayTarget;
+pub(crate) use float::{FloatFormat, FloatStyle};
+pub(crate) use info::InfoNumbers;
+pub(crate) use integer::Int64Format;
+pub(crate) use lines::ValueLines;
+use sink::Sink;
+pub(crate) use sink::TextCompression;
+pub(crate) use stdout::{stdout, stdout_is_terminal, OutputFile};
+pub(crate) use table::TableOverflow;
+use table::{escape_control_chars, TableFit, TableOptions};
+pub(crate) use temporal::{parse_strftime, DisplayTimeZone, TemporalFormat};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum FileOutputFormat {
     Csv,
+    Tsv,
     Json,
     Jsonl,
+    Arrow,
+    Parquet,
+}
+
+impl FileOutputFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Tsv => "tsv",
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Arrow => "arrow",
+            Self::Parquet => "parquet",
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -63,6 +147,65 @@ struct SchemaJsonRow {
     nullable: bool,
     physical_type: String,
     logical_type: Option<String>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    extended: Option<SchemaExtendedJson>,
+}
+
+#[derive(Serialize)]
+struct SchemaExtendedJson {
+    ordinal: usize,
+    max_definition_level: i16,
+    max_repetition_level: i16,
+    field_id: Option<i32>,
+}
+
+#[derive(Serialize)]
+struct StatsDiffJson {
+    old: String,
+    new: String,
+    tolerance: Option<String>,
+    exceeding: usize,
+    row_count: stats_diff::ComparisonJson,
+    columns: Vec<ColumnStatsDiffJson>,
+    only_in_old: Vec<String>,
+    only_in_new: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct ColumnStatsDiffJson {
+    column: String,
+    #[serde(rename = "type")]
+    display_type: String,
+    null_count: stats_diff::ComparisonJson,
+    min: stats_diff::ComparisonJson,
+    max: stats_diff::ComparisonJson,
+}
+
+#[derive(Serialize)]
+struct UnifiedSchemaJson {
+    files: Vec<String>,
+    compatible: bool,
+    columns: Vec<UnifiedColumnJsonRow>,
+}
+
+#[derive(Serialize)]
+struct UnifiedColumnJsonRow {
+    name: String,
+    #[serde(rename = "type")]
+    display_type: String,
+    nullable: bool,
+    physical_type: String,
+    logical_type: Option<String>,
+    files_missing: Vec<String>,
+    type_conflicts: Vec<TypeConflictJson>,
+}
+
+#[derive(Serialize)]
+struct TypeConflictJson {
+    #[serde(rename = "type")]
+    display_type: String,
+    nullable: bool,
+    files: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -77,6 +220,89 @@ struct StatsJsonRow {
     max: Option<Value>,
     physical_type: String,
     logical_type: Option<String>,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+    compressed_percent: f64,
+}
+
+#[derive(Serialize)]
+struct RowGroupStatsJsonRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    row_group: usize,
+    rows: i64,
+    statistics: bool,
+    column: String,
+    #[serde(rename = "type")]
+    display_type: String,
+    null_count: Option<u64>,
+    min: Option<Value>,
+    max: Option<Value>,
+    physical_type: String,
+    logical_type: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BenchJson {
+    operation: String,
+    files: usize,
+    rows: u64,
+    warmup: usize,
+    iterations: usize,
+    min_ms: f64,
+    median_ms: f64,
+    p95_ms: f64,
+    rows_per_second: Option<f64>,
+    timings_ms: Vec<f64>,
+}
+
+#[derive(Serialize)]
+struct ChecksumJson {
+    file: String,
+    rows: u64,
+    algorithm: String,
+    checksum: String,
+    #[serde(skip_serializing_if = "Map::is_empty")]
+    columns: Map<String, Value>,
+}
+
+#[derive(Serialize)]
+struct ConfigJsonRow {
+    setting: String,
+    value: Option<String>,
+    source: String,
+}
+
+#[derive(Serialize)]
+struct HistogramJson {
+    column: String,
+    edges: Vec<f64>,
+    labels: Vec<String>,
+    counts: Vec<u64>,
+    null_count: u64,
+    nan_count: u64,
+    below_min: u64,
+    above_max: u64,
+}
+
+#[derive(Serialize)]
+struct NullsJsonRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    column: String,
+    rows: u64,
+    null_count: u64,
+    null_percent: f64,
+    all_null: bool,
+    #[serde(flatten)]
+    runs: Option<NullRunsJson>,
+}
+
+#[derive(Serialize)]
+struct NullRunsJson {
+    longest_null_run: u64,
+    first_non_null: Option<u64>,
+    last_non_null: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -87,214 +313,1273 @@ struct FileInfoJsonRow {
     num_columns: usize,
     num_row_groups: usize,
     compression: String,
+    /// Leaf columns per codec.
+    compression_codecs: Map<String, Value>,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+    compression_ratio: Option<f64>,
     created_by: Option<String>,
     version: i32,
+    /// `sorted`, `undeclared`, or `mixed` when row groups disagree.
+    sort_order: &'static str,
+    sorting_columns: Vec<SortingColumnJson>,
+}
+
+#[derive(Serialize)]
+struct SortingColumnJson {
+    column: String,
+    descending: bool,
+    nulls_first: bool,
+}
+
+/// One nested document per file, suitable for saving as a dataset manifest.
+#[derive(Serialize)]
+struct DescribeJsonDocument {
+    #[serde(flatten)]
+    file: FileInfoJsonRow,
+    columns: Vec<DescribeColumnJsonRow>,
+}
+
+#[derive(Serialize)]
+struct DescribeColumnJsonRow {
+    name: String,
+    #[serde(rename = "type")]
+    display_type: String,
+    physical_type: String,
+    logical_type: Option<String>,
+    nullable: bool,
+    null_count: u64,
+    null_percent: f64,
+    min: Option<Value>,
+    max: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct ColumnChunkJsonRow {
+    file: String,
+    column: String,
+    #[serde(rename = "type")]
+    display_type: String,
+    compression: String,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+    row_groups: usize,
+    bloom_filter_row_groups: usize,
+    bloom_filter_bytes: Option<u64>,
+    encodings: Vec<String>,
+    dictionary_row_groups: usize,
+    dictionary_encoded_row_groups: usize,
+    dictionary_fraction: f64,
+    dictionary_entries: Option<u64>,
+    dictionary_bytes: Option<u64>,
+    mixed_dictionary_encoding: bool,
+}
+
+#[derive(Serialize)]
+struct RowGroupJsonRow {
+    file: String,
+    row_group: usize,
+    num_rows: i64,
+    compressed_bytes: u64,
+    uncompressed_bytes: u64,
+    columns: usize,
+    statistics_columns: usize,
+    dictionary_columns: usize,
+    page_index_columns: usize,
+    bloom_filter_columns: usize,
+    sorting_columns: Vec<SortingColumnJson>,
+}
+
+#[derive(Serialize)]
+struct KeyValueJsonRow {
+    file: String,
+    key: String,
+    value: Option<String>,
+}
+
+#[derive(Serialize)]
+struct ExistsJsonRow {
+    file: String,
+    column: String,
+    value: String,
+    result: &'static str,
+    evidence: &'static str,
+    row_groups: usize,
+    candidate_row_groups: usize,
+}
+
+#[derive(Serialize)]
+struct PageIndexJsonReport {
+    file: String,
+    has_page_index: bool,
+    row_groups: Vec<PageIndexJsonRowGroup>,
+}
+
+#[derive(Serialize)]
+struct PageIndexJsonRowGroup {
+    row_group: usize,
+    num_rows: i64,
+    columns: Vec<PageIndexJsonColumn>,
+}
+
+#[derive(Serialize)]
+struct PageIndexJsonColumn {
+    column: String,
+    column_index: bool,
+    offset_index: bool,
+    page_count: usize,
+    pages: Vec<PageJsonRow>,
+}
+
+#[derive(Serialize)]
+struct PageJsonRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    row_group: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<String>,
+    page: usize,
+    first_row_index: Option<i64>,
+    rows: Option<i64>,
+    offset: Option<i64>,
+    compressed_size: Option<i32>,
+    null_count: Option<i64>,
+    min: Option<Value>,
+    max: Option<Value>,
+}
+
+#[derive(Serialize)]
+struct PruneJsonReport {
+    file: String,
+    predicate: String,
+    columns: Vec<String>,
+    page_index: bool,
+    row_groups_total: usize,
+    row_groups_read: usize,
+    row_groups_skipped: usize,
+    rows_read: i64,
+    rows_skipped: i64,
+    pages_total: Option<usize>,
+    pages_skipped: Option<usize>,
+    row_groups: Vec<PruneJsonRowGroup>,
+}
+
+#[derive(Serialize)]
+struct PruneJsonRowGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    row_group: usize,
+    num_rows: i64,
+    decision: &'static str,
+    pages_total: Option<usize>,
+    pages_skipped: Option<usize>,
+    columns: Map<String, Value>,
+}
+
+pub fn write_table_batches(
+    no_header: bool,
+    display: &DisplayOptions,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    let batches = render_display(display, DisplayTarget::Text, batches)?;
+    let options = TableOptions {
+        no_header,
+        fit: TableFit::stdout(display.overflow.unwrap_or_default()),
+        raw_control_chars: display.raw_control_chars,
+        human_readable: display.human_readable,
+    };
+    table::write_batches(stdout(), &batches, &options)?;
+    Ok(())
+}
+
+pub fn write_structured_batches(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    write_structured_batches_to(stdout(), output, dialect, display, batches)
+}
+
+/// Like `write_structured_batches`, but writing each batch as it arrives so that none
+/// are held beyond their own write.
+pub fn write_structured_stream(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<()> {
+    write_structured_stream_to(stdout(), output, dialect, display, batches)
+}
+
+/// Write record batches to `writer` exactly as `pq head` prints them in `format`.
+pub fn write_batches<W: Write + Send>(
+    writer: W,
+    format: OutputFormat,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    match format {
+        OutputFormat::Arrow => return ipc::write_stream(writer, batches),
+        OutputFormat::Parquet => return parquet::write_batches(writer, batches),
+        _ => {}
+    }
+    let display = DisplayOptions::default();
+    let dialect = match format {
+        OutputFormat::Tsv => CsvDialect::default().tab_separated(),
+        _ => CsvDialect::default(),
+    };
+    match format.structured() {
+        Some(output) => write_structured_batches_to(writer, output, &dialect, &display, batches),
+        None => {
+            let batches = render_display(&display, DisplayTarget::Text, batches)?;
+            table::write_batches(writer, &batches, &TableOptions::default())
+        }
+    }
+}
+
+fn write_structured_batches_to<W: Write>(
+    writer: W,
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
+    batches: &[RecordBatch],
+) -> Result<()> {
+    let batches = batches.iter().cloned().map(Ok);
+    write_structured_stream_to(writer, output, dialect, display, batches)
+}
+
+fn write_structured_stream_to<W: Write>(
+    writer: W,
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    display: &DisplayOptions,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<()> {
+    let target = match output {
+        StructuredOutputFormat::Json | StructuredOutputFormat::Jsonl => DisplayTarget::Json,
+        StructuredOutputFormat::Csv => DisplayTarget::Text,
+    };
+    let batches = batches
+        .into_iter()
+        .map(|batch| Ok(display.apply(&batch?, target)?));
+    match output {
+        StructuredOutputFormat::Json => json::write_json(writer, batches),
+        StructuredOutputFormat::Jsonl => json::write_jsonl(writer, batches),
+        StructuredOutputFormat::Csv => csv::write_batches(writer, batches, dialect),
+    }
+}
+
+/// Binary formats go to a terminal only when forced, since they would garble it.
+pub(crate) fn ensure_binary_stdout(format: &str, force: bool) -> Result<()> {
+    if !force && stdout_is_terminal() {
+        return Err(PqError::binary_to_terminal(format));
+    }
+    Ok(())
+}
+
+/// Prepend each row's index within its file, named `#` for tables and CSV and `_row`
+/// for JSON. `file` adds a column naming the source file, for combined structured output.
+pub fn number_rows(
+    batches: &[RecordBatch],
+    first_row: usize,
+    format: OutputFormat,
+    file: Option<&Path>,
+) -> Result<Vec<RecordBatch>> {
+    let file = file.map(display_path);
+    Ok(row_numbers::prepend(
+        batches,
+        first_row,
+        row_number_columns(format),
+        file.as_deref(),
+    )?)
+}
+
+/// Name for the `--with-filename` column: `file` for tables and CSV and `_file` for
+/// JSON, with a numeric suffix when `schema` already has a column by that name.
+pub fn source_file_column(format: OutputFormat, schema: &Schema) -> Result<String> {
+    let base = row_number_columns(format).file;
+    let taken = |name: &str| schema.fields().iter().any(|field| field.name() == name);
+    if !taken(base) {
+        return Ok(base.to_string());
+    }
+    let name = (1..)
+        .map(|suffix| format!("{base}_{suffix}"))
+        .find(|name| !taken(name))
+        .unwrap_or_else(|| base.to_string());
+    warning::warn(format!(
+        "a column named '{base}' already exists, so the source file is in '{name}'"
+    ))?;
+    Ok(name)
+}
+
+/// Prepend a column holding `file` on every row.
+pub fn prepend_source_file(
+    batches: &[RecordBatch],
+    column: &str,
+    file: &Path,
+) -> Result<Vec<RecordBatch>> {
+    Ok(row_numbers::prepend_file(
+        batches,
+        column,
+        &display_path(file),
+    )?)
+}
+
+fn row_number_columns(format: OutputFormat) -> row_numbers::RowNumberColumns {
+    match format {
+        OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Arrow | OutputFormat::Parquet => {
+            row_numbers::RowNumberColumns {
+                row: "_row",
+                file: "_file",
+            }
+        }
+        OutputFormat::Table | OutputFormat::Csv | OutputFormat::Tsv => {
+            row_numbers::RowNumberColumns {
+                row: "#",
+                file: "file",
+            }
+        }
+    }
+}
+
+fn render_display(
+    display: &DisplayOptions,
+    target: DisplayTarget,
+    batches: &[RecordBatch],
+) -> Result<Vec<RecordBatch>> {
+    batches
+        .iter()
+        .map(|batch| Ok(display.apply(batch, target)?))
+        .collect()
+}
+
+/// `extended` adds each column's ordinal, definition and repetition levels, and field ID.
+pub fn write_schema_table(no_header: bool, columns: &[ColumnInfo], extended: bool) -> Result<()> {
+    table::write_schema_table(stdout(), columns, no_header, extended)?;
+    Ok(())
+}
+
+pub fn write_schema_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    results: &[SchemaResult],
+    extended: bool,
+) -> Result<()> {
+    if let [result] = results {
+        return write_schema_result(output, dialect, result, extended);
+    }
+
+    match output {
+        StructuredOutputFormat::Json => {
+            let rows = schema_result_rows(results, extended);
+            json::write_value(stdout(), &rows)?;
+        }
+        StructuredOutputFormat::Jsonl => {
+            let rows = schema_result_rows(results, extended);
+            json::write_json_lines(stdout(), &rows)?;
+        }
+        StructuredOutputFormat::Csv => {
+            schema::write_csv_results(stdout(), results, dialect, extended)?
+        }
+    }
+    Ok(())
+}
+
+fn write_schema_result(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    result: &SchemaResult,
+    extended: bool,
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => {
+            let rows = schema_rows(&result.columns, extended);
+            json::write_value(stdout(), &rows)?;
+        }
+        StructuredOutputFormat::Jsonl => {
+            let rows = schema_rows(&result.columns, extended);
+            json::write_json_lines(stdout(), &rows)?;
+        }
+        StructuredOutputFormat::Csv => {
+            schema::write_csv(stdout(), &result.columns, dialect, extended)?
+        }
+    }
+    Ok(())
+}
+
+pub fn write_unified_schema_table(no_header: bool, unified: &UnifiedSchema) -> Result<()> {
+    schema::write_unified_table(stdout(), unified, no_header)?;
+    Ok(())
+}
+
+/// JSON is one document holding the file list and every column; JSONL has a line per
+/// column.
+pub fn write_unified_schema(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    unified: &UnifiedSchema,
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => {
+            let document = UnifiedSchemaJson {
+                files: unified.files.iter().map(display_path).collect(),
+                compatible: unified.is_compatible(),
+                columns: unified_column_rows(&unified.columns),
+            };
+            json::write_value(stdout(), &document)?;
+        }
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &unified_column_rows(&unified.columns))?;
+        }
+        StructuredOutputFormat::Csv => schema::write_unified_csv(stdout(), unified, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_stats_diff_table(no_header: bool, diff: &StatsDiff) -> Result<()> {
+    stats_diff::write_table(stdout(), diff, no_header)
+}
+
+/// JSON is one document with every comparison, changed or not; JSONL and CSV have a
+/// row per statistic.
+pub fn write_stats_diff(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    diff: &StatsDiff,
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => {
+            let document = StatsDiffJson {
+                old: display_path(&diff.old),
+                new: display_path(&diff.new),
+                tolerance: diff.tolerance.map(|tolerance| tolerance.to_string()),
+                exceeding: diff.exceeding(),
+                row_count: (&diff.row_count).into(),
+                columns: diff
+                    .columns
+                    .iter()
+                    .map(|column| ColumnStatsDiffJson {
+                        column: column.column.clone(),
+                        display_type: column.display_type(),
+                        null_count: (&column.null_count).into(),
+                        min: (&column.min).into(),
+                        max: (&column.max).into(),
+                    })
+                    .collect(),
+                only_in_old: diff.only_in_old.clone(),
+                only_in_new: diff.only_in_new.clone(),
+            };
+            json::write_value(stdout(), &document)?;
+        }
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &stats_diff::rows(diff))?;
+        }
+        StructuredOutputFormat::Csv => stats_diff::write_csv(stdout(), diff, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_ddl(ddl: &str) -> Result<()> {
+    writeln!(stdout(), "{ddl}")?;
+    Ok(())
+}
+
+pub fn write_stats_table(
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+    value_width: Option<usize>,
+    result: &StatsResult,
+) -> Result<()> {
+    stats::write_table(stdout(), result, no_header, binary, value_width)?;
+    Ok(())
+}
+
+pub fn write_stats_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+    results: &[StatsResult],
+) -> Result<()> {
+    if let [result] = results {
+        return write_stats_result(output, dialect, binary, result);
+    }
+
+    match output {
+        StructuredOutputFormat::Json => {
+            json::write_value(stdout(), &stats_result_rows(results, binary))?
+        }
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &stats_result_rows(results, binary))?;
+        }
+        StructuredOutputFormat::Csv => {
+            stats::write_csv_results(stdout(), results, dialect, binary)?
+        }
+    }
+    Ok(())
+}
+
+pub fn write_row_group_stats_table(
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+    value_width: Option<usize>,
+    rows: &[RowGroupStats],
+) -> Result<()> {
+    stats::write_row_group_table(stdout(), rows, no_header, binary, value_width)?;
+    Ok(())
+}
+
+pub fn write_row_group_stats_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+    results: &[RowGroupStatsResult],
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => {
+            json::write_value(stdout(), &row_group_stats_rows(results, binary))?
+        }
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &row_group_stats_rows(results, binary))?;
+        }
+        StructuredOutputFormat::Csv => {
+            stats::write_row_group_csv(stdout(), results, dialect, binary)?
+        }
+    }
+    Ok(())
+}
+
+fn write_stats_result(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+    result: &StatsResult,
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &stats_rows(result, binary))?,
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &stats_rows(result, binary))?;
+        }
+        StructuredOutputFormat::Csv => stats::write_csv(stdout(), result, dialect, binary)?,
+    }
+    Ok(())
+}
+
+pub fn write_histogram(
+    output: OutputFormat,
+    no_header: bool,
+    quiet: bool,
+    dialect: &CsvDialect,
+    histogram: &Histogram,
+) -> Result<()> {
+    match output.structured() {
+        None => hist::write_table(stdout(), histogram, no_header, quiet)?,
+        Some(StructuredOutputFormat::Json) => {
+            json::write_value(stdout(), &histogram_json(histogram))?;
+        }
+        Some(StructuredOutputFormat::Jsonl) => {
+            json::write_json_lines(stdout(), &[histogram_json(histogram)])?;
+        }
+        Some(StructuredOutputFormat::Csv) => {
+            hist::write_csv(stdout(), histogram, dialect)?;
+        }
+    }
+    Ok(())
+}
+
+/// Each value on its own line as it is read, for `pq extract`.
+pub fn write_value_lines(
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+    lines: &ValueLines,
+    display: &DisplayOptions,
+) -> Result<()> {
+    let writer = io::BufWriter::new(stdout());
+    lines::write_lines(writer, batches, lines, display)
+}
+
+pub fn write_bench_table(no_header: bool, result: &BenchResult) -> Result<()> {
+    bench::write_table(stdout(), result, no_header)
+}
+
+pub fn write_bench_result(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    result: &BenchResult,
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &bench_json(result))?,
+        StructuredOutputFormat::Jsonl => json::write_json_lines(stdout(), &[bench_json(result)])?,
+        StructuredOutputFormat::Csv => bench::write_csv(stdout(), result, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_checksum_table(no_header: bool, result: &ChecksumResult) -> Result<()> {
+    checksum::write_table(stdout(), result, no_header)?;
+    Ok(())
+}
+
+pub fn write_checksum_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    results: &[ChecksumResult],
+) -> Result<()> {
+    let objects = || results.iter().map(checksum_json).collect::<Vec<_>>();
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &objects())?,
+        StructuredOutputFormat::Jsonl => json::write_json_lines(stdout(), &objects())?,
+        StructuredOutputFormat::Csv => checksum::write_csv(stdout(), results, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_config_table(no_header: bool, settings: &[ConfigSetting]) -> Result<()> {
+    config::write_table(stdout(), settings, no_header)?;
+    Ok(())
+}
+
+pub fn write_config_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    settings: &[ConfigSetting],
+) -> Result<()> {
+    let rows: Vec<ConfigJsonRow> = settings
+        .iter()
+        .map(|setting| ConfigJsonRow {
+            setting: setting.key.clone(),
+            value: setting.value.clone(),
+            source: setting.source.to_string(),
+        })
+        .collect();
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &rows)?,
+        StructuredOutputFormat::Jsonl => json::write_json_lines(stdout(), &rows)?,
+        StructuredOutputFormat::Csv => config::write_csv(stdout(), settings, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_nulls_table(no_header: bool, result: &NullsResult) -> Result<()> {
+    nulls::write_table(stdout(), result, no_header)?;
+    Ok(())
+}
+
+pub fn write_nulls_results(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    results: &[NullsResult],
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &nulls_rows(results))?,
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &nulls_rows(results))?;
+        }
+        StructuredOutputFormat::Csv => nulls::write_csv(stdout(), results, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_file_info(no_header: bool, numbers: InfoNumbers, row: &FileInfo) -> Result<()> {
+    info::write_table(stdout(), std::slice::from_ref(row), no_header, numbers)
+}
+
+pub fn write_file_infos(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    rows: &[FileInfo],
+) -> Result<()> {
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &file_info_rows(rows))?,
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &file_info_rows(rows))?;
+        }
+        StructuredOutputFormat::Csv => info::write_csv(stdout(), rows, dialect)?,
+    }
+    Ok(())
+}
+
+pub fn write_description_table(
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+    description: &FileDescription,
+) -> Result<()> {
+    describe::write_table(stdout(), description, no_header, binary)
+}
+
+pub fn write_descriptions(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+    descriptions: &[FileDescription],
+) -> Result<()> {
+    let documents = || {
+        descriptions
+            .iter()
+            .map(|description| describe_document(description, binary))
+    };
+    match output {
+        StructuredOutputFormat::Json => {
+            let mut documents: Vec<_> = documents().collect();
+            if documents.len() == 1 {
+                json::write_value(stdout(), &documents.remove(0))?;
+            } else {
+                json::write_value(stdout(), &documents)?;
+            }
+        }
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &documents().collect::<Vec<_>>())?;
+        }
+        StructuredOutputFormat::Csv => {
+            describe::write_csv(stdout(), descriptions, dialect, binary)?;
+        }
+    }
+    Ok(())
 }
 
-pub fn write_table_batches(quiet: bool, batches: &[RecordBatch]) -> Result<()> {
-    table::write_batches(io::stdout().lock(), batches, quiet)?;
+pub fn write_column_chunks_table(
+    no_header: bool,
+    numbers: InfoNumbers,
+    columns: &[ColumnChunkSummary],
+) -> Result<()> {
+    info::write_columns_table(stdout(), columns, no_header, numbers)?;
     Ok(())
 }
 
-pub fn write_structured_batches(
+pub fn write_column_chunks(
     output: StructuredOutputFormat,
-    quiet: bool,
-    batches: &[RecordBatch],
+    dialect: &CsvDialect,
+    results: &[ColumnChunksResult],
 ) -> Result<()> {
     match output {
-        StructuredOutputFormat::Json => json::write_json(io::stdout().lock(), batches)?,
-        StructuredOutputFormat::Jsonl => json::write_jsonl(io::stdout().lock(), batches)?,
-        StructuredOutputFormat::Csv => csv::write_batches(io::stdout().lock(), batches, !quiet)?,
+        StructuredOutputFormat::Json => json::write_value(stdout(), &column_chunk_rows(results))?,
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &column_chunk_rows(results))?;
+        }
+        StructuredOutputFormat::Csv => info::write_columns_csv(stdout(), results, dialect)?,
     }
     Ok(())
 }
 
-pub fn write_schema_table(quiet: bool, columns: &[ColumnInfo]) -> Result<()> {
-    table::write_schema_table(io::stdout().lock(), columns, quiet)?;
+pub fn write_row_groups_table(
+    no_header: bool,
+    numbers: InfoNumbers,
+    row_groups: &[RowGroupSummary],
+) -> Result<()> {
+    info::write_row_groups_table(stdout(), row_groups, no_header, numbers)?;
     Ok(())
 }
 
-pub fn write_schema_results(
+pub fn write_row_groups(
     output: StructuredOutputFormat,
-    quiet: bool,
-    results: &[SchemaResult],
+    dialect: &CsvDialect,
+    results: &[RowGroupsResult],
 ) -> Result<()> {
-    if let [result] = results {
-        return write_schema_result(output, quiet, result);
+    match output {
+        StructuredOutputFormat::Json => json::write_value(stdout(), &row_group_rows(results))?,
+        StructuredOutputFormat::Jsonl => {
+            json::write_json_lines(stdout(), &row_group_rows(results))?;
+        }
+        StructuredOutputFormat::Csv => info::write_row_groups_csv(stdout(), results, dialect)?,
     }
+    Ok(())
+}
 
+pub fn write_key_value_metadata_table(no_header: bool, entries: &[KeyValueEntry]) -> Result<()> {
+    info::write_key_value_metadata_table(stdout(), entries, no_header)?;
+    Ok(())
+}
+
+pub fn write_key_value_metadata(
+    output: StructuredOutputFormat,
+    dialect: &CsvDialect,
+    results: &[KeyValueMetadataResult],
+) -> Result<()> {
     match output {
-        StructuredOutputFormat::Json => {
-            json::write_value(io::stdout().lock(), &schema_result_rows(results))?;
-        }
+        StructuredOutputFormat::Json => json::write_value(stdout(), &key_value_rows(results))?,
         StructuredOutputFormat::Jsonl => {
-            json::write_json_lines(io::stdout().lock(), &schema_result_rows(results))?;
+            json::write_json_lines(stdout(), &key_value_rows(results))?;
         }
         StructuredOutputFormat::Csv => {
-            schema::write_csv_results(io::stdout().lock(), results, !quiet)?
+            info::write_key_value_metadata_csv(stdout(), results, dialect)?
         }
     }
     Ok(())
 }
 
-fn write_schema_result(
+pub fn write_page_index_table(no_header: bool, result: &PageIndexResult) -> Result<()> {
+    pages::write_table(stdout(), result, no_header)?;
+    Ok(())
+}
+
+pub fn write_page_indexes(
     output: StructuredOutputFormat,
-    quiet: bool,
-    result: &SchemaResult,
+    dialect: &CsvDialect,
+    results: &[PageIndexResult],
 ) -> Result<()> {
     match output {
         StructuredOutputFormat::Json => {
-            json::write_value(io::stdout().lock(), &schema_rows(&result.columns))?;
+            let mut reports: Vec<_> = results.iter().map(page_index_report_json).collect();
+            if reports.len() == 1 {
+                json::write_value(stdout(), &reports.remove(0))?;
+            } else {
+                json::write_value(stdout(), &reports)?;
+            }
         }
         StructuredOutputFormat::Jsonl => {
-            json::write_json_lines(io::stdout().lock(), &schema_rows(&result.columns))?;
+            let rows: Vec<_> =
+                results
+                    .iter()
+                    .flat_map(|result| {
+                        result.row_groups.iter().flat_map(move |row_group| {
+                            row_group.columns.iter().flat_map(move |column| {
+                                column.pages.iter().enumerate().map(move |(index, page)| {
+                                    PageJsonRow {
+                                        file: Some(display_path(&result.path)),
+                                        row_group: Some(row_group.index),
+                                        column: Some(column.column.clone()),
+                                        ..page_json(column, index, page)
+                                    }
+                                })
+                            })
+                        })
+                    })
+                    .collect();
+            json::write_json_lines(stdout(), &rows)?;
         }
         StructuredOutputFormat::Csv => {
-            schema::write_csv(io::stdout().lock(), &result.columns, !quiet)?
+            pages::write_csv(stdout(), results, results.len() > 1, dialect)?;
         }
     }
     Ok(())
 }
 
-pub fn write_stats_table(quiet: bool, rows: &[ColumnStats]) -> Result<()> {
-    stats::write_table(io::stdout().lock(), rows, quiet)?;
+pub fn write_counts(quiet: bool, is_multi_source: bool, counts: &CountResult) -> Result<()> {
+    let mut writer = stdout();
+
+    for entry in &counts.entries {
+        if quiet || !is_multi_source {
+            writeln!(writer, "{}", entry.rows)?;
+        } else {
+            writeln!(writer, "{}: {}", display_path(&entry.path), entry.rows)?;
+        }
+    }
+
+    if is_multi_source && !quiet {
+        writeln!(writer, "Total: {}", counts.total_rows)?;
+    }
+
     Ok(())
 }
 
-pub fn write_stats_results(
-    output: StructuredOutputFormat,
+/// Each input's share of a merge on stderr, unless `quiet`, and a warning when the
+/// output holds no rows at all.
+pub fn write_merge_summary(
+    output: &Path,
+    counts: &CountResult,
+    output_rows: i64,
     quiet: bool,
-    results: &[StatsResult],
 ) -> Result<()> {
-    if let [result] = results {
-        return write_stats_result(output, quiet, result);
+    let mut writer = io::stderr().lock();
+    if !quiet {
+        for entry in &counts.entries {
+            let note = if entry.rows == 0 { " (empty)" } else { "" };
+            writeln!(
+                writer,
+                "{}: {} rows{note}",
+                display_path(&entry.path),
+                entry.rows
+            )?;
+        }
+    }
+    drop(writer);
+    if output_rows == 0 {
+        warning::warn(format!(
+            "{} has no rows; every input was empty",
+            display_path(output)
+        ))?;
     }
+    Ok(())
+}
 
-    match output {
-        StructuredOutputFormat::Json => {
-            json::write_value(io::stdout().lock(), &stats_result_rows(results))?
+/// Each stratum's sampled and total rows on stderr, in the order the values were first read.
+pub fn write_strata_summary(column: &str, strata: &[Stratum]) -> Result<()> {
+    let mut writer = io::stderr().lock();
+    for stratum in strata {
+        let value = escape_control_chars(&stratum.value);
+        writeln!(
+            writer,
+            "{column}={value}: {} of {} rows",
+            stratum.sampled, stratum.rows
+        )?;
+    }
+    Ok(())
+}
+
+/// One line per converted file on stderr, each failure with its error indented below,
+/// then how many of `total` files were converted.
+pub fn write_convert_summary(
+    outcomes: &[(&Path, &Path, Option<&PqError>)],
+    total: usize,
+) -> Result<()> {
+    let mut writer = io::stderr().lock();
+    for (input, output, error) in outcomes {
+        match error {
+            None => writeln!(
+                writer,
+                "{} -> {}",
+                display_path(input),
+                display_path(output)
+            )?,
+            Some(error) => {
+                writeln!(writer, "{}: failed", display_path(input))?;
+                for line in error.to_string().lines() {
+                    writeln!(writer, "  {}", line.trim_start())?;
+                }
+            }
         }
-        StructuredOutputFormat::Jsonl => {
-            json::write_json_lines(io::stdout().lock(), &stats_result_rows(results))?;
+    }
+    let converted = outcomes
+        .iter()
+        .filter(|(_, _, error)| error.is_none())
+        .count();
+    writeln!(writer, "Converted {converted} of {total} files")?;
+    Ok(())
+}
+
+pub fn write_group_counts(
+    output: OutputFormat,
+    no_header: bool,
+    dialect: &CsvDialect,
+    result: &GroupCountResult,
+) -> Result<()> {
+    match output.structured() {
+        None => groups::write_table(stdout(), result, no_header)?,
+        Some(StructuredOutputFormat::Json) => {
+            json::write_value(stdout(), &group_count_rows(result))?;
         }
-        StructuredOutputFormat::Csv => {
-            stats::write_csv_results(io::stdout().lock(), results, !quiet)?
+        Some(StructuredOutputFormat::Jsonl) => {
+            json::write_json_lines(stdout(), &group_count_rows(result))?;
+        }
+        Some(StructuredOutputFormat::Csv) => {
+            groups::write_csv(stdout(), result, dialect)?;
         }
     }
     Ok(())
 }
 
-fn write_stats_result(
-    output: StructuredOutputFormat,
-    quiet: bool,
-    result: &StatsResult,
+pub fn write_exists_results(
+    output: OutputFormat,
+    no_header: bool,
+    dialect: &CsvDialect,
+    results: &[ExistsResult],
 ) -> Result<()> {
-    match output {
-        StructuredOutputFormat::Json => {
-            json::write_value(io::stdout().lock(), &stats_rows(&result.rows))?
+    match output.structured() {
+        None => exists::write_table(stdout(), results, no_header)?,
+        Some(StructuredOutputFormat::Json) => {
+            json::write_value(stdout(), &exists_rows(results))?;
         }
-        StructuredOutputFormat::Jsonl => {
-            json::write_json_lines(io::stdout().lock(), &stats_rows(&result.rows))?;
+        Some(StructuredOutputFormat::Jsonl) => {
+            json::write_json_lines(stdout(), &exists_rows(results))?;
+        }
+        Some(StructuredOutputFormat::Csv) => {
+            exists::write_csv(stdout(), results, dialect)?;
         }
-        StructuredOutputFormat::Csv => stats::write_csv(io::stdout().lock(), &result.rows, !quiet)?,
     }
     Ok(())
 }
 
-pub fn write_file_info(quiet: bool, row: &FileInfo) -> Result<()> {
-    info::write_table(io::stdout().lock(), std::slice::from_ref(row), quiet)
+/// One stderr line per file saying how many row groups a `--where` skipped.
+pub fn write_pruning_summary(reports: &[PruneReport]) -> Result<()> {
+    let mut writer = io::stderr().lock();
+    for report in reports {
+        writeln!(
+            writer,
+            "{}: skipped {} of {} row groups ({} of {} rows)",
+            display_path(&report.path),
+            report.row_groups_skipped(),
+            report.row_groups.len(),
+            report.rows_skipped(),
+            report.rows_read() + report.rows_skipped()
+        )?;
+    }
+    Ok(())
+}
+
+/// Rows each `--where` count took from statistics and rows it decoded, on stderr.
+pub fn write_match_sources(matches: &[MatchCount]) -> Result<()> {
+    let mut writer = io::stderr().lock();
+    for count in matches {
+        writeln!(
+            writer,
+            "{}: {} rows counted from metadata, {} rows scanned in {} of {} row groups",
+            display_path(&count.path),
+            count.metadata_rows,
+            count.scanned_rows,
+            count.row_groups_scanned,
+            count.row_groups
+        )?;
+    }
+    Ok(())
+}
+
+pub fn write_prune_table(no_header: bool, report: &PruneReport) -> Result<()> {
+    prune::write_table(stdout(), report, no_header)
 }
 
-pub fn write_file_infos(
+pub fn write_prune_reports(
     output: StructuredOutputFormat,
-    quiet: bool,
-    rows: &[FileInfo],
+    dialect: &CsvDialect,
+    reports: &[PruneReport],
 ) -> Result<()> {
     match output {
         StructuredOutputFormat::Json => {
-            json::write_value(io::stdout().lock(), &file_info_rows(rows))?
+            let mut rows: Vec<_> = reports.iter().map(prune_report_json).collect();
+            if rows.len() == 1 {
+                json::write_value(stdout(), &rows.remove(0))?;
+            } else {
+                json::write_value(stdout(), &rows)?;
+            }
         }
         StructuredOutputFormat::Jsonl => {
-            json::write_json_lines(io::stdout().lock(), &file_info_rows(rows))?;
+            let rows: Vec<_> = reports
+                .iter()
+                .flat_map(|report| {
+                    report
+                        .row_groups
+                        .iter()
+                        .map(|group| prune_row_group_json(Some(report.path.as_path()), group))
+                })
+                .collect();
+            json::write_json_lines(stdout(), &rows)?;
         }
-        StructuredOutputFormat::Csv => info::write_csv(io::stdout().lock(), rows, !quiet)?,
-    }
-    Ok(())
-}
-
-pub fn write_counts(quiet: bool, is_multi_source: bool, counts: &CountResult) -> Result<()> {
-    let mut writer = io::stdout().lock();
-
-    for entry in &counts.entries {
-        if quiet || !is_multi_source {
-            writeln!(writer, "{}", entry.rows)?;
-        } else {
-            writeln!(writer, "{}: {}", entry.path.display(), entry.rows)?;
+        StructuredOutputFormat::Csv => {
+            prune::write_csv(stdout(), reports, reports.len() > 1, dialect)?;
         }
     }
-
-    if is_multi_source && !quiet {
-        writeln!(writer, "Total: {}", counts.total_rows)?;
-    }
-
     Ok(())
 }
 
 pub(crate) struct BatchFileWriter {
     path: PathBuf,
     inner: BatchFileWriterKind,
+    display: DisplayOptions,
+    /// Text formats render values for display; Arrow keeps them as they are.
+    display_target: Option<DisplayTarget>,
 }
 
 enum BatchFileWriterKind {
     Csv(Box<csv::BatchFileWriter>),
     Json(json::JsonBatchFileWriter),
     Jsonl(json::JsonlBatchFileWriter),
+    Arrow(Box<ipc::BatchFileWriter>),
+    Parquet(Box<parquet::BatchFileWriter>),
 }
 
 impl BatchFileWriter {
-    pub fn create_at(write_path: &Path, error_path: &Path) -> Result<Self> {
-        let inner = match file_output_format(error_path)? {
-            FileOutputFormat::Csv => BatchFileWriterKind::Csv(Box::new(
-                csv::BatchFileWriter::create(write_path)
-                    .map_err(|error| PqError::write_error(error_path, error))?,
-            )),
-            FileOutputFormat::Json => BatchFileWriterKind::Json(
-                json::JsonBatchFileWriter::create(write_path)
-                    .map_err(|error| PqError::write_error(error_path, error))?,
-            ),
-            FileOutputFormat::Jsonl => BatchFileWriterKind::Jsonl(
-                json::JsonlBatchFileWriter::create(write_path)
-                    .map_err(|error| PqError::write_error(error_path, error))?,
-            ),
+    /// `schema` is the schema of the batches to come, which Arrow output writes up front.
+    pub fn create_at(
+        write_path: &Path,
+        error_path: &Path,
+        output: FileOutput,
+        schema: &Schema,
+    ) -> Result<Self> {
+        let file =
+            File::create(write_path).map_err(|error| PqError::write_error(error_path, error))?;
+        Self::new(Box::new(file), error_path, output, schema, false)
+    }
+
+    /// Stream batches to stdout as they arrive. The Parquet writer needs a `Send` sink,
+    /// so this holds the handle rather than a lock; the sink buffers either way.
+    pub fn stdout(output: FileOutput, schema: &Schema) -> Result<Self> {
+        let stdout = Box::new(stdout());
+        Self::new(stdout, Path::new("(stdout)"), output, schema, true)
+    }
+
+    fn new(
+        writer: Box<dyn Write + Send>,
+        path: &Path,
+        output: FileOutput,
+        schema: &Schema,
+        streaming: bool,
+    ) -> Result<Self> {
+        let sink = Sink::new(writer, output.compression)
+            .map_err(|error| PqError::write_error(path, error))?;
+        let display_target = match output.format {
+            FileOutputFormat::Csv | FileOutputFormat::Tsv => Some(DisplayTarget::Text),
+            FileOutputFormat::Json | FileOutputFormat::Jsonl => Some(DisplayTarget::Json),
+            FileOutputFormat::Arrow | FileOutputFormat::Parquet => None,
+        };
+        let inner = match output.format {
+            FileOutputFormat::Csv => {
+                BatchFileWriterKind::Csv(Box::new(csv::BatchFileWriter::new(sink, &output.csv)))
+            }
+            FileOutputFormat::Tsv => BatchFileWriterKind::Csv(Box::new(csv::BatchFileWriter::new(
+                sink,
+                &output.csv.clone().tab_separated(),
+            ))),
+            FileOutputFormat::Json => {
+                BatchFileWriterKind::Json(json::JsonBatchFileWriter::new(sink))
+            }
+            FileOutputFormat::Jsonl => {
+                BatchFileWriterKind::Jsonl(json::JsonlBatchFileWriter::new(sink))
+            }
+            FileOutputFormat::Arrow => {
+                let writer = if streaming {
+                    ipc::BatchFileWriter::stream(sink, schema)
+                } else {
+                    ipc::BatchFileWriter::file(sink, schema)
+                };
+                let writer = writer.map_err(|error| PqError::write_error(path, error))?;
+                BatchFileWriterKind::Arrow(Box::new(writer))
+            }
+            FileOutputFormat::Parquet => {
+                let properties = output.parquet.clone().unwrap_or_default();
+                let writer = parquet::BatchFileWriter::new(sink, schema, properties)
+                    .map_err(|error| PqError::write_error(path, error))?;
+                BatchFileWriterKind::Parquet(Box::new(writer))
+            }
         };
         Ok(Self {
-            path: error_path.to_path_buf(),
+            path: path.to_path_buf(),
             inner,
+            display: output.display,
+            display_target,
         })
     }
 
     pub fn write(&mut self, batch: &RecordBatch) -> Result<()> {
+        let batch = match self.display_target {
+            Some(target) => self.display.apply(batch, target)?,
+            None => batch.clone(),
+        };
         match &mut self.inner {
-            BatchFileWriterKind::Csv(writer) => writer.write(batch),
-            BatchFileWriterKind::Json(writer) => writer.write(batch),
-            BatchFileWriterKind::Jsonl(writer) => writer.write(batch),
+            BatchFileWriterKind::Csv(writer) => writer.write(&batch),
+            BatchFileWriterKind::Json(writer) => writer.write(&batch),
+            BatchFileWriterKind::Jsonl(writer) => writer.write(&batch),
+            BatchFileWriterKind::Arrow(writer) => writer.write(&batch),
+            BatchFileWriterKind::Parquet(writer) => writer.write(&batch),
         }
         .map_err(|error| PqError::write_error(&self.path, error))
     }
 
-    pub fn finish(mut self) -> Result<()> {
-        match &mut self.inner {
-            BatchFileWriterKind::Csv(writer) => {
-                writer.finish();
-                Ok(())
-            }
+    pub fn finish(self) -> Result<()> {
+        match self.inner {
+            BatchFileWriterKind::Csv(writer) => writer.finish(),
             BatchFileWriterKind::Json(writer) => writer.finish(),
             BatchFileWriterKind::Jsonl(writer) => writer.finish(),
+            BatchFileWriterKind::Arrow(writer) => writer.finish(),
+            BatchFileWriterKind::Parquet(writer) => writer.finish(),
         }
         .map_err(|error| PqError::write_error(&self.path, error))
     }
 }
 
+/// An output path of `-` means stdout.
+pub(crate) fn is_stdout_path(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Format, CSV dialect, value display, and optional compression for `convert` output.
+#[derive(Clone, Debug)]
+pub(crate) struct FileOutput {
+    pub format: FileOutputFormat,
+    pub csv: CsvDialect,
+    pub display: DisplayOptions,
+    pub compression: Option<TextCompression>,
+    /// Parquet writer settings, which need the output schema and so are filled in just
+    /// before writing
+    pub parquet: Option<WriterProperties>,
+}
+
+const FILE_OUTPUT_FORMATS: &str = "csv, tsv, json, jsonl, arrow, parquet";
+
+/// Use the requested format and compression when given, otherwise infer them from the
+/// output extension (`data.csv.gz`). Requested settings that disagree with the extension
+/// win, with a warning.
+pub(crate) fn resolve_file_output(
+    path: &Path,
+    requested_format: Option<FileOutputFormat>,
+    requested_compression: Option<TextCompression>,
+    csv: CsvDialect,
+    display: DisplayOptions,
+) -> Result<FileOutput> {
+    if is_stdout_path(path) {
+        let format = requested_format.ok_or_else(|| PqError::UnsupportedFormat {
+            format: "(stdout)".to_string(),
+            supported: format!("{FILE_OUTPUT_FORMATS} (choose one with --format)"),
+        })?;
+        check_parquet_compression(path, format, requested_compression)?;
+        return Ok(FileOutput {
+            format,
+            csv,
+            display,
+            compression: requested_compression,
+            parquet: None,
+        });
+    }
+
+    let inferred_compression = TextCompression::from_path(path);
+    let compression = match (requested_compression, inferred_compression) {
+        (Some(requested), Some(inferred)) => {
+            if requested.name() != inferred.name() {
+                warn_extension_mismatch(path, requested.name())?;
+            }
+            Some(requested)
+        }
+        (requested, inferred) => requested.or(inferred),
+    };
+
+    let format_path = if inferred_compression.is_some() {
+        path.with_extension("")
+    } else {
+        path.to_path_buf()
+    };
+    let format = match (requested_format, file_output_format(&format_path)) {
+        (Some(requested), Ok(inferred)) => {
+            if requested != inferred {
+                warn_extension_mismatch(path, requested.as_str())?;
+            }
+            requested
+        }
+        (Some(requested), Err(_)) => requested,
+        (None, inferred) => inferred?,
+    };
+    check_parquet_compression(path, format, compression)?;
+
+    Ok(FileOutput {
+        format,
+        csv,
+        display,
+        compression,
+        parquet: None,
+    })
+}
+
+/// Parquet compresses column by column, so a whole-file codec on top is refused.
+fn check_parquet_compression(
+    path: &Path,
+    format: FileOutputFormat,
+    compression: Option<TextCompression>,
+) -> Result<()> {
+    match compression {
+        Some(compression) if format == FileOutputFormat::Parquet => Err(PqError::write_error(
+            path,
+            format!(
+                "parquet output cannot be {} compressed as a whole; use --compression to pick its column codec",
+                compression.name()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn warn_extension_mismatch(path: &Path, requested: &str) -> Result<()> {
+    warning::warn(format!(
+        "writing {requested} to {} despite its extension",
+        display_path(path)
+    ))
+}
+
 fn file_output_format(path: &Path) -> Result<FileOutputFormat> {
     match path
         .extension()
@@ -303,41 +1588,75 @@ fn file_output_format(path: &Path) -> Result<FileOutputFormat> {
         .as_deref()
     {
         Some("csv") => Ok(FileOutputFormat::Csv),
+        Some("tsv") => Ok(FileOutputFormat::Tsv),
         Some("json") => Ok(FileOutputFormat::Json),
         Some("jsonl") => Ok(FileOutputFormat::Jsonl),
+        Some("arrow" | "arrows" | "feather" | "ipc") => Ok(FileOutputFormat::Arrow),
+        Some("parquet") => Ok(FileOutputFormat::Parquet),
         Some(format) => Err(PqError::UnsupportedFormat {
             format: format.to_string(),
-            supported: "csv, json, jsonl".to_string(),
+            supported: format!("{FILE_OUTPUT_FORMATS} (or choose one with --format)"),
         }),
         None => Err(PqError::UnsupportedFormat {
             format: "(no extension)".to_string(),
-            supported: "csv, json, jsonl".to_string(),
+            supported: format!("{FILE_OUTPUT_FORMATS} (or choose one with --format)"),
         }),
     }
 }
 
-fn schema_rows(columns: &[ColumnInfo]) -> Vec<SchemaJsonRow> {
+fn schema_rows(columns: &[ColumnInfo], extended: bool) -> Vec<SchemaJsonRow> {
     columns
         .iter()
-        .map(|column| schema_row(None, column))
+        .map(|column| schema_row(None, column, extended))
         .collect()
 }
 
-fn schema_result_rows(results: &[SchemaResult]) -> Vec<SchemaJsonRow> {
+fn schema_result_rows(results: &[SchemaResult], extended: bool) -> Vec<SchemaJsonRow> {
     results
         .iter()
         .flat_map(|result| {
             result
                 .columns
                 .iter()
-                .map(|column| schema_row(Some(result.path.as_path()), column))
+                .map(|column| schema_row(Some(result.path.as_path()), column, extended))
+        })
+        .collect()
+}
+
+fn unified_column_rows(columns: &[UnifiedColumn]) -> Vec<UnifiedColumnJsonRow> {
+    columns
+        .iter()
+        .map(|column| UnifiedColumnJsonRow {
+            name: column.name.clone(),
+            display_type: column.display_type(),
+            nullable: column.nullable,
+            physical_type: column.column_type.physical.to_string(),
+            logical_type: column
+                .column_type
+                .logical
+                .as_ref()
+                .map(|logical| logical.display_name()),
+            files_missing: column.files_missing.iter().map(display_path).collect(),
+            type_conflicts: column
+                .type_conflicts
+                .iter()
+                .map(type_conflict_json)
+                .collect(),
         })
         .collect()
 }
 
-fn schema_row(file: Option<&Path>, column: &ColumnInfo) -> SchemaJsonRow {
+fn type_conflict_json(conflict: &TypeConflict) -> TypeConflictJson {
+    TypeConflictJson {
+        display_type: conflict.display_type(),
+        nullable: conflict.nullable,
+        files: conflict.files.iter().map(display_path).collect(),
+    }
+}
+
+fn schema_row(file: Option<&Path>, column: &ColumnInfo, extended: bool) -> SchemaJsonRow {
     SchemaJsonRow {
-        file: file.map(|path| path.display().to_string()),
+        file: file.map(display_path),
         name: column.name.clone(),
         display_type: column.display_type(),
         nullable: column.nullable,
@@ -347,86 +1666,493 @@ fn schema_row(file: Option<&Path>, column: &ColumnInfo) -> SchemaJsonRow {
             .logical
             .as_ref()
             .map(|logical| logical.display_name()),
+        extended: extended.then_some(SchemaExtendedJson {
+            ordinal: column.ordinal,
+            max_definition_level: column.max_def_level,
+            max_repetition_level: column.max_rep_level,
+            field_id: column.field_id,
+        }),
     }
 }
 
-fn stats_rows(rows: &[ColumnStats]) -> Vec<StatsJsonRow> {
-    rows.iter().map(|row| stats_row(None, row)).collect()
+fn stats_rows(result: &StatsResult, binary: Option<BinaryEncoding>) -> Vec<StatsJsonRow> {
+    result
+        .rows
+        .iter()
+        .map(|row| stats_row(None, row, result.compressed_percent(row), binary))
+        .collect()
 }
 
-fn stats_result_rows(results: &[StatsResult]) -> Vec<StatsJsonRow> {
+fn stats_result_rows(results: &[StatsResult], binary: Option<BinaryEncoding>) -> Vec<StatsJsonRow> {
     results
         .iter()
         .flat_map(|result| {
-            result
-                .rows
-                .iter()
-                .map(|row| stats_row(Some(result.path.as_path()), row))
+            result.rows.iter().map(|row| {
+                let percent = result.compressed_percent(row);
+                stats_row(Some(result.path.as_path()), row, percent, binary)
+            })
         })
         .collect()
 }
 
-fn stats_row(file: Option<&Path>, row: &ColumnStats) -> StatsJsonRow {
+fn stats_row(
+    file: Option<&Path>,
+    row: &ColumnStats,
+    compressed_percent: f64,
+    binary: Option<BinaryEncoding>,
+) -> StatsJsonRow {
     StatsJsonRow {
-        file: file.map(|path| path.display().to_string()),
+        file: file.map(display_path),
         column: row.column.clone(),
         display_type: row.display_type(),
         null_count: row.null_count,
         min: row
             .min
             .as_ref()
-            .map(|value| stat_value_json(value, row.column_type.logical.as_ref())),
+            .map(|value| stat_value_json(value, row.column_type.logical.as_ref(), binary)),
         max: row
             .max
             .as_ref()
-            .map(|value| stat_value_json(value, row.column_type.logical.as_ref())),
+            .map(|value| stat_value_json(value, row.column_type.logical.as_ref(), binary)),
         physical_type: row.column_type.physical.to_string(),
         logical_type: row
             .column_type
             .logical
             .as_ref()
             .map(|logical| logical.display_name()),
+        compressed_bytes: row.compressed_bytes,
+        uncompressed_bytes: row.uncompressed_bytes,
+        compressed_percent,
     }
 }
 
+/// Rows carry a `file` key only when there are several files.
+fn row_group_stats_rows(
+    results: &[RowGroupStatsResult],
+    binary: Option<BinaryEncoding>,
+) -> Vec<RowGroupStatsJsonRow> {
+    let with_file = results.len() > 1;
+    results
+        .iter()
+        .flat_map(|result| {
+            let file = with_file.then_some(result.path.as_path());
+            result.rows.iter().map(move |row| {
+                let stats = stats_row(None, &row.stats, 0.0, binary);
+                RowGroupStatsJsonRow {
+                    file: file.map(display_path),
+                    row_group: row.row_group,
+                    rows: row.num_rows,
+                    statistics: row.has_statistics,
+                    column: stats.column,
+                    display_type: stats.display_type,
+                    null_count: row.has_statistics.then_some(stats.null_count),
+                    min: stats.min,
+                    max: stats.max,
+                    physical_type: stats.physical_type,
+                    logical_type: stats.logical_type,
+                }
+            })
+        })
+        .collect()
+}
+
+fn bench_json(result: &BenchResult) -> BenchJson {
+    BenchJson {
+        operation: result.operation.to_string(),
+        files: result.files,
+        rows: result.rows,
+        warmup: result.warmup,
+        iterations: result.timings.len(),
+        min_ms: bench::millis(result.min()),
+        median_ms: bench::millis(result.median()),
+        p95_ms: bench::millis(result.p95()),
+        rows_per_second: result.rows_per_second(),
+        timings_ms: result.timings.iter().copied().map(bench::millis).collect(),
+    }
+}
+
+fn checksum_json(result: &ChecksumResult) -> ChecksumJson {
+    ChecksumJson {
+        file: display_path(&result.path),
+        rows: result.num_rows,
+        algorithm: result.algorithm.to_string(),
+        checksum: result.checksum.clone(),
+        columns: result
+            .columns
+            .iter()
+            .map(|column| (column.column.clone(), Value::from(column.checksum.as_str())))
+            .collect(),
+    }
+}
+
+/// Bucket arrays: `edges` and `labels` hold one more entry than `counts`.
+fn histogram_json(histogram: &Histogram) -> HistogramJson {
+    let mut edges: Vec<f64> = histogram
+        .buckets
+        .iter()
+        .map(|bucket| bucket.lower)
+        .collect();
+    let mut labels: Vec<String> = histogram
+        .buckets
+        .iter()
+        .map(|bucket| bucket.lower_label.clone())
+        .collect();
+    if let Some(last) = histogram.buckets.last() {
+        edges.push(last.upper);
+        labels.push(last.upper_label.clone());
+    }
+    HistogramJson {
+        column: histogram.column.clone(),
+        edges,
+        labels,
+        counts: histogram
+            .buckets
+            .iter()
+            .map(|bucket| bucket.count)
+            .collect(),
+        null_count: histogram.null_count,
+        nan_count: histogram.nan_count,
+        below_min: histogram.below_min,
+        above_max: histogram.above_max,
+    }
+}
+
+fn nulls_rows(results: &[NullsResult]) -> Vec<NullsJsonRow> {
+    let with_file = results.len() > 1;
+    results
+        .iter()
+        .flat_map(|result| {
+            result.columns.iter().map(move |column| NullsJsonRow {
+                file: with_file.then(|| display_path(&result.path)),
+                column: column.column.clone(),
+                rows: result.num_rows,
+                null_count: column.null_count,
+                null_percent: column.null_percent(result.num_rows),
+                all_null: column.is_all_null(result.num_rows),
+                runs: column.runs.map(|runs| NullRunsJson {
+                    longest_null_run: runs.longest,
+                    first_non_null: runs.first_non_null,
+                    last_non_null: runs.last_non_null,
+                }),
+            })
+        })
+        .collect()
+}
+
 fn file_info_rows(rows: &[FileInfo]) -> Vec<FileInfoJsonRow> {
-    rows.iter()
-        .map(|row| FileInfoJsonRow {
-            file: row.path().display().to_string(),
-            file_size_bytes: row.file_size_bytes,
-            num_rows: row.num_rows,
-            num_columns: row.num_columns,
-            num_row_groups: row.num_row_groups,
-            compression: row.compression.to_string(),
-            created_by: row.created_by.clone(),
-            version: row.version,
+    rows.iter().map(file_info_row).collect()
+}
+
+fn file_info_row(row: &FileInfo) -> FileInfoJsonRow {
+    FileInfoJsonRow {
+        file: display_path(row.path()),
+        file_size_bytes: row.file_size_bytes,
+        num_rows: row.num_rows,
+        num_columns: row.num_columns,
+        num_row_groups: row.num_row_groups,
+        compression: row.compression.to_string(),
+        compression_codecs: row
+            .codec_columns
+            .iter()
+            .map(|usage| (usage.codec.to_string(), Value::from(usage.columns)))
+            .collect(),
+        compressed_bytes: row.compressed_bytes,
+        uncompressed_bytes: row.uncompressed_bytes,
+        compression_ratio: row.compression_ratio(),
+        created_by: row.created_by.clone(),
+        version: row.version,
+        sort_order: row.sort_order.as_str(),
+        sorting_columns: sorting_columns_json(row.sort_order.columns()),
+    }
+}
+
+fn sorting_columns_json(columns: &[SortingColumn]) -> Vec<SortingColumnJson> {
+    columns
+        .iter()
+        .map(|column| SortingColumnJson {
+            column: column.column.clone(),
+            descending: column.descending,
+            nulls_first: column.nulls_first,
+        })
+        .collect()
+}
+
+fn describe_document(
+    description: &FileDescription,
+    binary: Option<BinaryEncoding>,
+) -> DescribeJsonDocument {
+    let bound = |value: Option<&StatValue>, logical: Option<&LogicalTypeKind>| {
+        value.map(|value| stat_value_json(value, logical, binary))
+    };
+    DescribeJsonDocument {
+        file: file_info_row(&description.info),
+        columns: description
+            .columns
+            .iter()
+            .map(|column| {
+                let logical = column.column_type.logical.as_ref();
+                DescribeColumnJsonRow {
+                    name: column.name.clone(),
+                    display_type: column.display_type(),
+                    physical_type: column.column_type.physical.to_string(),
+                    logical_type: logical.map(LogicalTypeKind::display_name),
+                    nullable: column.nullable,
+                    null_count: column.null_count,
+                    null_percent: description.null_percent(column),
+                    min: bound(column.min.as_ref(), logical),
+                    max: bound(column.max.as_ref(), logical),
+                }
+            })
+            .collect(),
+    }
+}
+
+fn column_chunk_rows(results: &[ColumnChunksResult]) -> Vec<ColumnChunkJsonRow> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result.columns.iter().map(|column| ColumnChunkJsonRow {
+                file: display_path(&result.path),
+                column: column.column.clone(),
+                display_type: column.display_type(),
+                compression: column.compression.to_string(),
+                compressed_bytes: column.compressed_bytes,
+                uncompressed_bytes: column.uncompressed_bytes,
+                row_groups: column.row_groups,
+                bloom_filter_row_groups: column.bloom_filter_row_groups,
+                bloom_filter_bytes: column.bloom_filter_bytes,
+                encodings: column.encodings.clone(),
+                dictionary_row_groups: column.dictionary_row_groups,
+                dictionary_encoded_row_groups: column.dictionary_encoded_row_groups,
+                dictionary_fraction: column.dictionary_fraction(),
+                dictionary_entries: column.dictionary_entries,
+                dictionary_bytes: column.dictionary_bytes,
+                mixed_dictionary_encoding: column.mixed_dictionary_encoding(),
+            })
+        })
+        .collect()
+}
+
+fn row_group_rows(results: &[RowGroupsResult]) -> Vec<RowGroupJsonRow> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result.row_groups.iter().map(|row_group| RowGroupJsonRow {
+                file: display_path(&result.path),
+                row_group: row_group.index,
+                num_rows: row_group.num_rows,
+                compressed_bytes: row_group.compressed_bytes,
+                uncompressed_bytes: row_group.uncompressed_bytes,
+                columns: row_group.columns,
+                statistics_columns: row_group.statistics_columns,
+                dictionary_columns: row_group.dictionary_columns,
+                page_index_columns: row_group.page_index_columns,
+                bloom_filter_columns: row_group.bloom_filter_columns,
+                sorting_columns: sorting_columns_json(&row_group.sorting_columns),
+            })
+        })
+        .collect()
+}
+
+fn key_value_rows(results: &[KeyValueMetadataResult]) -> Vec<KeyValueJsonRow> {
+    results
+        .iter()
+        .flat_map(|result| {
+            result.entries.iter().map(|entry| KeyValueJsonRow {
+                file: display_path(&result.path),
+                key: entry.key.clone(),
+                value: entry.value.clone(),
+            })
+        })
+        .collect()
+}
+
+fn exists_rows(results: &[ExistsResult]) -> Vec<ExistsJsonRow> {
+    results
+        .iter()
+        .map(|result| ExistsJsonRow {
+            file: display_path(&result.path),
+            column: result.column.clone(),
+            value: result.value.clone(),
+            result: result.answer.as_str(),
+            evidence: result.evidence.as_str(),
+            row_groups: result.row_groups,
+            candidate_row_groups: result.candidate_row_groups,
+        })
+        .collect()
+}
+
+/// Group values sit in their own `group` object, so a column named `count` or
+/// `percent` cannot collide with the counts.
+fn group_count_rows(result: &GroupCountResult) -> Vec<Map<String, Value>> {
+    result
+        .groups
+        .iter()
+        .map(|group| {
+            let values: Map<String, Value> = result
+                .columns
+                .iter()
+                .zip(&group.values)
+                .map(|(column, value)| {
+                    (
+                        column.clone(),
+                        value.as_deref().map_or(Value::Null, Value::from),
+                    )
+                })
+                .collect();
+            let mut row = Map::new();
+            row.insert("group".to_string(), Value::Object(values));
+            row.insert("count".to_string(), Value::from(group.rows));
+            row.insert("percent".to_string(), Value::from(result.percent(group)));
+            row
         })
         .collect()
 }
 
-fn stat_value_json(value: &StatValue, logical_type: Option<&LogicalTypeKind>) -> Value {
+fn page_index_report_json(result: &PageIndexResult) -> PageIndexJsonReport {
+    PageIndexJsonReport {
+        file: display_path(&result.path),
+        has_page_index: result.has_page_index,
+        row_groups: result
+            .row_groups
+            .iter()
+            .map(|row_group| PageIndexJsonRowGroup {
+                row_group: row_group.index,
+                num_rows: row_group.num_rows,
+                columns: row_group
+                    .columns
+                    .iter()
+                    .map(|column| PageIndexJsonColumn {
+                        column: column.column.clone(),
+                        column_index: column.column_index,
+                        offset_index: column.offset_index,
+                        page_count: column.pages.len(),
+                        pages: column
+                            .pages
+                            .iter()
+                            .enumerate()
+                            .map(|(index, page)| page_json(column, index, page))
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn page_json(column: &ColumnPages, index: usize, page: &PageInfo) -> PageJsonRow {
+    let logical_type = column.column_type.logical.as_ref();
+    PageJsonRow {
+        file: None,
+        row_group: None,
+        column: None,
+        page: index,
+        first_row_index: page.first_row_index,
+        rows: page.rows,
+        offset: page.offset,
+        compressed_size: page.compressed_size,
+        null_count: page.null_count,
+        min: page
+            .min
+            .as_ref()
+            .map(|value| stat_value_json(value, logical_type, None)),
+        max: page
+            .max
+            .as_ref()
+            .map(|value| stat_value_json(value, logical_type, None)),
+    }
+}
+
+fn prune_report_json(report: &PruneReport) -> PruneJsonReport {
+    let pages = report.pages();
+    PruneJsonReport {
+        file: display_path(&report.path),
+        predicate: report.predicate.clone(),
+        columns: report.columns.clone(),
+        page_index: report.page_index,
+        row_groups_total: report.row_groups.len(),
+        row_groups_read: report.row_groups_read(),
+        row_groups_skipped: report.row_groups_skipped(),
+        rows_read: report.rows_read(),
+        rows_skipped: report.rows_skipped(),
+        pages_total: pages.map(|pages| pages.total),
+        pages_skipped: pages.map(|pages| pages.skipped),
+        row_groups: report
+            .row_groups
+            .iter()
+            .map(|group| prune_row_group_json(None, group))
+            .collect(),
+    }
+}
+
+fn prune_row_group_json(file: Option<&Path>, group: &RowGroupPruning) -> PruneJsonRowGroup {
+    let columns = group
+        .columns
+        .iter()
+        .map(|stats| {
+            let logical_type = stats.column_type.logical.as_ref();
+            let mut column = Map::new();
+            column.insert("null_count".to_string(), Value::from(stats.null_count));
+            for (key, value) in [("min", &stats.min), ("max", &stats.max)] {
+                column.insert(
+                    key.to_string(),
+                    value.as_ref().map_or(Value::Null, |value| {
+                        stat_value_json(value, logical_type, None)
+                    }),
+                );
+            }
+            (stats.column.clone(), Value::Object(column))
+        })
+        .collect();
+
+    PruneJsonRowGroup {
+        file: file.map(display_path),
+        row_group: group.index,
+        num_rows: group.num_rows,
+        decision: group.decision.as_str(),
+        pages_total: group.pages.map(|pages| pages.total),
+        pages_skipped: group.pages.map(|pages| pages.skipped),
+        columns,
+    }
+}
+
+/// Binary bounds of string columns stay text where valid UTF-8, UUIDs use canonical form,
+/// and other binary values use the requested encoding, defaulting to hex.
+fn stat_value_json(
+    value: &StatValue,
+    logical_type: Option<&LogicalTypeKind>,
+    binary: Option<BinaryEncoding>,
+) -> Value {
     match value {
+        StatValue::Int32(inner) if is_unsigned(logical_type) => Value::from(*inner as u32),
+        StatValue::Int64(inner) if is_unsigned(logical_type) => Value::from(*inner as u64),
         StatValue::Int32(inner) => Value::from(*inner),
         StatValue::Int64(inner) => Value::from(*inner),
         StatValue::Float(inner) => Value::from(*inner),
         StatValue::Double(inner) => Value::from(*inner),
-        StatValue::Binary(inner) | StatValue::FixedLenBinary(inner) => {
-            if logical_type == Some(&LogicalTypeKind::String) {
-                match std::str::from_utf8(inner) {
-                    Ok(value) => Value::from(value),
-                    Err(_) => Value::from(hex_string(inner)),
-                }
-            } else {
-                Value::from(hex_string(inner))
+        StatValue::Binary(inner) | StatValue::FixedLenBinary(inner) => match logical_type {
+            Some(LogicalTypeKind::String) => match std::str::from_utf8(inner) {
+                Ok(value) => Value::from(value),
+                Err(_) => Value::from(display_hex(inner)),
+            },
+            Some(LogicalTypeKind::Uuid) if inner.len() == 16 => {
+                Value::from(display_uuid(inner).unwrap_or_else(|| display_hex(inner)))
             }
-        }
+            _ => Value::from(binary.unwrap_or(BinaryEncoding::Hex).encode(inner)),
+        },
         StatValue::Boolean(inner) => Value::from(*inner),
         StatValue::Int96(inner) => Value::from(inner.as_str()),
     }
 }
 
-fn hex_string(value: &[u8]) -> String {
-    value.iter().map(|byte| format!("{byte:02x}")).collect()
+fn is_unsigned(logical_type: Option<&LogicalTypeKind>) -> bool {
+    matches!(
+        logical_type,
+        Some(LogicalTypeKind::Integer {
+            is_signed: false,
+            ..
+        })
+    )
 }
 
 #[cfg(test)]
@@ -469,7 +2195,14 @@ mod tests {
         let path = temp_path("jsonl")?;
         let batch = sample_batch()?;
 
-        let mut writer = BatchFileWriter::create_at(&path, &path)?;
+        let output = resolve_file_output(
+            &path,
+            None,
+            None,
+            CsvDialect::default(),
+            DisplayOptions::default(),
+        )?;
+        let mut writer = BatchFileWriter::create_at(&path, &path, output, &batch.schema())?;
         writer.write(&batch)?;
         writer.finish()?;
 
@@ -485,7 +2218,15 @@ mod tests {
         let error_path = temp_path("jsonl")?;
         let batch = sample_batch()?;
 
-        let mut writer = BatchFileWriter::create_at(&write_path, &error_path)?;
+        let output = resolve_file_output(
+            &error_path,
+            None,
+            None,
+            CsvDialect::default(),
+            DisplayOptions::default(),
+        )?;
+        let mut writer =
+            BatchFileWriter::create_at(&write_path, &error_path, output, &batch.schema())?;
         writer.write(&batch)?;
         writer.finish()?;
 
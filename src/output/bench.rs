@@ -0,0 +1,71 @@
+use crate::model::BenchResult;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::output::table;
+use crate::Result;
+use std::io::Write;
+use std::time::Duration;
+
+/// Milliseconds, the unit every bench output uses.
+pub(crate) fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn millis_text(duration: Duration) -> String {
+    format!("{:.3} ms", millis(duration))
+}
+
+fn rate_text(result: &BenchResult) -> String {
+    result
+        .rows_per_second()
+        .map_or_else(|| "N/A".to_string(), |rate| format!("{rate:.0}"))
+}
+
+pub fn write_table<W: Write>(writer: W, result: &BenchResult, no_header: bool) -> Result<()> {
+    let entries = [
+        ("Operation", result.operation.to_string()),
+        ("Files", result.files.to_string()),
+        ("Rows", result.rows.to_string()),
+        ("Warmup", result.warmup.to_string()),
+        ("Iterations", result.timings.len().to_string()),
+        ("Min", millis_text(result.min())),
+        ("Median", millis_text(result.median())),
+        ("P95", millis_text(result.p95())),
+        ("Rows/s", rate_text(result)),
+    ];
+    table::write_key_value(writer, &entries, no_header)
+}
+
+/// One record of summary figures; JSON also carries every run's time.
+pub fn write_csv<W: Write>(
+    writer: W,
+    result: &BenchResult,
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "operation",
+        "files",
+        "rows",
+        "warmup",
+        "iterations",
+        "min_ms",
+        "median_ms",
+        "p95_ms",
+        "rows_per_second",
+    ])?;
+    writer.write_record([
+        result.operation.to_string(),
+        result.files.to_string(),
+        result.rows.to_string(),
+        result.warmup.to_string(),
+        result.timings.len().to_string(),
+        millis(result.min()).to_string(),
+        millis(result.median()).to_string(),
+        millis(result.p95()).to_string(),
+        result
+            .rows_per_second()
+            .map(|rate| rate.to_string())
+            .unwrap_or_default(),
+    ])?;
+    writer.flush()
+}
@@ -0,0 +1,117 @@
+//! Binary value rendering for text output
+
+use super::display::DisplayTarget;
+use crate::model::{
+    display_hex, display_uuid, ColumnType, LogicalTypeKind, StatValue, ARROW_EXTENSION_NAME_KEY,
+    UUID_EXTENSION_NAME,
+};
+use arrow::array::{Array, ArrayRef, AsArray, StringArray};
+use arrow::datatypes::{DataType, Field};
+use arrow::error::ArrowError;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::sync::Arc;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum BinaryEncoding {
+    Hex,
+    Base64,
+    /// Decode as UTF-8, replacing invalid sequences with U+FFFD.
+    Utf8Lossy,
+}
+
+impl BinaryEncoding {
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Hex => display_hex(bytes),
+            Self::Base64 => STANDARD.encode(bytes),
+            Self::Utf8Lossy => String::from_utf8_lossy(bytes).into_owned(),
+        }
+    }
+}
+
+fn is_uuid_field(field: &Field) -> bool {
+    field.data_type() == &DataType::FixedSizeBinary(16)
+        && field
+            .metadata()
+            .get(ARROW_EXTENSION_NAME_KEY)
+            .is_some_and(|name| name == UUID_EXTENSION_NAME)
+}
+
+/// Render a binary column as strings: UUID columns always in canonical form, others
+/// with the requested encoding, or the target's default (Arrow's hex display for text,
+/// base64 for JSON).
+pub(crate) fn render_column(
+    encoding: Option<BinaryEncoding>,
+    field: &Field,
+    column: &ArrayRef,
+    target: DisplayTarget,
+) -> Result<Option<ArrayRef>, ArrowError> {
+    let values: Vec<Option<&[u8]>> = match column.data_type() {
+        DataType::Binary => column.as_binary::<i32>().iter().collect(),
+        DataType::LargeBinary => column.as_binary::<i64>().iter().collect(),
+        DataType::BinaryView => column.as_binary_view().iter().collect(),
+        DataType::FixedSizeBinary(_) => column.as_fixed_size_binary().iter().collect(),
+        _ => return Ok(None),
+    };
+
+    let rendered: StringArray = if is_uuid_field(field) {
+        values
+            .into_iter()
+            .map(|value| value.and_then(display_uuid))
+            .collect()
+    } else {
+        let encoding = match (encoding, target) {
+            (Some(encoding), _) => encoding,
+            (None, DisplayTarget::Json) => BinaryEncoding::Base64,
+            (None, DisplayTarget::Text) => return Ok(None),
+        };
+        values
+            .into_iter()
+            .map(|value| value.map(|bytes| encoding.encode(bytes)))
+            .collect()
+    };
+    Ok(Some(Arc::new(rendered)))
+}
+
+/// Statistics bounds for non-string binary columns use an explicitly requested encoding;
+/// everything else keeps the column type's default display.
+pub(crate) fn stat_value_text(
+    column_type: &ColumnType,
+    value: &StatValue,
+    encoding: Option<BinaryEncoding>,
+) -> String {
+    match (value, encoding) {
+        (StatValue::Binary(bytes) | StatValue::FixedLenBinary(bytes), Some(encoding))
+            if !matches!(
+                column_type.logical,
+                Some(LogicalTypeKind::String | LogicalTypeKind::Uuid)
+            ) =>
+        {
+            encoding.encode(bytes)
+        }
+        _ => column_type.display_stat_value(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_bytes_and_uuids() {
+        let bytes = [0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(BinaryEncoding::Hex.encode(&bytes), "deadbeef");
+        assert_eq!(BinaryEncoding::Base64.encode(&bytes), "3q2+7w==");
+        assert_eq!(BinaryEncoding::Utf8Lossy.encode(b"ok\xff"), "ok\u{fffd}");
+        assert_eq!(
+            display_uuid(&[
+                0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17,
+                0x40, 0x00
+            ])
+            .as_deref(),
+            Some("123e4567-e89b-12d3-a456-426614174000")
+        );
+        assert_eq!(display_uuid(&bytes), None);
+    }
+}
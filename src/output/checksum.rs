@@ -0,0 +1,52 @@
+use crate::model::ChecksumResult;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::path_text::display_path;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+/// The whole-file hash first, then one row per column when there are column hashes.
+pub fn write_table<W: Write>(
+    mut writer: W,
+    result: &ChecksumResult,
+    no_header: bool,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Column".to_string(),
+            format!("Checksum ({})", result.algorithm),
+        ]);
+    }
+
+    table.add_row(vec![
+        Cell::new("(all columns)"),
+        Cell::new(&result.checksum),
+    ]);
+    for column in &result.columns {
+        table.add_row(vec![Cell::new(&column.column), Cell::new(&column.checksum)]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+/// One record per file with an empty `column`, followed by any per-column records.
+pub fn write_csv<W: Write>(
+    writer: W,
+    results: &[ChecksumResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(["file", "rows", "column", "algorithm", "checksum"])?;
+
+    for result in results {
+        let file = display_path(&result.path);
+        let rows = result.num_rows.to_string();
+        let algorithm = result.algorithm.to_string();
+        writer.write_record([&file, &rows, "", &algorithm, &result.checksum])?;
+        for column in &result.columns {
+            writer.write_record([&file, &rows, &column.column, &algorithm, &column.checksum])?;
+        }
+    }
+
+    writer.flush()
+}
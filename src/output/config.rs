@@ -0,0 +1,45 @@
+use crate::model::ConfigSetting;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    settings: &[ConfigSetting],
+    no_header: bool,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec!["Setting", "Value", "Source"]);
+    }
+
+    for setting in settings {
+        table.add_row(vec![
+            Cell::new(&setting.key),
+            Cell::new(setting.value.as_deref().unwrap_or("-")),
+            Cell::new(setting.source.to_string()),
+        ]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    settings: &[ConfigSetting],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(["setting", "value", "source"])?;
+
+    for setting in settings {
+        let value = setting
+            .value
+            .as_deref()
+            .unwrap_or(writer.null_value())
+            .to_string();
+        writer.write_record([&setting.key, &value, &setting.source.to_string()])?;
+    }
+
+    writer.flush()
+}
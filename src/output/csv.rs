@@ -1,26 +1,21 @@
 //! CSV output formatting
 
+use super::csv_support::{CsvDialect, CsvRecordWriter};
+use super::sink::Sink;
 use crate::Result;
 use arrow::array::RecordBatch;
-use arrow::csv::{Writer, WriterBuilder};
 use arrow::error::ArrowError;
-use std::fs::File;
 use std::io::Write;
 
+/// The header goes out with the first batch, so no batches means no output at all.
 pub fn write_batches<W: Write>(
-    mut writer: W,
-    batches: &[RecordBatch],
-    include_header: bool,
+    writer: W,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+    dialect: &CsvDialect,
 ) -> Result<()> {
-    if batches.is_empty() {
-        return Ok(());
-    }
-
-    for (index, batch) in batches.iter().enumerate() {
-        let mut csv_writer = WriterBuilder::new()
-            .with_header(include_header && index == 0)
-            .build(&mut writer);
-        csv_writer.write(batch)?;
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    for batch in batches {
+        writer.write_batch(&batch?)?;
     }
 
     writer.flush()?;
@@ -28,20 +23,78 @@ pub fn write_batches<W: Write>(
 }
 
 pub struct BatchFileWriter {
-    writer: Writer<File>,
+    writer: CsvRecordWriter<Sink>,
 }
 
 impl BatchFileWriter {
-    pub fn create(path: &std::path::Path) -> std::io::Result<Self> {
-        let file = File::create(path)?;
-        Ok(Self {
-            writer: WriterBuilder::new().with_header(true).build(file),
-        })
+    pub fn new(sink: Sink, dialect: &CsvDialect) -> Self {
+        Self {
+            writer: CsvRecordWriter::new(sink, dialect),
+        }
     }
 
     pub fn write(&mut self, batch: &RecordBatch) -> std::result::Result<(), ArrowError> {
-        self.writer.write(batch)
+        self.writer.write_batch(batch)
+    }
+
+    pub fn finish(self) -> std::result::Result<(), ArrowError> {
+        self.writer.into_inner()?.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+    use std::sync::Arc;
+
+    fn batch(ids: &[i64], names: &[&str]) -> Result<RecordBatch> {
+        Ok(RecordBatch::try_from_iter([
+            ("id", Arc::new(Int64Array::from(ids.to_vec())) as ArrayRef),
+            (
+                "name",
+                Arc::new(StringArray::from(names.to_vec())) as ArrayRef,
+            ),
+        ])?)
     }
 
-    pub fn finish(&mut self) {}
+    fn render(batches: &[RecordBatch], dialect: &CsvDialect) -> Result<String> {
+        let mut out = Vec::new();
+        write_batches(&mut out, batches.iter().cloned().map(Ok), dialect)?;
+        Ok(String::from_utf8_lossy(&out).into_owned())
+    }
+
+    #[test]
+    fn leading_empty_batch_writes_the_header_once() -> Result<()> {
+        let batches = [batch(&[], &[])?, batch(&[1], &["a"])?, batch(&[2], &["b"])?];
+        assert_eq!(
+            render(&batches, &CsvDialect::default())?,
+            "id,name\n1,a\n2,b\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_empty_batches_still_write_the_header() -> Result<()> {
+        let batches = [batch(&[], &[])?, batch(&[], &[])?];
+        assert_eq!(render(&batches, &CsvDialect::default())?, "id,name\n");
+
+        let headerless = CsvDialect {
+            header: false,
+            ..CsvDialect::default()
+        };
+        assert_eq!(render(&batches, &headerless)?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn single_row() -> Result<()> {
+        let batches = [batch(&[7], &["only"])?];
+        assert_eq!(
+            render(&batches, &CsvDialect::default())?,
+            "id,name\n7,only\n"
+        );
+        Ok(())
+    }
 }
@@ -1,7 +1,188 @@
-pub(crate) fn escape_csv(value: &str) -> String {
-    if value.contains(',') || value.contains('"') || value.contains('\n') {
-        format!("\"{}\"", value.replace('"', "\"\""))
-    } else {
-        value.to_string()
+use super::values::TextValues;
+use arrow::array::RecordBatch;
+use arrow::error::ArrowError;
+use arrow::util::display::FormatOptions;
+use std::io::{self, Write};
+
+/// CSV settings shared by `-o csv` printing and `convert`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct CsvDialect {
+    pub delimiter: u8,
+    pub quote_style: CsvQuoteStyle,
+    /// Written in place of null data values.
+    pub null_value: String,
+    pub header: bool,
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote_style: CsvQuoteStyle::Necessary,
+            null_value: String::new(),
+            header: true,
+        }
+    }
+}
+
+impl CsvDialect {
+    /// The same dialect with tabs between fields, for `-o tsv`. Fields holding a tab,
+    /// quote, or line break are quoted as in CSV, so they read back unambiguously.
+    pub fn tab_separated(self) -> Self {
+        Self {
+            delimiter: b'\t',
+            ..self
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum CsvQuoteStyle {
+    Always,
+    #[default]
+    Necessary,
+    Never,
+}
+
+impl From<CsvQuoteStyle> for csv::QuoteStyle {
+    fn from(value: CsvQuoteStyle) -> Self {
+        match value {
+            CsvQuoteStyle::Always => Self::Always,
+            CsvQuoteStyle::Necessary => Self::Necessary,
+            CsvQuoteStyle::Never => Self::Never,
+        }
+    }
+}
+
+/// The one CSV writer behind every CSV output, so escaping and dialect handling
+/// cannot drift between record batches and metadata reports.
+pub(crate) struct CsvRecordWriter<W: Write> {
+    writer: csv::Writer<W>,
+    header_pending: bool,
+    null_value: String,
+}
+
+impl<W: Write> CsvRecordWriter<W> {
+    pub fn new(writer: W, dialect: &CsvDialect) -> Self {
+        let writer = csv::WriterBuilder::new()
+            .delimiter(dialect.delimiter)
+            .quote_style(dialect.quote_style.into())
+            .from_writer(writer);
+        Self {
+            writer,
+            header_pending: dialect.header,
+            null_value: dialect.null_value.clone(),
+        }
+    }
+
+    pub fn null_value(&self) -> &str {
+        &self.null_value
+    }
+
+    /// Write the header row unless the dialect suppresses it or it was already written.
+    pub fn write_header<I, T>(&mut self, fields: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        if self.header_pending {
+            self.header_pending = false;
+            self.writer.write_record(fields).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_record<I, T>(&mut self, fields: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        self.writer.write_record(fields).map_err(io_error)?;
+        Ok(())
+    }
+
+    /// Write a batch with the values tables show, with the schema as the header on
+    /// the first call.
+    pub fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), ArrowError> {
+        self.write_header(batch.schema().fields().iter().map(|field| field.name()))?;
+
+        if let Some(column) = batch
+            .columns()
+            .iter()
+            .find(|column| column.data_type().is_nested())
+        {
+            return Err(ArrowError::CsvError(format!(
+                "Nested type {} is not supported in CSV",
+                column.data_type()
+            )));
+        }
+        let options = FormatOptions::default().with_null(&self.null_value);
+        let values = TextValues::new(batch, &options)?;
+
+        let mut buffer = String::new();
+        let mut record = csv::StringRecord::with_capacity(1024, batch.num_columns());
+        for row in 0..batch.num_rows() {
+            record.clear();
+            for column in 0..batch.num_columns() {
+                values.write_value(column, row, &mut buffer)?;
+                record.push_field(&buffer);
+            }
+            self.writer.write_record(&record).map_err(io_error)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> io::Result<W> {
+        self.writer
+            .into_inner()
+            .map_err(|error| io::Error::new(error.error().kind(), error.to_string()))
+    }
+}
+
+/// Keep the kind of a failed write, which csv's own conversion reports as `Other`.
+fn io_error(error: csv::Error) -> io::Error {
+    let kind = match error.kind() {
+        csv::ErrorKind::Io(source) => source.kind(),
+        _ => io::ErrorKind::Other,
+    };
+    io::Error::new(kind, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(dialect: &CsvDialect, rows: &[&[&str]]) -> io::Result<String> {
+        let mut writer = CsvRecordWriter::new(Vec::new(), dialect);
+        writer.write_header(["name", "note"])?;
+        for row in rows {
+            writer.write_record(*row)?;
+        }
+        let bytes = writer.into_inner()?;
+        String::from_utf8(bytes).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    #[test]
+    fn quotes_carriage_returns_and_delimiters() -> io::Result<()> {
+        let output = render(&CsvDialect::default(), &[&["a,b", "line\rbreak"]])?;
+        assert_eq!(output, "name,note\n\"a,b\",\"line\rbreak\"\n");
+        Ok(())
+    }
+
+    #[test]
+    fn applies_delimiter_quote_style_and_header() -> io::Result<()> {
+        let dialect = CsvDialect {
+            delimiter: b'\t',
+            quote_style: CsvQuoteStyle::Always,
+            header: false,
+            ..CsvDialect::default()
+        };
+        let output = render(&dialect, &[&["a", "b"]])?;
+        assert_eq!(output, "\"a\"\t\"b\"\n");
+        Ok(())
     }
 }
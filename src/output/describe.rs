@@ -0,0 +1,93 @@
+use crate::model::{FileDescription, StatValue};
+use crate::output::binary::{stat_value_text, BinaryEncoding};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::output::info;
+use crate::path_text::display_path;
+use crate::Result;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+/// The `info` summary followed by one table row per column.
+pub fn write_table<W: Write>(
+    mut writer: W,
+    description: &FileDescription,
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+) -> Result<()> {
+    info::write_table(
+        &mut writer,
+        std::slice::from_ref(&description.info),
+        no_header,
+        info::InfoNumbers::default(),
+    )?;
+    writeln!(writer)?;
+
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Column", "Type", "Nullable", "Nulls", "Null %", "Min", "Max",
+        ]);
+    }
+    for column in &description.columns {
+        let bound = |value: Option<&StatValue>| {
+            value.map_or_else(
+                || "N/A".to_string(),
+                |value| stat_value_text(&column.column_type, value, binary),
+            )
+        };
+        table.add_row(vec![
+            Cell::new(&column.name),
+            Cell::new(column.display_type()),
+            Cell::new(if column.nullable { "Yes" } else { "No" }),
+            Cell::new(column.null_count),
+            Cell::new(format!("{:.2}%", description.null_percent(column))),
+            Cell::new(bound(column.min.as_ref())),
+            Cell::new(bound(column.max.as_ref())),
+        ]);
+    }
+
+    writeln!(writer, "{table}")?;
+    Ok(())
+}
+
+/// One row per column; file-level metadata is left to `info -o csv`.
+pub fn write_csv<W: Write>(
+    writer: W,
+    descriptions: &[FileDescription],
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "file",
+        "column",
+        "type",
+        "nullable",
+        "null_count",
+        "null_percent",
+        "min",
+        "max",
+    ])?;
+
+    for description in descriptions {
+        for column in &description.columns {
+            let bound = |value: Option<&StatValue>| {
+                value.map_or_else(String::new, |value| {
+                    stat_value_text(&column.column_type, value, binary)
+                })
+            };
+            writer.write_record([
+                display_path(description.info.path()),
+                column.name.clone(),
+                column.display_type(),
+                column.nullable.to_string(),
+                column.null_count.to_string(),
+                format!("{:.2}", description.null_percent(column)),
+                bound(column.min.as_ref()),
+                bound(column.max.as_ref()),
+            ])?;
+        }
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,133 @@
+//! Column rewrites applied before record batches reach a text writer
+
+use super::binary::{self, BinaryEncoding};
+use super::float::{self, FloatFormat};
+use super::integer::{self, Int64Format};
+use super::table::TableOverflow;
+use super::temporal::{self, TemporalFormat};
+use super::values;
+use arrow::array::RecordBatch;
+use arrow::datatypes::{Field, Schema};
+use arrow::error::ArrowError;
+use std::sync::Arc;
+
+/// User-chosen rendering for value types Arrow's default display handles poorly.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DisplayOptions {
+    pub temporal: TemporalFormat,
+    /// Encoding for binary columns; each target has its own default.
+    pub binary: Option<BinaryEncoding>,
+    pub float: FloatFormat,
+    /// How JSON writes 64-bit integers and decimals; numbers when unset.
+    pub int64: Option<Int64Format>,
+    /// How tables wider than the terminal fit; truncation when unset.
+    pub overflow: Option<TableOverflow>,
+    /// Print control characters in table cells as they are instead of escaping them.
+    pub raw_control_chars: bool,
+    /// Group integer digits in thousands in tables; CSV and JSON keep plain numbers.
+    pub human_readable: bool,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum DisplayTarget {
+    /// Tables and CSV keep Arrow's display text unless an option asks otherwise.
+    Text,
+    /// JSON renders timestamps as RFC 3339 strings and binary as base64, and keeps
+    /// floats and, unless asked otherwise, 64-bit integers numeric.
+    Json,
+}
+
+impl DisplayOptions {
+    /// Fill options this set leaves unspecified from `fallback`.
+    pub fn or(self, fallback: &Self) -> Self {
+        Self {
+            temporal: TemporalFormat {
+                timestamp_format: self
+                    .temporal
+                    .timestamp_format
+                    .or_else(|| fallback.temporal.timestamp_format.clone()),
+                date_format: self
+                    .temporal
+                    .date_format
+                    .or_else(|| fallback.temporal.date_format.clone()),
+                timezone: self.temporal.timezone.or(fallback.temporal.timezone),
+            },
+            binary: self.binary.or(fallback.binary),
+            float: FloatFormat {
+                precision: self.float.precision.or(fallback.float.precision),
+                style: self.float.style.or(fallback.float.style),
+            },
+            int64: self.int64.or(fallback.int64),
+            overflow: self.overflow.or(fallback.overflow),
+            raw_control_chars: self.raw_control_chars || fallback.raw_control_chars,
+            human_readable: self.human_readable || fallback.human_readable,
+        }
+    }
+
+    /// Replace columns whose rendering the options or the target change with their
+    /// strings; other columns pass through untouched.
+    pub fn apply(
+        &self,
+        batch: &RecordBatch,
+        target: DisplayTarget,
+    ) -> Result<RecordBatch, ArrowError> {
+        let schema = batch.schema();
+        let mut fields = Vec::with_capacity(schema.fields().len());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        let mut changed = false;
+
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            // Renderers match on value types, so dictionaries are resolved first
+            let decoded = values::decode_dictionaries(column)?;
+            let decoded_field = decoded.as_ref().map(|decoded| {
+                Arc::new(
+                    Field::new(
+                        field.name(),
+                        decoded.data_type().clone(),
+                        field.is_nullable(),
+                    )
+                    .with_metadata(field.metadata().clone()),
+                )
+            });
+            let field = decoded_field.as_ref().unwrap_or(field);
+            let column = decoded.as_ref().unwrap_or(column);
+            let rendered = match temporal::render_column(&self.temporal, column, target)? {
+                Some(rendered) => Some(rendered),
+                None => match binary::render_column(self.binary, field, column, target)? {
+                    Some(rendered) => Some(rendered),
+                    None => match float::render_column(&self.float, column, target)? {
+                        Some(rendered) => Some(rendered),
+                        None => integer::render_column(self.int64, column, target)?,
+                    },
+                },
+            }
+            .or_else(|| decoded.clone());
+            match rendered {
+                Some(rendered) => {
+                    changed = true;
+                    fields.push(Arc::new(
+                        Field::new(
+                            field.name(),
+                            rendered.data_type().clone(),
+                            field.is_nullable(),
+                        )
+                        .with_metadata(field.metadata().clone()),
+                    ));
+                    columns.push(rendered);
+                }
+                None => {
+                    fields.push(Arc::clone(field));
+                    columns.push(Arc::clone(column));
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(batch.clone());
+        }
+        RecordBatch::try_new(
+            Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+            columns,
+        )
+    }
+}
@@ -0,0 +1,66 @@
+use crate::model::ExistsResult;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::path_text::display_path;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    results: &[ExistsResult],
+    no_header: bool,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec!["File", "Column", "Value", "Result", "Row Groups"]);
+    }
+
+    for result in results {
+        table.add_row(vec![
+            Cell::new(display_path(&result.path)),
+            Cell::new(&result.column),
+            Cell::new(&result.value),
+            Cell::new(format!(
+                "{} ({})",
+                result.answer.as_str(),
+                result.evidence.as_str()
+            )),
+            Cell::new(format!(
+                "{}/{} candidates",
+                result.candidate_row_groups, result.row_groups
+            )),
+        ]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    results: &[ExistsResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "file",
+        "column",
+        "value",
+        "result",
+        "evidence",
+        "row_groups",
+        "candidate_row_groups",
+    ])?;
+
+    for result in results {
+        writer.write_record([
+            display_path(&result.path),
+            result.column.clone(),
+            result.value.clone(),
+            result.answer.as_str().to_string(),
+            result.evidence.as_str().to_string(),
+            result.row_groups.to_string(),
+            result.candidate_row_groups.to_string(),
+        ])?;
+    }
+
+    writer.flush()
+}
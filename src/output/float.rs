@@ -0,0 +1,166 @@
+//! Float rendering with a chosen precision, shared by table, CSV, and JSON output
+
+use super::display::DisplayTarget;
+use arrow::array::{Array, ArrayRef, AsArray, Float32Array, Float64Array, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Float32Type, Float64Type};
+use arrow::error::ArrowError;
+use std::sync::Arc;
+
+/// User-chosen rendering for float columns. With neither field set, floats keep Arrow's
+/// shortest round-trip display.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct FloatFormat {
+    /// Digits after the point for fixed and scientific styles, significant digits for
+    /// the general style; 6 when only a style is given.
+    pub precision: Option<usize>,
+    /// Fixed when only a precision is given.
+    pub style: Option<FloatStyle>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum FloatStyle {
+    /// `123.456000`
+    Fixed,
+    /// `1.234560e2`
+    Scientific,
+    /// Like C's `%g`: scientific for very small or large magnitudes, fixed otherwise,
+    /// without trailing zeros.
+    General,
+}
+
+const DEFAULT_PRECISION: usize = 6;
+
+impl FloatFormat {
+    fn is_default(&self) -> bool {
+        self.precision.is_none() && self.style.is_none()
+    }
+
+    pub fn format(&self, value: f64) -> String {
+        let precision = self.precision.unwrap_or(DEFAULT_PRECISION);
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        match self.style.unwrap_or(FloatStyle::Fixed) {
+            FloatStyle::Fixed => format!("{value:.precision$}"),
+            FloatStyle::Scientific => format!("{value:.precision$e}"),
+            FloatStyle::General => general(value, precision),
+        }
+    }
+
+    /// The number the formatted text reads as, or `value` itself where rounding would
+    /// overflow to infinity.
+    fn round(&self, value: f64) -> f64 {
+        self.format(value)
+            .parse::<f64>()
+            .ok()
+            .filter(|rounded| rounded.is_finite() || !value.is_finite())
+            .unwrap_or(value)
+    }
+}
+
+/// `%g`: `precision` significant digits, in scientific notation when the exponent is
+/// below -4 or at least `precision`, with trailing zeros removed.
+fn general(value: f64, precision: usize) -> String {
+    let precision = precision.max(1);
+    let scientific = format!("{:.*e}", precision - 1, value);
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return scientific;
+    };
+    let exponent: i64 = exponent.parse().unwrap_or_default();
+    if exponent < -4 || exponent >= precision as i64 {
+        return format!("{}e{exponent}", trim_fraction(mantissa));
+    }
+    let decimals = usize::try_from(precision as i64 - 1 - exponent).unwrap_or_default();
+    trim_fraction(&format!("{value:.decimals$}")).to_string()
+}
+
+fn trim_fraction(text: &str) -> &str {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        text
+    }
+}
+
+/// Render a float column with the chosen format: as strings for text output, and for
+/// JSON as numbers rounded to what the text would show.
+pub(crate) fn render_column(
+    format: &FloatFormat,
+    column: &ArrayRef,
+    target: DisplayTarget,
+) -> Result<Option<ArrayRef>, ArrowError> {
+    if format.is_default() {
+        return Ok(None);
+    }
+    let column = match column.data_type() {
+        DataType::Float16 => cast(column, &DataType::Float32)?,
+        DataType::Float32 | DataType::Float64 => Arc::clone(column),
+        _ => return Ok(None),
+    };
+
+    let values: Vec<Option<f64>> = match column.data_type() {
+        DataType::Float32 => column
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(|value| value.map(f64::from))
+            .collect(),
+        _ => column.as_primitive::<Float64Type>().iter().collect(),
+    };
+    let rendered: ArrayRef = match (target, column.data_type()) {
+        (DisplayTarget::Text, _) => Arc::new(
+            values
+                .into_iter()
+                .map(|value| value.map(|value| format.format(value)))
+                .collect::<StringArray>(),
+        ),
+        (DisplayTarget::Json, DataType::Float32) => Arc::new(
+            values
+                .into_iter()
+                .map(|value| value.map(|value| format.round(value) as f32))
+                .collect::<Float32Array>(),
+        ),
+        (DisplayTarget::Json, _) => Arc::new(
+            values
+                .into_iter()
+                .map(|value| value.map(|value| format.round(value)))
+                .collect::<Float64Array>(),
+        ),
+    };
+    Ok(Some(rendered))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(precision: Option<usize>, style: Option<FloatStyle>, value: f64) -> String {
+        FloatFormat { precision, style }.format(value)
+    }
+
+    #[test]
+    fn formats_fixed_and_scientific_digits() {
+        assert_eq!(format(Some(2), None, 0.1 + 0.2), "0.30");
+        assert_eq!(format(Some(0), None, 2.5), "2");
+        assert_eq!(format(Some(2), None, -0.0), "-0.00");
+        assert_eq!(
+            format(Some(3), Some(FloatStyle::Scientific), 123456.0),
+            "1.235e5"
+        );
+        assert_eq!(format(Some(2), None, f64::NAN), "NaN");
+        assert_eq!(format(Some(2), None, f64::NEG_INFINITY), "-inf");
+    }
+
+    #[test]
+    fn formats_general_like_printf_g() {
+        let general = |value| format(None, Some(FloatStyle::General), value);
+        assert_eq!(general(0.1 + 0.2), "0.3");
+        assert_eq!(general(100000.0), "100000");
+        assert_eq!(general(1000000.0), "1e6");
+        assert_eq!(general(0.0001), "0.0001");
+        assert_eq!(general(0.00001234), "1.234e-5");
+        assert_eq!(general(5e-324), "4.94066e-324");
+        assert_eq!(general(-0.0), "-0");
+        assert_eq!(format(Some(3), Some(FloatStyle::General), 999.9), "1e3");
+    }
+}
@@ -0,0 +1,58 @@
+use crate::model::GroupCountResult;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+const NULL_GROUP: &str = "(null)";
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    result: &GroupCountResult,
+    no_header: bool,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        let mut header = result.columns.iter().map(Cell::new).collect::<Vec<_>>();
+        header.push(Cell::new("Count"));
+        header.push(Cell::new("Percent"));
+        table.set_header(header);
+    }
+
+    for group in &result.groups {
+        let mut row = group
+            .values
+            .iter()
+            .map(|value| Cell::new(value.as_deref().unwrap_or(NULL_GROUP)))
+            .collect::<Vec<_>>();
+        row.push(Cell::new(group.rows));
+        row.push(Cell::new(format!("{:.2}%", result.percent(group))));
+        table.add_row(row);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    result: &GroupCountResult,
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let mut header = result.columns.clone();
+    header.push("count".to_string());
+    header.push("percent".to_string());
+    writer.write_header(&header)?;
+
+    for group in &result.groups {
+        let mut row = group
+            .values
+            .iter()
+            .map(|value| value.as_deref().unwrap_or(writer.null_value()).to_string())
+            .collect::<Vec<_>>();
+        row.push(group.rows.to_string());
+        row.push(format!("{:.2}", result.percent(group)));
+        writer.write_record(&row)?;
+    }
+
+    writer.flush()
+}
@@ -0,0 +1,78 @@
+use crate::model::Histogram;
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+/// Width of the longest bar, in characters.
+const BAR_WIDTH: u64 = 40;
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    histogram: &Histogram,
+    no_header: bool,
+    quiet: bool,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec!["Range", "Count", ""]);
+    }
+
+    let largest = histogram
+        .buckets
+        .iter()
+        .map(|bucket| bucket.count)
+        .max()
+        .unwrap_or_default();
+    let last = histogram.buckets.len().saturating_sub(1);
+    for (index, bucket) in histogram.buckets.iter().enumerate() {
+        let close = if index == last { ']' } else { ')' };
+        let bar_len = if largest == 0 {
+            0
+        } else {
+            (bucket.count * BAR_WIDTH).div_ceil(largest)
+        };
+        table.add_row(vec![
+            Cell::new(format!(
+                "[{}, {}{close}",
+                bucket.lower_label, bucket.upper_label
+            )),
+            Cell::new(bucket.count),
+            Cell::new("#".repeat(usize::try_from(bar_len).unwrap_or_default())),
+        ]);
+    }
+    writeln!(writer, "{table}")?;
+
+    if !quiet {
+        let mut excluded = vec![format!("{} null", histogram.null_count)];
+        if histogram.nan_count > 0 {
+            excluded.push(format!("{} NaN", histogram.nan_count));
+        }
+        if histogram.below_min > 0 {
+            excluded.push(format!("{} below min", histogram.below_min));
+        }
+        if histogram.above_max > 0 {
+            excluded.push(format!("{} above max", histogram.above_max));
+        }
+        writeln!(writer, "Excluded: {}", excluded.join(", "))?;
+    }
+    Ok(())
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    histogram: &Histogram,
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(["lower", "upper", "count"])?;
+
+    for bucket in &histogram.buckets {
+        writer.write_record([
+            bucket.lower_label.clone(),
+            bucket.upper_label.clone(),
+            bucket.count.to_string(),
+        ])?;
+    }
+
+    writer.flush()
+}
@@ -1,22 +1,68 @@
-use crate::model::FileInfo;
-use crate::output::csv_support::escape_csv;
+use crate::model::{
+    sorting_label, ColumnChunkSummary, ColumnChunksResult, FileInfo, KeyValueEntry,
+    KeyValueMetadataResult, RowGroupSummary, RowGroupsResult, SortOrder,
+};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::output::integer::group_thousands;
 use crate::output::table;
+use crate::path_text::display_path;
 use crate::Result;
+use comfy_table::{Cell, Table};
 use std::io::Write;
 
-pub fn write_table<W: Write>(mut writer: W, rows: &[FileInfo], quiet: bool) -> Result<()> {
+/// How info tables write counts and byte sizes; CSV and JSON always keep exact numbers.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct InfoNumbers {
+    /// Group the digits of counts, and of raw byte counts, as `1,234,567`
+    pub human_readable: bool,
+    /// Exact byte counts instead of sizes in binary units
+    pub raw_bytes: bool,
+}
+
+impl InfoNumbers {
+    fn count(self, value: impl ToString) -> String {
+        let text = value.to_string();
+        if self.human_readable {
+            group_thousands(&text)
+        } else {
+            text
+        }
+    }
+
+    fn size(self, bytes: u64) -> String {
+        if self.raw_bytes {
+            self.count(bytes)
+        } else {
+            format_size(bytes)
+        }
+    }
+}
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    rows: &[FileInfo],
+    no_header: bool,
+    numbers: InfoNumbers,
+) -> Result<()> {
     for (index, row) in rows.iter().enumerate() {
         if index > 0 {
             writeln!(writer)?;
         }
 
         let entries = [
-            ("File", row.path().display().to_string()),
-            ("File Size", format_size(row.file_size_bytes)),
-            ("Rows", row.num_rows.to_string()),
-            ("Columns", row.num_columns.to_string()),
-            ("Row Groups", row.num_row_groups.to_string()),
-            ("Compression", row.compression.to_string()),
+            ("File", display_path(row.path())),
+            ("File Size", numbers.size(row.file_size_bytes)),
+            ("Rows", numbers.count(row.num_rows)),
+            ("Columns", numbers.count(row.num_columns)),
+            ("Row Groups", numbers.count(row.num_row_groups)),
+            ("Compression", row.compression_label()),
+            ("Compressed", numbers.size(row.compressed_bytes)),
+            ("Uncompressed", numbers.size(row.uncompressed_bytes)),
+            (
+                "Compression Ratio",
+                row.compression_ratio()
+                    .map_or_else(|| "N/A".to_string(), |ratio| format!("{ratio:.2}x")),
+            ),
             (
                 "Created By",
                 row.created_by
@@ -24,42 +70,306 @@ pub fn write_table<W: Write>(mut writer: W, rows: &[FileInfo], quiet: bool) -> R
                     .unwrap_or_else(|| "unknown".to_string()),
             ),
             ("Version", row.version.to_string()),
+            ("Sorted By", row.sort_order.to_string()),
         ];
-        table::write_key_value(&mut writer, &entries, quiet)?;
+        table::write_key_value(&mut writer, &entries, no_header)?;
     }
 
     Ok(())
 }
 
 pub fn write_csv<W: Write>(
-    mut writer: W,
+    writer: W,
     rows: &[FileInfo],
-    include_header: bool,
+    dialect: &CsvDialect,
 ) -> std::io::Result<()> {
-    if include_header {
-        writeln!(writer, "file,file_size_bytes,num_rows,num_columns,num_row_groups,compression,created_by,version")?;
-    }
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "file",
+        "file_size_bytes",
+        "num_rows",
+        "num_columns",
+        "num_row_groups",
+        "compression",
+        "created_by",
+        "version",
+        "compressed_bytes",
+        "uncompressed_bytes",
+        "compression_ratio",
+        "sorted_by",
+    ])?;
 
     for row in rows {
-        writeln!(
-            writer,
-            "{},{},{},{},{},{},{},{}",
-            escape_csv(&row.path().display().to_string()),
-            row.file_size_bytes,
-            row.num_rows,
-            row.num_columns,
-            row.num_row_groups,
-            escape_csv(&row.compression.to_string()),
-            escape_csv(row.created_by.as_deref().unwrap_or("")),
-            row.version,
-        )?;
+        writer.write_record([
+            display_path(row.path()),
+            row.file_size_bytes.to_string(),
+            row.num_rows.to_string(),
+            row.num_columns.to_string(),
+            row.num_row_groups.to_string(),
+            row.compression_label(),
+            row.created_by.clone().unwrap_or_default(),
+            row.version.to_string(),
+            row.compressed_bytes.to_string(),
+            row.uncompressed_bytes.to_string(),
+            row.compression_ratio()
+                .map(|ratio| ratio.to_string())
+                .unwrap_or_default(),
+            sort_order_value(&row.sort_order),
+        ])?;
     }
 
-    Ok(())
+    writer.flush()
+}
+
+/// `sorted_by` for CSV: the sort columns when declared, otherwise the same `undeclared`
+/// or `mixed` JSON's `sort_order` carries.
+fn sort_order_value(order: &SortOrder) -> String {
+    match order {
+        SortOrder::Sorted(columns) => sorting_label(columns),
+        SortOrder::Undeclared | SortOrder::Mixed => order.as_str().to_string(),
+    }
+}
+
+pub fn write_columns_table<W: Write>(
+    mut writer: W,
+    columns: &[ColumnChunkSummary],
+    no_header: bool,
+    numbers: InfoNumbers,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Column",
+            "Type",
+            "Compression",
+            "Compressed",
+            "Uncompressed",
+            "Encodings",
+            "Dictionary",
+            "Bloom Filter",
+        ]);
+    }
+
+    for column in columns {
+        table.add_row(vec![
+            Cell::new(&column.column),
+            Cell::new(column.display_type()),
+            Cell::new(column.compression),
+            Cell::new(numbers.size(column.compressed_bytes)),
+            Cell::new(numbers.size(column.uncompressed_bytes)),
+            Cell::new(column.encodings.join(", ")),
+            Cell::new(dictionary_summary(column, numbers)),
+            Cell::new(bloom_filter_summary(column, numbers)),
+        ]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_columns_csv<W: Write>(
+    writer: W,
+    results: &[ColumnChunksResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let optional = |value: Option<u64>| value.map_or_else(String::new, |value| value.to_string());
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "file",
+        "column",
+        "type",
+        "compression",
+        "compressed_bytes",
+        "uncompressed_bytes",
+        "row_groups",
+        "encodings",
+        "dictionary_row_groups",
+        "dictionary_encoded_row_groups",
+        "dictionary_entries",
+        "dictionary_bytes",
+        "mixed_dictionary_encoding",
+        "bloom_filter_row_groups",
+        "bloom_filter_bytes",
+    ])?;
+
+    for result in results {
+        for column in &result.columns {
+            writer.write_record([
+                display_path(&result.path),
+                column.column.clone(),
+                column.display_type(),
+                column.compression.to_string(),
+                column.compressed_bytes.to_string(),
+                column.uncompressed_bytes.to_string(),
+                column.row_groups.to_string(),
+                column.encodings.join(" "),
+                column.dictionary_row_groups.to_string(),
+                column.dictionary_encoded_row_groups.to_string(),
+                optional(column.dictionary_entries),
+                optional(column.dictionary_bytes),
+                column.mixed_dictionary_encoding().to_string(),
+                column.bloom_filter_row_groups.to_string(),
+                optional(column.bloom_filter_bytes),
+            ])?;
+        }
+    }
+
+    writer.flush()
+}
+
+pub fn write_row_groups_table<W: Write>(
+    mut writer: W,
+    row_groups: &[RowGroupSummary],
+    no_header: bool,
+    numbers: InfoNumbers,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Row Group",
+            "Rows",
+            "Compressed",
+            "Uncompressed",
+            "Statistics",
+            "Dictionary",
+            "Page Index",
+            "Bloom Filter",
+            "Sorted By",
+        ]);
+    }
+
+    for row_group in row_groups {
+        let coverage = |count: usize| format!("{count}/{}", row_group.columns);
+        table.add_row(vec![
+            Cell::new(row_group.index),
+            Cell::new(numbers.count(row_group.num_rows)),
+            Cell::new(numbers.size(row_group.compressed_bytes)),
+            Cell::new(numbers.size(row_group.uncompressed_bytes)),
+            Cell::new(coverage(row_group.statistics_columns)),
+            Cell::new(coverage(row_group.dictionary_columns)),
+            Cell::new(coverage(row_group.page_index_columns)),
+            Cell::new(coverage(row_group.bloom_filter_columns)),
+            Cell::new(sorting_label(&row_group.sorting_columns)),
+        ]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_row_groups_csv<W: Write>(
+    writer: W,
+    results: &[RowGroupsResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "file",
+        "row_group",
+        "num_rows",
+        "compressed_bytes",
+        "uncompressed_bytes",
+        "columns",
+        "statistics_columns",
+        "dictionary_columns",
+        "page_index_columns",
+        "bloom_filter_columns",
+        "sorted_by",
+    ])?;
+
+    for result in results {
+        for row_group in &result.row_groups {
+            writer.write_record([
+                display_path(&result.path),
+                row_group.index.to_string(),
+                row_group.num_rows.to_string(),
+                row_group.compressed_bytes.to_string(),
+                row_group.uncompressed_bytes.to_string(),
+                row_group.columns.to_string(),
+                row_group.statistics_columns.to_string(),
+                row_group.dictionary_columns.to_string(),
+                row_group.page_index_columns.to_string(),
+                row_group.bloom_filter_columns.to_string(),
+                sorting_label(&row_group.sorting_columns),
+            ])?;
+        }
+    }
+
+    writer.flush()
+}
+
+pub fn write_key_value_metadata_table<W: Write>(
+    writer: W,
+    entries: &[KeyValueEntry],
+    no_header: bool,
+) -> Result<()> {
+    let rows: Vec<(&str, String)> = entries
+        .iter()
+        .map(|entry| {
+            let value = entry.value.clone().unwrap_or_else(|| "(null)".to_string());
+            (entry.key.as_str(), value)
+        })
+        .collect();
+    table::write_key_value(writer, &rows, no_header)
+}
+
+pub fn write_key_value_metadata_csv<W: Write>(
+    writer: W,
+    results: &[KeyValueMetadataResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(["file", "key", "value"])?;
+
+    for result in results {
+        for entry in &result.entries {
+            let value = entry
+                .value
+                .clone()
+                .unwrap_or_else(|| writer.null_value().to_string());
+            writer.write_record([display_path(&result.path), entry.key.clone(), value])?;
+        }
+    }
+
+    writer.flush()
+}
+
+fn dictionary_summary(column: &ColumnChunkSummary, numbers: InfoNumbers) -> String {
+    if column.dictionary_row_groups == 0 {
+        return "none".to_string();
+    }
+
+    let mut summary = format!(
+        "{}/{} row groups",
+        column.dictionary_encoded_row_groups, column.row_groups
+    );
+    if let Some(entries) = column.dictionary_entries {
+        summary.push_str(&format!(", {} entries", numbers.count(entries)));
+    }
+    if let Some(bytes) = column.dictionary_bytes {
+        summary.push_str(&format!(", {}", numbers.size(bytes)));
+    }
+    if column.mixed_dictionary_encoding() {
+        summary.push_str(" (mixed)");
+    }
+    summary
+}
+
+fn bloom_filter_summary(column: &ColumnChunkSummary, numbers: InfoNumbers) -> String {
+    if column.bloom_filter_row_groups == 0 {
+        return "none".to_string();
+    }
+
+    let coverage = format!(
+        "{}/{} row groups",
+        column.bloom_filter_row_groups, column.row_groups
+    );
+    match column.bloom_filter_bytes {
+        Some(bytes) => format!("{coverage}, {}", numbers.size(bytes)),
+        None => coverage,
+    }
 }
 
 #[allow(clippy::cast_precision_loss)]
-fn format_size(bytes: u64) -> String {
+pub(crate) fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -0,0 +1,117 @@
+//! Integer rendering: 64-bit integers and decimals for JSON, which many JSON readers
+//! parse into doubles and silently round, and digit grouping for `--human-readable` tables
+
+use super::display::DisplayTarget;
+use arrow::array::ArrayRef;
+use arrow::compute::cast;
+use arrow::datatypes::DataType;
+use arrow::error::ArrowError;
+
+/// How JSON output writes Int64, UInt64, and decimal values.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum Int64Format {
+    /// Bare numbers, exact but beyond 2^53 only for readers with 64-bit integers.
+    #[default]
+    Number,
+    /// Quoted decimal strings, exact for every reader.
+    String,
+}
+
+/// Render top-level Int64, UInt64, and decimal columns as strings for JSON in string
+/// mode; text output already shows every digit.
+pub(crate) fn render_column(
+    format: Option<Int64Format>,
+    column: &ArrayRef,
+    target: DisplayTarget,
+) -> Result<Option<ArrayRef>, ArrowError> {
+    if target != DisplayTarget::Json || format.unwrap_or_default() != Int64Format::String {
+        return Ok(None);
+    }
+    match column.data_type() {
+        DataType::Int64
+        | DataType::UInt64
+        | DataType::Decimal128(_, _)
+        | DataType::Decimal256(_, _) => Ok(Some(cast(column, &DataType::Utf8)?)),
+        _ => Ok(None),
+    }
+}
+
+/// `text` with a comma between each group of three digits when it is an integer,
+/// such as `-1,234,567`; anything else comes back unchanged. Locale plays no part.
+pub(crate) fn group_thousands(text: &str) -> String {
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", text),
+    };
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        return text.to_string();
+    }
+    let mut grouped = String::with_capacity(text.len() + digits.len() / 3);
+    grouped.push_str(sign);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Whether `--human-readable` groups the digits of a column of this type.
+pub(crate) fn is_grouped_type(data_type: &DataType) -> bool {
+    data_type.is_integer()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Array, AsArray, Decimal128Array, Int32Array, Int64Array};
+    use std::sync::Arc;
+
+    #[test]
+    fn groups_digits_in_threes_keeping_the_sign() {
+        assert_eq!(group_thousands("1234567890"), "1,234,567,890");
+        assert_eq!(group_thousands("-1234"), "-1,234");
+        assert_eq!(group_thousands("-123"), "-123");
+        assert_eq!(group_thousands("100"), "100");
+        assert_eq!(group_thousands("0"), "0");
+        assert_eq!(
+            group_thousands(&i64::MIN.to_string()),
+            "-9,223,372,036,854,775,808"
+        );
+        assert_eq!(
+            group_thousands(&u64::MAX.to_string()),
+            "18,446,744,073,709,551,615"
+        );
+        assert_eq!(group_thousands(""), "");
+        assert_eq!(group_thousands("-"), "-");
+        assert_eq!(group_thousands("12.5"), "12.5");
+    }
+
+    #[test]
+    fn string_mode_quotes_only_wide_integers_for_json() -> Result<(), ArrowError> {
+        let int64: ArrayRef = Arc::new(Int64Array::from(vec![Some(i64::MAX), None]));
+        let rendered = render_column(Some(Int64Format::String), &int64, DisplayTarget::Json)?
+            .ok_or_else(|| ArrowError::ComputeError("Int64 should render".to_string()))?;
+        let strings = rendered.as_string::<i32>();
+        assert_eq!(strings.value(0), "9223372036854775807");
+        assert!(strings.is_null(1));
+
+        let decimal: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![123_456_789_012_345_678_901_234_i128])
+                .with_precision_and_scale(30, 4)?,
+        );
+        let rendered = render_column(Some(Int64Format::String), &decimal, DisplayTarget::Json)?
+            .ok_or_else(|| ArrowError::ComputeError("decimal should render".to_string()))?;
+        assert_eq!(
+            rendered.as_string::<i32>().value(0),
+            "12345678901234567890.1234"
+        );
+
+        let int32: ArrayRef = Arc::new(Int32Array::from(vec![1]));
+        assert!(render_column(Some(Int64Format::String), &int32, DisplayTarget::Json)?.is_none());
+        assert!(render_column(Some(Int64Format::String), &int64, DisplayTarget::Text)?.is_none());
+        assert!(render_column(None, &int64, DisplayTarget::Json)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! Arrow IPC output, which hands batches to Arrow tools with their types intact
+
+use super::sink::Sink;
+use crate::Result;
+use arrow::array::RecordBatch;
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use arrow::ipc::writer::{FileWriter, StreamWriter};
+use std::io::Write;
+
+/// Write batches as one IPC stream; nothing is written when there are no batches,
+/// since a stream cannot start without a schema.
+pub fn write_stream<W: Write>(writer: W, batches: &[RecordBatch]) -> Result<()> {
+    let Some(first) = batches.first() else {
+        return Ok(());
+    };
+
+    let mut writer = StreamWriter::try_new(writer, &first.schema())?;
+    for batch in batches {
+        writer.write(batch)?;
+    }
+    writer.finish()?;
+    writer.into_inner()?.flush()?;
+    Ok(())
+}
+
+/// IPC file format, with its footer for random access, for files; the stream format
+/// where readers cannot seek, such as stdout.
+pub enum BatchFileWriter {
+    File(FileWriter<Sink>),
+    Stream(StreamWriter<Sink>),
+}
+
+impl BatchFileWriter {
+    pub fn file(sink: Sink, schema: &Schema) -> std::result::Result<Self, ArrowError> {
+        FileWriter::try_new(sink, schema).map(Self::File)
+    }
+
+    pub fn stream(sink: Sink, schema: &Schema) -> std::result::Result<Self, ArrowError> {
+        StreamWriter::try_new(sink, schema).map(Self::Stream)
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> std::result::Result<(), ArrowError> {
+        match self {
+            Self::File(writer) => writer.write(batch),
+            Self::Stream(writer) => writer.write(batch),
+        }
+    }
+
+    pub fn finish(self) -> std::result::Result<(), ArrowError> {
+        let sink = match self {
+            Self::File(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()?
+            }
+            Self::Stream(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()?
+            }
+        };
+        sink.finish()?;
+        Ok(())
+    }
+}
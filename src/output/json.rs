@@ -1,34 +1,41 @@
 //! JSON and JSONL output formatting
 
+use super::sink::Sink;
 use crate::Result;
 use arrow::array::RecordBatch;
 use arrow::error::ArrowError;
 use arrow::json::writer::{JsonArray, LineDelimited, Writer};
 use arrow::json::WriterBuilder;
 use serde::Serialize;
-use std::fs::File;
-use std::io::BufWriter;
 use std::io::Write;
 
-pub fn write_json<W: Write>(writer: W, batches: &[RecordBatch]) -> Result<()> {
+/// The array opens before the first batch and closes after the last, so batches are
+/// written as they arrive.
+pub fn write_json<W: Write>(
+    writer: W,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<()> {
     let mut writer = WriterBuilder::new()
         .with_explicit_nulls(true)
         .build::<_, JsonArray>(writer);
 
     for batch in batches {
-        writer.write(batch)?;
+        writer.write(&batch?)?;
     }
     writer.finish()?;
     Ok(())
 }
 
-pub fn write_jsonl<W: Write>(writer: W, batches: &[RecordBatch]) -> Result<()> {
+pub fn write_jsonl<W: Write>(
+    writer: W,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+) -> Result<()> {
     let mut writer = WriterBuilder::new()
         .with_explicit_nulls(true)
         .build::<_, LineDelimited>(writer);
 
     for batch in batches {
-        writer.write(batch)?;
+        writer.write(&batch?)?;
     }
     writer.finish()?;
     Ok(())
@@ -50,45 +57,47 @@ pub fn write_json_lines<W: Write, T: Serialize>(mut writer: W, values: &[T]) ->
 }
 
 pub struct JsonBatchFileWriter {
-    writer: Writer<BufWriter<File>, JsonArray>,
+    writer: Writer<Sink, JsonArray>,
 }
 
 impl JsonBatchFileWriter {
-    pub fn create(path: &std::path::Path) -> std::io::Result<Self> {
-        let file = File::create(path)?;
+    pub fn new(sink: Sink) -> Self {
         let writer = WriterBuilder::new()
             .with_explicit_nulls(true)
-            .build::<_, JsonArray>(BufWriter::new(file));
-        Ok(Self { writer })
+            .build::<_, JsonArray>(sink);
+        Self { writer }
     }
 
     pub fn write(&mut self, batch: &RecordBatch) -> std::result::Result<(), ArrowError> {
         self.writer.write(batch)
     }
 
-    pub fn finish(&mut self) -> std::result::Result<(), ArrowError> {
-        self.writer.finish()
+    pub fn finish(mut self) -> std::result::Result<(), ArrowError> {
+        self.writer.finish()?;
+        self.writer.into_inner().finish()?;
+        Ok(())
     }
 }
 
 pub struct JsonlBatchFileWriter {
-    writer: Writer<BufWriter<File>, LineDelimited>,
+    writer: Writer<Sink, LineDelimited>,
 }
 
 impl JsonlBatchFileWriter {
-    pub fn create(path: &std::path::Path) -> std::io::Result<Self> {
-        let file = File::create(path)?;
+    pub fn new(sink: Sink) -> Self {
         let writer = WriterBuilder::new()
             .with_explicit_nulls(true)
-            .build::<_, LineDelimited>(BufWriter::new(file));
-        Ok(Self { writer })
+            .build::<_, LineDelimited>(sink);
+        Self { writer }
     }
 
     pub fn write(&mut self, batch: &RecordBatch) -> std::result::Result<(), ArrowError> {
         self.writer.write(batch)
     }
 
-    pub fn finish(&mut self) -> std::result::Result<(), ArrowError> {
-        self.writer.finish()
+    pub fn finish(mut self) -> std::result::Result<(), ArrowError> {
+        self.writer.finish()?;
+        self.writer.into_inner().finish()?;
+        Ok(())
     }
 }
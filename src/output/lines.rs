@@ -0,0 +1,81 @@
+//! One value per line, for `pq extract`
+
+use super::display::{DisplayOptions, DisplayTarget};
+use super::values::TextValues;
+use crate::Result;
+use arrow::array::{Array, RecordBatch};
+use arrow::util::display::FormatOptions;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::io::Write;
+use twox_hash::XxHash3_128;
+
+/// What `pq extract` prints besides the values themselves.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ValueLines {
+    /// Print each distinct line once, where it first appears
+    pub unique: bool,
+    /// The line printed for a null, or `None` to skip nulls
+    pub null_value: Option<String>,
+}
+
+/// Write the first column of each batch, one value per line, as tables show it.
+///
+/// `--unique` remembers a 128-bit hash of each line printed rather than the line, so
+/// memory grows by 16 bytes per distinct value however long the values are.
+pub fn write_lines<W: Write>(
+    mut writer: W,
+    batches: impl IntoIterator<Item = Result<RecordBatch>>,
+    lines: &ValueLines,
+    display: &DisplayOptions,
+) -> Result<()> {
+    let options = FormatOptions::default();
+    let mut seen: HashSet<u128> = HashSet::new();
+    let mut line = String::new();
+    for batch in batches {
+        let batch = display.apply(&batch?, DisplayTarget::Text)?;
+        let Some(column) = batch.columns().first() else {
+            continue;
+        };
+        let values = TextValues::new(&batch, &options)?;
+        for row in 0..batch.num_rows() {
+            if column.is_null(row) {
+                let Some(null_value) = &lines.null_value else {
+                    continue;
+                };
+                line.clone_from(null_value);
+            } else {
+                values.write_value(0, row, &mut line)?;
+            }
+            let text = if display.raw_control_chars {
+                Cow::Borrowed(line.as_str())
+            } else {
+                escape_line(&line)
+            };
+            if lines.unique && !seen.insert(XxHash3_128::oneshot(text.as_bytes())) {
+                continue;
+            }
+            writer.write_all(text.as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Control characters, newlines among them, as `\x0a`-style escapes, so every value
+/// stays on its own line.
+fn escape_line(text: &str) -> Cow<'_, str> {
+    if !text.contains(char::is_control) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        if c.is_control() {
+            out.push_str(&format!("\\x{:02x}", u32::from(c)));
+        } else {
+            out.push(c);
+        }
+    }
+    Cow::Owned(out)
+}
@@ -0,0 +1,103 @@
+use crate::model::{ColumnNulls, NullsResult};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::path_text::display_path;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    result: &NullsResult,
+    no_header: bool,
+) -> std::io::Result<()> {
+    let runs = result.columns.iter().any(|column| column.runs.is_some());
+    let mut table = Table::new();
+    if !no_header {
+        let mut header = vec!["Column", "Nulls", "Null %", "All Null"];
+        if runs {
+            header.extend(["Longest Run", "First Non-Null", "Last Non-Null"]);
+        }
+        table.set_header(header);
+    }
+
+    for column in &result.columns {
+        let mut row = vec![
+            Cell::new(&column.column),
+            Cell::new(column.null_count),
+            Cell::new(format!("{:.2}%", column.null_percent(result.num_rows))),
+            Cell::new(if column.is_all_null(result.num_rows) {
+                "Yes"
+            } else {
+                "No"
+            }),
+        ];
+        if let Some(runs) = &column.runs {
+            let row_index = |index: Option<u64>| {
+                index.map_or_else(|| "-".to_string(), |index| index.to_string())
+            };
+            row.extend([
+                Cell::new(runs.longest),
+                Cell::new(row_index(runs.first_non_null)),
+                Cell::new(row_index(runs.last_non_null)),
+            ]);
+        }
+        table.add_row(row);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    results: &[NullsResult],
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let with_file = results.len() > 1;
+    let runs = results
+        .iter()
+        .flat_map(|result| &result.columns)
+        .any(|column| column.runs.is_some());
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+
+    let mut header = Vec::new();
+    if with_file {
+        header.push("file");
+    }
+    header.extend(["column", "rows", "null_count", "null_percent", "all_null"]);
+    if runs {
+        header.extend(["longest_null_run", "first_non_null", "last_non_null"]);
+    }
+    writer.write_header(header)?;
+
+    for result in results {
+        for column in &result.columns {
+            let mut fields = Vec::new();
+            if with_file {
+                fields.push(display_path(&result.path));
+            }
+            fields.extend(column_fields(column, result.num_rows));
+            writer.write_record(&fields)?;
+        }
+    }
+
+    writer.flush()
+}
+
+fn column_fields(column: &ColumnNulls, num_rows: u64) -> Vec<String> {
+    let mut fields = vec![
+        column.column.clone(),
+        num_rows.to_string(),
+        column.null_count.to_string(),
+        column.null_percent(num_rows).to_string(),
+        column.is_all_null(num_rows).to_string(),
+    ];
+    if let Some(runs) = &column.runs {
+        let row_index =
+            |index: Option<u64>| index.map_or_else(String::new, |index| index.to_string());
+        fields.extend([
+            runs.longest.to_string(),
+            row_index(runs.first_non_null),
+            row_index(runs.last_non_null),
+        ]);
+    }
+    fields
+}
@@ -0,0 +1,132 @@
+use crate::model::{ColumnPages, PageIndexResult, PageInfo, StatValue};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::path_text::display_path;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+pub fn write_table<W: Write>(
+    mut writer: W,
+    result: &PageIndexResult,
+    no_header: bool,
+) -> std::io::Result<()> {
+    if !result.has_page_index {
+        return writeln!(
+            writer,
+            "No page index: {} has no column index or offset index",
+            display_path(&result.path)
+        );
+    }
+
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Row Group",
+            "Column",
+            "Page",
+            "First Row",
+            "Rows",
+            "Size",
+            "Nulls",
+            "Min",
+            "Max",
+        ]);
+    }
+
+    for row_group in &result.row_groups {
+        for column in &row_group.columns {
+            if column.pages.is_empty() {
+                table.add_row(vec![
+                    Cell::new(row_group.index),
+                    Cell::new(&column.column),
+                    Cell::new("no index"),
+                ]);
+            }
+            for (index, page) in column.pages.iter().enumerate() {
+                table.add_row(vec![
+                    Cell::new(row_group.index),
+                    Cell::new(&column.column),
+                    Cell::new(index),
+                    Cell::new(display_optional(page.first_row_index)),
+                    Cell::new(display_optional(page.rows)),
+                    Cell::new(display_optional(page.compressed_size)),
+                    Cell::new(display_optional(page.null_count)),
+                    Cell::new(display_bound(column, page.min.as_ref(), "N/A")),
+                    Cell::new(display_bound(column, page.max.as_ref(), "N/A")),
+                ]);
+            }
+        }
+    }
+
+    writeln!(writer, "{table}")
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    results: &[PageIndexResult],
+    include_file: bool,
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let mut header = Vec::new();
+    if include_file {
+        header.push("file");
+    }
+    header.extend([
+        "row_group",
+        "column",
+        "page",
+        "first_row_index",
+        "rows",
+        "offset",
+        "compressed_size",
+        "null_count",
+        "min",
+        "max",
+    ]);
+    writer.write_header(header)?;
+
+    for result in results {
+        for row_group in &result.row_groups {
+            for column in &row_group.columns {
+                for (index, page) in column.pages.iter().enumerate() {
+                    let mut fields = Vec::new();
+                    if include_file {
+                        fields.push(display_path(&result.path));
+                    }
+                    fields.extend([
+                        row_group.index.to_string(),
+                        column.column.clone(),
+                        index.to_string(),
+                    ]);
+                    fields.extend(page_fields(column, page));
+                    writer.write_record(&fields)?;
+                }
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+fn page_fields(column: &ColumnPages, page: &PageInfo) -> [String; 7] {
+    [
+        display_optional(page.first_row_index),
+        display_optional(page.rows),
+        display_optional(page.offset),
+        display_optional(page.compressed_size),
+        display_optional(page.null_count),
+        display_bound(column, page.min.as_ref(), ""),
+        display_bound(column, page.max.as_ref(), ""),
+    ]
+}
+
+fn display_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or_else(String::new, |value| value.to_string())
+}
+
+fn display_bound(column: &ColumnPages, value: Option<&StatValue>, missing: &str) -> String {
+    value.map_or_else(
+        || missing.to_string(),
+        |value| column.column_type.display_stat_value(value),
+    )
+}
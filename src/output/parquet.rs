@@ -0,0 +1,70 @@
+//! Parquet output, so row commands compose through shell redirection
+
+use super::sink::Sink;
+use crate::error::PqError;
+use crate::Result;
+use arrow::array::RecordBatch;
+use arrow::datatypes::Schema;
+use arrow::error::ArrowError;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use std::io::{self, Write};
+use std::sync::Arc;
+
+/// Write batches as one Parquet file, footer included; nothing is written when there
+/// are no batches to take a schema from.
+pub fn write_batches<W: Write + Send>(writer: W, batches: &[RecordBatch]) -> Result<()> {
+    let Some(first) = batches.first() else {
+        return Ok(());
+    };
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer =
+        ArrowWriter::try_new(writer, first.schema(), Some(props)).map_err(writer_error)?;
+    for batch in batches {
+        writer.write(batch).map_err(writer_error)?;
+    }
+    writer.into_inner().map_err(writer_error)?.flush()?;
+    Ok(())
+}
+
+/// Streams batches into one Parquet file for `convert`, writing the footer on finish.
+pub struct BatchFileWriter {
+    writer: ArrowWriter<Sink>,
+}
+
+impl BatchFileWriter {
+    pub fn new(
+        sink: Sink,
+        schema: &Schema,
+        properties: WriterProperties,
+    ) -> std::result::Result<Self, ArrowError> {
+        let writer = ArrowWriter::try_new(sink, Arc::new(schema.clone()), Some(properties))?;
+        Ok(Self { writer })
+    }
+
+    pub fn write(&mut self, batch: &RecordBatch) -> std::result::Result<(), ArrowError> {
+        Ok(self.writer.write(batch)?)
+    }
+
+    pub fn finish(self) -> std::result::Result<(), ArrowError> {
+        self.writer.into_inner()?.finish()?;
+        Ok(())
+    }
+}
+
+/// The writer wraps stdout failures, so a closed pipe is recovered from the source error.
+fn writer_error(error: ParquetError) -> PqError {
+    if let ParquetError::External(source) = &error {
+        if let Some(source) = source.downcast_ref::<io::Error>() {
+            if source.kind() == io::ErrorKind::BrokenPipe {
+                return PqError::BrokenPipe;
+            }
+        }
+    }
+    PqError::output_error(error)
+}
@@ -0,0 +1,152 @@
+use crate::model::{ColumnStats, PruneReport, RowGroupPruning, StatValue};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::output::table;
+use crate::path_text::display_path;
+use crate::Result;
+use comfy_table::{Cell, Table};
+use std::io::Write;
+
+pub fn write_table<W: Write>(mut writer: W, report: &PruneReport, no_header: bool) -> Result<()> {
+    let row_groups = report.row_groups.len();
+    let pages = report.pages().map_or_else(
+        || "no page index".to_string(),
+        |pages| {
+            format!(
+                "{} of {} read, {} skipped",
+                pages.total - pages.skipped,
+                pages.total,
+                pages.skipped
+            )
+        },
+    );
+    let entries = [
+        ("Predicate", report.predicate.clone()),
+        (
+            "Row Groups",
+            format!(
+                "{} of {row_groups} read, {} skipped",
+                report.row_groups_read(),
+                report.row_groups_skipped()
+            ),
+        ),
+        (
+            "Rows",
+            format!(
+                "{} read, {} skipped",
+                report.rows_read(),
+                report.rows_skipped()
+            ),
+        ),
+        ("Pages", pages),
+    ];
+    table::write_key_value(&mut writer, &entries, no_header)?;
+
+    let mut groups = Table::new();
+    if !no_header {
+        let mut header = vec![
+            "Row Group".to_string(),
+            "Rows".to_string(),
+            "Decision".to_string(),
+            "Pages Skipped".to_string(),
+        ];
+        for column in &report.columns {
+            header.push(format!("{column} Min"));
+            header.push(format!("{column} Max"));
+        }
+        groups.set_header(header);
+    }
+
+    for group in &report.row_groups {
+        let mut row = vec![
+            Cell::new(group.index),
+            Cell::new(group.num_rows),
+            Cell::new(group.decision.as_str()),
+            Cell::new(group.pages.map_or_else(
+                || "N/A".to_string(),
+                |pages| format!("{}/{}", pages.skipped, pages.total),
+            )),
+        ];
+        for stats in &group.columns {
+            let mark = if stats.suspicious_bounds() { " !" } else { "" };
+            for bound in [stats.min.as_ref(), stats.max.as_ref()] {
+                let text = display_bound(stats, bound, "N/A");
+                row.push(Cell::new(format!("{text}{mark}")));
+            }
+        }
+        groups.add_row(row);
+    }
+
+    writeln!(writer, "{groups}")?;
+    Ok(())
+}
+
+pub fn write_csv<W: Write>(
+    writer: W,
+    reports: &[PruneReport],
+    include_file: bool,
+    dialect: &CsvDialect,
+) -> std::io::Result<()> {
+    let Some(first) = reports.first() else {
+        return Ok(());
+    };
+
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let mut header = Vec::new();
+    if include_file {
+        header.push("file".to_string());
+    }
+    header.extend(
+        [
+            "row_group",
+            "num_rows",
+            "decision",
+            "pages_total",
+            "pages_skipped",
+        ]
+        .map(str::to_string),
+    );
+    for column in &first.columns {
+        header.push(format!("{column}_min"));
+        header.push(format!("{column}_max"));
+    }
+    writer.write_header(&header)?;
+
+    for report in reports {
+        for group in &report.row_groups {
+            let mut fields = Vec::new();
+            if include_file {
+                fields.push(display_path(&report.path));
+            }
+            fields.extend(group_fields(group));
+            writer.write_record(&fields)?;
+        }
+    }
+
+    writer.flush()
+}
+
+fn group_fields(group: &RowGroupPruning) -> Vec<String> {
+    let mut fields = vec![
+        group.index.to_string(),
+        group.num_rows.to_string(),
+        group.decision.as_str().to_string(),
+        group
+            .pages
+            .map_or_else(String::new, |pages| pages.total.to_string()),
+        group
+            .pages
+            .map_or_else(String::new, |pages| pages.skipped.to_string()),
+    ];
+    for stats in &group.columns {
+        fields.push(display_bound(stats, stats.min.as_ref(), ""));
+        fields.push(display_bound(stats, stats.max.as_ref(), ""));
+    }
+    fields
+}
+
+fn display_bound(stats: &ColumnStats, value: Option<&StatValue>, missing: &str) -> String {
+    value.map_or_else(
+        || missing.to_string(),
+        |value| stats.display_stat_value(value),
+    )
+}
@@ -0,0 +1,83 @@
+//! Row index and source file columns prepended to scanned rows
+
+use arrow::array::{ArrayRef, RecordBatch, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::error::ArrowError;
+use std::sync::Arc;
+
+/// Column names for the row index and, when present, the source file.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RowNumberColumns {
+    pub row: &'static str,
+    pub file: &'static str,
+}
+
+/// Prepend a row index counting up from `first_row`, and `file` as a constant column
+/// when given.
+pub(crate) fn prepend(
+    batches: &[RecordBatch],
+    first_row: usize,
+    names: RowNumberColumns,
+    file: Option<&str>,
+) -> Result<Vec<RecordBatch>, ArrowError> {
+    let mut next_row = u64::try_from(first_row)
+        .map_err(|_| ArrowError::ComputeError(format!("row index {first_row} is too large")))?;
+
+    batches
+        .iter()
+        .map(|batch| {
+            let schema = batch.schema();
+            let mut fields = Vec::with_capacity(schema.fields().len() + 2);
+            let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns() + 2);
+
+            let rows = batch.num_rows() as u64;
+            fields.push(Arc::new(Field::new(names.row, DataType::UInt64, false)));
+            columns.push(Arc::new(UInt64Array::from_iter_values(
+                next_row..next_row + rows,
+            )));
+            next_row += rows;
+
+            if let Some(file) = file {
+                fields.push(Arc::new(Field::new(names.file, DataType::Utf8, false)));
+                columns.push(Arc::new(StringArray::from_iter_values(
+                    std::iter::repeat_n(file, batch.num_rows()),
+                )));
+            }
+
+            fields.extend(schema.fields().iter().cloned());
+            columns.extend(batch.columns().iter().cloned());
+            RecordBatch::try_new(
+                Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+                columns,
+            )
+        })
+        .collect()
+}
+
+/// Prepend `file` as a constant column named `name`.
+pub(crate) fn prepend_file(
+    batches: &[RecordBatch],
+    name: &str,
+    file: &str,
+) -> Result<Vec<RecordBatch>, ArrowError> {
+    batches
+        .iter()
+        .map(|batch| {
+            let schema = batch.schema();
+            let mut fields = Vec::with_capacity(schema.fields().len() + 1);
+            let mut columns: Vec<ArrayRef> = Vec::with_capacity(batch.num_columns() + 1);
+
+            fields.push(Arc::new(Field::new(name, DataType::Utf8, false)));
+            columns.push(Arc::new(StringArray::from_iter_values(
+                std::iter::repeat_n(file, batch.num_rows()),
+            )));
+
+            fields.extend(schema.fields().iter().cloned());
+            columns.extend(batch.columns().iter().cloned());
+            RecordBatch::try_new(
+                Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+                columns,
+            )
+        })
+        .collect()
+}
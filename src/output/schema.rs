@@ -1,51 +1,192 @@
-use crate::model::{ColumnInfo, SchemaResult};
-use crate::output::csv_support::escape_csv;
+use crate::model::{ColumnInfo, SchemaResult, TypeConflict, UnifiedColumn, UnifiedSchema};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::path_text::display_path;
 use crate::Result;
+use comfy_table::{Cell, Table};
 use std::io::Write;
+use std::path::PathBuf;
+
+const EXTENDED_CSV_HEADER: [&str; 4] = [
+    "ordinal",
+    "max_definition_level",
+    "max_repetition_level",
+    "field_id",
+];
 
 pub fn write_csv<W: Write>(
-    mut writer: W,
+    writer: W,
     columns: &[ColumnInfo],
-    include_header: bool,
+    dialect: &CsvDialect,
+    extended: bool,
 ) -> Result<()> {
-    if include_header {
-        writeln!(writer, "column,type,nullable")?;
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let mut header = vec!["column", "type", "nullable"];
+    if extended {
+        header.extend(EXTENDED_CSV_HEADER);
     }
+    writer.write_header(header)?;
 
     for column in columns {
-        writeln!(
-            writer,
-            "{},{},{}",
-            escape_csv(&column.name),
-            escape_csv(&column.display_type()),
-            column.nullable
-        )?;
+        let mut record = vec![
+            column.name.clone(),
+            column.display_type(),
+            column.nullable.to_string(),
+        ];
+        if extended {
+            record.extend(extended_cells(column));
+        }
+        writer.write_record(record)?;
     }
 
+    writer.flush()?;
     Ok(())
 }
 
 pub fn write_csv_results<W: Write>(
-    mut writer: W,
+    writer: W,
     results: &[SchemaResult],
-    include_header: bool,
+    dialect: &CsvDialect,
+    extended: bool,
 ) -> Result<()> {
-    if include_header {
-        writeln!(writer, "file,column,type,nullable")?;
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let mut header = vec!["file", "column", "type", "nullable"];
+    if extended {
+        header.extend(EXTENDED_CSV_HEADER);
     }
+    writer.write_header(header)?;
 
     for result in results {
         for column in &result.columns {
-            writeln!(
-                writer,
-                "{},{},{},{}",
-                escape_csv(&result.path.display().to_string()),
-                escape_csv(&column.name),
-                escape_csv(&column.display_type()),
-                column.nullable
-            )?;
+            let mut record = vec![
+                display_path(&result.path),
+                column.name.clone(),
+                column.display_type(),
+                column.nullable.to_string(),
+            ];
+            if extended {
+                record.extend(extended_cells(column));
+            }
+            writer.write_record(record)?;
+        }
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Ordinal, definition and repetition levels, and field ID; a column without a field
+/// ID gets an empty cell.
+pub fn extended_cells(column: &ColumnInfo) -> [String; 4] {
+    [
+        column.ordinal.to_string(),
+        column.max_def_level.to_string(),
+        column.max_rep_level.to_string(),
+        column.field_id.map(|id| id.to_string()).unwrap_or_default(),
+    ]
+}
+
+/// Each column once, with one line per group of files that lack it or declare it
+/// differently.
+pub fn write_unified_table<W: Write>(
+    mut writer: W,
+    unified: &UnifiedSchema,
+    no_header: bool,
+) -> Result<()> {
+    let mut table = Table::new();
+
+    if !no_header {
+        table.set_header(vec![
+            Cell::new("Column"),
+            Cell::new("Type"),
+            Cell::new("Nullable"),
+            Cell::new("Differs In"),
+        ]);
+    }
+
+    for column in &unified.columns {
+        let differences = deviations(column)
+            .map(|(label, files)| format!("{label}: {}", file_list(files)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        table.add_row(vec![
+            Cell::new(&column.name),
+            Cell::new(column.display_type()),
+            Cell::new(if column.nullable { "Yes" } else { "No" }),
+            Cell::new(differences),
+        ]);
+    }
+
+    writeln!(writer, "{table}")?;
+    Ok(())
+}
+
+/// One row per deviating file, and a single row with empty `issue` and `file` for a
+/// column every file agrees on.
+pub fn write_unified_csv<W: Write>(
+    writer: W,
+    unified: &UnifiedSchema,
+    dialect: &CsvDialect,
+) -> Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(["column", "type", "nullable", "issue", "file"])?;
+
+    for column in &unified.columns {
+        let fields = |issue: &str, file: String| {
+            [
+                column.name.clone(),
+                column.display_type(),
+                column.nullable.to_string(),
+                issue.to_string(),
+                file,
+            ]
+        };
+        if column.is_compatible() {
+            writer.write_record(fields("", String::new()))?;
+        }
+        for (label, files) in deviations(column) {
+            for file in files {
+                writer.write_record(fields(&label, display_path(file)))?;
+            }
         }
     }
 
+    writer.flush()?;
     Ok(())
 }
+
+/// `missing`, then each conflicting declaration labelled by what differs from the
+/// unified column: its type, its nullability, or both.
+fn deviations(column: &UnifiedColumn) -> impl Iterator<Item = (String, &[PathBuf])> {
+    let missing = (!column.files_missing.is_empty())
+        .then(|| ("missing".to_string(), column.files_missing.as_slice()));
+    missing.into_iter().chain(
+        column
+            .type_conflicts
+            .iter()
+            .map(|conflict| (conflict_label(column, conflict), conflict.files.as_slice())),
+    )
+}
+
+fn conflict_label(column: &UnifiedColumn, conflict: &TypeConflict) -> String {
+    let nullability = if conflict.nullable {
+        "nullable"
+    } else {
+        "required"
+    };
+    match (
+        conflict.column_type != column.column_type,
+        conflict.nullable != column.nullable,
+    ) {
+        (true, true) => format!("{} {nullability}", conflict.display_type()),
+        (true, false) => conflict.display_type(),
+        _ => nullability.to_string(),
+    }
+}
+
+fn file_list(files: &[PathBuf]) -> String {
+    files
+        .iter()
+        .map(display_path)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
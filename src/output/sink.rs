@@ -0,0 +1,172 @@
+//! Byte sinks for streamed text output, optionally compressed
+
+use flate2::write::GzEncoder;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+const DEFAULT_GZIP_LEVEL: u32 = 6;
+const DEFAULT_ZSTD_LEVEL: i32 = 3;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TextCompression {
+    Gzip { level: u32 },
+    Zstd { level: i32 },
+}
+
+impl TextCompression {
+    /// Recognize a trailing `.gz` or `.zst` extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("gz") => Some(Self::Gzip {
+                level: DEFAULT_GZIP_LEVEL,
+            }),
+            Some("zst") => Some(Self::Zstd {
+                level: DEFAULT_ZSTD_LEVEL,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gzip { .. } => "gzip",
+            Self::Zstd { .. } => "zstd",
+        }
+    }
+
+    /// The extension `from_path` recognizes, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gzip { .. } => "gz",
+            Self::Zstd { .. } => "zst",
+        }
+    }
+}
+
+impl FromStr for TextCompression {
+    type Err = String;
+
+    /// Parse `gzip`, `zstd`, or either with a `:level` suffix.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (codec, level) = match text.split_once(':') {
+            Some((codec, level)) => (codec, Some(level)),
+            None => (text, None),
+        };
+        let invalid_level = |range: &str| {
+            format!(
+                "invalid {codec} level '{}' (expected {range})",
+                level.unwrap_or("")
+            )
+        };
+
+        match codec.to_lowercase().as_str() {
+            "gzip" | "gz" => {
+                let level = match level {
+                    Some(level) => level
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|level| *level <= 9)
+                        .ok_or_else(|| invalid_level("0-9"))?,
+                    None => DEFAULT_GZIP_LEVEL,
+                };
+                Ok(Self::Gzip { level })
+            }
+            "zstd" | "zst" => {
+                let level = match level {
+                    Some(level) => level
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|level| zstd::compression_level_range().contains(level))
+                        .ok_or_else(|| invalid_level("1-22"))?,
+                    None => DEFAULT_ZSTD_LEVEL,
+                };
+                Ok(Self::Zstd { level })
+            }
+            _ => Err(format!(
+                "unknown compression '{text}' (expected gzip or zstd, optionally with :level)"
+            )),
+        }
+    }
+}
+
+/// Buffered output that must be finished explicitly so compressed streams get their trailer
+/// and any write error surfaces instead of being dropped.
+pub(crate) struct Sink {
+    inner: SinkKind,
+}
+
+enum SinkKind {
+    Plain(BufWriter<Box<dyn Write + Send>>),
+    Gzip(GzEncoder<BufWriter<Box<dyn Write + Send>>>),
+    Zstd(zstd::Encoder<'static, BufWriter<Box<dyn Write + Send>>>),
+}
+
+impl Sink {
+    pub fn new(
+        writer: Box<dyn Write + Send>,
+        compression: Option<TextCompression>,
+    ) -> io::Result<Self> {
+        let writer = BufWriter::new(writer);
+        let inner = match compression {
+            None => SinkKind::Plain(writer),
+            Some(TextCompression::Gzip { level }) => {
+                SinkKind::Gzip(GzEncoder::new(writer, flate2::Compression::new(level)))
+            }
+            Some(TextCompression::Zstd { level }) => {
+                SinkKind::Zstd(zstd::Encoder::new(writer, level)?)
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        match self.inner {
+            SinkKind::Plain(mut writer) => writer.flush(),
+            SinkKind::Gzip(encoder) => encoder.finish()?.flush(),
+            SinkKind::Zstd(encoder) => encoder.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for Sink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.inner {
+            SinkKind::Plain(writer) => writer.write(buf),
+            SinkKind::Gzip(encoder) => encoder.write(buf),
+            SinkKind::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.inner {
+            SinkKind::Plain(writer) => writer.flush(),
+            SinkKind::Gzip(encoder) => encoder.flush(),
+            SinkKind::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compression_with_optional_level() {
+        assert_eq!(
+            "gzip".parse::<TextCompression>(),
+            Ok(TextCompression::Gzip { level: 6 })
+        );
+        assert_eq!(
+            "zstd:19".parse::<TextCompression>(),
+            Ok(TextCompression::Zstd { level: 19 })
+        );
+        assert!("gzip:12".parse::<TextCompression>().is_err());
+        assert!("brotli".parse::<TextCompression>().is_err());
+    }
+}
@@ -1,102 +1,264 @@
-use crate::model::{ColumnStats, StatsResult};
-use crate::output::csv_support::escape_csv;
+use crate::model::{ColumnStats, RowGroupStats, RowGroupStatsResult, StatValue, StatsResult};
+use crate::output::binary::{stat_value_text, BinaryEncoding};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::output::info::format_size;
+use crate::path_text::display_path;
 use comfy_table::{Cell, Table};
 use std::io::Write;
 
+/// Merged statistics with each column's sizes and its share of the file's compressed
+/// column data. Bounds longer than `value_width` characters are cut short.
 pub fn write_table<W: Write>(
     mut writer: W,
-    rows: &[ColumnStats],
-    quiet: bool,
+    result: &StatsResult,
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+    value_width: Option<usize>,
 ) -> std::io::Result<()> {
     let mut table = Table::new();
-    if !quiet {
-        table.set_header(vec!["Column", "Type", "Nulls", "Min", "Max"]);
+    if !no_header {
+        table.set_header(vec![
+            "Column",
+            "Type",
+            "Nulls",
+            "Min",
+            "Max",
+            "Compressed",
+            "Uncompressed",
+            "Share",
+        ]);
     }
 
-    for row in rows {
+    for row in &result.rows {
         table.add_row(vec![
             Cell::new(&row.column),
             Cell::new(row.display_type()),
-            Cell::new(row.null_count),
-            Cell::new(
-                row.min
-                    .as_ref()
-                    .map_or_else(|| "N/A".to_string(), |value| row.display_stat_value(value)),
-            ),
-            Cell::new(
-                row.max
-                    .as_ref()
-                    .map_or_else(|| "N/A".to_string(), |value| row.display_stat_value(value)),
-            ),
+            Cell::new(nulls_preview(row)),
+            Cell::new(bound_preview(
+                row,
+                row.min.as_ref(),
+                row.min_truncated,
+                binary,
+                value_width,
+            )),
+            Cell::new(bound_preview(
+                row,
+                row.max.as_ref(),
+                row.max_truncated,
+                binary,
+                value_width,
+            )),
+            Cell::new(format_size(row.compressed_bytes)),
+            Cell::new(format_size(row.uncompressed_bytes)),
+            Cell::new(format!("{:.1}%", result.compressed_percent(row))),
         ]);
     }
 
     writeln!(writer, "{table}")
 }
 
+const SIZE_HEADER: [&str; 3] = [
+    "compressed_bytes",
+    "uncompressed_bytes",
+    "compressed_percent",
+];
+
 pub fn write_csv<W: Write>(
-    mut writer: W,
-    rows: &[ColumnStats],
-    include_header: bool,
+    writer: W,
+    result: &StatsResult,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
 ) -> std::io::Result<()> {
-    if include_header {
-        writeln!(writer, "column,type,null_count,min,max")?;
-    }
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(
+        ["column", "type", "null_count", "min", "max"]
+            .into_iter()
+            .chain(SIZE_HEADER),
+    )?;
 
-    for row in rows {
-        writeln!(
-            writer,
-            "{},{},{},{},{}",
-            escape_csv(&row.column),
-            escape_csv(&row.display_type()),
-            row.null_count,
-            escape_csv(
-                &row.min
-                    .as_ref()
-                    .map_or_else(String::new, |value| row.display_stat_value(value))
-            ),
-            escape_csv(
-                &row.max
-                    .as_ref()
-                    .map_or_else(String::new, |value| row.display_stat_value(value))
-            ),
-        )?;
+    for row in &result.rows {
+        let mut fields = stats_fields(row, binary).to_vec();
+        fields.extend(size_fields(result, row));
+        writer.write_record(&fields)?;
     }
 
-    Ok(())
+    writer.flush()
 }
 
 pub fn write_csv_results<W: Write>(
-    mut writer: W,
+    writer: W,
     results: &[StatsResult],
-    include_header: bool,
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
 ) -> std::io::Result<()> {
-    if include_header {
-        writeln!(writer, "file,column,type,null_count,min,max")?;
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header(
+        ["file", "column", "type", "null_count", "min", "max"]
+            .into_iter()
+            .chain(SIZE_HEADER),
+    )?;
+
+    for result in results {
+        for row in &result.rows {
+            let mut fields = vec![display_path(&result.path)];
+            fields.extend(stats_fields(row, binary));
+            fields.extend(size_fields(result, row));
+            writer.write_record(&fields)?;
+        }
     }
 
+    writer.flush()
+}
+
+/// One row per row group and column; groups written without statistics say so.
+pub fn write_row_group_table<W: Write>(
+    mut writer: W,
+    rows: &[RowGroupStats],
+    no_header: bool,
+    binary: Option<BinaryEncoding>,
+    value_width: Option<usize>,
+) -> std::io::Result<()> {
+    let mut table = Table::new();
+    if !no_header {
+        table.set_header(vec![
+            "Row Group",
+            "Rows",
+            "Column",
+            "Type",
+            "Nulls",
+            "Min",
+            "Max",
+        ]);
+    }
+
+    for row in rows {
+        let stats = &row.stats;
+        let bound = |value: Option<&StatValue>, truncated: bool| {
+            if !row.has_statistics {
+                return "no stats".to_string();
+            }
+            bound_preview(stats, value, truncated, binary, value_width)
+        };
+        let nulls = if row.has_statistics {
+            nulls_preview(stats)
+        } else {
+            "no stats".to_string()
+        };
+        table.add_row(vec![
+            Cell::new(row.row_group),
+            Cell::new(row.num_rows),
+            Cell::new(&stats.column),
+            Cell::new(stats.display_type()),
+            Cell::new(nulls),
+            Cell::new(bound(stats.min.as_ref(), stats.min_truncated)),
+            Cell::new(bound(stats.max.as_ref(), stats.max_truncated)),
+        ]);
+    }
+
+    writeln!(writer, "{table}")
+}
+
+/// Row group statistics as CSV, with a leading `file` column when there are several
+/// files. Groups without statistics have empty null counts and bounds.
+pub fn write_row_group_csv<W: Write>(
+    writer: W,
+    results: &[RowGroupStatsResult],
+    dialect: &CsvDialect,
+    binary: Option<BinaryEncoding>,
+) -> std::io::Result<()> {
+    let with_file = results.len() > 1;
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    let header = [
+        "file",
+        "row_group",
+        "rows",
+        "statistics",
+        "column",
+        "type",
+        "null_count",
+        "min",
+        "max",
+    ];
+    writer.write_header(header.iter().skip(usize::from(!with_file)))?;
+
     for result in results {
         for row in &result.rows {
-            writeln!(
-                writer,
-                "{},{},{},{},{},{}",
-                escape_csv(&result.path.display().to_string()),
-                escape_csv(&row.column),
-                escape_csv(&row.display_type()),
-                row.null_count,
-                escape_csv(
-                    &row.min
-                        .as_ref()
-                        .map_or_else(String::new, |value| row.display_stat_value(value))
-                ),
-                escape_csv(
-                    &row.max
-                        .as_ref()
-                        .map_or_else(String::new, |value| row.display_stat_value(value))
-                ),
-            )?;
+            let mut fields = Vec::with_capacity(9);
+            if with_file {
+                fields.push(display_path(&result.path));
+            }
+            fields.push(row.row_group.to_string());
+            fields.push(row.num_rows.to_string());
+            fields.push(row.has_statistics.to_string());
+            let [column, display_type, null_count, min, max] = stats_fields(&row.stats, binary);
+            let null_count = if row.has_statistics {
+                null_count
+            } else {
+                String::new()
+            };
+            fields.extend([column, display_type, null_count, min, max]);
+            writer.write_record(&fields)?;
         }
     }
 
-    Ok(())
+    writer.flush()
+}
+
+/// A bound as tables show it: at most `width` characters then `…`, marked `≈` when
+/// the writer stored it truncated, so it only approximates the column's true bound, and
+/// `!` when the column's bounds cannot be right.
+fn bound_preview(
+    stats: &ColumnStats,
+    value: Option<&StatValue>,
+    truncated: bool,
+    binary: Option<BinaryEncoding>,
+    width: Option<usize>,
+) -> String {
+    let Some(value) = value else {
+        return "N/A".to_string();
+    };
+    let mut text = stat_value_text(&stats.column_type, value, binary);
+    if let Some((cut, _)) = width.and_then(|width| text.char_indices().nth(width)) {
+        text.truncate(cut);
+        text.push('…');
+    }
+    if truncated {
+        text.insert_str(0, "≈ ");
+    }
+    if stats.suspicious_bounds() {
+        text.push_str(" !");
+    }
+    text
+}
+
+/// A null count as tables show it, marked `!` when a row group claims more nulls than rows.
+fn nulls_preview(stats: &ColumnStats) -> String {
+    if stats.suspicious_nulls() {
+        format!("{} !", stats.null_count)
+    } else {
+        stats.null_count.to_string()
+    }
+}
+
+fn stats_fields(row: &ColumnStats, binary: Option<BinaryEncoding>) -> [String; 5] {
+    let bound = |value: Option<&StatValue>| {
+        value.map_or_else(String::new, |value| {
+            stat_value_text(&row.column_type, value, binary)
+        })
+    };
+    [
+        row.column.clone(),
+        row.display_type(),
+        row.null_count.to_string(),
+        bound(row.min.as_ref()),
+        bound(row.max.as_ref()),
+    ]
+}
+
+fn size_fields(result: &StatsResult, row: &ColumnStats) -> [String; 3] {
+    [
+        row.compressed_bytes.to_string(),
+        row.uncompressed_bytes.to_string(),
+        result.compressed_percent(row).to_string(),
+    ]
 }
@@ -0,0 +1,173 @@
+//! Statistics comparison output
+//!
+//! Tables, CSV, and JSONL share one row per statistic: the row count first, then each
+//! shared column's null count, min, and max, then a `presence` row for each column only
+//! one file has.
+
+use crate::model::{StatComparison, StatsDiff};
+use crate::output::csv_support::{CsvDialect, CsvRecordWriter};
+use crate::Result;
+use comfy_table::{Cell, Table};
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+pub struct ComparisonRow {
+    pub column: Option<String>,
+    pub statistic: &'static str,
+    #[serde(flatten)]
+    pub comparison: ComparisonJson,
+}
+
+#[derive(Serialize)]
+pub struct ComparisonJson {
+    before: Option<String>,
+    after: Option<String>,
+    delta: Option<f64>,
+    delta_percent: Option<f64>,
+    changed: bool,
+    exceeds_tolerance: bool,
+}
+
+impl From<&StatComparison> for ComparisonJson {
+    fn from(comparison: &StatComparison) -> Self {
+        Self {
+            before: comparison.before.clone(),
+            after: comparison.after.clone(),
+            delta: comparison.delta,
+            delta_percent: comparison.delta_percent,
+            changed: comparison.changed(),
+            exceeds_tolerance: comparison.exceeds_tolerance,
+        }
+    }
+}
+
+pub fn rows(diff: &StatsDiff) -> Vec<ComparisonRow> {
+    let mut rows = vec![ComparisonRow {
+        column: None,
+        statistic: "row_count",
+        comparison: (&diff.row_count).into(),
+    }];
+    for column in &diff.columns {
+        for (statistic, comparison) in column.comparisons() {
+            rows.push(ComparisonRow {
+                column: Some(column.column.clone()),
+                statistic,
+                comparison: comparison.into(),
+            });
+        }
+    }
+    let presence = |column: &String, in_old: bool| ComparisonRow {
+        column: Some(column.clone()),
+        statistic: "presence",
+        comparison: ComparisonJson {
+            before: in_old.then(|| "present".to_string()),
+            after: (!in_old).then(|| "present".to_string()),
+            delta: None,
+            delta_percent: None,
+            changed: true,
+            exceeds_tolerance: diff.tolerance.is_some(),
+        },
+    };
+    rows.extend(diff.only_in_old.iter().map(|column| presence(column, true)));
+    rows.extend(
+        diff.only_in_new
+            .iter()
+            .map(|column| presence(column, false)),
+    );
+    rows
+}
+
+/// How far a changed statistic moved: a percentage when there is one, otherwise the
+/// difference, and for columns whether they were added or removed.
+fn change_label(row: &ComparisonRow) -> String {
+    let comparison = &row.comparison;
+    if row.statistic == "presence" {
+        return if comparison.after.is_some() {
+            "added"
+        } else {
+            "removed"
+        }
+        .to_string();
+    }
+    match (comparison.delta_percent, comparison.delta) {
+        (Some(percent), _) => format!("{percent:+.2}%"),
+        (None, Some(delta)) => format!("{delta:+}"),
+        (None, None) => String::new(),
+    }
+}
+
+/// Only the statistics that changed; an `Exceeds` column marks those beyond the
+/// tolerance, when there is one.
+pub fn write_table<W: Write>(mut writer: W, diff: &StatsDiff, no_header: bool) -> Result<()> {
+    let changed: Vec<ComparisonRow> = rows(diff)
+        .into_iter()
+        .filter(|row| row.comparison.changed)
+        .collect();
+    if changed.is_empty() {
+        writeln!(writer, "No statistics changed")?;
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    if !no_header {
+        let mut header = vec!["Column", "Statistic", "Before", "After", "Change"];
+        if diff.tolerance.is_some() {
+            header.push("Exceeds");
+        }
+        table.set_header(header.into_iter().map(Cell::new));
+    }
+
+    for row in &changed {
+        let mut cells = vec![
+            Cell::new(row.column.as_deref().unwrap_or("")),
+            Cell::new(row.statistic),
+            Cell::new(row.comparison.before.as_deref().unwrap_or("")),
+            Cell::new(row.comparison.after.as_deref().unwrap_or("")),
+            Cell::new(change_label(row)),
+        ];
+        if diff.tolerance.is_some() {
+            cells.push(Cell::new(if row.comparison.exceeds_tolerance {
+                "yes"
+            } else {
+                ""
+            }));
+        }
+        table.add_row(cells);
+    }
+
+    writeln!(writer, "{table}")?;
+    Ok(())
+}
+
+pub fn write_csv<W: Write>(writer: W, diff: &StatsDiff, dialect: &CsvDialect) -> Result<()> {
+    let mut writer = CsvRecordWriter::new(writer, dialect);
+    writer.write_header([
+        "column",
+        "statistic",
+        "before",
+        "after",
+        "delta",
+        "delta_percent",
+        "changed",
+        "exceeds_tolerance",
+    ])?;
+
+    let number = |value: Option<f64>| value.map(|value| value.to_string()).unwrap_or_default();
+    for row in rows(diff) {
+        let comparison = row.comparison;
+        writer.write_record([
+            row.column.unwrap_or_default(),
+            row.statistic.to_string(),
+            comparison.before.unwrap_or_default(),
+            comparison.after.unwrap_or_default(),
+            number(comparison.delta),
+            number(comparison.delta_percent),
+            comparison.changed.to_string(),
+            comparison.exceeds_tolerance.to_string(),
+        ])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
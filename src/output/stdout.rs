@@ -0,0 +1,99 @@
+//! Where data output goes: stdout, or the file `-O, --output-file` names
+//!
+//! Every command writes its data through [`stdout`], so redirecting it once sends any
+//! command's output to the file while warnings and progress stay on stderr. The file
+//! is written beside its target and renamed into place only when the command succeeds.
+
+use crate::atomic_output::{check_destination, PendingOutput};
+use crate::{PqError, Result};
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+// Set once from the command line before the command runs
+static REDIRECT: Mutex<Option<BufWriter<File>>> = Mutex::new(None);
+
+fn redirect() -> MutexGuard<'static, Option<BufWriter<File>>> {
+    REDIRECT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The command's data output: stdout, unless `--output-file` redirected it.
+#[derive(Debug)]
+pub(crate) struct Stdout;
+
+pub(crate) fn stdout() -> Stdout {
+    Stdout
+}
+
+/// Whether data output reaches a terminal; a redirected one never does.
+pub(crate) fn stdout_is_terminal() -> bool {
+    redirect().is_none() && io::stdout().is_terminal()
+}
+
+impl Write for Stdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match redirect().as_mut() {
+            Some(file) => file.write(buf),
+            None => io::stdout().write(buf),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match redirect().as_mut() {
+            Some(file) => file.write_all(buf),
+            None => io::stdout().lock().write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match redirect().as_mut() {
+            Some(file) => file.flush(),
+            None => io::stdout().flush(),
+        }
+    }
+}
+
+/// The file data output goes to while a command runs. Dropping it uncommitted, as a
+/// failed command does, removes the partial file and leaves any earlier one in place.
+#[derive(Debug)]
+pub(crate) struct OutputFile {
+    pending: Option<PendingOutput>,
+}
+
+impl OutputFile {
+    /// Redirect data output to `path`, refusing one of `inputs` and, unless `overwrite`,
+    /// an existing file.
+    pub fn create(path: &Path, inputs: &[&Path], overwrite: bool) -> Result<Self> {
+        check_destination(path, inputs, overwrite)?;
+        let pending = PendingOutput::new(path)?;
+        let file =
+            File::create(pending.path()).map_err(|error| PqError::write_error(path, error))?;
+        *redirect() = Some(BufWriter::new(file));
+        Ok(Self {
+            pending: Some(pending),
+        })
+    }
+
+    /// Flush what the command wrote and move the file into place.
+    pub fn commit(mut self) -> Result<()> {
+        let writer = redirect().take();
+        let Some(pending) = self.pending.take() else {
+            return Ok(());
+        };
+        if let Some(writer) = writer {
+            writer
+                .into_inner()
+                .map_err(|error| PqError::write_error(pending.path(), error.error()))?
+                .sync_all()
+                .map_err(|error| PqError::write_error(pending.path(), error))?;
+        }
+        pending.commit()
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        redirect().take();
+    }
+}
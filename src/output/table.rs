@@ -1,47 +1,226 @@
 //! Pretty table formatting using comfy-table
 
+use super::values::TextValues;
 use crate::model::ColumnInfo;
 use crate::Result;
 use arrow::array::RecordBatch;
-use comfy_table::{Cell, Table};
+use arrow::util::display::FormatOptions;
+use comfy_table::{Cell, ContentArrangement, Table};
+use std::borrow::Cow;
 use std::io::Write;
 
-pub fn write_batches<W: Write>(mut writer: W, batches: &[RecordBatch], quiet: bool) -> Result<()> {
-    if batches.is_empty() {
+/// Narrowest a column can get, padding and border included, before `auto-vertical`
+/// gives up on the grid.
+const MIN_COLUMN_WIDTH: usize = 9;
+
+/// What to do with a table wider than the terminal.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum TableOverflow {
+    /// Wrap cells onto as many lines as they need.
+    Wrap,
+    /// Keep one line per row, cutting long cells short with an ellipsis.
+    #[default]
+    Truncate,
+    /// Truncate, or print one record per block when the columns cannot fit at all.
+    AutoVertical,
+}
+
+/// The width a table has to fit in, and how to make it fit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct TableFit {
+    pub width: u16,
+    pub overflow: TableOverflow,
+}
+
+impl TableFit {
+    /// The terminal's width when stdout is one; piped output is never constrained.
+    pub fn stdout(overflow: TableOverflow) -> Option<Self> {
+        terminal_width().map(|width| Self { width, overflow })
+    }
+}
+
+/// Columns of the terminal on stdout, if stdout is a terminal.
+pub(crate) fn terminal_width() -> Option<u16> {
+    if !super::stdout_is_terminal() {
+        return None;
+    }
+    crossterm::terminal::size()
+        .ok()
+        .map(|(columns, _)| columns)
+        .filter(|columns| *columns > 0)
+}
+
+/// Text that cannot change the terminal's state: C0 and C1 control characters, the
+/// escape that starts an ANSI sequence among them, become visible `\x1b`-style escapes.
+/// Newlines stay, since cells already span lines for them.
+pub(crate) fn escape_control_chars(text: &str) -> Cow<'_, str> {
+    let escaped = |c: char| c != '\n' && c.is_control();
+    if !text.contains(escaped) {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        if escaped(c) {
+            out.push_str(&format!("\\x{:02x}", u32::from(c)));
+        } else {
+            out.push(c);
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// How `write_batches` lays out record batches.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct TableOptions {
+    /// Leave out the row of column names.
+    pub no_header: bool,
+    /// The width to fit in; unconstrained when unset.
+    pub fit: Option<TableFit>,
+    /// Print control characters as they are instead of escaping them.
+    pub raw_control_chars: bool,
+    /// Group the digits of integer columns in thousands.
+    pub human_readable: bool,
+}
+
+/// Values and column names are escaped with `escape_control_chars` unless
+/// `options.raw_control_chars` asks for them as they are.
+pub fn write_batches<W: Write>(
+    mut writer: W,
+    batches: &[RecordBatch],
+    options: &TableOptions,
+) -> Result<()> {
+    let TableOptions {
+        no_header,
+        fit,
+        raw_control_chars,
+        human_readable,
+    } = *options;
+    let text = |value: &str| -> String {
+        if raw_control_chars {
+            value.to_string()
+        } else {
+            escape_control_chars(value).into_owned()
+        }
+    };
+    let Some(first) = batches.first() else {
+        return Ok(());
+    };
+    // Without a header there is nothing to show, and comfy-table would draw an empty box
+    if no_header && batches.iter().all(|batch| batch.num_rows() == 0) {
         return Ok(());
     }
 
-    let schema = batches[0].schema();
+    let schema = first.schema();
+    if let Some(fit) = fit {
+        if fit.overflow == TableOverflow::AutoVertical
+            && schema.fields().len() * MIN_COLUMN_WIDTH + 1 > usize::from(fit.width)
+        {
+            return write_vertical(writer, batches, text, human_readable);
+        }
+    }
     let mut table = Table::new();
 
-    if !quiet {
-        table.set_header(schema.fields().iter().map(|field| Cell::new(field.name())));
+    if !no_header {
+        table.set_header(
+            schema
+                .fields()
+                .iter()
+                .map(|field| Cell::new(text(field.name()))),
+        );
     }
 
+    let options = FormatOptions::default();
     for batch in batches {
+        let values = TextValues::new(batch, &options)?.group_integers(batch, human_readable);
         for row_idx in 0..batch.num_rows() {
             let mut row = Vec::new();
             for col_idx in 0..batch.num_columns() {
-                let col = batch.column(col_idx);
-                let value = arrow::util::display::array_value_to_string(col, row_idx)?;
-                row.push(Cell::new(value));
+                let value = values.format_value(col_idx, row_idx)?;
+                row.push(Cell::new(text(&value)));
             }
             table.add_row(row);
         }
     }
 
+    if let Some(fit) = fit {
+        fit_to_width(&mut table, fit);
+    }
     writeln!(writer, "{table}")?;
     Ok(())
 }
 
+/// Squeeze `table` into `fit.width` when its natural width is larger.
+fn fit_to_width(table: &mut Table, fit: TableFit) {
+    let widths = table.column_max_content_widths();
+    // Each column has a space of padding either side and a border to its left, and the
+    // table has one more border on the right
+    let natural: usize = widths
+        .iter()
+        .map(|width| usize::from(*width) + 3)
+        .sum::<usize>()
+        + 1;
+    if natural <= usize::from(fit.width) {
+        return;
+    }
+
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(fit.width);
+    if fit.overflow != TableOverflow::Wrap {
+        table.set_truncation_indicator("…");
+        for row in table.row_iter_mut() {
+            row.max_height(1);
+        }
+    }
+}
+
+/// One block per row with a `name | value` line per column, like psql's expanded
+/// display.
+fn write_vertical<W: Write>(
+    mut writer: W,
+    batches: &[RecordBatch],
+    text: impl Fn(&str) -> String,
+    human_readable: bool,
+) -> Result<()> {
+    let Some(first) = batches.first() else {
+        return Ok(());
+    };
+    let schema = first.schema();
+    let names: Vec<String> = schema
+        .fields()
+        .iter()
+        .map(|field| text(field.name()))
+        .collect();
+    let name_width = names
+        .iter()
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let options = FormatOptions::default();
+    let mut record = 0usize;
+    for batch in batches {
+        let values = TextValues::new(batch, &options)?.group_integers(batch, human_readable);
+        for row_idx in 0..batch.num_rows() {
+            record += 1;
+            writeln!(writer, "-[ RECORD {record} ]{}", "-".repeat(name_width))?;
+            for (col_idx, name) in names.iter().enumerate() {
+                let value = values.format_value(col_idx, row_idx)?;
+                writeln!(writer, "{name:<name_width$} | {}", text(&value))?;
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn write_key_value<W: Write>(
     mut writer: W,
     rows: &[(&str, String)],
-    quiet: bool,
+    no_header: bool,
 ) -> Result<()> {
     let mut table = Table::new();
 
-    if !quiet {
+    if !no_header {
         table.set_header(vec![Cell::new("Key"), Cell::new("Value")]);
     }
 
@@ -56,26 +235,148 @@ pub fn write_key_value<W: Write>(
 pub fn write_schema_table<W: Write>(
     mut writer: W,
     columns: &[ColumnInfo],
-    quiet: bool,
+    no_header: bool,
+    extended: bool,
 ) -> Result<()> {
     let mut table = Table::new();
 
-    if !quiet {
-        table.set_header(vec![
+    if !no_header {
+        let mut header = vec![
             Cell::new("Column"),
             Cell::new("Type"),
             Cell::new("Nullable"),
-        ]);
+        ];
+        if extended {
+            header.extend(
+                ["Ordinal", "Max Def", "Max Rep", "Field ID"]
+                    .into_iter()
+                    .map(Cell::new),
+            );
+        }
+        table.set_header(header);
     }
 
     for column in columns {
-        table.add_row(vec![
+        let mut row = vec![
             Cell::new(&column.name),
             Cell::new(column.display_type()),
             Cell::new(if column.nullable { "Yes" } else { "No" }),
-        ]);
+        ];
+        if extended {
+            row.extend(
+                super::schema::extended_cells(column)
+                    .into_iter()
+                    .map(Cell::new),
+            );
+        }
+        table.add_row(row);
     }
 
     writeln!(writer, "{table}")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{ArrayRef, Int64Array, StringArray};
+    use std::sync::Arc;
+
+    fn batch(columns: usize) -> Result<RecordBatch> {
+        let mut fields: Vec<(String, ArrayRef)> = vec![(
+            "note".to_string(),
+            Arc::new(StringArray::from(vec![
+                "a rather long note that cannot fit",
+            ])),
+        )];
+        for index in 1..columns {
+            fields.push((
+                format!("col_{index}"),
+                Arc::new(Int64Array::from(vec![index as i64])),
+            ));
+        }
+        Ok(RecordBatch::try_from_iter(fields)?)
+    }
+
+    fn render(batch: &RecordBatch, fit: Option<TableFit>) -> Result<String> {
+        let mut out = Vec::new();
+        let options = TableOptions {
+            fit,
+            ..TableOptions::default()
+        };
+        write_batches(&mut out, std::slice::from_ref(batch), &options)?;
+        Ok(String::from_utf8_lossy(&out).into_owned())
+    }
+
+    fn fit(width: u16, overflow: TableOverflow) -> Option<TableFit> {
+        Some(TableFit { width, overflow })
+    }
+
+    #[test]
+    fn unconstrained_tables_keep_their_natural_width() -> Result<()> {
+        let batch = batch(2)?;
+        let natural = render(&batch, None)?;
+        assert!(natural.contains("a rather long note that cannot fit"));
+        assert_eq!(render(&batch, fit(200, TableOverflow::Truncate))?, natural);
+        Ok(())
+    }
+
+    #[test]
+    fn truncates_to_the_width_with_one_line_per_row() -> Result<()> {
+        let table = render(&batch(2)?, fit(30, TableOverflow::Truncate))?;
+        assert!(
+            table.lines().all(|line| line.chars().count() <= 30),
+            "{table}"
+        );
+        assert!(table.contains('…'), "{table}");
+        // Top border, header, header separator, one row, bottom border
+        assert_eq!(table.lines().count(), 5, "{table}");
+        Ok(())
+    }
+
+    #[test]
+    fn wraps_cells_when_asked() -> Result<()> {
+        let table = render(&batch(2)?, fit(30, TableOverflow::Wrap))?;
+        assert!(
+            table.lines().all(|line| line.chars().count() <= 30),
+            "{table}"
+        );
+        assert!(!table.contains('…'), "{table}");
+        assert!(table.lines().count() > 5, "{table}");
+        Ok(())
+    }
+
+    #[test]
+    fn auto_vertical_only_when_the_columns_cannot_fit() -> Result<()> {
+        let narrow = render(&batch(2)?, fit(30, TableOverflow::AutoVertical))?;
+        assert!(narrow.contains('…'), "{narrow}");
+
+        let vertical = render(&batch(6)?, fit(30, TableOverflow::AutoVertical))?;
+        assert_eq!(
+            vertical.lines().take(3).collect::<Vec<_>>(),
+            [
+                "-[ RECORD 1 ]-----",
+                "note  | a rather long note that cannot fit",
+                "col_1 | 1",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn escapes_control_characters_but_not_newlines() {
+        assert!(matches!(
+            escape_control_chars("plain"),
+            Cow::Borrowed("plain")
+        ));
+        assert_eq!(
+            escape_control_chars("a\u{1b}]0;title\u{7}b"),
+            "a\\x1b]0;title\\x07b"
+        );
+        assert_eq!(
+            escape_control_chars("tab\there\u{9b}31m"),
+            "tab\\x09here\\x9b31m"
+        );
+        assert_eq!(escape_control_chars("two\nlines"), "two\nlines");
+    }
+}
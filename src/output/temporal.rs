@@ -0,0 +1,178 @@
+//! Timestamp and date rendering shared by table, CSV, and JSON output
+
+use super::display::DisplayTarget;
+use arrow::array::timezone::Tz;
+use arrow::array::{Array, ArrayRef, AsArray, StringArray};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Date32Type, Date64Type, Int64Type, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::temporal_conversions::{date32_to_datetime, date64_to_datetime};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, NaiveDateTime, SecondsFormat, TimeZone, Utc};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// User-chosen rendering for timestamp and date columns.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct TemporalFormat {
+    /// strftime-style format for timestamps.
+    pub timestamp_format: Option<String>,
+    /// strftime-style format for dates.
+    pub date_format: Option<String>,
+    /// Zone to convert timestamps into before formatting.
+    pub timezone: Option<DisplayTimeZone>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum DisplayTimeZone {
+    Local,
+    Zone(Tz),
+}
+
+impl FromStr for DisplayTimeZone {
+    type Err = String;
+
+    /// `local`, an IANA name such as `Europe/Berlin`, `UTC`, or a fixed offset like `+05:30`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        if text.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        text.parse::<Tz>()
+            .map(Self::Zone)
+            .map_err(|_| format!("unknown time zone '{text}'"))
+    }
+}
+
+/// Reject strftime formats chrono cannot render, so formatting never fails mid-output.
+pub(crate) fn parse_strftime(text: &str) -> Result<String, String> {
+    if StrftimeItems::new(text).any(|item| matches!(item, Item::Error)) {
+        Err(format!("invalid format string '{text}'"))
+    } else {
+        Ok(text.to_string())
+    }
+}
+
+/// Render a timestamp or date column as strings when the options or the target call
+/// for it.
+pub(crate) fn render_column(
+    format: &TemporalFormat,
+    column: &ArrayRef,
+    target: DisplayTarget,
+) -> Result<Option<ArrayRef>, ArrowError> {
+    match column.data_type() {
+        DataType::Timestamp(unit, zone) => {
+            format.render_timestamps(column.as_ref(), *unit, zone.as_deref(), target)
+        }
+        DataType::Date32 | DataType::Date64 => format.render_dates(column.as_ref()),
+        _ => Ok(None),
+    }
+}
+
+impl TemporalFormat {
+    fn render_timestamps(
+        &self,
+        array: &dyn Array,
+        unit: TimeUnit,
+        source_zone: Option<&str>,
+        target: DisplayTarget,
+    ) -> Result<Option<ArrayRef>, ArrowError> {
+        let keep_display = target == DisplayTarget::Text
+            && self.timestamp_format.is_none()
+            && self.timezone.is_none();
+        if keep_display {
+            return Ok(None);
+        }
+
+        let source_zone = source_zone.map(str::parse::<Tz>).transpose()?;
+        let zone = self
+            .timezone
+            .or_else(|| source_zone.map(DisplayTimeZone::Zone));
+        let format = self.timestamp_format.as_deref();
+        let values = cast(array, &DataType::Int64)?;
+        let values = values.as_primitive::<Int64Type>();
+
+        let rendered = (0..values.len())
+            .map(|index| {
+                if values.is_null(index) {
+                    return Ok(None);
+                }
+                let instant = naive_datetime(values.value(index), unit).ok_or_else(|| {
+                    ArrowError::ComputeError(format!(
+                        "timestamp {} is out of range",
+                        values.value(index)
+                    ))
+                })?;
+                let text = match (zone, format) {
+                    (Some(DisplayTimeZone::Local), _) => {
+                        render(Local.from_utc_datetime(&instant), format)
+                    }
+                    (Some(DisplayTimeZone::Zone(zone)), _) => {
+                        render(zone.from_utc_datetime(&instant), format)
+                    }
+                    (None, Some(format)) => render_with(instant.format(format)),
+                    (None, None) => match target {
+                        DisplayTarget::Json => render(Utc.from_utc_datetime(&instant), None),
+                        DisplayTarget::Text => render_with(instant.format("%Y-%m-%dT%H:%M:%S%.f")),
+                    },
+                }?;
+                Ok(Some(text))
+            })
+            .collect::<Result<StringArray, ArrowError>>()?;
+        Ok(Some(Arc::new(rendered)))
+    }
+
+    fn render_dates(&self, array: &dyn Array) -> Result<Option<ArrayRef>, ArrowError> {
+        let Some(format) = self.date_format.as_deref() else {
+            return Ok(None);
+        };
+
+        let dates: Vec<Option<NaiveDateTime>> = match array.data_type() {
+            DataType::Date32 => array
+                .as_primitive::<Date32Type>()
+                .iter()
+                .map(|value| value.and_then(date32_to_datetime))
+                .collect(),
+            _ => array
+                .as_primitive::<Date64Type>()
+                .iter()
+                .map(|value| value.and_then(date64_to_datetime))
+                .collect(),
+        };
+        let rendered = dates
+            .into_iter()
+            .map(|date| {
+                date.map(|date| render_with(date.date().format(format)))
+                    .transpose()
+            })
+            .collect::<Result<StringArray, ArrowError>>()?;
+        Ok(Some(Arc::new(rendered)))
+    }
+}
+
+fn naive_datetime(value: i64, unit: TimeUnit) -> Option<NaiveDateTime> {
+    let instant = match unit {
+        TimeUnit::Second => DateTime::from_timestamp(value, 0),
+        TimeUnit::Millisecond => DateTime::from_timestamp_millis(value),
+        TimeUnit::Microsecond => DateTime::from_timestamp_micros(value),
+        TimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(value)),
+    };
+    instant.map(|instant| instant.naive_utc())
+}
+
+fn render<Z: TimeZone>(value: DateTime<Z>, format: Option<&str>) -> Result<String, ArrowError>
+where
+    Z::Offset: Display,
+{
+    match format {
+        Some(format) => render_with(value.format(format)),
+        None => Ok(value.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
+    }
+}
+
+fn render_with(value: impl Display) -> Result<String, ArrowError> {
+    let mut text = String::new();
+    write!(text, "{value}")
+        .map_err(|_| ArrowError::ComputeError("cannot format temporal value".to_string()))?;
+    Ok(text)
+}
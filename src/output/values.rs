@@ -0,0 +1,204 @@
+//! Values as the text writers show them
+//!
+//! Tables and CSV render cells through [`TextValues`], so the two agree on every type;
+//! `DisplayOptions` has already rewritten the columns an option changes. Dictionaries
+//! are resolved to the values their keys point at before any renderer sees them.
+
+use super::integer::{group_thousands, is_grouped_type};
+use arrow::array::{Array, ArrayRef, RecordBatch};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, FieldRef, Fields};
+use arrow::error::ArrowError;
+use arrow::util::display::{ArrayFormatter, FormatOptions};
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// A batch's values as text: Arrow's display form, with the options' null text in
+/// place of nulls.
+pub(crate) struct TextValues<'a> {
+    formatters: Vec<ArrayFormatter<'a>>,
+    /// Columns whose digits are grouped in thousands, for `--human-readable` tables
+    grouped: Vec<bool>,
+}
+
+impl<'a> TextValues<'a> {
+    pub fn new(batch: &'a RecordBatch, options: &'a FormatOptions<'a>) -> Result<Self, ArrowError> {
+        let formatters = batch
+            .columns()
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.as_ref(), options))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            formatters,
+            grouped: Vec::new(),
+        })
+    }
+
+    /// Group the digits of integer columns, as `1,234,567`, when `enabled`.
+    pub fn group_integers(mut self, batch: &RecordBatch, enabled: bool) -> Self {
+        if enabled {
+            self.grouped = batch
+                .columns()
+                .iter()
+                .map(|column| is_grouped_type(column.data_type()))
+                .collect();
+        }
+        self
+    }
+
+    pub fn format_value(&self, column: usize, row: usize) -> Result<String, ArrowError> {
+        let mut text = String::new();
+        self.write_value(column, row, &mut text)?;
+        Ok(text)
+    }
+
+    /// Replace `buffer`'s contents with the value, reusing its allocation.
+    pub fn write_value(
+        &self,
+        column: usize,
+        row: usize,
+        buffer: &mut String,
+    ) -> Result<(), ArrowError> {
+        buffer.clear();
+        let formatter = self.formatters.get(column).ok_or_else(|| {
+            ArrowError::InvalidArgumentError(format!("no column at index {column}"))
+        })?;
+        write!(buffer, "{}", formatter.value(row))
+            .map_err(|error| ArrowError::ExternalError(Box::new(error)))?;
+        if self.grouped.get(column).copied().unwrap_or(false) {
+            *buffer = group_thousands(buffer);
+        }
+        Ok(())
+    }
+}
+
+/// The column with every dictionary, top-level or nested in lists and structs,
+/// replaced by its values; `None` when it holds no dictionaries.
+///
+/// A null key and a key pointing at a null dictionary entry both become null.
+pub(crate) fn decode_dictionaries(column: &ArrayRef) -> Result<Option<ArrayRef>, ArrowError> {
+    match decoded_type(column.data_type()) {
+        Some(data_type) => cast(column.as_ref(), &data_type).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// `data_type` with its dictionaries replaced by their value types, or `None` when
+/// it has none.
+fn decoded_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        DataType::Dictionary(_, value) => {
+            Some(decoded_type(value).unwrap_or_else(|| value.as_ref().clone()))
+        }
+        DataType::List(item) => decoded_field(item).map(DataType::List),
+        DataType::LargeList(item) => decoded_field(item).map(DataType::LargeList),
+        DataType::FixedSizeList(item, size) => {
+            decoded_field(item).map(|item| DataType::FixedSizeList(item, *size))
+        }
+        DataType::Struct(fields) => {
+            let decoded: Vec<Option<FieldRef>> = fields.iter().map(decoded_field).collect();
+            if decoded.iter().all(Option::is_none) {
+                return None;
+            }
+            Some(DataType::Struct(Fields::from(
+                fields
+                    .iter()
+                    .zip(decoded)
+                    .map(|(field, decoded)| decoded.unwrap_or_else(|| Arc::clone(field)))
+                    .collect::<Vec<_>>(),
+            )))
+        }
+        _ => None,
+    }
+}
+
+fn decoded_field(field: &FieldRef) -> Option<FieldRef> {
+    decoded_type(field.data_type()).map(|data_type| {
+        Arc::new(
+            Field::new(field.name(), data_type, field.is_nullable())
+                .with_metadata(field.metadata().clone()),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{
+        AsArray, DictionaryArray, Float64Array, Int32Array, ListArray, StringArray,
+    };
+    use arrow::buffer::OffsetBuffer;
+    use arrow::datatypes::Int32Type;
+
+    fn dictionary() -> Result<DictionaryArray<Int32Type>, ArrowError> {
+        DictionaryArray::try_new(
+            Int32Array::from(vec![Some(1), None, Some(0), Some(2), Some(1)]),
+            Arc::new(StringArray::from(vec![Some("a"), Some("b"), None])),
+        )
+    }
+
+    fn strings(column: &ArrayRef) -> Vec<Option<&str>> {
+        column.as_string::<i32>().iter().collect()
+    }
+
+    #[test]
+    fn resolves_keys_and_both_kinds_of_null() -> Result<(), ArrowError> {
+        let column: ArrayRef = Arc::new(dictionary()?);
+        let decoded = decode_dictionaries(&column)?
+            .ok_or_else(|| ArrowError::ComputeError("dictionary should decode".to_string()))?;
+        assert_eq!(decoded.data_type(), &DataType::Utf8);
+        assert_eq!(
+            strings(&decoded),
+            [Some("b"), None, Some("a"), None, Some("b")]
+        );
+
+        let plain: ArrayRef = Arc::new(StringArray::from(vec!["a"]));
+        assert!(decode_dictionaries(&plain)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_dictionaries_inside_lists() -> Result<(), ArrowError> {
+        let values = dictionary()?;
+        let item = Arc::new(Field::new("item", values.data_type().clone(), true));
+        let column: ArrayRef = Arc::new(ListArray::try_new(
+            item,
+            OffsetBuffer::from_lengths([2, 3]),
+            Arc::new(values),
+            None,
+        )?);
+        let decoded = decode_dictionaries(&column)?
+            .ok_or_else(|| ArrowError::ComputeError("list should decode".to_string()))?;
+        let lists = decoded.as_list::<i32>();
+        assert_eq!(lists.value_type(), DataType::Utf8);
+        assert_eq!(
+            strings(lists.values()),
+            [Some("b"), None, Some("a"), None, Some("b")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn text_values_use_the_null_text() -> Result<(), ArrowError> {
+        let batch = RecordBatch::try_from_iter([
+            (
+                "score",
+                Arc::new(Float64Array::from(vec![Some(1.5), None])) as ArrayRef,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec![None, Some("a")])) as ArrayRef,
+            ),
+        ])?;
+        let options = FormatOptions::default().with_null("NULL");
+        let values = TextValues::new(&batch, &options)?;
+        assert_eq!(values.format_value(0, 0)?, "1.5");
+        assert_eq!(values.format_value(0, 1)?, "NULL");
+        assert_eq!(values.format_value(1, 0)?, "NULL");
+
+        let mut buffer = "stale".to_string();
+        values.write_value(1, 1, &mut buffer)?;
+        assert_eq!(buffer, "a");
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! File paths as pq prints them
+//!
+//! `Path::display` turns bytes that are not UTF-8 into U+FFFD, so two different names
+//! can print alike, and leaves newlines that split a line-per-file listing. Every path
+//! in output and messages goes through [`display_path`] instead.
+
+use std::fmt::Write;
+use std::path::Path;
+
+/// `path` as text, with bytes that are not UTF-8 and control characters, newlines
+/// among them, written as `\xff`-style escapes. On Unix, where a name may hold a
+/// backslash, it is doubled so no name can pass for an escape; on Windows it is the
+/// separator. Distinct paths stay distinct, and each stays on one line.
+pub(crate) fn display_path(path: impl AsRef<Path>) -> String {
+    let path = path.as_ref();
+    #[cfg(unix)]
+    let bytes = std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str());
+    #[cfg(not(unix))]
+    let lossy = path.to_string_lossy();
+    #[cfg(not(unix))]
+    let bytes = lossy.as_bytes();
+
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        for c in chunk.valid().chars() {
+            if c.is_control() {
+                let _infallible = write!(text, "\\x{:02x}", u32::from(c));
+            } else if cfg!(unix) && c == '\\' {
+                text.push_str("\\\\");
+            } else {
+                text.push(c);
+            }
+        }
+        for byte in chunk.invalid() {
+            let _infallible = write!(text, "\\x{byte:02x}");
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_control_characters_and_keeps_other_text() {
+        assert_eq!(
+            display_path("data/a,b \"q\".parquet"),
+            "data/a,b \"q\".parquet"
+        );
+        assert_eq!(
+            display_path("new\nline\t.parquet"),
+            "new\\x0aline\\x09.parquet"
+        );
+        assert_eq!(display_path("café.parquet"), "café.parquet");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn escapes_bytes_that_are_not_utf8() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = Path::new(OsStr::from_bytes(b"bad\xff\xfe.parquet"));
+        assert_eq!(display_path(path), "bad\\xff\\xfe.parquet");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn doubles_backslashes_so_they_cannot_pass_for_escapes() {
+        assert_eq!(display_path("a\\x0a"), "a\\\\x0a");
+        assert_ne!(display_path("a\\x0a"), display_path("a\n"));
+    }
+}
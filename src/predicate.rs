@@ -0,0 +1,420 @@
+//! Row predicates for `--where` style filters
+//!
+//! The grammar is deliberately small: comparisons between a column and a literal,
+//! `IS [NOT] NULL` checks, `AND`/`OR`, and parentheses.
+
+use crate::error::PqError;
+use crate::Result;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Predicate {
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    Compare {
+        column: String,
+        op: CompareOp,
+        value: Literal,
+    },
+    IsNull {
+        column: String,
+        negated: bool,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Literal {
+    Number(String),
+    String(String),
+    Boolean(bool),
+}
+
+impl Literal {
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Number(text) | Self::String(text) => text,
+            Self::Boolean(true) => "true",
+            Self::Boolean(false) => "false",
+        }
+    }
+
+    /// The value of a numeric literal, for comparing integer columns with one that is
+    /// fractional or out of their range; `None` for other literals and NaN.
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            Self::Number(text) => text.parse::<f64>().ok().filter(|value| !value.is_nan()),
+            Self::String(_) | Self::Boolean(_) => None,
+        }
+    }
+}
+
+impl Predicate {
+    pub fn parse(text: &str) -> Result<Self> {
+        let tokens = tokenize(text).map_err(|details| PqError::invalid_predicate(text, details))?;
+        let mut parser = Parser {
+            tokens,
+            position: 0,
+        };
+        let predicate = parser
+            .parse_or()
+            .and_then(|predicate| match parser.peek() {
+                None => Ok(predicate),
+                Some(token) => Err(format!("unexpected {}", token.describe())),
+            })
+            .map_err(|details| PqError::invalid_predicate(text, details))?;
+        Ok(predicate)
+    }
+
+    /// Column names in the order they first appear.
+    pub fn columns(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        self.collect_columns(&mut columns);
+        columns
+    }
+
+    fn collect_columns(&self, columns: &mut Vec<String>) {
+        match self {
+            Self::And(children) | Self::Or(children) => {
+                for child in children {
+                    child.collect_columns(columns);
+                }
+            }
+            Self::Compare { column, .. } | Self::IsNull { column, .. } => {
+                if !columns.contains(column) {
+                    columns.push(column.clone());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    QuotedIdentifier(String),
+    String(String),
+    Number(String),
+    Operator(CompareOp),
+    LeftParen,
+    RightParen,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Identifier(name) => format!("'{name}'"),
+            Self::QuotedIdentifier(name) => format!("\"{name}\""),
+            Self::String(value) => format!("string '{value}'"),
+            Self::Number(value) => format!("number {value}"),
+            Self::Operator(_) => "comparison operator".to_string(),
+            Self::LeftParen => "'('".to_string(),
+            Self::RightParen => "')'".to_string(),
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Identifier(name) if name.eq_ignore_ascii_case(keyword))
+    }
+}
+
+fn tokenize(text: &str) -> std::result::Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(start, ch)) = chars.peek() {
+        match ch {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LeftParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RightParen);
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, c)) if c == ch => {
+                            if chars.peek().is_some_and(|&(_, next)| next == ch) {
+                                chars.next();
+                                value.push(ch);
+                            } else {
+                                break;
+                            }
+                        }
+                        Some((_, c)) => value.push(c),
+                        None => {
+                            return Err(format!("unterminated quote starting at offset {start}"))
+                        }
+                    }
+                }
+                tokens.push(if ch == '\'' {
+                    Token::String(value)
+                } else {
+                    Token::QuotedIdentifier(value)
+                });
+            }
+            '=' | '!' | '<' | '>' => {
+                chars.next();
+                let next = chars.peek().map(|&(_, next)| next);
+                let op = match (ch, next) {
+                    ('=', Some('='))
+                    | ('!', Some('='))
+                    | ('<', Some('=' | '>'))
+                    | ('>', Some('=')) => {
+                        chars.next();
+                        match (ch, next) {
+                            ('=', _) => CompareOp::Eq,
+                            ('<', Some('=')) => CompareOp::LtEq,
+                            ('>', _) => CompareOp::GtEq,
+                            _ => CompareOp::NotEq,
+                        }
+                    }
+                    ('=', _) => CompareOp::Eq,
+                    ('<', _) => CompareOp::Lt,
+                    ('>', _) => CompareOp::Gt,
+                    _ => return Err(format!("unexpected '!' at offset {start}")),
+                };
+                tokens.push(Token::Operator(op));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '.' => {
+                let mut value = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+') {
+                        value.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if value.parse::<f64>().is_err() {
+                    return Err(format!("invalid number '{value}' at offset {start}"));
+                }
+                tokens.push(Token::Number(value));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut value = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_alphanumeric() || matches!(c, '_' | '.') {
+                        value.push(c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token::Identifier(value));
+            }
+            c => return Err(format!("unexpected character '{c}' at offset {start}")),
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_keyword(keyword)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> std::result::Result<Predicate, String> {
+        let mut children = vec![self.parse_and()?];
+        while self.eat_keyword("or") {
+            children.push(self.parse_and()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            Predicate::Or(children)
+        })
+    }
+
+    fn parse_and(&mut self) -> std::result::Result<Predicate, String> {
+        let mut children = vec![self.parse_atom()?];
+        while self.eat_keyword("and") {
+            children.push(self.parse_atom()?);
+        }
+        Ok(if children.len() == 1 {
+            children.remove(0)
+        } else {
+            Predicate::And(children)
+        })
+    }
+
+    fn parse_atom(&mut self) -> std::result::Result<Predicate, String> {
+        match self.next() {
+            Some(Token::LeftParen) => {
+                let predicate = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RightParen) => Ok(predicate),
+                    Some(token) => Err(format!("expected ')', found {}", token.describe())),
+                    None => Err("expected ')' before end of predicate".to_string()),
+                }
+            }
+            Some(Token::Identifier(column) | Token::QuotedIdentifier(column)) => {
+                self.parse_comparison(column)
+            }
+            Some(token) => Err(format!(
+                "expected a column name, found {}",
+                token.describe()
+            )),
+            None => Err("expected a column name before end of predicate".to_string()),
+        }
+    }
+
+    fn parse_comparison(&mut self, column: String) -> std::result::Result<Predicate, String> {
+        if self.eat_keyword("is") {
+            let negated = self.eat_keyword("not");
+            if !self.eat_keyword("null") {
+                return Err(format!(
+                    "expected NULL after IS in comparison on '{column}'"
+                ));
+            }
+            return Ok(Predicate::IsNull { column, negated });
+        }
+
+        let op = match self.next() {
+            Some(Token::Operator(op)) => op,
+            Some(token) => {
+                return Err(format!(
+                    "expected a comparison operator after '{column}', found {}",
+                    token.describe()
+                ))
+            }
+            None => return Err(format!("expected a comparison operator after '{column}'")),
+        };
+
+        let value = match self.next() {
+            Some(Token::Number(value)) => Literal::Number(value),
+            Some(Token::String(value)) => Literal::String(value),
+            Some(token) if token.is_keyword("true") => Literal::Boolean(true),
+            Some(token) if token.is_keyword("false") => Literal::Boolean(false),
+            Some(token) if token.is_keyword("null") => {
+                return Err(format!("use '{column} IS NULL' to compare with NULL"))
+            }
+            Some(token) => return Err(format!("expected a literal, found {}", token.describe())),
+            None => return Err(format!("expected a literal after comparison on '{column}'")),
+        };
+
+        Ok(Predicate::Compare { column, op, value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compare(column: &str, op: CompareOp, value: Literal) -> Predicate {
+        Predicate::Compare {
+            column: column.to_string(),
+            op,
+            value,
+        }
+    }
+
+    #[test]
+    fn parses_range_conjunction() -> Result<()> {
+        let predicate = Predicate::parse("ts >= '2024-01-01' AND ts < '2024-01-02'")?;
+
+        assert_eq!(
+            predicate,
+            Predicate::And(vec![
+                compare(
+                    "ts",
+                    CompareOp::GtEq,
+                    Literal::String("2024-01-01".to_string())
+                ),
+                compare(
+                    "ts",
+                    CompareOp::Lt,
+                    Literal::String("2024-01-02".to_string())
+                ),
+            ])
+        );
+        assert_eq!(predicate.columns(), vec!["ts".to_string()]);
+        Ok(())
+    }
+
+    #[test]
+    fn numeric_literals_read_as_floats() {
+        assert_eq!(Literal::Number("1.5".to_string()).as_float(), Some(1.5));
+        assert_eq!(Literal::Number("-1e30".to_string()).as_float(), Some(-1e30));
+        assert_eq!(Literal::Number("NaN".to_string()).as_float(), None);
+        assert_eq!(Literal::String("1.5".to_string()).as_float(), None);
+        assert_eq!(Literal::Boolean(true).as_float(), None);
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() -> Result<()> {
+        let predicate = Predicate::parse("a = 1 or b <> -2.5 and \"c d\" is not null")?;
+
+        assert_eq!(
+            predicate,
+            Predicate::Or(vec![
+                compare("a", CompareOp::Eq, Literal::Number("1".to_string())),
+                Predicate::And(vec![
+                    compare("b", CompareOp::NotEq, Literal::Number("-2.5".to_string())),
+                    Predicate::IsNull {
+                        column: "c d".to_string(),
+                        negated: true,
+                    },
+                ]),
+            ])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_malformed_predicates() {
+        for text in [
+            "",
+            "a =",
+            "a = 1 and",
+            "(a = 1",
+            "a = null",
+            "a ~ 1",
+            "'a' = 1",
+        ] {
+            assert!(
+                matches!(
+                    Predicate::parse(text),
+                    Err(PqError::InvalidPredicate { .. })
+                ),
+                "{text} should be rejected"
+            );
+        }
+    }
+}
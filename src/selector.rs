@@ -0,0 +1,329 @@
+//! Column selectors for `--columns` style flags
+//!
+//! Each selector is, in order of precedence: a `re:` regex, an exact column name, a
+//! 1-based position or inclusive range such as `4`, `1:10`, `3:` or `:2`, or a glob
+//! such as `*_id`. Selected columns follow selector order; a column picked twice keeps its
+//! first position. With [`ColumnCase::Insensitive`] a name that matches no column exactly
+//! falls back to the one column differing only in case.
+
+use crate::error::PqError;
+use crate::model::ColumnCase;
+use crate::suggest::nearest;
+use crate::Result;
+use glob::{MatchOptions, Pattern};
+use regex::RegexBuilder;
+use std::path::Path;
+
+/// Resolve `selectors` against `columns`, returning indices in selector order.
+///
+/// Patterns may overlap, with a column keeping its first position, but the same
+/// selector twice is most likely a typo and fails.
+pub(crate) fn select_columns<S: AsRef<str>>(
+    path: &Path,
+    columns: &[S],
+    selectors: &[String],
+    case: ColumnCase,
+) -> Result<Vec<usize>> {
+    let mut selected = Vec::new();
+    for (position, selector) in selectors.iter().enumerate() {
+        if selectors[..position].contains(selector) {
+            return Err(PqError::invalid_column_selector(
+                selector,
+                "listed more than once",
+            ));
+        }
+        for index in matching_columns(path, columns, selector, case)? {
+            if !selected.contains(&index) {
+                selected.push(index);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+/// The single column `name` refers to: the one spelled exactly like it, or with
+/// [`ColumnCase::Insensitive`] the only one differing in case.
+pub(crate) fn find_column<S: AsRef<str>>(
+    path: &Path,
+    columns: &[S],
+    name: &str,
+    case: ColumnCase,
+) -> Result<usize> {
+    if let Some(index) = columns.iter().position(|column| column.as_ref() == name) {
+        return Ok(index);
+    }
+    match case {
+        ColumnCase::Insensitive => case_insensitive_match(path, columns, name)?,
+        ColumnCase::Sensitive => None,
+    }
+    .ok_or_else(|| not_found(path, columns, name, true))
+}
+
+fn matching_columns<S: AsRef<str>>(
+    path: &Path,
+    columns: &[S],
+    selector: &str,
+    case: ColumnCase,
+) -> Result<Vec<usize>> {
+    let names = || columns.iter().map(AsRef::as_ref).enumerate();
+    let ignore_case = case == ColumnCase::Insensitive;
+    let is_glob = selector.contains(['*', '?', '[']);
+
+    let matched: Vec<usize> = if let Some(pattern) = selector.strip_prefix("re:") {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|error| PqError::invalid_column_selector(selector, error))?;
+        names()
+            .filter(|(_, name)| regex.is_match(name))
+            .map(|(index, _)| index)
+            .collect()
+    } else if names().any(|(_, name)| name == selector) {
+        names()
+            .filter(|(_, name)| *name == selector)
+            .map(|(index, _)| index)
+            .collect()
+    } else if let Some(index) = ignore_case
+        .then(|| case_insensitive_match(path, columns, selector))
+        .transpose()?
+        .flatten()
+    {
+        vec![index]
+    } else if let Some((start, end)) = position_range(selector) {
+        return range_columns(path, columns.len(), selector, start, end);
+    } else if is_glob {
+        let pattern = Pattern::new(selector)
+            .map_err(|error| PqError::invalid_column_selector(selector, error))?;
+        let options = MatchOptions {
+            case_sensitive: !ignore_case,
+            ..MatchOptions::new()
+        };
+        names()
+            .filter(|(_, name)| pattern.matches_with(name, options))
+            .map(|(index, _)| index)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    if matched.is_empty() {
+        let is_name = !is_glob && !selector.starts_with("re:");
+        return Err(not_found(path, columns, selector, is_name));
+    }
+    Ok(matched)
+}
+
+/// The one column equal to `name` ignoring case; several are an ambiguity error.
+fn case_insensitive_match<S: AsRef<str>>(
+    path: &Path,
+    columns: &[S],
+    name: &str,
+) -> Result<Option<usize>> {
+    let folded = name.to_lowercase();
+    let matches: Vec<(usize, &str)> = columns
+        .iter()
+        .map(AsRef::as_ref)
+        .enumerate()
+        .filter(|(_, column)| column.to_lowercase() == folded)
+        .collect();
+    match matches.as_slice() {
+        [] => Ok(None),
+        [(index, _)] => Ok(Some(*index)),
+        _ => Err(PqError::ambiguous_column(
+            path,
+            name,
+            matches.iter().map(|(_, column)| *column),
+        )),
+    }
+}
+
+/// A "column not found" error listing `columns`, suggesting the closest one when the
+/// selector is a plain name.
+fn not_found<S: AsRef<str>>(path: &Path, columns: &[S], selector: &str, is_name: bool) -> PqError {
+    let names = || columns.iter().map(AsRef::as_ref);
+    let suggestion = is_name
+        .then(|| nearest(selector, names()))
+        .flatten()
+        .map(ToString::to_string);
+    PqError::column_not_found_suggesting(path, selector, suggestion, names())
+}
+
+/// Split `A:B` into its bounds when both sides are empty or digits; a bare position
+/// `N` is the range `N:N`.
+fn position_range(selector: &str) -> Option<(&str, &str)> {
+    let is_bound = |text: &str| text.bytes().all(|byte| byte.is_ascii_digit());
+    match selector.split_once(':') {
+        Some((start, end)) => (is_bound(start) && is_bound(end)).then_some((start, end)),
+        None => (!selector.is_empty() && is_bound(selector)).then_some((selector, selector)),
+    }
+}
+
+fn range_columns(
+    path: &Path,
+    count: usize,
+    selector: &str,
+    start: &str,
+    end: &str,
+) -> Result<Vec<usize>> {
+    let position = |text: &str, default: usize| -> Result<usize> {
+        if text.is_empty() {
+            return Ok(default);
+        }
+        let position = text
+            .parse::<usize>()
+            .map_err(|error| PqError::invalid_column_selector(selector, error))?;
+        if position == 0 {
+            return Err(PqError::invalid_column_selector(
+                selector,
+                "column positions start at 1",
+            ));
+        }
+        if position > count {
+            return Err(PqError::column_out_of_range(path, position, count));
+        }
+        Ok(position)
+    };
+
+    let start = position(start, 1)?;
+    let end = position(end, count)?;
+    if start > end {
+        return Err(PqError::invalid_column_selector(
+            selector,
+            "range start is after its end",
+        ));
+    }
+    Ok((start - 1..end).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [&str; 6] = ["id", "sensor_1", "sensor_2", "user_id", "1:2", "note"];
+
+    fn select(selectors: &[&str]) -> Result<Vec<usize>> {
+        let selectors: Vec<String> = selectors.iter().map(ToString::to_string).collect();
+        select_columns(
+            Path::new("data.parquet"),
+            &COLUMNS,
+            &selectors,
+            ColumnCase::Sensitive,
+        )
+    }
+
+    #[test]
+    fn selects_by_regex_range_glob_and_name_in_selector_order() -> Result<()> {
+        assert_eq!(select(&[r"re:^sensor_\d+$"])?, vec![1, 2]);
+        assert_eq!(select(&["2:3"])?, vec![1, 2]);
+        assert_eq!(select(&["4"])?, vec![3]);
+        assert_eq!(select(&["5:"])?, vec![4, 5]);
+        assert_eq!(select(&[":2"])?, vec![0, 1]);
+        assert_eq!(select(&["*_id"])?, vec![3]);
+        assert_eq!(select(&["note", "*id", "sensor_2"])?, vec![5, 0, 3, 2]);
+        assert_eq!(select(&["sensor_2", "2:3"])?, vec![2, 1]);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_a_selector_listed_twice() {
+        assert!(matches!(
+            select(&["note", "id", "note"]),
+            Err(PqError::InvalidColumnSelector { ref selector, .. }) if selector == "note"
+        ));
+        assert!(select(&["1:2", "*_1"]).is_ok());
+    }
+
+    #[test]
+    fn exact_names_take_precedence_over_ranges_and_globs() -> Result<()> {
+        assert_eq!(select(&["1:2"])?, vec![4]);
+        let columns = ["a*", "ab"];
+        let selectors = vec!["a*".to_string(), "re:^a".to_string()];
+        assert_eq!(
+            select_columns(
+                Path::new("data.parquet"),
+                &columns,
+                &selectors,
+                ColumnCase::Sensitive
+            )?,
+            vec![0, 1]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_regex_and_ranges() {
+        assert!(matches!(
+            select(&["re:sensor_("]),
+            Err(PqError::InvalidColumnSelector { .. })
+        ));
+        assert!(matches!(
+            select(&["0:2"]),
+            Err(PqError::InvalidColumnSelector { .. })
+        ));
+        assert!(matches!(
+            select(&["3:2"]),
+            Err(PqError::InvalidColumnSelector { .. })
+        ));
+    }
+
+    #[test]
+    fn reports_out_of_range_positions_and_unmatched_selectors() {
+        assert!(matches!(
+            select(&["4:9"]),
+            Err(PqError::ColumnOutOfRange {
+                position: 9,
+                count: 6,
+                ..
+            })
+        ));
+        assert!(matches!(
+            select(&["7"]),
+            Err(PqError::ColumnOutOfRange { position: 7, .. })
+        ));
+        assert!(matches!(
+            select(&["7:"]),
+            Err(PqError::ColumnOutOfRange { position: 7, .. })
+        ));
+        assert!(matches!(
+            select(&["missing"]),
+            Err(PqError::ColumnNotFound { .. })
+        ));
+        assert!(matches!(
+            select(&["*_ts"]),
+            Err(PqError::ColumnNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn ignoring_case_prefers_exact_names_and_rejects_ambiguity() -> Result<()> {
+        let path = Path::new("data.parquet");
+        let columns = ["UserID", "Name", "name", "EventTs"];
+        let select = |selector: &str| {
+            select_columns(
+                path,
+                &columns,
+                &[selector.to_string()],
+                ColumnCase::Insensitive,
+            )
+        };
+
+        assert_eq!(select("userid")?, vec![0]);
+        assert_eq!(select("name")?, vec![2]);
+        assert_eq!(select("re:^event")?, vec![3]);
+        assert_eq!(select("*ID")?, vec![0]);
+        assert_eq!(
+            find_column(path, &columns, "EVENTTS", ColumnCase::Insensitive)?,
+            3
+        );
+        assert!(matches!(
+            select("NAME"),
+            Err(PqError::AmbiguousColumn { candidates, .. }) if candidates == ["Name", "name"]
+        ));
+        assert!(matches!(
+            find_column(path, &columns, "userid", ColumnCase::Sensitive),
+            Err(PqError::ColumnNotFound { suggestion: Some(suggestion), .. })
+                if suggestion == "UserID"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+//! Closest-match suggestions for misspelled names
+
+/// The candidate closest to `name` by edit distance, if any is close enough to be a
+/// plausible typo.
+pub(crate) fn nearest<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= name.len().max(candidate.len()) / 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous.get(j).copied().unwrap_or_default()
+                + usize::from(left_char != *right_char);
+            let deletion = previous.get(j + 1).copied().unwrap_or_default() + 1;
+            let insertion = current.get(j).copied().unwrap_or_default() + 1;
+            current.push(substitution.min(deletion).min(insertion));
+        }
+        previous = current;
+    }
+    previous.last().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_the_nearest_name() {
+        let names = ["amount", "user", "items"];
+        assert_eq!(nearest("amonut", names), Some("amount"));
+        assert_eq!(nearest("usr", names), Some("user"));
+        assert_eq!(nearest("zzzzzz", names), None);
+    }
+}
@@ -0,0 +1,74 @@
+//! Warnings on stderr, kept apart from errors and from the data on stdout
+//!
+//! Anything pq works around instead of failing is reported through [`warn`]:
+//! `-q, --quiet` silences it, `--strict` turns it into an error, and
+//! `--error-format json` writes it, like errors, as one JSON object per line.
+
+use crate::error::PqError;
+use crate::Result;
+use std::io::{self, Write};
+use std::sync::RwLock;
+
+/// How warnings and errors are written to stderr.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum MessageFormat {
+    /// `warning: ...` and `error: ...` lines
+    #[default]
+    Text,
+    /// `{"level":"warning","message":"..."}`, one object per line
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct WarningPolicy {
+    pub quiet: bool,
+    pub strict: bool,
+    pub format: MessageFormat,
+}
+
+// Set once from the command line before the command runs
+static POLICY: RwLock<WarningPolicy> = RwLock::new(WarningPolicy {
+    quiet: false,
+    strict: false,
+    format: MessageFormat::Text,
+});
+
+pub(crate) fn configure(policy: WarningPolicy) {
+    if let Ok(mut current) = POLICY.write() {
+        *current = policy;
+    }
+}
+
+fn policy() -> WarningPolicy {
+    POLICY.read().map(|policy| *policy).unwrap_or_default()
+}
+
+/// Report `message` on stderr, or fail with it under `--strict`.
+pub(crate) fn warn(message: impl Into<String>) -> Result<()> {
+    let message = message.into();
+    let policy = policy();
+    if policy.strict {
+        return Err(PqError::strict_warning(message));
+    }
+    if !policy.quiet {
+        write_message(policy.format, "warning", &message);
+    }
+    Ok(())
+}
+
+/// Report the error a command failed with, in the format `--error-format` chose.
+pub fn print_error(error: &PqError) {
+    write_message(policy().format, "error", &error.to_string());
+}
+
+// Nowhere is left to report a failure to write to stderr
+fn write_message(format: MessageFormat, level: &str, message: &str) {
+    let mut stderr = io::stderr().lock();
+    let _ignored = match format {
+        MessageFormat::Text => writeln!(stderr, "{level}: {message}"),
+        MessageFormat::Json => {
+            let line = serde_json::json!({ "level": level, "message": message });
+            writeln!(stderr, "{line}")
+        }
+    };
+}
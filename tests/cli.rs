@@ -1,22 +1,32 @@
 //! CLI integration tests for pq
 
 use anyhow::Result;
-use arrow::array::{ArrayRef, BooleanArray, Int64Array, StringArray};
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::array::{
+    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, DictionaryArray,
+    Float64Array, Int32Array, Int64Array, ListArray, StringArray, StructArray,
+    TimestampMicrosecondArray, TimestampMillisecondArray, UInt32Array,
+};
+use arrow::datatypes::{DataType, Field, Int32Type, Int64Type, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
 use parquet::arrow::ArrowWriter;
-use parquet::file::properties::WriterProperties;
+use parquet::data_type::{ByteArray, ByteArrayType, FixedLenByteArray, FixedLenByteArrayType};
+use parquet::file::properties::{EnabledStatistics, WriterProperties};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::parser::parse_message_type;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 fn pq() -> Command {
-    Command::new(env!("CARGO_BIN_EXE_pq"))
+    // Keep a developer's own config file out of the results
+    let mut command = Command::new(env!("CARGO_BIN_EXE_pq"));
+    command.env("PQ_CONFIG", "");
+    command
 }
 
 fn fixture_path() -> String {
@@ -69,6 +79,120 @@ fn test_help() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_shell_completions() -> Result<()> {
+    for shell in ["bash", "zsh", "fish"] {
+        let output = pq().args(["completions", shell]).output()?;
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains("prune-check"), "{shell}");
+        // The column helper is only reachable through the dynamic hooks
+        let hooks = stdout.matches("__complete-columns").count();
+        assert_eq!(hooks, usize::from(shell != "bash"), "{shell}");
+    }
+
+    let output = pq().args(["completions", "zsh"]).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("]:COLUMNS:_pq_columns'"));
+    assert!(stdout.contains("]:BY:_pq_columns'"));
+
+    let output = pq()
+        .args(["__complete-columns", &fixture_path()])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "id\nname\namount\nactive\n"
+    );
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_watch_reruns_on_change_and_exits_on_interrupt() -> Result<()> {
+    use std::io::{BufRead, BufReader};
+    use std::process::Stdio;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let dir = temp_path("watch", "d")?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("data.parquet");
+    fs::copy(fixture_path(), &path)?;
+
+    let mut child = pq()
+        .args(["watch", "--debounce", "50", "count"])
+        .arg(&path)
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("missing stdout"))?;
+    let (sender, lines) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout)
+            .lines()
+            .map_while(std::result::Result::ok)
+        {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let wait_for = |expected: &str| -> Result<()> {
+        loop {
+            let line = lines.recv_timeout(Duration::from_secs(10))?;
+            if line == expected {
+                return Ok(());
+            }
+        }
+    };
+    wait_for("5")?;
+
+    // Replace the file the way writers do: briefly absent, then renamed into place
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef],
+    )?;
+    let staged = dir.join("staged.tmp");
+    write_parquet(&staged, schema, &[batch], None)?;
+    fs::remove_file(&path)?;
+    std::thread::sleep(Duration::from_millis(100));
+    fs::rename(&staged, &path)?;
+    wait_for("3")?;
+
+    let status = Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()?;
+    assert!(status.success());
+    assert!(child.wait()?.success());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_watch_reports_a_bad_command_like_any_error() -> Result<()> {
+    let output = pq()
+        .args(["--error-format", "json", "watch", "head", "--bogus"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(1));
+    let message: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(message["level"], "error");
+    let text = message["message"].as_str().unwrap_or_default();
+    assert!(
+        text.starts_with("Invalid command to watch: head --bogus\n  unexpected argument '--bogus'"),
+        "{text}"
+    );
+
+    let output = pq().args(["watch", "head", "--help"]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Usage: pq head"));
+    Ok(())
+}
+
 #[test]
 fn test_version() -> Result<()> {
     let output = pq().arg("--version").output()?;
@@ -104,6 +228,29 @@ fn test_schema_json() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_schema_ddl() -> Result<()> {
+    let output = pq()
+        .args(["schema", &fixture_path(), "--ddl", "postgres"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "CREATE TABLE \"test\" (\n  \"id\" BIGINT,\n  \"name\" TEXT,\n  \"amount\" DOUBLE PRECISION,\n  \"active\" BOOLEAN\n);\n"
+    );
+
+    let output = pq()
+        .args(["schema", &fixture_path(), "--ddl", "arrow"])
+        .output()?;
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        schema["fields"][0]["type"],
+        serde_json::json!({"name": "int", "bitWidth": 64, "isSigned": true})
+    );
+    Ok(())
+}
+
 #[test]
 fn test_schema_multi_file_json_is_parseable() -> Result<()> {
     let file = fixture_path();
@@ -121,6 +268,53 @@ fn test_schema_multi_file_json_is_parseable() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_schema_unify_reports_deviating_files() -> Result<()> {
+    let file = fixture_path();
+    let output = pq().args(["schema", "--unify", &file, &file]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Differs In"));
+
+    let cast_path = temp_path("unify_cast", "parquet")?;
+    let cast = cast_path.display().to_string();
+    let status = pq()
+        .args([
+            "transform",
+            &file,
+            "-o",
+            &cast,
+            "--cast",
+            "id=int32",
+            "--drop",
+            "active",
+        ])
+        .status()?;
+    assert!(status.success());
+
+    let output = pq()
+        .args(["schema", "--unify", &file, &cast, "-o", "json"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 column(s)"));
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["compatible"], false);
+    assert_eq!(document["files"], serde_json::json!([file, cast]));
+    let columns = &document["columns"];
+    assert_eq!(columns[0]["name"], "id");
+    assert_eq!(columns[0]["type"], "INT64");
+    assert_eq!(columns[0]["type_conflicts"][0]["type"], "INT32");
+    assert_eq!(
+        columns[0]["type_conflicts"][0]["files"],
+        serde_json::json!([cast])
+    );
+    assert_eq!(columns[1]["type_conflicts"], serde_json::json!([]));
+    assert_eq!(columns[3]["name"], "active");
+    assert_eq!(columns[3]["files_missing"], serde_json::json!([cast]));
+
+    fs::remove_file(&cast_path)?;
+    Ok(())
+}
+
 #[test]
 fn test_schema_multi_file_csv_includes_source_file() -> Result<()> {
     let file = fixture_path();
@@ -138,6 +332,53 @@ fn test_schema_multi_file_csv_includes_source_file() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_schema_extended_shows_levels_and_field_ids() -> Result<()> {
+    let schema = Arc::new(parse_message_type(
+        "message m {
+            required int64 id = 1;
+            optional group tags (LIST) {
+                repeated group list { optional binary element (STRING); }
+            }
+        }",
+    )?);
+    let path = temp_path("schema_extended", "parquet")?;
+    SerializedFileWriter::new(fs::File::create(&path)?, schema, Default::default())?.close()?;
+    let file = path.display().to_string();
+
+    let output = pq()
+        .args(["schema", "--extended", &file, "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "column,type,nullable,ordinal,max_definition_level,max_repetition_level,field_id\n\
+         id,INT64,false,0,0,0,1\n\
+         element,STRING,true,1,3,1,\n"
+    );
+
+    let output = pq()
+        .args(["schema", "--extended", &file, "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["field_id"], serde_json::json!(1));
+    assert_eq!(rows[1]["max_repetition_level"], serde_json::json!(1));
+    assert_eq!(rows[1]["field_id"], serde_json::Value::Null);
+
+    let output = pq().args(["schema", &file, "-o", "json"]).output()?;
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(rows[0].get("ordinal").is_none());
+
+    let output = pq().args(["schema", "--extended", &file]).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Field ID"));
+    assert!(stdout.contains("Max Rep"));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
 #[test]
 fn test_head() -> Result<()> {
     let output = pq().args(["head", &fixture_path()]).output()?;
@@ -195,468 +436,4632 @@ fn test_tail() -> Result<()> {
 }
 
 #[test]
-fn test_count() -> Result<()> {
-    let output = pq().args(["count", &fixture_path()]).output()?;
-    assert!(output.status.success());
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert_eq!(stdout.trim(), "5");
+fn test_head_and_tail_sort_by_several_keys() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("city", DataType::Utf8, true),
+        Field::new("amount", DataType::Int64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("Oslo"),
+                None,
+                Some("Lima"),
+                Some("Oslo"),
+                Some("Lima"),
+            ])) as ArrayRef,
+            Arc::new(Int64Array::from(vec![
+                Some(10),
+                Some(5),
+                None,
+                Some(30),
+                Some(20),
+            ])) as ArrayRef,
+        ],
+    )?;
+    let path = temp_path("sort_by", "parquet")?;
+    write_parquet(&path, schema, &[batch], None)?;
+    let file = path.display().to_string();
+    let ids = |args: &[&str]| -> Result<String> {
+        let output = pq()
+            .arg(args[0])
+            .arg(&file)
+            .args(&args[1..])
+            .args(["-o", "csv", "--no-header"])
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(String::from_utf8(output.stdout)?
+            .lines()
+            .filter_map(|line| line.split(',').next())
+            .map(|id| format!("{id} "))
+            .collect())
+    };
+
+    // Nulls first by default; ties on city fall to amount, then to read order
+    assert_eq!(ids(&["head", "--sort-by", "city"])?, "2 3 5 1 4 ");
+    assert_eq!(
+        ids(&[
+            "head",
+            "--sort-by",
+            "city,nulls-last",
+            "--sort-by",
+            "amount,desc"
+        ])?,
+        "3 5 4 1 2 "
+    );
+    // Only the rows shown are sorted, and tail's streamed formats sort too
+    assert_eq!(ids(&["head", "-n", "3", "--sort-by", "amount"])?, "3 2 1 ");
+    assert_eq!(ids(&["tail", "-n", "2", "--sort-by", "id,desc"])?, "5 4 ");
+
+    let output = pq()
+        .args(["head", &file, "--sort-by", "id,sideways"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown sort option"));
+
+    fs::remove_file(path)?;
     Ok(())
 }
 
 #[test]
-fn test_stats() -> Result<()> {
-    let output = pq().args(["stats", &fixture_path()]).output()?;
+fn test_table_escapes_control_characters() -> Result<()> {
+    let title = "a\u{1b}]0;title\u{7}b";
+    let schema = Arc::new(Schema::new(vec![Field::new("note", DataType::Utf8, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(StringArray::from(vec![title])) as ArrayRef],
+    )?;
+    let path = temp_path("control_chars", "parquet")?;
+    write_parquet(&path, schema, &[batch], None)?;
+    let file = path.display().to_string();
+
+    let output = pq().args(["head", &file]).output()?;
     assert!(output.status.success());
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("Column"));
-    assert!(stdout.contains("Min"));
-    assert!(stdout.contains("Max"));
-    assert!(stdout.contains("id"));
+    assert!(stdout.contains(r"a\x1b]0;title\x07b"), "{stdout}");
+    assert!(!stdout.contains('\u{1b}'));
+
+    let output = pq().args(["head", &file, "--raw-control-chars"]).output()?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains(title));
+
+    let output = pq().args(["head", &file, "-o", "csv"]).output()?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        format!("note\n{title}\n")
+    );
+    let output = pq().args(["head", &file, "-o", "jsonl"]).output()?;
+    let row: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(row["note"], serde_json::json!(title));
+
+    fs::remove_file(path)?;
     Ok(())
 }
 
 #[test]
-fn test_stats_multi_file_json_is_parseable() -> Result<()> {
-    let file = fixture_path();
-    let output = pq().args(["stats", &file, &file, "-o", "json"]).output()?;
+fn test_unusual_file_names_stay_intact_in_output() -> Result<()> {
+    let dir = temp_path("odd_names", "d")?;
+    fs::create_dir(&dir)?;
+    let mut names = vec![
+        (
+            dir.join("a,b \"quoted\" name.parquet"),
+            "a,b \"quoted\" name.parquet",
+        ),
+        (dir.join("new\nline.parquet"), r"new\x0aline.parquet"),
+    ];
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let name = std::ffi::OsStr::from_bytes(b"bad\xff.parquet");
+        names.push((dir.join(name), r"bad\xff.parquet"));
+    }
+    for (path, _) in &names {
+        fs::copy(fixture_path(), path)?;
+    }
+    let shown = |name: &str| format!("{}/{name}", dir.display());
+
+    // One line per file, each naming it exactly
+    let output = pq().arg("count").arg(&dir).output()?;
     assert!(output.status.success());
-    assert_no_source_headers(&output.stdout);
+    let stdout = String::from_utf8(output.stdout)?;
+    for (_, name) in &names {
+        assert!(
+            stdout.contains(&format!("{}: 5\n", shown(name))),
+            "{stdout}"
+        );
+    }
+    assert_eq!(stdout.lines().count(), names.len() + 1);
 
-    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    let rows = rows
-        .as_array()
-        .ok_or_else(|| anyhow::anyhow!("stats json output should be an array"))?;
-    assert_eq!(rows.len(), 8);
-    assert_eq!(rows[0]["file"], serde_json::json!(file));
-    assert_eq!(rows[4]["file"], serde_json::json!(file));
-    Ok(())
-}
+    // CSV quotes the file column, so every row keeps its fields
+    let output = pq().args(["info", "-o", "csv"]).arg(&dir).output()?;
+    assert!(output.status.success());
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    let mut files = reader
+        .records()
+        .map(|record| Ok(record?.get(0).unwrap_or_default().to_string()))
+        .collect::<Result<Vec<_>>>()?;
+    let mut expected: Vec<String> = names.iter().map(|(_, name)| shown(name)).collect();
+    files.sort();
+    expected.sort();
+    assert_eq!(files, expected);
 
-#[test]
-fn test_stats_multi_file_csv_includes_source_file() -> Result<()> {
-    let file = fixture_path();
-    let output = pq().args(["stats", &file, &file, "-o", "csv"]).output()?;
+    let output = pq()
+        .args(["head", "-n", "1", "-H", "-o", "jsonl"])
+        .arg(&dir)
+        .output()?;
     assert!(output.status.success());
-    assert_no_source_headers(&output.stdout);
+    let mut files = String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| {
+            let row: serde_json::Value = serde_json::from_str(line)?;
+            Ok(row["_file"].as_str().unwrap_or_default().to_string())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    files.sort();
+    assert_eq!(files, expected);
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut lines = stdout.lines();
-    assert_eq!(lines.next(), Some("file,column,type,null_count,min,max"));
-    let first_row = lines
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("stats csv should contain rows"))?;
-    assert!(first_row.starts_with(&format!("{file},id,")));
+    fs::remove_dir_all(&dir)?;
     Ok(())
 }
 
 #[test]
-fn test_multi_file_jsonl_outputs_only_json_lines() -> Result<()> {
-    let file = fixture_path();
-
-    for command in ["schema", "stats", "head"] {
-        let output = pq().args([command, &file, &file, "-o", "jsonl"]).output()?;
+fn test_column_selectors() -> Result<()> {
+    let fixture = fixture_path();
+    let head_csv = |selectors: &str| -> Result<String> {
+        let output = pq()
+            .args(["head", &fixture, "-n", "1", "-o", "csv", "-c", selectors])
+            .output()?;
         assert!(output.status.success());
-        assert_no_source_headers(&output.stdout);
+        Ok(String::from_utf8(output.stdout)?)
+    };
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let lines = stdout.lines().collect::<Vec<_>>();
-        assert!(!lines.is_empty());
-        for line in lines {
-            let value: serde_json::Value = serde_json::from_str(line)?;
-            assert!(value.is_object());
-            if command != "head" {
-                assert_eq!(value["file"], serde_json::json!(file));
-            }
-        }
-    }
+    // Regex, range, and glob selectors mix with names and keep selector order
+    assert_eq!(head_csv("re:^(name|id)$")?, "id,name\n1,Alice\n");
+    assert_eq!(head_csv("3:4,1")?, "amount,active,id\n100.5,true,1\n");
+    assert_eq!(head_csv("*e,id")?, "name,active,id\nAlice,true,1\n");
+    assert_eq!(head_csv("name,id")?, "name,id\nAlice,1\n");
+
+    let output = pq()
+        .args(["stats", &fixture, "-c", "a*", "-o", "jsonl"])
+        .output()?;
+    assert!(output.status.success());
+    let columns: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).map(|row| row["column"].to_string())
+        })
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(columns, [r#""amount""#, r#""active""#]);
+
+    let output = pq().args(["head", &fixture, "-c", "2:9"]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Column position 9 out of range"));
+
+    let output = pq().args(["head", &fixture, "-c", "re:("]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Invalid column selector: re:("));
+
+    let output = pq()
+        .args(["head", &fixture, "-c", "name,id,name"])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Invalid column selector: name\n  listed more than once"));
 
     Ok(())
 }
 
 #[test]
-fn test_multi_file_table_output_keeps_source_headers() -> Result<()> {
-    let file = fixture_path();
-    let output = pq().args(["schema", &file, &file]).output()?;
+fn test_describe_summarizes_footer() -> Result<()> {
+    let path = temp_path("describe", "parquet")?;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("note", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(vec![3, 1, 4, 2])) as ArrayRef,
+            Arc::new(StringArray::from(vec![Some("b"), None, Some("a"), None])) as ArrayRef,
+        ],
+    )?;
+    write_parquet(&path, schema, &[batch], Some(2))?;
+    let path_arg = path.display().to_string();
+
+    let output = pq().args(["describe", &path_arg, "-o", "json"]).output()?;
     assert!(output.status.success());
+    let document: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(document["num_rows"], 4);
+    assert_eq!(document["num_row_groups"], 2);
+    let columns = document["columns"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("columns should be an array"))?;
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0]["name"], "id");
+    assert_eq!(columns[0]["nullable"], false);
+    assert_eq!(columns[0]["min"], 1);
+    assert_eq!(columns[0]["max"], 4);
+    assert_eq!(columns[1]["logical_type"], "STRING");
+    assert_eq!(columns[1]["null_count"], 2);
+    assert_eq!(columns[1]["null_percent"], 50.0);
+    assert_eq!(columns[1]["min"], "a");
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert_eq!(stdout.matches("==>").count(), 2);
+    let output = pq().args(["describe", &path_arg]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("Row Groups"));
+    assert!(stdout.contains("50.00%"));
+
+    fs::remove_file(path)?;
     Ok(())
 }
 
 #[test]
-fn test_info() -> Result<()> {
-    let output = pq().args(["info", &fixture_path()]).output()?;
+fn test_row_numbers() -> Result<()> {
+    let path = temp_path("row_numbers", "parquet")?;
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(Int64Array::from_iter_values(100..110)) as ArrayRef],
+    )?;
+    write_parquet(&path, schema, &[batch], Some(3))?;
+    let path = path.to_string_lossy().into_owned();
+
+    let output = pq()
+        .args(["head", &path, "-n", "2", "--row-numbers", "-o", "csv"])
+        .output()?;
     assert!(output.status.success());
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("Rows"));
-    assert!(stdout.contains("Columns"));
-    assert!(stdout.contains("Compression"));
-    Ok(())
-}
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "#,id\n0,100\n1,101\n"
+    );
 
-#[test]
-fn test_info_multi_file_json_is_parseable() -> Result<()> {
-    let file = fixture_path();
-    let output = pq().args(["info", &file, &file, "-o", "json"]).output()?;
+    // Tail counts from the start of the file, across row groups
+    let output = pq()
+        .args(["tail", &path, "-n", "4", "--row-numbers", "-o", "jsonl"])
+        .output()?;
     assert!(output.status.success());
-    assert_no_source_headers(&output.stdout);
+    let rows: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    let indexes: Vec<_> = rows.iter().map(|row| row["_row"].clone()).collect();
+    assert_eq!(indexes, [6, 7, 8, 9]);
+    assert_eq!(rows[0]["id"], 106);
 
+    // Each file counts from zero, and combined output names the file
+    let fixture = fixture_path();
+    let output = pq()
+        .args([
+            "tail",
+            &fixture,
+            &fixture,
+            "-n",
+            "1",
+            "--row-numbers",
+            "-o",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
     let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    let rows = rows
-        .as_array()
-        .ok_or_else(|| anyhow::anyhow!("info json output should be an array"))?;
-    assert_eq!(rows.len(), 2);
-    assert_eq!(rows[0]["file"], serde_json::json!(file));
-    assert_eq!(rows[1]["file"], serde_json::json!(file));
+    assert_eq!(rows[0]["_row"], 4);
+    assert_eq!(rows[0]["_file"], serde_json::json!(fixture));
+    assert_eq!(rows[1]["_row"], 4);
+
+    fs::remove_file(&path)?;
     Ok(())
 }
 
 #[test]
-fn test_convert_csv() -> Result<()> {
-    let temp_dir = std::env::temp_dir();
-    let output_path = temp_dir.join("pq_test_output.csv");
-
+fn test_with_filename() -> Result<()> {
+    let fixture = fixture_path();
     let output = pq()
         .args([
-            "convert",
-            &fixture_path(),
-            &output_path.display().to_string(),
+            "head", &fixture, &fixture, "-n", "1", "-H", "-q", "-o", "jsonl",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let rows: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["_file"], serde_json::json!(fixture));
+    assert_eq!(rows[1]["id"], 1);
+
+    // An existing `file` column keeps its name, and the source path takes a suffix
+    let path = temp_path("with_filename", "parquet")?;
+    let schema = Arc::new(Schema::new(vec![Field::new("file", DataType::Utf8, false)]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(StringArray::from(vec!["data.csv"])) as ArrayRef],
+    )?;
+    write_parquet(&path, schema, &[batch], None)?;
+    let path = path.to_string_lossy().into_owned();
+
+    let output = pq()
+        .args([
+            "tail",
+            &path,
+            "--with-filename",
+            "--row-numbers",
+            "-o",
+            "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!("#,file_1,file\n0,{path},data.csv\n")
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'file_1'"));
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_global_row_budget_spans_files() -> Result<()> {
+    let fixture = fixture_path();
+    let output = pq()
+        .args([
+            "head", &fixture, &fixture, "-n", "7", "--global", "-o", "jsonl",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let ids: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).map(|row| row["id"].clone()))
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(ids, [1, 2, 3, 4, 5, 1, 2]);
+
+    // Files past the budget are never opened, so an unreadable one does not matter
+    let broken = temp_path("global_broken", "parquet")?;
+    fs::write(&broken, b"not parquet")?;
+    let broken = broken.to_string_lossy().into_owned();
+
+    let output = pq()
+        .args([
+            "head", &fixture, &broken, "-n", "2", "--global", "-o", "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let output = pq()
+        .args(["tail", &broken, &fixture, &fixture, "-n", "6", "--global"])
+        .args(["--row-numbers", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(rows.len(), 7);
+    assert!(rows[1].starts_with("4,"), "{stdout}");
+
+    let output = pq()
+        .args(["head", &fixture, &broken, "-n", "2", "-o", "csv"])
+        .output()?;
+    assert!(!output.status.success());
+
+    fs::remove_file(&broken)?;
+    Ok(())
+}
+
+#[test]
+fn test_count() -> Result<()> {
+    let output = pq().args(["count", &fixture_path()]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "5");
+    Ok(())
+}
+
+/// Copy `source` to `dest` with its thrift footer changed by `edit`, leaving the row
+/// groups and their data untouched.
+fn rewrite_footer(
+    source: &str,
+    dest: &Path,
+    edit: impl FnOnce(&mut parquet::format::FileMetaData),
+) -> Result<()> {
+    use parquet::format::FileMetaData;
+    use parquet::thrift::TSerializable;
+    use thrift::protocol::{TCompactInputProtocol, TCompactOutputProtocol};
+
+    let bytes = fs::read(source)?;
+    let tail = bytes
+        .len()
+        .checked_sub(8)
+        .ok_or_else(|| anyhow::anyhow!("file too short"))?;
+    let length = bytes
+        .get(tail..tail + 4)
+        .ok_or_else(|| anyhow::anyhow!("missing footer length"))?;
+    let footer_len = u32::from_le_bytes(length.try_into()?) as usize;
+    let footer_start = tail
+        .checked_sub(footer_len)
+        .ok_or_else(|| anyhow::anyhow!("bad footer length"))?;
+    let mut footer = bytes
+        .get(footer_start..tail)
+        .ok_or_else(|| anyhow::anyhow!("missing footer"))?;
+    let mut metadata =
+        FileMetaData::read_from_in_protocol(&mut TCompactInputProtocol::new(&mut footer))?;
+    edit(&mut metadata);
+
+    let mut encoded = Vec::new();
+    metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut encoded))?;
+    let mut rewritten = bytes
+        .get(..footer_start)
+        .ok_or_else(|| anyhow::anyhow!("missing data pages"))?
+        .to_vec();
+    rewritten.extend_from_slice(&encoded);
+    rewritten.extend_from_slice(&u32::try_from(encoded.len())?.to_le_bytes());
+    rewritten.extend_from_slice(b"PAR1");
+    fs::write(dest, rewritten)?;
+    Ok(())
+}
+
+#[test]
+fn test_count_verify_checks_footer_against_data() -> Result<()> {
+    let output = pq().args(["count", "--verify", &fixture_path()]).output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+
+    let path = temp_path("count_verify", "parquet")?;
+    rewrite_footer(&fixture_path(), &path, |metadata| metadata.num_rows += 2)?;
+    let path_str = path.to_string_lossy().to_string();
+
+    // Without --verify the footer is trusted
+    let output = pq().args(["count", &path_str]).output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "7");
+
+    let output = pq().args(["count", "--verify", &path_str]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Row count mismatch"), "{stderr}");
+    assert!(
+        stderr.contains("footer says 7 rows, but 5 rows decoded"),
+        "{stderr}"
+    );
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_compressed_files_are_not_reported_as_corrupt() -> Result<()> {
+    use flate2::write::GzEncoder;
+    use std::io::Write;
+
+    let path = temp_path("gzipped", "parquet.gz")?;
+    let mut encoder = GzEncoder::new(fs::File::create(&path)?, flate2::Compression::default());
+    encoder.write_all(&fs::read(fixture_path())?)?;
+    encoder.finish()?;
+    let path = path.to_string_lossy().to_string();
+
+    for command in ["schema", "head", "count", "info"] {
+        let output = pq().args([command, path.as_str()]).output()?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains(&format!("File is gzip-compressed: {path}")),
+            "{command}: {stderr}"
+        );
+        assert!(stderr.contains("decompress it first, e.g. with `gunzip`"));
+    }
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_encrypted_files_are_not_reported_as_corrupt() -> Result<()> {
+    use parquet::format::{AesGcmV1, EncryptionAlgorithm};
+
+    // Encrypted footer: the file ends in PARE instead of PAR1
+    let encrypted_footer = temp_path("encrypted_footer", "parquet")?;
+    let mut bytes = fs::read(fixture_path())?;
+    let magic = bytes.len() - 4;
+    bytes.truncate(magic);
+    bytes.extend_from_slice(b"PARE");
+    fs::write(&encrypted_footer, bytes)?;
+
+    // Plaintext footer: the algorithm is named and column metadata is left out
+    let encrypted_columns = temp_path("encrypted_columns", "parquet")?;
+    rewrite_footer(&fixture_path(), &encrypted_columns, |metadata| {
+        metadata.encryption_algorithm = Some(EncryptionAlgorithm::AESGCMV1(AesGcmV1::new(
+            None, None, None,
+        )));
+        for row_group in &mut metadata.row_groups {
+            for column in &mut row_group.columns {
+                column.meta_data = None;
+            }
+        }
+    })?;
+
+    for (path, commands) in [
+        (&encrypted_footer, &["schema", "head", "count", "stats"][..]),
+        (&encrypted_columns, &["schema", "head", "stats"][..]),
+    ] {
+        let path = path.to_string_lossy().to_string();
+        for command in commands {
+            let output = pq().args([*command, path.as_str()]).output()?;
+            assert!(!output.status.success());
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            assert!(stderr.contains("File is encrypted"), "{command}: {stderr}");
+        }
+    }
+    // Plaintext footer with column metadata intact: the footer loads, and only the
+    // pages, encrypted here as unreadable bytes, fail to decode
+    let scrambled = temp_path("encrypted_pages_source", "parquet")?;
+    let mut bytes = fs::read(fixture_path())?;
+    let tail = bytes.len() - 8;
+    let footer_len = u32::from_le_bytes(bytes[tail..tail + 4].try_into()?) as usize;
+    for byte in &mut bytes[4..tail - footer_len] {
+        *byte ^= 0xff;
+    }
+    fs::write(&scrambled, bytes)?;
+    let encrypted_pages = temp_path("encrypted_pages", "parquet")?;
+    rewrite_footer(&scrambled.to_string_lossy(), &encrypted_pages, |metadata| {
+        metadata.encryption_algorithm = Some(EncryptionAlgorithm::AESGCMV1(AesGcmV1::new(
+            None, None, None,
+        )));
+    })?;
+    let encrypted_pages_text = encrypted_pages.to_string_lossy().to_string();
+    for args in [
+        &["head", encrypted_pages_text.as_str()][..],
+        &["tail", &encrypted_pages_text],
+        &["count", &encrypted_pages_text, "--by", "name"],
+        &["count", &encrypted_pages_text, "--where", "id > 1"],
+        &["nulls", &encrypted_pages_text],
+    ] {
+        let output = pq().args(args).output()?;
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains("File is encrypted"), "{args:?}: {stderr}");
+    }
+
+    // A plaintext footer still holds the row count
+    let output = pq()
+        .args(["count", &encrypted_columns.to_string_lossy()])
+        .output()?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+
+    let truncated = temp_path("truncated", "parquet")?;
+    let bytes = fs::read(fixture_path())?;
+    fs::write(&truncated, bytes.get(..bytes.len() / 2).unwrap_or_default())?;
+    let output = pq().args(["head", &truncated.to_string_lossy()]).output()?;
+    assert!(!output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("encrypted"));
+
+    fs::remove_file(&encrypted_footer)?;
+    fs::remove_file(&encrypted_columns)?;
+    fs::remove_file(&scrambled)?;
+    fs::remove_file(&encrypted_pages)?;
+    fs::remove_file(&truncated)?;
+    Ok(())
+}
+
+#[test]
+fn test_files_still_being_written_are_reported_and_awaited() -> Result<()> {
+    let path = temp_path("being_written", "parquet")?;
+    let bytes = fs::read(fixture_path())?;
+    let partial = bytes.get(..bytes.len() / 2).unwrap_or_default().to_vec();
+    fs::write(&path, &partial)?;
+    let text = path.to_string_lossy().to_string();
+
+    let output = pq().args(["head", &text]).output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            "File appears to be in the middle of being written: {text}"
+        )),
+        "{stderr}"
+    );
+    assert!(stderr.contains("pass --wait-for-complete"));
+
+    let output = pq()
+        .args(["count", &text, "--wait-for-complete", "--timeout", "200ms"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Its footer had not appeared after 200ms")
+    );
+
+    // The writer finishes while pq waits
+    let writer = {
+        let path = path.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(300));
+            fs::write(path, bytes)
+        })
+    };
+    let output = pq()
+        .args(["count", &text, "--wait-for-complete", "--timeout", "10s"])
+        .output()?;
+    writer
+        .join()
+        .map_err(|_| anyhow::anyhow!("writer thread panicked"))??;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats() -> Result<()> {
+    let output = pq().args(["stats", &fixture_path()]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Column"));
+    assert!(stdout.contains("Min"));
+    assert!(stdout.contains("Max"));
+    assert!(stdout.contains("id"));
+    Ok(())
+}
+
+#[test]
+fn test_stats_rejects_unknown_columns() -> Result<()> {
+    let file = fixture_path();
+    for extra in [&[][..], &["--by-row-group"][..]] {
+        let output = pq()
+            .args(["stats", &file, "-c", "id,typo_column"])
+            .args(extra)
+            .output()?;
+        assert_eq!(output.status.code(), Some(1));
+        assert!(output.stdout.is_empty());
+        let stderr = String::from_utf8(output.stderr)?;
+        assert!(stderr.contains("Column not found"), "{stderr}");
+        assert!(stderr.contains("typo_column"));
+        assert!(stderr.contains("Available columns: id, name, amount, active"));
+    }
+
+    let output = pq()
+        .args(["stats", &file, "-c", "id,amount", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?.lines().count(), 3);
+    Ok(())
+}
+
+#[test]
+fn test_ignore_column_case_resolves_names_per_file() -> Result<()> {
+    let write = |name: &str, id: &str| -> Result<String> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(id, DataType::Int64, false),
+            Field::new("Amount", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+                Arc::new(Int64Array::from(vec![10, 20])) as ArrayRef,
+            ],
+        )?;
+        let path = temp_path(name, "parquet")?;
+        write_parquet(&path, schema, &[batch], None)?;
+        Ok(path.display().to_string())
+    };
+    let upper = write("column_case_upper", "UserID")?;
+    let lower = write("column_case_lower", "userid")?;
+
+    let output = pq().args(["stats", &upper, "-c", "userid"]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Did you mean: UserID"));
+
+    let output = pq()
+        .args([
+            "head",
+            &upper,
+            &lower,
+            "-c",
+            "USERID",
+            "--ignore-column-case",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("| UserID |") && stdout.contains("| userid |"));
+    assert!(!stdout.contains("Amount"));
+
+    for args in [
+        &["stats", "-c", "userid,amount"][..],
+        &["hist", "-c", "amount"][..],
+        &["count", "--by", "USERID"][..],
+    ] {
+        let output = pq()
+            .args(args)
+            .args([&upper, &lower, "--ignore-column-case"])
+            .output()?;
+        assert!(output.status.success(), "{args:?}");
+    }
+
+    let transformed = temp_path("column_case_transformed", "parquet")?;
+    let output = pq()
+        .args(["transform", &upper, "-o"])
+        .arg(&transformed)
+        .args(["--drop", "amount", "--ignore-column-case"])
+        .output()?;
+    assert!(output.status.success());
+    let output = pq()
+        .args(["head", &transformed.display().to_string(), "-o", "csv"])
+        .output()?;
+    assert_eq!(String::from_utf8(output.stdout)?, "UserID\n1\n2\n");
+
+    for path in [upper, lower] {
+        fs::remove_file(path)?;
+    }
+    fs::remove_file(transformed)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_multi_file_json_is_parseable() -> Result<()> {
+    let file = fixture_path();
+    let output = pq().args(["stats", &file, &file, "-o", "json"]).output()?;
+    assert!(output.status.success());
+    assert_no_source_headers(&output.stdout);
+
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let rows = rows
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("stats json output should be an array"))?;
+    assert_eq!(rows.len(), 8);
+    assert_eq!(rows[0]["file"], serde_json::json!(file));
+    assert_eq!(rows[4]["file"], serde_json::json!(file));
+    Ok(())
+}
+
+#[test]
+fn test_stats_multi_file_csv_includes_source_file() -> Result<()> {
+    let file = fixture_path();
+    let output = pq().args(["stats", &file, &file, "-o", "csv"]).output()?;
+    assert!(output.status.success());
+    assert_no_source_headers(&output.stdout);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("file,column,type,null_count,min,max,compressed_bytes,uncompressed_bytes,compressed_percent")
+    );
+    let first_row = lines
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("stats csv should contain rows"))?;
+    assert!(first_row.starts_with(&format!("{file},id,")));
+    Ok(())
+}
+
+#[test]
+fn test_multi_file_jsonl_outputs_only_json_lines() -> Result<()> {
+    let file = fixture_path();
+
+    for command in ["schema", "stats", "head"] {
+        let output = pq().args([command, &file, &file, "-o", "jsonl"]).output()?;
+        assert!(output.status.success());
+        assert_no_source_headers(&output.stdout);
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines = stdout.lines().collect::<Vec<_>>();
+        assert!(!lines.is_empty());
+        for line in lines {
+            let value: serde_json::Value = serde_json::from_str(line)?;
+            assert!(value.is_object());
+            if command != "head" {
+                assert_eq!(value["file"], serde_json::json!(file));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_multi_file_table_output_keeps_source_headers() -> Result<()> {
+    let file = fixture_path();
+    let output = pq().args(["schema", &file, &file]).output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("==>").count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_quiet_keeps_headers_and_no_header_drops_them() -> Result<()> {
+    let file = fixture_path();
+    let schema = |flag: &str| -> Result<String> {
+        let output = pq().args(["schema", &file, &file, flag]).output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    let quiet = schema("-q")?;
+    assert!(!quiet.contains("==>"));
+    assert_eq!(quiet.matches("Column").count(), 2);
+
+    let no_header = schema("--no-header")?;
+    assert_eq!(no_header.matches("==>").count(), 2);
+    assert!(!no_header.contains("Column"));
+
+    let output = pq()
+        .args(["head", &file, "-n", "1", "-c", "id", "-o", "csv", "-q"])
+        .output()?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "id\n1\n");
+    let output = pq()
+        .args([
+            "head",
+            &file,
+            "-n",
+            "1",
+            "-c",
+            "id",
+            "-o",
+            "csv",
+            "--no-header",
+        ])
+        .output()?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+    Ok(())
+}
+
+#[test]
+fn test_info() -> Result<()> {
+    let output = pq().args(["info", &fixture_path()]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Rows"));
+    assert!(stdout.contains("Columns"));
+    assert!(stdout.contains("Compression"));
+    Ok(())
+}
+
+#[test]
+fn test_info_multi_file_json_is_parseable() -> Result<()> {
+    let file = fixture_path();
+    let output = pq().args(["info", &file, &file, "-o", "json"]).output()?;
+    assert!(output.status.success());
+    assert_no_source_headers(&output.stdout);
+
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let rows = rows
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("info json output should be an array"))?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["file"], serde_json::json!(file));
+    assert_eq!(rows[1]["file"], serde_json::json!(file));
+    Ok(())
+}
+
+#[test]
+fn test_bench_reports_timings_as_json() -> Result<()> {
+    let file = fixture_path();
+    let output = pq()
+        .args([
+            "bench",
+            "stats",
+            &file,
+            &file,
+            "--iterations",
+            "3",
+            "-o",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(result["operation"], "stats");
+    assert_eq!(result["files"], 2);
+    assert_eq!(result["rows"], 10);
+    assert_eq!(result["warmup"], 2);
+    assert_eq!(result["iterations"], 3);
+    let timings = result["timings_ms"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("bench json should list every run's time"))?;
+    assert_eq!(timings.len(), 3);
+    assert!(result["median_ms"].as_f64() >= result["min_ms"].as_f64());
+
+    let output = pq()
+        .args(["bench", "count", &file, "--iterations", "0"])
+        .output()?;
+    assert!(!output.status.success());
+    Ok(())
+}
+
+#[test]
+fn test_info_row_groups_reports_layout() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from_iter_values(0..5)) as ArrayRef],
+    )?;
+    let path = temp_path("info_row_groups", "parquet")?;
+    write_parquet(&path, schema, &[batch], Some(2))?;
+
+    let output = pq()
+        .args([
+            "info",
+            "--row-groups",
+            &path.display().to_string(),
+            "-o",
+            "jsonl",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let rows = String::from_utf8(output.stdout)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<Vec<serde_json::Value>, _>>()?;
+    let counts: Vec<_> = rows.iter().map(|row| row["num_rows"].clone()).collect();
+    assert_eq!(counts, [2, 2, 1]);
+    assert_eq!(rows[2]["row_group"], 2);
+    assert_eq!(rows[0]["statistics_columns"], 1);
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_human_readable_groups_table_integers_only() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("n", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![1_234_567_890, -1_000])) as ArrayRef],
+    )?;
+    let path = temp_path("human_readable", "parquet")?;
+    write_parquet(&path, schema, &[batch], None)?;
+    let file = path.display().to_string();
+
+    let table = pq().args(["head", &file, "--human-readable"]).output()?;
+    assert!(table.status.success());
+    let table = String::from_utf8(table.stdout)?;
+    assert!(table.contains("1,234,567,890"));
+    assert!(table.contains("-1,000"));
+
+    let csv = pq()
+        .args(["head", &file, "--human-readable", "-o", "csv"])
+        .output()?;
+    assert_eq!(String::from_utf8(csv.stdout)?, "n\n1234567890\n-1000\n");
+
+    let info = pq().args(["info", &file, "--raw-bytes"]).output()?;
+    assert!(info.status.success());
+    let size = fs::metadata(&path)?.len().to_string();
+    assert!(String::from_utf8(info.stdout)?.contains(&size));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_info_compression_ratio_without_rows() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+    let path = temp_path("info_no_rows", "parquet")?;
+    write_parquet(&path, schema, &[], None)?;
+    let file = path.display().to_string();
+
+    let output = pq().args(["info", &file]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let ratio = stdout
+        .lines()
+        .find(|line| line.contains("Compression Ratio"))
+        .unwrap_or_default();
+    assert!(ratio.contains("N/A"), "{stdout}");
+
+    let output = pq().args(["info", &file, "-o", "json"]).output()?;
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["uncompressed_bytes"], 0);
+    assert!(rows[0]["compression_ratio"].is_null());
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_info_summarizes_mixed_codecs() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Int64, false),
+        Field::new("c", DataType::Int64, false),
+    ]));
+    let column = || Arc::new(Int64Array::from_iter_values(0..100)) as ArrayRef;
+    let batch = RecordBatch::try_new(Arc::clone(&schema), vec![column(), column(), column()])?;
+    let path = temp_path("info_mixed_codecs", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_compression(parquet::basic::Compression::SNAPPY)
+        .set_column_compression(
+            parquet::schema::types::ColumnPath::from("c"),
+            parquet::basic::Compression::UNCOMPRESSED,
+        )
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let file = path.display().to_string();
+
+    let output = pq().args(["info", &file, "-o", "json"]).output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["compression"], serde_json::json!("MIXED"));
+    assert_eq!(
+        rows[0]["compression_codecs"],
+        serde_json::json!({"SNAPPY": 2, "UNCOMPRESSED": 1})
+    );
+    let compressed = rows[0]["compressed_bytes"].as_f64().unwrap_or_default();
+    let uncompressed = rows[0]["uncompressed_bytes"].as_f64().unwrap_or_default();
+    assert!(compressed > 0.0);
+    assert_eq!(
+        rows[0]["compression_ratio"],
+        serde_json::json!(uncompressed / compressed)
+    );
+
+    let output = pq().args(["info", &file]).output()?;
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("MIXED (SNAPPY: 2 cols, UNCOMPRESSED: 1 cols)"));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_info_reports_declared_sort_order() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("ts", DataType::Int64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from_iter_values(0..4)) as ArrayRef,
+            Arc::new(Int64Array::from_iter_values(10..14)) as ArrayRef,
+        ],
+    )?;
+    let path = temp_path("info_sorted", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(2)
+        .set_sorting_columns(Some(vec![
+            parquet::format::SortingColumn::new(1, false, true),
+            parquet::format::SortingColumn::new(0, true, false),
+        ]))
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let file = path.display().to_string();
+
+    let output = pq().args(["info", &file]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("ts (asc, nulls first), id (desc, nulls last)"));
+
+    let output = pq().args(["info", &file, "-o", "json"]).output()?;
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["sort_order"], serde_json::json!("sorted"));
+    assert_eq!(
+        rows[0]["sorting_columns"],
+        serde_json::json!([
+            {"column": "ts", "descending": false, "nulls_first": true},
+            {"column": "id", "descending": true, "nulls_first": false},
+        ])
+    );
+
+    let output = pq()
+        .args(["info", "--row-groups", &file, "-o", "csv"])
+        .output()?;
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(stdout.lines().count(), 3);
+    assert!(stdout
+        .lines()
+        .skip(1)
+        .all(|line| line.ends_with("\"ts (asc, nulls first), id (desc, nulls last)\"")));
+
+    let output = pq()
+        .args(["info", &fixture_path(), "-o", "json"])
+        .output()?;
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["sort_order"], serde_json::json!("undeclared"));
+    assert_eq!(rows[0]["sorting_columns"], serde_json::json!([]));
+
+    // New CSV columns follow the original ones, and the sort order is spelled as in JSON
+    let output = pq().args(["info", &fixture_path(), "-o", "csv"]).output()?;
+    let mut reader = csv::Reader::from_reader(output.stdout.as_slice());
+    let headers: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+    assert_eq!(
+        headers,
+        [
+            "file",
+            "file_size_bytes",
+            "num_rows",
+            "num_columns",
+            "num_row_groups",
+            "compression",
+            "created_by",
+            "version",
+            "compressed_bytes",
+            "uncompressed_bytes",
+            "compression_ratio",
+            "sorted_by",
+        ]
+    );
+    let record = reader.records().next().transpose()?.unwrap_or_default();
+    assert_eq!(record.get(11), Some("undeclared"));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_csv() -> Result<()> {
+    let temp_dir = std::env::temp_dir();
+    let output_path = temp_dir.join("pq_test_output.csv");
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_path.display().to_string(),
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(output_path.exists());
+
+    let contents = fs::read_to_string(&output_path)?;
+    assert!(contents.contains("id,name,amount,active"));
+    assert!(contents.contains("Alice"));
+
+    let _ignored = fs::remove_file(&output_path);
+    Ok(())
+}
+
+#[test]
+fn test_convert_json() -> Result<()> {
+    let temp_dir = std::env::temp_dir();
+    let output_path = temp_dir.join("pq_test_output.json");
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_path.display().to_string(),
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(output_path.exists());
+
+    let contents = fs::read_to_string(&output_path)?;
+    assert!(contents.starts_with('['));
+    assert!(contents.contains("\"name\""));
+
+    let _ignored = fs::remove_file(&output_path);
+    Ok(())
+}
+
+#[test]
+fn test_convert_projects_columns_and_limits_rows() -> Result<()> {
+    let output_path = temp_path("convert_projection", "csv")?;
+    let output_arg = output_path.display().to_string();
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_arg,
+            "-c",
+            "name,id",
+            "-n",
+            "2",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        fs::read_to_string(&output_path)?,
+        "name,id\nAlice,1\nBob,2\n"
+    );
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_arg,
+            "--exclude",
+            "name,active",
+            "--force",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let contents = fs::read_to_string(&output_path)?;
+    assert!(contents.starts_with("id,amount\n"));
+    assert_eq!(contents.lines().count(), 6);
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_arg,
+            "-c",
+            "nme",
+            "--force",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Column not found"));
+    assert!(stderr.contains("Available columns: id, name, amount, active"));
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_arg,
+            "-c",
+            "id",
+            "--exclude",
+            "name",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_to_stdout_with_format_override() -> Result<()> {
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            "-",
+            "--format",
+            "jsonl",
+            "-n",
+            "2",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    let rows = stdout
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["name"], serde_json::json!("Bob"));
+
+    let output = pq().args(["convert", &fixture_path(), "-"]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("--format"));
+
+    let output_path = temp_path("convert_override", "csv")?;
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_path.display().to_string(),
+            "-f",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stderr)?.starts_with("warning:"));
+    assert!(fs::read_to_string(&output_path)?.starts_with('['));
+
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_arrow_output_reads_back() -> Result<()> {
+    let parquet_schema = parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+        fs::File::open(fixture_path())?,
+    )?
+    .schema()
+    .clone();
+
+    // Files get the IPC file format, with the parquet schema's types intact
+    let output_path = temp_path("convert_arrow", "arrow")?;
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_path.display().to_string(),
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let reader = arrow::ipc::reader::FileReader::try_new(fs::File::open(&output_path)?, None)?;
+    assert_eq!(reader.schema().fields(), parquet_schema.fields());
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 5);
+    fs::remove_file(output_path)?;
+
+    // Stdout gets the stream format, from convert and from -o arrow alike
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            "-",
+            "-f",
+            "arrow",
+            "-c",
+            "name,id",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let reader = arrow::ipc::reader::StreamReader::try_new(output.stdout.as_slice(), None)?;
+    let names: Vec<_> = reader
+        .schema()
+        .fields()
+        .iter()
+        .map(|f| f.name().clone())
+        .collect();
+    assert_eq!(names, ["name", "id"]);
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 5);
+
+    let output = pq()
+        .args(["tail", &fixture_path(), "-n", "2", "-o", "arrow"])
+        .output()?;
+    assert!(output.status.success());
+    let reader = arrow::ipc::reader::StreamReader::try_new(output.stdout.as_slice(), None)?;
+    assert_eq!(reader.schema().fields(), parquet_schema.fields());
+    let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(batches.iter().map(RecordBatch::num_rows).sum::<usize>(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_output_file_holds_what_stdout_would() -> Result<()> {
+    let fixture = fixture_path();
+    let path = temp_path("output_file", "out")?;
+    let file = path.display().to_string();
+    let commands: [&[&str]; 5] = [
+        &["stats", &fixture],
+        &["head", &fixture, "-o", "csv"],
+        &["count", &fixture, "--where", "id > 2", "--verbose"],
+        &["tail", &fixture, "-n", "2", "-o", "arrow"],
+        &["head", &fixture, "-o", "parquet"],
+    ];
+    for args in commands {
+        let stdout = pq().args(args).arg("--force").output()?;
+        assert!(stdout.status.success());
+        let redirected = pq().args(args).args(["--force", "-O", &file]).output()?;
+        assert!(redirected.status.success(), "{args:?}");
+        assert!(redirected.stdout.is_empty());
+        // Progress stays on stderr
+        assert_eq!(redirected.stderr, stdout.stderr);
+        assert_eq!(fs::read(&path)?, stdout.stdout, "{args:?}");
+    }
+
+    // An existing file needs --force, and a failed run leaves it as it was
+    let before = fs::read(&path)?;
+    let output = pq().args(["count", &fixture, "-O", &file]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--force"));
+    let output = pq()
+        .args(["count", "missing.parquet", "--force", "-O", &file])
+        .output()?;
+    assert!(!output.status.success());
+    assert_eq!(fs::read(&path)?, before);
+
+    let output = pq()
+        .args(["count", &fixture, "--force", "--output-file", &fixture])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("also an input"));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_parquet_output_round_trips() -> Result<()> {
+    let output = pq()
+        .args([
+            "grep",
+            "e",
+            &fixture_path(),
+            "-c",
+            "name",
+            "-o",
+            "parquet",
+            "-q",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(output.stdout.starts_with(b"PAR1") && output.stdout.ends_with(b"PAR1"));
+
+    let path = temp_path("parquet_output", "parquet")?;
+    fs::write(&path, &output.stdout)?;
+    let output = pq()
+        .args([
+            "head",
+            &path.display().to_string(),
+            "-o",
+            "csv",
+            "-c",
+            "name",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "name\nAlice\nCharlie\nEve\n"
+    );
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_compressed_output_round_trips() -> Result<()> {
+    let gzip_path = temp_path("convert_gzip", "csv.gz")?;
+    let output = pq()
+        .args(["convert", &fixture_path(), &gzip_path.display().to_string()])
+        .output()?;
+    assert!(output.status.success());
+    let decompressed = Command::new("gzip").arg("-dc").arg(&gzip_path).output()?;
+    assert!(decompressed.status.success());
+    let csv = String::from_utf8(decompressed.stdout)?;
+    assert_eq!(csv.lines().count(), 6);
+    assert!(csv.starts_with("id,name,amount,active\n"));
+
+    let zstd_path = temp_path("convert_zstd", "jsonl.zst")?;
+    let output = pq()
+        .args(["convert", &fixture_path(), &zstd_path.display().to_string()])
+        .output()?;
+    assert!(output.status.success());
+    let jsonl = String::from_utf8(zstd::decode_all(fs::File::open(&zstd_path)?)?)?;
+    assert_eq!(jsonl.lines().count(), 5);
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            "-",
+            "--format",
+            "jsonl",
+            "--compress",
+            "zstd:19",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let jsonl = String::from_utf8(zstd::decode_all(output.stdout.as_slice())?)?;
+    assert_eq!(jsonl.lines().count(), 5);
+
+    fs::remove_file(gzip_path)?;
+    fs::remove_file(zstd_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_to_parquet_rewrites_with_write_options() -> Result<()> {
+    let output_path = temp_path("convert_rewrite", "parquet")?;
+    let output = output_path.display().to_string();
+    let result = pq()
+        .args(["convert", &fixture_path(), &output])
+        .args(["-c", "name,id", "--compression", "zstd"])
+        .output()?;
+    assert!(result.status.success());
+    let result = pq()
+        .args(["head", &output, "-n", "1", "-o", "csv"])
+        .output()?;
+    assert_eq!(
+        String::from_utf8_lossy(&result.stdout),
+        "name,id\nAlice,1\n"
+    );
+    let result = pq().args(["info", &output, "-o", "json"]).output()?;
+    let info: serde_json::Value = serde_json::from_slice(&result.stdout)?;
+    assert_eq!(info[0]["compression"], serde_json::json!("ZSTD"));
+
+    let result = pq()
+        .args(["convert", &fixture_path(), &format!("{output}.gz")])
+        .output()?;
+    assert!(!result.status.success());
+    assert!(String::from_utf8(result.stderr)?.contains("--compression"));
+
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_out_dir_converts_each_file() -> Result<()> {
+    let dir = temp_path("convert_parts", "d")?;
+    fs::create_dir_all(dir.join("parts"))?;
+    fs::copy(fixture_path(), dir.join("parts").join("a.parquet"))?;
+    fs::copy(fixture_path(), dir.join("parts").join("b.parquet"))?;
+    fs::write(dir.join("parts").join("c.parquet"), b"not parquet")?;
+    let glob = dir.join("parts").join("*.parquet").display().to_string();
+    let out_dir = dir.join("jsonl");
+    let out_arg = out_dir.display().to_string();
+
+    let output = pq()
+        .args(["convert", &glob, "--out-dir", &out_arg, "-f", "jsonl"])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("c.parquet: failed"));
+    assert!(stderr.contains("Converted 2 of 3 files"));
+    assert!(stderr.contains("--skip-bad-files"));
+    assert_eq!(
+        fs::read_to_string(out_dir.join("a.jsonl"))?.lines().count(),
+        5
+    );
+    assert!(!out_dir.join("c.jsonl").exists());
+
+    let output = pq()
+        .args(["convert", &glob, "--out-dir", &out_arg, "-f", "jsonl"])
+        .args(["--skip-bad-files", "-j", "2", "--force"])
+        .output()?;
+    assert!(output.status.success());
+    assert!(out_dir.join("b.jsonl").exists());
+
+    fs::create_dir_all(dir.join("parts").join("more"))?;
+    fs::copy(
+        fixture_path(),
+        dir.join("parts").join("more").join("a.parquet"),
+    )?;
+    let output = pq()
+        .args(["convert", &dir.join("parts").display().to_string()])
+        .args([
+            "--out-dir",
+            &dir.join("csv").display().to_string(),
+            "-f",
+            "csv",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Two inputs would write the same output"));
+    assert!(!dir.join("csv").exists());
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_csv_output_without_rows_keeps_the_header() -> Result<()> {
+    let path = temp_path("csv_no_rows", "parquet")?;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    write_parquet(&path, schema, &[], None)?;
+    let path = path.to_string_lossy().into_owned();
+
+    let output = pq().args(["head", &path, "-o", "csv"]).output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "id,name\n");
+
+    // Column selection applies to the header alone too
+    let fixture = fixture_path();
+    let output = pq()
+        .args(["tail", &fixture, "-n", "0", "-c", "name,id", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "name,id\n");
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_csv_dialect_options_apply_to_printing_and_convert() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("note", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef,
+            Arc::new(StringArray::from(vec![Some("a|b"), None])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("csv_dialect", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+
+    let output = pq()
+        .args([
+            "head",
+            &input,
+            "-o",
+            "csv",
+            "--delimiter",
+            "|",
+            "--null-value",
+            "NA",
+            "--no-header",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, "1|\"a|b\"\n2|NA\n");
+
+    let output = pq()
+        .args(["schema", &input, "-o", "csv", "--quote-style", "always"])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.starts_with("\"column\",\"type\",\"nullable\"\n"));
+
+    let output = pq()
+        .args([
+            "convert",
+            &input,
+            "-",
+            "--format",
+            "tsv",
+            "--null-value",
+            "\\N",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "id\tnote\n1\ta|b\n2\t\\N\n"
+    );
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_tsv_output_round_trips_tabs_and_newlines() -> Result<()> {
+    let values = ["a\tb", "line\nbreak", "ünïcode \"quoted\"", "plain"];
+    let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(StringArray::from(values.to_vec())) as ArrayRef],
+    )?;
+    let input_path = temp_path("tsv_input", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+    let read_tsv = |bytes: &[u8]| -> Result<Vec<String>> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .from_reader(bytes);
+        let mut fields = Vec::new();
+        for record in reader.records() {
+            fields.extend(record?.iter().map(str::to_string));
+        }
+        Ok(fields)
+    };
+
+    let output = pq().args(["head", &input, "-o", "tsv"]).output()?;
+    assert!(output.status.success());
+    assert!(output.stdout.starts_with(b"text\n\"a\tb\"\n"));
+    assert_eq!(read_tsv(&output.stdout)?, values);
+
+    let output_path = temp_path("tsv_output", "tsv")?;
+    let output = pq()
+        .args(["convert", &input, &output_path.display().to_string()])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(read_tsv(&fs::read(&output_path)?)?, values);
+
+    fs::remove_file(input_path)?;
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_temporal_formatting_options() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        Field::new(
+            "ts_utc",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            true,
+        ),
+        Field::new("day", DataType::Date32, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(TimestampMillisecondArray::from(vec![
+                Some(1_700_000_000_123),
+                None,
+            ])) as ArrayRef,
+            Arc::new(
+                TimestampMicrosecondArray::from(vec![Some(1_700_000_000_000_000), None])
+                    .with_timezone("UTC"),
+            ) as ArrayRef,
+            Arc::new(Date32Array::from(vec![Some(19_675), None])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("temporal_formatting", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+
+    let output = pq().args(["head", &input, "-o", "jsonl"]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert_eq!(
+        stdout.lines().next(),
+        Some(
+            r#"{"ts":"2023-11-14T22:13:20.123Z","ts_utc":"2023-11-14T22:13:20Z","day":"2023-11-14"}"#
+        )
+    );
+
+    let output = pq()
+        .args([
+            "head",
+            &input,
+            "-o",
+            "csv",
+            "--timestamp-format",
+            "%Y/%m/%d %H:%M",
+            "--timezone",
+            "Asia/Kolkata",
+            "--date-format",
+            "%d.%m.%Y",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "ts,ts_utc,day\n2023/11/15 03:43,2023/11/15 03:43,14.11.2023\n,,\n"
+    );
+
+    let output = pq()
+        .args([
+            "convert",
+            &input,
+            "-",
+            "--format",
+            "csv",
+            "--timezone",
+            "+01:00",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?
+        .contains("2023-11-14T23:13:20.123+01:00,2023-11-14T23:13:20+01:00,2023-11-14"));
+
+    let output = pq()
+        .args(["head", &input, "--timezone", "Mars/Olympus"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("unknown time zone"));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_float_precision_options() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("x", DataType::Float64, true)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Float64Array::from(vec![
+            Some(0.1 + 0.2),
+            Some(-0.0),
+            Some(1234567.0),
+            Some(f64::NAN),
+            None,
+        ])) as ArrayRef],
+    )?;
+    let input_path = temp_path("float_precision", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+
+    let csv = |args: &[&str]| -> Result<String> {
+        let output = pq()
+            .args(["head", &input, "-o", "csv"])
+            .args(args)
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+    assert_eq!(
+        csv(&[])?,
+        "x\n0.30000000000000004\n-0.0\n1234567.0\nNaN\n\"\"\n"
+    );
+    assert_eq!(
+        csv(&["--float-precision", "2"])?,
+        "x\n0.30\n-0.00\n1234567.00\nNaN\n\"\"\n"
+    );
+    assert_eq!(
+        csv(&["--float-format", "general"])?,
+        "x\n0.3\n-0\n1.23457e6\nNaN\n\"\"\n"
+    );
+
+    let output = pq()
+        .args(["head", &input, "-o", "jsonl", "-n", "3"])
+        .args(["--float-precision", "1"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "{\"x\":0.3}\n{\"x\":-0.0}\n{\"x\":1234567.0}\n"
+    );
+
+    let _ignored = fs::remove_file(&input_path);
+    Ok(())
+}
+
+#[test]
+fn test_int64_string_output_round_trips() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("big", DataType::Int64, true)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![Some(i64::MAX), Some(i64::MIN), None])) as ArrayRef],
+    )?;
+    let input_path = temp_path("int64_strings", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+
+    let output = pq()
+        .args(["head", &input, "-o", "jsonl", "--int64", "string"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(rows[0]["big"], "9223372036854775807");
+    assert_eq!(
+        rows[0]["big"].as_str().map(str::parse::<i64>),
+        Some(Ok(i64::MAX))
+    );
+    assert_eq!(rows[1]["big"], "-9223372036854775808");
+    assert_eq!(rows[2]["big"], serde_json::Value::Null);
+
+    // convert writes the same, and numbers stay the default
+    let json_path = temp_path("int64_strings", "json")?;
+    let json = json_path.display().to_string();
+    let status = pq()
+        .args(["convert", &input, &json, "--int64", "string"])
+        .status()?;
+    assert!(status.success());
+    let converted: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_path)?)?;
+    assert_eq!(converted[0]["big"], "9223372036854775807");
+
+    let output = pq()
+        .args(["head", &input, "-o", "json", "-n", "1"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["big"], i64::MAX);
+
+    let _ignored = fs::remove_file(&input_path);
+    let _ignored = fs::remove_file(&json_path);
+    Ok(())
+}
+
+#[test]
+fn test_config_file_and_environment_defaults() -> Result<()> {
+    let config_path = temp_path("config", "toml")?;
+    fs::write(&config_path, "output = \"csv\"\nfloat_precision = 1\n")?;
+    let run = |args: &[&str], envs: &[(&str, &str)]| -> Result<std::process::Output> {
+        Ok(pq()
+            .env("PQ_CONFIG", &config_path)
+            .envs(envs.iter().copied())
+            .args(args)
+            .output()?)
+    };
+
+    let output = run(&["head", &fixture_path(), "-n", "1"], &[])?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "id,name,amount,active\n1,Alice,100.5,true\n"
+    );
+
+    // The environment beats the file, and flags beat both
+    let output = run(
+        &["head", &fixture_path(), "-n", "1", "-o", "jsonl"],
+        &[("PQ_FLOAT_PRECISION", "2")],
+    )?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "{\"id\":1,\"name\":\"Alice\",\"amount\":100.5,\"active\":true}\n"
+    );
+    let output = run(
+        &["head", &fixture_path(), "-n", "1", "--float-precision", "0"],
+        &[("PQ_FLOAT_PRECISION", "2")],
+    )?;
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "id,name,amount,active\n1,Alice,100,true\n"
+    );
+
+    let output = run(
+        &["config", "show", "-o", "json"],
+        &[("PQ_FLOAT_PRECISION", "2")],
+    )?;
+    assert!(output.status.success());
+    let settings: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        settings[0],
+        serde_json::json!({
+            "setting": "output",
+            "value": "csv",
+            "source": config_path.display().to_string(),
+        })
+    );
+    assert_eq!(settings[1]["value"], serde_json::json!("2"));
+    assert_eq!(
+        settings[1]["source"],
+        serde_json::json!("$PQ_FLOAT_PRECISION")
+    );
+    assert_eq!(settings[2]["source"], serde_json::json!("default"));
+
+    let output = run(&["count", &fixture_path()], &[("PQ_OUTPUT", "xml")])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("$PQ_OUTPUT"));
+
+    let _ignored = fs::remove_file(&config_path);
+    Ok(())
+}
+
+#[test]
+fn test_binary_rendering_options() -> Result<()> {
+    let uuid = [
+        0x12, 0x3e, 0x45, 0x67, 0xe8, 0x9b, 0x12, 0xd3, 0xa4, 0x56, 0x42, 0x66, 0x14, 0x17, 0x40,
+        0x00,
+    ];
+    let schema = Arc::new(parse_message_type(
+        "message m { required fixed_len_byte_array(16) id (UUID); required binary payload; }",
+    )?);
+    let input_path = temp_path("binary_rendering", "parquet")?;
+    let mut writer =
+        SerializedFileWriter::new(fs::File::create(&input_path)?, schema, Default::default())?;
+    let mut row_group = writer.next_row_group()?;
+    if let Some(mut column) = row_group.next_column()? {
+        column.typed::<FixedLenByteArrayType>().write_batch(
+            &[FixedLenByteArray::from(uuid.to_vec())],
+            None,
+            None,
+        )?;
+        column.close()?;
+    }
+    if let Some(mut column) = row_group.next_column()? {
+        column.typed::<ByteArrayType>().write_batch(
+            &[ByteArray::from(vec![0xde, 0xad, 0xbe, 0xef])],
+            None,
+            None,
+        )?;
+        column.close()?;
+    }
+    row_group.close()?;
+    writer.close()?;
+    let input = input_path.display().to_string();
+
+    let output = pq().args(["head", &input, "-o", "jsonl"]).output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?.trim_end(),
+        r#"{"id":"123e4567-e89b-12d3-a456-426614174000","payload":"3q2+7w=="}"#
+    );
+
+    let output = pq().args(["head", &input, "-o", "csv"]).output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout)?,
+        "id,payload\n123e4567-e89b-12d3-a456-426614174000,deadbeef\n"
+    );
+
+    let output = pq()
+        .args(["stats", &input, "-o", "csv", "--binary", "base64"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains(
+        "id,UUID,0,123e4567-e89b-12d3-a456-426614174000,123e4567-e89b-12d3-a456-426614174000"
+    ));
+    assert!(stdout.contains("payload,BYTE_ARRAY,0,3q2+7w==,3q2+7w=="));
+
+    // Arrow writers tag UUIDs with the arrow.uuid extension instead of the logical type
+    let field = Field::new("id", DataType::FixedSizeBinary(16), false).with_metadata(
+        std::collections::HashMap::from([(
+            "ARROW:extension:name".to_string(),
+            "arrow.uuid".to_string(),
+        )]),
+    );
+    let schema = Arc::new(Schema::new(vec![field]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(arrow::array::FixedSizeBinaryArray::try_from_iter(
+            std::iter::once(uuid),
+        )?) as ArrayRef],
+    )?;
+    let tagged_path = temp_path("binary_rendering_tagged", "parquet")?;
+    write_parquet(&tagged_path, schema, &[batch], None)?;
+    let output = pq()
+        .args(["stats", &tagged_path.display().to_string(), "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains(
+        "id,UUID,0,123e4567-e89b-12d3-a456-426614174000,123e4567-e89b-12d3-a456-426614174000"
+    ));
+
+    fs::remove_file(tagged_path)?;
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_invalid_input_preserves_existing_output() -> Result<()> {
+    let input_path = temp_path("invalid_convert_input", "parquet")?;
+    let output_path = temp_path("invalid_convert_output", "csv")?;
+    fs::write(&input_path, b"not parquet")?;
+    fs::write(&output_path, b"sentinel")?;
+
+    let output = pq()
+        .args([
+            "convert",
+            &input_path.display().to_string(),
+            &output_path.display().to_string(),
+            "--force",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert_eq!(fs::read(&output_path)?, b"sentinel");
+
+    fs::remove_file(input_path)?;
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_unsupported_format_preserves_existing_output() -> Result<()> {
+    let output_path = temp_path("unsupported_convert_output", "unsupported")?;
+    fs::write(&output_path, b"sentinel")?;
+
+    let output = pq()
+        .args([
+            "convert",
+            &fixture_path(),
+            &output_path.display().to_string(),
+            "--force",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert_eq!(fs::read(&output_path)?, b"sentinel");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unsupported format"));
+    assert!(stderr.contains("parquet (or choose one with --format)"));
+
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge() -> Result<()> {
+    let output_path = temp_path("merged", "parquet")?;
+
+    let output = pq()
+        .args([
+            "merge",
+            &fixture_path(),
+            &fixture_path(),
+            "-o",
+            &output_path.display().to_string(),
         ])
         .output()?;
     assert!(output.status.success());
     assert!(output_path.exists());
 
-    let contents = fs::read_to_string(&output_path)?;
-    assert!(contents.contains("id,name,amount,active"));
-    assert!(contents.contains("Alice"));
+    let count_output = pq()
+        .args(["count", &output_path.display().to_string()])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&count_output.stdout);
+    assert_eq!(stdout.trim(), "10");
+
+    // An existing output needs --force, and an input is never an output
+    let output_arg = output_path.display().to_string();
+    let output = pq()
+        .args(["merge", &fixture_path(), "-o", &output_arg])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Output file already exists"));
+
+    let output = pq()
+        .args(["merge", &fixture_path(), "-o", &output_arg, "--force"])
+        .output()?;
+    assert!(output.status.success());
+    let count_output = pq().args(["count", &output_arg]).output()?;
+    assert_eq!(String::from_utf8_lossy(&count_output.stdout).trim(), "5");
+
+    let output = pq()
+        .args([
+            "merge",
+            &output_arg,
+            &fixture_path(),
+            "-o",
+            &output_arg,
+            "-f",
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Output file is also an input"));
+
+    fs::remove_file(&output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge_append() -> Result<()> {
+    let output_path = temp_path("appended", "parquet")?;
+    let output_arg = output_path.display().to_string();
+
+    // A missing output is created, then later runs copy it and add the new rows
+    for expected in ["0 existing, 5 total", "5 existing, 10 total"] {
+        let output = pq()
+            .args(["merge", &fixture_path(), "--append", "-o", &output_arg])
+            .output()?;
+        assert!(output.status.success());
+        let stdout = String::from_utf8(output.stdout)?;
+        assert!(stdout.contains("Appended 5 rows"), "{stdout}");
+        assert!(stdout.contains(expected), "{stdout}");
+    }
+
+    let tail = pq()
+        .args(["tail", "-n", "1", "-o", "jsonl", &output_arg])
+        .output()?;
+    assert!(String::from_utf8(tail.stdout)?.contains("\"name\":\"Eve\""));
+
+    // A schema mismatch fails before the output is touched
+    let other_path = temp_path("append-other", "parquet")?;
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef],
+    )?;
+    write_parquet(&other_path, schema, &[batch], None)?;
+    let output = pq()
+        .args([
+            "merge",
+            &other_path.display().to_string(),
+            "--append",
+            "-o",
+            &output_arg,
+        ])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("Schema mismatch"));
+    let count_output = pq().args(["count", &output_arg]).output()?;
+    assert_eq!(String::from_utf8_lossy(&count_output.stdout).trim(), "10");
+
+    fs::remove_file(&output_path)?;
+    fs::remove_file(&other_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge_sorted_by_interleaves_inputs() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("ts", DataType::Int64, true),
+        Field::new("source", DataType::Utf8, false),
+    ]));
+    let write = |name: &str, ts: Vec<Option<i64>>| -> Result<String> {
+        let path = temp_path(name, "parquet")?;
+        let source = StringArray::from(vec![name; ts.len()]);
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(ts)) as ArrayRef,
+                Arc::new(source) as ArrayRef,
+            ],
+        )?;
+        write_parquet(&path, Arc::clone(&schema), &[batch], None)?;
+        Ok(path.display().to_string())
+    };
+    // Several reader batches per input, and a null that sorts first
+    let evens = write("sorted-evens", (0..3000).step_by(2).map(Some).collect())?;
+    let odds = write(
+        "sorted-odds",
+        std::iter::once(None)
+            .chain((1..3000).step_by(2).map(Some))
+            .collect(),
+    )?;
+    let output_path = temp_path("sorted-merged", "parquet")?;
+    let output_arg = output_path.display().to_string();
+
+    let output = pq()
+        .args([
+            "merge",
+            &evens,
+            &odds,
+            "--sorted-by",
+            "ts",
+            "-o",
+            &output_arg,
+        ])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let head = pq()
+        .args([
+            "head",
+            "-n",
+            "4000",
+            "-c",
+            "ts",
+            "-o",
+            "csv",
+            "--no-header",
+            &output_arg,
+        ])
+        .output()?;
+    // CSV quotes the null so the line is not blank
+    let expected: String = std::iter::once("\"\"".to_string())
+        .chain((0..3000).map(|ts| ts.to_string()))
+        .map(|line| line + "\n")
+        .collect();
+    assert_eq!(String::from_utf8(head.stdout)?, expected);
+    let info = pq().args(["info", &output_arg, "-o", "csv"]).output()?;
+    assert!(String::from_utf8(info.stdout)?.contains("ts (asc, nulls first)"));
+
+    // Descending inputs merge with `,desc`
+    let high = write("sorted-high", vec![Some(9), Some(5), Some(1)])?;
+    let low = write("sorted-low", vec![Some(6), Some(2)])?;
+    let output = pq()
+        .args([
+            "merge",
+            &high,
+            &low,
+            "--sorted-by",
+            "ts,desc",
+            "-o",
+            &output_arg,
+            "--force",
+        ])
+        .output()?;
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let head = pq()
+        .args(["head", "-c", "ts", "-o", "csv", "--no-header", &output_arg])
+        .output()?;
+    assert_eq!(String::from_utf8(head.stdout)?, "9\n6\n5\n2\n1\n");
+
+    // An input out of order names the row, unless told to trust the inputs
+    let unsorted = write("sorted-broken", vec![Some(1), Some(4), Some(3)])?;
+    let merge_unsorted = |extra: &[&str]| {
+        pq().args(["merge", &evens, &unsorted, "--sorted-by", "ts", "--force"])
+            .args(["-o", &output_arg])
+            .args(extra)
+            .output()
+    };
+    let output = merge_unsorted(&[])?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(stderr.contains("Input is not sorted by ts"), "{stderr}");
+    assert!(stderr.contains(&unsorted), "{stderr}");
+    assert!(stderr.contains("Row 2 sorts before"), "{stderr}");
+    assert!(merge_unsorted(&["--assume-sorted"])?.status.success());
+
+    for path in [&evens, &odds, &high, &low, &unsorted, &output_arg] {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_meta_set_and_remove_keep_data_and_indexes() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from((0..8).collect::<Vec<i64>>())) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                "a", "c", "e", "g", "i", "k", "m", "o",
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("meta_input", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(4)
+        .set_bloom_filter_enabled(true)
+        .set_sorting_columns(Some(vec![parquet::format::SortingColumn::new(
+            0, false, true,
+        )]))
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&input_path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let input = input_path.display().to_string();
+    let output_path = temp_path("meta_output", "parquet")?;
+    let output = output_path.display().to_string();
+
+    let status = pq()
+        .args([
+            "meta",
+            "set",
+            &input,
+            "pipeline_run_id=42",
+            "source=s3://raw",
+        ])
+        .args(["-o", &output])
+        .status()?;
+    assert!(status.success());
+    let status = pq()
+        .args(["meta", "set", &output, "source=local", "--in-place"])
+        .status()?;
+    assert!(status.success());
+
+    let metadata = |path: &str| -> Result<Vec<(String, serde_json::Value)>> {
+        let output = pq()
+            .args(["info", "--metadata", path, "-o", "json"])
+            .output()?;
+        assert!(output.status.success());
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+        Ok(rows
+            .iter()
+            .filter(|row| row["key"] != "ARROW:schema")
+            .map(|row| {
+                (
+                    row["key"].as_str().unwrap_or_default().to_string(),
+                    row["value"].clone(),
+                )
+            })
+            .collect())
+    };
+    assert_eq!(
+        metadata(&output)?,
+        [
+            ("pipeline_run_id".to_string(), serde_json::json!("42")),
+            ("source".to_string(), serde_json::json!("local")),
+        ]
+    );
+
+    let status = pq()
+        .args(["meta", "remove", &output, "pipeline_run_id", "--in-place"])
+        .status()?;
+    assert!(status.success());
+    assert_eq!(
+        metadata(&output)?,
+        [("source".to_string(), serde_json::json!("local"))]
+    );
+
+    // The row groups are copied with their page indexes and bloom filters
+    let checksum = |path: &str| -> Result<String> {
+        let output = pq().args(["checksum", path, "-o", "json"]).output()?;
+        Ok(String::from_utf8_lossy(&output.stdout).replace(path, ""))
+    };
+    assert_eq!(checksum(&input)?, checksum(&output)?);
+    let row_groups = |path: &str| -> Result<serde_json::Value> {
+        let output = pq()
+            .args(["info", "--row-groups", path, "-o", "json"])
+            .output()?;
+        let mut rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        for row in rows.as_array_mut().into_iter().flatten() {
+            row["file"] = serde_json::Value::Null;
+        }
+        Ok(rows)
+    };
+    let layout = row_groups(&output)?;
+    assert_eq!(layout, row_groups(&input)?);
+    assert_eq!(layout[1]["page_index_columns"], serde_json::json!(2));
+    assert_eq!(layout[1]["bloom_filter_columns"], serde_json::json!(2));
+    // So is the sort order the row groups declare
+    let info = pq().args(["info", &output, "-o", "json"]).output()?;
+    let info: serde_json::Value = serde_json::from_slice(&info.stdout)?;
+    assert_eq!(info[0]["sort_order"], "sorted");
+    assert_eq!(
+        info[0]["sorting_columns"],
+        serde_json::json!([{"column": "id", "descending": false, "nulls_first": true}])
+    );
+
+    let result = pq()
+        .args(["meta", "remove", &output, "pipeline_run_id", "--in-place"])
+        .output()?;
+    assert!(!result.status.success());
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    assert!(
+        stderr.contains("No metadata key pipeline_run_id"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("ARROW:schema, source"), "{stderr}");
+
+    fs::remove_file(&input_path)?;
+    fs::remove_file(&output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge_and_transform_column_compression() -> Result<()> {
+    let merged_path = temp_path("column_compression", "parquet")?;
+    let merged = merged_path.display().to_string();
+    let status = pq()
+        .args(["merge", &fixture_path(), "-o", &merged])
+        .args(["--compression", "uncompressed"])
+        .args(["--column-compression", "name=zstd:9"])
+        .status()?;
+    assert!(status.success());
+
+    let codecs = |path: &str| -> Result<Vec<(String, String)>> {
+        let output = pq()
+            .args(["info", "--columns", path, "-o", "json"])
+            .output()?;
+        assert!(output.status.success());
+        let rows: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row["column"].as_str().unwrap_or_default().to_string(),
+                    row["compression"].as_str().unwrap_or_default().to_string(),
+                )
+            })
+            .collect())
+    };
+    let expected = |name: &str| {
+        vec![
+            ("id".to_string(), "UNCOMPRESSED".to_string()),
+            (name.to_string(), "ZSTD".to_string()),
+            ("amount".to_string(), "UNCOMPRESSED".to_string()),
+            ("active".to_string(), "UNCOMPRESSED".to_string()),
+        ]
+    };
+    assert_eq!(codecs(&merged)?, expected("name"));
+
+    // Overrides name the transform's output columns
+    let transformed_path = temp_path("column_compression_transform", "parquet")?;
+    let transformed = transformed_path.display().to_string();
+    let output = pq()
+        .args(["transform", &fixture_path(), "-o", &transformed])
+        .args(["--rename", "name=label", "--compression", "uncompressed"])
+        .args(["--column-compression", "name=zstd"])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Column not found"), "{stderr}");
+    assert!(stderr.contains("id, label, amount, active"), "{stderr}");
+    assert!(!transformed_path.exists());
+
+    let status = pq()
+        .args(["transform", &fixture_path(), "-o", &transformed])
+        .args(["--rename", "name=label", "--compression", "uncompressed"])
+        .args(["--column-compression", "label=zstd"])
+        .status()?;
+    assert!(status.success());
+    assert_eq!(codecs(&transformed)?, expected("label"));
+
+    // A column missing from the inputs is reported against them, not the output
+    let unmerged_path = temp_path("column_compression_missing", "parquet")?;
+    let output = pq()
+        .args(["merge", &fixture_path(), &fixture_path()])
+        .args(["-o", &unmerged_path.display().to_string()])
+        .args(["--column-compression", "nope=zstd:9"])
+        .output()?;
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Column not found in {}: nope", fixture_path())),
+        "{stderr}"
+    );
+    assert!(!unmerged_path.exists());
+
+    let output = pq()
+        .args(["merge", &fixture_path(), "-o", &merged, "--force"])
+        .args(["--column-compression", "name=zstd:30"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected 1-22"));
+
+    fs::remove_file(&merged_path)?;
+    fs::remove_file(&transformed_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge_writer_version_and_dictionary_options() -> Result<()> {
+    let path = temp_path("writer_version", "parquet")?;
+    let output = path.display().to_string();
+    let info = |args: &[&str]| -> Result<serde_json::Value> {
+        let result = pq()
+            .args(["info", &output, "-o", "json"])
+            .args(args)
+            .output()?;
+        assert!(result.status.success());
+        Ok(serde_json::from_slice(&result.stdout)?)
+    };
+
+    let status = pq()
+        .args(["merge", &fixture_path(), "-o", &output])
+        .args(["--writer-version", "1", "--disable-dictionary", "name"])
+        .args(["--dictionary-page-size-limit", "4096"])
+        .args(["--data-page-size-limit", "8192"])
+        .status()?;
+    assert!(status.success());
+    assert_eq!(info(&[])?[0]["version"], serde_json::json!(1));
+    let columns = info(&["--columns"])?;
+    assert_eq!(columns[1]["column"], serde_json::json!("name"));
+    assert_eq!(
+        columns[1]["dictionary_encoded_row_groups"],
+        serde_json::json!(0)
+    );
+    assert_eq!(
+        columns[0]["dictionary_encoded_row_groups"],
+        serde_json::json!(1)
+    );
+
+    let status = pq()
+        .args(["merge", &fixture_path(), "-o", &output, "--force"])
+        .args(["--writer-version", "2"])
+        .status()?;
+    assert!(status.success());
+    assert_eq!(info(&[])?[0]["version"], serde_json::json!(2));
+    let columns = info(&["--columns"])?;
+    assert_eq!(
+        columns[1]["dictionary_encoded_row_groups"],
+        serde_json::json!(1)
+    );
+
+    for args in [
+        ["--data-page-size-limit", "0"],
+        ["--dictionary-page-size-limit", "0"],
+        ["--disable-dictionary", "nope"],
+    ] {
+        let result = pq()
+            .args(["merge", &fixture_path(), "-o", &output, "--force"])
+            .args(args)
+            .output()?;
+        assert!(!result.status.success(), "{args:?}");
+    }
+
+    fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_transform_columns() -> Result<()> {
+    let output_path = temp_path("transform", "parquet")?;
+    let output = output_path.display().to_string();
+
+    let result = pq()
+        .args(["transform", &fixture_path(), "-o", &output])
+        .args([
+            "--rename", "name=who", "--drop", "active", "--cast", "id=int32",
+        ])
+        .output()?;
+    assert!(result.status.success());
+    let result = pq()
+        .args(["head", &output, "-n", "1", "-o", "csv"])
+        .output()?;
+    assert_eq!(
+        String::from_utf8_lossy(&result.stdout),
+        "id,who,amount\n1,Alice,100.5\n"
+    );
+    let result = pq().args(["schema", &output, "-o", "json"]).output()?;
+    assert!(String::from_utf8_lossy(&result.stdout).contains("INT32"));
+    fs::remove_file(&output_path)?;
+
+    for args in [
+        ["--rename", "name=id"],
+        ["--drop", "missing"],
+        ["--cast", "amount=int64"],
+    ] {
+        let result = pq()
+            .args(["transform", &fixture_path(), "-o", &output])
+            .args(args)
+            .output()?;
+        assert!(!result.status.success(), "{args:?} should fail");
+        assert!(!output_path.exists(), "{args:?} should not write output");
+    }
+
+    let result = pq()
+        .args(["transform", &fixture_path(), "-o", &output])
+        .args(["--cast", "amount=int64", "--allow-lossy"])
+        .output()?;
+    assert!(result.status.success());
+    let result = pq()
+        .args(["head", &output, "-n", "1", "-c", "amount", "-o", "csv"])
+        .output()?;
+    assert_eq!(String::from_utf8_lossy(&result.stdout), "amount\n100\n");
+
+    fs::remove_file(output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_merge_order_numeric() -> Result<()> {
+    let dir = temp_path("ordered", "d")?;
+    fs::create_dir_all(&dir)?;
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)]));
+    for part in [2, 10] {
+        let batch = RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![Arc::new(Int64Array::from(vec![part])) as ArrayRef],
+        )?;
+        let path = dir.join(format!("part-{part}.parquet"));
+        write_parquet(&path, Arc::clone(&schema), &[batch], None)?;
+    }
+    let glob = dir.join("part-*.parquet").display().to_string();
+    let merged = dir.join("merged.out").display().to_string();
+
+    for (order, first) in [("name", "10"), ("name-numeric", "2")] {
+        let output = pq()
+            .args(["merge", &glob, "--order", order, "-o", &merged, "--force"])
+            .output()?;
+        assert!(output.status.success());
+        let head = pq()
+            .args(["head", "-n", "1", "-o", "jsonl", &merged])
+            .output()?;
+        assert_eq!(
+            String::from_utf8(head.stdout)?.trim(),
+            format!("{{\"id\":{first}}}")
+        );
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_directory_inputs() -> Result<()> {
+    let dir = temp_path("lake", "d")?;
+    fs::create_dir_all(dir.join("day=2"))?;
+    fs::copy(fixture_path(), dir.join("part-0.parquet"))?;
+    fs::copy(fixture_path(), dir.join("day=2").join("part-0.parquet"))?;
+    let dir_arg = dir.display().to_string();
+
+    let output = pq().args(["count", &dir_arg]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains("Total: 10"));
+
+    let output = pq()
+        .args(["count", &dir_arg, "--max-depth", "1"])
+        .output()?;
+    assert_eq!(String::from_utf8(output.stdout)?.trim(), "5");
+
+    let output = pq().args(["count", &dir_arg, "--no-recursive"]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("is a directory"));
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_json_preserves_types() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("code", DataType::Utf8, true),
+        Field::new("qty", DataType::Int64, true),
+        Field::new("flag", DataType::Boolean, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(StringArray::from(vec![
+                Some("0012"),
+                Some(""),
+                Some("true"),
+                None,
+            ])) as ArrayRef,
+            Arc::new(Int64Array::from(vec![Some(12), None, Some(-7), Some(0)])) as ArrayRef,
+            Arc::new(BooleanArray::from(vec![
+                Some(true),
+                Some(false),
+                None,
+                Some(true),
+            ])) as ArrayRef,
+        ],
+    )?;
+
+    let input_path = temp_path("typed_input", "parquet")?;
+    let json_path = temp_path("typed_output", "json")?;
+    let jsonl_path = temp_path("typed_output", "jsonl")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+
+    let json_output = pq()
+        .args([
+            "convert",
+            &input_path.display().to_string(),
+            &json_path.display().to_string(),
+        ])
+        .output()?;
+    assert!(json_output.status.success());
+
+    let rows: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_path)?)?;
+    let rows = rows
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("json output should be an array"))?;
+    assert_eq!(
+        rows[0]["code"],
+        serde_json::Value::String("0012".to_string())
+    );
+    assert_eq!(rows[1]["code"], serde_json::Value::String(String::new()));
+    assert_eq!(
+        rows[2]["code"],
+        serde_json::Value::String("true".to_string())
+    );
+    assert_eq!(rows[0]["qty"], serde_json::json!(12));
+    assert_eq!(rows[1]["qty"], serde_json::Value::Null);
+    assert_eq!(rows[0]["flag"], serde_json::json!(true));
+    assert_eq!(rows[2]["flag"], serde_json::Value::Null);
+
+    let jsonl_output = pq()
+        .args([
+            "convert",
+            &input_path.display().to_string(),
+            &jsonl_path.display().to_string(),
+        ])
+        .output()?;
+    assert!(jsonl_output.status.success());
+
+    let lines: Vec<serde_json::Value> = fs::read_to_string(&jsonl_path)?
+        .lines()
+        .map(serde_json::from_str)
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(lines[1]["code"], serde_json::Value::String(String::new()));
+    assert_eq!(lines[1]["qty"], serde_json::Value::Null);
+    assert_eq!(
+        lines[2]["code"],
+        serde_json::Value::String("true".to_string())
+    );
+    assert_eq!(lines[2]["flag"], serde_json::Value::Null);
+
+    let _ignored = fs::remove_file(&input_path);
+    let _ignored = fs::remove_file(&json_path);
+    let _ignored = fs::remove_file(&jsonl_path);
+    Ok(())
+}
+
+#[test]
+fn test_head_multi_file_json_rejects_incompatible_schemas() -> Result<()> {
+    assert_scan_multi_file_json_rejects_incompatible_schemas("head")
+}
+
+#[test]
+fn test_tail_multi_file_json_rejects_incompatible_schemas() -> Result<()> {
+    assert_scan_multi_file_json_rejects_incompatible_schemas("tail")
+}
+
+fn assert_scan_multi_file_json_rejects_incompatible_schemas(command: &str) -> Result<()> {
+    let left_schema = Arc::new(Schema::new(vec![Field::new(
+        "value",
+        DataType::Int64,
+        false,
+    )]));
+    let right_schema = Arc::new(Schema::new(vec![Field::new(
+        "other",
+        DataType::Utf8,
+        false,
+    )]));
+    let left_batch = RecordBatch::try_new(
+        Arc::clone(&left_schema),
+        vec![Arc::new(Int64Array::from(vec![1])) as ArrayRef],
+    )?;
+    let right_batch = RecordBatch::try_new(
+        Arc::clone(&right_schema),
+        vec![Arc::new(StringArray::from(vec!["x"])) as ArrayRef],
+    )?;
+    let left = temp_path("head_schema_left", "parquet")?;
+    let right = temp_path("head_schema_right", "parquet")?;
+    write_parquet(&left, left_schema, &[left_batch], None)?;
+    write_parquet(&right, right_schema, &[right_batch], None)?;
+
+    let output = pq()
+        .args([
+            command,
+            &left.display().to_string(),
+            &right.display().to_string(),
+            "-o",
+            "json",
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Schema mismatch"));
+
+    fs::remove_file(left)?;
+    fs::remove_file(right)?;
+    Ok(())
+}
+
+#[test]
+fn test_convert_rejects_multi_match_glob() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "value",
+        DataType::Int64,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![1])) as ArrayRef],
+    )?;
+    let first = temp_path("convert_glob_pair", "parquet")?;
+    let stem = first
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .ok_or_else(|| anyhow::anyhow!("temp path should have utf-8 stem"))?;
+    let second = first.with_file_name(format!("{stem}_second.parquet"));
+    let output_path = temp_path("convert_glob_output", "csv")?;
+    write_parquet(
+        &first,
+        Arc::clone(&schema),
+        std::slice::from_ref(&batch),
+        None,
+    )?;
+    write_parquet(&second, schema, &[batch], None)?;
+    let glob = first.with_file_name(format!("{stem}*.parquet"));
+
+    let output = pq()
+        .args([
+            "convert",
+            &glob.display().to_string(),
+            &output_path.display().to_string(),
+        ])
+        .output()?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Expected exactly one input file"));
+    assert!(!output_path.exists());
+
+    fs::remove_file(first)?;
+    fs::remove_file(second)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_aggregates_across_row_groups() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "value",
+        DataType::Int64,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![5, 10, 1, 3])) as ArrayRef],
+    )?;
+    let input_path = temp_path("stats_groups", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+
+    let output = pq()
+        .args(["stats", &input_path.display().to_string(), "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        rows[0]["column"],
+        serde_json::Value::String("value".to_string())
+    );
+    assert_eq!(rows[0]["min"], serde_json::json!(1));
+    assert_eq!(rows[0]["max"], serde_json::json!(10));
+
+    let _ignored = fs::remove_file(&input_path);
+    Ok(())
+}
+
+#[test]
+fn test_stats_reports_column_sizes() -> Result<()> {
+    let fixture = fixture_path();
+    let output = pq()
+        .args(["stats", &fixture, "--sort-by", "size", "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)?;
+
+    let info = pq()
+        .args(["info", "--columns", &fixture, "-o", "json"])
+        .output()?;
+    let columns: Vec<serde_json::Value> = serde_json::from_slice(&info.stdout)?;
+    for row in &rows {
+        let column = columns
+            .iter()
+            .find(|column| column["column"] == row["column"]);
+        assert_eq!(
+            column.map(|column| &column["compressed_bytes"]),
+            Some(&row["compressed_bytes"])
+        );
+        assert_eq!(
+            column.map(|column| &column["uncompressed_bytes"]),
+            Some(&row["uncompressed_bytes"])
+        );
+    }
+
+    let sizes: Vec<u64> = rows
+        .iter()
+        .filter_map(|row| row["compressed_bytes"].as_u64())
+        .collect();
+    assert_eq!(sizes.len(), 4);
+    assert!(sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+    let share: f64 = rows
+        .iter()
+        .filter_map(|row| row["compressed_percent"].as_f64())
+        .sum();
+    assert!((share - 100.0).abs() < 1e-9);
+
+    let csv = pq()
+        .args(["stats", &fixture, "--sort-by", "name", "-o", "csv"])
+        .output()?;
+    let csv = String::from_utf8(csv.stdout)?;
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some(
+            "column,type,null_count,min,max,compressed_bytes,uncompressed_bytes,compressed_percent"
+        )
+    );
+    let names: Vec<&str> = lines.filter_map(|line| line.split(',').next()).collect();
+    assert_eq!(names, ["active", "amount", "id", "name"]);
+    Ok(())
+}
+
+#[test]
+fn test_warnings_follow_quiet_strict_and_error_format() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef],
+    )?;
+    let input_path = temp_path("no_statistics", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_statistics_enabled(EnabledStatistics::None)
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&input_path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let input = input_path.display().to_string();
+
+    let output = pq().args(["stats", &input, "-o", "csv"]).output()?;
+    assert!(output.status.success());
+    assert!(!output.stdout.is_empty());
+    assert_eq!(
+        String::from_utf8(output.stderr)?,
+        format!("warning: {input}: column 'id' has no statistics in 1 row group; its null count, min, and max leave it out\n")
+    );
+
+    let output = pq().args(["stats", &input, "-q"]).output()?;
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+
+    let output = pq()
+        .args(["stats", &input, "--error-format", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let line: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(line["level"], "warning");
+    assert!(line["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("no statistics")));
+
+    let output = pq()
+        .args(["stats", &input, "--strict", "-q", "--error-format", "json"])
+        .output()?;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    let line: serde_json::Value = serde_json::from_slice(&output.stderr)?;
+    assert_eq!(line["level"], "error");
+    assert!(line["message"]
+        .as_str()
+        .is_some_and(|message| message.contains("--strict treats warnings as errors")));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_table_previews_long_and_truncated_bounds() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("text", DataType::Utf8, false)]));
+    let long = "x".repeat(200);
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(StringArray::from(vec!["a", long.as_str()])) as ArrayRef],
+    )?;
+    let path = temp_path("stats_long_values", "parquet")?;
+    write_parquet(&path, schema, &[batch], None)?;
+    let input = path.display().to_string();
+    let stats = |args: &[&str]| -> Result<String> {
+        let output = pq().arg("stats").arg(&input).args(args).output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let table = stats(&[])?;
+    assert!(table.contains(&format!("{}…", "x".repeat(64))));
+    assert!(!table.contains(&"x".repeat(65)));
+    assert!(stats(&["--value-width", "3"])?.contains("xxx…"));
+    assert!(stats(&["--full-values"])?.contains(&long));
+    let json: serde_json::Value = serde_json::from_str(&stats(&["-o", "json"])?)?;
+    assert_eq!(json[0]["max"], serde_json::json!(long));
+
+    // A writer that truncated a bound marks it inexact
+    rewrite_footer(&input, &path, |metadata| {
+        for row_group in &mut metadata.row_groups {
+            for column in &mut row_group.columns {
+                if let Some(statistics) = column
+                    .meta_data
+                    .as_mut()
+                    .and_then(|meta| meta.statistics.as_mut())
+                {
+                    statistics.is_max_value_exact = Some(false);
+                }
+            }
+        }
+    })?;
+    let table = stats(&["--value-width", "3"])?;
+    assert!(table.contains("≈ xxx…"), "{table}");
+    assert!(!table.contains("≈ a"), "{table}");
+    assert!(stats(&["--by-row-group"])?.contains("≈ "));
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_takes_extremes_across_sorted_row_groups() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("unsigned", DataType::UInt32, false),
+        Field::new("amount", DataType::Decimal128(20, 2), false),
+        Field::new("ratio", DataType::Float64, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(UInt32Array::from(vec![1, 2, 3_000_000_000, 4_000_000_000])) as ArrayRef,
+            Arc::new(
+                Decimal128Array::from(vec![-500, -100, 200, 300])
+                    .with_precision_and_scale(20, 2)?,
+            ) as ArrayRef,
+            Arc::new(Float64Array::from(vec![1.0, 2.0, f64::NAN, f64::NAN])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("stats_sorted_groups", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+
+    let output = pq()
+        .args(["stats", &input_path.display().to_string(), "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let bounds: Vec<_> = rows
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|row| (row["min"].clone(), row["max"].clone()))
+        .collect();
+    assert_eq!(
+        bounds,
+        vec![
+            (serde_json::json!(1), serde_json::json!(4_000_000_000u32)),
+            // Decimals print as their stored bytes: -5.00 and 3.00.
+            (
+                serde_json::json!("fffffffffffffffe0c"),
+                serde_json::json!("00000000000000012c")
+            ),
+            (serde_json::json!(1.0), serde_json::json!(2.0)),
+        ]
+    );
+
+    let _ignored = fs::remove_file(&input_path);
+    Ok(())
+}
+
+#[test]
+fn test_stats_flags_suspicious_statistics() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from(vec![10, 11, 12, 1, 2, 3])) as ArrayRef],
+    )?;
+    let written = temp_path("stats_suspicious_source", "parquet")?;
+    write_parquet(&written, schema, &[batch], Some(3))?;
+
+    // Both row groups declare id sorted ascending, yet the second starts below the
+    // first; it also has its bounds swapped, and the first claims 5 nulls in 3 rows
+    let input_path = temp_path("stats_suspicious", "parquet")?;
+    rewrite_footer(&written.display().to_string(), &input_path, |metadata| {
+        for (index, row_group) in metadata.row_groups.iter_mut().enumerate() {
+            row_group.sorting_columns = Some(vec![parquet::format::SortingColumn {
+                column_idx: 0,
+                descending: false,
+                nulls_first: false,
+            }]);
+            let Some(statistics) = row_group
+                .columns
+                .first_mut()
+                .and_then(|column| column.meta_data.as_mut())
+                .and_then(|meta| meta.statistics.as_mut())
+            else {
+                continue;
+            };
+            if index == 0 {
+                statistics.null_count = Some(5);
+            } else {
+                std::mem::swap(&mut statistics.min, &mut statistics.max);
+                std::mem::swap(&mut statistics.min_value, &mut statistics.max_value);
+            }
+        }
+    })?;
+    let input = input_path.display().to_string();
+
+    let output = pq().args(["stats", &input]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("| 5 !"), "{stdout}");
+    assert!(stdout.contains("| 3 !"), "{stdout}");
+    assert!(stdout.contains("| 12 !"), "{stdout}");
+    let warning = format!("warning: {input}: column 'id' has suspicious statistics: ");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        format!(
+            "{warning}row group 0: 5 nulls in 3 rows\n\
+             {warning}row group 1: min is greater than max\n\
+             {warning}row group 1: every value is below row group 0's, but the column is declared sorted ascending\n"
+        )
+    );
+
+    // The values stay as written in structured output
+    let output = pq()
+        .args(["stats", &input, "--by-row-group", "-q", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "row_group,rows,statistics,column,type,null_count,min,max\n\
+         0,3,true,id,INT64,5,10,12\n\
+         1,3,true,id,INT64,0,3,1\n"
+    );
+    assert!(output.stderr.is_empty());
+
+    let output = pq()
+        .args(["--strict", "prune-check", &input, "-w", "id = 2"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("row group 0: 5 nulls in 3 rows"));
+
+    fs::remove_file(written)?;
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_by_row_group() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("value", DataType::Int64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+            Arc::new(Int64Array::from(vec![
+                Some(5),
+                None,
+                Some(1),
+                Some(3),
+                None,
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("stats_by_row_group", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
+
+    let output = pq()
+        .args([
+            "stats",
+            &input,
+            "-c",
+            "value",
+            "--by-row-group",
+            "-o",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let summary: Vec<_> = rows
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|row| {
+            (
+                row["row_group"].clone(),
+                row["rows"].clone(),
+                row["null_count"].clone(),
+                row["min"].clone(),
+                row["max"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (
+                serde_json::json!(0),
+                serde_json::json!(2),
+                serde_json::json!(1),
+                serde_json::json!(5),
+                serde_json::json!(5)
+            ),
+            (
+                serde_json::json!(1),
+                serde_json::json!(2),
+                serde_json::json!(0),
+                serde_json::json!(1),
+                serde_json::json!(3)
+            ),
+            (
+                serde_json::json!(2),
+                serde_json::json!(1),
+                serde_json::json!(1),
+                serde_json::json!(null),
+                serde_json::json!(null)
+            ),
+        ]
+    );
+
+    let output = pq()
+        .args([
+            "stats",
+            &input,
+            "-c",
+            "value",
+            "--by-row-group",
+            "-o",
+            "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout.lines().next(),
+        Some("row_group,rows,statistics,column,type,null_count,min,max")
+    );
+    assert_eq!(stdout.lines().count(), 4);
+
+    let _ignored = fs::remove_file(&input_path);
+    Ok(())
+}
+
+#[test]
+fn test_row_group_selection() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from((0..10).collect::<Vec<i64>>())) as ArrayRef],
+    )?;
+    let input_path = temp_path("row_group_selection", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
+    let run = |args: &[&str]| -> Result<String> {
+        let output = pq().args(args).arg(&input).output()?;
+        assert!(output.status.success(), "{args:?} should succeed");
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert_eq!(
+        run(&["head", "--row-group", "3", "-o", "csv"])?,
+        "id\n6\n7\n"
+    );
+    assert_eq!(
+        run(&["tail", "-n", "3", "--row-group", "0,2:3", "-o", "csv"])?,
+        "id\n5\n6\n7\n"
+    );
+    let stats = run(&["stats", "--row-group", "1:2", "-o", "csv"])?;
+    let bounds: Vec<String> = stats
+        .lines()
+        .map(|line| line.split(',').take(5).collect::<Vec<_>>().join(","))
+        .collect();
+    assert_eq!(bounds, ["column,type,null_count,min,max", "id,INT64,0,2,5"]);
+
+    let output = pq().args(["head", &input, "--row-group", "4:5"]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)?.contains("(file has 5 row groups)"));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_stats_diff_compares_footer_statistics() -> Result<()> {
+    let write = |name: &str, ids: Vec<i64>, with_score: bool| -> Result<String> {
+        let mut fields = vec![Field::new("id", DataType::Int64, false)];
+        let mut columns = vec![Arc::new(Int64Array::from(ids.clone())) as ArrayRef];
+        if with_score {
+            fields.push(Field::new("score", DataType::Float64, true));
+            let scores: Vec<f64> = ids.iter().map(|id| *id as f64 / 2.0).collect();
+            columns.push(Arc::new(Float64Array::from(scores)) as ArrayRef);
+        }
+        let schema = Arc::new(Schema::new(fields));
+        let batch = RecordBatch::try_new(Arc::clone(&schema), columns)?;
+        let path = temp_path(name, "parquet")?;
+        write_parquet(&path, schema, &[batch], None)?;
+        Ok(path.display().to_string())
+    };
+    let old = write("stats_diff_old", (1..=100).collect(), false)?;
+    let new = write("stats_diff_new", (1..=104).collect(), true)?;
+
+    let output = pq().args(["stats-diff", &old, &new]).output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("row_count"), "{stdout}");
+    assert!(stdout.contains("+4.00%"), "{stdout}");
+    assert!(stdout.contains("added"), "{stdout}");
+    assert!(!stdout.contains("null_count"), "{stdout}");
+
+    let output = pq()
+        .args(["stats-diff", &old, &new, "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(diff["row_count"]["before"], serde_json::json!("100"));
+    assert_eq!(diff["columns"][0]["column"], serde_json::json!("id"));
+    assert_eq!(diff["columns"][0]["max"]["after"], serde_json::json!("104"));
+    assert_eq!(
+        diff["columns"][0]["null_count"]["changed"],
+        serde_json::json!(false)
+    );
+    assert_eq!(diff["only_in_new"], serde_json::json!(["score"]));
+
+    let output = pq()
+        .args(["stats-diff", &old, &old, "--tolerance", "0"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "No statistics changed\n"
+    );
+
+    let output = pq()
+        .args(["stats-diff", &old, &new, "--tolerance", "5%"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("beyond the tolerance of 5%"));
+
+    fs::remove_file(old)?;
+    fs::remove_file(new)?;
+    Ok(())
+}
+
+#[test]
+fn test_schema_jsonl_outputs_one_object_per_line() -> Result<()> {
+    let output = pq()
+        .args(["schema", &fixture_path(), "-o", "jsonl"])
+        .output()?;
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<_> = stdout.lines().collect();
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| line.starts_with('{')));
+    assert!(lines.iter().all(|line| !line.starts_with('[')));
+
+    Ok(())
+}
+
+#[test]
+fn test_count_by_groups_nulls_and_limits() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("kind", DataType::Utf8, true)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(StringArray::from(vec![
+            Some("a"),
+            None,
+            Some("b"),
+            Some("a"),
+            None,
+            Some("a"),
+        ])) as ArrayRef],
+    )?;
+    let input_path = temp_path("count_by", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+
+    let output = pq()
+        .args([
+            "count",
+            &input_path.display().to_string(),
+            "--by",
+            "kind",
+            "-o",
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(rows[0]["group"]["kind"], serde_json::json!("a"));
+    assert_eq!(rows[0]["count"], serde_json::json!(3));
+    assert_eq!(rows[0]["percent"], serde_json::json!(50.0));
+    assert_eq!(rows[1]["group"]["kind"], serde_json::Value::Null);
+    assert_eq!(rows[1]["count"], serde_json::json!(2));
+
+    let output = pq()
+        .args([
+            "count",
+            &input_path.display().to_string(),
+            "--by",
+            "kind",
+            "--limit",
+            "2",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("(null)"));
+    assert!(!stdout.contains("| b "));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_count_by_json_keeps_groups_named_like_counts() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("count", DataType::Utf8, false),
+        Field::new("percent", DataType::Int64, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(StringArray::from(vec!["x", "x", "y"])) as ArrayRef,
+            Arc::new(Int64Array::from(vec![7, 7, 8])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("count_by_count", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+
+    let output = pq()
+        .args(["count", &input_path.display().to_string()])
+        .args(["--by", "count,percent", "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(
+        rows[0]["group"],
+        serde_json::json!({"count": "x", "percent": "7"})
+    );
+    assert_eq!(rows[0]["count"], serde_json::json!(2));
+    assert_eq!(rows[1]["group"]["count"], serde_json::json!("y"));
+    assert_eq!(rows[1]["count"], serde_json::json!(1));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_grep_searches_string_and_typed_columns() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 12, 21, 112, 120])) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("alpha"),
+                Some("Beta"),
+                None,
+                Some("gamma"),
+                Some("ALPHABET"),
+                Some("delta"),
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("grep", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
+
+    let output = pq()
+        .args(["grep", "alpha", &input, "-i", "-o", "jsonl"])
+        .output()?;
+    assert!(output.status.success());
+    let ids = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).map(|row| row["id"].clone()))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(ids, vec![serde_json::json!(1), serde_json::json!(112)]);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 matching rows"));
+
+    let output = pq()
+        .args([
+            "grep",
+            "12",
+            &input,
+            "-c",
+            "id",
+            "-o",
+            "csv",
+            "-q",
+            "--no-header",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "12,\n");
+    assert!(output.stderr.is_empty());
+
+    let output = pq()
+        .args(["grep", "^[ab]", &input, "--regex", "-n", "1", "-o", "jsonl"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().count(), 1);
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_nulls_report() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("note", DataType::Utf8, true),
+        Field::new("empty", DataType::Int64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                None,
+                Some("a"),
+                None,
+                None,
+                Some("b"),
+            ])) as ArrayRef,
+            Arc::new(Int64Array::from(vec![None::<i64>; 5])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("nulls", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
+
+    let output = pq().args(["nulls", &input, "-o", "csv"]).output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "column,rows,null_count,null_percent,all_null\n\
+         empty,5,5,100,true\n\
+         note,5,3,60,false\n\
+         id,5,0,0,false\n"
+    );
+
+    let output = pq()
+        .args(["nulls", &input, "--runs", "-c", "note,empty", "-o", "jsonl"])
+        .output()?;
+    assert!(output.status.success());
+    let rows = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["first_non_null"], serde_json::Value::Null);
+    assert_eq!(rows[1]["column"], "note");
+    assert_eq!(rows[1]["longest_null_run"], 2);
+    assert_eq!(rows[1]["first_non_null"], 1);
+    assert_eq!(rows[1]["last_non_null"], 4);
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_checksum_ignores_file_layout() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batch = |names: Vec<Option<&str>>| -> Result<RecordBatch> {
+        Ok(RecordBatch::try_new(
+            Arc::clone(&schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+                Arc::new(StringArray::from(names)) as ArrayRef,
+            ],
+        )?)
+    };
+    let names = vec![Some("a"), None, Some("c"), Some("d")];
+    let one_group = temp_path("checksum_one_group", "parquet")?;
+    let many_groups = temp_path("checksum_many_groups", "parquet")?;
+    let changed = temp_path("checksum_changed", "parquet")?;
+    write_parquet(
+        &one_group,
+        Arc::clone(&schema),
+        &[batch(names.clone())?],
+        None,
+    )?;
+    write_parquet(
+        &many_groups,
+        Arc::clone(&schema),
+        &[batch(names.clone())?],
+        Some(1),
+    )?;
+    let mut changed_names = names;
+    changed_names[3] = Some("e");
+    write_parquet(
+        &changed,
+        Arc::clone(&schema),
+        &[batch(changed_names)?],
+        None,
+    )?;
+
+    let output = pq()
+        .args(["checksum", "--by-column", "-o", "json"])
+        .args([&one_group, &many_groups, &changed])
+        .output()?;
+    assert!(output.status.success());
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let checksum = |index: usize, key: &str| match key {
+        "" => results[index]["checksum"].clone(),
+        column => results[index]["columns"][column].clone(),
+    };
+    assert!(checksum(0, "").is_string());
+    assert_eq!(checksum(0, ""), checksum(1, ""));
+    assert_ne!(checksum(0, ""), checksum(2, ""));
+    assert_eq!(checksum(0, "id"), checksum(2, "id"));
+    assert_ne!(checksum(0, "name"), checksum(2, "name"));
 
-    let _ignored = fs::remove_file(&output_path);
+    let output = pq()
+        .args(["checksum", "--algorithm", "sha256", "-q", "-o", "csv"])
+        .arg(&one_group)
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let hash = stdout.trim().rsplit(',').next().unwrap_or_default();
+    assert_eq!(hash.len(), 64);
+    assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+
+    for path in [one_group, many_groups, changed] {
+        let _ignored = fs::remove_file(path);
+    }
     Ok(())
 }
 
 #[test]
-fn test_convert_json() -> Result<()> {
-    let temp_dir = std::env::temp_dir();
-    let output_path = temp_dir.join("pq_test_output.json");
+fn test_hist_buckets() -> Result<()> {
+    let output = pq()
+        .args([
+            "hist",
+            &fixture_path(),
+            "-c",
+            "amount",
+            "-b",
+            "4",
+            "-o",
+            "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "lower,upper,count\n\
+         100.5,150.375,2\n\
+         150.375,200.25,0\n\
+         200.25,250.125,1\n\
+         250.125,300,2\n"
+    );
 
     let output = pq()
         .args([
-            "convert",
+            "hist",
             &fixture_path(),
-            &output_path.display().to_string(),
+            "-c",
+            "id",
+            "--min",
+            "2",
+            "--max",
+            "4",
         ])
+        .args(["-b", "2", "-o", "json"])
         .output()?;
     assert!(output.status.success());
-    assert!(output_path.exists());
+    let histogram: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(histogram["edges"], serde_json::json!([2.0, 3.0, 4.0]));
+    assert_eq!(histogram["counts"], serde_json::json!([1, 2]));
+    assert_eq!(histogram["below_min"], 1);
+    assert_eq!(histogram["above_max"], 1);
 
-    let contents = fs::read_to_string(&output_path)?;
-    assert!(contents.starts_with('['));
-    assert!(contents.contains("\"name\""));
+    let output = pq()
+        .args(["hist", &fixture_path(), "-c", "amount", "-b", "2"])
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[100.5, 200.25)"));
+    assert!(stdout.contains("[200.25, 300]"));
+    assert!(stdout.contains("Excluded: 0 null"));
 
-    let _ignored = fs::remove_file(&output_path);
+    let output = pq()
+        .args(["hist", &fixture_path(), "-c", "name"])
+        .output()?;
+    assert!(!output.status.success());
     Ok(())
 }
 
 #[test]
-fn test_convert_invalid_input_preserves_existing_output() -> Result<()> {
-    let input_path = temp_path("invalid_convert_input", "parquet")?;
-    let output_path = temp_path("invalid_convert_output", "csv")?;
-    fs::write(&input_path, b"not parquet")?;
-    fs::write(&output_path, b"sentinel")?;
+fn test_extract_prints_one_column_one_value_per_line() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("city", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4, 5])) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("Oslo"),
+                None,
+                Some("Lima"),
+                Some("Oslo"),
+                Some("two\nlines"),
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("extract", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
+    let extract = |extra: &[&str]| -> Result<String> {
+        let output = pq()
+            .args(["extract", &input, "-c", "city"])
+            .args(extra)
+            .output()?;
+        assert!(output.status.success());
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    assert_eq!(extract(&[])?, "Oslo\n\nLima\nOslo\ntwo\\x0alines\n");
+    assert_eq!(
+        extract(&["--no-nulls"])?,
+        "Oslo\nLima\nOslo\ntwo\\x0alines\n"
+    );
+    assert_eq!(
+        extract(&["--unique", "--null-value", "NULL"])?,
+        "Oslo\nNULL\nLima\ntwo\\x0alines\n"
+    );
+
+    // Files are read in turn, and --unique spans them
+    let output = pq()
+        .args(["extract", &input, &input, "-c", "id", "--unique"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?, "1\n2\n3\n4\n5\n");
+
+    let output = pq().args(["extract", &input, "-c", "citty"]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Did you mean: city"));
+
+    fs::remove_file(&input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_sample_stratifies_by_column() -> Result<()> {
+    let events: Vec<&str> = (0..1000)
+        .map(|row| if row % 200 == 7 { "rare" } else { "common" })
+        .collect();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("event", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from_iter_values(0..1000)) as ArrayRef,
+            Arc::new(StringArray::from(events)) as ArrayRef,
+        ],
+    )?;
+    let path = temp_path("sample", "parquet")?;
+    write_parquet(&path, schema, &[batch], Some(300))?;
+    let file = path.display().to_string();
+    let sample = |extra: &[&str]| -> Result<(String, String)> {
+        let output = pq()
+            .args(["sample", &file, "-n", "10", "--seed", "42", "-o", "csv"])
+            .args(extra)
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok((
+            String::from_utf8(output.stdout)?,
+            String::from_utf8(output.stderr)?,
+        ))
+    };
+
+    // Equal shares: all five rare rows make it in, and a seed repeats the sample
+    let (rows, counts) = sample(&["--stratify", "event"])?;
+    assert_eq!(rows.matches(",rare\n").count(), 5);
+    assert_eq!(rows.matches(",common\n").count(), 5);
+    assert_eq!(
+        counts,
+        "event=common: 5 of 995 rows\nevent=rare: 5 of 5 rows\n"
+    );
+    assert_eq!(sample(&["--stratify", "event"])?.0, rows);
+    let ids: Vec<i64> = rows
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split(',').next()?.parse().ok())
+        .collect();
+    assert!(ids.windows(2).all(|pair| pair[0] < pair[1]), "{ids:?}");
+
+    // Proportional shares keep the minimum for rare values
+    let (rows, counts) = sample(&["--stratify", "event", "--min-per-group", "2", "-q"])?;
+    assert_eq!(rows.matches(",rare\n").count(), 2);
+    assert_eq!(rows.matches(",common\n").count(), 10);
+    assert!(counts.is_empty());
+
+    // Too many distinct values falls back to a uniform sample, with a warning
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(Int64Array::from_iter_values(0..10_001)) as ArrayRef],
+    )?;
+    let distinct = temp_path("sample_distinct", "parquet")?;
+    write_parquet(&distinct, schema, &[batch], None)?;
+    let output = pq()
+        .arg("sample")
+        .arg(&distinct)
+        .args(["-n", "3", "--stratify", "id", "-o", "csv", "--no-header"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout)?.lines().count(), 3);
+    let stderr = String::from_utf8(output.stderr)?;
+    assert!(
+        stderr.contains("more than 10000 distinct values"),
+        "{stderr}"
+    );
+    fs::remove_file(distinct)?;
+
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_select_field_paths() -> Result<()> {
+    let user = StructArray::from(vec![
+        (
+            Arc::new(Field::new("id", DataType::Int64, true)),
+            Arc::new(Int64Array::from(vec![10, 20, 30])) as ArrayRef,
+        ),
+        (
+            Arc::new(Field::new("name", DataType::Utf8, true)),
+            Arc::new(StringArray::from(vec!["ann", "bob", "cy"])) as ArrayRef,
+        ),
+    ]);
+    let items = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+        Some(vec![Some(7), Some(8)]),
+        Some(vec![]),
+        None,
+    ]);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("user", user.data_type().clone(), true),
+        Field::new("items", items.data_type().clone(), true),
+        Field::new("amount", DataType::Int64, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(user) as ArrayRef,
+            Arc::new(items) as ArrayRef,
+            Arc::new(Int64Array::from(vec![1, 2, 3])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("select", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
 
     let output = pq()
         .args([
-            "convert",
-            &input_path.display().to_string(),
-            &output_path.display().to_string(),
+            "select",
+            ".user.id, .items[0], .amount",
+            &input,
+            "-o",
+            "jsonl",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let rows = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(serde_json::from_str::<serde_json::Value>)
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0]["user"]["id"], serde_json::json!(10));
+    assert_eq!(rows[0]["items[0]"], serde_json::json!(7));
+    assert_eq!(rows[1]["items[0]"], serde_json::Value::Null);
+    assert_eq!(rows[2]["amount"], serde_json::json!(3));
+    assert!(rows[0].get("name").is_none());
+
+    let output = pq()
+        .args([
+            "select",
+            ".user.name,.amount",
+            &input,
+            "--flatten",
+            "-n",
+            "2",
         ])
+        .args(["-o", "jsonl"])
         .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2);
+    assert!(stdout.contains(r#""user.name":"ann""#));
 
+    let output = pq().args(["select", ".user.nmae", &input]).output()?;
     assert!(!output.status.success());
-    assert_eq!(fs::read(&output_path)?, b"sentinel");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Did you mean: .user.name"));
+
+    let output = pq().args(["select", ".items.sku", &input]).output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains(".items[0]"));
 
     fs::remove_file(input_path)?;
-    fs::remove_file(output_path)?;
     Ok(())
 }
 
 #[test]
-fn test_convert_unsupported_format_preserves_existing_output() -> Result<()> {
-    let output_path = temp_path("unsupported_convert_output", "unsupported")?;
-    fs::write(&output_path, b"sentinel")?;
+fn test_flatten_and_explode_nested_columns() -> Result<()> {
+    let user = StructArray::try_new(
+        vec![Field::new("id", DataType::Int64, true)].into(),
+        vec![Arc::new(Int64Array::from(vec![10, 20, 30])) as ArrayRef],
+        Some(vec![true, false, true].into()),
+    )?;
+    let tags = ListArray::from_iter_primitive::<Int64Type, _, _>(vec![
+        Some(vec![Some(1), None]),
+        None,
+        Some(vec![]),
+    ]);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("user", user.data_type().clone(), true),
+        Field::new("tags", tags.data_type().clone(), true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![Arc::new(user) as ArrayRef, Arc::new(tags) as ArrayRef],
+    )?;
+    let input_path = temp_path("explode", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], None)?;
+    let input = input_path.display().to_string();
 
     let output = pq()
         .args([
-            "convert",
-            &fixture_path(),
-            &output_path.display().to_string(),
+            "head",
+            &input,
+            "--flatten",
+            "--explode",
+            "tags",
+            "-o",
+            "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "user.id,tags\n10,1\n10,\n"
+    );
+
+    let output = pq()
+        .args([
+            "head",
+            &input,
+            "--flatten",
+            "--explode",
+            "tags",
+            "--keep-empty",
         ])
+        .args(["--row-numbers", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "#,user.id,tags\n0,10,1\n0,10,\n1,,\n2,30,\n"
+    );
+
+    let output_path = temp_path("explode", "csv")?;
+    let output = pq()
+        .args(["convert", &input])
+        .arg(&output_path)
+        .args(["--flatten", "-c", "user"])
         .output()?;
+    assert!(output.status.success());
+    assert_eq!(fs::read_to_string(&output_path)?, "user.id\n10\n\"\"\n30\n");
 
+    let output = pq().args(["head", &input, "--explode", "user"]).output()?;
     assert!(!output.status.success());
-    assert_eq!(fs::read(&output_path)?, b"sentinel");
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("Unsupported format"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("It is a struct, not a list"));
 
+    fs::remove_file(input_path)?;
     fs::remove_file(output_path)?;
     Ok(())
 }
 
 #[test]
-fn test_merge() -> Result<()> {
-    let temp_dir = std::env::temp_dir();
-    let output_path = temp_dir.join("pq_test_merged.parquet");
+fn test_prune_check_reports_skipped_row_groups() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from((0..8).collect::<Vec<i64>>())) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                Some("b"),
+                Some("c"),
+                Some("d"),
+                None,
+                None,
+                Some("g"),
+                Some("h"),
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("prune_check", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
 
     let output = pq()
         .args([
-            "merge",
-            &fixture_path(),
-            &fixture_path(),
+            "prune-check",
+            &input,
+            "--where",
+            "id >= 3 AND id < 5",
             "-o",
-            &output_path.display().to_string(),
+            "json",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["row_groups_total"], serde_json::json!(4));
+    assert_eq!(report["row_groups_read"], serde_json::json!(2));
+    assert_eq!(report["rows_skipped"], serde_json::json!(4));
+    assert_eq!(report["pages_total"], serde_json::json!(4));
+    assert_eq!(report["pages_skipped"], serde_json::json!(2));
+    assert_eq!(
+        report["row_groups"][0]["decision"],
+        serde_json::json!("skip")
+    );
+    assert_eq!(
+        report["row_groups"][1]["decision"],
+        serde_json::json!("read")
+    );
+    assert_eq!(
+        report["row_groups"][1]["columns"]["id"]["min"],
+        serde_json::json!(2)
+    );
+
+    let output = pq()
+        .args([
+            "prune-check",
+            &input,
+            "-w",
+            "name IS NULL OR name = 'h'",
+            "-o",
+            "jsonl",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let decisions: Vec<String> = stdout
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).map(|row| row["decision"].to_string())
+        })
+        .collect::<std::result::Result<_, _>>()?;
+    assert_eq!(decisions, ["\"skip\"", "\"skip\"", "\"read\"", "\"read\""]);
+
+    let output = pq()
+        .args(["prune-check", &input, "-w", "missing = 1"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Column not found"));
+
+    let output = pq()
+        .args(["prune-check", &input, "-w", "id = 'abc'"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid predicate"));
+
+    fs::remove_file(input_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_where_filters_head_tail_and_count() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from((0..8).collect::<Vec<i64>>())) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                Some("b"),
+                Some("c"),
+                Some("d"),
+                None,
+                None,
+                Some("g"),
+                Some("h"),
+            ])) as ArrayRef,
+        ],
+    )?;
+    let input_path = temp_path("where_filter", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input = input_path.display().to_string();
+
+    let output = pq()
+        .args(["count", &input, "--where", "id = 5", "--explain-pruning"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "1\n");
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        format!("{input}: skipped 3 of 4 row groups (6 of 8 rows)\n")
+    );
+
+    // A part naming two columns is checked after decoding, even if not selected
+    let output = pq()
+        .args([
+            "head",
+            &input,
+            "--where",
+            "id > 2 AND (id > 6 OR name IS NULL)",
+            "-c",
+            "name",
+            "-o",
+            "csv",
+        ])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "name\n\"\"\n\"\"\nh\n"
+    );
+
+    let output = pq()
+        .args([
+            "tail",
+            &input,
+            "-n",
+            "2",
+            "--where",
+            "name >= 'b'",
+            "-o",
+            "jsonl",
         ])
         .output()?;
     assert!(output.status.success());
-    assert!(output_path.exists());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"id\":6,\"name\":\"g\"}\n{\"id\":7,\"name\":\"h\"}\n"
+    );
+
+    let output = pq()
+        .args(["head", &input, "--where", "id > 100", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "id,name\n");
 
-    let count_output = pq()
-        .args(["count", &output_path.display().to_string()])
+    let output = pq()
+        .args(["head", &input, "--where", "id = 'x'"])
         .output()?;
-    let stdout = String::from_utf8_lossy(&count_output.stdout);
-    assert_eq!(stdout.trim(), "10");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot compare"));
 
-    let _ignored = fs::remove_file(&output_path);
+    // A fractional bound on an integer column filters rather than failing, and pruning
+    // reads every row group it cannot rule out
+    for (predicate, expected) in [
+        ("id > 5.5", "2\n"),
+        ("id <= 2.5", "3\n"),
+        ("id = 2.5", "0\n"),
+    ] {
+        let output = pq()
+            .args(["count", &input, "--where", predicate])
+            .output()?;
+        assert!(output.status.success(), "{predicate}");
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            expected,
+            "{predicate}"
+        );
+    }
+    let output = pq()
+        .args(["prune-check", &input, "--where", "id > 5.5", "-o", "json"])
+        .output()?;
+    assert!(output.status.success());
+
+    fs::remove_file(input_path)?;
     Ok(())
 }
 
 #[test]
-fn test_convert_json_preserves_types() -> Result<()> {
+fn test_count_where_counts_proven_row_groups_from_metadata() -> Result<()> {
     let schema = Arc::new(Schema::new(vec![
-        Field::new("code", DataType::Utf8, true),
-        Field::new("qty", DataType::Int64, true),
-        Field::new("flag", DataType::Boolean, true),
+        Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            false,
+        ),
+        Field::new("tag", DataType::Utf8, true),
     ]));
     let batch = RecordBatch::try_new(
         Arc::clone(&schema),
         vec![
-            Arc::new(StringArray::from(vec![
-                Some("0012"),
-                Some(""),
-                Some("true"),
-                None,
-            ])) as ArrayRef,
-            Arc::new(Int64Array::from(vec![Some(12), None, Some(-7), Some(0)])) as ArrayRef,
-            Arc::new(BooleanArray::from(vec![
-                Some(true),
-                Some(false),
-                None,
-                Some(true),
-            ])) as ArrayRef,
+            Arc::new(TimestampMillisecondArray::from(
+                (0..100)
+                    .map(|second| 1_700_000_000_000 + second * 1000)
+                    .collect::<Vec<i64>>(),
+            )) as ArrayRef,
+            Arc::new(StringArray::from(
+                (0..100)
+                    .map(|row| (row >= 5).then_some("x"))
+                    .collect::<Vec<_>>(),
+            )) as ArrayRef,
         ],
     )?;
+    let input_path = temp_path("count_where_metadata", "parquet")?;
+    write_parquet(&input_path, schema, &[batch], Some(10))?;
+    let input = input_path.display().to_string();
 
-    let input_path = temp_path("typed_input", "parquet")?;
-    let json_path = temp_path("typed_output", "json")?;
-    let jsonl_path = temp_path("typed_output", "jsonl")?;
-    write_parquet(&input_path, schema, &[batch], None)?;
-
-    let json_output = pq()
+    // Sorted timestamps: only the row group straddling the bound is decoded
+    let output = pq()
         .args([
-            "convert",
-            &input_path.display().to_string(),
-            &json_path.display().to_string(),
+            "count",
+            &input,
+            "--where",
+            "ts < '2023-11-14T22:14:05'",
+            "-v",
         ])
         .output()?;
-    assert!(json_output.status.success());
-
-    let rows: serde_json::Value = serde_json::from_str(&fs::read_to_string(&json_path)?)?;
-    let rows = rows
-        .as_array()
-        .ok_or_else(|| anyhow::anyhow!("json output should be an array"))?;
-    assert_eq!(
-        rows[0]["code"],
-        serde_json::Value::String("0012".to_string())
-    );
-    assert_eq!(rows[1]["code"], serde_json::Value::String(String::new()));
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "45\n");
     assert_eq!(
-        rows[2]["code"],
-        serde_json::Value::String("true".to_string())
+        String::from_utf8_lossy(&output.stderr),
+        format!("{input}: 40 rows counted from metadata, 10 rows scanned in 1 of 10 row groups\n")
     );
-    assert_eq!(rows[0]["qty"], serde_json::json!(12));
-    assert_eq!(rows[1]["qty"], serde_json::Value::Null);
-    assert_eq!(rows[0]["flag"], serde_json::json!(true));
-    assert_eq!(rows[2]["flag"], serde_json::Value::Null);
 
-    let jsonl_output = pq()
+    // Nulls in the first row group leave it to be decoded
+    let output = pq()
         .args([
-            "convert",
-            &input_path.display().to_string(),
-            &jsonl_path.display().to_string(),
+            "count",
+            &input,
+            "--where",
+            "ts < '2023-11-14T22:14:05' AND tag IS NOT NULL",
+            "--verbose",
         ])
         .output()?;
-    assert!(jsonl_output.status.success());
-
-    let lines: Vec<serde_json::Value> = fs::read_to_string(&jsonl_path)?
-        .lines()
-        .map(serde_json::from_str)
-        .collect::<std::result::Result<_, _>>()?;
-    assert_eq!(lines[1]["code"], serde_json::Value::String(String::new()));
-    assert_eq!(lines[1]["qty"], serde_json::Value::Null);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "40\n");
     assert_eq!(
-        lines[2]["code"],
-        serde_json::Value::String("true".to_string())
+        String::from_utf8_lossy(&output.stderr),
+        format!("{input}: 30 rows counted from metadata, 20 rows scanned in 2 of 10 row groups\n")
     );
-    assert_eq!(lines[2]["flag"], serde_json::Value::Null);
 
-    let _ignored = fs::remove_file(&input_path);
-    let _ignored = fs::remove_file(&json_path);
-    let _ignored = fs::remove_file(&jsonl_path);
+    fs::remove_file(input_path)?;
     Ok(())
 }
 
 #[test]
-fn test_head_multi_file_json_rejects_incompatible_schemas() -> Result<()> {
-    assert_scan_multi_file_json_rejects_incompatible_schemas("head")
-}
-
-#[test]
-fn test_tail_multi_file_json_rejects_incompatible_schemas() -> Result<()> {
-    assert_scan_multi_file_json_rejects_incompatible_schemas("tail")
-}
-
-fn assert_scan_multi_file_json_rejects_incompatible_schemas(command: &str) -> Result<()> {
-    let left_schema = Arc::new(Schema::new(vec![Field::new(
-        "value",
-        DataType::Int64,
-        false,
-    )]));
-    let right_schema = Arc::new(Schema::new(vec![Field::new(
-        "other",
-        DataType::Utf8,
-        false,
-    )]));
-    let left_batch = RecordBatch::try_new(
-        Arc::clone(&left_schema),
-        vec![Arc::new(Int64Array::from(vec![1])) as ArrayRef],
-    )?;
-    let right_batch = RecordBatch::try_new(
-        Arc::clone(&right_schema),
-        vec![Arc::new(StringArray::from(vec!["x"])) as ArrayRef],
+fn test_exists_uses_statistics_bloom_filters_and_scan() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        Arc::clone(&schema),
+        vec![
+            Arc::new(Int64Array::from((0..8).collect::<Vec<i64>>())) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                "a", "c", "e", "g", "i", "k", "m", "o",
+            ])) as ArrayRef,
+        ],
     )?;
-    let left = temp_path("head_schema_left", "parquet")?;
-    let right = temp_path("head_schema_right", "parquet")?;
-    write_parquet(&left, left_schema, &[left_batch], None)?;
-    write_parquet(&right, right_schema, &[right_batch], None)?;
+    let input_path = temp_path("exists", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(4)
+        .set_bloom_filter_enabled(true)
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&input_path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let input = input_path.display().to_string();
 
     let output = pq()
-        .args([
-            command,
-            &left.display().to_string(),
-            &right.display().to_string(),
-            "-o",
-            "json",
-        ])
+        .args(["info", &input, "--columns", "-o", "json"])
         .output()?;
+    assert!(output.status.success());
+    let columns: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(columns[0]["column"], serde_json::json!("id"));
+    assert_eq!(columns[0]["bloom_filter_row_groups"], serde_json::json!(2));
 
-    assert!(!output.status.success());
-    assert!(output.stdout.is_empty());
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("Schema mismatch"));
+    let exists = |column: &str, value: &str, scan: bool| -> Result<serde_json::Value> {
+        let mut args = vec!["exists", &input, "-c", column, "-v", value, "-o", "json"];
+        if scan {
+            args.push("--scan");
+        }
+        let output = pq().args(args).output()?;
+        assert!(output.status.success());
+        let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+        Ok(rows[0].clone())
+    };
 
-    fs::remove_file(left)?;
-    fs::remove_file(right)?;
+    let row = exists("id", "100", false)?;
+    assert_eq!(row["result"], serde_json::json!("definitely absent"));
+    assert_eq!(row["evidence"], serde_json::json!("statistics"));
+
+    let row = exists("name", "d", false)?;
+    assert_eq!(row["result"], serde_json::json!("definitely absent"));
+    assert_eq!(row["evidence"], serde_json::json!("bloom"));
+
+    let row = exists("id", "5", false)?;
+    assert_eq!(row["result"], serde_json::json!("possibly present"));
+    assert_eq!(row["evidence"], serde_json::json!("bloom"));
+    assert_eq!(row["candidate_row_groups"], serde_json::json!(1));
+
+    let row = exists("name", "k", true)?;
+    assert_eq!(row["result"], serde_json::json!("confirmed present"));
+    assert_eq!(row["evidence"], serde_json::json!("scanned"));
+
+    fs::remove_file(input_path)?;
     Ok(())
 }
 
 #[test]
-fn test_convert_rejects_multi_match_glob() -> Result<()> {
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "value",
-        DataType::Int64,
-        false,
-    )]));
+fn test_info_columns_flags_dictionary_fallback() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, false)]));
+    let long = |prefix: char| prefix.to_string().repeat(40);
     let batch = RecordBatch::try_new(
         Arc::clone(&schema),
-        vec![Arc::new(Int64Array::from(vec![1])) as ArrayRef],
-    )?;
-    let first = temp_path("convert_glob_pair", "parquet")?;
-    let stem = first
-        .file_stem()
-        .and_then(|value| value.to_str())
-        .ok_or_else(|| anyhow::anyhow!("temp path should have utf-8 stem"))?;
-    let second = first.with_file_name(format!("{stem}_second.parquet"));
-    let output_path = temp_path("convert_glob_output", "csv")?;
-    write_parquet(
-        &first,
-        Arc::clone(&schema),
-        std::slice::from_ref(&batch),
-        None,
+        vec![Arc::new(StringArray::from(vec![
+            long('a'),
+            long('a'),
+            long('a'),
+            long('a'),
+            long('b'),
+            long('c'),
+            long('d'),
+            long('e'),
+        ])) as ArrayRef],
     )?;
-    write_parquet(&second, schema, &[batch], None)?;
-    let glob = first.with_file_name(format!("{stem}*.parquet"));
+    let input_path = temp_path("dictionary_fallback", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(4)
+        .set_write_batch_size(1)
+        .set_dictionary_page_size_limit(64)
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&input_path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let input = input_path.display().to_string();
 
     let output = pq()
-        .args([
-            "convert",
-            &glob.display().to_string(),
-            &output_path.display().to_string(),
-        ])
+        .args(["info", &input, "--columns", "-o", "json"])
         .output()?;
+    assert!(output.status.success());
+    let columns: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let column = &columns[0];
+    assert_eq!(column["row_groups"], serde_json::json!(2));
+    assert_eq!(column["dictionary_row_groups"], serde_json::json!(2));
+    assert_eq!(
+        column["dictionary_encoded_row_groups"],
+        serde_json::json!(1)
+    );
+    assert_eq!(column["dictionary_fraction"], serde_json::json!(0.5));
+    assert_eq!(column["mixed_dictionary_encoding"], serde_json::json!(true));
+    assert!(column["encodings"]
+        .as_array()
+        .is_some_and(|encodings| encodings.contains(&serde_json::json!("RLE_DICTIONARY"))));
 
-    assert!(!output.status.success());
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    assert!(stderr.contains("Expected exactly one input file"));
-    assert!(!output_path.exists());
+    let output = pq().args(["info", &input, "--columns"]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)?.contains("1/2 row groups"));
 
-    fs::remove_file(first)?;
-    fs::remove_file(second)?;
+    fs::remove_file(input_path)?;
     Ok(())
 }
 
 #[test]
-fn test_stats_aggregates_across_row_groups() -> Result<()> {
-    let schema = Arc::new(Schema::new(vec![Field::new(
-        "value",
-        DataType::Int64,
-        false,
-    )]));
+fn test_info_pages_reports_page_index() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
     let batch = RecordBatch::try_new(
         Arc::clone(&schema),
-        vec![Arc::new(Int64Array::from(vec![5, 10, 1, 3])) as ArrayRef],
+        vec![
+            Arc::new(Int64Array::from((0..8).collect::<Vec<i64>>())) as ArrayRef,
+            Arc::new(StringArray::from(vec![
+                Some("a"),
+                None,
+                Some("c"),
+                Some("d"),
+                None,
+                None,
+                Some("g"),
+                Some("h"),
+            ])) as ArrayRef,
+        ],
     )?;
-    let input_path = temp_path("stats_groups", "parquet")?;
-    write_parquet(&input_path, schema, &[batch], Some(2))?;
+    let input_path = temp_path("info_pages", "parquet")?;
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(4)
+        .set_write_batch_size(2)
+        .set_data_page_row_count_limit(2)
+        .build();
+    let mut writer = ArrowWriter::try_new(fs::File::create(&input_path)?, schema, Some(props))?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let input = input_path.display().to_string();
 
     let output = pq()
-        .args(["stats", &input_path.display().to_string(), "-o", "json"])
+        .args(["info", &input, "--pages", "--row-group", "1", "-o", "json"])
         .output()?;
     assert!(output.status.success());
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(report["has_page_index"], serde_json::json!(true));
+    let row_groups = report["row_groups"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("row_groups should be an array"))?;
+    assert_eq!(row_groups.len(), 1);
+    let name = &row_groups[0]["columns"][1];
+    assert_eq!(name["column"], serde_json::json!("name"));
+    assert_eq!(name["page_count"], serde_json::json!(2));
+    assert_eq!(name["pages"][0]["rows"], serde_json::json!(2));
+    assert_eq!(name["pages"][0]["null_count"], serde_json::json!(2));
+    assert_eq!(name["pages"][0]["min"], serde_json::Value::Null);
+    assert_eq!(name["pages"][1]["min"], serde_json::json!("g"));
 
-    let rows: serde_json::Value = serde_json::from_slice(&output.stdout)?;
-    assert_eq!(
-        rows[0]["column"],
-        serde_json::Value::String("value".to_string())
-    );
-    assert_eq!(rows[0]["min"], serde_json::json!(1));
-    assert_eq!(rows[0]["max"], serde_json::json!(10));
+    let output = pq()
+        .args(["info", &input, "--pages", "-c", "id", "-o", "csv"])
+        .output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 5);
+    assert!(stdout.lines().skip(1).all(|line| line.contains(",id,")));
 
-    let _ignored = fs::remove_file(&input_path);
+    let output = pq().args(["info", &fixture_path(), "--pages"]).output()?;
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No page index"));
+
+    let output = pq()
+        .args(["info", &input, "--pages", "--row-group", "9"])
+        .output()?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("out of range"));
+
+    fs::remove_file(input_path)?;
     Ok(())
 }
 
 #[test]
-fn test_schema_jsonl_outputs_one_object_per_line() -> Result<()> {
+fn test_dictionary_columns_render_values() -> Result<()> {
+    // Key 2 points at a null dictionary entry; key None is itself null
+    let keys = Int32Array::from(vec![Some(0), None, Some(1), Some(2), Some(0)]);
+    let names = DictionaryArray::<Int32Type>::try_new(
+        keys.clone(),
+        Arc::new(StringArray::from(vec![Some("ada"), Some("bob"), None])),
+    )?;
+    let tags = DictionaryArray::<Int32Type>::try_new(
+        keys,
+        Arc::new(BinaryArray::from(vec![
+            Some(&b"ab"[..]),
+            Some(&b"\xff"[..]),
+            None,
+        ])),
+    )?;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("name", names.data_type().clone(), true),
+        Field::new("tag", tags.data_type().clone(), true),
+    ]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(names), Arc::new(tags)])?;
+    let dictionary = temp_path("dictionary", "parquet")?;
+    write_parquet(&dictionary, schema, &[batch], None)?;
+
+    let plain_schema = Arc::new(Schema::new(vec![
+        Field::new("name", DataType::Utf8, true),
+        Field::new("tag", DataType::Binary, true),
+    ]));
+    let plain_batch = RecordBatch::try_new(
+        plain_schema.clone(),
+        vec![
+            Arc::new(StringArray::from(vec![
+                Some("ada"),
+                None,
+                Some("bob"),
+                None,
+                Some("ada"),
+            ])),
+            Arc::new(BinaryArray::from(vec![
+                Some(&b"ab"[..]),
+                None,
+                Some(&b"\xff"[..]),
+                None,
+                Some(&b"ab"[..]),
+            ])),
+        ],
+    )?;
+    let plain = temp_path("dictionary_plain", "parquet")?;
+    write_parquet(&plain, plain_schema, &[plain_batch], None)?;
+
     let output = pq()
-        .args(["schema", &fixture_path(), "-o", "jsonl"])
+        .arg("head")
+        .arg(&dictionary)
+        .args(["-o", "jsonl"])
         .output()?;
     assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"name\":\"ada\",\"tag\":\"YWI=\"}\n\
+         {\"name\":null,\"tag\":null}\n\
+         {\"name\":\"bob\",\"tag\":\"/w==\"}\n\
+         {\"name\":null,\"tag\":null}\n\
+         {\"name\":\"ada\",\"tag\":\"YWI=\"}\n"
+    );
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let lines: Vec<_> = stdout.lines().collect();
-    assert!(!lines.is_empty());
-    assert!(lines.iter().all(|line| line.starts_with('{')));
-    assert!(lines.iter().all(|line| !line.starts_with('[')));
+    for format in ["csv", "table"] {
+        let from_dictionary = pq()
+            .arg("head")
+            .arg(&dictionary)
+            .args(["-o", format, "--binary", "base64"])
+            .output()?;
+        let from_plain = pq()
+            .arg("head")
+            .arg(&plain)
+            .args(["-o", format, "--binary", "base64"])
+            .output()?;
+        assert!(from_dictionary.status.success());
+        assert_eq!(from_dictionary.stdout, from_plain.stdout, "{format}");
+    }
+
+    let output = pq()
+        .arg("grep")
+        .arg("ada")
+        .arg(&dictionary)
+        .args(["-o", "csv", "-q"])
+        .output()?;
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "name,tag\nada,6162\nada,6162\n"
+    );
+
+    let checksums: Vec<Vec<u8>> = [&dictionary, &plain]
+        .into_iter()
+        .map(|path| {
+            pq().arg("checksum")
+                .arg(path)
+                .args(["-o", "csv", "--no-header", "--by-column"])
+                .output()
+                .map(|output| {
+                    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+                    stdout
+                        .lines()
+                        .filter_map(|line| line.split_once(','))
+                        .map(|(_, rest)| rest.to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                        .into_bytes()
+                })
+        })
+        .collect::<std::io::Result<_>>()?;
+    assert_eq!(checksums[0], checksums[1]);
 
+    fs::remove_file(dictionary)?;
+    fs::remove_file(plain)?;
     Ok(())
 }
@@ -6,7 +6,7 @@ use parquet::arrow::ArrowWriter;
 use std::fs;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
@@ -154,7 +154,7 @@ fn file_info_comes_from_public_api() -> Result<()> {
 #[test]
 fn column_stats_come_from_public_api() -> Result<()> {
     let dataset = pq::dataset_from_inputs(vec![fixture_path()])?;
-    let results = pq::stats(&dataset, Some("id"))?;
+    let results = pq::stats(&dataset, &["id".to_string()], &[])?;
     let rows = &results[0].rows;
 
     assert_eq!(rows.len(), 1);
@@ -174,7 +174,7 @@ fn column_stats_come_from_public_api() -> Result<()> {
 #[test]
 fn missing_stats_column_is_typed_error() -> Result<()> {
     let dataset = pq::dataset_from_inputs(vec![fixture_path()])?;
-    let Err(error) = pq::stats(&dataset, Some("missing_column")) else {
+    let Err(error) = pq::stats(&dataset, &["missing_column".to_string()], &[]) else {
         return Err(anyhow::anyhow!("missing stats column should fail"));
     };
 
@@ -194,6 +194,12 @@ fn binary_stats_display_is_deterministic() {
         null_count: 0,
         min: None,
         max: None,
+        compressed_bytes: 0,
+        uncompressed_bytes: 0,
+        row_groups_without_statistics: 0,
+        min_truncated: false,
+        max_truncated: false,
+        problems: Vec::new(),
     };
     let string_stats = pq::ColumnStats {
         column: "name".to_string(),
@@ -204,6 +210,12 @@ fn binary_stats_display_is_deterministic() {
         null_count: 0,
         min: None,
         max: None,
+        compressed_bytes: 0,
+        uncompressed_bytes: 0,
+        row_groups_without_statistics: 0,
+        min_truncated: false,
+        max_truncated: false,
+        problems: Vec::new(),
     };
 
     assert_eq!(
@@ -231,6 +243,44 @@ fn library_api_exposes_typed_schema_results() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn scan_projects_columns_and_renders_batches() -> Result<()> {
+    let dataset = pq::dataset_from_inputs(vec![fixture_path()])?;
+    let options = pq::ScanOptions {
+        rows: 2,
+        columns: vec!["name".to_string(), "id".to_string()],
+        global: false,
+        filter: None,
+        row_groups: Vec::new(),
+    };
+    let results = pq::scan(&dataset, pq::ScanKind::Tail, &options)?;
+
+    let mut rendered = Vec::new();
+    pq::write_batches(&mut rendered, pq::OutputFormat::Jsonl, &results[0].batches)?;
+    assert_eq!(
+        String::from_utf8(rendered)?,
+        "{\"name\":\"Diana\",\"id\":4}\n{\"name\":\"Eve\",\"id\":5}\n"
+    );
+
+    let mut rendered = Vec::new();
+    pq::write_batches(&mut rendered, pq::OutputFormat::Tsv, &results[0].batches)?;
+    assert_eq!(String::from_utf8(rendered)?, "name\tid\nDiana\t4\nEve\t5\n");
+
+    let options = pq::ScanOptions {
+        rows: 1,
+        columns: vec!["missing".to_string()],
+        global: false,
+        filter: None,
+        row_groups: Vec::new(),
+    };
+    let Err(error) = pq::scan(&dataset, pq::ScanKind::Head, &options) else {
+        return Err(anyhow::anyhow!("unknown projected column should fail"));
+    };
+    assert!(matches!(error, pq::PqError::ColumnNotFound { .. }));
+
+    Ok(())
+}
+
 #[test]
 fn merge_comes_from_public_api() -> Result<()> {
     let schema = Arc::new(Schema::new(vec![Field::new(
@@ -250,7 +300,7 @@ fn merge_comes_from_public_api() -> Result<()> {
     write_parquet(&right, schema, &[batch])?;
 
     let merge_dataset = pq::dataset_from_inputs(vec![left.clone(), right.clone()])?;
-    pq::merge(&merge_dataset, &output)?;
+    pq::merge(&merge_dataset, &output, &pq::ParquetWriteOptions::default())?;
 
     let output_dataset = pq::dataset_from_inputs(vec![output.clone()])?;
     let count = pq::count(&output_dataset)?;
@@ -291,7 +341,7 @@ fn merge_schema_mismatch_does_not_truncate_existing_output() -> Result<()> {
     fs::write(&output, b"sentinel")?;
 
     let dataset = pq::dataset_from_inputs(vec![left.clone(), right.clone()])?;
-    let Err(error) = pq::merge(&dataset, &output) else {
+    let Err(error) = pq::merge(&dataset, &output, &pq::ParquetWriteOptions::default()) else {
         fs::remove_file(left)?;
         fs::remove_file(right)?;
         fs::remove_file(output)?;
@@ -306,3 +356,58 @@ fn merge_schema_mismatch_does_not_truncate_existing_output() -> Result<()> {
     fs::remove_file(output)?;
     Ok(())
 }
+
+#[test]
+fn grouped_counts_come_from_public_api() -> Result<()> {
+    let dataset = pq::dataset_from_inputs(vec![fixture_path(), fixture_path()])?;
+    let result = pq::count_by(
+        &dataset,
+        &["active".to_string()],
+        pq::GroupCountOptions::default(),
+    )?;
+
+    assert_eq!(result.total_rows, 10);
+    assert_eq!(result.total_groups, 2);
+    assert_eq!(result.groups[0].values, vec![Some("true".to_string())]);
+    assert_eq!(result.groups[0].rows, 6);
+
+    Ok(())
+}
+
+#[test]
+fn bench_times_each_run_through_public_api() -> Result<()> {
+    let dataset = pq::dataset_from_inputs(vec![fixture_path(), fixture_path()])?;
+    let options = pq::BenchOptions {
+        operation: pq::BenchOperation::Head { rows: 2 },
+        iterations: 4,
+        warmup: 1,
+    };
+    let result = pq::bench(&dataset, &options)?;
+
+    assert_eq!(result.files, 2);
+    assert_eq!(result.rows, 4);
+    assert_eq!(result.timings.len(), 4);
+    assert!(result.min() <= result.median() && result.median() <= result.p95());
+
+    let millis = |values: &[u64]| values.iter().map(|&ms| Duration::from_millis(ms)).collect();
+    let timed = pq::BenchResult {
+        operation: pq::BenchOperation::Count,
+        files: 1,
+        rows: 1000,
+        warmup: 0,
+        timings: millis(&[40, 10, 30, 20]),
+    };
+    assert_eq!(timed.min(), Duration::from_millis(10));
+    assert_eq!(timed.median(), Duration::from_millis(25));
+    assert_eq!(timed.p95(), Duration::from_millis(40));
+    assert_eq!(timed.rows_per_second(), Some(40_000.0));
+
+    let many = pq::BenchResult {
+        timings: millis(&(1..=40).collect::<Vec<_>>()),
+        ..timed
+    };
+    assert_eq!(many.p95(), Duration::from_millis(38));
+    assert_eq!(many.median(), Duration::from_micros(20_500));
+
+    Ok(())
+}
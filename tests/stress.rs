@@ -4,10 +4,11 @@
 //! Run ignored (heavy) tests: cargo test --test stress -- --ignored --test-threads=1
 #![allow(clippy::expect_used, clippy::panic, clippy::unwrap_used)]
 
+use std::collections::HashSet;
 use std::fs::{self, File};
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 use std::sync::OnceLock;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -413,6 +414,70 @@ fn empty_parquet_file() {
     );
 }
 
+#[test]
+fn merge_skips_empty_inputs_and_warns_on_empty_output() {
+    let empty = generate_fixture(
+        "empty.parquet",
+        &["--rows", "0", "--cols", "5", "--profile", "empty"],
+    );
+    // The empty profile's columns, but with rows and a required first column
+    let rows = generate_fixture(
+        "empty_compatible.parquet",
+        &[
+            "--rows",
+            "100",
+            "--schema",
+            "col_0:int64,col_1:int64?,col_2:int64?,col_3:int64?,col_4:int64?",
+        ],
+    );
+    let (empty, rows) = (empty.to_str().unwrap(), rows.to_str().unwrap());
+
+    let merged = fixtures_dir().join("merged_with_empty.parquet");
+    let merged = merged.to_str().unwrap();
+    let output = run_pq(&["merge", empty, rows, empty, "-o", merged, "--force"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("{empty}: 0 rows (empty)")),
+        "{stderr}"
+    );
+    assert!(stderr.contains(&format!("{rows}: 100 rows")), "{stderr}");
+    assert!(!stderr.contains("warning"), "{stderr}");
+    assert_eq!(run_pq_success(&["count", merged]).trim(), "100");
+    // The schema comes from the input with rows
+    let schema = run_pq_success(&["schema", merged, "-o", "csv"]);
+    assert!(schema.contains("col_0,INT64,false"), "{schema}");
+
+    let output = run_pq(&["merge", empty, empty, "-o", merged, "--force"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).ends_with(&format!(
+        "warning: {merged} has no rows; every input was empty\n"
+    )));
+    let output = run_pq(&["merge", empty, empty, "-o", merged, "--force", "--quiet"]);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+    let stderr = run_pq_failure(&["merge", empty, empty, "-o", merged, "--force", "--strict"]);
+    assert!(
+        stderr.contains("--strict treats warnings as errors"),
+        "{stderr}"
+    );
+
+    // Empty inputs still need the same columns
+    let narrow = generate_fixture(
+        "empty_narrow.parquet",
+        &["--rows", "0", "--cols", "3", "--profile", "empty"],
+    );
+    let stderr = run_pq_failure(&[
+        "merge",
+        rows,
+        narrow.to_str().unwrap(),
+        "-o",
+        merged,
+        "--force",
+    ]);
+    assert!(stderr.contains("Schema mismatch"), "{stderr}");
+}
+
 // ============================================================================
 // Unicode and Special Character Tests
 // ============================================================================
@@ -489,6 +554,33 @@ fn long_strings_1kb() {
     );
 }
 
+/// Converts a 33 MB file of 1 KB strings under a 90 MB address-space limit: small
+/// batches fit, while decoding the whole file as one batch does not.
+#[cfg(unix)]
+#[test]
+fn long_strings_convert_within_memory_ceiling() {
+    let path = generate_fixture(
+        "long_strings_wide.parquet",
+        &["--rows", "8000", "--cols", "3", "--profile", "long-strings"],
+    );
+    let limited = |batch_size: &str| {
+        Command::new("sh")
+            .arg("-c")
+            .arg("ulimit -v 90000 && exec \"$@\"")
+            .arg("sh")
+            .arg(pq_bin())
+            .args(["convert", path.to_str().unwrap(), "-", "-f", "jsonl"])
+            .args(["--batch-size", batch_size])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .expect("Failed to execute pq under ulimit")
+    };
+
+    assert!(limited("256").success());
+    assert!(!limited("8000").success());
+}
+
 // ============================================================================
 // Edge Case Values Tests
 // ============================================================================
@@ -508,6 +600,328 @@ fn edge_case_values() {
     assert!(!output.is_empty());
 }
 
+// ============================================================================
+// Binary Data Tests
+// ============================================================================
+
+#[test]
+fn binary_columns_render_as_text() {
+    let path = generate_fixture(
+        "binary.parquet",
+        &[
+            "--rows",
+            "100",
+            "--cols",
+            "3",
+            "--profile",
+            "binary",
+            "--null-ratio",
+            "0",
+        ],
+    );
+
+    let output = run_pq_success(&["head", "-n", "5", path.to_str().unwrap(), "-o", "json"]);
+    let rows: serde_json::Value = serde_json::from_str(&output).expect("JSON output should parse");
+    let uuid = rows[0]["uuid_2"].as_str().unwrap_or_default();
+    assert!(
+        uuid.len() == 36 && uuid.matches('-').count() == 4,
+        "UUID-tagged column should render canonically, got {uuid}"
+    );
+
+    for encoding in &["hex", "base64", "utf8-lossy"] {
+        let output = run_pq_success(&[
+            "head",
+            path.to_str().unwrap(),
+            "-o",
+            "csv",
+            "--binary",
+            encoding,
+        ]);
+        assert!(output.lines().count() > 1);
+    }
+
+    let output = run_pq_success(&["stats", path.to_str().unwrap(), "--binary", "base64"]);
+    assert!(!output.is_empty());
+}
+
+// ============================================================================
+// Temporal, Decimal, and Nested Type Tests
+// ============================================================================
+
+#[test]
+fn temporal_columns() {
+    let path = generate_fixture(
+        "temporal.parquet",
+        &["--rows", "1000", "--cols", "6", "--profile", "temporal"],
+    );
+    let path = path.to_str().unwrap();
+
+    let output = run_pq_success(&["head", "-n", "20", path, "-o", "json"]);
+    let rows: serde_json::Value = serde_json::from_str(&output).expect("JSON output should parse");
+    assert_eq!(rows.as_array().map(Vec::len), Some(20));
+
+    run_pq_success(&["head", path, "--timezone", "Asia/Tokyo"]);
+    run_pq_success(&["stats", path]);
+
+    let output = run_pq_success(&["convert", path, "-", "--format", "csv"]);
+    assert_eq!(output.lines().count(), 1001);
+}
+
+#[test]
+fn decimal_columns() {
+    let path = generate_fixture(
+        "decimal.parquet",
+        &["--rows", "1000", "--cols", "4", "--profile", "decimal"],
+    );
+    let path = path.to_str().unwrap();
+
+    let output = run_pq_success(&["head", "-n", "20", path, "-o", "jsonl"]);
+    assert_eq!(output.lines().count(), 20);
+
+    let output = run_pq_success(&["stats", path]);
+    assert!(output.contains("DECIMAL(38,10)"));
+
+    let output = run_pq_success(&["convert", path, "-", "--format", "csv"]);
+    assert_eq!(output.lines().count(), 1001);
+}
+
+#[test]
+fn nested_columns() {
+    let path = generate_fixture(
+        "nested.parquet",
+        &["--rows", "1000", "--cols", "3", "--profile", "nested"],
+    );
+    let path = path.to_str().unwrap();
+
+    run_pq_success(&["head", path]);
+    run_pq_success(&["stats", path]);
+
+    let output = run_pq_success(&["convert", path, "-", "--format", "jsonl"]);
+    assert_eq!(output.lines().count(), 1000);
+
+    // CSV has no representation for nested values
+    run_pq_failure(&["convert", path, "-", "--format", "csv"]);
+}
+
+// ============================================================================
+// Generator Layout Tests
+// ============================================================================
+
+#[test]
+fn generator_row_group_layout() {
+    let path = generate_fixture(
+        "layout.parquet",
+        &[
+            "--rows",
+            "1000",
+            "--cols",
+            "4",
+            "--row-group-size",
+            "250",
+            "--no-dictionary",
+            "--write-page-index",
+            "--bloom-filter",
+            "int_0,str_2",
+            "--sorted-by",
+            "int_0",
+        ],
+    );
+    let path = path.to_str().unwrap();
+
+    let output = run_pq_success(&["info", "--row-groups", path, "-o", "json"]);
+    let groups: serde_json::Value =
+        serde_json::from_str(&output).expect("JSON output should parse");
+    let groups = groups.as_array().expect("row groups should be an array");
+    assert_eq!(groups.len(), 4);
+    for group in groups {
+        assert_eq!(group["num_rows"], 250);
+        assert_eq!(group["statistics_columns"], 4);
+        assert_eq!(group["dictionary_columns"], 0);
+        assert_eq!(group["page_index_columns"], 4);
+        assert_eq!(group["bloom_filter_columns"], 2);
+    }
+
+    // Sorted values give each row group disjoint bounds, so half the groups prune away
+    let output = run_pq_success(&["prune-check", path, "-w", "int_0 >= 500", "-o", "json"]);
+    let report: serde_json::Value =
+        serde_json::from_str(&output).expect("JSON output should parse");
+    assert_eq!(report["row_groups_skipped"], 2);
+
+    let path = generate_fixture(
+        "no_statistics.parquet",
+        &["--rows", "100", "--cols", "4", "--no-statistics"],
+    );
+    let output = run_pq_success(&["info", "--row-groups", path.to_str().unwrap(), "-o", "csv"]);
+    assert!(
+        output
+            .lines()
+            .nth(1)
+            .is_some_and(|line| line.ends_with(",4,0,3,0,0,-")),
+        "{output}"
+    );
+}
+
+#[test]
+fn generator_skewed_keys() {
+    let path = generate_fixture(
+        "skewed.parquet",
+        &["--rows", "10000", "--cols", "4", "--profile", "skewed"],
+    );
+    let path = path.to_str().unwrap();
+
+    let output = run_pq_success(&["count", path, "--by", "key", "-o", "json"]);
+    let groups: serde_json::Value =
+        serde_json::from_str(&output).expect("JSON output should parse");
+    let groups = groups.as_array().expect("groups should be an array");
+    assert_eq!(groups[0]["group"]["key"], "hot");
+    let hot_percent = groups[0]["percent"].as_f64().expect("percent is a number");
+    assert!((85.0..95.0).contains(&hot_percent), "{hot_percent}");
+    assert!(groups.len() > 50, "long tail should have many keys");
+}
+
+#[test]
+fn generator_duplicate_rows() {
+    let path = generate_fixture(
+        "duplicates.parquet",
+        &[
+            "--rows",
+            "10000",
+            "--cols",
+            "4",
+            "--profile",
+            "duplicates",
+            "--dup-ratio",
+            "0.5",
+        ],
+    );
+    let output = run_pq_success(&["convert", path.to_str().unwrap(), "-", "--format", "jsonl"]);
+    let rows: Vec<&str> = output.lines().collect();
+    let distinct: HashSet<&str> = rows.iter().copied().collect();
+    assert_eq!(rows.len(), 10000);
+    let duplicated = rows.len() - distinct.len();
+    assert!((4500..5500).contains(&duplicated), "{duplicated}");
+
+    // The seed fixes which rows repeat
+    let again = fixtures_dir().join("duplicates_again.parquet");
+    let regenerated = Command::new(generate_bin())
+        .args([
+            "-o",
+            again.to_str().unwrap(),
+            "--rows",
+            "10000",
+            "--cols",
+            "4",
+            "--profile",
+            "duplicates",
+            "--dup-ratio",
+            "0.5",
+        ])
+        .output()
+        .expect("Failed to execute pq-generate");
+    assert!(regenerated.status.success());
+    let again_output =
+        run_pq_success(&["convert", again.to_str().unwrap(), "-", "--format", "jsonl"]);
+    let _ = fs::remove_file(&again);
+    assert_eq!(again_output, output);
+}
+
+#[test]
+fn generator_schema_spec() {
+    let path = generate_fixture(
+        "schema_spec.parquet",
+        &[
+            "--rows",
+            "1000",
+            "--schema",
+            "id:int64,name:utf8,score:float64?:0.3,ts:timestamp_ms,amount:decimal(9,2)?",
+        ],
+    );
+    let path = path.to_str().unwrap();
+
+    let output = run_pq_success(&["schema", path, "-o", "json"]);
+    let columns: serde_json::Value =
+        serde_json::from_str(&output).expect("JSON output should parse");
+    let columns = columns.as_array().expect("schema should be an array");
+    let names: Vec<&str> = columns
+        .iter()
+        .filter_map(|column| column["name"].as_str())
+        .collect();
+    assert_eq!(names, ["id", "name", "score", "ts", "amount"]);
+    let nullable: Vec<bool> = columns
+        .iter()
+        .filter_map(|column| column["nullable"].as_bool())
+        .collect();
+    assert_eq!(nullable, [false, false, true, false, true]);
+
+    let output = run_pq_success(&["stats", path, "-o", "json"]);
+    let stats: serde_json::Value = serde_json::from_str(&output).expect("JSON output should parse");
+    let nulls: Vec<u64> = stats
+        .as_array()
+        .expect("stats should be an array")
+        .iter()
+        .filter_map(|column| column["null_count"].as_u64())
+        .collect();
+    assert_eq!(nulls[0], 0);
+    assert!((200..400).contains(&nulls[2]), "{nulls:?}");
+
+    for (spec, message) in [
+        ("id:int64,score:float65?", "column 2 'score:float65?'"),
+        ("id:int64:0.2", "needs a nullable type"),
+        ("id:int64,id:utf8", "Duplicate --schema column 'id'"),
+    ] {
+        let output = Command::new(generate_bin())
+            .args(["-o", "/dev/null", "--schema", spec])
+            .output()
+            .expect("Failed to execute pq-generate");
+        assert!(!output.status.success());
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(message), "{stderr}");
+    }
+}
+
+#[test]
+fn large_and_view_layouts_match_small() {
+    let layouts = [
+        ("layout_small.parquet", "s:utf8?,b:binary?,l:list?"),
+        (
+            "layout_large.parquet",
+            "s:large_utf8?,b:large_binary?,l:large_list?",
+        ),
+        ("layout_view.parquet", "s:utf8_view?,b:binary_view?,l:list?"),
+    ];
+    let outputs: Vec<Vec<String>> = layouts
+        .iter()
+        .map(|(name, schema)| {
+            let path = generate_fixture(name, &["--rows", "20", "--schema", schema]);
+            let path = path.to_str().unwrap();
+            let without_file = |output: String| {
+                output
+                    .lines()
+                    .map(|line| line.split_once(',').map_or(line, |(_, rest)| rest))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            vec![
+                run_pq_success(&["head", path, "-n", "20", "-o", "jsonl"]),
+                run_pq_success(&["head", path, "-c", "s,b", "-o", "csv"]),
+                run_pq_success(&["stats", path, "-o", "csv"]),
+                run_pq_success(&["convert", path, "-", "-f", "jsonl"]),
+                without_file(run_pq_success(&["checksum", path, "-o", "csv"])),
+                without_file(run_pq_success(&[
+                    "checksum",
+                    path,
+                    "--by-column",
+                    "-o",
+                    "csv",
+                ])),
+            ]
+        })
+        .collect();
+
+    assert_eq!(outputs[0], outputs[1]);
+    assert_eq!(outputs[0], outputs[2]);
+}
+
 // ============================================================================
 // All Nulls Tests
 // ============================================================================
@@ -565,6 +979,37 @@ fn wide_schema_1000_columns() {
     assert!(!output.is_empty());
 }
 
+#[test]
+#[ignore] // Run with --ignored
+fn wide_schema_1000_columns_count() {
+    // 100 row groups of 1000 columns: a footer dominated by column chunk metadata
+    let path = generate_fixture(
+        "wide_1000_row_groups.parquet",
+        &[
+            "--rows",
+            "5000",
+            "--cols",
+            "1000",
+            "--profile",
+            "integers",
+            "--row-group-size",
+            "50",
+        ],
+    );
+
+    let _ = run_pq_success(&["count", path.to_str().unwrap()]);
+    let start = Instant::now();
+    let output = run_pq_success(&["count", path.to_str().unwrap()]);
+    let elapsed = start.elapsed();
+
+    assert_eq!(output.trim(), "5000");
+    assert!(
+        elapsed < Duration::from_millis(200),
+        "Count of a 1000-column file took too long: {:?}",
+        elapsed
+    );
+}
+
 // ============================================================================
 // Medium Load Tests (run with --ignored)
 // ============================================================================
@@ -591,6 +1036,17 @@ fn medium_load_1m_rows() {
         elapsed
     );
 
+    // Describe reads only the footer, like count
+    let start = Instant::now();
+    let output = run_pq_success(&["describe", path.to_str().unwrap(), "-o", "json"]);
+    let elapsed = start.elapsed();
+    assert!(output.contains("\"num_rows\": 1000000"));
+    assert!(
+        elapsed < Duration::from_millis(200),
+        "Describe took too long: {:?}",
+        elapsed
+    );
+
     // Head should be fast (streaming)
     let start = Instant::now();
     let output = run_pq_success(&["head", "-n", "100", path.to_str().unwrap()]);
@@ -627,6 +1083,52 @@ fn medium_load_tail_1m_rows() {
     assert!(lines.len() >= 10, "Should have at least 10 lines");
 }
 
+/// Structured tail output is written batch by batch, so half the file fits in a
+/// ceiling that collecting it first would not.
+#[cfg(unix)]
+#[test]
+#[ignore]
+fn medium_load_tail_500k_rows_within_memory_ceiling() {
+    let path = generate_fixture(
+        "medium_1m.parquet",
+        &["--rows", "1000000", "--cols", "10", "--profile", "mixed"],
+    );
+
+    for format in ["jsonl", "json"] {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg("ulimit -v 100000 && exec \"$@\"")
+            .arg("sh")
+            .arg(pq_bin())
+            .args(["tail", "-n", "500000", path.to_str().unwrap(), "-o", format])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("Failed to execute pq under ulimit");
+
+        let mut reader = BufReader::new(child.stdout.take().unwrap());
+        let (mut lines, mut first, mut last) = (0usize, None, None);
+        loop {
+            let buffer = reader.fill_buf().expect("Failed to read pq output");
+            let Some(&end) = buffer.last() else {
+                break;
+            };
+            first = first.or(buffer.first().copied());
+            last = Some(end);
+            lines += buffer.iter().filter(|&&byte| byte == b'\n').count();
+            let consumed = buffer.len();
+            reader.consume(consumed);
+        }
+
+        assert!(child.wait().unwrap().success(), "{format}: tail failed");
+        if format == "jsonl" {
+            assert_eq!(lines, 500000);
+        } else {
+            assert_eq!((first, last), (Some(b'['), Some(b']')));
+        }
+    }
+}
+
 // ============================================================================
 // Large Load Tests (run with --ignored)
 // ============================================================================
@@ -836,6 +1338,42 @@ fn output_csv_large() {
     assert_eq!(lines.len(), 100001, "Should have header + 100k rows");
 }
 
+#[test]
+fn output_reader_closes_pipe_early() {
+    let path = generate_fixture(
+        "pipe_100k.parquet",
+        &["--rows", "100000", "--cols", "5", "--profile", "mixed"],
+    );
+
+    // Far more output than a pipe buffers, so pq is still writing when the reader leaves
+    for format in ["table", "json", "jsonl", "csv"] {
+        let mut child = Command::new(pq_bin())
+            .args(["head", "-n", "100000", "-o", format, path.to_str().unwrap()])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to execute pq");
+
+        let mut first_line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut first_line)
+            .expect("Failed to read pq output");
+        assert!(!first_line.is_empty(), "{format}: expected some output");
+
+        let output = child.wait_with_output().expect("Failed to wait for pq");
+        assert!(
+            output.status.success(),
+            "{format}: expected exit 0, got {:?}",
+            output.status
+        );
+        assert!(
+            output.stderr.is_empty(),
+            "{format}: expected empty stderr, got: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
 // ============================================================================
 // Merge Stress Tests
 // ============================================================================
@@ -873,6 +1411,7 @@ fn merge_many_files() {
     }
     args.push("-o");
     args.push(output_path.to_str().unwrap());
+    args.push("--force");
 
     let _ = run_pq_success(&args);
 